@@ -1,6 +1,14 @@
 use probability::prelude::*;
 use test::{Bencher, black_box};
 
+#[bench]
+fn density(bencher: &mut Bencher) {
+    let d = Beta::new(0.5, 1.5, 0.0, 1.0);
+    let x = Independent(&d, &mut source::default()).take(1000).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(x.iter().map(|&x| d.density(x)).collect::<Vec<_>>()));
+}
+
 #[bench]
 fn distribution(bencher: &mut Bencher) {
     let d = Beta::new(0.5, 1.5, 0.0, 1.0);
@@ -18,6 +26,14 @@ fn inverse(bencher: &mut Bencher) {
     bencher.iter(|| black_box(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>()));
 }
 
+#[bench]
+fn ln_density(bencher: &mut Bencher) {
+    let d = Beta::new(0.5, 1.5, 0.0, 1.0);
+    let x = Independent(&d, &mut source::default()).take(1000).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>()));
+}
+
 #[bench]
 fn sample(bencher: &mut Bencher) {
     let mut source = source::Xorshift128Plus::new([42, 69]);
@@ -0,0 +1,19 @@
+use probability::prelude::*;
+use probability::source::Source;
+use test::{Bencher, black_box};
+
+#[bench]
+fn sample(bencher: &mut Bencher) {
+    let mut source = source::Xorshift128Plus::new([42, 69]);
+    let d = Exponential::new(2.0);
+
+    bencher.iter(|| black_box(d.sample(&mut source)));
+}
+
+#[bench]
+fn sample_inverse(bencher: &mut Bencher) {
+    let mut source = source::Xorshift128Plus::new([42, 69]);
+    let lambda = black_box(2.0);
+
+    bencher.iter(|| black_box(-source.read::<f64>().ln() / lambda));
+}
@@ -1,6 +1,14 @@
 use probability::prelude::*;
 use test::{Bencher, black_box};
 
+#[bench]
+fn density(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+    let x = Independent(&d, &mut source::default()).take(1000).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(x.iter().map(|&x| d.density(x)).collect::<Vec<_>>()));
+}
+
 #[bench]
 fn distribution(bencher: &mut Bencher) {
     let d = Gaussian::new(0.0, 1.0);
@@ -18,6 +26,34 @@ fn inverse(bencher: &mut Bencher) {
     bencher.iter(|| black_box(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>()));
 }
 
+#[bench]
+fn inverse_tail(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+    let p = Independent(&Uniform::new(0.0, 0.05), &mut source::default()).take(1000)
+                                                                         .collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>()));
+}
+
+#[bench]
+fn inverse_ln(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+    let ln_p = Independent(&Exponential::new(0.01), &mut source::default()).take(1000)
+                                                                           .map(|x| -x)
+                                                                           .collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(ln_p.iter().map(|&ln_p| d.inverse_ln(ln_p)).collect::<Vec<_>>()));
+}
+
+#[bench]
+fn inverse_single(bencher: &mut Bencher) {
+    let d = Gaussian::from_deviation(0.0f32, 1.0);
+    let p = Independent(&Uniform::new(0.0, 1.0), &mut source::default()).take(1000)
+                                                                        .collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>()));
+}
+
 #[bench]
 fn sample(bencher: &mut Bencher) {
     let mut source = source::Xorshift128Plus::new([42, 69]);
@@ -25,3 +61,77 @@ fn sample(bencher: &mut Bencher) {
 
     bencher.iter(|| black_box(d.sample(&mut source)));
 }
+
+#[bench]
+fn ln_density(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+    let x = Independent(&d, &mut source::default()).take(1000).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>()));
+}
+
+#[bench]
+fn log_likelihood(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+    let x = Independent(&d, &mut source::default()).take(1000).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(d.log_likelihood(&x)));
+}
+
+#[bench]
+fn log_likelihood_naive(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+    let x = Independent(&d, &mut source::default()).take(1000).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(x.iter().fold((0.0, 0.0), |(sum, compensation), &x| {
+        let (value, total) = (d.ln_density(x), sum + d.ln_density(x));
+        if sum.abs() >= value.abs() {
+            (total, compensation + (sum - total) + value)
+        } else {
+            (total, compensation + (value - total) + sum)
+        }
+    })));
+}
+
+#[bench]
+fn sample_standard(bencher: &mut Bencher) {
+    let mut source = source::Xorshift128Plus::new([42, 69]);
+    let d = StandardGaussian;
+
+    bencher.iter(|| black_box(d.sample(&mut source)));
+}
+
+#[bench]
+fn sample_collect(bencher: &mut Bencher) {
+    let mut source = source::Xorshift128Plus::new([42, 69]);
+    let d = Gaussian::new(0.0, 1.0);
+
+    bencher.iter(|| black_box((0..1000000).map(|_| d.sample(&mut source)).collect::<Vec<_>>()));
+}
+
+#[bench]
+fn sample_into(bencher: &mut Bencher) {
+    let mut source = source::Xorshift128Plus::new([42, 69]);
+    let d = Gaussian::new(0.0, 1.0);
+    let mut x = vec![0.0; 1000000];
+
+    bencher.iter(|| {
+        d.sample_into(&mut source, &mut x);
+        black_box(&x);
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[bench]
+fn par_samples(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+
+    bencher.iter(|| black_box(d.par_samples(42, 1000000)));
+}
+
+#[bench]
+fn samples(bencher: &mut Bencher) {
+    let d = Gaussian::new(0.0, 1.0);
+
+    bencher.iter(|| black_box(d.samples(&mut source::seeded(42), 1000000)));
+}
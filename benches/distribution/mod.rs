@@ -1,4 +1,7 @@
 mod beta;
 mod binomial;
 mod categorical;
+mod exponential;
+mod gamma;
 mod gaussian;
+mod poisson;
@@ -0,0 +1,19 @@
+use probability::prelude::*;
+use test::{Bencher, black_box};
+
+#[bench]
+fn inverse(bencher: &mut Bencher) {
+    let d = Poisson::new(1e6);
+    let p = Independent(&Uniform::new(0.0, 1.0), &mut source::default()).take(1000)
+                                                                        .collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>()));
+}
+
+#[bench]
+fn inverse_cached(bencher: &mut Bencher) {
+    let d = Cached::new(Poisson::new(1e6));
+    let p = (1..1000).map(|i| i as f64 / 1000.0).collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>()));
+}
@@ -4,3 +4,4 @@ extern crate probability;
 extern crate test;
 
 mod distribution;
+mod statistics;
@@ -0,0 +1,13 @@
+use probability::prelude::*;
+use probability::statistics;
+use test::{Bencher, black_box};
+
+#[bench]
+fn kendall_tau(bencher: &mut Bencher) {
+    let mut source = source::Xorshift128Plus::new([42, 69]);
+    let x = Gaussian::new(0.0, 1.0).samples(&mut source, 100000);
+    let y = x.iter().map(|&x| x + Uniform::new(-1.0, 1.0).sample(&mut source))
+                    .collect::<Vec<_>>();
+
+    bencher.iter(|| black_box(statistics::kendall_tau(&x, &y)));
+}
@@ -0,0 +1,148 @@
+//! The Cornish–Fisher expansion.
+//!
+//! The quantile of order `p` of a distribution with mean `μ`, standard
+//! deviation `σ`, skewness `γ`, and excess kurtosis `κ` is approximated by
+//! `μ + σ w(z)` where `z` is the standard Gaussian quantile of order `p` and
+//!
+//! `w(z) = z + γ (z² - 1) / 6 + κ (z³ - 3z) / 24 - γ² (2z³ - 5z) / 36`
+//!
+//! is the expansion up to the fourth cumulant. The approximation is accurate
+//! for distributions close to the Gaussian one: for the gamma distribution
+//! with shape 20, the relative error in the central 98% is below `1e-3`. It
+//! degrades as the skewness and the kurtosis grow: for the exponential
+//! distribution, the error is about 4% at the median and 2% at the quantile
+//! of order 0.99, and the lower quantiles are not available at all, since
+//! `w` is not increasing there. An expansion that is not increasing between the median and the
+//! requested quantile is rejected, because the quantiles it produces are not
+//! ordered.
+//!
+//! ```
+//! use probability::approx;
+//! use probability::prelude::*;
+//!
+//! let d = Gamma::new(20.0, 1.0);
+//! let x = approx::cornish_fisher_quantile(d.mean(), d.variance().sqrt(), d.skewness(),
+//!                                         d.kurtosis(), 0.95).unwrap();
+//! assert!((x - d.inverse(0.95)).abs() < 1e-2);
+//! ```
+
+use distribution::gaussian;
+use ParameterError;
+
+/// Approximate the quantile of order `p` of a distribution with mean `mean`,
+/// standard deviation `deviation`, skewness `skewness`, and excess kurtosis
+/// `kurtosis`.
+///
+/// An error is returned if the mean is not finite, if the standard deviation
+/// is not positive and finite, if `p` is not in `(0, 1)`, or if the expansion
+/// is not increasing between the median and the quantile.
+pub fn cornish_fisher_quantile(mean: f64, deviation: f64, skewness: f64, kurtosis: f64, p: f64)
+                               -> Result<f64, ParameterError>
+{
+    ensure!(mean.is_finite(), ParameterError::MuNotFinite { value: mean });
+    ensure!(deviation > 0.0 && deviation.is_finite(),
+            ParameterError::SigmaNotPositive { value: deviation });
+    ensure!(0.0 < p && p < 1.0, ParameterError::ProbabilityOutOfRange { value: p });
+    let z = gaussian::inverse(p);
+    ensure!(increasing(skewness, kurtosis, z),
+            ParameterError::ExpansionNotMonotone { skewness: skewness, kurtosis: kurtosis });
+    let (z2, z3) = (z * z, z * z * z);
+    let w = z + skewness * (z2 - 1.0) / 6.0 + kurtosis * (z3 - 3.0 * z) / 24.0 -
+            skewness * skewness * (2.0 * z3 - 5.0 * z) / 36.0;
+    Ok(mean + deviation * w)
+}
+
+// Check if the derivative of the expansion, which is the quadratic
+// `a z² + b z + c`, is positive between zero and `z`.
+fn increasing(skewness: f64, kurtosis: f64, z: f64) -> bool {
+    let square = skewness * skewness;
+    let a = kurtosis / 8.0 - square / 6.0;
+    let b = skewness / 3.0;
+    let c = 1.0 - kurtosis / 8.0 + 5.0 * square / 36.0;
+    let derivative = |z: f64| (a * z + b) * z + c;
+    if !(derivative(0.0) > 0.0) || !(derivative(z) > 0.0) {
+        return false;
+    }
+    // A convex quadratic can dip below zero between the endpoints only at its
+    // vertex.
+    let vertex = -b / (2.0 * a);
+    if a > 0.0 && z.min(0.0) < vertex && vertex < z.max(0.0) {
+        return derivative(vertex) > 0.0;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::cornish_fisher_quantile;
+
+    fn quantile<D>(d: &D, p: f64) -> Result<f64, ParameterError> where D: Kurtosis {
+        cornish_fisher_quantile(d.mean(), d.variance().sqrt(), d.skewness(), d.kurtosis(), p)
+    }
+
+    #[test]
+    fn exponential() {
+        let d = Gamma::new(1.0, 1.0);
+        for &(p, delta) in &[(0.5, 5e-2), (0.9, 2e-3), (0.95, 1e-2), (0.99, 3e-2)] {
+            let x = quantile(&d, p).unwrap();
+            assert!((x / d.inverse(p) - 1.0).abs() < delta);
+        }
+        assert_eq!(quantile(&d, 0.05),
+                   Err(ParameterError::ExpansionNotMonotone { skewness: 2.0, kurtosis: 6.0 }));
+    }
+
+    #[test]
+    fn gaussian() {
+        let d = Gaussian::new(1.0, 2.0);
+        for &p in &[1e-10, 0.01, 0.3, 0.5, 0.9, 1.0 - 1e-10] {
+            assert::close(quantile(&d, p).unwrap(), d.inverse(p), 1e-12);
+        }
+    }
+
+    #[test]
+    fn gamma() {
+        let d = Gamma::new(20.0, 1.0);
+        for &p in &[0.01, 0.05, 0.25, 0.5, 0.75, 0.95, 0.99] {
+            let x = quantile(&d, p).unwrap();
+            assert!((x / d.inverse(p) - 1.0).abs() < 1e-3);
+        }
+        let x = quantile(&d, 1e-6).unwrap();
+        assert!((x / d.inverse(1e-6) - 1.0).abs() < 3e-2);
+    }
+
+    #[test]
+    fn method() {
+        let d = Gamma::new(20.0, 1.0);
+        assert_eq!(d.inverse_cornish_fisher(0.95).unwrap(), quantile(&d, 0.95).unwrap());
+        assert!(Gamma::new(1.0, 1.0).inverse_cornish_fisher(0.05).is_err());
+    }
+
+    #[test]
+    fn monotone() {
+        for &p in &[0.01, 0.5, 0.99] {
+            assert_eq!(cornish_fisher_quantile(0.0, 1.0, 0.0, 10.0, p),
+                       Err(ParameterError::ExpansionNotMonotone {
+                           skewness: 0.0,
+                           kurtosis: 10.0,
+                       }));
+        }
+        let error = ParameterError::ExpansionNotMonotone { skewness: 5.0, kurtosis: 3.0 };
+        assert_eq!(cornish_fisher_quantile(0.0, 1.0, 5.0, 3.0, 0.99), Err(error));
+        assert!(cornish_fisher_quantile(0.0, 1.0, 0.5, 1.0, 1e-12).is_ok());
+        assert!(cornish_fisher_quantile(0.0, 1.0, 0.0, 2.0, 0.99).is_ok());
+        assert!(cornish_fisher_quantile(0.0, 1.0, ::std::f64::NAN, 0.0, 0.5).is_err());
+    }
+
+    #[test]
+    fn parameters() {
+        assert_eq!(cornish_fisher_quantile(0.0, 0.0, 0.0, 0.0, 0.5),
+                   Err(ParameterError::SigmaNotPositive { value: 0.0 }));
+        assert_eq!(cornish_fisher_quantile(0.0, 1.0, 0.0, 0.0, 1.0),
+                   Err(ParameterError::ProbabilityOutOfRange { value: 1.0 }));
+        assert_eq!(cornish_fisher_quantile(::std::f64::INFINITY, 1.0, 0.0, 0.0, 0.5),
+                   Err(ParameterError::MuNotFinite { value: ::std::f64::INFINITY }));
+    }
+}
@@ -0,0 +1,7 @@
+//! Analytic approximations of distributions.
+
+mod cornish_fisher;
+mod saddlepoint;
+
+pub use self::cornish_fisher::cornish_fisher_quantile;
+pub use self::saddlepoint::{SaddlepointResult, saddlepoint_sum};
@@ -0,0 +1,265 @@
+//! The saddlepoint approximation.
+//!
+//! The density and the tail probabilities of the sum `S` of `n` independent
+//! copies of `X` are approximated using the cumulant generating function `K`
+//! of `X`. The saddlepoint `t` solves `K'(t) = x / n`, and, with
+//! `w = sign(t) √(2 n (t x / n - K(t)))` and `u = t √(n K''(t))`, the density
+//! is approximated by `exp(n K(t) - t x) / √(2π n K''(t))` and the survival
+//! function by the formula of Lugannani and Rice (1980),
+//! `1 - Φ(w) + φ(w) (1 / u - 1 / w)`. For distributions supported on the
+//! integers, the mass is approximated in place of the density, and the tail
+//! probabilities are computed with the second continuity correction of
+//! Daniels (1987): the probability that the sum is at least `x` is evaluated
+//! at `x - 1/2` with `u` replaced with `2 sinh(t / 2) √(n K''(t))`, and the
+//! probability that it does not exceed `x` is evaluated at `x + 1/2` in the
+//! same way. The relative error of the approximations stays small far into
+//! the tails, where the Gaussian approximation fails.
+//!
+//! ```
+//! use probability::approx;
+//! use probability::prelude::*;
+//!
+//! let result = approx::saddlepoint_sum(&Exponential::new(1.0), 10, 30.0);
+//! let exact = Gamma::new(10.0, 1.0).survival(30.0);
+//! assert!((result.survival / exact - 1.0).abs() < 1e-2);
+//! ```
+
+use distribution::{Distribution, Mgf, StandardGaussian};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// The result of a saddlepoint approximation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SaddlepointResult {
+    /// The approximation of the density of the sum at the point or, for
+    /// distributions supported on the integers, of its mass.
+    pub density: f64,
+    /// The approximation of the probability that the sum does not exceed the
+    /// point.
+    pub distribution: f64,
+    /// The approximation of the probability that the sum is at least the
+    /// point.
+    pub survival: f64,
+    /// The saddlepoint.
+    pub saddlepoint: f64,
+    /// The flag telling whether the saddlepoint exists. The approximations
+    /// are NaN otherwise.
+    pub exists: bool,
+}
+
+/// Approximate the distribution of the sum of `n` independent copies of a
+/// random variable following `distribution` at `x`.
+///
+/// The saddlepoint equation is solved by the Newton method safeguarded by
+/// bisection within the domain of the cumulant generating function. The
+/// saddlepoint does not exist when `x` lies on or beyond the boundary of the
+/// support of the sum, and when the equation has no solution within the
+/// domain, which happens, for instance, when the moment-generating function
+/// does not exist. Near the mean, the formula for the tail probabilities is
+/// singular, and the probabilities are interpolated linearly between the
+/// points at which `|u| = 1e-3`. For distributions supported on the
+/// integers, `x` should be an integer. It should hold that `n > 0`.
+pub fn saddlepoint_sum<D>(distribution: &D, n: usize, x: f64) -> SaddlepointResult
+    where D: Mgf
+{
+    use std::f64::consts::PI;
+    use std::f64::NAN;
+
+    should!(n > 0);
+    let missing = SaddlepointResult {
+        density: NAN,
+        distribution: NAN,
+        survival: NAN,
+        saddlepoint: NAN,
+        exists: false,
+    };
+    let (a, b) = distribution.support();
+    let (n, mean) = (n as f64, x / n as f64);
+    if !(a < mean && mean < b) {
+        return missing;
+    }
+    let t = match solve(distribution, mean) {
+        Some(t) => t,
+        _ => return missing,
+    };
+    let (k, k2) = (distribution.ln_mgf(t), distribution.ln_mgf_second_derivative(t));
+    if !k.is_finite() || !(k2 > 0.0) {
+        return missing;
+    }
+    let density = (n * (k - t * mean)).exp() / (2.0 * PI * n * k2).sqrt();
+    let (below, above) = if distribution.lattice() {
+        (tails(distribution, n, mean + 0.5 / n).0, tails(distribution, n, mean - 0.5 / n).1)
+    } else {
+        tails(distribution, n, mean)
+    };
+    if !below.is_finite() || !above.is_finite() {
+        return missing;
+    }
+    SaddlepointResult {
+        density: density,
+        distribution: below,
+        survival: above,
+        saddlepoint: t,
+        exists: true,
+    }
+}
+
+const ITERATIONS: usize = 200;
+const THRESHOLD: f64 = 1e-3;
+
+// Evaluate the formula of Lugannani and Rice at `n mean` given saddlepoint
+// `t` for the lower and the upper tail.
+fn lugannani_rice<D>(distribution: &D, n: f64, mean: f64, t: f64) -> (f64, f64) where D: Mgf {
+    use std::f64::consts::PI;
+    let k = distribution.ln_mgf(t);
+    let scale = (n * distribution.ln_mgf_second_derivative(t)).sqrt();
+    let w = t.signum() * (2.0 * n * (t * mean - k)).max(0.0).sqrt();
+    let u = if distribution.lattice() { 2.0 * (0.5 * t).sinh() * scale } else { t * scale };
+    let correction = (-0.5 * w * w).exp() / (2.0 * PI).sqrt() * (1.0 / u - 1.0 / w);
+    (StandardGaussian.distribution(w) - correction, StandardGaussian.survival(w) + correction)
+}
+
+// Solve `K'(t) = mean` within the domain of `K`.
+fn solve<D>(distribution: &D, mean: f64) -> Option<f64> where D: Mgf {
+    let (mut lower, mut upper) = distribution.mgf_domain();
+    if !(lower < upper) {
+        return None;
+    }
+    let mut t = 0f64.max(lower).min(upper);
+    if t == lower || t == upper {
+        t = if lower.is_finite() && upper.is_finite() { 0.5 * (lower + upper) } else { t };
+    }
+    for _ in 0..ITERATIONS {
+        let difference = distribution.ln_mgf_derivative(t) - mean;
+        if difference == 0.0 {
+            return Some(t);
+        }
+        if difference < 0.0 { lower = t } else { upper = t }
+        let mut next = t - difference / distribution.ln_mgf_second_derivative(t);
+        if !(lower < next && next < upper) {
+            next = match (lower.is_finite(), upper.is_finite()) {
+                (true, true) => 0.5 * (lower + upper),
+                (true, false) => lower + lower.abs().max(1.0),
+                (false, true) => upper - upper.abs().max(1.0),
+                _ => return None,
+            };
+        }
+        if (next - t).abs() <= 1e-14 * t.abs().max(1.0) {
+            return Some(next);
+        }
+        t = next;
+    }
+    let (a, b) = distribution.mgf_domain();
+    if t - a <= 1e-10 * t.abs().max(1.0) || b - t <= 1e-10 * t.abs().max(1.0) {
+        None
+    } else {
+        Some(t)
+    }
+}
+
+// Compute the probabilities that the sum does not exceed and that it is at
+// least `n mean`, interpolating near the mean of the sum. The probabilities
+// are NaN if the saddlepoint does not exist.
+fn tails<D>(distribution: &D, n: f64, mean: f64) -> (f64, f64) where D: Mgf {
+    use std::f64::NAN;
+    let t = match solve(distribution, mean) {
+        Some(t) => t,
+        _ => return (NAN, NAN),
+    };
+    if (t * (n * distribution.ln_mgf_second_derivative(t)).sqrt()).abs() >= THRESHOLD {
+        return lugannani_rice(distribution, n, mean, t);
+    }
+    let t = THRESHOLD / (n * distribution.ln_mgf_second_derivative(0.0)).sqrt();
+    let (lower, upper) = (distribution.ln_mgf_derivative(-t), distribution.ln_mgf_derivative(t));
+    let (p, q) = (lugannani_rice(distribution, n, lower, -t),
+                  lugannani_rice(distribution, n, upper, t));
+    let weight = (mean - lower) / (upper - lower);
+    (p.0 + weight * (q.0 - p.0), p.1 + weight * (q.1 - p.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::saddlepoint_sum;
+
+    // Check that `actual` agrees with `expected` up to relative error `delta`.
+    fn relative(actual: f64, expected: f64, delta: f64) {
+        assert!((actual / expected - 1.0).abs() < delta, "{} !~ {}", actual, expected);
+    }
+
+    #[test]
+    fn bernoulli() {
+        let (n, p) = (60, 0.2);
+        let (d, exact) = (Bernoulli::new(p), Binomial::new(n, p));
+        for k in 1..n {
+            let result = saddlepoint_sum(&d, n, k as f64);
+            assert!(result.exists);
+            // The error of the mass is that of the Stirling formula for the
+            // binomial coefficient.
+            let delta = 0.1 / k as f64 + 0.1 / (n - k) as f64;
+            relative(result.density, exact.mass(k), delta);
+            relative(result.distribution, exact.distribution(k as f64), 3e-2);
+            relative(result.survival, exact.survival(k as f64 - 1.0), 3e-2);
+        }
+    }
+
+    #[test]
+    fn existence() {
+        let result = saddlepoint_sum(&StudentT::new(3.0, 0.0, 1.0), 10, 1.0);
+        assert!(!result.exists && result.survival.is_nan());
+        assert!(!saddlepoint_sum(&Exponential::new(1.0), 10, 0.0).exists);
+        assert!(!saddlepoint_sum(&Exponential::new(1.0), 10, -1.0).exists);
+        assert!(!saddlepoint_sum(&Bernoulli::new(0.3), 10, 10.0).exists);
+        assert!(saddlepoint_sum(&Bernoulli::new(0.3), 10, 9.0).exists);
+    }
+
+    #[test]
+    fn gamma() {
+        let (n, k, theta) = (5, 2.0, 1.5);
+        let (d, exact) = (Gamma::new(k, theta), Gamma::new(n as f64 * k, theta));
+        for &p in &[1e-12, 1e-6, 1e-3, 0.1, 0.3, 0.5, 0.7, 0.9, 1.0 - 1e-3, 1.0 - 1e-6] {
+            let x = exact.inverse(p);
+            let result = saddlepoint_sum(&d, n, x);
+            assert!(result.exists);
+            relative(result.density, exact.density(x), 1e-2);
+            relative(result.distribution, exact.distribution(x), 1e-2);
+            relative(result.survival, exact.survival(x), 1e-2);
+            assert::close(result.distribution + result.survival, 1.0, 1e-12);
+        }
+        let x = exact.inverse(1.0 - 1e-6) * 3.0;
+        relative(saddlepoint_sum(&d, n, x).survival, exact.survival(x), 1e-2);
+    }
+
+    #[test]
+    fn mean() {
+        use std::f64::consts::PI;
+        let (n, k, theta) = (5, 2.0, 1.5);
+        let (d, exact) = (Gamma::new(k, theta), Gamma::new(n as f64 * k, theta));
+        let x = n as f64 * d.mean();
+        let result = saddlepoint_sum(&d, n, x);
+        assert::close(result.saddlepoint, 0.0, 1e-12);
+        let skewness = d.skewness() / (n as f64).sqrt();
+        assert::close(result.survival, 0.5 - skewness / (6.0 * (2.0 * PI).sqrt()), 1e-6);
+        relative(result.survival, exact.survival(x), 1e-2);
+        for &delta in &[1e-12, 1e-8, 1e-4] {
+            let (lower, upper) = (saddlepoint_sum(&d, n, x - delta),
+                                  saddlepoint_sum(&d, n, x + delta));
+            assert!(lower.survival > result.survival && result.survival > upper.survival);
+            assert::close(lower.survival, result.survival, 2.0 * delta);
+        }
+    }
+
+    #[test]
+    fn poisson() {
+        let (n, lambda) = (4, 2.5);
+        let (d, exact) = (Poisson::new(lambda), Poisson::new(n as f64 * lambda));
+        for k in 1..60 {
+            let result = saddlepoint_sum(&d, n, k as f64);
+            relative(result.density, exact.mass(k), 0.1 / k as f64);
+            relative(result.distribution, exact.distribution(k as f64), 3e-2);
+            relative(result.survival, exact.survival(k as f64 - 1.0), 3e-2);
+        }
+    }
+}
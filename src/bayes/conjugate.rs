@@ -0,0 +1,451 @@
+use distribution::{BetaBinomial, Dirichlet, GaussianInverseGamma, Inverse, NegativeBinomial};
+use distribution::{Beta, Categorical, Gamma, Gaussian, StudentT};
+
+/// A beta prior updated with Bernoulli observations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BetaBernoulli {
+    alpha: f64,
+    beta: f64,
+}
+
+/// A Dirichlet prior updated with categorical observations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirichletMultinomial {
+    alpha: Vec<f64>,
+}
+
+/// A gamma prior on the rate updated with Poisson observations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaPoisson {
+    k: f64,
+    rate: f64,
+}
+
+/// A Gaussian–inverse-gamma prior on the mean and variance updated with
+/// Gaussian observations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalInverseGamma {
+    prior: GaussianInverseGamma,
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+/// A Gaussian prior on the mean updated with Gaussian observations of known
+/// variance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalKnownVariance {
+    prior: Gaussian,
+    sigma: f64,
+    count: usize,
+    sum: f64,
+}
+
+impl BetaBernoulli {
+    /// Start from a prior.
+    ///
+    /// It should hold that the prior is supported on `[0, 1]`; the function
+    /// panics otherwise.
+    pub fn new(prior: Beta) -> Self {
+        assert!(prior.a() == 0.0 && prior.b() == 1.0,
+                "the prior should be supported on [0, 1] (got [{}, {}])", prior.a(), prior.b());
+        BetaBernoulli { alpha: prior.alpha(), beta: prior.beta() }
+    }
+
+    /// Incorporate an observation, which should be either zero or one.
+    pub fn update(&mut self, observation: u8) {
+        assert!(observation <= 1, "the observation should be 0 or 1 (got {})", observation);
+        if observation == 1 {
+            self.alpha += 1.0;
+        } else {
+            self.beta += 1.0;
+        }
+    }
+
+    /// Incorporate a number of observations.
+    pub fn update_batch(&mut self, data: &[u8]) {
+        for &observation in data {
+            self.update(observation);
+        }
+    }
+
+    /// Return the posterior distribution of the success probability.
+    #[inline]
+    pub fn posterior(&self) -> Beta {
+        Beta::new(self.alpha, self.beta, 0.0, 1.0)
+    }
+
+    /// Return the posterior predictive distribution of the number of
+    /// successes in `trials` future trials.
+    #[inline]
+    pub fn posterior_predictive(&self, trials: usize) -> BetaBinomial {
+        BetaBinomial::new(trials, self.alpha, self.beta)
+    }
+
+    /// Compute the equal-tailed credible interval of the success probability
+    /// at level `level`.
+    #[inline]
+    pub fn credible_interval(&self, level: f64) -> (f64, f64) {
+        equal_tailed(&self.posterior(), level)
+    }
+}
+
+impl DirichletMultinomial {
+    /// Start from a prior.
+    pub fn new(prior: Dirichlet) -> Self {
+        DirichletMultinomial { alpha: prior.alpha().to_vec() }
+    }
+
+    /// Incorporate an observation, which is the index of a category.
+    pub fn update(&mut self, observation: usize) {
+        let k = self.alpha.len();
+        assert!(observation < k, "the category should be less than {} (got {})", k, observation);
+        self.alpha[observation] += 1.0;
+    }
+
+    /// Incorporate a number of observations.
+    pub fn update_batch(&mut self, data: &[usize]) {
+        for &observation in data {
+            self.update(observation);
+        }
+    }
+
+    /// Return the posterior distribution of the category probabilities.
+    #[inline]
+    pub fn posterior(&self) -> Dirichlet {
+        Dirichlet::new(&self.alpha)
+    }
+
+    /// Return the posterior predictive distribution of the next observation.
+    pub fn posterior_predictive(&self) -> Categorical {
+        let sum = self.alpha.iter().sum::<f64>();
+        Categorical::new(&self.alpha.iter().map(|&a| a / sum).collect::<Vec<_>>())
+    }
+
+    /// Compute the equal-tailed credible interval of each category probability
+    /// at level `level`.
+    pub fn credible_interval(&self, level: f64) -> Vec<(f64, f64)> {
+        let posterior = self.posterior();
+        (0..self.alpha.len()).map(|i| equal_tailed(&posterior.marginal(i), level)).collect()
+    }
+}
+
+impl GammaPoisson {
+    /// Start from a prior.
+    pub fn new(prior: Gamma) -> Self {
+        GammaPoisson { k: prior.k(), rate: prior.rate() }
+    }
+
+    /// Incorporate an observation.
+    #[inline]
+    pub fn update(&mut self, observation: usize) {
+        self.k += observation as f64;
+        self.rate += 1.0;
+    }
+
+    /// Incorporate a number of observations.
+    pub fn update_batch(&mut self, data: &[usize]) {
+        for &observation in data {
+            self.update(observation);
+        }
+    }
+
+    /// Return the posterior distribution of the rate.
+    #[inline]
+    pub fn posterior(&self) -> Gamma {
+        Gamma::with_rate(self.k, self.rate)
+    }
+
+    /// Return the posterior predictive distribution of the next observation.
+    #[inline]
+    pub fn posterior_predictive(&self) -> NegativeBinomial {
+        NegativeBinomial::new(self.k, self.rate / (self.rate + 1.0))
+    }
+
+    /// Compute the equal-tailed credible interval of the rate at level
+    /// `level`.
+    #[inline]
+    pub fn credible_interval(&self, level: f64) -> (f64, f64) {
+        equal_tailed(&self.posterior(), level)
+    }
+}
+
+impl NormalInverseGamma {
+    /// Start from a prior.
+    pub fn new(prior: GaussianInverseGamma) -> Self {
+        NormalInverseGamma { prior: prior, count: 0, mean: 0.0, m2: 0.0 }
+    }
+
+    /// Incorporate an observation.
+    pub fn update(&mut self, observation: f64) {
+        self.count += 1;
+        let delta = observation - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (observation - self.mean);
+    }
+
+    /// Incorporate a number of observations.
+    pub fn update_batch(&mut self, data: &[f64]) {
+        for &observation in data {
+            self.update(observation);
+        }
+    }
+
+    /// Return the posterior distribution of the mean and variance.
+    pub fn posterior(&self) -> GaussianInverseGamma {
+        let prior = &self.prior;
+        if self.count == 0 {
+            return *prior;
+        }
+        let n = self.count as f64;
+        let lambda = prior.lambda() + n;
+        let mu = (prior.lambda() * prior.mu() + n * self.mean) / lambda;
+        let alpha = prior.alpha() + n / 2.0;
+        let delta = self.mean - prior.mu();
+        let beta = prior.beta() + self.m2 / 2.0 +
+                   prior.lambda() * n * delta * delta / (2.0 * lambda);
+        GaussianInverseGamma::new(mu, lambda, alpha, beta)
+    }
+
+    /// Return the posterior predictive distribution of the next observation.
+    pub fn posterior_predictive(&self) -> StudentT {
+        let posterior = self.posterior();
+        let (lambda, alpha, beta) = (posterior.lambda(), posterior.alpha(), posterior.beta());
+        let sigma = (beta * (lambda + 1.0) / (alpha * lambda)).sqrt();
+        StudentT::new(2.0 * alpha, posterior.mu(), sigma)
+    }
+
+    /// Compute the equal-tailed credible intervals of the mean and of the
+    /// variance at level `level`.
+    pub fn credible_interval(&self, level: f64) -> ((f64, f64), (f64, f64)) {
+        let posterior = self.posterior();
+        let precision = Gamma::new(posterior.alpha(), 1.0 / posterior.beta());
+        let (lower, upper) = equal_tailed(&precision, level);
+        (equal_tailed(&posterior.mean_marginal(), level), (1.0 / upper, 1.0 / lower))
+    }
+}
+
+impl NormalKnownVariance {
+    /// Start from a prior on the mean given the standard deviation `sigma` of
+    /// the observations.
+    ///
+    /// It should hold that `sigma > 0`.
+    pub fn new(prior: Gaussian, sigma: f64) -> Self {
+        should!(sigma > 0.0);
+        NormalKnownVariance { prior: prior, sigma: sigma, count: 0, sum: 0.0 }
+    }
+
+    /// Incorporate an observation.
+    #[inline]
+    pub fn update(&mut self, observation: f64) {
+        self.count += 1;
+        self.sum += observation;
+    }
+
+    /// Incorporate a number of observations.
+    pub fn update_batch(&mut self, data: &[f64]) {
+        for &observation in data {
+            self.update(observation);
+        }
+    }
+
+    /// Return the posterior distribution of the mean.
+    pub fn posterior(&self) -> Gaussian {
+        let tau = self.prior.precision();
+        let sigma2 = self.sigma * self.sigma;
+        let precision = tau + self.count as f64 / sigma2;
+        Gaussian::from_precision((tau * self.prior.mu() + self.sum / sigma2) / precision, precision)
+    }
+
+    /// Return the posterior predictive distribution of the next observation.
+    pub fn posterior_predictive(&self) -> Gaussian {
+        let posterior = self.posterior();
+        let sigma = posterior.sigma();
+        Gaussian::from_variance(posterior.mu(), sigma * sigma + self.sigma * self.sigma)
+    }
+
+    /// Compute the equal-tailed credible interval of the mean at level
+    /// `level`.
+    #[inline]
+    pub fn credible_interval(&self, level: f64) -> (f64, f64) {
+        equal_tailed(&self.posterior(), level)
+    }
+}
+
+// It should hold that 0 < level < 1.
+fn equal_tailed<D>(distribution: &D, level: f64) -> (f64, f64) where D: Inverse<Value = f64> {
+    should!(0.0 < level && level < 1.0);
+    let tail = (1.0 - level) / 2.0;
+    (distribution.inverse(tail), distribution.inverse_survival(tail))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use bayes::*;
+    use prelude::*;
+
+    #[test]
+    fn beta_bernoulli() {
+        let mut model = BetaBernoulli::new(Beta::new(2.0, 3.0, 0.0, 1.0));
+        model.update_batch(&[1, 0, 1, 1, 0, 1]);
+        assert_eq!(model.posterior(), Beta::new(6.0, 5.0, 0.0, 1.0));
+
+        let predictive = model.posterior_predictive(10);
+        assert_eq!(predictive, BetaBinomial::new(10, 6.0, 5.0));
+        assert::close(model.posterior_predictive(1).mass(1), 6.0 / 11.0, 1e-15);
+        assert::close(predictive.mass(10), 6.0 * 7.0 * 8.0 * 9.0 * 10.0 * 11.0 * 12.0 * 13.0 *
+                                           14.0 * 15.0 / (11.0 * 12.0 * 13.0 * 14.0 * 15.0 *
+                                                          16.0 * 17.0 * 18.0 * 19.0 * 20.0),
+                      1e-15);
+
+        let (lower, upper) = model.credible_interval(0.9);
+        let posterior = model.posterior();
+        assert::close(&[posterior.distribution(lower), posterior.survival(upper)][..],
+                      &[0.05, 0.05][..], 1e-12);
+    }
+
+    #[test]
+    fn beta_bernoulli_batch() {
+        let data = [1, 1, 0, 1, 0, 0, 0, 1];
+        let (mut one, mut two) = (BetaBernoulli::new(Beta::new(0.5, 0.5, 0.0, 1.0)),
+                                  BetaBernoulli::new(Beta::new(0.5, 0.5, 0.0, 1.0)));
+        one.update_batch(&data);
+        for &x in &data {
+            two.update(x);
+        }
+        assert_eq!(one, two);
+    }
+
+    #[test]
+    #[should_panic]
+    fn beta_bernoulli_support() {
+        BetaBernoulli::new(Beta::new(2.0, 3.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn dirichlet_multinomial() {
+        let mut model = DirichletMultinomial::new(Dirichlet::new(&[1.0, 1.0, 1.0]));
+        model.update_batch(&[0, 2, 2, 1, 2]);
+        assert_eq!(model.posterior(), Dirichlet::new(&[2.0, 2.0, 4.0]));
+        assert_eq!(model.posterior_predictive(), Categorical::new(&[0.25, 0.25, 0.5]));
+
+        let intervals = model.credible_interval(0.95);
+        assert_eq!(intervals.len(), 3);
+        let marginal = Beta::new(4.0, 4.0, 0.0, 1.0);
+        let (lower, upper) = intervals[2];
+        assert::close(&[marginal.distribution(lower), marginal.survival(upper)][..],
+                      &[0.025, 0.025][..], 1e-12);
+    }
+
+    #[test]
+    fn dirichlet_multinomial_batch() {
+        let data = [3, 0, 0, 1, 3, 3, 2];
+        let prior = Dirichlet::new(&[0.5, 1.0, 1.5, 2.0]);
+        let (mut one, mut two) = (DirichletMultinomial::new(prior.clone()),
+                                  DirichletMultinomial::new(prior));
+        one.update_batch(&data);
+        for &x in &data {
+            two.update(x);
+        }
+        assert_eq!(one, two);
+    }
+
+    #[test]
+    fn gamma_poisson() {
+        let mut model = GammaPoisson::new(Gamma::with_rate(2.0, 1.0));
+        model.update_batch(&[3, 1, 4]);
+        let posterior = model.posterior();
+        assert_eq!((posterior.k(), posterior.rate()), (10.0, 4.0));
+
+        let predictive = model.posterior_predictive();
+        assert_eq!(predictive, NegativeBinomial::new(10.0, 0.8));
+        assert::close(predictive.mass(0), 0.8f64.powi(10), 1e-15);
+        assert::close(predictive.mass(1), 10.0 * 0.8f64.powi(10) * 0.2, 1e-15);
+        let compound = Compound::new(posterior, |lambda| Poisson::new(lambda));
+        for k in 0..8 {
+            assert::close(predictive.mass(k), compound.mass(k), 1e-6);
+        }
+
+        let (lower, upper) = model.credible_interval(0.9);
+        assert::close(&[posterior.distribution(lower), posterior.survival(upper)][..],
+                      &[0.05, 0.05][..], 1e-12);
+    }
+
+    #[test]
+    fn gamma_poisson_batch() {
+        let data = [0, 5, 2, 2, 7, 1];
+        let (mut one, mut two) = (GammaPoisson::new(Gamma::new(1.5, 2.0)),
+                                  GammaPoisson::new(Gamma::new(1.5, 2.0)));
+        one.update_batch(&data);
+        for &x in &data {
+            two.update(x);
+        }
+        assert_eq!(one, two);
+    }
+
+    #[test]
+    fn normal_inverse_gamma() {
+        let mut model = NormalInverseGamma::new(GaussianInverseGamma::new(0.0, 1.0, 1.0, 1.0));
+        assert_eq!(model.posterior(), GaussianInverseGamma::new(0.0, 1.0, 1.0, 1.0));
+        model.update_batch(&[1.0, 2.0, 3.0]);
+        assert_eq!(model.posterior(), GaussianInverseGamma::new(1.5, 4.0, 2.5, 3.5));
+
+        let predictive = model.posterior_predictive();
+        assert_eq!((predictive.nu(), predictive.mu()), (5.0, 1.5));
+        assert::close(predictive.sigma(), 1.75f64.sqrt(), 1e-15);
+
+        let ((lower, upper), (low, high)) = model.credible_interval(0.9);
+        let marginal = model.posterior().mean_marginal();
+        assert::close(&[marginal.distribution(lower), marginal.survival(upper)][..],
+                      &[0.05, 0.05][..], 1e-12);
+        let precision = Gamma::new(2.5, 1.0 / 3.5);
+        assert::close(&[precision.survival(1.0 / low), precision.distribution(1.0 / high)][..],
+                      &[0.05, 0.05][..], 1e-12);
+    }
+
+    #[test]
+    fn normal_inverse_gamma_batch() {
+        let data = [0.3, -1.2, 2.5, 0.8, 1.1];
+        let prior = GaussianInverseGamma::new(0.5, 2.0, 3.0, 4.0);
+        let (mut one, mut two) = (NormalInverseGamma::new(prior), NormalInverseGamma::new(prior));
+        one.update_batch(&data[..2]);
+        one.update_batch(&data[2..]);
+        for &x in &data {
+            two.update(x);
+        }
+        assert_eq!(one, two);
+
+        let posterior = one.posterior();
+        assert::close(posterior.mu(), (0.5 * 2.0 + 3.5) / 7.0, 1e-15);
+        assert::close(posterior.beta(), 4.0 + 7.18 / 2.0 + 2.0 * 5.0 * 0.2 * 0.2 / 14.0, 1e-14);
+    }
+
+    #[test]
+    fn normal_known_variance() {
+        let mut model = NormalKnownVariance::new(Gaussian::new(0.0, 2.0), 1.0);
+        model.update_batch(&[1.0, 2.0, 3.0]);
+        let posterior = model.posterior();
+        assert::close(posterior.mu(), 6.0 / 3.25, 1e-15);
+        assert::close(posterior.precision(), 3.25, 1e-14);
+
+        let predictive = model.posterior_predictive();
+        assert::close(predictive.mu(), 6.0 / 3.25, 1e-15);
+        assert::close(predictive.variance(), 1.0 / 3.25 + 1.0, 1e-15);
+
+        let (lower, upper) = model.credible_interval(0.95);
+        assert::close(upper - lower, 2.0 * 1.959963984540054 / 3.25f64.sqrt(), 1e-12);
+    }
+
+    #[test]
+    fn normal_known_variance_batch() {
+        let data = [0.3, -1.2, 2.5, 0.8];
+        let (mut one, mut two) = (NormalKnownVariance::new(Gaussian::new(1.0, 3.0), 0.5),
+                                  NormalKnownVariance::new(Gaussian::new(1.0, 3.0), 0.5));
+        one.update_batch(&data);
+        for &x in &data {
+            two.update(x);
+        }
+        assert_eq!(one, two);
+    }
+}
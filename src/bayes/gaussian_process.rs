@@ -0,0 +1,380 @@
+use distribution::{Gaussian, Sample, StandardGaussian};
+use math;
+use source::Source;
+use {FitError, ParameterError};
+
+/// A Gaussian process regression on the real line.
+///
+/// The latent function is a Gaussian process with zero mean and a covariance
+/// kernel, and the observations are the function values corrupted by
+/// independent Gaussian noise. Before fitting, the process represents the
+/// prior; after fitting, it represents the posterior given the data.
+///
+/// ```
+/// use probability::bayes::{GaussianProcess, Kernel};
+/// use probability::prelude::*;
+///
+/// let kernel = Kernel::SquaredExponential { length_scale: 1.0, variance: 1.0 };
+/// let mut process = GaussianProcess::new(kernel, 0.1);
+/// process.fit(&[0.0, 1.0, 2.0], &[0.0, 0.8, 0.9]).unwrap();
+/// let prediction = process.predict(&[1.5]);
+/// assert!((prediction[0].mean() - 0.9).abs() < 0.2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianProcess {
+    kernel: Kernel,
+    noise_sd: f64,
+    x: Vec<f64>,
+    lower: Vec<f64>,
+    weights: Vec<f64>,
+    log_marginal_likelihood: f64,
+}
+
+/// A stationary covariance kernel.
+///
+/// Each kernel depends on the distance `r = |x - y| / length_scale` and is
+/// scaled by the variance, which is its value at `r = 0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kernel {
+    /// The Matérn kernel with smoothness 3/2, `(1 + √3 r) exp(-√3 r)`.
+    Matern32 { length_scale: f64, variance: f64 },
+    /// The Matérn kernel with smoothness 5/2, `(1 + √5 r + 5 r² / 3)
+    /// exp(-√5 r)`.
+    Matern52 { length_scale: f64, variance: f64 },
+    /// The squared-exponential kernel, `exp(-r² / 2)`.
+    SquaredExponential { length_scale: f64, variance: f64 },
+}
+
+// The multiples of the kernel variance added to the diagonal when a
+// covariance matrix is numerically not positive definite.
+const JITTER: [f64; 6] = [0.0, 1e-10, 1e-9, 1e-8, 1e-7, 1e-6];
+
+impl GaussianProcess {
+    /// Create a process with the kernel `kernel` and the standard deviation of
+    /// the noise `noise_sd`.
+    ///
+    /// It should hold that the parameters of the kernel are positive and
+    /// finite and that `noise_sd >= 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(kernel: Kernel, noise_sd: f64) -> Self {
+        unwrap!(GaussianProcess::try_new(kernel, noise_sd))
+    }
+
+    /// Create a process with the kernel `kernel` and the standard deviation of
+    /// the noise `noise_sd` or return an error if the parameters are invalid.
+    pub fn try_new(kernel: Kernel, noise_sd: f64) -> Result<Self, ParameterError> {
+        let (length_scale, variance) = kernel.parameters();
+        ensure!(length_scale > 0.0 && length_scale.is_finite(),
+                ParameterError::LengthScaleNotPositive { value: length_scale });
+        ensure!(variance > 0.0 && variance.is_finite(),
+                ParameterError::VarianceNotPositive { value: variance });
+        ensure!(noise_sd >= 0.0 && noise_sd.is_finite(),
+                ParameterError::NoiseNotNonnegative { value: noise_sd });
+        Ok(GaussianProcess {
+            kernel: kernel,
+            noise_sd: noise_sd,
+            x: vec![],
+            lower: vec![],
+            weights: vec![],
+            log_marginal_likelihood: 0.0,
+        })
+    }
+
+    /// Condition the process on the observations `y` at the inputs `x`,
+    /// replacing any previous data.
+    ///
+    /// The covariance matrix of the observations is factorized by the Cholesky
+    /// decomposition. If it is numerically not positive definite, a small
+    /// multiple of the kernel variance is added to the diagonal, and an error
+    /// is returned if that does not help either. Without noise, repeated
+    /// inputs make the matrix singular, so they are rejected. It should hold
+    /// that `x` and `y` have the same length; the function panics otherwise.
+    pub fn fit(&mut self, x: &[f64], y: &[f64]) -> Result<(), FitError> {
+        use std::f64::consts::PI;
+
+        assert!(x.len() == y.len(),
+                "the inputs and outputs should have the same length (got {} and {})",
+                x.len(), y.len());
+        ensure!(!x.is_empty(), FitError::DataEmpty);
+        for &value in x.iter().chain(y) {
+            ensure!(value.is_finite(), FitError::DatumNotFinite { value: value });
+        }
+        if self.noise_sd == 0.0 {
+            let mut sorted = x.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            ensure!(sorted.windows(2).all(|pair| pair[0] < pair[1]), FitError::DataNotDistinct);
+        }
+        let n = x.len();
+        let mut covariance = self.covariance(x, x);
+        for i in 0..n {
+            covariance[i * n + i] += self.noise_sd * self.noise_sd;
+        }
+        let lower = match self.factorize(covariance, n) {
+            Some(lower) => lower,
+            _ => return Err(FitError::CovarianceNotDefinite),
+        };
+        let half = forward(&lower, y);
+        let weights = backward(&lower, &half);
+        let ln_determinant = (0..n).map(|i| lower[i * n + i].ln()).sum::<f64>();
+        self.log_marginal_likelihood = -0.5 * half.iter().map(|z| z * z).sum::<f64>() -
+                                       ln_determinant - 0.5 * n as f64 * (2.0 * PI).ln();
+        self.x = x.to_vec();
+        self.lower = lower;
+        self.weights = weights;
+        Ok(())
+    }
+
+    /// Return the kernel.
+    #[inline(always)]
+    pub fn kernel(&self) -> &Kernel { &self.kernel }
+
+    /// Return the logarithm of the marginal likelihood of the data, which is
+    /// zero before fitting.
+    ///
+    /// The marginal likelihood is the density of the observations with the
+    /// latent function integrated out, and it can be used to compare kernels
+    /// and their parameters.
+    #[inline(always)]
+    pub fn log_marginal_likelihood(&self) -> f64 { self.log_marginal_likelihood }
+
+    /// Return the standard deviation of the noise.
+    #[inline(always)]
+    pub fn noise_sd(&self) -> f64 { self.noise_sd }
+
+    /// Compute the marginal distributions of the latent function at the
+    /// inputs `x`.
+    ///
+    /// The distributions exclude the observation noise. The variance is
+    /// bounded below by a tiny fraction of the kernel variance, which keeps
+    /// the distributions proper at the inputs observed without noise.
+    pub fn predict(&self, x: &[f64]) -> Vec<Gaussian> {
+        let (n, floor) = (self.x.len(), 1e-12 * self.kernel.parameters().1);
+        x.iter().map(|&x| {
+            let cross = self.x.iter().map(|&y| self.kernel.covariance(x, y)).collect::<Vec<_>>();
+            let mean = cross.iter().zip(&self.weights).map(|(k, w)| k * w).sum::<f64>();
+            let reduction = if n > 0 {
+                forward(&self.lower, &cross).iter().map(|v| v * v).sum::<f64>()
+            } else {
+                0.0
+            };
+            let variance = self.kernel.covariance(x, x) - reduction;
+            Gaussian::from_variance(mean, variance.max(floor))
+        }).collect()
+    }
+
+    /// Draw the values of the latent function at the inputs `x` jointly.
+    ///
+    /// The function panics if the posterior covariance matrix is numerically
+    /// not positive definite even after adding a small multiple of the kernel
+    /// variance to the diagonal.
+    pub fn sample_posterior<S>(&self, source: &mut S, x: &[f64]) -> Vec<f64> where S: Source {
+        let (m, n) = (x.len(), self.x.len());
+        if m == 0 {
+            return vec![];
+        }
+        let mut covariance = self.covariance(x, x);
+        let mut mean = vec![0.0; m];
+        if n > 0 {
+            let cross = self.covariance(&self.x, x);
+            let columns = (0..m).map(|j| {
+                let column = (0..n).map(|i| cross[i * m + j]).collect::<Vec<_>>();
+                mean[j] = column.iter().zip(&self.weights).map(|(k, w)| k * w).sum();
+                forward(&self.lower, &column)
+            }).collect::<Vec<_>>();
+            for i in 0..m {
+                for j in 0..m {
+                    covariance[i * m + j] -= columns[i].iter().zip(&columns[j])
+                                                       .map(|(a, b)| a * b).sum::<f64>();
+                }
+            }
+        }
+        let lower = self.factorize(covariance, m);
+        let lower = lower.expect("the posterior covariance should be positive definite");
+        let noise = (0..m).map(|_| StandardGaussian.sample(source)).collect::<Vec<_>>();
+        (0..m).map(|i| {
+            mean[i] + (0..i + 1).map(|j| lower[i * m + j] * noise[j]).sum::<f64>()
+        }).collect()
+    }
+
+    fn covariance(&self, x: &[f64], y: &[f64]) -> Vec<f64> {
+        x.iter().flat_map(|&x| y.iter().map(move |&y| (x, y)))
+                .map(|(x, y)| self.kernel.covariance(x, y)).collect()
+    }
+
+    fn factorize(&self, mut matrix: Vec<f64>, n: usize) -> Option<Vec<f64>> {
+        let (variance, mut added) = (self.kernel.parameters().1, 0.0);
+        for &jitter in &JITTER {
+            for i in 0..n {
+                matrix[i * n + i] += (jitter - added) * variance;
+            }
+            added = jitter;
+            if let Some(lower) = math::cholesky(&matrix) {
+                return Some(lower);
+            }
+        }
+        None
+    }
+}
+
+impl Kernel {
+    /// Compute the covariance between the values at `x` and `y`.
+    pub fn covariance(&self, x: f64, y: f64) -> f64 {
+        let (length_scale, variance) = self.parameters();
+        let r = (x - y).abs() / length_scale;
+        variance * match *self {
+            Kernel::Matern32 { .. } => {
+                let r = 3f64.sqrt() * r;
+                (1.0 + r) * (-r).exp()
+            },
+            Kernel::Matern52 { .. } => {
+                let r = 5f64.sqrt() * r;
+                (1.0 + r + r * r / 3.0) * (-r).exp()
+            },
+            Kernel::SquaredExponential { .. } => (-0.5 * r * r).exp(),
+        }
+    }
+
+    fn parameters(&self) -> (f64, f64) {
+        match *self {
+            Kernel::Matern32 { length_scale, variance } |
+            Kernel::Matern52 { length_scale, variance } |
+            Kernel::SquaredExponential { length_scale, variance } => (length_scale, variance),
+        }
+    }
+}
+
+// Solve L z = b, where L is lower triangular and stored in the row-major order.
+fn forward(lower: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut z = vec![0.0; n];
+    for i in 0..n {
+        let sum = (0..i).fold(b[i], |sum, j| sum - lower[i * n + j] * z[j]);
+        z[i] = sum / lower[i * n + i];
+    }
+    z
+}
+
+// Solve Lᵀ z = b, where L is lower triangular and stored in the row-major
+// order.
+fn backward(lower: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut z = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).fold(b[i], |sum, j| sum - lower[j * n + i] * z[j]);
+        z[i] = sum / lower[i * n + i];
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use bayes::{GaussianProcess, Kernel};
+    use prelude::*;
+
+    const KERNEL: Kernel = Kernel::SquaredExponential { length_scale: 1.5, variance: 2.0 };
+
+    #[test]
+    fn coverage() {
+        let kernel = Kernel::Matern52 { length_scale: 0.7, variance: 1.5 };
+        let prior = GaussianProcess::new(kernel, 0.0);
+        let noise = Gaussian::new(0.0, 0.3);
+        let mut source = source::seeded(42);
+        let (mut covered, mut total) = (0, 0);
+        for _ in 0..50 {
+            let x = (0..40).map(|_| Uniform::new(0.0, 5.0).sample(&mut source))
+                           .collect::<Vec<_>>();
+            let truth = prior.sample_posterior(&mut source, &x);
+            let y = truth[..20].iter().map(|f| f + noise.sample(&mut source))
+                               .collect::<Vec<_>>();
+            let mut process = GaussianProcess::new(kernel, 0.3);
+            process.fit(&x[..20], &y).unwrap();
+            for (prediction, &f) in process.predict(&x[20..]).iter().zip(&truth[20..]) {
+                if (f - prediction.mean()).abs() < 1.96 * prediction.sigma() {
+                    covered += 1;
+                }
+                total += 1;
+            }
+        }
+        assert::close(covered as f64 / total as f64, 0.95, 0.02);
+    }
+
+    #[test]
+    fn fit() {
+        let mut process = GaussianProcess::new(KERNEL, 0.0);
+        assert_eq!(process.fit(&[0.0, 1.0, 0.0], &[1.0, 2.0, 1.0]),
+                   Err(FitError::DataNotDistinct));
+        assert_eq!(process.fit(&[], &[]), Err(FitError::DataEmpty));
+        assert!(process.fit(&[0.0, 1.0], &[1.0, ::std::f64::NAN]).is_err());
+        let mut process = GaussianProcess::new(KERNEL, 0.1);
+        assert!(process.fit(&[0.0, 1.0, 0.0], &[1.0, 2.0, 1.2]).is_ok());
+        let prediction = process.predict(&[0.0]);
+        assert!(prediction[0].mean().is_finite() && prediction[0].sigma().is_finite());
+        let mut process = GaussianProcess::new(KERNEL, 0.0);
+        assert!(process.fit(&[0.0, 1e-9], &[1.0, 1.0]).is_ok());
+        assert!(process.predict(&[0.5])[0].mean().is_finite());
+    }
+
+    #[test]
+    fn interpolation() {
+        let x = (0..8).map(|i| i as f64 * 0.7).collect::<Vec<_>>();
+        let y = x.iter().map(|x| x.sin()).collect::<Vec<_>>();
+        for &kernel in &[KERNEL, Kernel::Matern32 { length_scale: 1.0, variance: 1.0 },
+                         Kernel::Matern52 { length_scale: 1.0, variance: 1.0 }] {
+            let mut process = GaussianProcess::new(kernel, 0.0);
+            process.fit(&x, &y).unwrap();
+            for (prediction, &y) in process.predict(&x).iter().zip(&y) {
+                assert::close(prediction.mean(), y, 1e-6);
+                assert!(prediction.sigma() < 1e-4);
+            }
+            let path = process.sample_posterior(&mut source::seeded(42), &x);
+            assert::close(&path, &y, 1e-3);
+        }
+    }
+
+    #[test]
+    fn kernel() {
+        let kernel = Kernel::Matern32 { length_scale: 2.0, variance: 3.0 };
+        assert_eq!(kernel.covariance(1.0, 1.0), 3.0);
+        assert::close(kernel.covariance(0.0, 2.0), 3.0 * (1.0 + 3f64.sqrt()) *
+                                                   (-3f64.sqrt()).exp(), 1e-14);
+        let kernel = Kernel::Matern52 { length_scale: 2.0, variance: 3.0 };
+        assert::close(kernel.covariance(2.0, 0.0), 3.0 * (1.0 + 5f64.sqrt() + 5.0 / 3.0) *
+                                                   (-5f64.sqrt()).exp(), 1e-14);
+        assert::close(KERNEL.covariance(0.0, 1.5), 2.0 * (-0.5f64).exp(), 1e-14);
+    }
+
+    #[test]
+    fn log_marginal_likelihood() {
+        let mut process = GaussianProcess::new(KERNEL, 0.5);
+        assert_eq!(process.log_marginal_likelihood(), 0.0);
+        process.fit(&[0.0, 1.0, 2.5], &[0.5, -0.3, 1.2]).unwrap();
+        assert::close(process.log_marginal_likelihood(), -4.521_502_552_416_881, 1e-12);
+    }
+
+    #[test]
+    fn prior() {
+        let process = GaussianProcess::new(KERNEL, 0.1);
+        let prediction = process.predict(&[-1.0, 3.0]);
+        assert_eq!(prediction, vec![Gaussian::new(0.0, 2f64.sqrt()); 2]);
+        let mut source = source::seeded(42);
+        let samples = (0..10000).map(|_| process.sample_posterior(&mut source, &[0.0, 1.5]))
+                                .collect::<Vec<_>>();
+        let product = samples.iter().map(|x| x[0] * x[1]).sum::<f64>() / 10000.0;
+        assert::close(product, 2.0 * (-0.5f64).exp(), 0.1);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(GaussianProcess::try_new(Kernel::Matern32 { length_scale: 0.0, variance: 1.0 },
+                                            0.1),
+                   Err(ParameterError::LengthScaleNotPositive { value: 0.0 }));
+        assert_eq!(GaussianProcess::try_new(Kernel::Matern52 { length_scale: 1.0, variance: -1.0 },
+                                            0.1),
+                   Err(ParameterError::VarianceNotPositive { value: -1.0 }));
+        assert_eq!(GaussianProcess::try_new(KERNEL, -0.1),
+                   Err(ParameterError::NoiseNotNonnegative { value: -0.1 }));
+        let process = GaussianProcess::new(KERNEL, 0.1);
+        assert_eq!((process.kernel(), process.noise_sd()), (&KERNEL, 0.1));
+    }
+}
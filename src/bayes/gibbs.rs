@@ -0,0 +1,87 @@
+use distribution::{Gamma, Gaussian, GaussianInverseGamma, Sample};
+use mcmc::{Chain, Gibbs};
+use source::Source;
+use statistics;
+
+/// Draw from the posterior distribution of the mean and variance of Gaussian
+/// `data` under the Gaussian–inverse-gamma `prior` using Gibbs sampling.
+///
+/// The chain has two dimensions, `mu` and `variance`, which are drawn in turn
+/// from their full conditionals: the Gaussian distribution of the mean given
+/// the variance and the inverse-gamma distribution of the variance given the
+/// mean. The posterior is available in closed form, see
+/// `bayes::NormalInverseGamma`, so the function serves mainly as an example
+/// of `mcmc::Gibbs`. The chain starts at the mean of the data and the
+/// posterior mean of the variance given it. It should hold that `data` is
+/// nonempty.
+///
+/// ```
+/// use probability::bayes;
+/// use probability::prelude::*;
+///
+/// let data = [4.2, 5.1, 3.8, 4.9, 5.5, 4.4];
+/// let prior = GaussianInverseGamma::new(0.0, 0.01, 1.0, 1.0);
+/// let chain = bayes::gaussian_unknown_mean_variance(&data, prior, &mut source::seeded(42),
+///                                                   10000, 100);
+/// assert!((chain.means()[0] - 4.65).abs() < 0.05);
+/// ```
+pub fn gaussian_unknown_mean_variance<S>(data: &[f64], prior: GaussianInverseGamma,
+                                         source: &mut S, samples: usize, burn_in: usize)
+                                         -> Chain
+    where S: Source
+{
+    let n = data.len() as f64;
+    let mean = statistics::mean(data);
+    let m2 = data.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>();
+    let (mu0, lambda, alpha, beta) = (prior.mu(), prior.lambda(), prior.alpha(), prior.beta());
+    let mut gibbs = Gibbs::new();
+    gibbs.add("mu", mean, move |state, source| {
+        let precision = lambda + n;
+        let deviation = (state.get("variance") / precision).sqrt();
+        Gaussian::new((lambda * mu0 + n * mean) / precision, deviation).sample(source)
+    });
+    gibbs.add("variance", (beta + m2 / 2.0) / (alpha + n / 2.0), move |state, source| {
+        let mu = state.get("mu");
+        let squares = m2 + n * (mean - mu) * (mean - mu) + lambda * (mu - mu0) * (mu - mu0);
+        let shape = alpha + (n + 1.0) / 2.0;
+        1.0 / Gamma::new(shape, 1.0 / (beta + squares / 2.0)).sample(source)
+    });
+    gibbs.run(source, samples, burn_in)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use bayes::{NormalInverseGamma, gaussian_unknown_mean_variance};
+    use prelude::*;
+
+    const DATA: [f64; 10] = [1.2, 0.4, 2.3, 1.9, 0.8, 1.5, 2.8, 1.1, 0.2, 1.7];
+
+    #[test]
+    fn posterior() {
+        let prior = GaussianInverseGamma::new(0.0, 1.0, 2.0, 2.0);
+        let mut model = NormalInverseGamma::new(prior);
+        model.update_batch(&DATA);
+        let posterior = model.posterior();
+
+        let chain = gaussian_unknown_mean_variance(&DATA, prior, &mut source::seeded(42),
+                                                   50000, 1000);
+        assert_eq!(chain.names, vec!["mu".to_string(), "variance".to_string()]);
+        let (means, sds) = (chain.means(), chain.sds());
+        let sizes = chain.effective_sample_sizes();
+        let variance = posterior.beta() / (posterior.alpha() - 1.0);
+        assert::close(means[0], posterior.mu(), 4.0 * sds[0] / sizes[0].sqrt());
+        assert::close(means[1], variance, 4.0 * sds[1] / sizes[1].sqrt());
+        assert::close(sds[0], posterior.mean_marginal().deviation(), 0.01);
+    }
+
+    #[test]
+    fn reproducibility() {
+        let prior = GaussianInverseGamma::new(0.0, 1.0, 2.0, 2.0);
+        let one = gaussian_unknown_mean_variance(&DATA, prior, &mut source::seeded(42), 100, 10);
+        let two = gaussian_unknown_mean_variance(&DATA, prior, &mut source::seeded(42), 100, 10);
+        assert_eq!(one, two);
+        let three = gaussian_unknown_mean_variance(&DATA, prior, &mut source::seeded(7), 100, 10);
+        assert!(one != three);
+    }
+}
@@ -0,0 +1,19 @@
+//! Bayesian inference.
+//!
+//! ```
+//! use probability::bayes::BetaBernoulli;
+//! use probability::prelude::*;
+//!
+//! let mut model = BetaBernoulli::new(Beta::new(1.0, 1.0, 0.0, 1.0));
+//! model.update_batch(&[1, 0, 1, 1]);
+//! assert_eq!(model.posterior().mean(), 4.0 / 6.0);
+//! ```
+
+mod conjugate;
+mod gaussian_process;
+mod gibbs;
+
+pub use self::conjugate::{BetaBernoulli, DirichletMultinomial, GammaPoisson};
+pub use self::conjugate::{NormalInverseGamma, NormalKnownVariance};
+pub use self::gaussian_process::{GaussianProcess, Kernel};
+pub use self::gibbs::gaussian_unknown_mean_variance;
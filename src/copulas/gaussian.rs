@@ -0,0 +1,180 @@
+use copulas::Correlation;
+use distribution::{Distribution, Inverse, StandardGaussian};
+use source::Source;
+use ParameterError;
+
+/// A Gaussian copula.
+///
+/// The copula is the joint distribution of `Φ(Z)`, where `Z` is a Gaussian
+/// vector with zero mean and the correlation matrix of the copula, and `Φ` is
+/// the standard Gaussian distribution function applied elementwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianCopula {
+    correlation: Correlation,
+}
+
+impl GaussianCopula {
+    /// Create a copula with the correlation matrix `correlation`.
+    ///
+    /// It should hold that the matrix is symmetric and positive definite with
+    /// a unit diagonal; the function panics otherwise.
+    #[inline]
+    pub fn new(correlation: Vec<Vec<f64>>) -> Self {
+        unwrap!(GaussianCopula::try_new(correlation))
+    }
+
+    /// Create a copula with the correlation matrix `correlation` or return an
+    /// error if the matrix is invalid.
+    #[inline]
+    pub fn try_new(correlation: Vec<Vec<f64>>) -> Result<Self, ParameterError> {
+        Ok(GaussianCopula { correlation: Correlation::try_new(correlation)? })
+    }
+
+    /// Return the correlation matrix.
+    #[inline(always)]
+    pub fn correlation(&self) -> &[Vec<f64>] { &self.correlation.matrix }
+
+    /// Return the number of variables.
+    #[inline(always)]
+    pub fn dimension(&self) -> usize { self.correlation.matrix.len() }
+
+    /// Compute the natural logarithm of the copula density at `u`.
+    ///
+    /// The density is `|R|^(-1/2) exp(-zᵀ (R⁻¹ - I) z / 2)`, where `R` is the
+    /// correlation matrix and `z = Φ⁻¹(u)`. It should hold that the length of
+    /// `u` is the dimension of the copula and that `0 < u[i] < 1`; the
+    /// function panics otherwise.
+    pub fn ln_density(&self, u: &[f64]) -> f64 {
+        check(u, self.dimension());
+        let z = u.iter().map(|&u| StandardGaussian.inverse(u)).collect::<Vec<_>>();
+        let squares = z.iter().map(|z| z * z).sum::<f64>();
+        -0.5 * (self.correlation.ln_determinant + self.correlation.quadratic(&z) - squares)
+    }
+
+    /// Draw a sample of uniform variables.
+    pub fn sample_uniform<S>(&self, source: &mut S) -> Vec<f64> where S: Source {
+        let z = self.correlation.correlate(source);
+        z.into_iter().map(|z| StandardGaussian.distribution(z)).collect()
+    }
+
+    /// Draw a sample of variables with the marginal distributions `marginals`
+    /// by mapping a sample of uniform variables through the inverses of their
+    /// distribution functions.
+    ///
+    /// It should hold that the number of marginals is the dimension of the
+    /// copula; the function panics otherwise.
+    pub fn sample_with_marginals<S>(&self, source: &mut S, marginals: &[&dyn Inverse<Value=f64>])
+                                    -> Vec<f64>
+        where S: Source
+    {
+        assert!(marginals.len() == self.dimension(),
+                "the number of marginals should be the dimension (got {} and {})",
+                marginals.len(), self.dimension());
+        let u = self.sample_uniform(source);
+        u.into_iter().zip(marginals).map(|(u, marginal)| marginal.inverse(u)).collect()
+    }
+}
+
+pub(super) fn check(u: &[f64], dimension: usize) {
+    assert!(u.len() == dimension, "the point should have the dimension of the copula (got {} \
+                                   and {})", u.len(), dimension);
+    assert!(u.iter().all(|&u| 0.0 < u && u < 1.0),
+            "the point should lie within the open unit cube");
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use copulas::{self, GaussianCopula};
+    use prelude::*;
+    use statistics;
+    use tests::ks_one_sample;
+
+    fn bivariate(rho: f64) -> GaussianCopula {
+        GaussianCopula::new(vec![vec![1.0, rho], vec![rho, 1.0]])
+    }
+
+    #[test]
+    fn ln_density() {
+        let rho: f64 = 0.6;
+        let copula = bivariate(rho);
+        for &(u, v) in &[(0.5, 0.5), (0.1, 0.9), (0.02, 0.03), (0.7, 0.4)] {
+            let (x, y) = (Gaussian::new(0.0, 1.0).inverse(u), Gaussian::new(0.0, 1.0).inverse(v));
+            let expected = -0.5 * (1.0 - rho * rho).ln() -
+                           (rho * rho * (x * x + y * y) - 2.0 * rho * x * y) /
+                           (2.0 * (1.0 - rho * rho));
+            assert::close(copula.ln_density(&[u, v]), expected, 1e-12);
+        }
+        let identity = GaussianCopula::new(vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(identity.ln_density(&[0.2, 0.5, 0.9]), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the point should lie within the open unit cube")]
+    fn ln_density_boundary() {
+        bivariate(0.5).ln_density(&[0.0, 0.5]);
+    }
+
+    #[test]
+    fn marginals() {
+        let copula = GaussianCopula::new(vec![
+            vec![1.0, 0.5, -0.3],
+            vec![0.5, 1.0, 0.2],
+            vec![-0.3, 0.2, 1.0],
+        ]);
+        let (gamma, exponential, uniform) = (Gamma::new(2.0, 1.0), Exponential::new(3.0),
+                                             Uniform::new(-1.0, 1.0));
+        let mut source = source::seeded(42);
+        let samples = (0..5000).map(|_| {
+            copula.sample_with_marginals(&mut source, &[&gamma, &exponential, &uniform])
+        }).collect::<Vec<_>>();
+        let column = |i: usize| samples.iter().map(|x| x[i]).collect::<Vec<_>>();
+        assert!(ks_one_sample(&column(0), &gamma).p_value > 0.05);
+        assert!(ks_one_sample(&column(1), &exponential).p_value > 0.05);
+        assert!(ks_one_sample(&column(2), &uniform).p_value > 0.05);
+        let tau = statistics::kendall_tau(&column(0), &column(2));
+        assert::close(tau, copulas::kendall_from_pearson(-0.3), 0.03);
+    }
+
+    #[test]
+    fn rank_correlations() {
+        for &rho in &[-0.8, 0.3, 0.7] {
+            let copula = bivariate(rho);
+            let mut source = source::seeded(42);
+            let samples = (0..5000).map(|_| copula.sample_uniform(&mut source))
+                                   .collect::<Vec<_>>();
+            let (u, v) = (samples.iter().map(|x| x[0]).collect::<Vec<_>>(),
+                          samples.iter().map(|x| x[1]).collect::<Vec<_>>());
+            assert!(u.iter().chain(&v).all(|&u| 0.0 < u && u < 1.0));
+            assert!(ks_one_sample(&u, &Uniform::new(0.0, 1.0)).p_value > 0.05);
+            assert::close(statistics::spearman(&u, &v), copulas::spearman_from_pearson(rho),
+                          0.03);
+            assert::close(statistics::kendall_tau(&u, &v), copulas::kendall_from_pearson(rho),
+                          0.03);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(GaussianCopula::try_new(vec![
+            vec![1.0, 0.9, 0.9],
+            vec![0.9, 1.0, -0.9],
+            vec![0.9, -0.9, 1.0],
+        ]), Err(ParameterError::CorrelationNotDefinite));
+        assert_eq!(GaussianCopula::try_new(vec![vec![1.0, 0.5], vec![0.4, 1.0]]),
+                   Err(ParameterError::CorrelationNotDefinite));
+        assert_eq!(GaussianCopula::try_new(vec![vec![2.0, 0.5], vec![0.5, 2.0]]),
+                   Err(ParameterError::CorrelationNotDefinite));
+        assert_eq!(GaussianCopula::try_new(vec![vec![1.0, 1.0], vec![1.0, 1.0]]),
+                   Err(ParameterError::CorrelationNotDefinite));
+        assert_eq!(GaussianCopula::try_new(vec![vec![1.0, 0.5], vec![0.5]]),
+                   Err(ParameterError::MatrixNotSquare { rows: 2, columns: 1 }));
+        assert_eq!(GaussianCopula::try_new(vec![]), Err(ParameterError::CorrelationNotDefinite));
+        let copula = bivariate(0.5);
+        assert_eq!((copula.dimension(), copula.correlation()[0][1]), (2, 0.5));
+    }
+}
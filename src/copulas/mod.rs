@@ -0,0 +1,129 @@
+//! Copulas.
+//!
+//! A copula is a joint distribution of variables that are each uniformly
+//! distributed on `[0, 1]`, and it captures the dependence between variables
+//! separately from their marginal distributions. Joint samples with arbitrary
+//! marginals are obtained by mapping the uniforms through the inverses of the
+//! marginal distribution functions.
+//!
+//! ```
+//! use probability::copulas::GaussianCopula;
+//! use probability::prelude::*;
+//!
+//! let copula = GaussianCopula::new(vec![vec![1.0, 0.7], vec![0.7, 1.0]]);
+//! let mut source = source::seeded(42);
+//! let (gamma, exponential) = (Gamma::new(2.0, 1.0), Exponential::new(3.0));
+//! let x = copula.sample_with_marginals(&mut source, &[&gamma, &exponential]);
+//! assert!(x[0] > 0.0 && x[1] > 0.0);
+//! ```
+
+use distribution::{Sample, StandardGaussian};
+use math;
+use source::Source;
+use ParameterError;
+
+mod gaussian;
+mod student;
+
+pub use self::gaussian::GaussianCopula;
+pub use self::student::TCopula;
+
+/// Convert Pearson’s correlation parameter of an elliptical copula, such as
+/// the Gaussian and t copulas, into Kendall’s tau using `τ = 2 asin(ρ) / π`.
+#[inline]
+pub fn kendall_from_pearson(rho: f64) -> f64 {
+    2.0 * rho.asin() / ::std::f64::consts::PI
+}
+
+/// Convert Kendall’s tau into Pearson’s correlation parameter of an
+/// elliptical copula, such as the Gaussian and t copulas, using
+/// `ρ = sin(πτ / 2)`.
+#[inline]
+pub fn pearson_from_kendall(tau: f64) -> f64 {
+    (::std::f64::consts::PI * tau / 2.0).sin()
+}
+
+/// Convert Spearman’s rho into Pearson’s correlation parameter of the
+/// Gaussian copula using `ρ = 2 sin(πρₛ / 6)`.
+#[inline]
+pub fn pearson_from_spearman(rho: f64) -> f64 {
+    2.0 * (::std::f64::consts::PI * rho / 6.0).sin()
+}
+
+/// Convert Pearson’s correlation parameter of the Gaussian copula into
+/// Spearman’s rho using `ρₛ = 6 asin(ρ / 2) / π`.
+#[inline]
+pub fn spearman_from_pearson(rho: f64) -> f64 {
+    6.0 * (rho / 2.0).asin() / ::std::f64::consts::PI
+}
+
+// A correlation matrix along with its Cholesky factor.
+#[derive(Clone, Debug, PartialEq)]
+struct Correlation {
+    matrix: Vec<Vec<f64>>,
+    lower: Vec<f64>,
+    ln_determinant: f64,
+}
+
+impl Correlation {
+    fn try_new(matrix: Vec<Vec<f64>>) -> Result<Self, ParameterError> {
+        let n = matrix.len();
+        ensure!(n > 0, ParameterError::CorrelationNotDefinite);
+        for row in &matrix {
+            ensure!(row.len() == n,
+                    ParameterError::MatrixNotSquare { rows: n, columns: row.len() });
+        }
+        for (i, row) in matrix.iter().enumerate() {
+            ensure!(row[i] == 1.0, ParameterError::CorrelationNotDefinite);
+            for (j, other) in matrix.iter().take(i).enumerate() {
+                ensure!(row[j] == other[i] && row[j].abs() <= 1.0,
+                        ParameterError::CorrelationNotDefinite);
+            }
+        }
+        let flat = matrix.iter().flat_map(|row| row.iter().cloned()).collect::<Vec<_>>();
+        let lower = match math::cholesky(&flat) {
+            Some(lower) => lower,
+            None => return Err(ParameterError::CorrelationNotDefinite),
+        };
+        let ln_determinant = 2.0 * (0..n).map(|i| lower[i * n + i].ln()).sum::<f64>();
+        Ok(Correlation { matrix: matrix, lower: lower, ln_determinant: ln_determinant })
+    }
+
+    // Draw a Gaussian vector with the correlation matrix.
+    fn correlate<S>(&self, source: &mut S) -> Vec<f64> where S: Source {
+        let n = self.matrix.len();
+        let g = (0..n).map(|_| StandardGaussian.sample(source)).collect::<Vec<_>>();
+        (0..n).map(|i| (0..(i + 1)).map(|j| self.lower[i * n + j] * g[j]).sum()).collect()
+    }
+
+    // Compute `zᵀ R⁻¹ z` by forward substitution.
+    fn quadratic(&self, z: &[f64]) -> f64 {
+        let n = self.matrix.len();
+        let mut y = Vec::with_capacity(n);
+        for (i, &z) in z.iter().take(n).enumerate() {
+            let row = &self.lower[(i * n)..(i * n + i + 1)];
+            let sum = row.iter().zip(&y).fold(z, |sum, (lower, y)| sum - lower * y);
+            y.push(sum / row[i]);
+        }
+        y.iter().map(|y| y * y).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use copulas;
+
+    #[test]
+    fn conversions() {
+        for &rho in &[-0.9, -0.3, 0.0, 0.5, 0.99] {
+            let tau = copulas::kendall_from_pearson(rho);
+            assert::close(copulas::pearson_from_kendall(tau), rho, 1e-14);
+            let spearman = copulas::spearman_from_pearson(rho);
+            assert::close(copulas::pearson_from_spearman(spearman), rho, 1e-14);
+            assert!(spearman.abs() <= rho.abs() + 1e-15 && tau.abs() <= spearman.abs());
+        }
+        assert::close(copulas::kendall_from_pearson(0.5), 1.0 / 3.0, 1e-15);
+        assert::close(copulas::spearman_from_pearson(1.0), 1.0, 1e-15);
+    }
+}
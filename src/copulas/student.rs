@@ -0,0 +1,188 @@
+use copulas::Correlation;
+use copulas::gaussian::check;
+use distribution::{Gamma, Inverse, Sample};
+use math;
+use source::Source;
+use ParameterError;
+
+/// A t copula.
+///
+/// The copula is the joint distribution of `T(Z / √(W / ν))`, where `Z` is a
+/// Gaussian vector with zero mean and the correlation matrix of the copula,
+/// `W` is an independent chi-squared variable with `ν` degrees of freedom,
+/// and `T` is the distribution function of Student’s t distribution with `ν`
+/// degrees of freedom applied elementwise. Unlike the Gaussian copula, it
+/// exhibits tail dependence, which is stronger for fewer degrees of freedom.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TCopula {
+    correlation: Correlation,
+    nu: f64,
+    chi: Gamma,
+}
+
+impl TCopula {
+    /// Create a copula with the correlation matrix `correlation` and `nu`
+    /// degrees of freedom.
+    ///
+    /// It should hold that the matrix is symmetric and positive definite with
+    /// a unit diagonal and that `nu > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(correlation: Vec<Vec<f64>>, nu: f64) -> Self {
+        unwrap!(TCopula::try_new(correlation, nu))
+    }
+
+    /// Create a copula with the correlation matrix `correlation` and `nu`
+    /// degrees of freedom or return an error if the parameters are invalid.
+    pub fn try_new(correlation: Vec<Vec<f64>>, nu: f64) -> Result<Self, ParameterError> {
+        ensure!(nu > 0.0 && nu.is_finite(), ParameterError::NuNotPositive { value: nu });
+        Ok(TCopula {
+            correlation: Correlation::try_new(correlation)?,
+            nu: nu,
+            chi: Gamma::new(nu / 2.0, 2.0),
+        })
+    }
+
+    /// Return the correlation matrix.
+    #[inline(always)]
+    pub fn correlation(&self) -> &[Vec<f64>] { &self.correlation.matrix }
+
+    /// Return the number of variables.
+    #[inline(always)]
+    pub fn dimension(&self) -> usize { self.correlation.matrix.len() }
+
+    /// Compute the natural logarithm of the copula density at `u`.
+    ///
+    /// The density is the ratio of the multivariate t density at
+    /// `t = T⁻¹(u)` to the product of the univariate t densities at the
+    /// elements of `t`. It should hold that the length of `u` is the
+    /// dimension of the copula and that `0 < u[i] < 1`; the function panics
+    /// otherwise.
+    pub fn ln_density(&self, u: &[f64]) -> f64 {
+        use special::Gamma;
+
+        let (nu, d) = (self.nu, self.dimension() as f64);
+        check(u, self.dimension());
+        let t = u.iter().map(|&u| math::student_inverse(u, nu)).collect::<Vec<_>>();
+        let ln_norm = Gamma::ln_gamma((nu + d) / 2.0).0 +
+                      (d - 1.0) * Gamma::ln_gamma(nu / 2.0).0 -
+                      d * Gamma::ln_gamma((nu + 1.0) / 2.0).0;
+        let marginal = t.iter().map(|t| (t * t / nu).ln_1p()).sum::<f64>();
+        ln_norm - 0.5 * self.correlation.ln_determinant -
+            (nu + d) / 2.0 * (self.correlation.quadratic(&t) / nu).ln_1p() +
+            (nu + 1.0) / 2.0 * marginal
+    }
+
+    /// Return the number of degrees of freedom.
+    #[inline(always)]
+    pub fn nu(&self) -> f64 { self.nu }
+
+    /// Draw a sample of uniform variables.
+    pub fn sample_uniform<S>(&self, source: &mut S) -> Vec<f64> where S: Source {
+        let z = self.correlation.correlate(source);
+        let scale = (self.chi.sample(source) / self.nu).sqrt();
+        z.into_iter().map(|z| math::student(-z / scale, self.nu)).collect()
+    }
+
+    /// Draw a sample of variables with the marginal distributions `marginals`
+    /// by mapping a sample of uniform variables through the inverses of their
+    /// distribution functions.
+    ///
+    /// It should hold that the number of marginals is the dimension of the
+    /// copula; the function panics otherwise.
+    pub fn sample_with_marginals<S>(&self, source: &mut S, marginals: &[&dyn Inverse<Value=f64>])
+                                    -> Vec<f64>
+        where S: Source
+    {
+        assert!(marginals.len() == self.dimension(),
+                "the number of marginals should be the dimension (got {} and {})",
+                marginals.len(), self.dimension());
+        let u = self.sample_uniform(source);
+        u.into_iter().zip(marginals).map(|(u, marginal)| marginal.inverse(u)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use copulas::{self, GaussianCopula, TCopula};
+    use prelude::*;
+    use statistics;
+    use tests::ks_one_sample;
+
+    fn correlation(rho: f64) -> Vec<Vec<f64>> {
+        vec![vec![1.0, rho], vec![rho, 1.0]]
+    }
+
+    #[test]
+    fn kendall() {
+        let copula = TCopula::new(correlation(0.6), 4.0);
+        let mut source = source::seeded(42);
+        let samples = (0..5000).map(|_| copula.sample_uniform(&mut source)).collect::<Vec<_>>();
+        let (u, v) = (samples.iter().map(|x| x[0]).collect::<Vec<_>>(),
+                      samples.iter().map(|x| x[1]).collect::<Vec<_>>());
+        assert!(ks_one_sample(&u, &Uniform::new(0.0, 1.0)).p_value > 0.05);
+        assert!(ks_one_sample(&v, &Uniform::new(0.0, 1.0)).p_value > 0.05);
+        assert::close(statistics::kendall_tau(&u, &v), copulas::kendall_from_pearson(0.6), 0.03);
+    }
+
+    #[test]
+    fn ln_density() {
+        let (rho, nu): (f64, f64) = (-0.4, 3.0);
+        let copula = TCopula::new(correlation(rho), nu);
+        let marginal = StudentT::new(nu, 0.0, 1.0);
+        for &(u, v) in &[(0.5, 0.5), (0.1, 0.9), (0.02, 0.03), (0.7, 0.4)] {
+            let (x, y) = (marginal.inverse(u), marginal.inverse(v));
+            let joint = -(2.0 * ::std::f64::consts::PI).ln() - 0.5 * (1.0 - rho * rho).ln() -
+                        (nu + 2.0) / 2.0 * ((x * x - 2.0 * rho * x * y + y * y) /
+                                            (nu * (1.0 - rho * rho))).ln_1p();
+            let expected = joint - marginal.ln_density(x) - marginal.ln_density(y);
+            assert::close(copula.ln_density(&[u, v]), expected, 1e-10);
+        }
+        let copula = TCopula::new(correlation(0.5), 1e4);
+        let gaussian = GaussianCopula::new(correlation(0.5));
+        assert::close(copula.ln_density(&[0.3, 0.6]), gaussian.ln_density(&[0.3, 0.6]), 1e-3);
+    }
+
+    #[test]
+    fn marginals() {
+        let copula = TCopula::new(correlation(0.8), 5.0);
+        let (gamma, gaussian) = (Gamma::new(3.0, 0.5), Gaussian::new(1.0, 2.0));
+        let mut source = source::seeded(42);
+        let samples = (0..5000).map(|_| {
+            copula.sample_with_marginals(&mut source, &[&gamma, &gaussian])
+        }).collect::<Vec<_>>();
+        let (x, y) = (samples.iter().map(|x| x[0]).collect::<Vec<_>>(),
+                      samples.iter().map(|x| x[1]).collect::<Vec<_>>());
+        assert!(ks_one_sample(&x, &gamma).p_value > 0.05);
+        assert!(ks_one_sample(&y, &gaussian).p_value > 0.05);
+    }
+
+    #[test]
+    fn tail_dependence() {
+        let (rho, nu, n, level) = (0.5, 3.0, 200000, 0.99);
+        let mut source = source::seeded(42);
+        let joint = |samples: Vec<Vec<f64>>| {
+            let upper = samples.iter().filter(|x| x[0] > level).collect::<Vec<_>>();
+            upper.iter().filter(|x| x[1] > level).count() as f64 / upper.len() as f64
+        };
+        let copula = TCopula::new(correlation(rho), nu);
+        let t = joint((0..n).map(|_| copula.sample_uniform(&mut source)).collect());
+        let copula = GaussianCopula::new(correlation(rho));
+        let gaussian = joint((0..n).map(|_| copula.sample_uniform(&mut source)).collect());
+        let limit = 2.0 * StudentT::new(nu + 1.0, 0.0, 1.0)
+                              .distribution(-((nu + 1.0) * (1.0 - rho) / (1.0 + rho)).sqrt());
+        assert!(t > 2.0 * gaussian);
+        assert!(t > 0.7 * limit);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(TCopula::try_new(correlation(0.5), 0.0),
+                   Err(ParameterError::NuNotPositive { value: 0.0 }));
+        assert_eq!(TCopula::try_new(correlation(1.5), 3.0),
+                   Err(ParameterError::CorrelationNotDefinite));
+        let copula = TCopula::new(correlation(0.5), 3.0);
+        assert_eq!((copula.dimension(), copula.nu()), (2, 3.0));
+        assert_eq!(copula.correlation(), &correlation(0.5)[..]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Diagnostic plots.
+//!
+//! The functions produce the points of quantile–quantile and
+//! probability–probability plots, leaving the drawing to the caller.
+//!
+//! ```
+//! use probability::diagnostics::{self, PlottingPosition};
+//! use probability::prelude::*;
+//!
+//! let data = Gaussian::new(2.0, 3.0).samples(&mut source::seeded(42), 100);
+//! let d = Gaussian::new(0.0, 1.0);
+//! let points = diagnostics::qq_points(&data, &d, PlottingPosition::Hazen);
+//! let (intercept, slope) = diagnostics::qq_line(&data, &d);
+//! assert!((intercept - 2.0).abs() < 1.0 && (slope - 3.0).abs() < 1.0);
+//! ```
+
+use distribution::{Distribution, Empirical, Inverse};
+
+/// A rule assigning probabilities to order statistics.
+///
+/// The probability of the `i`th smallest of `n` observations is
+/// `(i - a) / (n + 1 - 2a)`, which lies strictly between zero and one, so
+/// that the quantiles of distributions with unbounded support remain finite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlottingPosition {
+    /// Take `a = 1 / 2`, that is, `(i - 1 / 2) / n`.
+    Hazen,
+    /// Take `a = 0`, that is, `i / (n + 1)`.
+    Weibull,
+    /// Take `a = 3 / 8`, that is, `(i - 3 / 8) / (n + 1 / 4)`.
+    Blom,
+}
+
+impl PlottingPosition {
+    /// Compute the probability of the `i`th smallest of `n` observations,
+    /// where `1 <= i <= n`.
+    pub fn probability(&self, i: usize, n: usize) -> f64 {
+        should!(1 <= i && i <= n);
+        let a = match *self {
+            PlottingPosition::Hazen => 0.5,
+            PlottingPosition::Weibull => 0.0,
+            PlottingPosition::Blom => 0.375,
+        };
+        (i as f64 - a) / (n as f64 + 1.0 - 2.0 * a)
+    }
+}
+
+/// Compute the points of the probability–probability plot of `data` against
+/// `distribution`.
+///
+/// Each point pairs the plotting position of an order statistic with the
+/// distribution function at it. It should hold that `data` contain no NaN.
+pub fn pp_points<D>(data: &[f64], distribution: &D, position: PlottingPosition)
+                    -> Vec<(f64, f64)>
+    where D: Distribution
+{
+    let data = sort(data);
+    let n = data.len();
+    data.iter().enumerate().map(|(i, &x)| {
+        (position.probability(i + 1, n), distribution.distribution(x))
+    }).collect()
+}
+
+/// Compute the probability-plot correlation coefficient of `data` against
+/// `distribution`.
+///
+/// The coefficient is the Pearson correlation of the points of the
+/// quantile–quantile plot. It is close to one when the data are drawn from
+/// the distribution up to location and scale, and smaller values indicate a
+/// departure in shape; it is the basis of the Shapiro–Francia and Filliben
+/// normality tests. It should hold that `data` contain at least two distinct
+/// values and no NaN.
+pub fn qq_correlation<D>(data: &[f64], distribution: &D, position: PlottingPosition) -> f64
+    where D: Inverse<Value=f64>
+{
+    let points = qq_points(data, distribution, position);
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+    for &(x, y) in &points {
+        let (x, y) = (x - mean_x, y - mean_y);
+        xx += x * x;
+        xy += x * y;
+        yy += y * y;
+    }
+    xy / (xx * yy).sqrt()
+}
+
+/// Compute the reference line of the quantile–quantile plot of `data`
+/// against `distribution`.
+///
+/// The line passes through the first and third quartiles of the data and of
+/// the distribution, which makes it insensitive to outliers, and it is
+/// returned as the intercept and the slope. The quartiles of the data are
+/// computed using linear interpolation. It should hold that `data` are
+/// nonempty and finite.
+pub fn qq_line<D>(data: &[f64], distribution: &D) -> (f64, f64) where D: Inverse<Value=f64> {
+    let empirical = Empirical::new(data);
+    let (x1, x3) = (distribution.inverse(0.25), distribution.inverse(0.75));
+    let (y1, y3) = (empirical.inverse(0.25), empirical.inverse(0.75));
+    let slope = (y3 - y1) / (x3 - x1);
+    (y1 - slope * x1, slope)
+}
+
+/// Compute the points of the quantile–quantile plot of `data` against
+/// `distribution`.
+///
+/// Each point pairs the quantile of the distribution at the plotting position
+/// of an order statistic with the order statistic. It should hold that `data`
+/// contain no NaN.
+pub fn qq_points<D>(data: &[f64], distribution: &D, position: PlottingPosition)
+                    -> Vec<(f64, f64)>
+    where D: Inverse<Value=f64>
+{
+    let data = sort(data);
+    let n = data.len();
+    data.iter().enumerate().map(|(i, &x)| {
+        (distribution.inverse(position.probability(i + 1, n)), x)
+    }).collect()
+}
+
+fn sort(data: &[f64]) -> Vec<f64> {
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::PlottingPosition::{Blom, Hazen, Weibull};
+
+    #[test]
+    fn pp_points() {
+        use super::pp_points;
+
+        let d = Uniform::new(0.0, 4.0);
+        let x = [3.0, 1.0, 2.0];
+        assert_eq!(pp_points(&x, &d, Hazen),
+                   vec![(1.0 / 6.0, 0.25), (0.5, 0.5), (5.0 / 6.0, 0.75)]);
+        assert_eq!(pp_points(&x, &d, Weibull), vec![(0.25, 0.25), (0.5, 0.5), (0.75, 0.75)]);
+        assert_eq!(pp_points(&x, &d, Blom),
+                   vec![(0.625 / 3.25, 0.25), (0.5, 0.5), (2.625 / 3.25, 0.75)]);
+    }
+
+    #[test]
+    fn qq_correlation() {
+        use super::qq_correlation;
+
+        let d = Gaussian::new(0.0, 1.0);
+        let mut source = source::seeded(42);
+        let x = Gaussian::new(5.0, 2.0).samples(&mut source, 200);
+        assert!(qq_correlation(&x, &d, Blom) > 0.99);
+        let x = Exponential::new(1.0).samples(&mut source, 200);
+        assert!(qq_correlation(&x, &d, Blom) < 0.95);
+        assert!(qq_correlation(&x, &Exponential::new(3.0), Blom) > 0.99);
+        assert::close(qq_correlation(&[1.0, 2.0, 3.0], &Uniform::new(0.0, 1.0), Hazen), 1.0,
+                      1e-15);
+    }
+
+    #[test]
+    fn qq_line() {
+        use super::qq_line;
+
+        let x = [5.0, 1.0, 4.0, 2.0, 3.0];
+        assert_eq!(qq_line(&x, &Uniform::new(0.0, 1.0)), (1.0, 4.0));
+
+        let d = Gaussian::new(0.0, 1.0);
+        let (intercept, slope) = qq_line(&x, &d);
+        assert::close(intercept, 3.0, 1e-15);
+        assert::close(slope, 1.0 / d.inverse(0.75), 1e-15);
+    }
+
+    #[test]
+    fn qq_points() {
+        use super::qq_points;
+
+        let d = Uniform::new(0.0, 1.0);
+        let x = [3.0, 1.0, 2.0];
+        assert_eq!(qq_points(&x, &d, Hazen), vec![(1.0 / 6.0, 1.0), (0.5, 2.0), (5.0 / 6.0, 3.0)]);
+        assert_eq!(qq_points(&x, &d, Weibull), vec![(0.25, 1.0), (0.5, 2.0), (0.75, 3.0)]);
+        assert_eq!(qq_points(&x, &d, Blom),
+                   vec![(0.625 / 3.25, 1.0), (0.5, 2.0), (2.625 / 3.25, 3.0)]);
+
+        let d = Exponential::new(1.0);
+        for &position in &[Hazen, Weibull, Blom] {
+            assert!(qq_points(&[1.0], &d, position)[0].0.is_finite());
+            assert!(qq_points(&x, &d, position).iter().all(|&(q, _)| q.is_finite()));
+        }
+    }
+}
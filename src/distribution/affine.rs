@@ -0,0 +1,489 @@
+use std::{fmt, ops};
+
+use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Float;
+
+/// An affine transformation of a distribution.
+///
+/// The distribution is that of `scale * X + shift` where `X` follows the inner
+/// distribution. When the scale is negative, the orientation of the
+/// cumulative distribution function is flipped, which is exact for continuous
+/// inner distributions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine<T> {
+    inner: T,
+    scale: f64,
+    shift: f64,
+}
+
+impl<T> Affine<T> where T: distribution::Distribution<Value=f64> {
+    /// Create an affine transformation of distribution `inner` with scale
+    /// `scale` and shift `shift`.
+    ///
+    /// It should hold that `scale != 0` and that `scale` and `shift` are
+    /// finite; the function panics otherwise.
+    #[inline]
+    pub fn new(inner: T, scale: f64, shift: f64) -> Self {
+        unwrap!(Affine::try_new(inner, scale, shift))
+    }
+
+    /// Create an affine transformation of distribution `inner` with scale
+    /// `scale` and shift `shift` or return an error if the parameters are
+    /// invalid.
+    #[inline]
+    pub fn try_new(inner: T, scale: f64, shift: f64) -> Result<Self, ParameterError> {
+        ensure!(scale != 0.0 && scale.is_finite(),
+                ParameterError::ScaleNotNonzero { value: scale });
+        ensure!(shift.is_finite(), ParameterError::ShiftNotFinite { value: shift });
+        Ok(Affine { inner: inner, scale: scale, shift: shift })
+    }
+
+    /// Return the inner distribution.
+    #[inline(always)]
+    pub fn inner(&self) -> &T { &self.inner }
+
+    /// Return the scale.
+    #[inline(always)]
+    pub fn scale(&self) -> f64 { self.scale }
+
+    /// Return the shift.
+    #[inline(always)]
+    pub fn shift(&self) -> f64 { self.shift }
+
+    #[inline(always)]
+    fn backward(&self, x: f64) -> f64 {
+        (x - self.shift) / self.scale
+    }
+
+    #[inline(always)]
+    fn forward(&self, x: f64) -> f64 {
+        self.scale * x + self.shift
+    }
+}
+
+impl<T> fmt::Display for Affine<T> where T: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Affine({}, scale = {}, shift = {})", self.inner, self.scale, self.shift)
+    }
+}
+
+impl<T> distribution::Continuous for Affine<T> where T: distribution::Continuous<Value=f64> {
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        self.inner.density(self.backward(x)) / self.scale.abs()
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        self.inner.ln_density(self.backward(x)) - self.scale.abs().ln()
+    }
+}
+
+impl<T> distribution::Distribution for Affine<T> where T: distribution::Distribution<Value=f64> {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        let x = self.backward(x);
+        if self.scale > 0.0 { self.inner.distribution(x) } else { self.inner.survival(x) }
+    }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        let x = self.backward(x);
+        if self.scale > 0.0 { self.inner.ln_distribution(x) } else { self.inner.ln_survival(x) }
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        let x = self.backward(x);
+        if self.scale > 0.0 { self.inner.ln_survival(x) } else { self.inner.ln_distribution(x) }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        let (a, b) = self.inner.support();
+        let (a, b) = (self.forward(a), self.forward(b));
+        if self.scale > 0.0 { (a, b) } else { (b, a) }
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        let x = self.backward(x);
+        if self.scale > 0.0 { self.inner.survival(x) } else { self.inner.distribution(x) }
+    }
+}
+
+impl<T> distribution::Entropy for Affine<T> where T: distribution::Entropy<Value=f64> {
+    #[inline]
+    fn entropy(&self) -> f64 {
+        self.inner.entropy() + self.scale.abs().ln()
+    }
+}
+
+impl<T> distribution::Inverse for Affine<T> where T: distribution::Inverse<Value=f64> {
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        if self.scale > 0.0 {
+            self.forward(self.inner.inverse(p))
+        } else {
+            self.forward(self.inner.inverse_survival(p))
+        }
+    }
+
+    #[inline]
+    fn inverse_survival(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        if self.scale > 0.0 {
+            self.forward(self.inner.inverse_survival(p))
+        } else {
+            self.forward(self.inner.inverse(p))
+        }
+    }
+}
+
+impl<T> distribution::Kurtosis for Affine<T> where T: distribution::Kurtosis<Value=f64> {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.inner.kurtosis()
+    }
+}
+
+impl<T> distribution::Mean for Affine<T> where T: distribution::Mean<Value=f64> {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.forward(self.inner.mean())
+    }
+}
+
+impl<T> distribution::Median for Affine<T> where T: distribution::Median<Value=f64> {
+    #[inline]
+    fn median(&self) -> f64 {
+        self.forward(self.inner.median())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> distribution::Modes for Affine<T> where T: distribution::Modes<Value=f64> {
+    fn modes(&self) -> ModeList<f64> {
+        let mut modes = self.inner.modes();
+        for x in modes.iter_mut() {
+            *x = self.forward(*x);
+        }
+        if self.scale < 0.0 {
+            modes.reverse();
+        }
+        modes
+    }
+}
+
+impl<T> distribution::Sample for Affine<T> where T: distribution::Sample<Value=f64> {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        self.forward(self.inner.sample(source))
+    }
+}
+
+impl<T> distribution::Skewness for Affine<T> where T: distribution::Skewness<Value=f64> {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.scale.signum() * self.inner.skewness()
+    }
+}
+
+impl<T> distribution::Variance for Affine<T> where T: distribution::Variance<Value=f64> {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.scale * self.scale * self.inner.variance()
+    }
+
+    #[inline]
+    fn deviation(&self) -> f64 {
+        self.scale.abs() * self.inner.deviation()
+    }
+}
+
+impl<T> ops::Add<f64> for Affine<T> where T: distribution::Distribution<Value=f64> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, shift: f64) -> Self {
+        Affine::new(self.inner, self.scale, self.shift + shift)
+    }
+}
+
+impl<T> ops::Sub<f64> for Affine<T> where T: distribution::Distribution<Value=f64> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, shift: f64) -> Self {
+        self + (-shift)
+    }
+}
+
+impl<T> ops::Mul<f64> for Affine<T> where T: distribution::Distribution<Value=f64> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, scale: f64) -> Self {
+        Affine::new(self.inner, self.scale * scale, self.shift * scale)
+    }
+}
+
+impl<T> ops::Neg for Affine<T> where T: distribution::Distribution<Value=f64> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self * -1.0
+    }
+}
+
+// Implement `Add<f64>`, `Sub<f64>`, `Mul<f64>`, and `Neg` for a distribution
+// by delegating to the closures `$shift` and `$scale`, which return `$output`.
+// Without the closures, the result is wrapped in `Affine`.
+macro_rules! affine(
+    ($kind:ty => $output:ty, $shift:expr, $scale:expr) => (
+        impl ::std::ops::Add<f64> for $kind {
+            type Output = $output;
+
+            #[inline]
+            fn add(self, shift: f64) -> $output {
+                $shift(self, shift)
+            }
+        }
+
+        impl ::std::ops::Add<$kind> for f64 {
+            type Output = $output;
+
+            #[inline]
+            fn add(self, distribution: $kind) -> $output {
+                $shift(distribution, self)
+            }
+        }
+
+        impl ::std::ops::Sub<f64> for $kind {
+            type Output = $output;
+
+            #[inline]
+            fn sub(self, shift: f64) -> $output {
+                $shift(self, -shift)
+            }
+        }
+
+        impl ::std::ops::Mul<f64> for $kind {
+            type Output = $output;
+
+            #[inline]
+            fn mul(self, scale: f64) -> $output {
+                $scale(self, scale)
+            }
+        }
+
+        impl ::std::ops::Mul<$kind> for f64 {
+            type Output = $output;
+
+            #[inline]
+            fn mul(self, distribution: $kind) -> $output {
+                $scale(distribution, self)
+            }
+        }
+
+        impl ::std::ops::Neg for $kind {
+            type Output = $output;
+
+            #[inline]
+            fn neg(self) -> $output {
+                $scale(self, -1.0)
+            }
+        }
+    );
+    ($kind:ty) => (
+        affine!($kind => ::distribution::Affine<$kind>,
+                |d, shift| ::distribution::Affine::new(d, 1.0, shift),
+                |d, scale| ::distribution::Affine::new(d, scale, 0.0));
+    );
+);
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($inner:expr, $scale:expr, $shift:expr) => (Affine::new($inner, $scale, $shift));
+    );
+
+    #[test]
+    fn central_moment() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        assert::close(d.central_moment(2), d.variance(), 1e-10);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-7);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-6);
+    }
+
+    #[test]
+    fn density() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 1.5];
+        let p = vec![4.0 * (-8.0f64).exp(), 4.0 * (-4.0f64).exp(), 4.0 * (-2.0f64).exp(),
+                     4.0, 0.0];
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Affine::new(Gaussian::new(0.0, 1.0), 2.0, -1.5).to_string(),
+                   "Affine(Gaussian(μ = 0, σ = 1), scale = 2, shift = -1.5)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 1.5];
+        let p = vec![(-8.0f64).exp(), (-4.0f64).exp(), (-2.0f64).exp(), 1.0, 1.0];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-15);
+
+        let d = new!(Uniform::new(0.0, 1.0), 2.0, -1.0);
+        let x = vec![-2.0, -1.0, -0.5, 0.0, 0.5, 1.0, 2.0];
+        let p = vec![0.0, 0.0, 0.25, 0.5, 0.75, 1.0, 1.0];
+        assert_eq!(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p);
+    }
+
+    #[test]
+    fn entropy() {
+        let d = new!(Gaussian::new(0.0, 1.0), -3.0, 1.0);
+        assert::close(d.entropy(), Gaussian::new(1.0, 3.0).entropy(), 1e-15);
+    }
+
+    #[test]
+    fn inverse() {
+        for d in &[new!(Beta::new(2.0, 1.5, 0.0, 3.0), -0.5, 1.0),
+                   new!(Beta::new(2.0, 1.5, 0.0, 3.0), 3.0, -2.0)] {
+            for &x in &[-4.0, -1.0, 0.0, 0.5, 2.0, 7.0] {
+                let p = d.distribution(x);
+                if 0.0 < p && p < 1.0 {
+                    assert::close(d.inverse(p), x, 1e-10);
+                }
+            }
+        }
+
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        assert_eq!(d.inverse(1.0), 1.0);
+        assert::close(d.inverse(0.5), 1.0 - 0.5 * Exponential::new(2.0).median(), 1e-15);
+    }
+
+    #[test]
+    fn inverse_survival() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        let p = vec![0.0, 0.05, 0.5, 0.95, 1.0];
+        assert::close(&p.iter().map(|&p| d.inverse_survival(p)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| d.inverse(1.0 - p)).collect::<Vec<_>>(), 1e-14);
+        let d = new!(Gaussian::new(0.0, 1.0), -1.0, 0.0);
+        assert::close(d.inverse_survival(1e-15), 7.9413453261709968, 1e-12);
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0];
+        assert::close(&x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.density(x).ln()).collect::<Vec<_>>(), 1e-15);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(Gaussian::new(0.0, 1.0), -2.0, 1.0);
+        assert::close(d.ln_distribution(-79.0), -804.60844201375378817, 1e-12);
+        assert::close(d.ln_survival(81.0), -804.60844201375378817, 1e-12);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(Exponential::new(2.0), -0.5, 1.0).mean(), 0.75);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(Exponential::new(2.0), -0.5, 1.0).modes(), vec![1.0]);
+        assert_eq!(new!(Beta::new(0.5, 0.5, 0.0, 1.0), -2.0, 1.0).modes(), vec![-1.0, 1.0]);
+    }
+
+    #[test]
+    fn moment() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        assert::close(d.moment(1), 0.75, 1e-10);
+        assert::close(d.moment(2), 0.625, 1e-10);
+    }
+
+    #[test]
+    fn operators() {
+        let d = 2.0 * Gaussian::new(0.0, 1.0) + 3.0;
+        assert_eq!((d.mu(), d.sigma()), (3.0, 2.0));
+        let d = -Gaussian::new(1.0, 2.0) * 3.0 - 1.0;
+        assert_eq!((d.mu(), d.sigma()), (-4.0, 6.0));
+
+        let d = 2.0 * Uniform::new(0.0, 1.0) + 3.0;
+        assert_eq!((d.a(), d.b()), (3.0, 5.0));
+        let d = -2.0 * Uniform::new(0.0, 1.0);
+        assert_eq!((d.a(), d.b()), (-2.0, 0.0));
+
+        let d = -2.0 * Exponential::new(1.0) + 1.0;
+        assert_eq!((d.scale(), d.shift()), (-2.0, 1.0));
+        let d = d * 2.0 + 1.0;
+        assert_eq!((d.scale(), d.shift()), (-4.0, 3.0));
+    }
+
+    #[test]
+    fn sample() {
+        for x in Independent(&new!(Exponential::new(2.0), -0.5, 1.0), &mut source::seeded(42))
+                     .take(100) {
+            assert!(x <= 1.0);
+        }
+    }
+
+    #[test]
+    fn skewness() {
+        assert_eq!(new!(Exponential::new(2.0), -0.5, 1.0).skewness(), -2.0);
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 1.5];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| 1.0 - d.distribution(x)).collect::<Vec<_>>(), 1e-15);
+
+        let d = new!(Gaussian::new(0.0, 1.0), -1.0, 0.0);
+        assert::close(d.distribution(-8.0) / 6.2209605742717841235e-16, 1.0, 1e-13);
+        assert::close(d.survival(8.0) / 6.2209605742717841235e-16, 1.0, 1e-13);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        let inner = Gaussian::new(0.0, 1.0);
+        assert!(Affine::try_new(inner, -2.0, 1.0).is_ok());
+        for &scale in &[0.0, INFINITY] {
+            assert_eq!(Affine::try_new(inner, scale, 1.0).err(),
+                       Some(ParameterError::ScaleNotNonzero { value: scale }));
+        }
+        assert_eq!(Affine::try_new(inner, 2.0, -INFINITY).err(),
+                   Some(ParameterError::ShiftNotFinite { value: -INFINITY }));
+        assert!(Affine::try_new(inner, NAN, 1.0).is_err());
+        assert!(Affine::try_new(inner, 2.0, NAN).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        let d = new!(Exponential::new(2.0), -0.5, 1.0);
+        assert_eq!(d.variance(), 0.0625);
+        assert_eq!(d.deviation(), 0.25);
+    }
+}
@@ -1,8 +1,17 @@
+use std::fmt;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
 use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
 use source::Source;
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// A Bernoulli distribution.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Bernoulli {
     p: f64,
     q: f64,
@@ -12,11 +21,18 @@ pub struct Bernoulli {
 impl Bernoulli {
     /// Create a Bernoulli distribution with success probability `p`.
     ///
-    /// It should hold that `p > 0` and `p < 1`.
+    /// It should hold that `p > 0` and `p < 1`; the function panics otherwise.
     #[inline]
     pub fn new(p: f64) -> Self {
-        should!(p > 0.0 && p < 1.0);
-        Bernoulli { p: p, q: 1.0 - p, pq: p * (1.0 - p) }
+        unwrap!(Bernoulli::try_new(p))
+    }
+
+    /// Create a Bernoulli distribution with success probability `p` or return
+    /// an error if the parameter is invalid.
+    #[inline]
+    pub fn try_new(p: f64) -> Result<Self, ParameterError> {
+        ensure!(p > 0.0 && p < 1.0, ParameterError::ProbabilityOutOfRange { value: p });
+        Ok(Bernoulli { p: p, q: 1.0 - p, pq: p * (1.0 - p) })
     }
 
     /// Create a Bernoulli distribution with failure probability `q`.
@@ -38,6 +54,12 @@ impl Bernoulli {
     pub fn q(&self) -> f64 { self.q }
 }
 
+impl fmt::Display for Bernoulli {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Bernoulli(p = {})", self.p)
+    }
+}
+
 impl distribution::Discrete for Bernoulli {
     #[inline]
     fn mass(&self, x: u8) -> f64 {
@@ -56,6 +78,7 @@ impl distribution::Distribution for Bernoulli {
 
     #[inline]
     fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
         if x < 0.0 {
             0.0
         } else if x < 1.0 {
@@ -64,6 +87,18 @@ impl distribution::Distribution for Bernoulli {
             1.0
         }
     }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+impl distribution::Divergence for Bernoulli {
+    #[inline]
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        self.p * (self.p / other.p).ln() + self.q * (self.q / other.q).ln()
+    }
 }
 
 impl distribution::Entropy for Bernoulli {
@@ -72,10 +107,21 @@ impl distribution::Entropy for Bernoulli {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Bernoulli {
+    /// Compute the Fisher information matrix with respect to `p`.
+    ///
+    /// The information is infinite when `p` is zero or one.
+    #[inline]
+    fn fisher_information(&self) -> Vec<f64> {
+        vec![1.0 / self.pq]
+    }
+}
+
 impl distribution::Inverse for Bernoulli {
     #[inline]
     fn inverse(&self, p: f64) -> u8 {
-        should!(0.0 <= p && p <= 1.0);
+        guard!(inverse(p));
         if p <= self.q { 0 } else { 1 }
     }
 }
@@ -87,6 +133,20 @@ impl distribution::Kurtosis for Bernoulli {
     }
 }
 
+impl distribution::MaximumLikelihood for Bernoulli {
+    /// Fit the distribution to `data`.
+    ///
+    /// The success probability is estimated by the proportion of ones.
+    fn fit_mle(data: &[u8]) -> Result<Self, FitError> {
+        ensure!(!data.is_empty(), FitError::DataEmpty);
+        if let Some(&value) = data.iter().find(|&&x| x > 1) {
+            return Err(FitError::DatumOutOfSupport { value: value as f64 });
+        }
+        let successes = data.iter().filter(|&&x| x == 1).count();
+        Ok(Bernoulli::try_new(successes as f64 / data.len() as f64)?)
+    }
+}
+
 impl distribution::Mean for Bernoulli {
     #[inline]
     fn mean(&self) -> f64 { self.p }
@@ -104,18 +164,57 @@ impl distribution::Median for Bernoulli {
     }
 }
 
+impl distribution::Mgf for Bernoulli {
+    #[inline]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        ln_mgf(self.p, self.q, t)
+    }
+
+    #[inline]
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        tilt(self.p, self.q, t)
+    }
+
+    #[inline]
+    fn ln_mgf_second_derivative(&self, t: f64) -> f64 {
+        let p = tilt(self.p, self.q, t);
+        p * (1.0 - p)
+    }
+
+    #[inline(always)]
+    fn lattice(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl distribution::Modes for Bernoulli {
-    fn modes(&self) -> Vec<u8> {
+    fn modes(&self) -> ModeList<u8> {
         use std::cmp::Ordering::*;
         match self.p.partial_cmp(&self.q) {
-            Some(Less) => vec![0],
-            Some(Equal) => vec![0, 1],
-            Some(Greater) => vec![1],
+            Some(Less) => ModeList::one(0),
+            Some(Equal) => ModeList::two(0, 1),
+            Some(Greater) => ModeList::one(1),
             None => unreachable!(),
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::Parametric for Bernoulli {
+    const POSITIVE: &'static [bool] = &[false];
+
+    #[inline]
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.p]
+    }
+
+    #[inline]
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError> {
+        Bernoulli::try_new(parameters[0])
+    }
+}
+
 impl distribution::Sample for Bernoulli {
     #[inline]
     fn sample<S>(&self, source: &mut S) -> u8 where S: Source {
@@ -135,6 +234,19 @@ impl distribution::Variance for Bernoulli {
     fn variance(&self) -> f64 { self.pq }
 }
 
+// Compute the cumulant generating function, that is, `ln(q + p exp(t))`.
+#[inline]
+pub fn ln_mgf(p: f64, q: f64, t: f64) -> f64 {
+    if t > 0.0 { t + (p + q * (-t).exp()).ln() } else { (p * t.exp_m1()).ln_1p() }
+}
+
+// Compute the probability of success of the distribution exponentially tilted
+// by `t`, that is, `p exp(t) / (q + p exp(t))`.
+#[inline]
+pub fn tilt(p: f64, q: f64, t: f64) -> f64 {
+    if t > 0.0 { p / (p + q * (-t).exp()) } else { p * t.exp() / (q + p * t.exp()) }
+}
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -145,6 +257,11 @@ mod tests {
         ($p:expr) => (Bernoulli::new($p));
     );
 
+    #[test]
+    fn display() {
+        assert_eq!(Bernoulli::new(0.25).to_string(), "Bernoulli(p = 0.25)");
+    }
+
     #[test]
     fn distribution() {
         let d = new!(0.25);
@@ -160,6 +277,27 @@ mod tests {
                       &vec![0.5623351446188083, 0.6931471805599453, 0.5623351446188083], 1e-16);
     }
 
+    #[test]
+    fn fisher_information() {
+        assert_eq!(new!(0.5).fisher_information(), vec![4.0]);
+        assert::close(new!(0.25).fisher_information()[0], 16.0 / 3.0, 1e-15);
+    }
+
+    #[test]
+    fn fit_mle() {
+        assert_eq!(Bernoulli::fit_mle(&[1, 0, 0, 1]), Ok(new!(0.5)));
+
+        let n = 100000;
+        let x = new!(0.3).samples(&mut source::seeded(42), n);
+        let d = Bernoulli::fit_mle(&x).unwrap();
+        assert!((d.p() - 0.3).abs() < 5.0 * (0.3 * 0.7 / n as f64).sqrt());
+
+        assert_eq!(Bernoulli::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Bernoulli::fit_mle(&[1, 2]), Err(FitError::DatumOutOfSupport { value: 2.0 }));
+        assert_eq!(Bernoulli::fit_mle(&[1, 1]),
+                   Err(FitError::Parameter(ParameterError::ProbabilityOutOfRange { value: 1.0 })));
+    }
+
     #[test]
     fn inverse() {
         let d = new!(0.25);
@@ -168,6 +306,13 @@ mod tests {
         assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
     }
 
+    #[test]
+    fn kl_divergence() {
+        let p = new!(0.3);
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&new!(0.6)), 0.18378689738681218714, 1e-15);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.5).kurtosis(), -2.0);
@@ -200,7 +345,7 @@ mod tests {
 
     #[test]
     fn sample() {
-        assert!(Independent(&new!(0.25), &mut source::default()).take(100)
+        assert!(Independent(&new!(0.25), &mut source::seeded(42)).take(100)
                                                                 .fold(0, |a, b| a + b) <= 100);
     }
 
@@ -209,6 +354,17 @@ mod tests {
         assert_eq!(new!(0.5).skewness(), 0.0);
     }
 
+    #[test]
+    fn try_new() {
+        use std::f64::NAN;
+        assert!(Bernoulli::try_new(0.25).is_ok());
+        for &p in &[0.0, 1.0, -0.5, 1.5] {
+            assert_eq!(Bernoulli::try_new(p).err(),
+                       Some(ParameterError::ProbabilityOutOfRange { value: p }));
+        }
+        assert!(Bernoulli::try_new(NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.25).variance(), 0.1875);
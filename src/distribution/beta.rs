@@ -1,26 +1,79 @@
-use distribution;
+use std::fmt;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use distribution::{self, PointMass};
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
 use source::Source;
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// A beta distribution.
-#[derive(Clone, Copy)]
+///
+/// The endpoints of the support are allowed to coincide, in which case the
+/// distribution is the point mass at `a`: the density is infinite at `a`, the
+/// distribution function is a step at `a`, and every sample is `a`. The
+/// skewness and kurtosis depend only on the shape parameters and are
+/// unaffected.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Beta {
     alpha: f64,
     beta: f64,
     a: f64,
     b: f64,
     ln_beta: f64,
+    ln_scale: f64,
 }
 
 impl Beta {
     /// Create a beta distribution with shape parameters `alpha` and `beta`
     /// on interval `[a, b]`.
     ///
-    /// It should hold that `alpha > 0`, `beta > 0`, and `a < b`.
+    /// It should hold that `alpha > 0`, `beta > 0`, and `a <= b` with `a` and
+    /// `b` finite; the function panics otherwise.
     #[inline]
     pub fn new(alpha: f64, beta: f64, a: f64, b: f64) -> Self {
+        unwrap!(Beta::try_new(alpha, beta, a, b))
+    }
+
+    /// Create a beta distribution with shape parameters `alpha` and `beta`
+    /// on interval `[a, b]` or return an error if the parameters are invalid.
+    pub fn try_new(alpha: f64, beta: f64, a: f64, b: f64) -> Result<Self, ParameterError> {
         use special::Beta as SpecialBeta;
-        should!(alpha > 0.0 && beta > 0.0 && a < b);
-        Beta { alpha: alpha, beta: beta, a: a, b: b, ln_beta: alpha.ln_beta(beta) }
+        ensure!(alpha > 0.0 && alpha.is_finite(),
+                ParameterError::AlphaNotPositive { value: alpha });
+        ensure!(beta > 0.0 && beta.is_finite(), ParameterError::BetaNotPositive { value: beta });
+        ensure!(a <= b && a.is_finite() && b.is_finite(),
+                ParameterError::BoundsNotOrdered { a: a, b: b });
+        Ok(Beta {
+            alpha: alpha,
+            beta: beta,
+            a: a,
+            b: b,
+            ln_beta: alpha.ln_beta(beta),
+            ln_scale: (b - a).ln(),
+        })
+    }
+
+    /// Create a beta distribution on interval `[0, 1]` with mean `mu` and
+    /// sample size `nu`, that is, with `alpha = mu nu` and `beta = (1 - mu)
+    /// nu`.
+    ///
+    /// It should hold that `0 < mu < 1` and `nu > 0`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn from_mean_and_sample_size(mu: f64, nu: f64) -> Self {
+        unwrap!(Beta::try_from_mean_and_sample_size(mu, nu))
+    }
+
+    /// Create a beta distribution on interval `[0, 1]` with mean `mu` and
+    /// sample size `nu` or return an error if the parameters are invalid.
+    pub fn try_from_mean_and_sample_size(mu: f64, nu: f64) -> Result<Self, ParameterError> {
+        ensure!(0.0 < mu && mu < 1.0, ParameterError::MeanOutOfRange { value: mu });
+        ensure!(nu > 0.0 && nu.is_finite(), ParameterError::SampleSizeNotPositive { value: nu });
+        Beta::try_new(mu * nu, (1.0 - mu) * nu, 0.0, 1.0)
     }
 
     /// Return the first shape parameter.
@@ -38,10 +91,49 @@ impl Beta {
     /// Return the right endpoint of the support.
     #[inline(always)]
     pub fn b(&self) -> f64 { self.b }
+
+    /// Return the sample size, that is, the sum of the shape parameters.
+    #[inline(always)]
+    pub fn sample_size(&self) -> f64 { self.alpha + self.beta }
+
+    /// Return the point mass at `a` if the distribution is degenerate.
+    #[inline(always)]
+    fn point(&self) -> Option<PointMass> {
+        if self.a == self.b { Some(PointMass::new(self.a)) } else { None }
+    }
+
+    /// Fit the distribution on interval `[0, 1]` to `data` weighted by
+    /// `weights` by maximum likelihood.
+    ///
+    /// The weights are relative and need not sum up to one. The estimate is
+    /// computed as described in `MaximumLikelihood`.
+    #[inline]
+    pub fn fit_weighted_mle(data: &[f64], weights: &[f64]) -> Result<Self, FitError> {
+        fit(data, Some(weights))
+    }
+}
+
+affine!(Beta => Beta,
+        |d: Beta, shift| Beta::new(d.alpha, d.beta, d.a + shift, d.b + shift),
+        |d: Beta, scale: f64| if scale > 0.0 {
+            Beta::new(d.alpha, d.beta, scale * d.a, scale * d.b)
+        } else {
+            Beta::new(d.beta, d.alpha, scale * d.b, scale * d.a)
+        });
+
+impl fmt::Display for Beta {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Beta(α = {}, β = {}, a = {}, b = {})", self.alpha, self.beta, self.a,
+               self.b)
+    }
 }
 
 impl distribution::Continuous for Beta {
     fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        if let Some(point) = self.point() {
+            return point.density(x);
+        }
         if x < self.a || x > self.b {
             0.0
         } else {
@@ -51,20 +143,102 @@ impl distribution::Continuous for Beta {
                 self.ln_beta).exp() / scale
         }
     }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The moment is computed from the raw moments of the distribution on
+    /// `[0, 1]`, which are `Π (α + i) / (α + β + i)` for `i < k`.
+    #[cfg(feature = "alloc")]
+    fn central_moment(&self, k: u32) -> f64 {
+        use distribution::translate;
+        let (alpha, beta) = (self.alpha, self.beta);
+        let moment = |k| (0..k).fold(1.0, |product, i| {
+            product * (alpha + i as f64) / (alpha + beta + i as f64)
+        });
+        translate(moment, k, -alpha / (alpha + beta)) * (self.b - self.a).powi(k as i32)
+    }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        guard!(ln_density(x));
+        if let Some(point) = self.point() {
+            return point.ln_density(x);
+        }
+        if x < self.a || x > self.b {
+            return ::std::f64::NEG_INFINITY;
+        }
+        let scale = self.b - self.a;
+        let x = (x - self.a) / scale;
+        let left = if self.alpha == 1.0 { 0.0 } else { (self.alpha - 1.0) * x.ln() };
+        let right = if self.beta == 1.0 { 0.0 } else { (self.beta - 1.0) * (-x).ln_1p() };
+        left + right - self.ln_beta - self.ln_scale
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        use distribution::{Mean, translate};
+        translate(|j| self.central_moment(j), k, self.mean())
+    }
 }
 
 impl distribution::Distribution for Beta {
     type Value = f64;
 
     fn distribution(&self, x: f64) -> f64 {
-        use special::Beta;
-        if x <= self.a {
+        use math::special;
+        guard!(distribution(x));
+        if x >= self.b {
+            1.0
+        } else if x <= self.a {
             0.0
-        } else if x >= self.b {
+        } else {
+            special::beta_inc(self.alpha, self.beta, (x - self.a) / (self.b - self.a))
+        }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(survival(x));
+        if x >= self.b {
+            0.0
+        } else if x <= self.a {
             1.0
         } else {
-            ((x - self.a) / (self.b - self.a)).inc_beta(self.alpha, self.beta, self.ln_beta)
+            special::beta_inc(self.beta, self.alpha, (self.b - x) / (self.b - self.a))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Divergence for Beta {
+    /// Compute the Kullback–Leibler divergence.
+    ///
+    /// A closed form is used when the two intervals coincide, and the
+    /// divergence is computed numerically otherwise. If the distribution is
+    /// degenerate, the divergence is zero when the other one is the same
+    /// point mass and infinite otherwise.
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        use distribution::{divergence, nested};
+        use special::Gamma;
+
+        if !nested(self, other) {
+            return ::std::f64::INFINITY;
+        }
+        if self.a == self.b {
+            return if other.a == other.b { 0.0 } else { ::std::f64::INFINITY };
+        }
+        if self.a != other.a || self.b != other.b {
+            return divergence::continuous(self, other);
         }
+        let (alpha, beta) = (self.alpha, self.beta);
+        other.ln_beta - self.ln_beta + (alpha - other.alpha) * alpha.digamma() +
+            (beta - other.beta) * beta.digamma() +
+            (other.alpha - alpha + other.beta - beta) * (alpha + beta).digamma()
     }
 }
 
@@ -77,12 +251,33 @@ impl distribution::Entropy for Beta {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Beta {
+    /// Compute the Fisher information matrix with respect to `(α, β)`.
+    ///
+    /// The endpoints of the support are treated as known.
+    fn fisher_information(&self) -> Vec<f64> {
+        use math;
+        let total = math::trigamma(self.alpha + self.beta);
+        vec![
+            math::trigamma(self.alpha) - total, -total,
+            -total, math::trigamma(self.beta) - total,
+        ]
+    }
+}
+
 impl distribution::Inverse for Beta {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
-        use special::Beta;
-        should!(0.0 <= p && p <= 1.0);
-        self.a + (self.b - self.a) * p.inv_inc_beta(self.alpha, self.beta, self.ln_beta)
+        use math::special;
+        guard!(inverse(p));
+        self.a + (self.b - self.a) * special::inv_beta_inc(self.alpha, self.beta, p)
+    }
+
+    fn inverse_survival(&self, p: f64) -> f64 {
+        use math::special;
+        guard!(inverse(p));
+        self.b - (self.b - self.a) * special::inv_beta_inc(self.beta, self.alpha, p)
     }
 }
 
@@ -96,6 +291,23 @@ impl distribution::Kurtosis for Beta {
     }
 }
 
+impl distribution::MaximumLikelihood for Beta {
+    /// Fit the distribution on interval `[0, 1]` to `data`.
+    ///
+    /// The shape parameters solve `ψ(α) - ψ(α + β) = l₁` and `ψ(β) - ψ(α + β)
+    /// = l₂`, where `l₁` and `l₂` are the means of `ln(x)` and `ln(1 - x)` over
+    /// the data, respectively; the system is solved by Newton’s method started
+    /// from the method-of-moments estimate. The likelihood is unbounded when a
+    /// datum is zero or one, and such data are rejected. If all the data are
+    /// the same, the likelihood increases without bound as `α` and `β` grow
+    /// with their ratio fixed; the limit is a point mass, which is not a beta
+    /// distribution on `[0, 1]`, and such data are rejected as well.
+    #[inline]
+    fn fit_mle(data: &[f64]) -> Result<Self, FitError> {
+        fit(data, None)
+    }
+}
+
 impl distribution::Mean for Beta {
     #[inline]
     fn mean(&self) -> f64 {
@@ -107,7 +319,7 @@ impl distribution::Median for Beta {
     fn median(&self) -> f64 {
         use distribution::Inverse;
         match (self.alpha, self.beta) {
-            (alpha, beta) if alpha == beta => 0.5 * (self.b - self.a),
+            (alpha, beta) if alpha == beta => self.a + 0.5 * (self.b - self.a),
             (alpha, beta) if alpha > 1.0 && beta > 1.0 => {
                 self.a + (self.b - self.a) * (alpha - 1.0 / 3.0) / (alpha + beta - 2.0 / 3.0)
             },
@@ -116,22 +328,55 @@ impl distribution::Median for Beta {
     }
 }
 
+impl distribution::MethodOfMoments for Beta {
+    /// Fit the distribution on interval `[0, 1]` to mean `mean` and variance
+    /// `variance`.
+    ///
+    /// The moments are feasible when `0 < mean < 1` and `0 < variance <
+    /// mean (1 - mean)`, in which case the sample size is `mean (1 - mean) /
+    /// variance - 1`.
+    fn fit_moments_from(mean: f64, variance: f64) -> Result<Self, FitError> {
+        ensure!(0.0 < mean && mean < 1.0 && variance > 0.0 && variance < mean * (1.0 - mean),
+                FitError::MomentsInfeasible { mean: mean, variance: variance });
+        let nu = mean * (1.0 - mean) / variance - 1.0;
+        Ok(Beta::try_new(mean * nu, (1.0 - mean) * nu, 0.0, 1.0)?)
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl distribution::Modes for Beta {
-    fn modes(&self) -> Vec<f64> {
+    fn modes(&self) -> ModeList<f64> {
         match (self.alpha, self.beta) {
-            (1.0, 1.0) => vec![],
-            (1.0, beta) if beta > 1.0 => vec![self.a],
-            (alpha, 1.0) if alpha > 1.0 => vec![self.b],
-            (alpha, beta) if alpha < 1.0 && beta < 1.0 => vec![self.a, self.b],
-            (alpha, beta) if alpha < 1.0 && beta >= 1.0 => vec![self.a],
-            (alpha, beta) if alpha >= 1.0 && beta < 1.0 => vec![self.b],
+            _ if self.a == self.b => ModeList::one(self.a),
+            (1.0, 1.0) => ModeList::new(),
+            (1.0, beta) if beta > 1.0 => ModeList::one(self.a),
+            (alpha, 1.0) if alpha > 1.0 => ModeList::one(self.b),
+            (alpha, beta) if alpha < 1.0 && beta < 1.0 => ModeList::two(self.a, self.b),
+            (alpha, beta) if alpha < 1.0 && beta >= 1.0 => ModeList::one(self.a),
+            (alpha, beta) if alpha >= 1.0 && beta < 1.0 => ModeList::one(self.b),
             (alpha, beta) => {
-                vec![self.a + (self.b - self.a) * (alpha - 1.0) / (alpha + beta - 2.0)]
+                ModeList::one(self.a + (self.b - self.a) * (alpha - 1.0) / (alpha + beta - 2.0))
             },
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::Parametric for Beta {
+    const POSITIVE: &'static [bool] = &[true, true];
+
+    /// Return the shape parameters.
+    #[inline]
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.alpha, self.beta]
+    }
+
+    #[inline]
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError> {
+        Beta::try_new(parameters[0], parameters[1], self.a, self.b)
+    }
+}
+
 impl distribution::Sample for Beta {
     #[inline]
     fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
@@ -158,6 +403,46 @@ impl distribution::Variance for Beta {
     }
 }
 
+fn fit(data: &[f64], weights: Option<&[f64]>) -> Result<Beta, FitError> {
+    use distribution::{average, examine_weights};
+    use math;
+    use special::Gamma;
+
+    const ITERATIONS: usize = 100;
+    const TOLERANCE: f64 = 1e-12;
+
+    examine_weights(data, weights)?;
+    if let Some(&value) = data.iter().find(|&&x| x <= 0.0 || x >= 1.0) {
+        return Err(FitError::DatumOutOfSupport { value: value });
+    }
+    let mean = average(data, weights, |x| x);
+    let variance = average(data, weights, |x| (x - mean) * (x - mean));
+    ensure!(variance > 0.0, FitError::DataNotDistinct);
+    let (l1, l2) = (average(data, weights, |x| x.ln()), average(data, weights, |x| (-x).ln_1p()));
+    let nu = (mean * (1.0 - mean) / variance - 1.0).max(::std::f64::EPSILON);
+    let (mut alpha, mut beta) = (mean * nu, (1.0 - mean) * nu);
+    for _ in 0..ITERATIONS {
+        let total = (alpha + beta).digamma();
+        let (f1, f2) = (alpha.digamma() - total - l1, beta.digamma() - total - l2);
+        let total = math::trigamma(alpha + beta);
+        let (d1, d2) = (math::trigamma(alpha) - total, math::trigamma(beta) - total);
+        let determinant = d1 * d2 - total * total;
+        let (mut step1, mut step2) = ((d2 * f1 + total * f2) / determinant,
+                                      (total * f1 + d1 * f2) / determinant);
+        while step1 >= alpha || step2 >= beta {
+            step1 /= 2.0;
+            step2 /= 2.0;
+        }
+        let (next1, next2) = (alpha - step1, beta - step2);
+        if step1.abs() <= TOLERANCE * next1 && step2.abs() <= TOLERANCE * next2 {
+            return Ok(Beta::try_new(next1, next2, 0.0, 1.0)?);
+        }
+        alpha = next1;
+        beta = next2;
+    }
+    Err(FitError::IterationsExhausted { iterations: ITERATIONS, estimate: [alpha, beta] })
+}
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -167,6 +452,43 @@ mod tests {
         ($alpha:expr, $beta:expr, $a:expr, $b:expr) => (Beta::new($alpha, $beta, $a, $b));
     );
 
+    #[test]
+    fn central_moment() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        assert::close(d.central_moment(1), 0.0, 1e-15);
+        assert::close(d.central_moment(2), d.variance(), 1e-15);
+        assert::close(d.central_moment(3), 0.061714285714285714286, 1e-15);
+        assert::close(d.central_moment(5), 0.14070857142857142857, 1e-14);
+        assert::close(d.central_moment(8), 0.56954251636363636364, 1e-12);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-14);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-14);
+    }
+
+    #[test]
+    fn degenerate() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = new!(2.0, 3.0, 1.5, 1.5);
+        let x = [NEG_INFINITY, 1.0, 1.5, 2.0, INFINITY];
+        assert_eq!(d.density_vec(&x), vec![0.0, 0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!(d.distribution_vec(&x), vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!((d.survival(1.0), d.survival(1.5)), (1.0, 0.0));
+        assert_eq!((d.ln_density(1.5), d.ln_density(2.0)), (INFINITY, NEG_INFINITY));
+        for &p in &[0.0, 0.25, 1.0] {
+            assert_eq!((d.inverse(p), d.inverse_survival(p)), (1.5, 1.5));
+        }
+        assert!(d.samples(&mut source::seeded(42), 10).iter().all(|&x| x == 1.5));
+        assert_eq!((d.mean(), d.median(), d.variance()), (1.5, 1.5, 0.0));
+        assert_eq!(new!(2.0, 2.0, 1.5, 1.5).median(), 1.5);
+        assert_eq!(d.entropy(), NEG_INFINITY);
+        assert_eq!(d.skewness(), new!(2.0, 3.0, 0.0, 1.0).skewness());
+        assert_eq!(d.modes(), vec![1.5]);
+        assert_eq!(new!(0.5, 0.5, 1.5, 1.5).modes(), vec![1.5]);
+        assert_eq!(d.kl_divergence(&new!(1.0, 1.0, 1.5, 1.5)), 0.0);
+        assert_eq!(d.kl_divergence(&new!(2.0, 3.0, 1.0, 2.0)), INFINITY);
+        assert_eq!(new!(2.0, 3.0, 1.0, 2.0).kl_divergence(&d), INFINITY);
+    }
+
     #[test]
     fn density() {
         let d = new!(2.0, 3.0, -1.0, 2.0);
@@ -188,6 +510,20 @@ mod tests {
         assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-14);
     }
 
+    #[test]
+    fn density_slice() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let x = vec![-2.0, -1.0, 0.0, 0.5, 2.0, 3.0];
+        assert_eq!(d.density_vec(&x), x.iter().map(|&x| d.density(x)).collect::<Vec<_>>());
+        assert_eq!(d.distribution_vec(&x),
+                   x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Beta::new(2.0, 5.0, 0.0, 1.0).to_string(), "Beta(α = 2, β = 5, a = 0, b = 1)");
+    }
+
     #[test]
     fn distribution() {
         let d = new!(2.0, 3.0, -1.0, 2.0);
@@ -220,6 +556,76 @@ mod tests {
                       &vec![0.0, 1.0, -0.2349066497879999, 0.8637056388801096], 1e-15);
     }
 
+    #[test]
+    fn fisher_information() {
+        assert::close(&new!(2.0, 3.0, -1.0, 2.0).fisher_information(), &[
+            0.42361111111111111111, -0.22132295573711532536,
+            -0.22132295573711532536, 0.17361111111111111111,
+        ], 1e-15);
+    }
+
+    #[test]
+    fn fit_mle() {
+        let d = Beta::fit_mle(&[0.12, 0.45, 0.33, 0.78, 0.56, 0.21, 0.67, 0.39]).unwrap();
+        assert::close(d.alpha(), 2.1600821123784129503, 1e-10);
+        assert::close(d.beta(), 2.7719895819342135928, 1e-10);
+        assert_eq!((d.a(), d.b()), (0.0, 1.0));
+
+        let x = new!(2.0, 5.0, 0.0, 1.0).samples(&mut source::seeded(42), 100000);
+        let d = Beta::fit_mle(&x).unwrap();
+        assert!((d.alpha() / 2.0 - 1.0).abs() < 0.02);
+        assert!((d.beta() / 5.0 - 1.0).abs() < 0.02);
+
+        let x = new!(0.1, 0.2, 0.0, 1.0).samples(&mut source::seeded(42), 1000);
+        let x = x.into_iter().filter(|&x| x > 0.0 && x < 1.0).collect::<Vec<_>>();
+        assert!(Beta::fit_mle(&x).is_ok());
+
+        assert_eq!(Beta::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Beta::fit_mle(&[0.5, 0.5]), Err(FitError::DataNotDistinct));
+        assert_eq!(Beta::fit_mle(&[0.5, 1.0]), Err(FitError::DatumOutOfSupport { value: 1.0 }));
+        assert_eq!(Beta::fit_mle(&[0.0, 0.5]), Err(FitError::DatumOutOfSupport { value: 0.0 }));
+    }
+
+    #[test]
+    fn fit_moments() {
+        let x = new!(2.0, 5.0, 0.0, 1.0).samples(&mut source::seeded(42), 1000);
+        let mean = x.iter().sum::<f64>() / 1000.0;
+        let variance = x.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / 1000.0;
+        let d = Beta::fit_moments(&x).unwrap();
+        assert::close(d.mean(), mean, 1e-12);
+        assert::close(d.variance(), variance, 1e-12);
+
+        assert_eq!(Beta::fit_moments(&[0.0, 1.0, 0.0, 1.0]),
+                   Err(FitError::MomentsInfeasible { mean: 0.5, variance: 0.25 }));
+        assert_eq!(Beta::fit_moments_from(0.5, 0.3),
+                   Err(FitError::MomentsInfeasible { mean: 0.5, variance: 0.3 }));
+        assert_eq!(Beta::fit_moments_from(1.5, 0.1),
+                   Err(FitError::MomentsInfeasible { mean: 1.5, variance: 0.1 }));
+    }
+
+    #[test]
+    fn fit_weighted_mle() {
+        let d = Beta::fit_weighted_mle(&[0.2, 0.5, 0.7, 0.9], &[2.0, 1.0, 3.0, 0.0]).unwrap();
+        let e = Beta::fit_mle(&[0.2, 0.2, 0.5, 0.7, 0.7, 0.7]).unwrap();
+        assert::close(d.alpha(), e.alpha(), 1e-12);
+        assert::close(d.beta(), e.beta(), 1e-12);
+
+        assert_eq!(Beta::fit_weighted_mle(&[0.2, 0.5], &[1.0, 2.0, 3.0]),
+                   Err(FitError::WeightsMismatch { data: 2, weights: 3 }));
+        assert_eq!(Beta::fit_weighted_mle(&[0.2, 0.5], &[0.0, 0.0]), Err(FitError::WeightsZero));
+    }
+
+    #[test]
+    fn from_mean_and_sample_size() {
+        let d = Beta::from_mean_and_sample_size(0.25, 8.0);
+        assert_eq!(d, new!(2.0, 6.0, 0.0, 1.0));
+        assert_eq!((d.mean(), d.sample_size()), (0.25, 8.0));
+        assert_eq!(Beta::try_from_mean_and_sample_size(1.0, 8.0),
+                   Err(ParameterError::MeanOutOfRange { value: 1.0 }));
+        assert_eq!(Beta::try_from_mean_and_sample_size(0.5, 0.0),
+                   Err(ParameterError::SampleSizeNotPositive { value: 0.0 }));
+    }
+
     #[test]
     fn inverse() {
         let d = new!(1.0, 2.0, 3.0, 4.0);
@@ -239,6 +645,27 @@ mod tests {
         assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x, 1e-14);
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let p = vec![0.0, 0.05, 0.5, 0.95, 1.0];
+        assert::close(&p.iter().map(|&p| d.inverse_survival(p)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| d.inverse(1.0 - p)).collect::<Vec<_>>(), 1e-12);
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.inverse_survival(3.999997e-18), 1.0 - 1e-6, 1e-12);
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let p = Beta::new(2.0, 3.0, 0.0, 1.0);
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&Beta::new(4.0, 1.5, 0.0, 1.0)), 1.4897365804287604615,
+                      1e-14);
+        assert::close(p.kl_divergence(&Beta::new(2.0, 2.0, -1.0, 2.0)), 0.95082815771050352561,
+                      1e-8);
+        assert_eq!(Beta::new(2.0, 2.0, -1.0, 2.0).kl_divergence(&p), ::std::f64::INFINITY);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).kurtosis(), -6.0 / 5.0);
@@ -246,6 +673,40 @@ mod tests {
         assert_eq!(new!(3.0, 2.0, -1.0, 2.0).kurtosis(), -0.6428571428571429);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let x = vec![-1.5, -0.5, 0.0, 0.5, 1.5, 2.5];
+        assert::close(&x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.density(x).ln()).collect::<Vec<_>>(), 1e-14);
+
+        let d = new!(2.0, 3.0, 0.0, 2.0);
+        assert::close(d.ln_density(1e-200), -459.41840631014102711, 1e-12);
+
+        let d = new!(100.0, 3.0, 0.0, 2.0);
+        assert_eq!(d.density(1e-5), 0.0);
+        assert::close(d.ln_density(1e-5), -1195.942232752518468, 1e-10);
+
+        let d = new!(1.0, 1.0, 0.0, 2.0);
+        assert_eq!(d.ln_density(0.0), -(2.0f64).ln());
+
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        for i in 1..100 {
+            let x = -1.0 + 3.0 * i as f64 / 100.0;
+            let y = (x + 1.0) / 3.0;
+            assert_eq!(d.ln_density(x), y.ln() + 2.0 * (-y).ln_1p() - d.ln_beta - (3.0f64).ln());
+        }
+    }
+
+    #[test]
+    fn log_likelihood() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let x = vec![-0.5, 0.0, 1.5];
+        assert::close(d.log_likelihood(&x), x.iter().map(|&x| d.ln_density(x)).sum::<f64>(),
+                      1e-14);
+        assert_eq!(d.log_likelihood(&[0.0, 3.0]), ::std::f64::NEG_INFINITY);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.5, 0.5, 0.0, 1.0).mean(), 0.5);
@@ -257,6 +718,7 @@ mod tests {
         assert_eq!(new!(2.0, 2.0, 0.0, 1.0).median(), 0.5);
         assert_eq!(new!(2.0, 3.0, 0.0, 1.0).median(), 5.0 / 13.0);
         assert_eq!(new!(2.0, 3.0, -1.0, 2.0).median(), 3.0 * (5.0 / 13.0) -1.0);
+        assert_eq!(new!(2.0, 2.0, -1.0, 2.0).median(), 0.5);
     }
 
     #[test]
@@ -276,13 +738,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn moment() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        assert_eq!(d.moment(0), 1.0);
+        assert::close(d.moment(1), 0.2, 1e-15);
+        assert::close(d.moment(3), 0.28571428571428571429, 1e-15);
+        assert::close(d.moment(8), 1.5454545454545454545, 1e-12);
+    }
+
     #[test]
     fn sample() {
-        for x in Independent(&new!(1.0, 2.0, 7.0, 42.0), &mut source::default()).take(100) {
+        for x in Independent(&new!(1.0, 2.0, 7.0, 42.0), &mut source::seeded(42)).take(100) {
             assert!(7.0 <= x && x <= 42.0);
         }
     }
 
+    #[test]
+    fn sample_size() {
+        assert_eq!(new!(2.0, 3.0, -1.0, 2.0).sample_size(), 5.0);
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).skewness(), 0.0);
@@ -290,6 +766,37 @@ mod tests {
         assert_eq!(new!(3.0, 2.0, -1.0, 2.0).skewness(), -0.28571428571428575);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let x = vec![-1.5, -1.0, -0.25, 0.5, 1.25, 2.0, 2.5];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| 1.0 - d.distribution(x)).collect::<Vec<_>>(), 1e-14);
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.survival(1.0 - 1e-6) / 3.999997e-18, 1.0, 1e-8);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Beta::try_new(2.0, 3.0, -1.0, 2.0).is_ok());
+        assert!(Beta::try_new(2.0, 3.0, 2.0, 2.0).is_ok());
+        assert_eq!(Beta::try_new(0.0, 3.0, -1.0, 2.0).err(),
+                   Some(ParameterError::AlphaNotPositive { value: 0.0 }));
+        assert_eq!(Beta::try_new(INFINITY, 3.0, -1.0, 2.0).err(),
+                   Some(ParameterError::AlphaNotPositive { value: INFINITY }));
+        assert_eq!(Beta::try_new(2.0, -3.0, -1.0, 2.0).err(),
+                   Some(ParameterError::BetaNotPositive { value: -3.0 }));
+        assert_eq!(Beta::try_new(2.0, 3.0, 2.0, -1.0).err(),
+                   Some(ParameterError::BoundsNotOrdered { a: 2.0, b: -1.0 }));
+        assert_eq!(Beta::try_new(2.0, 3.0, -1.0, INFINITY).err(),
+                   Some(ParameterError::BoundsNotOrdered { a: -1.0, b: INFINITY }));
+        assert!(Beta::try_new(NAN, 3.0, -1.0, 2.0).is_err());
+        assert!(Beta::try_new(2.0, NAN, -1.0, 2.0).is_err());
+        assert!(Beta::try_new(2.0, 3.0, NAN, 2.0).is_err());
+        assert!(Beta::try_new(2.0, 3.0, -1.0, NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).variance(), 1.0 / 12.0);
@@ -0,0 +1,238 @@
+use std::fmt;
+
+use distribution;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Float;
+
+/// A beta-binomial distribution.
+///
+/// The distribution is that of the number of successes in `n` trials whose
+/// success probability follows a beta distribution with shape parameters
+/// `alpha` and `beta`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BetaBinomial {
+    n: usize,
+    alpha: f64,
+    beta: f64,
+    ln_beta: f64,
+}
+
+impl BetaBinomial {
+    /// Create a beta-binomial distribution with `n` trials and shape
+    /// parameters `alpha` and `beta`.
+    ///
+    /// It should hold that `alpha > 0` and `beta > 0`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn new(n: usize, alpha: f64, beta: f64) -> Self {
+        unwrap!(BetaBinomial::try_new(n, alpha, beta))
+    }
+
+    /// Create a beta-binomial distribution with `n` trials and shape
+    /// parameters `alpha` and `beta` or return an error if the parameters are
+    /// invalid.
+    pub fn try_new(n: usize, alpha: f64, beta: f64) -> Result<Self, ParameterError> {
+        use special::Beta;
+        ensure!(alpha > 0.0 && alpha.is_finite(),
+                ParameterError::AlphaNotPositive { value: alpha });
+        ensure!(beta > 0.0 && beta.is_finite(), ParameterError::BetaNotPositive { value: beta });
+        Ok(BetaBinomial { n: n, alpha: alpha, beta: beta, ln_beta: alpha.ln_beta(beta) })
+    }
+
+    /// Return the number of trials.
+    #[inline(always)]
+    pub fn n(&self) -> usize { self.n }
+
+    /// Return the first shape parameter.
+    #[inline(always)]
+    pub fn alpha(&self) -> f64 { self.alpha }
+
+    /// Return the second shape parameter.
+    #[inline(always)]
+    pub fn beta(&self) -> f64 { self.beta }
+}
+
+impl fmt::Display for BetaBinomial {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "BetaBinomial(n = {}, α = {}, β = {})", self.n, self.alpha, self.beta)
+    }
+}
+
+impl distribution::Discrete for BetaBinomial {
+    #[inline]
+    fn mass(&self, x: usize) -> f64 {
+        self.ln_mass(x).exp()
+    }
+
+    fn ln_mass(&self, x: usize) -> f64 {
+        use special::{Beta, Gamma};
+        if x > self.n {
+            return ::std::f64::NEG_INFINITY;
+        }
+        let (n, k) = (self.n as f64, x as f64);
+        Gamma::ln_gamma(n + 1.0).0 - Gamma::ln_gamma(k + 1.0).0 -
+            Gamma::ln_gamma(n - k + 1.0).0 +
+            (k + self.alpha).ln_beta(n - k + self.beta) - self.ln_beta
+    }
+}
+
+impl distribution::Distribution for BetaBinomial {
+    type Value = usize;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The probability mass function is summed up over the outcomes not
+    /// exceeding `x`.
+    fn distribution(&self, x: f64) -> f64 {
+        use distribution::Discrete;
+        guard!(distribution(x));
+        if x < 0.0 {
+            return 0.0;
+        }
+        if x >= self.n as f64 {
+            return 1.0;
+        }
+        (0..(x as usize + 1)).map(|k| self.mass(k)).sum::<f64>().min(1.0)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, self.n as f64)
+    }
+
+    /// Compute the survival function.
+    ///
+    /// The probability mass function is summed up over the outcomes exceeding
+    /// `x`, which avoids cancellation.
+    fn survival(&self, x: f64) -> f64 {
+        use distribution::Discrete;
+        guard!(survival(x));
+        if x < 0.0 {
+            return 1.0;
+        }
+        if x >= self.n as f64 {
+            return 0.0;
+        }
+        ((x as usize + 1)..(self.n + 1)).map(|k| self.mass(k)).sum::<f64>().min(1.0)
+    }
+}
+
+impl distribution::Inverse for BetaBinomial {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The summation of the probability mass function is utilized, and the
+    /// result is then adjusted to be the smallest `k` such that
+    /// `distribution(k) >= p`.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::{search, Discrete};
+
+        guard!(inverse(p));
+
+        let mut x = 0;
+        let mut sum = self.mass(0);
+        while sum < p && x < self.n {
+            x += 1;
+            sum += self.mass(x);
+        }
+        search(self, p, x, self.n)
+    }
+}
+
+impl distribution::Mean for BetaBinomial {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.alpha / (self.alpha + self.beta)
+    }
+}
+
+impl distribution::Sample for BetaBinomial {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> usize where S: Source {
+        use distribution::Inverse;
+        self.inverse(source.read::<f64>())
+    }
+}
+
+impl distribution::Variance for BetaBinomial {
+    fn variance(&self) -> f64 {
+        let (n, a, b) = (self.n as f64, self.alpha, self.beta);
+        let s = a + b;
+        n * a * b * (s + n) / (s * s * (s + 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($n:expr, $alpha:expr, $beta:expr) => (BetaBinomial::new($n, $alpha, $beta));
+    );
+
+    #[test]
+    fn compound() {
+        let d = new!(10, 2.0, 3.0);
+        let e = Compound::new(Beta::new(2.0, 3.0, 0.0, 1.0), |p| Binomial::new(10, p));
+        for k in 0..11 {
+            assert::close(d.mass(k), e.mass(k), 1e-8);
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(new!(10, 2.0, 3.0).to_string(), "BetaBinomial(n = 10, α = 2, β = 3)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(10, 2.0, 3.0);
+        let x = vec![-1.0, 0.0, 3.5, 7.0, 10.0];
+        let p = vec![0.0, 0.065934065934065934066, 0.45454545454545454545,
+                     0.90509490509490509491, 1.0];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-14);
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| 1.0 - p).collect::<Vec<_>>(), 1e-14);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(10, 2.0, 3.0);
+        for k in 0..10 {
+            let p = d.distribution(k as f64);
+            assert_eq!(d.inverse(p), k);
+            assert_eq!(d.inverse(p + 1e-12), k + 1);
+        }
+        assert_eq!((d.inverse(0.0), d.inverse(1.0)), (0, 10));
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(10, 2.0, 3.0);
+        let p = vec![
+            0.065934065934065934066, 0.10989010989010989011, 0.13486513486513486513,
+            0.14385614385614385614, 0.13986013986013986014, 0.12587412587412587413,
+            0.1048951048951048951, 0.07992007992007992008, 0.053946053946053946054,
+            0.02997002997002997003, 0.010989010989010989011,
+        ];
+        assert::close(&(0..11).map(|k| d.mass(k)).collect::<Vec<_>>(), &p, 1e-14);
+        assert_eq!(d.mass(11), 0.0);
+    }
+
+    #[test]
+    fn moments() {
+        let d = new!(10, 2.0, 3.0);
+        assert_eq!(d.mean(), 4.0);
+        assert::close(d.variance(), 6.0, 1e-14);
+    }
+
+    #[test]
+    fn sample() {
+        let x = new!(10, 2.0, 3.0).samples(&mut source::seeded(42), 100000);
+        let mean = x.iter().sum::<usize>() as f64 / 100000.0;
+        assert!((mean - 4.0).abs() < 0.03);
+        assert!(x.iter().all(|&x| x <= 10));
+    }
+}
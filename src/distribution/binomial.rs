@@ -1,8 +1,17 @@
+use std::{fmt, ops};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
 use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
 use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// A binomial distribution.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Binomial {
     n: usize,
     p: f64,
@@ -13,19 +22,26 @@ pub struct Binomial {
 }
 
 impl Binomial {
-    /// Create a binomial distribution with `n` trails and success probability
+    /// Create a binomial distribution with `n` trials and success probability
     /// `p`.
     ///
-    /// It should hold that `p >= 0` and `p <= 1`.
+    /// It should hold that `p > 0` and `p < 1`; the function panics otherwise.
+    #[inline]
     pub fn new(n: usize, p: f64) -> Self {
-        should!(0.0 < p && p < 1.0);
+        unwrap!(Binomial::try_new(n, p))
+    }
+
+    /// Create a binomial distribution with `n` trials and success probability
+    /// `p` or return an error if the parameters are invalid.
+    pub fn try_new(n: usize, p: f64) -> Result<Self, ParameterError> {
+        ensure!(0.0 < p && p < 1.0, ParameterError::ProbabilityOutOfRange { value: p });
         let q = 1.0 - p;
         let np = n as f64 * p;
         let nq = n as f64 * q;
-        Binomial { n: n, p: p, q: q, np: np, nq: nq, npq: np * q }
+        Ok(Binomial { n: n, p: p, q: q, np: np, nq: nq, npq: np * q })
     }
 
-    /// Create a binomial distribution with `n` trails and failure probability
+    /// Create a binomial distribution with `n` trials and failure probability
     /// `q`.
     ///
     /// It should hold that if `q >= 0` or `q <= 1`. This constructor is
@@ -51,6 +67,26 @@ impl Binomial {
     pub fn q(&self) -> f64 { self.q }
 }
 
+impl ops::Add for Binomial {
+    type Output = Self;
+
+    /// Compute the distribution of the sum of two independent variables.
+    ///
+    /// It should hold that the success probabilities are equal.
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        should!(self.p == other.p);
+        Binomial { n: self.n + other.n, p: self.p, q: self.q, np: self.np + other.np,
+                   nq: self.nq + other.nq, npq: self.npq + other.npq }
+    }
+}
+
+impl fmt::Display for Binomial {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Binomial(n = {}, p = {})", self.n, self.p)
+    }
+}
+
 impl distribution::Discrete for Binomial {
     /// Compute the probability mass function.
     ///
@@ -64,6 +100,9 @@ impl distribution::Discrete for Binomial {
     fn mass(&self, x: usize) -> f64 {
         use std::f64::consts::PI;
 
+        if x > self.n {
+            return 0.0;
+        }
         if self.p == 0.0 {
             return if x == 0 { 1.0 } else { 0.0 };
         }
@@ -84,6 +123,33 @@ impl distribution::Discrete for Binomial {
             ln_c.exp() * (n / (2.0 * PI * x * (n_m_x))).sqrt()
         }
     }
+
+    fn ln_mass(&self, x: usize) -> f64 {
+        use std::f64::consts::PI;
+        use std::f64::NEG_INFINITY;
+
+        if x > self.n {
+            return NEG_INFINITY;
+        }
+        if self.p == 0.0 {
+            return if x == 0 { 0.0 } else { NEG_INFINITY };
+        }
+        if self.p == 1.0 {
+            return if x == self.n { 0.0 } else { NEG_INFINITY };
+        }
+
+        let n = self.n as f64;
+        if x == 0 {
+            n * self.q.ln()
+        } else if x == self.n {
+            n * self.p.ln()
+        } else {
+            let x = x as f64;
+            let n_m_x = n - x;
+            stirlerr(n) - stirlerr(x) - stirlerr(n_m_x) - ln_d0(x, self.np) -
+                ln_d0(n_m_x, self.nq) + 0.5 * (n / (2.0 * PI * x * (n_m_x))).ln()
+        }
+    }
 }
 
 impl distribution::Distribution for Binomial {
@@ -93,7 +159,8 @@ impl distribution::Distribution for Binomial {
     ///
     /// The implementation is based on the incomplete beta function.
     fn distribution(&self, x: f64) -> f64 {
-        use special::Beta;
+        use math::special;
+        guard!(distribution(x));
         if x < 0.0 {
             return 0.0;
         }
@@ -105,7 +172,43 @@ impl distribution::Distribution for Binomial {
             return 1.0;
         }
         let (p, q) = ((self.n - x) as f64, (x + 1) as f64);
-        self.q.inc_beta(p, q, p.ln_beta(q))
+        special::beta_inc(p, q, self.q)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, self.n as f64)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(survival(x));
+        if x < 0.0 {
+            return 1.0;
+        }
+        let x = x as usize;
+        if x >= self.n {
+            return 0.0;
+        }
+        let (p, q) = ((x + 1) as f64, (self.n - x) as f64);
+        special::beta_inc(p, q, self.p)
+    }
+}
+
+impl distribution::Divergence for Binomial {
+    /// Compute the Kullback–Leibler divergence.
+    ///
+    /// A closed form is used when the numbers of trials coincide, and the
+    /// divergence is computed numerically otherwise.
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        use distribution::divergence;
+        if self.n > other.n {
+            return ::std::f64::INFINITY;
+        }
+        if self.n != other.n {
+            return divergence::discrete(self, other);
+        }
+        self.n as f64 * (self.p * (self.p / other.p).ln() + self.q * (self.q / other.q).ln())
     }
 }
 
@@ -123,12 +226,24 @@ impl distribution::Entropy for Binomial {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Binomial {
+    /// Compute the Fisher information matrix with respect to `p`.
+    ///
+    /// The information is infinite when `p` is zero or one.
+    #[inline]
+    fn fisher_information(&self) -> Vec<f64> {
+        vec![self.n as f64 / (self.p * self.q)]
+    }
+}
+
 impl distribution::Inverse for Binomial {
     /// Compute the inverse of the cumulative distribution function.
     ///
-    /// For small `n`, a simple summation is utilized. For large `n` and large
-    /// variances, a normal asymptotic approximation is used. Otherwise, the
-    /// Newton method is employed.
+    /// The result is the smallest `k` such that `distribution(k) >= p`, which
+    /// is found by a search starting from an approximation. For large
+    /// variances, the approximation is the asymptotic expansion due to
+    /// Moorhead; otherwise, it is the Cornish–Fisher approximation.
     ///
     /// ## References
     ///
@@ -136,67 +251,22 @@ impl distribution::Inverse for Binomial {
     ///    distribution function where the number of trials is large,” Oxford
     ///    University, 2013.
     fn inverse(&self, p: f64) -> usize {
-        use distribution::{Distribution, Discrete, Modes};
-
-        should!(0.0 <= p && p <= 1.0);
-
-        // Rename p as to not be confused with self.p.
-        let u = p;
-
-        macro_rules! buttom_up_sum(
-            ($prod_term: expr) => ({
-                let mut k = 1;
-                let mut a = self.q.powi(self.n as i32);
-                let mut sum = a - u;
-                while sum < 0.0 {
-                    a *= $prod_term(k);
-                    sum += a;
-                    k += 1;
-                }
-                k - 1
-            });
-        );
-        macro_rules! top_down_sum(
-            ($prod_term: expr) => ({
-                let mut k = 1;
-                let mut a = self.p.powi(self.n as i32);
-                let mut sum = (1.0 - u) - a;
-                while sum >= 0.0 {
-                    a *= $prod_term(k);
-                    sum -= a;
-                    k += 1;
-                }
-                self.n - k + 1
-            });
-        );
-
-        if u == 1.0 {
-            self.n
-        } else if u == 0.0 {
-            0
-        } else if self.n < 1000 {
-            // Find if top-down or bottom-up summation is better.
-            if u <= self.distribution((self.n / 2) as f64) {
-                buttom_up_sum!(|k| self.p / self.q * ((self.n - k + 1) as f64 / k as f64))
-            } else {
-                top_down_sum!(|k| self.q / self.p * ((self.n - k + 1) as f64 / k as f64))
-            }
-        } else if self.npq > 80.0 {
-            // Use a normal approximation.
-            approximate_by_normal(self.p, self.np, self.npq, u).floor() as usize
-        } else {
-            // Use the Newton method starting at the mode.
-            let modes = self.modes();
-            let mut m = modes[0];
-            loop {
-                let next = (u - self.distribution(m as f64)) / self.mass(m);
-                if -0.5 < next && next < 0.5 {
-                    break;
-                }
-                m = (m as isize + next.round() as isize) as usize;
-            }
-            m
+        use distribution::{guess, search, Skewness};
+
+        guard!(inverse(p));
+
+        if p == 1.0 {
+            return self.n;
+        } else if p == 0.0 {
+            return 0;
         }
+        let k = if self.npq > 80.0 {
+            let x = approximate_by_normal(self.p, self.np, self.npq, p).floor();
+            if x > 0.0 { (x as usize).min(self.n) } else { 0 }
+        } else {
+            guess(p, self.np, self.npq.sqrt(), self.skewness(), self.n)
+        };
+        search(self, p, k, self.n)
     }
 }
 
@@ -233,17 +303,44 @@ impl distribution::Median for Binomial {
     }
 }
 
+impl distribution::Mgf for Binomial {
+    #[inline]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        use distribution::bernoulli::ln_mgf;
+        self.n as f64 * ln_mgf(self.p, self.q, t)
+    }
+
+    #[inline]
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        use distribution::bernoulli::tilt;
+        self.n as f64 * tilt(self.p, self.q, t)
+    }
+
+    #[inline]
+    fn ln_mgf_second_derivative(&self, t: f64) -> f64 {
+        use distribution::bernoulli::tilt;
+        let p = tilt(self.p, self.q, t);
+        self.n as f64 * p * (1.0 - p)
+    }
+
+    #[inline(always)]
+    fn lattice(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl distribution::Modes for Binomial {
-    fn modes(&self) -> Vec<usize> {
+    fn modes(&self) -> ModeList<usize> {
         let r = self.p * (self.n + 1) as f64;
         if r == 0.0 {
-            vec![0]
+            ModeList::one(0)
         } else if self.p == 1.0 {
-            vec![self.n]
+            ModeList::one(self.n)
         } else if r.fract() != 0.0 {
-            vec![r.floor() as usize]
+            ModeList::one(r.floor() as usize)
         } else {
-            vec![r as usize - 1, r as usize]
+            ModeList::two(r as usize - 1, r as usize)
         }
     }
 }
@@ -325,7 +422,7 @@ fn stirlerr(n: f64) -> f64 {
         2.079067210376509311e-02, 1.664469118982119216e-02,
         1.387612882307074800e-02, 1.189670994589177010e-02,
         1.041126526197209650e-02, 9.255462182712732918e-03,
-        8.330563433362871256e-03, 7.757367548795184079e-03,
+        8.330563433362871256e-03, 7.573675487951840795e-03,
         6.942840107209529866e-03, 6.408994188004207068e-03,
         5.951370112758847736e-03, 5.554733551962801371e-03,
     ];
@@ -377,6 +474,24 @@ mod tests {
         ($n:expr, $p:expr) => (Binomial::new($n, $p));
     }
 
+    #[test]
+    fn add() {
+        let (a, b) = (new!(3, 0.25), new!(5, 0.25));
+        let d = a + b;
+        assert_eq!((d.n(), d.p()), (8, 0.25));
+        let p = (0..9).map(|x| {
+            (0..(x + 1)).filter(|&i| i <= 3 && x - i <= 5).fold(0.0, |sum, i| {
+                sum + a.mass(i) * b.mass(x - i)
+            })
+        }).collect::<Vec<_>>();
+        assert::close(&(0..9).map(|x| d.mass(x)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Binomial::new(16, 0.25).to_string(), "Binomial(n = 16, p = 0.25)");
+    }
+
     #[test]
     fn distribution() {
         let d = new!(16, 0.75);
@@ -396,10 +511,16 @@ mod tests {
 
     #[test]
     fn entropy() {
-        assert_eq!(new!(16, 0.25).entropy(), 1.9588018945068573);
+        assert::close(new!(16, 0.25).entropy(), 1.9588258531430700645, 1e-14);
         assert_eq!(new!(10_000_000, 0.5).entropy(), 8.784839178123887);
     }
 
+    #[test]
+    fn fisher_information() {
+        assert_eq!(new!(16, 0.5).fisher_information(), vec![64.0]);
+        assert::close(new!(16, 0.25).fisher_information()[0], 256.0 / 3.0, 1e-13);
+    }
+
     #[test]
     fn inverse() {
         let d = Binomial::new(250, 0.55);
@@ -413,8 +534,58 @@ mod tests {
         assert_eq!(new!(1001, 0.25).inverse(0.5), 250);
         assert_eq!(new!(1500, 0.15).inverse(0.2), 213);
 
-        assert_eq!(new!(1_000_000, 2.5e-5).inverse(0.9995), 42);
-        assert_eq!(new!(1_000_000_000, 6.66e-9).inverse(0.8), 8);
+        assert_eq!(new!(1_000_000, 2.5e-5).inverse(0.9995), 43);
+        assert_eq!(new!(1_000_000_000, 6.66e-9).inverse(0.8), 9);
+
+        let d = new!(10, 0.5);
+        let p = d.distribution(4.0);
+        assert_eq!(d.inverse(p), 4);
+        assert_eq!(d.inverse(p + 1e-12), 5);
+        assert_eq!(d.inverse(d.distribution(3.0) + 1e-12), 4);
+
+        for &(n, p) in &[(10, 0.5), (250, 0.55), (1500, 0.15), (100_000, 0.3)] {
+            let d = new!(n, p);
+            for &u in &[1e-6, 0.01, 0.3, 0.5, 0.7, 0.99, 1.0 - 1e-6] {
+                let k = d.inverse(u);
+                assert!(d.distribution(k as f64) >= u);
+                assert!(k == 0 || d.distribution((k - 1) as f64) < u);
+            }
+        }
+
+        for &(n, p) in &[(1, 0.3), (12, 0.05), (40, 0.5), (60, 0.9)] {
+            let d = new!(n, p);
+            for i in 1..1000 {
+                let p = i as f64 / 1000.0;
+                assert_eq!(d.inverse(p), (0..).find(|&k| d.distribution(k as f64) >= p).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_boundary() {
+        let d = new!(1, 0.55);
+        let p = d.distribution(0.0);
+        assert_eq!(d.inverse(f64::from_bits(p.to_bits() + 1)), 1);
+        assert_eq!(d.inverse(1.0 - 1e-16), 1);
+    }
+
+    #[test]
+    fn inverse_slice() {
+        let d = new!(16, 0.25);
+        let p = vec![0.0, 0.1, 0.5, 0.9, 1.0];
+        let mut x = vec![0; p.len()];
+        d.inverse_slice(&p, &mut x);
+        assert_eq!(x, p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>());
+        assert_eq!(d.inverse_vec(&p), x);
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let p = new!(10, 0.3);
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&new!(10, 0.6)), 10.0 * 0.18378689738681218714, 1e-14);
+        assert::close(p.kl_divergence(&new!(12, 0.4)), 0.59961922003171620710, 1e-14);
+        assert_eq!(new!(12, 0.4).kl_divergence(&p), ::std::f64::INFINITY);
     }
 
     #[test]
@@ -422,6 +593,27 @@ mod tests {
         assert_eq!(new!(16, 0.25).kurtosis(), -0.041666666666666664);
     }
 
+    #[test]
+    fn ln_mass() {
+        let d = new!(20, 0.3);
+        assert::close(&(0..21).map(|x| d.ln_mass(x)).collect::<Vec<_>>(),
+                      &(0..21).map(|x| d.mass(x).ln()).collect::<Vec<_>>(), 1e-12);
+        assert::close(d.ln_mass(7), -1.8062926549204250303, 1e-14);
+        assert_eq!(d.ln_mass(21), ::std::f64::NEG_INFINITY);
+
+        assert_eq!(new!(10000, 0.5).mass(0), 0.0);
+        assert::close(new!(10000, 0.5).ln_mass(0), -6931.4718055994530942, 1e-10);
+        assert::close(new!(10000, 0.4).ln_mass(100), -4592.0047367952525524, 1e-10);
+    }
+
+    #[test]
+    fn log_likelihood() {
+        let d = new!(16, 0.25);
+        let x = vec![0, 4, 4, 16];
+        assert::close(d.log_likelihood(&x), x.iter().map(|&x| d.ln_mass(x)).sum::<f64>(), 1e-14);
+        assert_eq!(d.log_likelihood(&[]), 0.0);
+    }
+
     #[test]
     fn mass() {
         let d = new!(16, 0.25);
@@ -460,6 +652,26 @@ mod tests {
         assert_eq!(new!(16, 0.25).skewness(), 0.2886751345948129);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(20, 0.3);
+        let x = vec![-1.0, 0.0, 2.5, 6.0, 10.0, 20.0, 21.0];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| 1.0 - d.distribution(x)).collect::<Vec<_>>(), 1e-14);
+        assert::close(d.survival(18.0) / 1.66203389781e-9, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::NAN;
+        assert!(Binomial::try_new(16, 0.25).is_ok());
+        for &p in &[0.0, 1.0, -0.5, 1.5] {
+            assert_eq!(Binomial::try_new(16, p).err(),
+                       Some(ParameterError::ProbabilityOutOfRange { value: p }));
+        }
+        assert!(Binomial::try_new(16, NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(16, 0.25).variance(), 3.0);
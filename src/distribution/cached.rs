@@ -0,0 +1,181 @@
+use std::cell::Cell;
+use std::fmt;
+
+use distribution;
+use source::Source;
+
+/// A discrete distribution that remembers its last quantile.
+///
+/// The first inverse of the distribution function is computed by the inner
+/// distribution, and every later one is found by a search starting from the
+/// previous result, which takes a number of evaluations of the distribution
+/// function logarithmic in the distance between the two outcomes. Repeated
+/// nearby queries, which are common when plotting or when inverting sorted
+/// probabilities, thus cost a few evaluations each. The last result is kept in
+/// a `Cell`, so the distribution is not `Sync`, and each thread should use its
+/// own copy.
+#[derive(Clone, Debug)]
+pub struct Cached<T> {
+    inner: T,
+    last: Cell<Option<usize>>,
+}
+
+impl<T> Cached<T> where T: distribution::Inverse<Value=usize> {
+    /// Wrap distribution `inner`.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        Cached { inner: inner, last: Cell::new(None) }
+    }
+
+    /// Return the inner distribution.
+    #[inline(always)]
+    pub fn inner(&self) -> &T { &self.inner }
+}
+
+impl<T> fmt::Display for Cached<T> where T: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Cached({})", self.inner)
+    }
+}
+
+impl<T> distribution::Discrete for Cached<T> where T: distribution::Discrete<Value=usize> {
+    #[inline]
+    fn mass(&self, x: usize) -> f64 {
+        self.inner.mass(x)
+    }
+
+    #[inline]
+    fn ln_mass(&self, x: usize) -> f64 {
+        self.inner.ln_mass(x)
+    }
+}
+
+impl<T> distribution::Distribution for Cached<T> where T: distribution::Distribution {
+    type Value = T::Value;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        self.inner.distribution(x)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        self.inner.support()
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        self.inner.survival(x)
+    }
+}
+
+impl<T> distribution::Inverse for Cached<T> where T: distribution::Inverse<Value=usize> {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The result is the smallest `k` such that `distribution(k) >= p`. The
+    /// probabilities zero and one are passed to the inner distribution, which
+    /// defines the results for them.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::search;
+        use std::usize::MAX;
+
+        guard!(inverse(p));
+        if p == 0.0 || p == 1.0 {
+            return self.inner.inverse(p);
+        }
+        let k = match self.last.get() {
+            Some(k) => {
+                let limit = self.inner.support().1;
+                search(&self.inner, p, k, if limit < MAX as f64 { limit as usize } else { MAX })
+            },
+            _ => self.inner.inverse(p),
+        };
+        self.last.set(Some(k));
+        k
+    }
+}
+
+impl<T> distribution::Mean for Cached<T> where T: distribution::Mean {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.inner.mean()
+    }
+}
+
+impl<T> distribution::Sample for Cached<T> where T: distribution::Sample {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> T::Value where S: Source {
+        self.inner.sample(source)
+    }
+}
+
+impl<T> distribution::Variance for Cached<T> where T: distribution::Variance {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.inner.variance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use prelude::*;
+
+    struct Counted(Poisson, Cell<usize>);
+
+    impl Distribution for Counted {
+        type Value = usize;
+
+        fn distribution(&self, x: f64) -> f64 {
+            self.1.set(self.1.get() + 1);
+            self.0.distribution(x)
+        }
+
+        fn support(&self) -> (f64, f64) {
+            self.0.support()
+        }
+    }
+
+    impl Inverse for Counted {
+        fn inverse(&self, p: f64) -> usize {
+            self.0.inverse(p)
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Cached::new(Poisson::new(2.0)).to_string(), "Cached(Poisson(λ = 2))");
+    }
+
+    #[test]
+    fn inverse() {
+        let d = Poisson::new(1e6);
+        let e = Cached::new(Counted(d, Cell::new(0)));
+        assert_eq!(e.inverse(0.5), d.inverse(0.5));
+        for i in 1..100 {
+            let p = 0.5 + i as f64 * 1e-5;
+            e.inner().1.set(0);
+            assert_eq!(e.inverse(p), d.inverse(p));
+            assert!(e.inner().1.get() <= 4);
+        }
+        for &p in &[0.0, 1e-12, 0.25, 0.99, 1.0 - 1e-12, 1.0] {
+            assert_eq!(e.inverse(p), d.inverse(p));
+        }
+
+        let d = Binomial::new(20, 0.3);
+        let e = Cached::new(d);
+        for i in (0..101).rev() {
+            let p = i as f64 / 100.0;
+            assert_eq!(e.inverse(p), d.inverse(p));
+        }
+    }
+
+    #[test]
+    fn mass() {
+        let d = Cached::new(Poisson::new(2.0));
+        assert_eq!(d.mass(3), Poisson::new(2.0).mass(3));
+        assert_eq!(d.distribution(3.0), Poisson::new(2.0).distribution(3.0));
+        assert_eq!((d.mean(), d.variance()), (2.0, 2.0));
+    }
+}
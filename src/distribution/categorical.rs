@@ -1,8 +1,16 @@
-use distribution;
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{self, ModeList};
+use math;
 use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// A categorical distribution.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Categorical {
     k: usize,
     p: Vec<f64>,
@@ -12,13 +20,25 @@ pub struct Categorical {
 impl Categorical {
     /// Create a categorical distribution with success probability `p`.
     ///
-    /// It should hold that `p[i] >= 0`, `p[i] <= 1`, and `sum(p) == 1`.
+    /// It should hold that `p` is not empty, `p[i] >= 0`, `p[i] <= 1`, and
+    /// `sum(p) == 1`; the function panics otherwise.
+    #[inline]
     pub fn new(p: &[f64]) -> Self {
-        should!(is_probability_vector(p), {
-            const EPSILON: f64 = 1e-12;
-            p.iter().all(|&p| p >= 0.0 && p <= 1.0) &&
-                (p.iter().fold(0.0, |sum, &p| sum + p) - 1.0).abs() < EPSILON
-        });
+        unwrap!(Categorical::try_new(p))
+    }
+
+    /// Create a categorical distribution with success probability `p` or
+    /// return an error if the parameters are invalid.
+    pub fn try_new(p: &[f64]) -> Result<Self, ParameterError> {
+        const EPSILON: f64 = 1e-12;
+
+        ensure!(!p.is_empty(), ParameterError::ProbabilitiesEmpty);
+        for &p in p {
+            ensure!(p >= 0.0 && p <= 1.0, ParameterError::ProbabilityOutOfRange { value: p });
+        }
+        let sum = p.iter().fold(0.0, |sum, &p| sum + p);
+        ensure!((sum - 1.0).abs() < EPSILON,
+                ParameterError::ProbabilitiesNotNormalized { sum: sum });
 
         let k = p.len();
         let mut cumsum = p.to_vec();
@@ -26,7 +46,37 @@ impl Categorical {
             cumsum[i] += cumsum[i - 1];
         }
         cumsum[k - 1] = 1.0;
-        Categorical { k: k, p: p.to_vec(), cumsum: cumsum }
+        Ok(Categorical { k: k, p: p.to_vec(), cumsum: cumsum })
+    }
+
+    /// Create a categorical distribution with probabilities proportional to
+    /// the exponentials of `ln_weights`.
+    ///
+    /// The weights are normalized in the log domain, so they may be too large
+    /// or too small to be represented themselves. It should hold that
+    /// `ln_weights` is not empty, its elements are below positive infinity,
+    /// and at least one of them is above negative infinity; the function
+    /// panics otherwise.
+    #[inline]
+    pub fn from_ln_weights(ln_weights: &[f64]) -> Self {
+        unwrap!(Categorical::try_from_ln_weights(ln_weights))
+    }
+
+    /// Create a categorical distribution with probabilities proportional to
+    /// the exponentials of `ln_weights` or return an error if the weights are
+    /// invalid.
+    pub fn try_from_ln_weights(ln_weights: &[f64]) -> Result<Self, ParameterError> {
+        ensure!(!ln_weights.is_empty(), ParameterError::ProbabilitiesEmpty);
+        for &w in ln_weights {
+            ensure!(w < ::std::f64::INFINITY,
+                    ParameterError::WeightNotNonnegative { value: w.exp() });
+        }
+        let max = ln_weights.iter().cloned().fold(::std::f64::NEG_INFINITY, f64::max);
+        ensure!(max > ::std::f64::NEG_INFINITY, ParameterError::WeightsZero);
+        let shifted = ln_weights.iter().map(|&w| w - max).collect::<Vec<_>>();
+        let ln_total = math::log_sum_exp(&shifted);
+        let p = shifted.iter().map(|&w| (w - ln_total).exp().min(1.0)).collect::<Vec<_>>();
+        Categorical::try_new(&p)
     }
 
     /// Return the number of categories.
@@ -38,6 +88,12 @@ impl Categorical {
     pub fn p(&self) -> &[f64] { &self.p }
 }
 
+impl fmt::Display for Categorical {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Categorical(p = {:?})", self.p)
+    }
+}
+
 impl distribution::Discrete for Categorical {
     #[inline]
     fn mass(&self, x: usize) -> f64 {
@@ -50,6 +106,7 @@ impl distribution::Distribution for Categorical {
     type Value = usize;
 
     fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
         if x < 0.0 {
             return 0.0;
         }
@@ -59,6 +116,20 @@ impl distribution::Distribution for Categorical {
         }
         self.cumsum[x]
     }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, (self.k - 1) as f64)
+    }
+}
+
+impl distribution::Divergence for Categorical {
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        self.p.iter().enumerate().filter(|&(_, &p)| p > 0.0).fold(0.0, |sum, (i, &p)| {
+            let q = if i < other.k { other.p[i] } else { 0.0 };
+            sum + p * (p / q).ln()
+        })
+    }
 }
 
 impl distribution::Entropy for Categorical {
@@ -69,7 +140,7 @@ impl distribution::Entropy for Categorical {
 
 impl distribution::Inverse for Categorical {
     fn inverse(&self, p: f64) -> usize {
-        should!(0.0 <= p && p <= 1.0);
+        guard!(inverse(p));
         self.cumsum.iter().position(|&sum| sum > 0.0 && sum >= p).unwrap_or_else(|| {
             self.p.iter().rposition(|&p| p > 0.0).unwrap()
         })
@@ -113,8 +184,8 @@ impl distribution::Median for Categorical {
 }
 
 impl distribution::Modes for Categorical {
-    fn modes(&self) -> Vec<usize> {
-        let mut modes = Vec::new();
+    fn modes(&self) -> ModeList<usize> {
+        let mut modes = ModeList::new();
         let mut max = 0.0;
         for (i, &p) in self.p.iter().enumerate() {
             if p == max {
@@ -122,7 +193,7 @@ impl distribution::Modes for Categorical {
             }
             if p > max {
                 max = p;
-                modes = vec![i];
+                modes = ModeList::one(i);
             }
         }
         modes
@@ -160,13 +231,20 @@ impl distribution::Variance for Categorical {
 
 #[cfg(test)]
 mod tests {
+    use assert;
     use prelude::*;
 
     macro_rules! new(
         (equal $k:expr) => { Categorical::new(&[1.0 / $k as f64; $k]) };
-        ($p:expr) => { Categorical::new(&$p); }
+        ($p:expr) => { Categorical::new(&$p) }
     );
 
+    #[test]
+    fn display() {
+        assert_eq!(Categorical::new(&[0.25, 0.5, 0.25]).to_string(),
+                   "Categorical(p = [0.25, 0.5, 0.25])");
+    }
+
     #[test]
     fn distribution() {
         let d = new!([0.0, 0.75, 0.25, 0.0]);
@@ -195,6 +273,25 @@ mod tests {
         assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).entropy(), 1.2798542258336676);
     }
 
+    #[test]
+    fn from_ln_weights() {
+        use std::f64::{INFINITY, NAN, NEG_INFINITY};
+
+        let d = Categorical::from_ln_weights(&[-1001.0, -1000.0, NEG_INFINITY]);
+        let e = (-1f64).exp();
+        assert::close(d.p(), &[e / (1.0 + e), 1.0 / (1.0 + e), 0.0], 1e-15);
+        let d = Categorical::from_ln_weights(&[800.0, 800.0, 800.0, 800.0]);
+        assert::close(d.p(), &[0.25; 4], 1e-15);
+        let d = Categorical::from_ln_weights(&[0.0, -700.0]);
+        assert_eq!(d.p(), &[1.0, (-700f64).exp()]);
+        assert_eq!(Categorical::try_from_ln_weights(&[]), Err(ParameterError::ProbabilitiesEmpty));
+        assert_eq!(Categorical::try_from_ln_weights(&[NEG_INFINITY, NEG_INFINITY]),
+                   Err(ParameterError::WeightsZero));
+        assert_eq!(Categorical::try_from_ln_weights(&[0.0, INFINITY]),
+                   Err(ParameterError::WeightNotNonnegative { value: INFINITY }));
+        assert!(Categorical::try_from_ln_weights(&[0.0, NAN]).is_err());
+    }
+
     #[test]
     fn inverse() {
         let d = new!([0.0, 0.75, 0.25, 0.0]);
@@ -206,6 +303,15 @@ mod tests {
         assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &vec![0, 1, 2, 2]);
     }
 
+    #[test]
+    fn kl_divergence() {
+        let p = new!([0.2, 0.5, 0.3]);
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&new!([0.5, 0.25, 0.25])), 0.21801191094332802954, 1e-15);
+        assert_eq!(p.kl_divergence(&new!([0.5, 0.5])), ::std::f64::INFINITY);
+        assert_eq!(new!([0.5, 0.5, 0.0]).kl_divergence(&new!([0.5, 0.5])), 0.0);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(equal 2).kurtosis(), -2.0);
@@ -248,7 +354,7 @@ mod tests {
 
     #[test]
     fn sample() {
-        let mut source = source::default();
+        let mut source = source::seeded(42);
 
         let sum = Independent(&new!([0.0, 0.5, 0.5]), &mut source).take(100).fold(0, |a, b| a + b);
         assert!(100 <= sum && sum <= 200);
@@ -257,6 +363,19 @@ mod tests {
         assert!(Independent(&new!(p), &mut source).take(1000).all(|x| x % 2 != 0));
     }
 
+    #[test]
+    fn sample_into() {
+        use source::Source;
+
+        let d = new!([0.25, 0.5, 0.25]);
+        let mut one = source::Xorshift128Plus::new([42, 69]);
+        let mut other = source::Xorshift128Plus::new([42, 69]);
+        let mut x = vec![0; 1000];
+        d.sample_into(&mut one, &mut x);
+        assert_eq!(x, (0..1000).map(|_| d.sample(&mut other)).collect::<Vec<_>>());
+        assert_eq!(one.read::<u64>(), other.read::<u64>());
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(equal 6).skewness(), 0.0);
@@ -264,6 +383,20 @@ mod tests {
         assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).skewness(), -0.6);
     }
 
+    #[test]
+    fn try_new() {
+        use std::f64::NAN;
+        assert!(Categorical::try_new(&[0.25, 0.75]).is_ok());
+        assert_eq!(Categorical::try_new(&[]).err(), Some(ParameterError::ProbabilitiesEmpty));
+        assert_eq!(Categorical::try_new(&[1.5, -0.5]).err(),
+                   Some(ParameterError::ProbabilityOutOfRange { value: 1.5 }));
+        assert_eq!(Categorical::try_new(&[0.5, -0.5]).err(),
+                   Some(ParameterError::ProbabilityOutOfRange { value: -0.5 }));
+        assert_eq!(Categorical::try_new(&[0.25, 0.5]).err(),
+                   Some(ParameterError::ProbabilitiesNotNormalized { sum: 0.75 }));
+        assert!(Categorical::try_new(&[NAN, 1.0]).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(equal 3).variance(), 2.0 / 3.0);
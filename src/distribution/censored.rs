@@ -0,0 +1,325 @@
+use std::fmt;
+
+use distribution;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Real;
+
+/// A censored distribution.
+///
+/// The distribution is that of `min(X, limit)` under right censoring and that
+/// of `max(X, limit)` under left censoring where `X` follows the inner
+/// distribution. The censored probability mass forms an atom at the limit,
+/// and the distribution is thus mixed: the density describes only the
+/// continuous part, and the mass of the atom is given by `censoring`. The
+/// moments are computed by numerical integration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Censored<T> {
+    inner: T,
+    limit: f64,
+    censoring: Censoring,
+}
+
+/// A kind of censoring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Censoring {
+    /// Values below the limit are replaced with the limit.
+    Left,
+    /// Values above the limit are replaced with the limit.
+    Right,
+}
+
+impl<T> Censored<T> where T: distribution::Distribution<Value=f64> {
+    /// Create a censored distribution with inner distribution `inner`, limit
+    /// `limit`, and kind `censoring`.
+    ///
+    /// It should hold that `limit` is finite; the function panics otherwise.
+    #[inline]
+    pub fn new(inner: T, limit: f64, censoring: Censoring) -> Self {
+        unwrap!(Censored::try_new(inner, limit, censoring))
+    }
+
+    /// Create a censored distribution with inner distribution `inner`, limit
+    /// `limit`, and kind `censoring` or return an error if the parameters are
+    /// invalid.
+    #[inline]
+    pub fn try_new(inner: T, limit: f64, censoring: Censoring) -> Result<Self, ParameterError> {
+        ensure!(limit.is_finite(), ParameterError::LimitNotFinite { value: limit });
+        Ok(Censored { inner: inner, limit: limit, censoring: censoring })
+    }
+
+    /// Return the inner distribution.
+    #[inline(always)]
+    pub fn inner(&self) -> &T { &self.inner }
+
+    /// Return the limit.
+    #[inline(always)]
+    pub fn limit(&self) -> f64 { self.limit }
+
+    /// Return the kind of censoring.
+    #[inline(always)]
+    pub fn kind(&self) -> Censoring { self.censoring }
+
+    /// Compute the probability of censoring.
+    ///
+    /// The probability is the mass of the atom at the limit.
+    #[inline]
+    pub fn censoring(&self) -> f64 {
+        let p = self.inner.distribution(self.limit);
+        match self.censoring {
+            Censoring::Left => p,
+            Censoring::Right => 1.0 - p,
+        }
+    }
+
+    #[inline(always)]
+    fn censor(&self, x: f64) -> f64 {
+        match self.censoring {
+            Censoring::Left => if x < self.limit { self.limit } else { x },
+            Censoring::Right => if x > self.limit { self.limit } else { x },
+        }
+    }
+}
+
+impl<T> Censored<T> where T: distribution::Continuous<Value=f64> {
+    // Compute the expectation of `f(Y)` where `Y` follows the distribution.
+    fn expectation<F>(&self, f: F) -> f64
+        where F: Fn(f64) -> f64, Self: distribution::Inverse<Value=f64>
+    {
+        use distribution::range;
+        use math;
+        let (a, b) = range(self);
+        let continuous = if a < b {
+            math::simpson(|x| f(x) * self.inner.density(x), a, b, NODES)
+        } else {
+            0.0
+        };
+        continuous + f(self.limit) * self.censoring()
+    }
+}
+
+impl<T> fmt::Display for Censored<T> where T: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.censoring {
+            Censoring::Left => "left",
+            Censoring::Right => "right",
+        };
+        write!(formatter, "Censored({}, limit = {}, {})", self.inner, self.limit, kind)
+    }
+}
+
+impl<T> distribution::Continuous for Censored<T> where T: distribution::Continuous<Value=f64> {
+    /// Compute the density of the continuous part.
+    ///
+    /// The density vanishes at the limit and beyond it; the atom at the limit
+    /// is not represented.
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        match self.censoring {
+            Censoring::Left if x <= self.limit => 0.0,
+            Censoring::Right if x >= self.limit => 0.0,
+            _ => self.inner.density(x),
+        }
+    }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The atom at the limit is taken into account.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 where Self: distribution::Inverse<Value=f64> {
+        let mean = self.moment(1);
+        self.expectation(|x| (x - mean).powi(k as i32))
+    }
+
+    /// Compute the raw moment of order `k`.
+    ///
+    /// The atom at the limit is taken into account.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 where Self: distribution::Inverse<Value=f64> {
+        self.expectation(|x| x.powi(k as i32))
+    }
+}
+
+impl<T> distribution::Distribution for Censored<T> where T: distribution::Distribution<Value=f64> {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        match self.censoring {
+            Censoring::Left if x < self.limit => 0.0,
+            Censoring::Right if x >= self.limit => 1.0,
+            _ => self.inner.distribution(x),
+        }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        let (a, b) = self.inner.support();
+        match self.censoring {
+            Censoring::Left => (self.limit, b.max(self.limit)),
+            Censoring::Right => (a.min(self.limit), self.limit),
+        }
+    }
+}
+
+impl<T> distribution::Inverse for Censored<T> where T: distribution::Inverse<Value=f64> {
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        self.censor(self.inner.inverse(p))
+    }
+}
+
+impl<T> distribution::Mean for Censored<T>
+    where T: distribution::Continuous<Value=f64> + distribution::Inverse
+{
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.expectation(|x| x)
+    }
+}
+
+impl<T> distribution::Sample for Censored<T> where T: distribution::Sample<Value=f64> {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        self.censor(self.inner.sample(source))
+    }
+}
+
+impl<T> distribution::Variance for Censored<T>
+    where T: distribution::Continuous<Value=f64> + distribution::Inverse
+{
+    #[inline]
+    fn variance(&self) -> f64 {
+        use distribution::Mean;
+        let mean = self.mean();
+        self.expectation(|x| (x - mean).powi(2))
+    }
+}
+
+const NODES: usize = 10000;
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($inner:expr, $limit:expr, $censoring:ident) => (
+            Censored::new($inner, $limit, Censoring::$censoring)
+        );
+    );
+
+    #[test]
+    fn censoring() {
+        assert::close(new!(Exponential::new(2.0), 1.0, Right).censoring(), (-2.0f64).exp(),
+                      1e-15);
+        assert::close(new!(Exponential::new(2.0), 1.0, Left).censoring(),
+                      1.0 - (-2.0f64).exp(), 1e-15);
+    }
+
+    #[test]
+    fn central_moment() {
+        let (lambda, c) = (2.0f64, 1.5);
+        let d = new!(Exponential::new(lambda), c, Right);
+        assert::close(d.central_moment(1), 0.0, 1e-12);
+        assert::close(d.central_moment(2), d.variance(), 1e-12);
+    }
+
+    #[test]
+    fn density() {
+        let d = new!(Exponential::new(2.0), 1.0, Right);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0];
+        let p = vec![0.0, 2.0, 2.0 * (-1.0f64).exp(), 0.0, 0.0];
+        assert_eq!(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p);
+
+        let d = new!(Exponential::new(2.0), 1.0, Left);
+        let p = vec![0.0, 0.0, 0.0, 0.0, 2.0 * (-4.0f64).exp()];
+        assert_eq!(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Censored::new(Exponential::new(2.0), 1.5, Censoring::Right).to_string(),
+                   "Censored(Exponential(λ = 2), limit = 1.5, right)");
+        assert_eq!(Censored::new(Gaussian::new(0.0, 1.0), -1.0, Censoring::Left).to_string(),
+                   "Censored(Gaussian(μ = 0, σ = 1), limit = -1, left)");
+    }
+
+    #[test]
+    fn distribution() {
+        let e = Exponential::new(2.0);
+        let d = new!(e, 1.0, Right);
+        let x = vec![-1.0, 0.5, 0.999, 1.0, 2.0];
+        let p = vec![0.0, e.distribution(0.5), e.distribution(0.999), 1.0, 1.0];
+        assert_eq!(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p);
+
+        let d = new!(e, 1.0, Left);
+        let p = vec![0.0, 0.0, 0.0, e.distribution(1.0), e.distribution(2.0)];
+        assert_eq!(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p);
+    }
+
+    #[test]
+    fn inverse() {
+        let e = Exponential::new(2.0);
+        let d = new!(e, 1.0, Right);
+        let p = vec![0.0, 0.5, 0.9, 1.0];
+        let x = vec![0.0, e.inverse(0.5), 1.0, 1.0];
+        assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
+
+        let d = new!(e, 1.0, Left);
+        let x = vec![1.0, 1.0, e.inverse(0.9), ::std::f64::INFINITY];
+        assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
+    }
+
+    #[test]
+    fn mean() {
+        let (lambda, c) = (2.0, 1.5);
+        let d = new!(Exponential::new(lambda), c, Right);
+        assert::close(d.mean(), -(-lambda * c).exp_m1() / lambda, 1e-12);
+        let d = new!(Exponential::new(lambda), c, Left);
+        assert::close(d.mean(), c + (-lambda * c).exp() / lambda, 1e-10);
+        let d = new!(Gaussian::new(0.0, 1.0), -10.0, Left);
+        assert::close(d.mean(), 0.0, 1e-11);
+    }
+
+    #[test]
+    fn moment() {
+        let (lambda, c) = (2.0f64, 1.5);
+        let d = new!(Exponential::new(lambda), c, Right);
+        assert::close(d.moment(1), d.mean(), 1e-15);
+        assert::close(d.moment(2), 2.0 / lambda.powi(2) * (1.0 - (-lambda * c).exp() *
+                                                          (1.0 + lambda * c)), 1e-12);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!(Exponential::new(2.0), 0.5, Right);
+        assert!(Independent(&d, &mut source::seeded(42)).take(100).all(|x| x <= 0.5));
+        let d = new!(Exponential::new(2.0), 0.5, Left);
+        assert!(Independent(&d, &mut source::seeded(42)).take(100).all(|x| x >= 0.5));
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        let inner = Gaussian::new(0.0, 1.0);
+        assert!(Censored::try_new(inner, 1.0, Censoring::Right).is_ok());
+        assert_eq!(Censored::try_new(inner, INFINITY, Censoring::Left).err(),
+                   Some(ParameterError::LimitNotFinite { value: INFINITY }));
+        assert!(Censored::try_new(inner, NAN, Censoring::Right).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        let (lambda, c) = (2.0f64, 1.5);
+        let d = new!(Exponential::new(lambda), c, Right);
+        let mean = -(-lambda * c).exp_m1() / lambda;
+        let square = 2.0 / lambda.powi(2) * (1.0 - (-lambda * c).exp() * (1.0 + lambda * c));
+        assert::close(d.variance(), square - mean * mean, 1e-12);
+    }
+}
@@ -0,0 +1,292 @@
+use std::fmt;
+
+use distribution;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Real;
+
+/// A compound distribution.
+///
+/// The distribution is the marginal of `X` where `X` given `θ` follows the
+/// distribution constructed by the builder from `θ`, and `θ` follows the
+/// prior distribution. Sampling is exact. The density, mass, and distribution
+/// functions as well as the moments are computed by integrating over the
+/// prior using the composite Simpson rule; infinite endpoints of the support
+/// of the prior are truncated at the quantiles of order `1e-12` and
+/// `1 - 1e-12`. The builder is not invoked at points where the density of the
+/// prior vanishes, and the density of the prior should be finite on the
+/// truncated support.
+#[derive(Clone, Copy)]
+pub struct Compound<P, F> {
+    prior: P,
+    builder: F,
+    nodes: usize,
+}
+
+impl<P, F, D> Compound<P, F> where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+                                   F: Fn(f64) -> D
+{
+    /// Create a compound distribution with prior `prior` and builder
+    /// `builder`.
+    ///
+    /// The number of integration nodes is 1000.
+    #[inline]
+    pub fn new(prior: P, builder: F) -> Self {
+        Compound::with_nodes(prior, builder, 1000)
+    }
+
+    /// Create a compound distribution with prior `prior` and builder
+    /// `builder` with `nodes` integration nodes.
+    ///
+    /// It should hold that `nodes > 0`; the function panics otherwise.
+    #[inline]
+    pub fn with_nodes(prior: P, builder: F, nodes: usize) -> Self {
+        unwrap!(Compound::try_with_nodes(prior, builder, nodes))
+    }
+
+    /// Create a compound distribution with `nodes` integration nodes or return an error if the
+    /// number of nodes is zero.
+    #[inline]
+    pub fn try_with_nodes(prior: P, builder: F, nodes: usize) -> Result<Self, ParameterError> {
+        ensure!(nodes > 0, ParameterError::NodesZero);
+        Ok(Compound { prior: prior, builder: builder, nodes: nodes })
+    }
+
+    /// Return the prior distribution.
+    #[inline(always)]
+    pub fn prior(&self) -> &P { &self.prior }
+
+    /// Return the number of integration nodes.
+    #[inline(always)]
+    pub fn nodes(&self) -> usize { self.nodes }
+
+    // Compute the expectation of `f(D(θ))` with respect to the prior.
+    fn expectation<G>(&self, f: G) -> f64 where G: Fn(&D) -> f64 {
+        use distribution::range;
+        use math;
+        let (a, b) = range(&self.prior);
+        math::simpson(|theta| {
+            let density = self.prior.density(theta);
+            if density == 0.0 { 0.0 } else { density * f(&(self.builder)(theta)) }
+        }, a, b, self.nodes)
+    }
+}
+
+impl<P, F> fmt::Debug for Compound<P, F> where P: fmt::Debug {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("Compound")
+                 .field("prior", &self.prior)
+                 .field("nodes", &self.nodes)
+                 .finish()
+    }
+}
+
+impl<P, F> fmt::Display for Compound<P, F> where P: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Compound({}, nodes = {})", self.prior, self.nodes)
+    }
+}
+
+impl<P, F, D> distribution::Continuous for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+          F: Fn(f64) -> D,
+          D: distribution::Continuous
+{
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        self.expectation(|d| d.density(x))
+    }
+}
+
+impl<P, F, D> distribution::Discrete for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+          F: Fn(f64) -> D,
+          D: distribution::Discrete,
+          D::Value: Copy
+{
+    #[inline]
+    fn mass(&self, x: D::Value) -> f64 {
+        self.expectation(|d| d.mass(x))
+    }
+}
+
+impl<P, F, D> distribution::Distribution for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+          F: Fn(f64) -> D,
+          D: distribution::Distribution
+{
+    type Value = D::Value;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
+        self.expectation(|d| d.distribution(x))
+    }
+}
+
+impl<P, F, D> distribution::Inverse for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+          F: Fn(f64) -> D,
+          D: distribution::Inverse<Value=f64>
+{
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The quantile is found by `distribution::invert` starting from the
+    /// quantile of the distribution built from the median of the prior.
+    fn inverse(&self, p: f64) -> f64 {
+        use distribution::invert;
+        use std::f64::NAN;
+
+        guard!(inverse(p));
+        let x = (self.builder)(self.prior.inverse(0.5)).inverse(p);
+        invert(self, p, (x, x), 1e-12 * (1.0 + x.abs())).unwrap_or(NAN)
+    }
+}
+
+impl<P, F, D> distribution::Mean for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+          F: Fn(f64) -> D,
+          D: distribution::Mean
+{
+    /// Compute the expected value.
+    ///
+    /// The law of total expectation is utilized.
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.expectation(|d| d.mean())
+    }
+}
+
+impl<P, F, D> distribution::Sample for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse + distribution::Sample,
+          F: Fn(f64) -> D,
+          D: distribution::Sample
+{
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> D::Value where S: Source {
+        (self.builder)(self.prior.sample(source)).sample(source)
+    }
+}
+
+impl<P, F, D> distribution::Variance for Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse,
+          F: Fn(f64) -> D,
+          D: distribution::Variance
+{
+    /// Compute the variance.
+    ///
+    /// The law of total variance is utilized.
+    fn variance(&self) -> f64 {
+        use distribution::Mean;
+        let mean = self.mean();
+        self.expectation(|d| d.variance() + (d.mean() - mean).powi(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn density() {
+        let d = Compound::new(Gaussian::new(1.0, 2.0), |mu| Gaussian::new(mu, 1.5));
+        let e = Gaussian::new(1.0, 2.5);
+        let x = vec![-6.0, -3.0, -1.0, 0.0, 0.5, 2.0, 5.0];
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| e.density(x)).collect::<Vec<_>>(), 1e-12);
+    }
+
+    #[test]
+    fn display() {
+        let d = Compound::new(Gamma::new(3.0, 1.5), |lambda| Poisson::new(lambda));
+        assert_eq!(d.to_string(), "Compound(Gamma(k = 3, θ = 1.5), nodes = 1000)");
+        let d = Compound::with_nodes(Gaussian::new(1.0, 2.0), |mu| Gaussian::new(mu, 1.5), 10);
+        assert_eq!(format!("{:?}", d),
+                   "Compound { prior: Gaussian { mu: 1.0, sigma: 2.0 }, nodes: 10 }");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = Compound::new(Gaussian::new(1.0, 2.0), |mu| Gaussian::new(mu, 1.5));
+        let e = Gaussian::new(1.0, 2.5);
+        let x = vec![-6.0, -3.0, -1.0, 0.0, 0.5, 2.0, 5.0];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| e.distribution(x)).collect::<Vec<_>>(), 1e-11);
+    }
+
+    #[test]
+    fn inverse() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = Compound::new(Gaussian::new(1.0, 2.0), |mu| Gaussian::new(mu, 1.5));
+        let e = Gaussian::new(1.0, 2.5);
+        assert_eq!(d.inverse(0.0), NEG_INFINITY);
+        assert_eq!(d.inverse(1.0), INFINITY);
+        for &p in &[0.01, 0.1, 0.5, 0.9, 0.99] {
+            assert::close(d.inverse(p), e.inverse(p), 1e-10);
+        }
+    }
+
+    #[test]
+    fn mass() {
+        use special::Beta as BetaFunction;
+        use special::Gamma as GammaFunction;
+
+        let ln_gamma = |x: f64| GammaFunction::ln_gamma(x).0;
+
+        let (k, theta) = (3.0, 1.5);
+        let d = Compound::with_nodes(Gamma::new(k, theta), |lambda| Poisson::new(lambda), 10000);
+        let p: f64 = theta / (1.0 + theta);
+        let e = (0..20).map(|x| {
+            let x = x as f64;
+            (ln_gamma(x + k) - ln_gamma(k) - ln_gamma(x + 1.0) + k * (1.0 - p).ln() +
+             x * p.ln()).exp()
+        }).collect::<Vec<_>>();
+        assert::close(&(0..20).map(|x| d.mass(x)).collect::<Vec<_>>(), &e, 1e-10);
+
+        let (n, alpha, beta) = (10, 2.0, 3.5);
+        let d = Compound::with_nodes(Beta::new(alpha, beta, 0.0, 1.0), |p| Binomial::new(n, p),
+                                     10000);
+        let e = (0..(n + 1)).map(|x| {
+            let (x, n) = (x as f64, n as f64);
+            (ln_gamma(n + 1.0) - ln_gamma(x + 1.0) - ln_gamma(n - x + 1.0) +
+             BetaFunction::ln_beta(x + alpha, n - x + beta) -
+             BetaFunction::ln_beta(alpha, beta)).exp()
+        }).collect::<Vec<_>>();
+        assert::close(&(0..(n + 1)).map(|x| d.mass(x)).collect::<Vec<_>>(), &e, 1e-10);
+    }
+
+    #[test]
+    fn mean() {
+        let d = Compound::new(Gamma::new(3.0, 1.5), |lambda| Poisson::new(lambda));
+        assert::close(d.mean(), 4.5, 1e-7);
+        let d = Compound::with_nodes(Gamma::new(3.0, 1.5), |lambda| Poisson::new(lambda), 10000);
+        assert::close(d.mean(), 4.5, 1e-10);
+    }
+
+    #[test]
+    fn sample() {
+        let d = Compound::new(Gamma::new(3.0, 1.5), |lambda| Poisson::new(lambda));
+        let sum = Independent(&d, &mut source::seeded(42)).take(10000).fold(0, |a, b| a + b);
+        assert!((sum as f64 / 10000.0 - 4.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn try_with_nodes() {
+        let builder = |mu| Gaussian::new(mu, 1.5);
+        assert!(Compound::try_with_nodes(Gaussian::new(1.0, 2.0), builder, 10).is_ok());
+        assert_eq!(Compound::try_with_nodes(Gaussian::new(1.0, 2.0), builder, 0).err(),
+                   Some(ParameterError::NodesZero));
+    }
+
+    #[test]
+    fn variance() {
+        let d = Compound::with_nodes(Gamma::new(3.0, 1.5), |lambda| Poisson::new(lambda), 10000);
+        assert::close(d.variance(), 4.5 + 3.0 * 1.5 * 1.5, 1e-8);
+        let d = Compound::new(Gaussian::new(1.0, 2.0), |mu| Gaussian::new(mu, 1.5));
+        assert::close(d.variance(), 6.25, 1e-8);
+    }
+}
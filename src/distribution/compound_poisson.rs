@@ -0,0 +1,543 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A compound Poisson distribution.
+///
+/// The distribution is that of `X₁ + ⋯ + X_N` where `N` follows a Poisson
+/// distribution with rate `λ` and the summands are independent draws from a
+/// nonnegative severity distribution. With probability `exp(-λ)`, there are
+/// no summands, which forms an atom at zero, and the distribution is thus
+/// mixed: the density describes only the continuous part, and the mass of the
+/// atom is given by `atom`.
+///
+/// Sampling is exact, and so are the moments, which are computed from the
+/// cumulants `κₖ = λ E[Xᵏ]` with the raw moments of the severity. The
+/// distribution function is computed by the Panjer recursion applied to the
+/// severity discretized with `discretize` on a lattice of a given width. The
+/// lattice values are interpolated linearly between the midpoints of the
+/// lattice points, and the atom is added explicitly; the density is the
+/// derivative of the interpolant and is thus piecewise constant. The lattice
+/// is computed once at construction, and the cost is quadratic in the number
+/// of points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundPoisson<D> {
+    lambda: f64,
+    severity: D,
+    width: f64,
+    moments: [f64; 4],
+    cumulative: Vec<f64>,
+}
+
+impl<D> CompoundPoisson<D> where D: distribution::Continuous<Value=f64> + distribution::Inverse {
+    /// Create a compound Poisson distribution with rate `lambda` and severity
+    /// `severity`.
+    ///
+    /// The lattice spans the range where the probability mass lies with 4096
+    /// points. It should hold that `lambda > 0` and that the support of the
+    /// severity is nonnegative; the function panics otherwise.
+    #[inline]
+    pub fn new(lambda: f64, severity: D) -> Self {
+        unwrap!(CompoundPoisson::try_new(lambda, severity))
+    }
+
+    /// Create a compound Poisson distribution with rate `lambda` and severity
+    /// `severity` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_new(lambda: f64, severity: D) -> Result<Self, ParameterError> {
+        CompoundPoisson::build(lambda, severity, None)
+    }
+
+    /// Create a compound Poisson distribution with rate `lambda`, severity
+    /// `severity`, and lattice width `width`.
+    ///
+    /// It should hold that `lambda > 0`, that `width > 0`, and that the
+    /// support of the severity is nonnegative; the function panics otherwise.
+    #[inline]
+    pub fn with_width(lambda: f64, severity: D, width: f64) -> Self {
+        unwrap!(CompoundPoisson::try_with_width(lambda, severity, width))
+    }
+
+    /// Create a compound Poisson distribution with rate `lambda`, severity
+    /// `severity`, and lattice width `width` or return an error if the
+    /// parameters are invalid.
+    #[inline]
+    pub fn try_with_width(lambda: f64, severity: D, width: f64)
+                          -> Result<Self, ParameterError>
+    {
+        ensure!(width > 0.0 && width.is_finite(),
+                ParameterError::WidthNotPositive { value: width });
+        CompoundPoisson::build(lambda, severity, Some(width))
+    }
+
+    fn build(lambda: f64, severity: D, width: Option<f64>) -> Result<Self, ParameterError> {
+        ensure!(lambda > 0.0 && lambda.is_finite(),
+                ParameterError::LambdaNotPositive { value: lambda });
+        let lower = severity.support().0;
+        ensure!(lower >= 0.0, ParameterError::SupportNotNonnegative { value: lower });
+        let moments = moments(&severity);
+        let upper = extent(lambda, &severity, &moments);
+        let width = width.unwrap_or(upper / BINS as f64);
+        let bins = (upper / width).ceil().max(1.0) as usize + 1;
+        let masses = panjer(lambda, &discretize(&severity, width, bins));
+        let mut cumulative = Vec::with_capacity(bins);
+        let (atom, mut sum) = ((-lambda).exp(), 0.0);
+        for mass in masses {
+            sum += mass;
+            cumulative.push(sum.max(atom).min(1.0));
+        }
+        cumulative[bins - 1] = 1.0;
+        Ok(CompoundPoisson {
+            lambda: lambda,
+            severity: severity,
+            width: width,
+            moments: moments,
+            cumulative: cumulative,
+        })
+    }
+}
+
+impl<D> CompoundPoisson<D> {
+    /// Return the rate parameter.
+    #[inline(always)]
+    pub fn lambda(&self) -> f64 { self.lambda }
+
+    /// Return the severity distribution.
+    #[inline(always)]
+    pub fn severity(&self) -> &D { &self.severity }
+
+    /// Return the width of the lattice.
+    #[inline(always)]
+    pub fn width(&self) -> f64 { self.width }
+
+    /// Compute the probability of no summands.
+    ///
+    /// The probability is the mass of the atom at zero.
+    #[inline]
+    pub fn atom(&self) -> f64 {
+        (-self.lambda).exp()
+    }
+
+    // Return knot `k` and the cumulative probability there; the first knot is
+    // zero, and the others are the midpoints between the lattice points.
+    #[inline]
+    fn knot(&self, k: usize) -> (f64, f64) {
+        if k == 0 {
+            (0.0, self.atom())
+        } else {
+            ((k as f64 - 0.5) * self.width, self.cumulative[k - 1])
+        }
+    }
+
+    // Compute the cumulant of order `k`, taking the first four raw moments of
+    // the severity from the cache.
+    fn cumulant(&self, k: u32) -> f64
+        where D: distribution::Continuous<Value=f64> + distribution::Inverse
+    {
+        self.lambda * if k <= 4 { self.moments[k as usize - 1] } else { self.severity.moment(k) }
+    }
+
+    // Compute the moment of order `k` from the cumulants by the recurrence
+    // `mₙ = Σⱼ C(n - 1, j) κⱼ₊₁ mₙ₋₁₋ⱼ`; the central moments follow with the
+    // first cumulant set to zero.
+    fn moment_from_cumulants(&self, k: u32, central: bool) -> f64
+        where D: distribution::Continuous<Value=f64> + distribution::Inverse
+    {
+        let cumulants = (1..(k + 1)).map(|j| {
+            if central && j == 1 { 0.0 } else { self.cumulant(j) }
+        }).collect::<Vec<_>>();
+        let mut moments = vec![1.0; k as usize + 1];
+        for n in 1..(k as usize + 1) {
+            let (mut sum, mut binomial) = (0.0, 1.0);
+            for j in 0..n {
+                sum += binomial * cumulants[j] * moments[n - 1 - j];
+                binomial = binomial * (n - 1 - j) as f64 / (j + 1) as f64;
+            }
+            moments[n] = sum;
+        }
+        moments[k as usize]
+    }
+}
+
+impl<D> fmt::Display for CompoundPoisson<D> where D: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "CompoundPoisson(λ = {}, {})", self.lambda, self.severity)
+    }
+}
+
+impl<D> distribution::Continuous for CompoundPoisson<D>
+    where D: distribution::Continuous<Value=f64> + distribution::Inverse
+{
+    /// Compute the density of the continuous part.
+    ///
+    /// The density vanishes below zero and beyond the lattice; the atom at
+    /// zero is not represented.
+    fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        if x < 0.0 {
+            return 0.0;
+        }
+        let k = (x / self.width + 0.5).floor() as usize;
+        if k >= self.cumulative.len() {
+            return 0.0;
+        }
+        let ((a, p), (b, q)) = (self.knot(k), self.knot(k + 1));
+        (q - p) / (b - a)
+    }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The moment is computed exactly from the cumulants.
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 where Self: distribution::Inverse<Value=f64> {
+        self.moment_from_cumulants(k, true)
+    }
+
+    /// Compute the raw moment of order `k`.
+    ///
+    /// The moment is computed exactly from the cumulants.
+    #[inline]
+    fn moment(&self, k: u32) -> f64 where Self: distribution::Inverse<Value=f64> {
+        self.moment_from_cumulants(k, false)
+    }
+}
+
+impl<D> distribution::Distribution for CompoundPoisson<D> {
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
+        if x < 0.0 {
+            return 0.0;
+        }
+        let k = (x / self.width + 0.5).floor() as usize;
+        if k >= self.cumulative.len() {
+            return 1.0;
+        }
+        let ((a, p), (b, q)) = (self.knot(k), self.knot(k + 1));
+        p + (q - p) * (x - a) / (b - a)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, ::std::f64::INFINITY)
+    }
+}
+
+impl<D> distribution::Inverse for CompoundPoisson<D> {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The probabilities up to the mass of the atom map to zero.
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        if p <= self.atom() {
+            return 0.0;
+        }
+        if p == 1.0 {
+            return ::std::f64::INFINITY;
+        }
+        use std::cmp::Ordering;
+        let k = match self.cumulative.binary_search_by(|&q| {
+            if q < p { Ordering::Less } else { Ordering::Greater }
+        }) {
+            Ok(k) | Err(k) => k,
+        };
+        let ((a, p0), (b, q)) = (self.knot(k), self.knot(k + 1));
+        a + (p - p0) / (q - p0) * (b - a)
+    }
+}
+
+impl<D> distribution::Kurtosis for CompoundPoisson<D> {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.moments[3] / (self.lambda * self.moments[1].powi(2))
+    }
+}
+
+impl<D> distribution::Mean for CompoundPoisson<D> {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.lambda * self.moments[0]
+    }
+}
+
+impl<D> distribution::Sample for CompoundPoisson<D> where D: distribution::Sample<Value=f64> {
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        use distribution::Poisson;
+        let n = Poisson::new(self.lambda).sample(source);
+        (0..n).fold(0.0, |sum, _| sum + self.severity.sample(source))
+    }
+}
+
+impl<D> distribution::Skewness for CompoundPoisson<D> {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.moments[2] / (self.lambda.sqrt() * self.moments[1].powf(1.5))
+    }
+}
+
+impl<D> distribution::Variance for CompoundPoisson<D> {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.lambda * self.moments[1]
+    }
+}
+
+/// Discretize a distribution on a lattice preserving the mean.
+///
+/// The masses are placed at the points `k width` for `k` from zero to
+/// `bins - 1`. The mass between two neighboring points is split between them
+/// so that its mean is preserved, which amounts to setting the mass at point
+/// `k` to `(I(k) - I(k + 1)) / width` where `I(k)` is the integral of the
+/// survival function over `((k - 1) width, k width)`, and the mass at zero to
+/// `1 - I(1) / width`. The integrals are computed by the Boole rule with four
+/// subintervals, whose error is of the seventh order in the width. Unlike
+/// rounding, which shifts the mean of each summand by a term quadratic in the
+/// width, the discretization preserves the mean up to the error of the
+/// integrals, which keeps the mean of sums of many summands in place. The
+/// distribution should be supported on the nonnegative half-line, and the mass
+/// beyond the last point is not represented. It should hold that `width > 0`
+/// and `bins > 0`; the function panics otherwise.
+///
+/// ```
+/// use probability::distribution::discretize;
+/// use probability::prelude::*;
+///
+/// let masses = discretize(&Exponential::new(1.0), 0.5, 80);
+/// assert!((masses[0] - (1.0 - 2.0 * (1.0 - (-0.5f64).exp()))).abs() < 1e-8);
+/// let mean = masses.iter().enumerate()
+///                   .map(|(k, mass)| 0.5 * k as f64 * mass).sum::<f64>();
+/// assert!((mean - 1.0).abs() < 1e-8);
+/// ```
+pub fn discretize<D>(distribution: &D, width: f64, bins: usize) -> Vec<f64>
+    where D: distribution::Distribution
+{
+    assert!(width > 0.0 && width.is_finite(),
+            "the width should be positive and finite (got {})", width);
+    assert!(bins > 0, "the number of bins should be positive");
+    let step = width / 4.0;
+    let survival = (0..(4 * bins + 1)).map(|i| distribution.survival(i as f64 * step))
+                                      .collect::<Vec<_>>();
+    let integral = |k: usize| {
+        if k == 0 || k > bins {
+            return 0.0;
+        }
+        let s = &survival[(4 * k - 4)..(4 * k + 1)];
+        2.0 * step / 45.0 * (7.0 * (s[0] + s[4]) + 32.0 * (s[1] + s[3]) + 12.0 * s[2])
+    };
+    let mut masses = Vec::with_capacity(bins);
+    masses.push((1.0 - integral(1) / width).max(0.0));
+    for k in 1..bins {
+        masses.push(((integral(k) - integral(k + 1)) / width).max(0.0));
+    }
+    masses
+}
+
+const BINS: usize = 4096;
+
+// Compute the upper end of the lattice: the mean plus twelve standard
+// deviations, or the number of summands at the quantile of order `1 - 1e-12`
+// times the same quantile of the severity if the variance is infinite, but
+// no less than the latter quantile.
+fn extent<D>(lambda: f64, severity: &D, moments: &[f64; 4]) -> f64
+    where D: distribution::Inverse<Value=f64>
+{
+    use distribution::{Inverse, Poisson};
+    let quantile = severity.inverse(1.0 - 1e-12);
+    let bound = lambda * moments[0] + 12.0 * (lambda * moments[1]).sqrt();
+    if bound.is_finite() {
+        bound.max(quantile)
+    } else {
+        Poisson::new(lambda).inverse(1.0 - 1e-12).max(1) as f64 * quantile
+    }
+}
+
+// Compute the first four raw moments of the severity.
+fn moments<D>(severity: &D) -> [f64; 4]
+    where D: distribution::Continuous<Value=f64> + distribution::Inverse
+{
+    [severity.moment(1), severity.moment(2), severity.moment(3), severity.moment(4)]
+}
+
+// Compute the distribution of the sum on the lattice by the Panjer recursion
+// `gₖ = λ / k Σⱼ j fⱼ gₖ₋ⱼ`. The recursion is linear, so it starts from one
+// and is rescaled when the values grow large, which avoids the underflow of
+// `g₀ = exp(λ (f₀ - 1))` for large rates; the result is normalized to unit
+// mass.
+fn panjer(lambda: f64, masses: &[f64]) -> Vec<f64> {
+    use math;
+    let n = masses.len();
+    let weights = masses.iter().enumerate().map(|(j, &f)| j as f64 * f).collect::<Vec<_>>();
+    let mut g = Vec::with_capacity(n);
+    g.push(1.0);
+    for k in 1..n {
+        let sum = (1..(k + 1)).fold(0.0, |sum, j| sum + weights[j] * g[k - j]);
+        g.push(lambda / k as f64 * sum);
+        if g[k] > 1e250 {
+            for value in g.iter_mut() {
+                *value *= 1e-250;
+            }
+        }
+    }
+    let total = math::sum(g.iter().cloned());
+    for value in g.iter_mut() {
+        *value /= total;
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use math;
+    use prelude::*;
+
+    // Compute the empirical distribution function of `samples` at `x`.
+    fn empirical(samples: &[f64], x: f64) -> f64 {
+        samples.iter().filter(|&&sample| sample <= x).count() as f64 / samples.len() as f64
+    }
+
+    fn samples<D>(d: &D, n: usize) -> Vec<f64> where D: Sample<Value=f64> {
+        Independent(d, &mut source::seeded(42)).take(n).collect()
+    }
+
+    #[test]
+    fn atom() {
+        let lambda = 1.5f64;
+        let d = CompoundPoisson::new(lambda, Gamma::new(2.0, 1.0));
+        assert_eq!(d.atom(), (-lambda).exp());
+        assert_eq!(d.distribution(0.0), (-lambda).exp());
+        assert_eq!(d.distribution(-1e-12), 0.0);
+        assert!(d.distribution(1e-9) - (-lambda).exp() < 1e-8);
+        assert_eq!(d.inverse(0.5 * (-lambda).exp()), 0.0);
+        assert_eq!(d.inverse(0.0), 0.0);
+        assert::close(empirical(&samples(&d, 100000), 0.0), (-lambda).exp(), 5e-3);
+    }
+
+    #[test]
+    fn convergence() {
+        let d = CompoundPoisson::new(3.0, Gamma::new(2.0, 1.0));
+        let samples = samples(&d, 200000);
+        let points = (1..30).map(|i| i as f64 * 0.5).collect::<Vec<_>>();
+        let errors = [2.0, 1.0, 0.25].iter().map(|&width| {
+            let d = CompoundPoisson::with_width(3.0, Gamma::new(2.0, 1.0), width);
+            points.iter().fold(0.0f64, |error, &x| {
+                error.max((d.distribution(x) - empirical(&samples, x)).abs())
+            })
+        }).collect::<Vec<_>>();
+        assert!(errors[0] > errors[1] && errors[1] > errors[2]);
+        assert!(errors[2] < 3e-3);
+    }
+
+    #[test]
+    fn density() {
+        let d = CompoundPoisson::new(3.0, Gamma::new(2.0, 1.0));
+        assert_eq!(d.density(-1.0), 0.0);
+        assert_eq!(d.density(1e6), 0.0);
+        let (a, b) = (2.0, 2.5);
+        let mass = math::simpson(|x| d.density(x), a, b, 1000);
+        assert::close(mass, d.distribution(b) - d.distribution(a), 1e-6);
+        let mass = math::simpson(|x| d.density(x), 0.0, 100.0, 100000);
+        assert::close(mass, 1.0 - d.atom(), 1e-4);
+    }
+
+    #[test]
+    fn discretize() {
+        use distribution::discretize;
+        let masses = discretize(&Uniform::new(0.0, 2.0), 0.5, 6);
+        for (&mass, &expected) in masses.iter().zip(&[0.125, 0.25, 0.25, 0.25, 0.125, 0.0]) {
+            assert::close(mass, expected, 1e-15);
+        }
+
+        let d = Gamma::new(3.0, 2.0);
+        let masses = discretize(&d, 0.1, 1000);
+        let moment = |j| math::sum(masses.iter().enumerate().map(|(k, &mass)| {
+            (0.1 * k as f64).powi(j) * mass
+        }));
+        assert::close(moment(0), 1.0, 1e-12);
+        assert::close(moment(1), d.mean(), 1e-8);
+        assert::close(moment(2) - d.mean().powi(2), d.variance(), 1e-2);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(CompoundPoisson::new(2.0, Exponential::new(1.0)).to_string(),
+                   "CompoundPoisson(λ = 2, Exponential(λ = 1))");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = CompoundPoisson::new(3.0, Gamma::new(2.0, 1.0));
+        let samples = samples(&d, 200000);
+        for &x in &[0.5, 1.0, 2.0, 4.0, 6.0, 8.0, 12.0, 20.0] {
+            assert::close(d.distribution(x), empirical(&samples, x), 5e-3);
+        }
+        assert_eq!(d.distribution(1e6), 1.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = CompoundPoisson::new(3.0, Gamma::new(2.0, 1.0));
+        for &p in &[0.1, 0.25, 0.5, 0.75, 0.99, 1.0 - 1e-9] {
+            assert::close(d.distribution(d.inverse(p)), p, 1e-12);
+        }
+        assert_eq!(d.inverse(1.0), ::std::f64::INFINITY);
+    }
+
+    #[test]
+    fn large_rate() {
+        let lambda = 1000.0;
+        let d = CompoundPoisson::new(lambda, Exponential::new(1.0));
+        assert_eq!(d.atom(), 0.0);
+        // Given `n` summands, the sum follows the gamma distribution with shape `n`.
+        let poisson = Poisson::new(lambda);
+        for &x in &[900.0, 950.0, 1000.0, 1050.0, 1100.0] {
+            let expected = math::sum((800..1200).map(|n| {
+                poisson.mass(n) * Gamma::new(n as f64, 1.0).distribution(x)
+            }));
+            assert::close(d.distribution(x), expected, 2e-3);
+        }
+    }
+
+    #[test]
+    fn moments() {
+        let (lambda, k, theta) = (3.0f64, 2.0, 1.5);
+        let d = CompoundPoisson::new(lambda, Gamma::new(k, theta));
+        let raw = |j: i32| (0..j).fold(theta.powi(j), |product, i| product * (k + i as f64));
+        assert::close(d.mean(), lambda * raw(1), 1e-8);
+        assert::close(d.variance(), lambda * raw(2), 1e-8);
+        assert::close(d.skewness(), raw(3) / (lambda.sqrt() * raw(2).powf(1.5)), 1e-8);
+        assert::close(d.kurtosis(), raw(4) / (lambda * raw(2).powi(2)), 1e-8);
+        assert::close(d.moment(1), d.mean(), 1e-12);
+        assert::close(d.moment(2), d.variance() + d.mean().powi(2), 1e-8);
+        assert::close(d.central_moment(2), d.variance(), 1e-12);
+        assert::close(d.central_moment(3), d.skewness() * d.variance().powf(1.5), 1e-8);
+
+        let samples = samples(&d, 200000);
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() /
+                       samples.len() as f64;
+        assert::close(mean, d.mean(), 5e-2);
+        assert::close(variance / d.variance(), 1.0, 2e-2);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{NAN, NEG_INFINITY};
+        let severity = Exponential::new(1.0);
+        assert!(CompoundPoisson::try_new(2.0, severity).is_ok());
+        assert_eq!(CompoundPoisson::try_new(0.0, severity).err(),
+                   Some(ParameterError::LambdaNotPositive { value: 0.0 }));
+        assert_eq!(CompoundPoisson::try_new(1.0, Gaussian::new(0.0, 1.0)).err(),
+                   Some(ParameterError::SupportNotNonnegative { value: NEG_INFINITY }));
+        assert_eq!(CompoundPoisson::try_with_width(1.0, severity, 0.0).err(),
+                   Some(ParameterError::WidthNotPositive { value: 0.0 }));
+        assert!(CompoundPoisson::try_with_width(1.0, severity, NAN).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{self, Beta};
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Float;
+
+/// A Dirichlet distribution.
+///
+/// The outcomes are points of the probability simplex, that is, vectors of
+/// nonnegative components summing up to one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dirichlet {
+    alpha: Vec<f64>,
+    sum: f64,
+    ln_norm: f64,
+}
+
+impl Dirichlet {
+    /// Create a Dirichlet distribution with concentration parameters `alpha`.
+    ///
+    /// It should hold that `alpha` is nonempty and that its elements are
+    /// positive; the function panics otherwise.
+    #[inline]
+    pub fn new(alpha: &[f64]) -> Self {
+        unwrap!(Dirichlet::try_new(alpha))
+    }
+
+    /// Create a Dirichlet distribution with concentration parameters `alpha`
+    /// or return an error if the parameters are invalid.
+    pub fn try_new(alpha: &[f64]) -> Result<Self, ParameterError> {
+        use special::Gamma;
+        ensure!(!alpha.is_empty(), ParameterError::ConcentrationsEmpty);
+        if let Some(&value) = alpha.iter().find(|&&a| !(a > 0.0 && a.is_finite())) {
+            return Err(ParameterError::AlphaNotPositive { value: value });
+        }
+        let sum = alpha.iter().sum::<f64>();
+        let ln_norm = alpha.iter().map(|&a| Gamma::ln_gamma(a).0).sum::<f64>() -
+                      Gamma::ln_gamma(sum).0;
+        Ok(Dirichlet { alpha: alpha.to_vec(), sum: sum, ln_norm: ln_norm })
+    }
+
+    /// Return the concentration parameters.
+    #[inline(always)]
+    pub fn alpha(&self) -> &[f64] { &self.alpha }
+
+    /// Compute the marginal distribution of component `i`.
+    ///
+    /// The marginal is a beta distribution on `[0, 1]`. It should hold that
+    /// there are at least two components.
+    #[inline]
+    pub fn marginal(&self, i: usize) -> Beta {
+        Beta::new(self.alpha[i], self.sum - self.alpha[i], 0.0, 1.0)
+    }
+}
+
+impl fmt::Display for Dirichlet {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Dirichlet(α = {:?})", self.alpha)
+    }
+}
+
+impl distribution::Multivariate for Dirichlet {
+    type Value = Vec<f64>;
+
+    #[inline]
+    fn dimension(&self) -> usize {
+        self.alpha.len()
+    }
+}
+
+impl distribution::MultivariateContinuous for Dirichlet {
+    /// Compute the natural logarithm of the probability density function.
+    ///
+    /// The density is taken with respect to the Lebesgue measure on the first
+    /// `k - 1` components, and it vanishes outside of the simplex. It should
+    /// hold that the point has as many components as the distribution.
+    fn ln_density(&self, x: &Vec<f64>) -> f64 {
+        should!(x.len() == self.alpha.len());
+        let sum = x.iter().sum::<f64>();
+        if x.iter().any(|&x| !(x >= 0.0)) || (sum - 1.0).abs() > 1e-12 * x.len() as f64 {
+            return ::std::f64::NEG_INFINITY;
+        }
+        x.iter().zip(&self.alpha).map(|(&x, &a)| (a - 1.0) * x.ln()).sum::<f64>() - self.ln_norm
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::MultivariateMean for Dirichlet {
+    #[inline]
+    fn mean_vector(&self) -> Vec<f64> {
+        self.alpha.iter().map(|&a| a / self.sum).collect()
+    }
+}
+
+impl distribution::MultivariateSample for Dirichlet {
+    /// Draw a sample.
+    ///
+    /// Independent gamma variables with the concentration parameters as their
+    /// shape parameters are normalized by their sum.
+    fn sample<S>(&self, source: &mut S) -> Vec<f64> where S: Source {
+        use distribution::gamma;
+        let mut x = self.alpha.iter().map(|&a| gamma::sample(a, source)).collect::<Vec<_>>();
+        let sum = x.iter().sum::<f64>();
+        for x in &mut x {
+            *x /= sum;
+        }
+        x
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::MultivariateVariance for Dirichlet {
+    fn covariance(&self) -> Vec<f64> {
+        let k = self.alpha.len();
+        let mean = self.alpha.iter().map(|&a| a / self.sum).collect::<Vec<_>>();
+        let mut matrix = vec![0.0; k * k];
+        for i in 0..k {
+            for j in 0..k {
+                let delta = if i == j { mean[i] } else { 0.0 };
+                matrix[i * k + j] = (delta - mean[i] * mean[j]) / (self.sum + 1.0);
+            }
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn covariance() {
+        let d = Dirichlet::new(&[1.0, 2.0, 1.0]);
+        assert::close(&d.covariance(),
+                      &vec![0.0375, -0.025, -0.0125, -0.025, 0.05, -0.025, -0.0125, -0.025,
+                            0.0375], 1e-15);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Dirichlet::new(&[1.0, 2.5]).to_string(), "Dirichlet(α = [1.0, 2.5])");
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = Dirichlet::new(&[1.5, 2.0, 0.5]);
+        assert::close(d.ln_density(&vec![0.2, 0.5, 0.3]), 0.44429702932457263568, 1e-14);
+        assert_eq!(d.ln_density(&vec![0.2, 0.5, 0.4]), ::std::f64::NEG_INFINITY);
+        assert_eq!(d.ln_density(&vec![-0.1, 0.5, 0.6]), ::std::f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn marginal() {
+        let d = Dirichlet::new(&[1.5, 2.0, 0.5]);
+        let m = d.marginal(1);
+        assert_eq!((m.alpha(), m.beta()), (2.0, 2.0));
+        assert_eq!(m.mean(), d.mean_vector()[1]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = Dirichlet::new(&[1.5, 2.0, 0.5]);
+        let mut source = source::seeded(42);
+        let n = 100000;
+        let mut mean = vec![0.0; 3];
+        for _ in 0..n {
+            let x = d.sample(&mut source);
+            assert::close(x.iter().sum::<f64>(), 1.0, 1e-14);
+            for i in 0..3 {
+                mean[i] += x[i] / n as f64;
+            }
+        }
+        assert::close(&mean, &d.mean_vector(), 0.005);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(Dirichlet::try_new(&[]).err(), Some(ParameterError::ConcentrationsEmpty));
+        assert_eq!(Dirichlet::try_new(&[1.0, 0.0]).err(),
+                   Some(ParameterError::AlphaNotPositive { value: 0.0 }));
+    }
+}
@@ -0,0 +1,169 @@
+//! Statistical distances between distributions.
+//!
+//! The distances are computed numerically using the adaptive Gauss–Kronrod
+//! rule with the given tolerance on each of the pieces delimited by quantiles
+//! of the two distributions, which follow the probability mass; infinite
+//! endpoints are truncated at the quantiles of order `1e-12` and `1 - 1e-12`.
+//! See `math::integrate`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{Continuous, Inverse};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// Compute the Hellinger distance between `p` and `q`.
+///
+/// The distance is `sqrt(1 - ∫ sqrt(p(x) q(x)) dx)`, which lies in `[0, 1]`.
+/// The integral is normalized by the masses of `p` and `q` computed on the
+/// same pieces, which cancels most of the error due to truncation. It should
+/// hold that `tolerance > 0`.
+pub fn hellinger<P, Q>(p: &P, q: &Q, tolerance: f64) -> f64
+    where P: Continuous + Inverse<Value=f64>, Q: Continuous + Inverse<Value=f64>
+{
+    let points = partition(p, q);
+    let affinity = integrate(|x| (p.density(x) * q.density(x)).sqrt(), &points, tolerance);
+    let mass_p = integrate(|x| p.density(x), &points, tolerance);
+    let mass_q = integrate(|x| q.density(x), &points, tolerance);
+    (1.0 - affinity / (mass_p * mass_q).sqrt()).max(0.0).sqrt()
+}
+
+/// Compute the total variation distance between `p` and `q`.
+///
+/// The distance is `∫ |p(x) - q(x)| dx / 2`, which lies in `[0, 1]`. The
+/// points where the densities cross are located by bisection so that the
+/// integrand is smooth on each piece. It should hold that `tolerance > 0`.
+pub fn total_variation<P, Q>(p: &P, q: &Q, tolerance: f64) -> f64
+    where P: Continuous + Inverse<Value=f64>, Q: Continuous + Inverse<Value=f64>
+{
+    use math;
+
+    let difference = |x| p.density(x) - q.density(x);
+    let points = partition(p, q);
+    let mut crossings = vec![];
+    for pair in points.windows(2) {
+        let step = (pair[1] - pair[0]) / STEPS as f64;
+        for i in 0..STEPS {
+            let (a, b) = (pair[0] + i as f64 * step, pair[0] + (i + 1) as f64 * step);
+            let (lower, upper) = (difference(a), difference(b));
+            if lower <= 0.0 && upper > 0.0 {
+                crossings.push(math::bisect(difference, 0.0, a, b));
+            } else if lower >= 0.0 && upper < 0.0 {
+                crossings.push(math::bisect(|x| -difference(x), 0.0, a, b));
+            }
+        }
+    }
+    let points = merge(points, crossings);
+    let distance = integrate(|x| difference(x).abs(), &points, tolerance) / 2.0;
+    distance.max(0.0).min(1.0)
+}
+
+/// Compute the Wasserstein distance of order one between `p` and `q`.
+///
+/// The distance is computed as `∫ |F_p^{-1}(u) - F_q^{-1}(u)| du` over
+/// `(0, 1)`, which is better behaved numerically than the equivalent integral
+/// of the absolute difference of the distribution functions. It should hold
+/// that `tolerance > 0`.
+pub fn wasserstein<P, Q>(p: &P, q: &Q, tolerance: f64) -> f64
+    where P: Inverse<Value=f64>, Q: Inverse<Value=f64>
+{
+    integrate(|u| (p.inverse(u) - q.inverse(u)).abs(), &levels(), tolerance)
+}
+
+const STEPS: usize = 16;
+
+// Integrate `f` on each of the pieces delimited by `points` and sum up the
+// results.
+fn integrate<F>(f: F, points: &[f64], tolerance: f64) -> f64 where F: Fn(f64) -> f64 {
+    use math;
+
+    should!(points.len() > 1);
+    points.windows(2).fold(0.0, |sum, pair| {
+        sum + math::integrate(&f, pair[0], pair[1], tolerance).value
+    })
+}
+
+// Compute the probability levels from `1e-12` to `1 - 1e-12` that split the
+// unit interval into pieces a decade apart in the tails.
+fn levels() -> Vec<f64> {
+    let mut levels = (1..13).rev().map(|i| 10f64.powi(-i)).collect::<Vec<_>>();
+    levels.push(0.5);
+    levels.extend((1..13).map(|i| 1.0 - 10f64.powi(-i)));
+    levels
+}
+
+// Merge two sets of points into one sorted set without duplicates.
+fn merge(mut points: Vec<f64>, other: Vec<f64>) -> Vec<f64> {
+    points.extend(other);
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    points.dedup();
+    points
+}
+
+// Split the union of the ranges of `p` and `q` at the quantiles of both
+// distributions and at the endpoints of their supports, where the densities
+// can jump.
+fn partition<P, Q>(p: &P, q: &Q) -> Vec<f64> where P: Inverse<Value=f64>, Q: Inverse<Value=f64> {
+    use distribution::range;
+
+    let ((a, b), (c, d)) = (range(p), range(q));
+    let (lower, upper) = (a.min(c), b.max(d));
+    let ((e, f), (g, h)) = (p.support(), q.support());
+    let mut points = vec![lower, upper, e, f, g, h];
+    for level in levels() {
+        points.push(p.inverse(level));
+        points.push(q.inverse(level));
+    }
+    points.retain(|&x| lower <= x && x <= upper);
+    merge(points, vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn hellinger() {
+        use super::hellinger;
+
+        let p = Gaussian::new(0.0, 1.0);
+        assert::close(hellinger(&p, &p, 1e-12), 0.0, 1e-7);
+        assert::close(hellinger(&p, &Gaussian::new(0.0, 2.0), 1e-12),
+                      0.32491969623290628795, 1e-10);
+        assert::close(hellinger(&Gaussian::new(1.0, 2.0), &Gaussian::new(-1.0, 3.0), 1e-12),
+                      0.33221284124764512822, 1e-10);
+        assert::close(hellinger(&Uniform::new(0.0, 1.0), &Uniform::new(2.0, 3.0), 1e-12), 1.0,
+                      1e-15);
+    }
+
+    #[test]
+    fn total_variation() {
+        use super::total_variation;
+
+        let p = Gaussian::new(1.0, 2.0);
+        assert_eq!(total_variation(&p, &p, 1e-12), 0.0);
+        assert::close(total_variation(&p, &Gaussian::new(-1.0, 3.0), 1e-12),
+                      0.34612308398976227488, 1e-10);
+        assert::close(total_variation(&p, &Gaussian::new(2.5, 2.0), 1e-12),
+                      0.29233953334544758875, 1e-8);
+        assert::close(total_variation(&Uniform::new(0.0, 1.0), &Uniform::new(2.0, 3.0), 1e-12),
+                      1.0, 1e-12);
+    }
+
+    #[test]
+    fn wasserstein() {
+        use super::wasserstein;
+
+        let p = Gaussian::new(0.0, 1.0);
+        assert_eq!(wasserstein(&p, &p, 1e-12), 0.0);
+        for &m in &[-3.0, 0.5, 10.0] {
+            assert::close(wasserstein(&p, &Gaussian::new(m, 1.0), 1e-12), m.abs(), 1e-6);
+        }
+        assert::close(wasserstein(&Gaussian::new(1.0, 2.0), &Gaussian::new(-1.0, 3.0), 1e-12),
+                      2.0169814052336592751, 1e-6);
+        assert::close(wasserstein(&Uniform::new(0.0, 1.0), &Exponential::new(1.0), 1e-12),
+                      0.5, 1e-6);
+    }
+}
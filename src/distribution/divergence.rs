@@ -0,0 +1,112 @@
+//! Kullback–Leibler divergences computed numerically.
+//!
+//! The functions are fallbacks for pairs of distributions for which no closed
+//! form is implemented via `Divergence`.
+
+use distribution::Discrete;
+#[cfg(feature = "alloc")]
+use distribution::{Continuous, Inverse};
+#[cfg(not(feature = "std"))]
+use float::Float;
+
+/// Compute the Kullback–Leibler divergence of continuous distribution `q`
+/// from continuous distribution `p`.
+///
+/// The integral of `p(x) ln(p(x) / q(x))` is computed over the support of `p`
+/// using the adaptive Gauss–Kronrod rule on the pieces delimited by quantiles
+/// of `p`, which keeps the probability mass from being overlooked; see
+/// `math::integrate`. The result is infinite when the support of `p` is not
+/// contained in that of `q`.
+#[cfg(feature = "alloc")]
+pub fn continuous<P, Q>(p: &P, q: &Q) -> f64
+    where P: Continuous + Inverse<Value=f64> + ?Sized, Q: Continuous + ?Sized
+{
+    use distribution::nested;
+    use math;
+
+    const LEVELS: [f64; 11] = [1e-12, 1e-9, 1e-6, 1e-3, 0.1, 0.5, 0.9, 1.0 - 1e-3, 1.0 - 1e-6,
+                               1.0 - 1e-9, 1.0 - 1e-12];
+
+    if !nested(p, q) {
+        return ::std::f64::INFINITY;
+    }
+    let (a, b) = p.support();
+    let mut points = vec![a];
+    for &level in &LEVELS {
+        let x = p.inverse(level);
+        if x > points[points.len() - 1] && x < b {
+            points.push(x);
+        }
+    }
+    points.push(b);
+    points.windows(2).fold(0.0, |sum, pair| {
+        sum + math::integrate(|x| {
+            let ln_density = p.ln_density(x);
+            if ln_density == ::std::f64::NEG_INFINITY {
+                return 0.0;
+            }
+            ln_density.exp() * (ln_density - q.ln_density(x))
+        }, pair[0], pair[1], 1e-12).value
+    }).max(0.0)
+}
+
+/// Compute the Kullback–Leibler divergence of discrete distribution `q` from
+/// discrete distribution `p`.
+///
+/// The sum of `p(k) ln(p(k) / q(k))` is computed over the support of `p`; an
+/// infinite support is truncated once the remaining probability falls below
+/// `1e-16`. The result is infinite when `q` vanishes where `p` does not.
+pub fn discrete<P, Q>(p: &P, q: &Q) -> f64
+    where P: Discrete<Value=usize> + ?Sized, Q: Discrete<Value=usize> + ?Sized
+{
+    const TAIL: f64 = 1e-16;
+
+    let (a, b) = p.support();
+    let mut sum = 0.0;
+    let mut k = a.max(0.0) as usize;
+    while (k as f64) <= b {
+        let ln_mass = p.ln_mass(k);
+        if ln_mass > ::std::f64::NEG_INFINITY {
+            sum += ln_mass.exp() * (ln_mass - q.ln_mass(k));
+        }
+        if !b.is_finite() && p.survival(k as f64) < TAIL {
+            break;
+        }
+        k += 1;
+    }
+    sum.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn continuous() {
+        let (p, q) = (Gamma::new(3.0, 1.5), Gamma::new(2.0, 2.5));
+        assert::close(super::continuous(&p, &q), p.kl_divergence(&q), 1e-8);
+        assert::close(super::continuous(&q, &p), q.kl_divergence(&p), 1e-8);
+
+        let (p, q) = (Gaussian::new(1.0, 2.0), Gaussian::new(-1.0, 3.0));
+        assert::close(super::continuous(&p, &q), p.kl_divergence(&q), 1e-8);
+
+        let (p, q) = (Beta::new(2.0, 3.0, 0.0, 1.0), Uniform::new(-1.0, 1.0));
+        assert::close(super::continuous(&p, &q), 2f64.ln() - p.entropy(), 1e-8);
+        assert_eq!(super::continuous(&q, &p), ::std::f64::INFINITY);
+
+        assert_eq!(super::continuous(&Gaussian::new(0.0, 1.0), &Exponential::new(1.0)),
+                   ::std::f64::INFINITY);
+    }
+
+    #[test]
+    fn discrete() {
+        let (p, q) = (Poisson::new(3.5), Poisson::new(2.0));
+        assert::close(super::discrete(&p, &q), p.kl_divergence(&q), 1e-12);
+
+        let (p, q) = (Binomial::new(10, 0.3), Poisson::new(3.0));
+        let e = (0..11).fold(0.0, |sum, k| sum + p.mass(k) * (p.mass(k) / q.mass(k)).ln());
+        assert::close(super::discrete(&p, &q), e, 1e-12);
+        assert_eq!(super::discrete(&q, &p), ::std::f64::INFINITY);
+    }
+}
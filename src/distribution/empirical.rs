@@ -0,0 +1,363 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{self, ModeList};
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Real;
+
+/// An empirical distribution.
+///
+/// The distribution places equal mass on each of the observed values, and
+/// repeated values receive proportionally more mass. The moments are those of
+/// this distribution, that is, the population moments of the data. The
+/// entropy is not supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Empirical {
+    samples: Vec<f64>,
+    interpolation: Interpolation,
+}
+
+/// An interpolation scheme for computing sample quantiles.
+///
+/// Given a probability `p` and `n` sorted observations `x[0], ..., x[n - 1]`,
+/// the quantile is computed at the fractional position `h = (n - 1) * p`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Interpolation {
+    /// Take `x[floor(h)]`.
+    Lower,
+    /// Interpolate linearly between `x[floor(h)]` and `x[ceil(h)]`.
+    Linear,
+    /// Take the observation closest to `h`, rounding halves away from zero.
+    Nearest,
+}
+
+impl Empirical {
+    /// Create an empirical distribution from observations `samples`.
+    ///
+    /// The quantiles are computed using linear interpolation.
+    ///
+    /// It should hold that `samples` is not empty and contains only finite
+    /// values; the function panics otherwise.
+    #[inline]
+    pub fn new(samples: &[f64]) -> Self {
+        unwrap!(Empirical::try_new(samples))
+    }
+
+    /// Create an empirical distribution from observations `samples` or return
+    /// an error if the observations are invalid.
+    #[inline]
+    pub fn try_new(samples: &[f64]) -> Result<Self, ParameterError> {
+        Empirical::try_with_interpolation(samples, Interpolation::Linear)
+    }
+
+    /// Create an empirical distribution from observations `samples` with
+    /// quantiles computed according to `interpolation`.
+    ///
+    /// It should hold that `samples` is not empty and contains only finite
+    /// values; the function panics otherwise.
+    #[inline]
+    pub fn with_interpolation(samples: &[f64], interpolation: Interpolation) -> Self {
+        unwrap!(Empirical::try_with_interpolation(samples, interpolation))
+    }
+
+    /// Create an empirical distribution from observations `samples` with
+    /// quantiles computed according to `interpolation` or return an error if
+    /// the observations are invalid.
+    pub fn try_with_interpolation(samples: &[f64], interpolation: Interpolation)
+                                  -> Result<Self, ParameterError> {
+        ensure!(!samples.is_empty(), ParameterError::SamplesEmpty);
+        for &x in samples {
+            ensure!(x.is_finite(), ParameterError::SampleNotFinite { value: x });
+        }
+        let mut samples = samples.to_vec();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Ok(Empirical { samples: samples, interpolation: interpolation })
+    }
+
+    /// Return the sorted observations.
+    #[inline(always)]
+    pub fn samples(&self) -> &[f64] { &self.samples }
+
+    /// Return the interpolation scheme.
+    #[inline(always)]
+    pub fn interpolation(&self) -> Interpolation { self.interpolation }
+
+    // Count the observations less than `x` (or at most `x` if `inclusive`).
+    fn count(&self, x: f64, inclusive: bool) -> usize {
+        let (mut lower, mut upper) = (0, self.samples.len());
+        while lower < upper {
+            let middle = (lower + upper) / 2;
+            let y = self.samples[middle];
+            if y < x || inclusive && y == x {
+                lower = middle + 1;
+            } else {
+                upper = middle;
+            }
+        }
+        lower
+    }
+
+    fn moment(&self, order: i32) -> f64 {
+        use distribution::Mean;
+        let mean = self.mean();
+        self.samples.iter().fold(0.0, |sum, &x| sum + (x - mean).powi(order)) /
+            self.samples.len() as f64
+    }
+}
+
+impl fmt::Display for Empirical {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Empirical(n = {})", self.samples.len())
+    }
+}
+
+impl distribution::Discrete for Empirical {
+    #[inline]
+    fn mass(&self, x: f64) -> f64 {
+        (self.count(x, true) - self.count(x, false)) as f64 / self.samples.len() as f64
+    }
+}
+
+impl distribution::Distribution for Empirical {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
+        self.count(x, true) as f64 / self.samples.len() as f64
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.samples[0], self.samples[self.samples.len() - 1])
+    }
+}
+
+impl distribution::Inverse for Empirical {
+    /// Compute the sample quantile of order `p`.
+    ///
+    /// The result depends on the interpolation scheme.
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        let h = (self.samples.len() - 1) as f64 * p;
+        let i = h.floor() as usize;
+        match self.interpolation {
+            Interpolation::Lower => self.samples[i],
+            Interpolation::Linear => {
+                let x = self.samples[i];
+                if i + 1 < self.samples.len() {
+                    x + (h - i as f64) * (self.samples[i + 1] - x)
+                } else {
+                    x
+                }
+            },
+            Interpolation::Nearest => self.samples[h.round() as usize],
+        }
+    }
+}
+
+impl distribution::Kurtosis for Empirical {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.moment(4) / self.moment(2).powi(2) - 3.0
+    }
+}
+
+impl distribution::Mean for Empirical {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.samples.iter().fold(0.0, |sum, &x| sum + x) / self.samples.len() as f64
+    }
+}
+
+impl distribution::Median for Empirical {
+    /// Compute the median.
+    ///
+    /// For an even number of observations, the median is the average of the
+    /// two middle ones regardless of the interpolation scheme.
+    fn median(&self) -> f64 {
+        let n = self.samples.len();
+        if n % 2 == 1 {
+            self.samples[n / 2]
+        } else {
+            (self.samples[n / 2 - 1] + self.samples[n / 2]) / 2.0
+        }
+    }
+}
+
+impl distribution::Modes for Empirical {
+    /// Compute the modes.
+    ///
+    /// The modes are the most frequent observations in ascending order.
+    fn modes(&self) -> ModeList<f64> {
+        let mut modes = ModeList::new();
+        let (mut max, mut i) = (0, 0);
+        while i < self.samples.len() {
+            let x = self.samples[i];
+            let mut j = i + 1;
+            while j < self.samples.len() && self.samples[j] == x {
+                j += 1;
+            }
+            if j - i == max {
+                modes.push(x);
+            }
+            if j - i > max {
+                max = j - i;
+                modes = ModeList::one(x);
+            }
+            i = j;
+        }
+        modes
+    }
+}
+
+impl distribution::Sample for Empirical {
+    /// Draw a sample.
+    ///
+    /// The observations are drawn uniformly with replacement.
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        let n = self.samples.len();
+        let i = (source.read::<f64>() * n as f64) as usize;
+        self.samples[if i < n { i } else { n - 1 }]
+    }
+}
+
+impl distribution::Skewness for Empirical {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.moment(3) / self.moment(2).powf(1.5)
+    }
+}
+
+impl distribution::Variance for Empirical {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.moment(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($samples:expr) => (Empirical::new(&$samples));
+        ($samples:expr, $interpolation:ident) => (
+            Empirical::with_interpolation(&$samples, Interpolation::$interpolation)
+        );
+    );
+
+    #[test]
+    fn display() {
+        assert_eq!(Empirical::new(&[3.0, 1.0, 2.0]).to_string(), "Empirical(n = 3)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!([3.0, 1.0, 2.0, 2.0]);
+        let x = vec![0.0, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0];
+        let p = vec![0.0, 0.25, 0.25, 0.75, 0.75, 1.0, 1.0];
+        assert_eq!(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p);
+
+        let d = new!([5.0]);
+        assert_eq!((d.distribution(4.0), d.distribution(5.0)), (0.0, 1.0));
+    }
+
+    #[test]
+    fn inverse() {
+        let samples = [4.0, 1.0, 3.0, 2.0, 2.0];
+        let p = vec![0.0, 0.1, 0.125, 0.3, 0.5, 0.8, 0.9, 1.0];
+
+        let d = new!(samples, Lower);
+        let x = vec![1.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0];
+        assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
+
+        let d = new!(samples, Linear);
+        let x = vec![1.0, 1.4, 1.5, 2.0, 2.0, 3.2, 3.6, 4.0];
+        assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x, 1e-15);
+
+        let d = new!(samples, Nearest);
+        let x = vec![1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 4.0, 4.0];
+        assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
+
+        let d = new!([5.0]);
+        assert_eq!((d.inverse(0.0), d.inverse(0.5), d.inverse(1.0)), (5.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert::close(new!([1.0, 2.0, 3.0, 4.0]).kurtosis(), -1.36, 1e-14);
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!([3.0, 1.0, 2.0, 2.0]);
+        assert_eq!(&[0.0, 1.0, 2.0, 3.0].iter().map(|&x| d.mass(x)).collect::<Vec<_>>(),
+                   &[0.0, 0.25, 0.5, 0.25]);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!([3.0, 1.0, 2.0, 2.0]).mean(), 2.0);
+        assert_eq!(new!([5.0]).mean(), 5.0);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(new!([3.0, 1.0, 2.0]).median(), 2.0);
+        assert_eq!(new!([4.0, 1.0, 3.0, 2.0], Lower).median(), 2.5);
+        assert_eq!(new!([5.0]).median(), 5.0);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!([3.0, 1.0, 2.0, 2.0]).modes(), vec![2.0]);
+        assert_eq!(new!([3.0, 1.0, 3.0, 1.0, 2.0]).modes(), vec![1.0, 3.0]);
+        assert_eq!(new!([5.0]).modes(), vec![5.0]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!([3.0, 1.0, 2.0, 2.0]);
+        let mut counts = [0; 3];
+        for x in Independent(&d, &mut source::seeded(42)).take(10000) {
+            counts[x as usize - 1] += 1;
+        }
+        assert!((counts[0] as f64 / 10000.0 - 0.25).abs() < 0.02);
+        assert!((counts[1] as f64 / 10000.0 - 0.5).abs() < 0.02);
+        assert!((counts[2] as f64 / 10000.0 - 0.25).abs() < 0.02);
+
+        let d = new!([5.0]);
+        assert!(Independent(&d, &mut source::seeded(42)).take(100).all(|x| x == 5.0));
+    }
+
+    #[test]
+    fn skewness() {
+        use std::f64::consts::FRAC_1_SQRT_2;
+        assert_eq!(new!([1.0, 2.0, 3.0]).skewness(), 0.0);
+        assert::close(new!([0.0, 0.0, 3.0]).skewness(), FRAC_1_SQRT_2, 1e-15);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Empirical::try_new(&[1.0, 2.0]).is_ok());
+        assert_eq!(Empirical::try_new(&[]).err(), Some(ParameterError::SamplesEmpty));
+        assert_eq!(Empirical::try_new(&[1.0, -INFINITY]).err(),
+                   Some(ParameterError::SampleNotFinite { value: -INFINITY }));
+        assert!(Empirical::try_new(&[NAN, 1.0]).is_err());
+        assert!(Empirical::try_with_interpolation(&[NAN], Interpolation::Lower).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!([3.0, 1.0, 2.0, 2.0]).variance(), 0.5);
+        assert_eq!(new!([5.0]).variance(), 0.0);
+    }
+}
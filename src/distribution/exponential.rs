@@ -1,8 +1,23 @@
+use std::fmt;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
 use source::Source;
-use distribution;
+use distribution::{self, PointMass};
+#[cfg(feature = "alloc")]
+use distribution::{Fitted, ModeList};
+use distribution::ziggurat::{self, Ziggurat};
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// An exponential distribution.
-#[derive(Clone, Copy)]
+///
+/// The rate is allowed to be infinite, in which case the distribution is the
+/// point mass at zero: the density is infinite at zero, the distribution
+/// function is a step at zero, and every sample is zero. The skewness and
+/// kurtosis remain those of the nondegenerate distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Exponential {
     lambda: f64,
 }
@@ -10,27 +25,179 @@ pub struct Exponential {
 impl Exponential {
     /// Create an exponential distribution with rate `lambda`.
     ///
-    /// It should hold that `lambda > 0`.
+    /// It should hold that `lambda > 0`, and `lambda` may be infinite; the
+    /// function panics otherwise.
     #[inline]
     pub fn new(lambda: f64) -> Self {
-        should!(lambda > 0.0);
-        Exponential { lambda: lambda }
+        unwrap!(Exponential::try_new(lambda))
+    }
+
+    /// Create an exponential distribution with rate `lambda` or return an
+    /// error if the parameter is invalid.
+    #[inline]
+    pub fn try_new(lambda: f64) -> Result<Self, ParameterError> {
+        ensure!(lambda > 0.0, ParameterError::LambdaNotPositive { value: lambda });
+        Ok(Exponential { lambda: lambda })
+    }
+
+    /// Create an exponential distribution with mean `mean`, that is, the
+    /// reciprocal of the rate.
+    ///
+    /// It should hold that `mean > 0`; the function panics otherwise.
+    #[inline]
+    pub fn from_mean(mean: f64) -> Self {
+        unwrap!(Exponential::try_from_mean(mean))
+    }
+
+    /// Create an exponential distribution with mean `mean` or return an error
+    /// if the parameter is invalid.
+    #[inline]
+    pub fn try_from_mean(mean: f64) -> Result<Self, ParameterError> {
+        ensure!(mean > 0.0 && mean.is_finite(), ParameterError::MeanNotPositive { value: mean });
+        Exponential::try_new(1.0 / mean)
+    }
+
+    /// Create the standard exponential distribution, that is, the one with
+    /// rate one.
+    ///
+    /// ```
+    /// use probability::distribution::Exponential;
+    ///
+    /// const STANDARD: Exponential = Exponential::standard();
+    /// assert_eq!(STANDARD, Exponential::new(1.0));
+    /// ```
+    #[inline]
+    pub const fn standard() -> Self {
+        Exponential { lambda: 1.0 }
     }
 
     /// Return the rate parameter.
     #[inline(always)]
     pub fn lambda(&self) -> f64 { self.lambda }
+
+    /// Return the scale parameter, that is, the reciprocal of the rate, which
+    /// is also the mean.
+    #[inline(always)]
+    pub fn scale(&self) -> f64 { 1.0 / self.lambda }
+
+    /// Return the point mass at zero if the distribution is degenerate.
+    #[inline(always)]
+    fn point(&self) -> Option<PointMass> {
+        if self.lambda.is_infinite() { Some(PointMass::new(0.0)) } else { None }
+    }
+
+    /// Fit the distribution to right-censored observations by maximum
+    /// likelihood.
+    ///
+    /// Each observation is a time and a flag telling whether the time is that
+    /// of an event, as opposed to the time at which the observation was
+    /// censored. The rate is estimated by the number of events `d` over the
+    /// total time `T`, and its standard error is `√d / T`.
+    #[cfg(feature = "alloc")]
+    pub fn fit_mle_censored(observations: &[(f64, bool)]) -> Result<Fitted<Self>, FitError> {
+        ensure!(!observations.is_empty(), FitError::DataEmpty);
+        let (mut time, mut events) = (0.0, 0);
+        for &(value, event) in observations {
+            ensure!(value.is_finite(), FitError::DatumNotFinite { value: value });
+            ensure!(value >= 0.0, FitError::DatumOutOfSupport { value: value });
+            time += value;
+            events += event as usize;
+        }
+        ensure!(events > 0, FitError::EventsZero);
+        let distribution = Exponential::try_new(events as f64 / time)?;
+        Ok(Fitted {
+            distribution: distribution,
+            standard_errors: vec![(events as f64).sqrt() / time],
+        })
+    }
+}
+
+impl Default for Exponential {
+    #[inline]
+    fn default() -> Self {
+        Exponential::standard()
+    }
+}
+
+affine!(Exponential);
+
+impl fmt::Display for Exponential {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Exponential(λ = {})", self.lambda)
+    }
 }
 
 impl distribution::Continuous for Exponential {
     #[inline]
     fn density(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.density(x);
+        }
         if x < 0.0 {
             0.0
         } else {
             self.lambda * (-self.lambda * x).exp()
         }
     }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The moment is `!k / λ^k` where `!k` is the number of derangements of
+    /// `k` elements.
+    #[cfg(feature = "alloc")]
+    fn central_moment(&self, k: u32) -> f64 {
+        let derangements = (1..(k + 1)).fold(1.0, |count, i| {
+            i as f64 * count + if i % 2 == 0 { 1.0 } else { -1.0 }
+        });
+        derangements / self.lambda.powi(k as i32)
+    }
+
+    #[inline]
+    fn hazard(&self, x: f64) -> f64 {
+        if x < 0.0 { 0.0 } else { self.lambda }
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.ln_density(x);
+        }
+        if x < 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            self.lambda.ln() - self.lambda * x
+        }
+    }
+
+    /// Compute the log-likelihood of a sample.
+    ///
+    /// The log-likelihood depends on the data only via their number and sum
+    /// unless some of them are negative, in which case it is negative
+    /// infinity.
+    fn log_likelihood(&self, data: &[f64]) -> f64 {
+        use math;
+        if data.iter().any(|&x| x < 0.0) {
+            return ::std::f64::NEG_INFINITY;
+        }
+        if self.point().is_some() {
+            if data.iter().any(|&x| x != 0.0) {
+                return ::std::f64::NEG_INFINITY;
+            }
+            return if data.is_empty() { 0.0 } else { ::std::f64::INFINITY };
+        }
+        data.len() as f64 * self.lambda.ln() - self.lambda * math::pairwise(data, |x| x)
+    }
+
+    #[inline]
+    fn mean_residual_life(&self, x: f64) -> f64 {
+        if x < 0.0 { 1.0 / self.lambda - x } else { 1.0 / self.lambda }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        (1..(k + 1)).fold(1.0, |product, i| product * i as f64 / self.lambda)
+    }
 }
 
 impl distribution::Distribution for Exponential {
@@ -38,12 +205,71 @@ impl distribution::Distribution for Exponential {
 
     #[inline]
     fn distribution(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.distribution(x);
+        }
         if x <= 0.0 {
             0.0
         } else {
             -(-self.lambda * x).exp_m1()
         }
     }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.survival(x).ln();
+        }
+        if x <= 0.0 {
+            0.0
+        } else {
+            -self.lambda * x
+        }
+    }
+
+    #[inline]
+    fn probability(&self, a: f64, b: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.probability(a, b);
+        }
+        let a = a.max(0.0);
+        if b <= a {
+            return 0.0;
+        }
+        (-self.lambda * a).exp() * -(-self.lambda * (b - a)).exp_m1()
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, ::std::f64::INFINITY)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.survival(x);
+        }
+        if x <= 0.0 {
+            1.0
+        } else {
+            (-self.lambda * x).exp()
+        }
+    }
+}
+
+impl distribution::Divergence for Exponential {
+    /// Compute the Kullback–Leibler divergence.
+    ///
+    /// If either distribution is degenerate, the divergence is zero when both
+    /// are and infinite otherwise.
+    #[inline]
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        if self.lambda.is_infinite() || other.lambda.is_infinite() {
+            return if self == other { 0.0 } else { ::std::f64::INFINITY };
+        }
+        let ratio = other.lambda / self.lambda;
+        ratio - 1.0 - ratio.ln()
+    }
 }
 
 impl distribution::Entropy for Exponential {
@@ -53,12 +279,33 @@ impl distribution::Entropy for Exponential {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Exponential {
+    /// Compute the Fisher information matrix with respect to `λ`.
+    #[inline]
+    fn fisher_information(&self) -> Vec<f64> {
+        vec![1.0 / (self.lambda * self.lambda)]
+    }
+}
+
 impl distribution::Inverse for Exponential {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
-        should!(0.0 <= p && p <= 1.0);
+        guard!(inverse(p));
+        if self.point().is_some() {
+            return 0.0;
+        }
         -(-p).ln_1p() / self.lambda
     }
+
+    #[inline]
+    fn inverse_survival(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        if self.point().is_some() {
+            return 0.0;
+        }
+        -p.ln() / self.lambda
+    }
 }
 
 impl distribution::Kurtosis for Exponential {
@@ -66,6 +313,23 @@ impl distribution::Kurtosis for Exponential {
     fn kurtosis(&self) -> f64 { 6.0 }
 }
 
+impl distribution::MaximumLikelihood for Exponential {
+    /// Fit the distribution to `data`.
+    ///
+    /// The rate is estimated by the reciprocal of the mean of the data. If all
+    /// the data are zero, the estimate is the degenerate distribution with an
+    /// infinite rate.
+    fn fit_mle(data: &[f64]) -> Result<Self, FitError> {
+        use distribution::examine;
+        use math;
+        examine(data)?;
+        if let Some(&value) = data.iter().find(|&&x| x < 0.0) {
+            return Err(FitError::DatumOutOfSupport { value: value });
+        }
+        Ok(Exponential::try_new(1.0 / (math::pairwise(data, |x| x) / data.len() as f64))?)
+    }
+}
+
 impl distribution::Mean for Exponential {
     #[inline]
     fn mean(&self) -> f64 {
@@ -81,17 +345,74 @@ impl distribution::Median for Exponential {
     }
 }
 
+impl distribution::MethodOfMoments for Exponential {
+    /// Fit the distribution to mean `mean` and variance `variance`.
+    ///
+    /// The distribution has a single parameter, which is determined by the
+    /// mean; the variance is ignored.
+    fn fit_moments_from(mean: f64, variance: f64) -> Result<Self, FitError> {
+        ensure!(mean > 0.0 && mean.is_finite(),
+                FitError::MomentsInfeasible { mean: mean, variance: variance });
+        Ok(Exponential::try_from_mean(mean)?)
+    }
+}
+
+impl distribution::Mgf for Exponential {
+    #[inline]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        if t < self.lambda { -(-t / self.lambda).ln_1p() } else { ::std::f64::INFINITY }
+    }
+
+    #[inline]
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        1.0 / (self.lambda - t)
+    }
+
+    #[inline]
+    fn ln_mgf_second_derivative(&self, t: f64) -> f64 {
+        (self.lambda - t).powi(-2)
+    }
+
+    #[inline]
+    fn mgf_domain(&self) -> (f64, f64) {
+        (::std::f64::NEG_INFINITY, self.lambda)
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl distribution::Modes for Exponential {
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![0.0]
+    fn modes(&self) -> ModeList<f64> {
+        ModeList::one(0.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Parametric for Exponential {
+    const POSITIVE: &'static [bool] = &[true];
+
+    #[inline]
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    #[inline]
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError> {
+        Exponential::try_new(parameters[0])
     }
 }
 
 impl distribution::Sample for Exponential {
+    /// Draw a sample.
+    ///
+    /// ## References
+    ///
+    /// 1. G. Marsaglia and W. W. Tsang, “The ziggurat method for generating
+    ///    random variables,” Journal of Statistical Software, vol. 5, no. 8,
+    ///    pp. 1–7, 10 2000.
     #[inline]
     fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
-        -source.read::<f64>().ln() / self.lambda
+        sample(source) / self.lambda
     }
 }
 
@@ -112,6 +433,219 @@ impl distribution::Variance for Exponential {
     }
 }
 
+/// Draw a sample from the standard exponential distribution.
+pub fn sample<S: Source>(source: &mut S) -> f64 {
+    // The tail is the standard exponential distribution shifted by `R`.
+    ziggurat::sample(source, &ZIGGURAT, |x| (-x).exp(), |source| {
+        R - (-source.read::<f64>()).ln_1p()
+    })
+}
+
+const R: f64 = 7.69711747013105;
+
+const ZIGGURAT: Ziggurat = Ziggurat { k: &K, w: &W, y: &Y, symmetric: false };
+
+const K: [u64; 256] = [
+    0, 2742928985168064, 3438700186803720, 3744780257810519,
+    3914896975372862, 4022625697542797, 4096776410635448, 4150853606149210,
+    4192001604687416, 4224344877584100, 4250427292531739, 4271901371161555,
+    4289886428824119, 4305167164135198, 4318309783140431, 4329732973408939,
+    4339752937704679, 4348612900760388, 4356502988721768, 4363573953227345,
+    4369946852445019, 4375720012348348, 4380974119031480, 4385776001930299,
+    4390181484145306, 4394237557465219, 4397984061535398, 4401454994146429,
+    4404679543790856, 4407682910787986, 4410486965794400, 4413110782053579,
+    4415571068741702, 4417882526198713, 4420058138987325, 4422109419110770,
+    4424046609003130, 4425878851844253, 4427614335173868, 4429260412563040,
+    4430823707156474, 4432310200160197, 4433725306767516, 4435073941555377,
+    4436360575016074, 4437589282595121, 4438763787369085, 4439887497305302,
+    4440963537889317, 4441994780778251, 4442983869033584, 4443933239400428,
+    4444845142028909, 4445721657973834, 4446564714759240, 4447376100252992,
+    4448157475061632, 4448910383626429, 4449636264176642, 4450336457674983,
+    4451012215872352, 4451664708573597, 4452295030203006, 4452904205747010,
+    4453493196141905, 4454062903166143, 4454614173889474, 4455147804725090,
+    4455664545125435, 4456165100957688, 4456650137590828, 4457120282722584,
+    4457576128971459, 4458018236256244, 4458447133983073, 4458863323057846,
+    4459267277740095, 4459659447352585, 4460040257859578, 4460410113325309,
+    4460769397263133, 4461118473884709, 4461457689257741, 4461787372379910,
+    4462107836175978, 4462419378424319, 4462722282618581, 4463016818769709,
+    4463303244152965, 4463581804004301, 4463852732169940, 4464116251712773,
+    4464372575478779, 4464621906626490, 4464864439122178, 4465100358203283,
+    4465329840812355, 4465553056003595, 4465770165323938, 4465981323170418,
+    4466186677125454, 4466386368271563, 4466580531486826, 4466769295722448,
+    4466952784263502, 4467131114974005, 4467304400527265, 4467472748622447,
+    4467636262188208, 4467795039574164, 4467949174730939, 4468098757379442,
+    4468243873170018, 4468384603832024, 4468521027314373, 4468653217917529,
+    4468781246417428, 4468905180181701, 4469025083278642, 4469141016579233,
+    4469253037852582, 4469361201855066, 4469465560413473, 4469566162502383,
+    4469663054316032, 4469756279334881, 4469845878387080, 4469931889704995,
+    4470014348976986, 4470093289394551, 4470168741694983, 4470240734199652,
+    4470309292847996, 4470374441227331, 4470436200598525, 4470494589917604,
+    4470549625853344, 4470601322800852, 4470649692891184, 4470694745996980,
+    4470736489734115, 4470774929459349, 4470810068263924, 4470841906963074,
+    4470870444081369, 4470895675833820, 4470917596102651, 4470936196409614,
+    4470951465883736, 4470963391224346, 4470971956659198, 4470977143897542,
+    4470978932077904, 4470977297710362, 4470972214613072, 4470963653842747,
+    4470951583618801, 4470935969240827, 4470916772999008, 4470893954077117,
+    4470867468447603, 4470837268758337, 4470803304210460, 4470765520426768,
+    4470723859310029, 4470678258890503, 4470628653161980, 4470574971905457,
+    4470517140499614, 4470455079717082, 4470388705505446, 4470317928751818,
+    4470242655029689, 4470162784326668, 4470078210751556, 4469988822219058,
+    4469894500110288, 4469795118907000, 4469690545797298, 4469580640250320,
+    4469465253557163, 4469344228335006, 4469217397991048, 4469084586142556,
+    4468945605988874, 4468800259630802, 4468648337332217, 4468489616718258,
+    4468323861903709, 4468150822544456, 4467970232804101, 4467781810226787,
+    4467585254506222, 4467380246139658, 4467166444954115, 4466943488490516,
+    4466710990229517, 4466468537640690, 4466215690034133, 4465951976190801,
+    4465676891744455, 4465389896284246, 4465090410142477, 4464777810826750,
+    4464451429049612, 4464110544301482, 4463754379904174, 4463382097472202,
+    4462992790697122, 4462585478355952, 4462159096427753, 4461712489182115,
+    4461244399078944, 4460753455289386, 4460238160612098, 4459696876515553,
+    4459127805983955, 4458528973779075, 4457898203649721, 4457233091920645,
+    4456530976767893, 4455788902331217, 4455003576616606, 4454171321891082,
+    4453288015951104, 4452349022232650, 4451349106194827, 4450282334707461,
+    4449141954247902, 4447920242480610, 4446608326137821, 4445195955871677,
+    4443671225661689, 4442020220072462, 4440226566619900, 4438270861888260,
+    4436129927556551, 4433775834104270, 4431174602388627, 4428284451100006,
+    4425053392146958, 4421415870372502, 4417287970124083, 4412560416174563,
+    4407088078325945, 4400673742272493, 4393042098597074, 4383796248451588,
+    4372341169422858, 4357740343059956, 4338425130125967, 4311541827049176,
+    4271262897902398, 4203411844498905, 4061213381260384, 3985772928715747,
+];
+
+const W: [f64; 256] = [
+    1.4178028487910822e-17, 2.3278824993382435e-17, 3.0487830247064302e-17, 3.6665697714474854e-17,
+    4.2179302189289702e-17, 4.7222561556862733e-17, 5.1911915446217861e-17, 5.6323471083955022e-17,
+    6.0510082606427634e-17, 6.4510165096727506e-17, 6.8352646803700553e-17, 7.2059939574689050e-17,
+    7.5649815537392969e-17, 7.9136643961951065e-17, 8.2532235563518929e-17, 8.5846436168850513e-17,
+    8.9087554865647441e-17, 9.2262679629663743e-17, 9.5377914505292731e-17, 9.8438560874559294e-17,
+    1.0144925809006298e-16, 1.0441409405585350e-16, 1.0733669323436392e-16, 1.1022028745670196e-16,
+    1.1306777346479339e-16, 1.1588176009705538e-16, 1.1866460730417891e-16, 1.2141845865694364e-16,
+    1.2414526862326389e-16, 1.2684682560606155e-16, 1.2952477151912287e-16, 1.3218061851538813e-16,
+    1.3481576335745447e-16, 1.3743149982367627e-16, 1.4002902946807862e-16, 1.4260947099321292e-16,
+    1.4517386844829302e-16, 1.4772319842763586e-16, 1.5025837641447459e-16, 1.5278026239101654e-16,
+    1.5528966581595701e-16, 1.5778735005459586e-16, 1.6027403633350916e-16, 1.6275040728083529e-16,
+    1.6521711010420081e-16, 1.6767475945078284e-16, 1.7012393998770651e-16, 1.7256520873568231e-16,
+    1.7499909718432370e-16, 1.7742611321380509e-16, 1.7984674284430719e-16, 1.8226145183195823e-16,
+    1.8467068712763581e-16, 1.8707487821298260e-16, 1.8947443832625904e-16, 1.9186976558916000e-16,
+    1.9426124404443047e-16, 1.9664924461299028e-16, 1.9903412597830149e-16, 2.0141623540485904e-16,
+    2.0379590949693886e-16, 2.0617347490308444e-16, 2.0854924897123779e-16, 2.1092354035891533e-16,
+    2.1329664960238301e-16, 2.1566886964838975e-16, 2.1804048635167012e-16, 2.2041177894111565e-16,
+    2.2278302045723950e-16, 2.2515447816331350e-16, 2.2752641393233694e-16, 2.2989908461180191e-16,
+    2.3227274236804366e-16, 2.3464763501180921e-16, 2.3702400630653394e-16, 2.3940209626069308e-16,
+    2.4178214140547715e-16, 2.4416437505894128e-16, 2.4654902757768309e-16, 2.4893632659702260e-16,
+    2.5132649726057980e-16, 2.5371976244007966e-16, 2.5611634294614997e-16, 2.5851645773082396e-16,
+    2.6092032408240592e-16, 2.6332815781331461e-16, 2.6574017344147633e-16, 2.6815658436579999e-16,
+    2.7057760303623519e-16, 2.7300344111887965e-16, 2.7543430965657629e-16, 2.7787041922541283e-16,
+    2.8031198008751436e-16, 2.8275920234049709e-16, 2.8521229606393314e-16, 2.8767147146315813e-16,
+    2.9013693901073764e-16, 2.9260890958589524e-16, 2.9508759461219043e-16, 2.9757320619372526e-16,
+    3.0006595725014749e-16, 3.0256606165070798e-16, 3.0507373434762526e-16, 3.0758919150899949e-16,
+    3.1011265065151553e-16, 3.1264433077316765e-16, 3.1518445248623538e-16, 3.1773323815073693e-16,
+    3.2029091200858345e-16, 3.2285770031865588e-16, 3.2543383149302620e-16, 3.2801953623454369e-16,
+    3.3061504767600748e-16, 3.3322060152114851e-16, 3.3583643618764582e-16, 3.3846279295240455e-16,
+    3.4109991609932606e-16, 3.4374805306980643e-16, 3.4640745461620177e-16, 3.4907837495850689e-16,
+    3.5176107194449837e-16, 3.5445580721360140e-16, 3.5716284636474662e-16, 3.5988245912849289e-16,
+    3.6261491954370041e-16, 3.6536050613905055e-16, 3.6811950211971766e-16, 3.7089219555951399e-16,
+    3.7367887959883864e-16, 3.7647985264877851e-16, 3.7929541860172344e-16, 3.8212588704887541e-16,
+    3.8497157350504891e-16, 3.8783279964118003e-16, 3.9070989352498193e-16, 3.9360318987020762e-16,
+    3.9651303029500396e-16, 3.9943976358986856e-16, 4.0238374599574708e-16, 4.0534534149283981e-16,
+    4.0832492210071790e-16, 4.1132286819038372e-16, 4.1433956880894755e-16, 4.1737542201763209e-16,
+    4.2043083524385866e-16, 4.2350622564821533e-16, 4.2660202050715597e-16, 4.2971865761233276e-16,
+    4.3285658568752109e-16, 4.3601626482415690e-16, 4.3919816693657424e-16, 4.4240277623809929e-16,
+    4.4563058973923621e-16, 4.4888211776926182e-16, 4.5215788452263485e-16, 4.5545842863172431e-16,
+    4.5878430376746237e-16, 4.6213607926964276e-16, 4.6551434080870702e-16, 4.6891969108099167e-16,
+    4.7235275053955490e-16, 4.7581415816285544e-16, 4.7930457226372480e-16, 4.8282467134125875e-16,
+    4.8637515497845129e-16, 4.8995674478861424e-16, 4.9357018541385785e-16, 4.9721624557917044e-16,
+    5.0089571920591151e-16, 5.0460942658884350e-16, 5.0835821564116254e-16, 5.1214296321235425e-16,
+    5.1596457648410628e-16, 5.1982399444994948e-16, 5.2372218948478504e-16, 5.2766016901098866e-16,
+    5.3163897726836912e-16, 5.3565969719590523e-16, 5.3972345243389798e-16, 5.4383140945596380e-16,
+    5.4798477984116306e-16, 5.5218482269752352e-16, 5.5643284724928731e-16, 5.6073021560139679e-16,
+    5.6507834569605083e-16, 5.6947871447763502e-16, 5.7393286128396363e-16, 5.7844239148359932e-16,
+    5.8300898038105883e-16, 5.8763437741400583e-16, 5.9232041066909333e-16, 5.9706899174600925e-16,
+    6.0188212100252383e-16, 6.0676189321700078e-16, 6.1171050370897237e-16, 6.1673025496306220e-16,
+    6.2182356380685347e-16, 6.2699296919933292e-16, 6.3224114069342134e-16, 6.3757088764394282e-16,
+    6.4298516924135967e-16, 6.4848710546189052e-16, 6.5407998903644829e-16, 6.5976729855445683e-16,
+    6.6555271283433448e-16, 6.7144012671064901e-16, 6.7743366840910123e-16, 6.8353771870512760e-16,
+    6.8975693209068498e-16, 6.9609626020748865e-16, 7.0256097784459608e-16, 7.0915671184495857e-16,
+    7.1588947332085551e-16, 7.2276569364381222e-16, 7.2979226475290871e-16, 7.3697658441912436e-16,
+    7.4432660721604166e-16, 7.5185090208325141e-16, 7.5955871753377507e-16, 7.6746005575784293e-16,
+    7.7556575712157916e-16, 7.8388759686228587e-16, 7.9243839615735510e-16, 8.0123215021130844e-16,
+    8.1028417659131473e-16, 8.1961128778061260e-16, 8.2923199285818101e-16, 8.3916673441467988e-16,
+    8.4943816836487711e-16, 8.6007149633349424e-16, 8.7109486293879040e-16, 8.8253983380721408e-16,
+    8.9444197485198646e-16, 9.0684155971316690e-16, 9.1978444098118649e-16, 9.3332313294229516e-16,
+    9.4751817065249860e-16, 9.6243983456584759e-16, 9.7817036547844198e-16, 9.9480684723838815e-16,
+    1.0124650144288321e-15, 1.0312843657756168e-15, 1.0514351604044552e-15, 1.0731281954224043e-15,
+    1.0966288068517408e-15, 1.1222774909350319e-15, 1.1505212963006663e-15, 1.1819635283304206e-15,
+    1.2174462832361817e-15, 1.2581958069755114e-15, 1.3060984107128082e-15, 1.3642786158057859e-15,
+    1.4384889932178725e-15, 1.5412190700064194e-15, 1.7091034077168055e-15, 1.9311480126418370e-15,
+];
+
+const Y: [f64; 256] = [
+    1.0000000000000000e0, 9.3814368086217470e-1, 9.0046992992574648e-1, 8.7170433238120371e-1,
+    8.4778550062398972e-1, 8.2699329664305044e-1, 8.0842165152300849e-1, 7.9152763697249573e-1,
+    7.7595685204011566e-1, 7.6146338884989628e-1, 7.4786862198519510e-1, 7.3503809243142348e-1,
+    7.2286765959357202e-1, 7.1127476080507601e-1, 7.0019265508278816e-1, 6.8956649611707799e-1,
+    6.7935057226476536e-1, 6.6950631673192473e-1, 6.6000084107899970e-1, 6.5080583341457099e-1,
+    6.4189671642726598e-1, 6.3325199421436595e-1, 6.2485273870366576e-1, 6.1668218091520743e-1,
+    6.0872538207962190e-1, 6.0096896636523212e-1, 5.9340090169173332e-1, 5.8601031847726792e-1,
+    5.7878735860284491e-1, 5.7172304866482571e-1, 5.6480919291240017e-1, 5.5803828226258745e-1,
+    5.5140341654064129e-1, 5.4489823767243961e-1, 5.3851687200286180e-1, 5.3225388026304321e-1,
+    5.2610421398361962e-1, 5.2006317736823349e-1, 5.1412639381474845e-1, 5.0828977641064277e-1,
+    5.0254950184134761e-1, 4.9690198724154944e-1, 4.9134386959403242e-1, 4.8587198734188480e-1,
+    4.8048336393045410e-1, 4.7517519303737726e-1, 4.6994482528395987e-1, 4.6478975625042607e-1,
+    4.5970761564213758e-1, 4.5469615747461539e-1, 4.4975325116275489e-1, 4.4487687341454840e-1,
+    4.4006510084235378e-1, 4.3531610321563652e-1, 4.3062813728845878e-1, 4.2599954114303429e-1,
+    4.2142872899761652e-1, 4.1691418643300282e-1, 4.1245446599716107e-1, 4.0804818315203228e-1,
+    4.0369401253053017e-1, 3.9939068447523096e-1, 3.9513698183329005e-1, 3.9093173698479700e-1,
+    3.8677382908413760e-1, 3.8266218149600972e-1, 3.7859575940958073e-1, 3.7457356761590210e-1,
+    3.7059464843514595e-1, 3.6665807978151410e-1, 3.6276297335481772e-1, 3.5890847294874972e-1,
+    3.5509375286678740e-1, 3.5131801643748328e-1, 3.4758049462163693e-1, 3.4388044470450235e-1,
+    3.4021714906677997e-1, 3.3658991402867749e-1, 3.3299806876180887e-1, 3.2944096426413622e-1,
+    3.2591797239355608e-1, 3.2242848495608900e-1, 3.1897191284495707e-1, 3.1554768522712878e-1,
+    3.1215524877417944e-1, 3.0879406693456002e-1, 3.0546361924459009e-1, 3.0216340067569336e-1,
+    2.9889292101558163e-1, 2.9565170428126109e-1, 2.9243928816189246e-1, 2.8925522348967764e-1,
+    2.8609907373707677e-1, 2.8297041453878069e-1, 2.7986883323697281e-1, 2.7679392844851725e-1,
+    2.7374530965280286e-1, 2.7072259679905991e-1, 2.6772541993204468e-1, 2.6475341883506209e-1,
+    2.6180624268936287e-1, 2.5888354974901612e-1, 2.5598500703041527e-1, 2.5311029001562935e-1,
+    2.5025908236886218e-1, 2.4743107566532752e-1, 2.4462596913189200e-1, 2.4184346939887710e-1,
+    2.3908329026244904e-1, 2.3634515245705950e-1, 2.3362878343743321e-1, 2.3093391716962730e-1,
+    2.2826029393071659e-1, 2.2560766011668396e-1, 2.2297576805812008e-1, 2.2036437584335941e-1,
+    2.1777324714870044e-1, 2.1520215107537860e-1, 2.1265086199297820e-1, 2.1011915938898817e-1,
+    2.0760682772422195e-1, 2.0511365629383763e-1, 2.0263943909370893e-1, 2.0018397469191118e-1,
+    1.9774706610509876e-1, 1.9532852067956313e-1, 1.9292814997677124e-1, 1.9054576966319531e-1,
+    1.8818119940425421e-1, 1.8583426276219703e-1, 1.8350478709776738e-1, 1.8119260347549621e-1,
+    1.7889754657247822e-1, 1.7661945459049480e-1, 1.7435816917135338e-1, 1.7211353531531995e-1,
+    1.6988540130252755e-1, 1.6767361861725008e-1, 1.6547804187493589e-1, 1.6329852875190171e-1,
+    1.6113493991759192e-1, 1.5898713896931410e-1, 1.5685499236936512e-1, 1.5473836938446797e-1,
+    1.5263714202744277e-1, 1.5055118500103981e-1, 1.4848037564386671e-1, 1.4642459387834486e-1,
+    1.4438372216063469e-1, 1.4235764543247212e-1, 1.4034625107486237e-1, 1.3834942886358015e-1,
+    1.3636707092642880e-1, 1.3439907170221357e-1, 1.3244532790138747e-1, 1.3050573846833072e-1,
+    1.2858020454522812e-1, 1.2666862943751062e-1, 1.2477091858083091e-1, 1.2288697950954508e-1,
+    1.2101672182667478e-1, 1.1916005717532763e-1, 1.1731689921155551e-1, 1.1548716357863348e-1,
+    1.1367076788274426e-1, 1.1186763167005624e-1, 1.1007767640518533e-1, 1.0830082545103374e-1,
+    1.0653700405000160e-1, 1.0478613930657012e-1, 1.0304816017125766e-1, 1.0132299742595358e-1,
+    9.9610583670637076e-2, 9.7910853311492144e-2, 9.6223742550432742e-2, 9.4549189376055803e-2,
+    9.2887133556043500e-2, 9.1237516631040114e-2, 8.9600281910032817e-2, 8.7975374467270176e-2,
+    8.6362741140756871e-2, 8.4762330532368091e-2, 8.3174093009632341e-2, 8.1597980709237378e-2,
+    8.0033947542319864e-2, 7.8481949201606380e-2, 7.6941943170480462e-2, 7.5413888734058354e-2,
+    7.3897746992364691e-2, 7.2393480875708682e-2, 7.0901055162371773e-2, 6.9420436498728699e-2,
+    6.7951593421936560e-2, 6.6494496385339733e-2, 6.5049117786753707e-2, 6.3615431999807279e-2,
+    6.2193415408540946e-2, 6.0783046445479584e-2, 5.9384305633420210e-2, 5.7997175631200604e-2,
+    5.6621641283742821e-2, 5.5257689676696989e-2, 5.3905310196046038e-2, 5.2564494593071644e-2,
+    5.1235237055126233e-2, 4.9917534282706330e-2, 4.8611385573379448e-2, 4.7316792913181506e-2,
+    4.6033761076175128e-2, 4.4762297732943240e-2, 4.3502413568888142e-2, 4.2254122413316192e-2,
+    4.1017441380414785e-2, 3.9792391023374091e-2, 3.8578995503074830e-2, 3.7377282772959333e-2,
+    3.6187284781931395e-2, 3.5009037697397390e-2, 3.3842582150874309e-2, 3.2687963508959514e-2,
+    3.1545232172893588e-2, 3.0414443910466590e-2, 2.9295660224637379e-2, 2.8188948763978622e-2,
+    2.7094383780955786e-2, 2.6012046645134207e-2, 2.4942026419731773e-2, 2.3884420511558160e-2,
+    2.2839335406385230e-2, 2.1806887504283574e-2, 2.0787204072578110e-2, 1.9780424338009736e-2,
+    1.8786700744696024e-2, 1.7806200410911355e-2, 1.6839106826039941e-2, 1.5885621839973156e-2,
+    1.4945968011691143e-2, 1.4020391403181932e-2, 1.3109164931254986e-2, 1.2212592426255376e-2,
+    1.1331013597834593e-2, 1.0464810181029975e-2, 9.6144136425022064e-3, 8.7803149858089718e-3,
+    7.9630774380170365e-3, 7.1633531836349821e-3, 6.3819059373191774e-3, 5.6196422072054813e-3,
+    4.8776559835423906e-3, 4.1572951208337936e-3, 3.4602647778369027e-3, 2.7887987935740748e-3,
+    2.1459677437189054e-3, 1.5362997803015719e-3, 9.6726928232717421e-4, 4.5413435384149660e-4,
+];
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -121,6 +655,56 @@ mod tests {
         ($lambda:expr) => (Exponential::new($lambda));
     );
 
+    #[test]
+    fn central_moment() {
+        let d = new!(2.0);
+        assert_eq!(d.central_moment(1), 0.0);
+        assert_eq!(d.central_moment(2), 0.25);
+        assert_eq!(d.central_moment(3), 0.25);
+        assert_eq!(d.central_moment(5), 1.375);
+        assert_eq!(d.central_moment(8), 57.94140625);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-14);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-14);
+    }
+
+    #[test]
+    fn cumulative_hazard() {
+        let d = new!(2.0);
+        assert_eq!(d.cumulative_hazard(-1.0), 0.0);
+        assert_eq!(d.cumulative_hazard(1.5), 3.0);
+        assert_eq!(d.cumulative_hazard(1000.0), 2000.0);
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Exponential::default(), Exponential::new(1.0));
+    }
+
+    #[test]
+    fn degenerate() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = new!(INFINITY);
+        let x = [NEG_INFINITY, -1.0, 0.0, 1.0, INFINITY];
+        assert_eq!(d.density_vec(&x), vec![0.0, 0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!(d.distribution_vec(&x), vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!((d.survival(-1.0), d.survival(0.0)), (1.0, 0.0));
+        assert_eq!((d.ln_density(0.0), d.ln_density(1.0)), (INFINITY, NEG_INFINITY));
+        assert_eq!((d.ln_survival(-1.0), d.ln_survival(0.0)), (0.0, NEG_INFINITY));
+        assert_eq!((d.probability(-1.0, 0.0), d.probability(0.0, 1.0)), (1.0, 0.0));
+        for &p in &[0.0, 0.25, 1.0] {
+            assert_eq!((d.inverse(p), d.inverse_survival(p)), (0.0, 0.0));
+        }
+        assert_eq!((d.log_likelihood(&[0.0, 0.0]), d.log_likelihood(&[0.0, 1.0])),
+                   (INFINITY, NEG_INFINITY));
+        assert!(d.samples(&mut source::seeded(42), 10).iter().all(|&x| x == 0.0));
+        assert_eq!((d.mean(), d.median(), d.variance()), (0.0, 0.0, 0.0));
+        assert_eq!((d.skewness(), d.kurtosis(), d.entropy()), (2.0, 6.0, NEG_INFINITY));
+        assert_eq!(d.kl_divergence(&d), 0.0);
+        assert_eq!((d.kl_divergence(&new!(1.0)), new!(1.0).kl_divergence(&d)),
+                   (INFINITY, INFINITY));
+    }
+
     #[test]
     fn density() {
         let d = new!(2.0);
@@ -135,6 +719,11 @@ mod tests {
         assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-15);
     }
 
+    #[test]
+    fn display() {
+        assert_eq!(Exponential::new(2.0).to_string(), "Exponential(λ = 2)");
+    }
+
     #[test]
     fn distribution() {
         let d = new!(2.0);
@@ -156,6 +745,115 @@ mod tests {
         assert_eq!(new!(E).entropy(), 0.0);
     }
 
+    #[test]
+    fn fisher_information() {
+        assert_eq!(new!(2.0).fisher_information(), vec![0.25]);
+    }
+
+    #[test]
+    fn fit_mle() {
+        use std::f64::NAN;
+
+        assert_eq!(Exponential::fit_mle(&[0.25, 0.75, 0.5]), Ok(new!(2.0)));
+
+        let n = 100000;
+        let x = new!(2.0).samples(&mut source::seeded(42), n);
+        let d = Exponential::fit_mle(&x).unwrap();
+        assert!((d.lambda() - 2.0).abs() < 5.0 * 2.0 / (n as f64).sqrt());
+
+        assert_eq!(Exponential::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Exponential::fit_mle(&[1.0, -0.5]),
+                   Err(FitError::DatumOutOfSupport { value: -0.5 }));
+        assert!(Exponential::fit_mle(&[1.0, NAN]).is_err());
+        assert_eq!(Exponential::fit_mle(&[0.0, 0.0]), Ok(new!(::std::f64::INFINITY)));
+    }
+
+    #[test]
+    fn fit_mle_censored() {
+        use std::f64::INFINITY;
+
+        let observations = [(1.0, true), (2.0, false), (3.0, true), (3.0, true), (0.5, false)];
+        let fitted = Exponential::fit_mle_censored(&observations).unwrap();
+        assert_eq!(fitted.distribution, new!(3.0 / 9.5));
+        assert_eq!(fitted.standard_errors, vec![3f64.sqrt() / 9.5]);
+
+        let (n, limit) = (100000, 1.0);
+        let x = new!(0.8).samples(&mut source::seeded(42), n);
+        let observations = x.iter().map(|&x| (x.min(limit), x <= limit)).collect::<Vec<_>>();
+        let fitted = Exponential::fit_mle_censored(&observations).unwrap();
+        let lambda = fitted.distribution.lambda();
+        assert!((lambda - 0.8).abs() < 5.0 * fitted.standard_errors[0]);
+        assert!(fitted.standard_errors[0] < 0.01);
+        let naive = Exponential::fit_mle(&observations.iter().filter(|&&(_, event)| event)
+                                                       .map(|&(x, _)| x)
+                                                       .collect::<Vec<_>>()).unwrap();
+        assert!(naive.lambda() > 1.5);
+
+        assert_eq!(Exponential::fit_mle_censored(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Exponential::fit_mle_censored(&[(1.0, false), (2.0, false)]),
+                   Err(FitError::EventsZero));
+        assert_eq!(Exponential::fit_mle_censored(&[(1.0, true), (-1.0, false)]),
+                   Err(FitError::DatumOutOfSupport { value: -1.0 }));
+        assert_eq!(Exponential::fit_mle_censored(&[(INFINITY, true)]),
+                   Err(FitError::DatumNotFinite { value: INFINITY }));
+        assert_eq!(Exponential::fit_mle_censored(&[(0.0, true)]).unwrap().distribution,
+                   new!(INFINITY));
+    }
+
+    #[test]
+    fn fit_mle_with_ci() {
+        let d = new!(2.0);
+        let mut source = source::seeded(42);
+        let (m, n) = (2000, 50);
+        let mut covered = 0;
+        for _ in 0..m {
+            let x = d.samples(&mut source, n);
+            let fit = Exponential::fit_mle_with_ci(&x, 0.95).unwrap();
+            let (a, b) = fit.intervals[0];
+            covered += (a < 2.0 && 2.0 < b) as usize;
+            assert!(a > 0.0);
+            assert::close(fit.standard_errors[0], fit.distribution.lambda() / (n as f64).sqrt(),
+                          1e-12);
+        }
+        let coverage = covered as f64 / m as f64;
+        assert!(0.93 < coverage && coverage < 0.97);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let x = new!(2.0).samples(&mut source::seeded(42), 1000);
+        let mean = x.iter().sum::<f64>() / 1000.0;
+        assert::close(Exponential::fit_moments(&x).unwrap().mean(), mean, 1e-12);
+
+        assert_eq!(Exponential::fit_moments_from(0.5, 1.0), Ok(new!(2.0)));
+        assert_eq!(Exponential::fit_moments_from(0.0, 1.0),
+                   Err(FitError::MomentsInfeasible { mean: 0.0, variance: 1.0 }));
+    }
+
+    #[test]
+    fn from_mean() {
+        let d = Exponential::from_mean(0.5);
+        assert_eq!(d, new!(2.0));
+        assert_eq!((d.mean(), d.scale()), (0.5, 0.5));
+        assert_eq!(Exponential::try_from_mean(0.0),
+                   Err(ParameterError::MeanNotPositive { value: 0.0 }));
+    }
+
+    #[test]
+    fn hazard() {
+        let d = new!(2.0);
+        let x = vec![-1.0, 0.0, 0.5, 10.0, 1000.0];
+        assert_eq!(&x.iter().map(|&x| d.hazard(x)).collect::<Vec<_>>(),
+                   &vec![0.0, 2.0, 2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn hpd_interval() {
+        let (lower, upper) = new!(2.0).hpd_interval(0.9);
+        assert_eq!(lower, 0.0);
+        assert::close(upper, 10f64.ln() / 2.0, 1e-12);
+    }
+
     #[test]
     fn inverse() {
         use std::f64::INFINITY;
@@ -175,16 +873,77 @@ mod tests {
         assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x, 1e-14);
     }
 
+    #[test]
+    fn inverse_survival() {
+        use std::f64::INFINITY;
+
+        let d = new!(2.0);
+        let p = vec![0.0, 0.05, 0.5, 0.95, 1.0];
+        assert::close(&p.iter().map(|&p| d.inverse_survival(p)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| d.inverse(1.0 - p)).collect::<Vec<_>>(), 1e-14);
+        assert_eq!(d.inverse_survival(0.0), INFINITY);
+        assert::close(d.inverse_survival(1e-300), 345.38776394910684, 1e-12);
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let p = new!(2.0);
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&new!(3.0)), 0.094534891891835618022, 1e-15);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(2.0).kurtosis(), 6.0);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(2.0);
+        let x = vec![-1.0, 0.0, 0.5, 4.0];
+        assert::close(&x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.density(x).ln()).collect::<Vec<_>>(), 1e-14);
+        assert_eq!(d.density(1000.0), 0.0);
+        assert_eq!(d.ln_density(1000.0), 2.0f64.ln() - 2000.0);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(2.0);
+        assert_eq!(d.ln_survival(-1.0), 0.0);
+        assert_eq!(d.ln_survival(1.5), -3.0);
+        assert_eq!(d.ln_survival(1000.0), -2000.0);
+    }
+
+    #[test]
+    fn log_likelihood() {
+        use math;
+
+        let d = new!(2.0);
+        let x = vec![0.0, 0.5, 4.0];
+        assert::close(d.log_likelihood(&x), x.iter().map(|&x| d.ln_density(x)).sum::<f64>(),
+                      1e-14);
+        assert_eq!(d.log_likelihood(&[0.5, -1.0]), ::std::f64::NEG_INFINITY);
+
+        let x = Independent(&new!(0.5), &mut source::seeded(42)).take(1_000_000)
+                                                               .collect::<Vec<_>>();
+        let e = math::sum(x.iter().map(|&x| d.ln_density(x)));
+        assert::close(d.log_likelihood(&x) / e, 1.0, 1e-10);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0).mean(), 0.5);
     }
 
+    #[test]
+    fn mean_residual_life() {
+        let d = new!(2.0);
+        let x = vec![-1.0, 0.0, 0.5, 10.0, 1000.0];
+        assert_eq!(&x.iter().map(|&x| d.mean_residual_life(x)).collect::<Vec<_>>(),
+                   &vec![1.5, 0.5, 0.5, 0.5, 0.5]);
+    }
+
     #[test]
     fn median() {
         use std::f64::consts::LN_2;
@@ -196,11 +955,117 @@ mod tests {
         assert_eq!(new!(2.0).modes(), vec![0.0]);
     }
 
+    #[test]
+    fn moment() {
+        let d = new!(2.0);
+        assert_eq!(d.moment(0), 1.0);
+        assert_eq!(d.moment(1), 0.5);
+        assert_eq!(d.moment(8), 157.5);
+    }
+
+    #[test]
+    fn probability() {
+        let d = new!(2.0);
+        assert::close(d.probability(-1.0, 1.0), d.distribution(1.0), 1e-15);
+        assert::close(d.probability(0.5, 1.5), d.distribution(1.5) - d.distribution(0.5), 1e-15);
+        assert_eq!(d.probability(1.0, 0.5), 0.0);
+        assert_eq!(d.probability(-2.0, -1.0), 0.0);
+        assert::close(d.probability(1e-10, 2e-10) / 2e-10, 1.0, 1e-9);
+        assert_eq!(d.distribution(100.001) - d.distribution(100.0), 0.0);
+        assert::close(d.probability(100.0, 100.001) / 2.7650271046931418002e-90, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn sample() {
+        let n = 10000000;
+        let d = Exponential::standard();
+        let x = d.samples(&mut source::seeded(42), n);
+
+        let sums = x.iter().fold([0.0; 4], |sums, &x| {
+            let y = x * x;
+            [sums[0] + x, sums[1] + y, sums[2] + x * y, sums[3] + y * y]
+        });
+        let moment = |k: usize| sums[k - 1] / n as f64;
+        assert!((moment(1) - 1.0).abs() < 5.0 / (n as f64).sqrt());
+        assert!((moment(2) - 2.0).abs() < 5.0 * (20.0 / n as f64).sqrt());
+        assert!((moment(3) - 6.0).abs() < 5.0 * (684.0 / n as f64).sqrt());
+        assert!((moment(4) - 24.0).abs() < 5.0 * (39744.0 / n as f64).sqrt());
+
+        // The Kolmogorov–Smirnov statistic is evaluated at the edges of bins
+        // that are much narrower than its critical value.
+        let m = 1 << 20;
+        let mut counts = vec![0; m];
+        for &x in &x {
+            counts[((d.distribution(x) * m as f64) as usize).min(m - 1)] += 1;
+        }
+        let mut total = 0;
+        let statistic = counts.iter().enumerate().fold(0.0f64, |statistic, (i, &count)| {
+            total += count;
+            statistic.max((total as f64 / n as f64 - (i + 1) as f64 / m as f64).abs())
+        });
+        assert!(statistic < 1.63 / (n as f64).sqrt());
+        assert!(x.iter().all(|&x| x >= 0.0));
+
+        let d = new!(2.0);
+        let mut one = source::seeded(42);
+        let mut other = source::seeded(42);
+        for _ in 0..1000 {
+            assert_eq!(d.sample(&mut one), super::sample(&mut other) / 2.0);
+        }
+    }
+
+    #[test]
+    fn sample_into() {
+        use source::Source;
+
+        let d = new!(2.0);
+        let mut one = source::Xorshift128Plus::new([42, 69]);
+        let mut other = source::Xorshift128Plus::new([42, 69]);
+        let mut x = vec![0.0; 1000];
+        d.sample_into(&mut one, &mut x);
+        assert_eq!(x, (0..1000).map(|_| d.sample(&mut other)).collect::<Vec<_>>());
+        assert_eq!(one.read::<u64>(), other.read::<u64>());
+    }
+
+    #[test]
+    fn scale() {
+        assert_eq!(new!(2.0).scale(), 0.5);
+        assert_eq!(new!(0.25).scale(), 4.0);
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(2.0).skewness(), 2.0);
     }
 
+    #[test]
+    fn standard() {
+        const STANDARD: Exponential = Exponential::standard();
+        assert_eq!(STANDARD, Exponential::default());
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(2.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.5, 20.0];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| 1.0 - d.distribution(x)).collect::<Vec<_>>(), 1e-15);
+        assert_eq!(1.0 - d.distribution(20.0), 0.0);
+        assert_eq!(d.survival(20.0), (-40.0f64).exp());
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Exponential::try_new(2.0).is_ok());
+        assert!(Exponential::try_new(INFINITY).is_ok());
+        for &lambda in &[0.0, -1.0, -INFINITY] {
+            assert_eq!(Exponential::try_new(lambda).err(),
+                       Some(ParameterError::LambdaNotPositive { value: lambda }));
+        }
+        assert!(Exponential::try_new(NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(2.0).variance(), 0.25);
@@ -210,4 +1075,17 @@ mod tests {
     fn deviation() {
         assert_eq!(new!(2.0).deviation(), 0.5);
     }
+
+    #[test]
+    fn ziggurat() {
+        use distribution::ziggurat;
+        use super::{R, ZIGGURAT};
+
+        let density = |x: f64| (-x).exp();
+        let inverse = |y: f64| -y.ln();
+
+        assert_eq!(ziggurat::radius(256, density, inverse, density, 7.0, 8.0), R);
+        let (k, w, y) = ziggurat::generate(256, R, density, inverse, density);
+        ziggurat::assert_tables(&ZIGGURAT, &k, &w, &y);
+    }
 }
@@ -1,24 +1,90 @@
-use distribution;
+use std::{fmt, ops};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use distribution::{self, PointMass};
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
 use source::Source;
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// A gamma distribution.
-#[derive(Clone, Copy)]
+///
+/// The scale parameter is allowed to be zero, in which case the distribution
+/// is the point mass at zero: the density is infinite at zero, the
+/// distribution function is a step at zero, and every sample is zero. The
+/// skewness and kurtosis remain `2 / √k` and `6 / k`, which are their limits as
+/// `θ` tends to zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Gamma {
     k: f64,
     theta: f64,
     norm: f64,
+    ln_gamma: f64,
+    ln_scale: f64,
 }
 
 impl Gamma {
     /// Create a gamma distribution with shape parameter `k` and scale parameter
     /// `theta`.
     ///
-    /// It should hold that `k > 0` and `theta > 0`.
+    /// It should hold that `k > 0` and `theta >= 0`; the function panics
+    /// otherwise.
     #[inline]
     pub fn new(k: f64, theta: f64) -> Self {
+        unwrap!(Gamma::try_new(k, theta))
+    }
+
+    /// Create a gamma distribution with shape parameter `k` and scale parameter
+    /// `theta` or return an error if the parameters are invalid.
+    pub fn try_new(k: f64, theta: f64) -> Result<Self, ParameterError> {
         use special::Gamma as SpecialGamma;
-        should!(k > 0.0 && theta > 0.0);
-        Gamma { k: k, theta: theta, norm: k.gamma() * theta.powf(k) }
+        ensure!(k > 0.0 && k.is_finite(), ParameterError::KNotPositive { value: k });
+        ensure!(theta >= 0.0 && theta.is_finite(),
+                ParameterError::ThetaNotNonnegative { value: theta });
+        Ok(Gamma {
+            k: k,
+            theta: theta,
+            norm: k.gamma() * theta.powf(k),
+            ln_gamma: SpecialGamma::ln_gamma(k).0,
+            ln_scale: k * theta.ln(),
+        })
+    }
+
+    /// Create a gamma distribution with shape parameter `k` and rate parameter
+    /// `rate`, that is, the reciprocal of the scale parameter.
+    ///
+    /// It should hold that `k > 0` and `rate > 0`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn with_rate(k: f64, rate: f64) -> Self {
+        unwrap!(Gamma::try_with_rate(k, rate))
+    }
+
+    /// Create a gamma distribution with shape parameter `k` and rate parameter
+    /// `rate` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_with_rate(k: f64, rate: f64) -> Result<Self, ParameterError> {
+        ensure!(rate > 0.0 && rate.is_finite(), ParameterError::RateNotPositive { value: rate });
+        Gamma::try_new(k, 1.0 / rate)
+    }
+
+    /// Create a gamma distribution with shape parameter `k` and scale parameter
+    /// `theta`.
+    ///
+    /// The function is the same as `new` and spells out the parameterization.
+    #[inline]
+    pub fn with_scale(k: f64, theta: f64) -> Self {
+        Gamma::new(k, theta)
+    }
+
+    /// Create a gamma distribution with shape parameter `k` and scale parameter
+    /// `theta` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_with_scale(k: f64, theta: f64) -> Result<Self, ParameterError> {
+        Gamma::try_new(k, theta)
     }
 
     /// Return the shape parameter.
@@ -28,31 +94,191 @@ impl Gamma {
     /// Return the scale parameter.
     #[inline(always)]
     pub fn theta(&self) -> f64 { self.theta }
+
+    /// Return the rate parameter, that is, the reciprocal of the scale
+    /// parameter.
+    #[inline(always)]
+    pub fn rate(&self) -> f64 { 1.0 / self.theta }
+
+    /// Return the point mass at zero if the distribution is degenerate.
+    #[inline(always)]
+    fn point(&self) -> Option<PointMass> {
+        if self.theta == 0.0 { Some(PointMass::new(0.0)) } else { None }
+    }
+
+    /// Fit the distribution to `data` weighted by `weights` by maximum
+    /// likelihood.
+    ///
+    /// The weights are relative and need not sum up to one. The estimate is
+    /// computed as described in `MaximumLikelihood`.
+    #[inline]
+    pub fn fit_weighted_mle(data: &[f64], weights: &[f64]) -> Result<Self, FitError> {
+        fit(data, Some(weights))
+    }
+}
+
+affine!(Gamma);
+
+impl ops::Add for Gamma {
+    type Output = Self;
+
+    /// Compute the distribution of the sum of two independent variables.
+    ///
+    /// It should hold that the scale parameters are equal.
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        should!(self.theta == other.theta);
+        Gamma::new(self.k + other.k, self.theta)
+    }
+}
+
+impl fmt::Display for Gamma {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Gamma(k = {}, θ = {})", self.k, self.theta)
+    }
 }
 
 impl distribution::Continuous for Gamma {
     fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        if let Some(point) = self.point() {
+            return point.density(x);
+        }
         if x <= 0.0 {
             0.0
         } else {
             x.powf(self.k - 1.0) * (-x / self.theta).exp() / self.norm
         }
     }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The moments are computed from the cumulants `s θ^n (n - 1)!`, where `s`
+    /// is the shape parameter, by a recurrence that involves no cancellation.
+    #[cfg(feature = "alloc")]
+    fn central_moment(&self, k: u32) -> f64 {
+        let k = k as usize;
+        let mut cumulants = vec![0.0; k + 1];
+        for n in 1..(k + 1) {
+            cumulants[n] = if n == 1 {
+                self.k * self.theta
+            } else {
+                cumulants[n - 1] * (n - 1) as f64 * self.theta
+            };
+        }
+        let mut moments = vec![0.0; k + 1];
+        moments[0] = 1.0;
+        for n in 2..(k + 1) {
+            let mut binomial = 1.0;
+            for j in 0..(n - 1) {
+                moments[n] += binomial * cumulants[n - j] * moments[j];
+                binomial = binomial * (n - 1 - j) as f64 / (j + 1) as f64;
+            }
+        }
+        moments[k]
+    }
+
+    /// Compute the probability density function at each point of `xs` and
+    /// write the results to `out`.
+    ///
+    /// The density is evaluated in the logarithmic domain, which keeps the
+    /// normalizing constant from overflowing for large shape parameters.
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        use distribution::conform;
+        conform(xs.len(), out.len());
+        if let Some(point) = self.point() {
+            return point.density_slice(xs, out);
+        }
+        let norm = self.ln_gamma + self.ln_scale;
+        for (y, &x) in out.iter_mut().zip(xs) {
+            *y = if x.is_nan() {
+                x
+            } else if x <= 0.0 || x == ::std::f64::INFINITY {
+                0.0
+            } else {
+                ((self.k - 1.0) * x.ln() - x / self.theta - norm).exp()
+            };
+        }
+    }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        guard!(ln_density(x));
+        if let Some(point) = self.point() {
+            return point.ln_density(x);
+        }
+        if x <= 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            (self.k - 1.0) * x.ln() - x / self.theta - self.ln_gamma - self.ln_scale
+        }
+    }
+
+    /// Compute the raw moment of order `k`.
+    ///
+    /// The moment is `θ^k Γ(s + k) / Γ(s)`, where `s` is the shape parameter,
+    /// which is evaluated as a product.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        (0..k).fold(1.0, |product, i| product * (self.k + i as f64) * self.theta)
+    }
 }
 
 impl distribution::Distribution for Gamma {
     type Value = f64;
 
     fn distribution(&self, x: f64) -> f64 {
-        use special::Gamma;
+        use math::special;
+        guard!(distribution(x));
+        if let Some(point) = self.point() {
+            return point.distribution(x);
+        }
         if x <= 0.0 {
             0.0
         } else {
-            (x / self.theta).inc_gamma(self.k)
+            special::gamma_p(self.k, x / self.theta)
+        }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, ::std::f64::INFINITY)
+    }
+
+    /// Compute the survival function.
+    ///
+    /// The upper incomplete gamma function is evaluated directly, which avoids
+    /// cancellation in the right tail.
+    fn survival(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(survival(x));
+        if let Some(point) = self.point() {
+            return point.survival(x);
+        }
+        if x <= 0.0 {
+            1.0
+        } else {
+            special::gamma_q(self.k, x / self.theta)
         }
     }
 }
 
+impl distribution::Divergence for Gamma {
+    /// Compute the Kullback–Leibler divergence.
+    ///
+    /// If either distribution is degenerate, the divergence is zero when both
+    /// are and infinite otherwise.
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        use special::Gamma;
+        if self.theta == 0.0 || other.theta == 0.0 {
+            return if self.theta == other.theta { 0.0 } else { ::std::f64::INFINITY };
+        }
+        let (k, theta) = (self.k, self.theta);
+        (k - other.k) * k.digamma() - Gamma::ln_gamma(k).0 + Gamma::ln_gamma(other.k).0 +
+            other.k * (other.theta / theta).ln() + k * (theta - other.theta) / other.theta
+    }
+}
+
 impl distribution::Entropy for Gamma {
     fn entropy(&self) -> f64 {
         use special::Gamma;
@@ -60,6 +286,32 @@ impl distribution::Entropy for Gamma {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Gamma {
+    /// Compute the Fisher information matrix with respect to `(k, θ)`.
+    fn fisher_information(&self) -> Vec<f64> {
+        use math;
+        let rate = 1.0 / self.theta;
+        vec![math::trigamma(self.k), rate, rate, self.k * rate * rate]
+    }
+}
+
+impl distribution::Inverse for Gamma {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The quantile is obtained by inverting the regularized lower incomplete
+    /// gamma function.
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        use math::special;
+        guard!(inverse(p));
+        if self.point().is_some() {
+            return 0.0;
+        }
+        self.theta * special::inv_gamma_p(self.k, p)
+    }
+}
+
 impl distribution::Kurtosis for Gamma {
     #[inline]
     fn kurtosis(&self) -> f64 {
@@ -67,6 +319,28 @@ impl distribution::Kurtosis for Gamma {
     }
 }
 
+impl distribution::MaximumLikelihood for Gamma {
+    /// Fit the distribution to `data`.
+    ///
+    /// The shape parameter solves `ln(k) - ψ(k) = ln(m) - l`, where `m` and
+    /// `l` are the means of the data and their logarithms, respectively; the
+    /// equation is solved by Newton’s method started from the approximation
+    /// due to Minka. The scale parameter is then `m / k`. The likelihood is
+    /// unbounded when a datum is zero, and such data are rejected. If all the
+    /// data are the same positive value, the likelihood increases without
+    /// bound as `k` grows and `θ` shrinks with `k θ` fixed; the limit is a
+    /// point mass away from zero, which is not a degenerate gamma
+    /// distribution, and such data are rejected as well.
+    ///
+    /// ## References
+    ///
+    /// 1. T. P. Minka, “Estimating a gamma distribution,” 2002.
+    #[inline]
+    fn fit_mle(data: &[f64]) -> Result<Self, FitError> {
+        fit(data, None)
+    }
+}
+
 impl distribution::Mean for Gamma {
     #[inline]
     fn mean(&self) -> f64 {
@@ -74,16 +348,69 @@ impl distribution::Mean for Gamma {
     }
 }
 
+impl distribution::MethodOfMoments for Gamma {
+    /// Fit the distribution to mean `mean` and variance `variance`.
+    ///
+    /// The parameters are `k = mean² / variance` and `θ = variance / mean`.
+    fn fit_moments_from(mean: f64, variance: f64) -> Result<Self, FitError> {
+        ensure!(mean > 0.0 && mean.is_finite() && variance > 0.0 && variance.is_finite(),
+                FitError::MomentsInfeasible { mean: mean, variance: variance });
+        Ok(Gamma::try_new(mean * mean / variance, variance / mean)?)
+    }
+}
+
+impl distribution::Mgf for Gamma {
+    #[inline]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        if t * self.theta < 1.0 {
+            -self.k * (-t * self.theta).ln_1p()
+        } else {
+            ::std::f64::INFINITY
+        }
+    }
+
+    #[inline]
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        self.k * self.theta / (1.0 - t * self.theta)
+    }
+
+    #[inline]
+    fn ln_mgf_second_derivative(&self, t: f64) -> f64 {
+        self.k * (self.theta / (1.0 - t * self.theta)).powi(2)
+    }
+
+    #[inline]
+    fn mgf_domain(&self) -> (f64, f64) {
+        (::std::f64::NEG_INFINITY, 1.0 / self.theta)
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl distribution::Modes for Gamma {
-    fn modes(&self) -> Vec<f64> {
-        if self.k >= 1.0 {
-            vec![(self.k - 1.0) * self.theta]
+    fn modes(&self) -> ModeList<f64> {
+        if self.k >= 1.0 || self.theta == 0.0 {
+            ModeList::one((self.k - 1.0) * self.theta)
         } else {
-            vec![]
+            ModeList::new()
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::Parametric for Gamma {
+    const POSITIVE: &'static [bool] = &[true, true];
+
+    #[inline]
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.k, self.theta]
+    }
+
+    #[inline]
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError> {
+        Gamma::try_new(parameters[0], parameters[1])
+    }
+}
+
 impl distribution::Sample for Gamma {
     /// Draw a sample.
     ///
@@ -151,6 +478,34 @@ pub fn sample<S: Source>(k: f64, source: &mut S) -> f64 {
     }
 }
 
+fn fit(data: &[f64], weights: Option<&[f64]>) -> Result<Gamma, FitError> {
+    use distribution::{average, examine_weights};
+    use math;
+    use special::Gamma as SpecialGamma;
+
+    const ITERATIONS: usize = 100;
+    const TOLERANCE: f64 = 1e-12;
+
+    examine_weights(data, weights)?;
+    if let Some(&value) = data.iter().find(|&&x| x <= 0.0) {
+        return Err(FitError::DatumOutOfSupport { value: value });
+    }
+    let mean = average(data, weights, |x| x);
+    let s = mean.ln() - average(data, weights, |x| x.ln());
+    ensure!(s > 0.0, FitError::DataNotDistinct);
+    let mut k = (3.0 - s + ((s - 3.0) * (s - 3.0) + 24.0 * s).sqrt()) / (12.0 * s);
+    for _ in 0..ITERATIONS {
+        let step = (k.ln() - k.digamma() - s) / (1.0 / k - math::trigamma(k));
+        let next = k - step;
+        let next = if next > 0.0 { next } else { k / 2.0 };
+        if (next - k).abs() <= TOLERANCE * next {
+            return Ok(Gamma::try_new(next, mean / next)?);
+        }
+        k = next;
+    }
+    Err(FitError::IterationsExhausted { iterations: ITERATIONS, estimate: [k, mean / k] })
+}
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -160,6 +515,56 @@ mod tests {
         ($k:expr, $theta:expr) => (Gamma::new($k, $theta));
     );
 
+    #[test]
+    fn add() {
+        let d = new!(2.0, 1.5) + new!(3.0, 1.5);
+        assert_eq!((d.k(), d.theta()), (5.0, 1.5));
+
+        let e = Sum::new(new!(2.0, 1.5), new!(3.0, 1.5));
+        let x = vec![0.5, 1.0, 2.0, 5.0, 7.5, 10.0, 20.0];
+        assert::close(&x.iter().map(|&x| e.density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), 1e-10);
+        assert::close(&x.iter().map(|&x| e.distribution(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), 1e-9);
+    }
+
+    #[test]
+    fn central_moment() {
+        let d = new!(3.0, 1.5);
+        assert_eq!(d.central_moment(1), 0.0);
+        assert::close(d.central_moment(2), 6.75, 1e-14);
+        assert::close(d.central_moment(3), 20.25, 1e-13);
+        assert::close(d.central_moment(5) / 1913.625, 1.0, 1e-14);
+        assert::close(d.central_moment(8) / 3938060.84765625, 1.0, 1e-14);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-14);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-14);
+    }
+
+    #[test]
+    fn degenerate() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = new!(4.0, 0.0);
+        let x = [-1.0, 0.0, 1.0, INFINITY];
+        assert_eq!(d.density_vec(&x), vec![0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!(x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+                   vec![0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!(d.distribution_vec(&x), vec![0.0, 1.0, 1.0, 1.0]);
+        assert_eq!((d.survival(-1.0), d.survival(0.0)), (1.0, 0.0));
+        assert_eq!((d.ln_density(0.0), d.ln_density(1.0)), (INFINITY, NEG_INFINITY));
+        for &p in &[0.0, 0.25, 1.0] {
+            assert_eq!(d.inverse(p), 0.0);
+        }
+        assert!(d.samples(&mut source::seeded(42), 10).iter().all(|&x| x == 0.0));
+        assert_eq!((d.mean(), d.variance(), d.entropy()), (0.0, 0.0, NEG_INFINITY));
+        assert_eq!((d.skewness(), d.kurtosis()), (1.0, 1.5));
+        assert_eq!(d.modes(), vec![0.0]);
+        assert_eq!(new!(0.5, 0.0).modes(), vec![0.0]);
+        assert_eq!(d.kl_divergence(&new!(0.5, 0.0)), 0.0);
+        assert_eq!((d.kl_divergence(&new!(2.0, 1.0)), new!(2.0, 1.0).kl_divergence(&d)),
+                   (INFINITY, INFINITY));
+    }
+
     #[test]
     fn density() {
         let d = new!(9.0, 0.5);
@@ -180,6 +585,24 @@ mod tests {
         assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-14);
     }
 
+    #[test]
+    fn density_slice() {
+        let d = new!(9.0, 0.5);
+        let x = (-4..81).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let p = d.density_vec(&x);
+        for (&x, &p) in x.iter().zip(&p) {
+            assert::close(p, d.density(x), 1e-13 * d.density(x));
+        }
+
+        let d = new!(500.0, 2.0);
+        assert::close(d.density_vec(&[1000.0])[0], (-4.7195564296201583924f64).exp(), 1e-10);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Gamma::new(9.0, 0.5).to_string(), "Gamma(k = 9, θ = 0.5)");
+    }
+
     #[test]
     fn distribution() {
         let d = new!(9.0, 0.5);
@@ -206,11 +629,135 @@ mod tests {
         assert_eq!(new!(1.0, 1.0 / 5.0).entropy(), Exponential::new(5.0).entropy());
     }
 
+    #[test]
+    fn fisher_information() {
+        assert::close(&new!(3.0, 1.5).fisher_information(),
+                      &[0.39493406684822643647, 2.0 / 3.0, 2.0 / 3.0, 4.0 / 3.0], 1e-15);
+    }
+
+    #[test]
+    fn fit_mle() {
+        use std::f64::NAN;
+
+        let d = Gamma::fit_mle(&[0.5, 1.2, 2.3, 0.8, 3.1, 1.7, 0.9, 2.6]).unwrap();
+        assert::close(d.k(), 3.1548097172150241656, 1e-10);
+        assert::close(d.theta(), 0.51904873725491697488, 1e-10);
+
+        let x = new!(2.5, 1.5).samples(&mut source::seeded(42), 100000);
+        let d = Gamma::fit_mle(&x).unwrap();
+        assert!((d.k() / 2.5 - 1.0).abs() < 0.02);
+        assert!((d.theta() / 1.5 - 1.0).abs() < 0.02);
+
+        let d = Gamma::fit_mle(&[1e-3, 1e-3 * (1.0 + 1e-6)]).unwrap();
+        assert!(d.k() > 1e11);
+
+        assert_eq!(Gamma::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Gamma::fit_mle(&[2.0, 2.0]), Err(FitError::DataNotDistinct));
+        assert_eq!(Gamma::fit_mle(&[1.0, 0.0]), Err(FitError::DatumOutOfSupport { value: 0.0 }));
+        assert!(Gamma::fit_mle(&[1.0, NAN]).is_err());
+    }
+
+    #[test]
+    fn fit_moments() {
+        let x = new!(2.5, 1.5).samples(&mut source::seeded(42), 1000);
+        let mean = x.iter().sum::<f64>() / 1000.0;
+        let variance = x.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / 1000.0;
+        let d = Gamma::fit_moments(&x).unwrap();
+        assert::close(d.mean(), mean, 1e-12);
+        assert::close(d.variance(), variance, 1e-12);
+
+        assert_eq!(Gamma::fit_moments_from(2.0, 0.0),
+                   Err(FitError::MomentsInfeasible { mean: 2.0, variance: 0.0 }));
+        assert_eq!(Gamma::fit_moments(&[-1.0, -2.0]),
+                   Err(FitError::MomentsInfeasible { mean: -1.5, variance: 0.25 }));
+    }
+
+    #[test]
+    fn fit_moments_online() {
+        use statistics::OnlineStats;
+
+        let x = new!(2.5, 1.5).samples(&mut source::seeded(42), 1000);
+        let mut stats = OnlineStats::from_slice(&x[..400]);
+        stats.merge(&OnlineStats::from_slice(&x[400..]));
+        let d = Gamma::fit_moments_online(&stats).unwrap();
+        let e = Gamma::fit_moments(&x).unwrap();
+        assert::close(&[d.k(), d.theta()], &[e.k(), e.theta()], 1e-12);
+
+        assert_eq!(Gamma::fit_moments_online(&OnlineStats::new()), Err(FitError::DataEmpty));
+    }
+
+    #[test]
+    fn fit_weighted_mle() {
+        let d = Gamma::fit_weighted_mle(&[0.5, 1.2, 2.3, 4.0], &[2.0, 1.0, 3.0, 0.0]).unwrap();
+        let e = Gamma::fit_mle(&[0.5, 0.5, 1.2, 2.3, 2.3, 2.3]).unwrap();
+        assert::close(d.k(), e.k(), 1e-12);
+        assert::close(d.theta(), e.theta(), 1e-12);
+
+        assert_eq!(Gamma::fit_weighted_mle(&[1.0, 2.0], &[1.0]),
+                   Err(FitError::WeightsMismatch { data: 2, weights: 1 }));
+        assert_eq!(Gamma::fit_weighted_mle(&[1.0, 2.0], &[1.0, -1.0]),
+                   Err(FitError::WeightNotNonnegative { value: -1.0 }));
+        assert_eq!(Gamma::fit_weighted_mle(&[1.0, 2.0], &[0.0, 0.0]), Err(FitError::WeightsZero));
+    }
+
+    #[test]
+    fn hpd_interval() {
+        let d = new!(2.0, 1.0);
+        let (lower, upper) = d.hpd_interval(0.95);
+        assert::close(lower, 0.042363333429956502668, 1e-8);
+        assert::close(upper, 4.7651682473890762522, 1e-8);
+        assert::close(d.distribution(upper) - d.distribution(lower), 0.95, 1e-12);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(9.0, 0.5);
+        let p = vec![
+            0.0, 2.374473282611617e-04, 2.136343448798417e-02, 1.527625060154386e-01,
+            4.074526585624087e-01, 6.671803212492811e-01, 9.379448040996508e-01,
+        ];
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 7.0];
+        assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x, 1e-12);
+        assert_eq!(d.inverse(1.0), ::std::f64::INFINITY);
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let (p, q) = (new!(3.0, 1.5), new!(2.0, 2.5));
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&q), 0.051288402070503196387, 1e-14);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(3.0, 1.5).kurtosis(), 2.0);
     }
 
+    #[test]
+    fn ln_density() {
+        use special::Gamma as SpecialGamma;
+        let d = new!(9.0, 0.5);
+        let x = vec![-1.0, 0.0, 0.5, 4.0, 10.0];
+        assert::close(&x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.density(x).ln()).collect::<Vec<_>>(), 1e-13);
+
+        let d = new!(500.0, 2.0);
+        assert!(d.density(1000.0).is_nan());
+        assert::close(d.ln_density(1000.0), -4.7195564296201583924, 1e-10);
+
+        let d = new!(0.5, 1.0);
+        assert::close(d.ln_density(1e-300), 344.81539900618215252, 1e-12);
+
+        for &(k, theta) in &[(9.0, 0.5), (0.5, 1.0), (500.0, 2.0), (2.5, 7.0)] {
+            let d = new!(k, theta);
+            for i in 1..101 {
+                let x = k * theta * i as f64 / 25.0;
+                assert_eq!(d.ln_density(x), (k - 1.0) * x.ln() - x / theta -
+                                            k.ln_gamma().0 - k * theta.ln());
+            }
+        }
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(9.0, 0.5).mean(), 4.5);
@@ -219,6 +766,32 @@ mod tests {
     #[test]
     fn modes() {
         assert_eq!(new!(5.5, 1.5).modes(), vec![6.75]);
+        assert_eq!(new!(1.0, 1.5).modes(), vec![0.0]);
+        assert!(new!(0.5, 1.5).modes().is_empty());
+    }
+
+    #[test]
+    fn moment() {
+        let d = new!(3.0, 1.5);
+        assert_eq!(d.moment(0), 1.0);
+        assert_eq!(d.moment(1), 4.5);
+        assert::close(d.moment(3), 202.5, 1e-12);
+        assert::close(d.moment(8) / 46501087.5, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn probability() {
+        let d = new!(3.0, 1.0);
+        assert::close(d.probability(1.0, 2.0), 0.24302218674554234452, 1e-14);
+        assert_eq!(d.probability(2.0, 1.0), 0.0);
+        assert::close(d.probability(1e-6, 2e-6) / 1.1666647916682165074e-18, 1.0, 1e-10);
+        assert::close(d.probability(40.0, 41.0) / 2.1936223966120987572e-15, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn rate() {
+        assert_eq!(new!(3.0, 0.5).rate(), 2.0);
+        assert_eq!(new!(3.0, 4.0).rate(), 0.25);
     }
 
     #[test]
@@ -226,8 +799,55 @@ mod tests {
         assert_eq!(new!(4.0, 1.5).skewness(), 1.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(3.0, 1.0);
+        let x = vec![-1.0, 0.0, 1.0, 3.0, 4.5, 10.0];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| 1.0 - d.distribution(x)).collect::<Vec<_>>(), 1e-14);
+        assert::close(d.survival(40.0) / 3.5728659287002263451e-15, 1.0, 1e-13);
+        assert::close(new!(3.0, 2.0).survival(80.0) / 3.5728659287002263451e-15, 1.0, 1e-13);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Gamma::try_new(9.0, 0.5).is_ok());
+        for &k in &[0.0, -1.0, INFINITY] {
+            assert_eq!(Gamma::try_new(k, 1.0).err(),
+                       Some(ParameterError::KNotPositive { value: k }));
+        }
+        assert!(Gamma::try_new(1.0, 0.0).is_ok());
+        for &theta in &[-1.0, INFINITY] {
+            assert_eq!(Gamma::try_new(1.0, theta).err(),
+                       Some(ParameterError::ThetaNotNonnegative { value: theta }));
+        }
+        assert!(Gamma::try_new(NAN, 1.0).is_err());
+        assert!(Gamma::try_new(1.0, NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(9.0, 0.5).variance(), 2.25);
     }
+
+    #[test]
+    fn with_rate() {
+        let d = Gamma::with_rate(3.0, 2.0);
+        assert_eq!(d, new!(3.0, 0.5));
+        assert_eq!((d.k(), d.rate()), (3.0, 2.0));
+        assert_eq!(Gamma::try_with_rate(3.0, 0.0),
+                   Err(ParameterError::RateNotPositive { value: 0.0 }));
+        assert_eq!(Gamma::try_with_rate(-1.0, 2.0),
+                   Err(ParameterError::KNotPositive { value: -1.0 }));
+    }
+
+    #[test]
+    fn with_scale() {
+        let d = Gamma::with_scale(3.0, 0.5);
+        assert_eq!(d, new!(3.0, 0.5));
+        assert_eq!((d.k(), d.theta()), (3.0, 0.5));
+        assert_eq!(Gamma::try_with_scale(3.0, -1.0),
+                   Err(ParameterError::ThetaNotNonnegative { value: -1.0 }));
+    }
 }
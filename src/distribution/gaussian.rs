@@ -1,55 +1,396 @@
-use distribution;
+//! The Gaussian distribution and related functions.
+
+use std::{fmt, ops};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use distribution::{self, PointMass};
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
+use distribution::ziggurat::{self, Ziggurat};
 use source::Source;
+use {FitError, Float, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::Real;
+
+// The reciprocal of the square root of 2π.
+const FRAC_1_SQRT_2PI: f64 = 0.3989422804014327;
 
 /// A Gaussian distribution.
-#[derive(Clone, Copy)]
-pub struct Gaussian {
-    mu: f64,
-    sigma: f64,
+///
+/// The distribution is generic over the precision of its parameters and
+/// values, which is `f64` unless stated otherwise; see `Float`. In other
+/// precisions, the distribution is created using `from_deviation`. With
+/// `f32`, the density, the distribution function, and its inverse are
+/// computed in single precision, and the inverse is accurate to about seven
+/// significant digits. The error function is evaluated in double precision in
+/// either case, and the samples are drawn in double precision and rounded.
+///
+/// The standard deviation is allowed to be zero, in which case the
+/// distribution is the point mass at `μ`: the density is infinite at `μ` and
+/// zero elsewhere, the distribution function is a step at `μ`, the inverse
+/// maps every probability to `μ`, and every sample is `μ`. The skewness and
+/// kurtosis remain zero, which are their limits as `σ` tends to zero.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Gaussian<T: Float = f64> {
+    mu: T,
+    sigma: T,
+    inv_sigma: T::Cache,
+    ln_norm: T::Cache,
+    neg_half_inv_var: T::Cache,
 }
 
-impl Gaussian {
+impl<T> Gaussian<T> where T: Float {
     /// Create a Gaussian distribution with mean `mu` and standard deviation
-    /// `sigma`.
+    /// `sigma` in the precision of `T`.
     ///
-    /// It should hold that `sigma > 0`.
+    /// The function is the same as `new` except that it is generic over the
+    /// precision. It should hold that `mu` is finite and `sigma >= 0`; the
+    /// function panics otherwise.
     #[inline]
-    pub fn new(mu: f64, sigma: f64) -> Self {
-        should!(sigma > 0.0);
-        Gaussian { mu: mu, sigma: sigma }
+    pub fn from_deviation(mu: T, sigma: T) -> Self {
+        unwrap!(Gaussian::try_from_deviation(mu, sigma))
+    }
+
+    /// Create a Gaussian distribution with mean `mu` and standard deviation
+    /// `sigma` in the precision of `T` or return an error if the parameters
+    /// are invalid.
+    #[inline]
+    pub fn try_from_deviation(mu: T, sigma: T) -> Result<Self, ParameterError> {
+        ensure!(mu.is_finite(), ParameterError::MuNotFinite { value: mu.to_f64() });
+        ensure!(sigma >= T::from_f64(0.0) && sigma.is_finite(),
+                ParameterError::SigmaNotNonnegative { value: sigma.to_f64() });
+        let inv_sigma = 1.0 / sigma.to_f64();
+        Ok(Gaussian {
+            mu: mu,
+            sigma: sigma,
+            inv_sigma: T::cache(inv_sigma),
+            ln_norm: T::cache(ln_norm(sigma.to_f64())),
+            neg_half_inv_var: T::cache(-0.5 * inv_sigma * inv_sigma),
+        })
     }
 
     /// Return the mean.
     #[inline(always)]
-    pub fn mu(&self) -> f64 { self.mu }
+    pub fn mu(&self) -> T { self.mu }
 
     /// Return the standard deviation.
     #[inline(always)]
-    pub fn sigma(&self) -> f64 { self.sigma }
+    pub fn sigma(&self) -> T { self.sigma }
+
+    // Return the reciprocal of the standard deviation.
+    #[inline(always)]
+    fn inv_sigma(&self) -> f64 {
+        T::cached(self.inv_sigma, || 1.0 / self.sigma.to_f64())
+    }
+
+    // Return the logarithm of the normalizing constant of the density.
+    #[inline(always)]
+    fn ln_norm(&self) -> f64 {
+        T::cached(self.ln_norm, || ln_norm(self.sigma.to_f64()))
+    }
+
+    // Return the factor of the squared deviation in the exponent of the
+    // density, that is, `-1 / (2σ^2)`.
+    #[inline(always)]
+    fn neg_half_inv_var(&self) -> f64 {
+        T::cached(self.neg_half_inv_var, || {
+            let inv_sigma = self.inv_sigma();
+            -0.5 * inv_sigma * inv_sigma
+        })
+    }
+
+    /// Return the point mass at the mean if the distribution is degenerate.
+    #[inline(always)]
+    fn point(&self) -> Option<PointMass> {
+        if self.sigma == T::from_f64(0.0) { Some(PointMass::new(self.mu.to_f64())) } else { None }
+    }
+}
+
+impl Gaussian {
+    /// Create a Gaussian distribution with mean `mu` and standard deviation
+    /// `sigma`.
+    ///
+    /// It should hold that `mu` is finite and `sigma >= 0`; the function
+    /// panics otherwise.
+    #[inline]
+    pub fn new(mu: f64, sigma: f64) -> Self {
+        unwrap!(Gaussian::try_new(mu, sigma))
+    }
+
+    /// Create a Gaussian distribution with mean `mu` and standard deviation
+    /// `sigma` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, ParameterError> {
+        Gaussian::try_from_deviation(mu, sigma)
+    }
+
+    /// Create a Gaussian distribution with mean `mu` and precision `tau`, that
+    /// is, the reciprocal of the variance.
+    ///
+    /// It should hold that `mu` is finite and `tau > 0`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn from_precision(mu: f64, tau: f64) -> Self {
+        unwrap!(Gaussian::try_from_precision(mu, tau))
+    }
+
+    /// Create a Gaussian distribution with mean `mu` and precision `tau` or
+    /// return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_from_precision(mu: f64, tau: f64) -> Result<Self, ParameterError> {
+        ensure!(tau > 0.0 && tau.is_finite(), ParameterError::PrecisionNotPositive { value: tau });
+        Gaussian::try_new(mu, 1.0 / tau.sqrt())
+    }
+
+    /// Create a Gaussian distribution with mean `mu` and variance `variance`.
+    ///
+    /// It should hold that `mu` is finite and `variance >= 0`; the function
+    /// panics otherwise.
+    #[inline]
+    pub fn from_variance(mu: f64, variance: f64) -> Self {
+        unwrap!(Gaussian::try_from_variance(mu, variance))
+    }
+
+    /// Create a Gaussian distribution with mean `mu` and variance `variance`
+    /// or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_from_variance(mu: f64, variance: f64) -> Result<Self, ParameterError> {
+        ensure!(variance >= 0.0 && variance.is_finite(),
+                ParameterError::VarianceNotNonnegative { value: variance });
+        Gaussian::try_new(mu, variance.sqrt())
+    }
+
+    /// Create the standard Gaussian distribution, that is, the one with mean
+    /// zero and standard deviation one.
+    ///
+    /// ```
+    /// use probability::distribution::Gaussian;
+    ///
+    /// const STANDARD: Gaussian = Gaussian::standard();
+    /// assert_eq!(STANDARD, Gaussian::new(0.0, 1.0));
+    /// ```
+    #[inline]
+    pub const fn standard() -> Self {
+        Gaussian {
+            mu: 0.0,
+            sigma: 1.0,
+            inv_sigma: 1.0,
+            ln_norm: -0.9189385332046727,
+            neg_half_inv_var: -0.5,
+        }
+    }
+
+    /// Return the precision, that is, the reciprocal of the variance.
+    #[inline(always)]
+    pub fn precision(&self) -> f64 { 1.0 / (self.sigma * self.sigma) }
+
+    /// Compute the inverse of the cumulative distribution function given the
+    /// natural logarithm of the probability.
+    ///
+    /// The function panics if `ln_p` is positive or NaN. Unlike
+    /// `inverse(ln_p.exp())`, the computation retains accuracy when the
+    /// probability underflows.
+    #[inline]
+    pub fn inverse_ln(&self, ln_p: f64) -> f64 {
+        use distribution::Inverse;
+        if let Some(point) = self.point() {
+            return point.inverse(ln_p.exp());
+        }
+        self.mu + self.sigma * inverse_ln(ln_p)
+    }
 }
 
 impl Default for Gaussian {
     #[inline]
     fn default() -> Self {
-        Gaussian::new(0.0, 1.0)
+        Gaussian::standard()
+    }
+}
+
+affine!(Gaussian => Gaussian,
+        |d: Gaussian, shift| Gaussian::new(d.mu + shift, d.sigma),
+        |d: Gaussian, scale: f64| Gaussian::new(scale * d.mu, scale.abs() * d.sigma));
+
+impl ops::Add for Gaussian {
+    type Output = Self;
+
+    /// Compute the distribution of the sum of two independent variables.
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Gaussian::new(self.mu + other.mu, self.sigma.hypot(other.sigma))
     }
 }
 
-impl distribution::Continuous for Gaussian {
+impl<T> fmt::Debug for Gaussian<T> where T: Float {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("Gaussian")
+                 .field("mu", &self.mu)
+                 .field("sigma", &self.sigma)
+                 .finish()
+    }
+}
+
+impl<T> fmt::Display for Gaussian<T> where T: Float {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Gaussian(μ = {}, σ = {})", self.mu, self.sigma)
+    }
+}
+
+impl<T> distribution::Continuous for Gaussian<T> where T: Float {
     fn density(&self, x: f64) -> f64 {
-        use distribution::Variance;
-        use std::f64::consts::PI;
-        (-(x - self.mu).powi(2) / (2.0 * self.variance())).exp() / ((2.0 * PI).sqrt() * self.sigma)
+        if let Some(point) = self.point() {
+            return point.density(x);
+        }
+        let z = T::from_f64(x) - self.mu;
+        let (factor, inv_sigma) = (self.neg_half_inv_var(), self.inv_sigma());
+        let density = (T::from_f64(factor) * z * z).exp() * T::from_f64(inv_sigma);
+        (density * T::from_f64(FRAC_1_SQRT_2PI)).to_f64()
+    }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The moments of odd orders vanish, and the moment of order `k` is
+    /// `σ^k (k - 1)!!` otherwise.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 {
+        central_moment(self.sigma.to_f64(), k)
+    }
+
+    /// Compute the probability density function at each point of `xs` and
+    /// write the results to `out`.
+    ///
+    /// The normalizing constant is computed once for all the points.
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        use distribution::conform;
+        conform(xs.len(), out.len());
+        if let Some(point) = self.point() {
+            return point.density_slice(xs, out);
+        }
+        let factor = T::from_f64(self.neg_half_inv_var());
+        let norm = T::from_f64(self.inv_sigma()) * T::from_f64(FRAC_1_SQRT_2PI);
+        for (y, &x) in out.iter_mut().zip(xs) {
+            let z = T::from_f64(x) - self.mu;
+            *y = ((factor * z * z).exp() * norm).to_f64();
+        }
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.ln_density(x);
+        }
+        let z = x - self.mu.to_f64();
+        self.neg_half_inv_var() * z * z + self.ln_norm()
+    }
+
+    /// Compute the log-likelihood of a sample.
+    ///
+    /// The log-likelihood depends on the data only via their number and the
+    /// sum of their squared deviations from `μ`, which is computed in one pass
+    /// using pairwise summation without evaluating any logarithms.
+    fn log_likelihood(&self, data: &[f64]) -> f64 {
+        use math;
+        use std::f64::{INFINITY, NEG_INFINITY};
+        if let Some(point) = self.point() {
+            if data.iter().any(|&x| x != point.value()) {
+                return NEG_INFINITY;
+            }
+            return if data.is_empty() { 0.0 } else { INFINITY };
+        }
+        let mu = self.mu.to_f64();
+        let squares = math::pairwise(data, |x| (x - mu) * (x - mu));
+        squares * self.neg_half_inv_var() + data.len() as f64 * self.ln_norm()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        use distribution::translate;
+        let sigma = self.sigma.to_f64();
+        translate(|j| central_moment(sigma, j), k, self.mu.to_f64())
     }
 }
 
-impl distribution::Distribution for Gaussian {
-    type Value = f64;
+impl<T> distribution::Distribution for Gaussian<T> where T: Float {
+    type Value = T;
 
     fn distribution(&self, x: f64) -> f64 {
-        use special::Error;
-        use std::f64::consts::SQRT_2;
-        (1.0 + ((x - self.mu) / (self.sigma * SQRT_2)).erf()) / 2.0
+        use std::f64::consts::FRAC_1_SQRT_2;
+        if let Some(point) = self.point() {
+            return point.distribution(x);
+        }
+        let z = (self.mu - T::from_f64(x)) * T::from_f64(self.inv_sigma());
+        ((z * T::from_f64(FRAC_1_SQRT_2)).erfc() / T::from_f64(2.0)).to_f64()
+    }
+
+    /// Compute the cumulative distribution function at each point of `xs` and
+    /// write the results to `out`.
+    ///
+    /// The scaling factor of the argument of the error function is computed
+    /// once for all the points.
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        use distribution::conform;
+        use std::f64::consts::FRAC_1_SQRT_2;
+        conform(xs.len(), out.len());
+        if let Some(point) = self.point() {
+            return point.distribution_slice(xs, out);
+        }
+        let factor = T::from_f64(self.inv_sigma()) * T::from_f64(FRAC_1_SQRT_2);
+        for (y, &x) in out.iter_mut().zip(xs) {
+            *y = (((self.mu - T::from_f64(x)) * factor).erfc() / T::from_f64(2.0)).to_f64();
+        }
+    }
+
+    /// Compute the natural logarithm of the cumulative distribution function.
+    ///
+    /// The computation is accurate in both tails; far in the left tail, an
+    /// asymptotic expansion is used.
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.distribution(x).ln();
+        }
+        ln_distribution((x - self.mu.to_f64()) * self.inv_sigma())
+    }
+
+    /// Compute the natural logarithm of the survival function.
+    ///
+    /// The computation is accurate in both tails; far in the right tail, an
+    /// asymptotic expansion is used.
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.survival(x).ln();
+        }
+        ln_distribution((self.mu.to_f64() - x) * self.inv_sigma())
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        use std::f64::consts::FRAC_1_SQRT_2;
+        if let Some(point) = self.point() {
+            return point.survival(x);
+        }
+        let z = (T::from_f64(x) - self.mu) * T::from_f64(self.inv_sigma());
+        ((z * T::from_f64(FRAC_1_SQRT_2)).erfc() / T::from_f64(2.0)).to_f64()
+    }
+}
+
+impl distribution::Divergence for Gaussian {
+    /// Compute the Kullback–Leibler divergence.
+    ///
+    /// If either distribution is degenerate, the divergence is zero when the
+    /// two are the same point mass and infinite otherwise.
+    #[inline]
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        if self.sigma == 0.0 || other.sigma == 0.0 {
+            return if self == other { 0.0 } else { ::std::f64::INFINITY };
+        }
+        let ratio = self.sigma / other.sigma;
+        let z = (self.mu - other.mu) / other.sigma;
+        (ratio * ratio + z * z - 1.0) / 2.0 - ratio.ln()
     }
 }
 
@@ -62,6 +403,16 @@ impl distribution::Entropy for Gaussian {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Gaussian {
+    /// Compute the Fisher information matrix with respect to `(μ, σ)`.
+    #[inline]
+    fn fisher_information(&self) -> Vec<f64> {
+        let precision = self.precision();
+        vec![precision, 0.0, 0.0, 2.0 * precision]
+    }
+}
+
 impl distribution::Inverse for Gaussian {
     /// Compute the inverse of the cumulative distribution function.
     ///
@@ -74,33 +425,134 @@ impl distribution::Inverse for Gaussian {
     /// 2. http://people.sc.fsu.edu/~jburkardt/c_src/asa241/asa241.html
     #[inline(always)]
     fn inverse(&self, p: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.inverse(p);
+        }
         self.mu + self.sigma * inverse(p)
     }
+
+    #[inline(always)]
+    fn inverse_survival(&self, p: f64) -> f64 {
+        if let Some(point) = self.point() {
+            return point.inverse_survival(p);
+        }
+        self.mu - self.sigma * inverse(p)
+    }
+}
+
+impl distribution::Inverse for Gaussian<f32> {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The probability is rounded to `f32`, and the computation is carried out
+    /// in single precision using the variant of the algorithm for `f64` with
+    /// lower-degree approximations, which is accurate to about seven
+    /// significant digits. Since the rounding loses the probabilities close to
+    /// one, `inverse_survival` should be used in the right tail.
+    #[inline]
+    fn inverse(&self, p: f64) -> f32 {
+        guard!(inverse(p));
+        if self.sigma == 0.0 {
+            return self.mu;
+        }
+        self.mu + self.sigma * inverse_single(p as f32)
+    }
+
+    #[inline]
+    fn inverse_survival(&self, p: f64) -> f32 {
+        guard!(inverse(p));
+        if self.sigma == 0.0 {
+            return self.mu;
+        }
+        self.mu - self.sigma * inverse_single(p as f32)
+    }
 }
 
-impl distribution::Kurtosis for Gaussian {
+impl<T> distribution::Kurtosis for Gaussian<T> where T: Float {
     #[inline]
     fn kurtosis(&self) -> f64 { 0.0 }
 }
 
-impl distribution::Mean for Gaussian {
+impl distribution::MaximumLikelihood for Gaussian {
+    /// Fit the distribution to `data`.
+    ///
+    /// The variance is estimated by the mean squared deviation from the mean
+    /// of the data, that is, with denominator `n` rather than `n - 1`, which
+    /// makes the estimate biased downward by the factor of `(n - 1) / n`. If
+    /// all the data are the same, the estimate is the degenerate distribution
+    /// at that value.
+    fn fit_mle(data: &[f64]) -> Result<Self, FitError> {
+        use distribution::examine;
+        use math;
+        examine(data)?;
+        if data.iter().all(|&x| x == data[0]) {
+            return Ok(Gaussian::try_new(data[0], 0.0)?);
+        }
+        let n = data.len() as f64;
+        let mu = math::pairwise(data, |x| x) / n;
+        let variance = math::pairwise(data, |x| (x - mu) * (x - mu)) / n;
+        Ok(Gaussian::try_from_variance(mu, variance)?)
+    }
+}
+
+impl<T> distribution::Mean for Gaussian<T> where T: Float {
     #[inline]
-    fn mean(&self) -> f64 { self.mu }
+    fn mean(&self) -> f64 { self.mu.to_f64() }
 }
 
-impl distribution::Median for Gaussian {
+impl<T> distribution::Median for Gaussian<T> where T: Float {
     #[inline]
-    fn median(&self) -> f64 { self.mu }
+    fn median(&self) -> f64 { self.mu.to_f64() }
+}
+
+impl distribution::MethodOfMoments for Gaussian {
+    fn fit_moments_from(mean: f64, variance: f64) -> Result<Self, FitError> {
+        ensure!(mean.is_finite() && variance >= 0.0 && variance.is_finite(),
+                FitError::MomentsInfeasible { mean: mean, variance: variance });
+        Ok(Gaussian::try_from_variance(mean, variance)?)
+    }
 }
 
-impl distribution::Modes for Gaussian {
+impl distribution::Mgf for Gaussian {
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![self.mu]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        t * (self.mu + 0.5 * self.sigma * self.sigma * t)
+    }
+
+    #[inline]
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        self.mu + self.sigma * self.sigma * t
+    }
+
+    #[inline]
+    fn ln_mgf_second_derivative(&self, _: f64) -> f64 {
+        self.sigma * self.sigma
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> distribution::Modes for Gaussian<T> where T: Float {
+    #[inline]
+    fn modes(&self) -> ModeList<T> {
+        ModeList::one(self.mu)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Parametric for Gaussian {
+    const POSITIVE: &'static [bool] = &[false, true];
+
+    #[inline]
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.mu, self.sigma]
+    }
+
+    #[inline]
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError> {
+        Gaussian::try_new(parameters[0], parameters[1])
     }
 }
 
-impl distribution::Sample for Gaussian {
+impl<T> distribution::Sample for Gaussian<T> where T: Float {
     /// Draw a sample.
     ///
     /// ## References
@@ -111,24 +563,333 @@ impl distribution::Sample for Gaussian {
     ///
     /// 2. D. Eddelbuettel, “Ziggurat Revisited,” 2014.
     #[inline]
-    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
-        self.sigma * sample(source) + self.mu
+    fn sample<S>(&self, source: &mut S) -> T where S: Source {
+        self.sigma * T::from_f64(sample(source)) + self.mu
+    }
+
+    /// Draw as many samples as there are elements in `out` and write them to
+    /// `out`.
+    ///
+    /// The standard samples are drawn first and transformed afterwards in a
+    /// separate pass, which the compiler is free to vectorize.
+    fn sample_into<S>(&self, source: &mut S, out: &mut [T]) where S: Source {
+        for x in out.iter_mut() {
+            *x = T::from_f64(sample(source));
+        }
+        for x in out.iter_mut() {
+            *x = self.sigma * *x + self.mu;
+        }
     }
 }
 
-impl distribution::Skewness for Gaussian {
+impl<T> distribution::Skewness for Gaussian<T> where T: Float {
     #[inline]
     fn skewness(&self) -> f64 { 0.0 }
 }
 
-impl distribution::Variance for Gaussian {
+impl<T> distribution::Variance for Gaussian<T> where T: Float {
     #[inline]
     fn variance(&self) -> f64 {
-        self.sigma * self.sigma
+        let sigma = self.sigma.to_f64();
+        sigma * sigma
+    }
+
+    #[inline]
+    fn deviation(&self) -> f64 { self.sigma.to_f64() }
+}
+
+/// The standard Gaussian distribution.
+///
+/// The distribution is the same as `Gaussian::standard()`, and the results
+/// agree with those of the latter bit for bit. The parameters are not stored,
+/// which spares the location-scale transformation in hot loops.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StandardGaussian;
+
+affine!(StandardGaussian => Gaussian,
+        |_, shift| Gaussian::new(shift, 1.0),
+        |_, scale: f64| Gaussian::new(0.0, scale.abs()));
+
+impl StandardGaussian {
+    /// Compute the inverse of the cumulative distribution function given the
+    /// natural logarithm of the probability.
+    ///
+    /// The function panics if `ln_p` is positive or NaN.
+    #[inline(always)]
+    pub fn inverse_ln(&self, ln_p: f64) -> f64 {
+        inverse_ln(ln_p)
+    }
+}
+
+impl fmt::Display for StandardGaussian {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "StandardGaussian")
+    }
+}
+
+impl From<StandardGaussian> for Gaussian {
+    #[inline]
+    fn from(_: StandardGaussian) -> Self {
+        Gaussian::standard()
+    }
+}
+
+impl distribution::Continuous for StandardGaussian {
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        (-0.5 * x * x).exp() * FRAC_1_SQRT_2PI
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 {
+        self.moment(k)
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        use std::f64::consts::PI;
+        -0.5 * x * x - (2.0 * PI).sqrt().ln()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        if k % 2 == 1 {
+            return 0.0;
+        }
+        (1..(k / 2 + 1)).fold(1.0, |product, i| product * (2 * i - 1) as f64)
+    }
+}
+
+impl distribution::Distribution for StandardGaussian {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        use special::Error;
+        use std::f64::consts::FRAC_1_SQRT_2;
+        Error::erfc(-x * FRAC_1_SQRT_2) / 2.0
+    }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        ln_distribution(x)
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        ln_distribution(-x)
     }
 
     #[inline]
-    fn deviation(&self) -> f64 { self.sigma }
+    fn survival(&self, x: f64) -> f64 {
+        use special::Error;
+        use std::f64::consts::FRAC_1_SQRT_2;
+        Error::erfc(x * FRAC_1_SQRT_2) / 2.0
+    }
+}
+
+impl distribution::Entropy for StandardGaussian {
+    #[inline]
+    fn entropy(&self) -> f64 {
+        use std::f64::consts::{E, PI};
+        0.5 * (2.0 * PI * E).ln()
+    }
+}
+
+impl distribution::Inverse for StandardGaussian {
+    #[inline(always)]
+    fn inverse(&self, p: f64) -> f64 {
+        inverse(p)
+    }
+
+    #[inline(always)]
+    fn inverse_survival(&self, p: f64) -> f64 {
+        -inverse(p)
+    }
+}
+
+impl distribution::Kurtosis for StandardGaussian {
+    #[inline]
+    fn kurtosis(&self) -> f64 { 0.0 }
+}
+
+impl distribution::Mean for StandardGaussian {
+    #[inline]
+    fn mean(&self) -> f64 { 0.0 }
+}
+
+impl distribution::Median for StandardGaussian {
+    #[inline]
+    fn median(&self) -> f64 { 0.0 }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Modes for StandardGaussian {
+    #[inline]
+    fn modes(&self) -> ModeList<f64> {
+        ModeList::one(0.0)
+    }
+}
+
+impl distribution::Sample for StandardGaussian {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        sample(source)
+    }
+}
+
+impl distribution::Skewness for StandardGaussian {
+    #[inline]
+    fn skewness(&self) -> f64 { 0.0 }
+}
+
+impl distribution::Variance for StandardGaussian {
+    #[inline]
+    fn variance(&self) -> f64 { 1.0 }
+
+    #[inline]
+    fn deviation(&self) -> f64 { 1.0 }
+}
+
+/// Compute the cumulative distribution function of the standard bivariate
+/// Gaussian distribution with correlation `rho`, that is, `P(X ≤ x, Y ≤ y)`.
+///
+/// The algorithm is the one of Drezner and Wesolowsky as refined by Genz: the
+/// integral over the correlation is evaluated by the Gauss–Legendre rule with
+/// 6, 12, or 20 points depending on `|ρ|`, and an asymptotic expansion is
+/// integrated instead for `|ρ| ≥ 0.925`. The absolute error is about `1e-15`.
+/// The probability is `Φ(x) Φ(y)` for `ρ = 0`, `Φ(min(x, y))` for `ρ = 1`, and
+/// `max(0, Φ(x) - Φ(-y))` for `ρ = -1`, where `Φ` is the standard Gaussian
+/// distribution function; these cases are computed directly.
+///
+/// It should hold that `-1 ≤ ρ ≤ 1`; the function panics otherwise.
+///
+/// ## References
+///
+/// 1. Z. Drezner and G. O. Wesolowsky, “On the computation of the bivariate
+///    normal integral,” Journal of Statistical Computation and Simulation,
+///    vol. 35, pp. 101–107, 1990.
+///
+/// 2. A. Genz, “Numerical computation of rectangular bivariate and trivariate
+///    normal and t probabilities,” Statistics and Computing, vol. 14,
+///    pp. 251–260, 2004.
+#[cfg(feature = "alloc")]
+pub fn bivariate_cdf(x: f64, y: f64, rho: f64) -> f64 {
+    use distribution::Distribution;
+    use std::f64::consts::PI;
+    use std::f64::{INFINITY, NAN, NEG_INFINITY};
+
+    const W6: [f64; 3] = [0.1713244923791705, 0.3607615730481384, 0.4679139345726904];
+    const X6: [f64; 3] = [0.9324695142031522, 0.6612093864662647, 0.2386191860831970];
+    const W12: [f64; 6] = [
+        0.04717533638651177, 0.1069393259953183, 0.1600783285433464, 0.2031674267230659,
+        0.2334925365383547, 0.2491470458134029,
+    ];
+    const X12: [f64; 6] = [
+        0.9815606342467191, 0.9041172563704750, 0.7699026741943050, 0.5873179542866171,
+        0.3678314989981802, 0.1252334085114692,
+    ];
+    const W20: [f64; 10] = [
+        0.01761400713915212, 0.04060142980038694, 0.06267204833410906, 0.08327674157670475,
+        0.1019301198172404, 0.1181945319615184, 0.1316886384491766, 0.1420961093183821,
+        0.1491729864726037, 0.1527533871307259,
+    ];
+    const X20: [f64; 10] = [
+        0.9931285991850949, 0.9639719272779138, 0.9122344282513259, 0.8391169718222188,
+        0.7463319064601508, 0.6360536807265150, 0.5108670019508271, 0.3737060887154196,
+        0.2277858511416451, 0.07652652113349733,
+    ];
+
+    assert!(-1.0 <= rho && rho <= 1.0, "the correlation should be in [-1, 1] (got {})", rho);
+    if x.is_nan() || y.is_nan() {
+        return NAN;
+    }
+    let phi = |x: f64| StandardGaussian.distribution(x);
+    if x == NEG_INFINITY || y == NEG_INFINITY {
+        return 0.0;
+    }
+    if x == INFINITY {
+        return phi(y);
+    }
+    if y == INFINITY {
+        return phi(x);
+    }
+    if rho == 0.0 {
+        return phi(x) * phi(y);
+    }
+    if rho == 1.0 {
+        return phi(x.min(y));
+    }
+    if rho == -1.0 {
+        return if x <= -y { 0.0 } else if x > 0.0 { phi(y) - phi(-x) } else { phi(x) - phi(-y) };
+    }
+
+    // The upper probability `P(X > h, Y > k)` is computed with `h = -x` and
+    // `k = -y`, following the reference.
+    let (h, mut k) = (-x, -y);
+    let mut hk = h * k;
+    let (weights, nodes): (&[f64], &[f64]) = if rho.abs() < 0.3 {
+        (&W6, &X6)
+    } else if rho.abs() < 0.75 {
+        (&W12, &X12)
+    } else {
+        (&W20, &X20)
+    };
+    let points = || {
+        weights.iter().zip(nodes).flat_map(|(&w, &x)| vec![(w, 1.0 - x), (w, 1.0 + x)])
+    };
+    if rho.abs() < 0.925 {
+        let hs = (h * h + k * k) / 2.0;
+        let asr = rho.asin() / 2.0;
+        let sum = points().fold(0.0, |sum, (w, x)| {
+            let sn = (asr * x).sin();
+            sum + w * ((sn * hk - hs) / (1.0 - sn * sn)).exp()
+        });
+        return (sum * asr / (2.0 * PI) + phi(x) * phi(y)).max(0.0).min(1.0);
+    }
+    if rho < 0.0 {
+        k = -k;
+        hk = -hk;
+    }
+    let s = 1.0 - rho * rho;
+    let a = s.sqrt();
+    let bs = (h - k) * (h - k);
+    let c = (4.0 - hk) / 8.0;
+    let d = (12.0 - hk) / 80.0;
+    let asr = -(bs / s + hk) / 2.0;
+    let mut bvn = 0.0;
+    if asr > -100.0 {
+        bvn = a * asr.exp() * (1.0 - c * (bs - s) * (1.0 - d * bs) / 3.0 + c * d * s * s);
+    }
+    if hk > -100.0 {
+        let b = bs.sqrt();
+        let sp = (2.0 * PI).sqrt() * phi(-b / a);
+        bvn -= (-hk / 2.0).exp() * sp * b * (1.0 - c * bs * (1.0 - d * bs) / 3.0);
+    }
+    let a = a / 2.0;
+    let sum = points().fold(0.0, |sum, (w, x)| {
+        let xs = (a * x) * (a * x);
+        let asr = -(bs / xs + hk) / 2.0;
+        if asr <= -100.0 {
+            return sum;
+        }
+        let sp = 1.0 + c * xs * (1.0 + 5.0 * d * xs);
+        let rs = (1.0 - xs).sqrt();
+        let ep = (-(hk / 2.0) * xs / ((1.0 + rs) * (1.0 + rs))).exp() / rs;
+        sum + w * asr.exp() * (sp - ep)
+    });
+    bvn = (a * sum - bvn) / (2.0 * PI);
+    let p = if rho > 0.0 {
+        bvn + phi(-h.max(k))
+    } else if h >= k {
+        -bvn
+    } else {
+        (if h < 0.0 { phi(k) - phi(h) } else { phi(-h) - phi(-k) }) - bvn
+    };
+    p.max(0.0).min(1.0)
 }
 
 /// Compute the inverse cumulative distribution function of the standard
@@ -136,12 +897,149 @@ impl distribution::Variance for Gaussian {
 pub fn inverse(p: f64) -> f64 {
     use std::f64::{INFINITY, NEG_INFINITY};
 
-    should!(0.0 <= p && p <= 1.0);
+    guard!(inverse(p));
+
+    if p <= 0.0 {
+        return NEG_INFINITY;
+    }
+    if 1.0 <= p {
+        return INFINITY;
+    }
+
+    let q = p - 0.5;
+    if q.abs() <= SPLIT {
+        return center(q);
+    }
+    if q < 0.0 { tail(p.ln()) } else { -tail((1.0 - p).ln()) }
+}
+
+/// Compute the inverse cumulative distribution function of the standard
+/// Gaussian distribution given the natural logarithm of the probability.
+///
+/// The logarithm is used directly in the tails, so that probabilities too
+/// small to be represented, such as `exp(-1000)`, are handled accurately.
+pub fn inverse_ln(ln_p: f64) -> f64 {
+    use math;
+    use std::f64::{INFINITY, NEG_INFINITY};
+
+    assert!(ln_p <= 0.0, "the logarithm of the probability should be nonpositive (got {})", ln_p);
+
+    if ln_p == NEG_INFINITY {
+        return NEG_INFINITY;
+    }
+    if 0.0 <= ln_p {
+        return INFINITY;
+    }
+
+    let q = ln_p.exp() - 0.5;
+    if q.abs() <= SPLIT {
+        return center(q);
+    }
+    if q < 0.0 { tail(ln_p) } else { -tail(math::log1mexp(ln_p)) }
+}
+
+/// Compute Owen’s T function, that is,
+/// `T(h, a) = ∫₀ᵃ exp(-h² (1 + t²) / 2) / (1 + t²) dt / (2π)`.
+///
+/// The function is even in `h` and odd in `a`, and `T(h, a)` for `|a| > 1` is
+/// obtained from `T(|a h|, 1 / |a|)` by the reflection formula. For `|a| ≤ 1`,
+/// Owen’s series in powers of `a` is summed when `|h| ≤ 4`, and the integral
+/// is otherwise evaluated using the adaptive Gauss–Kronrod rule with the
+/// factor `exp(-h² / 2)` taken out, which keeps the result accurate where it
+/// underflows. The case `|a| = 1`, where `T(h, 1) = Φ(h) (1 - Φ(h)) / 2`, and
+/// infinite arguments are computed directly. The absolute error is about
+/// `1e-16`.
+///
+/// ## References
+///
+/// 1. D. B. Owen, “Tables for computing bivariate normal probabilities,” The
+///    Annals of Mathematical Statistics, vol. 27, pp. 1075–1090, 1956.
+///
+/// 2. M. Patefield and D. Tandy, “Fast and accurate calculation of Owen’s T
+///    function,” Journal of Statistical Software, vol. 5, pp. 1–25, 2000.
+#[cfg(feature = "alloc")]
+pub fn owens_t(h: f64, a: f64) -> f64 {
+    use distribution::Distribution;
+    use math;
+    use std::f64::consts::PI;
+    use std::f64::{INFINITY, NAN};
+
+    const SERIES: f64 = 4.0;
+    const TERMS: usize = 80;
+
+    if h.is_nan() || a.is_nan() {
+        return NAN;
+    }
+    let (h, sign, a) = (h.abs(), a.signum(), a.abs());
+    if a == 0.0 || h == INFINITY {
+        return 0.0;
+    }
+    if h == 0.0 {
+        return sign * a.atan() / (2.0 * PI);
+    }
+    let phi = |x: f64| StandardGaussian.distribution(x);
+    let q = |x: f64| StandardGaussian.survival(x);
+    if a == 1.0 {
+        return sign * phi(h) * q(h) / 2.0;
+    }
+    if a > 1.0 {
+        let ah = a * h;
+        let t = (phi(h) * q(ah) + phi(ah) * q(h)) / 2.0 - owens_t(ah, 1.0 / a);
+        return sign * t;
+    }
+    let t = if h <= SERIES {
+        // T(h, a) = (atan(a) - Σ cⱼ a^(2j + 1)) / (2π), where
+        // cⱼ = (-1)ʲ exp(-h² / 2) Σᵢ₌ⱼ₊₁ (h² / 2)ⁱ / i! / (2j + 1). The inner
+        // sums are accumulated backward, which avoids cancellation.
+        let x = h * h / 2.0;
+        let mut terms = [0.0; TERMS];
+        terms[0] = (-x).exp();
+        for i in 1..TERMS {
+            terms[i] = terms[i - 1] * x / i as f64;
+        }
+        let (mut tail, mut sum) = (0.0, 0.0);
+        for j in (0..(TERMS - 1)).rev() {
+            tail += terms[j + 1];
+            let value = tail * a.powi(2 * j as i32 + 1) / (2 * j + 1) as f64;
+            sum += if j % 2 == 0 { value } else { -value };
+        }
+        (a.atan() - sum) / (2.0 * PI)
+    } else {
+        let f = |t: f64| (-h * h * t * t / 2.0).exp() / (1.0 + t * t);
+        (-h * h / 2.0).exp() * math::integrate(f, 0.0, a, 1e-15).value / (2.0 * PI)
+    };
+    sign * t
+}
 
-    const CONST1: f64 = 0.180625;
-    const CONST2: f64 = 1.6;
-    const SPLIT1: f64 = 0.425;
-    const SPLIT2: f64 = 5.0;
+const SPLIT: f64 = 0.425;
+
+#[inline(always)]
+fn poly(c: &[f64], x: f64) -> f64 {
+    c[0] + x * (c[1] + x * (c[2] + x * (c[3] + x * (
+    c[4] + x * (c[5] + x * (c[6] + x * (c[7])))))))
+}
+
+// Compute the logarithm of the normalizing constant of the density of a
+// Gaussian distribution with standard deviation `sigma`.
+#[inline(always)]
+fn ln_norm(sigma: f64) -> f64 {
+    -((2.0 * ::std::f64::consts::PI).sqrt() * sigma).ln()
+}
+
+// Compute the central moment of order `k` of a Gaussian distribution with
+// standard deviation `sigma`.
+#[cfg(feature = "alloc")]
+fn central_moment(sigma: f64, k: u32) -> f64 {
+    if k % 2 == 1 {
+        return 0.0;
+    }
+    (1..(k / 2 + 1)).fold(1.0, |product, i| product * (2 * i - 1) as f64) * sigma.powi(k as i32)
+}
+
+// Compute the inverse cumulative distribution function of the standard
+// Gaussian distribution at `0.5 + q` for `|q| <= SPLIT`.
+fn center(q: f64) -> f64 {
+    const CONST: f64 = 0.180625;
     const A: [f64; 8] = [
         3.3871328727963666080e+00, 1.3314166789178437745e+02, 1.9715909503065514427e+03,
         1.3731693765509461125e+04, 4.5921953931549871457e+04, 6.7265770927008700853e+04,
@@ -152,6 +1050,28 @@ pub fn inverse(p: f64) -> f64 {
         5.3941960214247511077e+03, 2.1213794301586595867e+04, 3.9307895800092710610e+04,
         2.8729085735721942674e+04, 5.2264952788528545610e+03,
     ];
+
+    let x = CONST - q * q;
+    q * poly(&A, x) / poly(&B, x)
+}
+
+// Compute the inverse cumulative distribution function of the standard
+// Gaussian distribution at `exp(ln_p)` for `exp(ln_p) < 0.5 - SPLIT`.
+//
+// The rational approximations are accurate to about 1e-16 in absolute terms
+// for probabilities down to 1e-316, which leaves the relative error in the far
+// tail at a few units in the last place. The result is corrected by one step of
+// Halley's method applied to the logarithm of the distribution function, which
+// is skipped when the correction is below one unit in the last place. Further
+// out, the iteration is continued from a starting point given by the
+// asymptotic expansion of the distribution function.
+fn tail(ln_p: f64) -> f64 {
+    use std::f64::consts::PI;
+    use std::f64::EPSILON;
+
+    const CONST: f64 = 1.6;
+    const SPLIT1: f64 = 5.0;
+    const SPLIT2: f64 = 27.0;
     const C: [f64; 8] = [
         1.42343711074968357734e+00, 4.63033784615654529590e+00, 5.76949722146069140550e+00,
         3.64784832476320460504e+00, 1.27045825245236838258e+00, 2.41780725177450611770e-01,
@@ -173,10 +1093,55 @@ pub fn inverse(p: f64) -> f64 {
         1.42151175831644588870e-07, 2.04426310338993978564e-15,
     ];
 
-    #[inline(always)]
-    fn poly(c: &[f64], x: f64) -> f64 {
-        c[0] + x * (c[1] + x * (c[2] + x * (c[3] + x * (
-        c[4] + x * (c[5] + x * (c[6] + x * (c[7])))))))
+    let step = |z: f64| {
+        let l = ln_distribution(z);
+        let f = l - ln_p;
+        let m = (-z * z / 2.0 - (2.0 * PI).sqrt().ln() - l).exp();
+        f / m / (1.0 + f * (z + m) / (2.0 * m))
+    };
+
+    let r = (-ln_p).sqrt();
+    let mut z = if r <= SPLIT1 {
+        -poly(&C, r - CONST) / poly(&D, r - CONST)
+    } else {
+        -poly(&E, r - SPLIT1) / poly(&F, r - SPLIT1)
+    };
+    if r <= SPLIT2 {
+        let delta = step(z);
+        if delta.abs() > EPSILON * z.abs() {
+            z -= delta;
+        }
+        return z;
+    }
+
+    z = z.min(-(2.0 * r * r - (4.0 * PI * r * r).ln()).sqrt());
+    for _ in 0..10 {
+        let delta = step(z);
+        z -= delta;
+        if delta.abs() <= EPSILON * z.abs() {
+            break;
+        }
+    }
+    z
+}
+
+// Compute the inverse cumulative distribution function of the standard
+// Gaussian distribution in single precision.
+//
+// The approximations are those of PPND7 in the reference of `inverse`, which
+// are accurate to about 1e-7.
+fn inverse_single(p: f32) -> f32 {
+    use std::f32::{INFINITY, NEG_INFINITY};
+
+    const A: [f32; 4] = [3.3871327179e+00, 5.0434271938e+01, 1.5929113202e+02, 5.9109374720e+01];
+    const B: [f32; 4] = [1.0000000000e+00, 1.7895169469e+01, 7.8757757664e+01, 6.7187563600e+01];
+    const C: [f32; 4] = [1.4234372777e+00, 2.7568153900e+00, 1.3067284816e+00, 1.7023821103e-01];
+    const D: [f32; 3] = [1.0000000000e+00, 7.3700164250e-01, 1.2021132975e-01];
+    const E: [f32; 4] = [6.6579051150e+00, 3.0812263860e+00, 4.2868294337e-01, 1.7337203997e-02];
+    const F: [f32; 3] = [1.0000000000e+00, 2.4197894225e-01, 1.2258202635e-02];
+
+    fn poly(c: &[f32], x: f32) -> f32 {
+        c.iter().rev().fold(0.0, |sum, &c| sum * x + c)
     }
 
     if p <= 0.0 {
@@ -187,162 +1152,161 @@ pub fn inverse(p: f64) -> f64 {
     }
 
     let q = p - 0.5;
-
-    if (if q < 0.0 { -q } else { q }) <= SPLIT1 {
-        let x = CONST1 - q * q;
+    if q.abs() <= SPLIT as f32 {
+        let x = 0.180625 - q * q;
         return q * poly(&A, x) / poly(&B, x);
     }
+    let r = (-(if q < 0.0 { p } else { 1.0 - p }).ln()).sqrt();
+    let x = if r <= 5.0 {
+        let r = r - 1.6;
+        poly(&C, r) / poly(&D, r)
+    } else {
+        let r = r - 5.0;
+        poly(&E, r) / poly(&F, r)
+    };
+    if q < 0.0 { -x } else { x }
+}
 
-    let mut x = if q < 0.0 { p } else { 1.0 - p };
-
-    x = (-x.ln()).sqrt();
+// Compute the natural logarithm of the cumulative distribution function of the
+// standard Gaussian distribution.
+fn ln_distribution(z: f64) -> f64 {
+    use special::Error;
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
 
-    if x <= SPLIT2 {
-        x -= CONST2;
-        x = poly(&C, x) / poly(&D, x);
-    } else {
-        x -= SPLIT2;
-        x = poly(&E, x) / poly(&F, x);
+    if z >= 0.0 {
+        return (-Error::erfc(z * FRAC_1_SQRT_2) / 2.0).ln_1p();
+    }
+    if z > -35.0 {
+        return (Error::erfc(-z * FRAC_1_SQRT_2) / 2.0).ln();
+    }
+    if z == ::std::f64::NEG_INFINITY {
+        return z;
     }
 
-    if q < 0.0 { -x } else { x }
+    // See [Abramowitz and Stegun, 1964, eq. 26.2.12].
+    let r = (z * z).recip();
+    let series = 1.0 - r * (1.0 - 3.0 * r * (1.0 - 5.0 * r * (1.0 - 7.0 * r * (1.0 - 9.0 * r))));
+    -z * z / 2.0 - (-z).ln() - (2.0 * PI).sqrt().ln() + series.ln()
 }
 
 /// Draw a sample from the standard Gaussian distribution.
 pub fn sample<S: Source>(source: &mut S) -> f64 {
-    loop {
-        let u = source.read::<u64>();
-
-        let i = (u & 0x7F) as usize;
-        let j = ((u >> 8) & 0xFFFFFF) as u32;
-        let s = if u & 0x80 != 0 { 1.0 } else { -1.0 };
-
-        if j < K[i] {
-            let x = j as f64 * W[i];
-            return s * x;
+    ziggurat::sample(source, &ZIGGURAT, |x| (-0.5 * x * x).exp(), |source| loop {
+        let x = R - (-source.read::<f64>()).ln_1p() / R;
+        let y = (-R * (x - 0.5 * R)).exp() * source.read::<f64>();
+        if y < (-0.5 * x * x).exp() {
+            return x;
         }
+    })
+}
 
-        let (x, y) = if i < 127 {
-            let x = j as f64 * W[i];
-            let y = Y[i + 1] + (Y[i] - Y[i + 1]) * source.read::<f64>();
-            (x, y)
-        } else {
-            let x = R - (-source.read::<f64>()).ln_1p() / R;
-            let y = (-R * (x - 0.5 * R)).exp() * source.read::<f64>();
-            (x, y)
-        };
+const R: f64 = 3.4426198558966523;
 
-        if y < (-0.5 * x * x).exp() {
-            return s * x;
-        }
-    }
-}
-
-const R: f64 = 3.44428647676;
-
-const K: [u32; 128] = [
-    00000000, 12590644, 14272653, 14988939,
-    15384584, 15635009, 15807561, 15933577,
-    16029594, 16105155, 16166147, 16216399,
-    16258508, 16294295, 16325078, 16351831,
-    16375291, 16396026, 16414479, 16431002,
-    16445880, 16459343, 16471578, 16482744,
-    16492970, 16502368, 16511031, 16519039,
-    16526459, 16533352, 16539769, 16545755,
-    16551348, 16556584, 16561493, 16566101,
-    16570433, 16574511, 16578353, 16581977,
-    16585398, 16588629, 16591685, 16594575,
-    16597311, 16599901, 16602354, 16604679,
-    16606881, 16608968, 16610945, 16612818,
-    16614592, 16616272, 16617861, 16619363,
-    16620782, 16622121, 16623383, 16624570,
-    16625685, 16626730, 16627708, 16628619,
-    16629465, 16630248, 16630969, 16631628,
-    16632228, 16632768, 16633248, 16633671,
-    16634034, 16634340, 16634586, 16634774,
-    16634903, 16634972, 16634980, 16634926,
-    16634810, 16634628, 16634381, 16634066,
-    16633680, 16633222, 16632688, 16632075,
-    16631380, 16630598, 16629726, 16628757,
-    16627686, 16626507, 16625212, 16623794,
-    16622243, 16620548, 16618698, 16616679,
-    16614476, 16612071, 16609444, 16606571,
-    16603425, 16599973, 16596178, 16591995,
-    16587369, 16582237, 16576520, 16570120,
-    16562917, 16554758, 16545450, 16534739,
-    16522287, 16507638, 16490152, 16468907,
-    16442518, 16408804, 16364095, 16301683,
-    16207738, 16047994, 15704248, 15472926
-];
+const ZIGGURAT: Ziggurat = Ziggurat { k: &K, w: &W, y: &Y, symmetric: true };
 
-const Y: [f64; 128] = [
-    1.0000000000000, 0.96359862301100, 0.93628081335300, 0.91304110425300,
-    0.8922785066960, 0.87323935691900, 0.85549640763400, 0.83877892834900,
-    0.8229020836990, 0.80773273823400, 0.79317104551900, 0.77913972650500,
-    0.7655774360820, 0.75243445624800, 0.73966978767700, 0.72724912028500,
-    0.7151433774130, 0.70332764645500, 0.69178037703500, 0.68048276891000,
-    0.6694182972330, 0.65857233912000, 0.64793187618900, 0.63748525489600,
-    0.6272219914500, 0.61713261153200, 0.60720851746700, 0.59744187729600,
-    0.5878255314650, 0.57835291380300, 0.56901798419800, 0.55981517091100,
-    0.5507393208770, 0.54178565668200, 0.53294973914500, 0.52422743462800,
-    0.5156148863730, 0.50710848925300, 0.49870486747800, 0.49040085481200,
-    0.4821934769860, 0.47407993601000, 0.46605759612500, 0.45812397121400,
-    0.4502767134670, 0.44251360317100, 0.43483253947300, 0.42723153202200,
-    0.4197086933790, 0.41226223212000, 0.40489044654800, 0.39759171895500,
-    0.3903645103820, 0.38320735581600, 0.37611885978800, 0.36909769233400,
-    0.3621425852820, 0.35525232883400, 0.34842576841500, 0.34166180177600,
-    0.3349593763110, 0.32831748658800, 0.32173517206300, 0.31521151497000,
-    0.3087456383670, 0.30233670433800, 0.29598391232000, 0.28968649757100,
-    0.2834437297390, 0.27725491156000, 0.27111937764900, 0.26503649338700,
-    0.2590056539120, 0.25302628318300, 0.24709783313900, 0.24121978293200,
-    0.2353916382390, 0.22961293064900, 0.22388321712200, 0.21820207951800,
-    0.2125691242010, 0.20698398170900, 0.20144630649600, 0.19595577674500,
-    0.1905120942560, 0.18511498440600, 0.17976419618500, 0.17445950232400,
-    0.1692006994920, 0.16398760860000, 0.15882007519500, 0.15369796996400,
-    0.1486211893480, 0.14358965629500, 0.13860332114300, 0.13366216266900,
-    0.1287661893090, 0.12391544058200, 0.11910998874500, 0.11434994070300,
-    0.1096354402300, 0.10496667053300, 0.10034385723200, 0.09576727182660,
-    0.0912372357329, 0.08675412501270, 0.08231837593200, 0.07793049152950,
-    0.0735910494266, 0.06930071117420, 0.06506023352900, 0.06087048217450,
-    0.0567324485840, 0.05264727098000, 0.04861626071630, 0.04464093597690,
-    0.0407230655415, 0.03686472673860, 0.03306838393780, 0.02933699774110,
-    0.0256741818288, 0.02208443726340, 0.01857352005770, 0.01514905528540,
-    0.0118216532614, 0.00860719483079, 0.00553245272614, 0.00265435214565,
+const K: [u64; 128] = [
+    0, 3379775975566161, 3831286734526851, 4023563283590518,
+    4129768419115227, 4196991532633768, 4243310511083078, 4277137686796669,
+    4302912106990730, 4323195228662123, 4339567658642611, 4353057144122516,
+    4364360617431756, 4373967262173819, 4382230485636501, 4389411929903501,
+    4395709417334893, 4401275276263186, 4406228698624106, 4410664279175826,
+    4414658044733433, 4418271793565810, 4421556272609774, 4424553539968471,
+    4427298746340481, 4429821495487545, 4432146895355719, 4434296378887524,
+    4436288351302870, 4438138705177786, 4439861233773350, 4441467965307511,
+    4442969435257930, 4444374909689735, 4445692569578957, 4446929663848128,
+    4448092637133591, 4449187237015762, 4450218604457139, 4451191350431949,
+    4452109621139927, 4452977153733924, 4453797324126574, 4454573188152275,
+    4455307517130500, 4456002828691723, 4456661413578440, 4457285359013123,
+    4457876569126857, 4458436782861960, 4458967589696029, 4459470443480259,
+    4459946674639829, 4460397500946502, 4460824037042185, 4461227302865859,
+    4461608231114029, 4461967673846078, 4462306408329867, 4462625142209357,
+    4462924518064216, 4463205117421310, 4463467464269207, 4463712028119065,
+    4463939226648588, 4464149427959626, 4464342952474581, 4464520074491855,
+    4464681023415911, 4464825984673288, 4464955100321656, 4465068469355018,
+    4465166147704041, 4465248147926355, 4465314438577310, 4465364943247031,
+    4465399539244577, 4465418055904430, 4465420272484300, 4465405915616076,
+    4465374656263637, 4465326106131623, 4465259813458241, 4465175258111877,
+    4465071845895692, 4464948901945609, 4464805663084419, 4464641268967427,
+    4464454751821617, 4464245024539475, 4464010866838201, 4463750909132682,
+    4463463613692800, 4463147252558230, 4462799881560920, 4462419309649370,
+    4462003062509373, 4461548339218994, 4461051960342455, 4460510305431869,
+    4459919237331224, 4459274009912251, 4458569154844191, 4457798341604028,
+    4456954203017901, 4456028115962157, 4455009923105172, 4453887576222420,
+    4452646673865708, 4451269854747306, 4449735991066149, 4448019099782901,
+    4446086848831429, 4443898469498492, 4441401777876553, 4438528824267547,
+    4435189365694384, 4431260764018535, 4426571775788001, 4420875399586245,
+    4413800979183156, 4404764157628087, 4392783389226545, 4376064387201799,
+    4350911317440051, 4308179400602085, 4216406383257514, 4175551137225862,
 ];
 
 const W: [f64; 128] = [
-    1.62318314817e-08, 2.16291505214e-08, 2.54246305087e-08, 2.84579525938e-08,
-    3.10340022482e-08, 3.33011726243e-08, 3.53439060345e-08, 3.72152672658e-08,
-    3.89509895720e-08, 4.05763964764e-08, 4.21101548915e-08, 4.35664624904e-08,
-    4.49563968336e-08, 4.62887864029e-08, 4.75707945735e-08, 4.88083237257e-08,
-    5.00063025384e-08, 5.11688950428e-08, 5.22996558616e-08, 5.34016475624e-08,
-    5.44775307871e-08, 5.55296344581e-08, 5.65600111659e-08, 5.75704813695e-08,
-    5.85626690412e-08, 5.95380306862e-08, 6.04978791776e-08, 6.14434034901e-08,
-    6.23756851626e-08, 6.32957121259e-08, 6.42043903937e-08, 6.51025540077e-08,
-    6.59909735447e-08, 6.68703634341e-08, 6.77413882848e-08, 6.86046683810e-08,
-    6.94607844804e-08, 7.03102820203e-08, 7.11536748229e-08, 7.19914483720e-08,
-    7.28240627230e-08, 7.36519550992e-08, 7.44755422158e-08, 7.52952223703e-08,
-    7.61113773308e-08, 7.69243740467e-08, 7.77345662086e-08, 7.85422956743e-08,
-    7.93478937793e-08, 8.01516825471e-08, 8.09539758128e-08, 8.17550802699e-08,
-    8.25552964535e-08, 8.33549196661e-08, 8.41542408569e-08, 8.49535474601e-08,
-    8.57531242006e-08, 8.65532538723e-08, 8.73542180955e-08, 8.81562980590e-08,
-    8.89597752521e-08, 8.97649321908e-08, 9.05720531451e-08, 9.13814248700e-08,
-    9.21933373471e-08, 9.30080845407e-08, 9.38259651738e-08, 9.46472835298e-08,
-    9.54723502847e-08, 9.63014833769e-08, 9.71350089201e-08, 9.79732621669e-08,
-    9.88165885297e-08, 9.96653446693e-08, 1.00519899658e-07, 1.01380636230e-07,
-    1.02247952126e-07, 1.03122261554e-07, 1.04003996769e-07, 1.04893609795e-07,
-    1.05791574313e-07, 1.06698387725e-07, 1.07614573423e-07, 1.08540683296e-07,
-    1.09477300508e-07, 1.10425042570e-07, 1.11384564771e-07, 1.12356564007e-07,
-    1.13341783071e-07, 1.14341015475e-07, 1.15355110887e-07, 1.16384981291e-07,
-    1.17431607977e-07, 1.18496049514e-07, 1.19579450872e-07, 1.20683053909e-07,
-    1.21808209468e-07, 1.22956391410e-07, 1.24129212952e-07, 1.25328445797e-07,
-    1.26556042658e-07, 1.27814163916e-07, 1.29105209375e-07, 1.30431856341e-07,
-    1.31797105598e-07, 1.33204337360e-07, 1.34657379914e-07, 1.36160594606e-07,
-    1.37718982103e-07, 1.39338316679e-07, 1.41025317971e-07, 1.42787873535e-07,
-    1.44635331499e-07, 1.46578891730e-07, 1.48632138436e-07, 1.50811780719e-07,
-    1.53138707402e-07, 1.55639532047e-07, 1.58348931426e-07, 1.61313325908e-07,
-    1.64596952856e-07, 1.68292495203e-07, 1.72541128694e-07, 1.77574279496e-07,
-    1.83813550477e-07, 1.92166040885e-07, 2.05295471952e-07, 2.22600839893e-07,
+    6.0467378816192385e-17, 8.0573643541287366e-17, 9.4712679100286179e-17, 1.0601249594927966e-16,
+    1.1560886442055743e-16, 1.2405458402209775e-16, 1.3166422228971378e-16, 1.3863545807104384e-16,
+    1.4510140616042940e-16, 1.5115640264926770e-16, 1.5686998618172029e-16, 1.6229504642903593e-16,
+    1.6747284990670516e-16, 1.7243628477906511e-16, 1.7721203629556574e-16, 1.8182209220081615e-16,
+    1.8628481297105132e-16, 1.9061571058866025e-16, 1.9482802684438856e-16, 1.9893317050684092e-16,
+    2.0294105307545681e-16, 2.0686035031610823e-16, 2.1069870859100912e-16, 2.1446290951757251e-16,
+    2.1815900275229654e-16, 2.2179241409695400e-16, 2.2536803428769865e-16, 2.2889029250993270e-16,
+    2.3236321772314859e-16, 2.3579049017366417e-16, 2.3917548494666072e-16, 2.4252130901215697e-16,
+    2.4583083291752331e-16, 2.4910671804706633e-16, 2.5235144018933045e-16, 2.5556731001218960e-16,
+    2.5875649093510761e-16, 2.6192101480015085e-16, 2.6506279567323722e-16, 2.6818364205078073e-16,
+    2.7128526770136294e-16, 2.7436930133506054e-16, 2.7743729526282446e-16, 2.8049073318348695e-16,
+    2.8353103721550097e-16, 2.8655957427356471e-16, 2.8957766187618856e-16, 2.9258657345850733e-16,
+    2.9558754325479990e-16, 2.9858177080693025e-16, 3.0157042514798420e-16, 3.0455464870454316e-16,
+    3.0753556095611798e-16, 3.1051426188613163e-16, 3.1349183525536636e-16, 3.1646935172588144e-16,
+    3.1944787186099208e-16, 3.2242844902490676e-16, 3.2541213220400490e-16, 3.2839996877045386e-16,
+    3.3139300720788431e-16, 3.3439229981814274e-16, 3.3739890542769299e-16, 3.4041389211204731e-16,
+    3.4343833995664851e-16, 3.4647334387290865e-16, 3.4952001648863590e-16, 3.5257949113285415e-16,
+    3.5565292493606237e-16, 3.5874150206830126e-16, 3.6184643713903042e-16, 3.6496897878479279e-16,
+    3.6811041347300136e-16, 3.7127206955297539e-16, 3.7445532158863746e-16, 3.7766159501113651e-16,
+    3.8089237113417099e-16, 3.8414919258006202e-16, 3.8743366917079517e-16, 3.9074748434547338e-16,
+    3.9409240217408874e-16, 3.9747027504746668e-16, 4.0088305213493703e-16, 4.0433278871510178e-16,
+    4.0782165650141361e-16, 4.1135195510368656e-16, 4.1492612478978122e-16, 4.1854676073935112e-16,
+    4.2221662901471963e-16, 4.2593868451395624e-16, 4.2971609121964535e-16, 4.3355224511574020e-16,
+    4.3745080021689059e-16, 4.4141569824308865e-16, 4.4545120258176302e-16, 4.4956193731525361e-16,
+    4.5375293226139087e-16, 4.5802967518859015e-16, 4.6239817263765141e-16, 4.6686502112813760e-16,
+    4.7143749097195767e-16, 4.7612362549382098e-16, 4.8093235921361304e-16, 4.8587365954411617e-16,
+    4.9095869789067067e-16, 4.9620005783947036e-16, 5.0161199058103680e-16, 5.0721073112073457e-16,
+    5.1301489360944307e-16, 5.1904597094488266e-16, 5.2532897368040293e-16, 5.3189325788404575e-16,
+    5.3877361361869540e-16, 5.4601171970610776e-16, 5.5365812425644007e-16, 5.6177499816133892e-16,
+    5.7044005650689112e-16, 5.7975230137255219e-16, 5.8984071210814568e-16, 6.0087791922541155e-16,
+    6.1310276242213611e-16, 6.2685975311581967e-16, 6.4267347155559280e-16, 6.6140343261023407e-16,
+    6.8461433371563052e-16, 7.1566863204056001e-16, 7.6441516580964038e-16, 8.2447076871003130e-16,
+];
+
+const Y: [f64; 128] = [
+    1.0000000000000000e0, 9.6359969315576477e-1, 9.3628268170836881e-1, 9.1304364799203608e-1,
+    8.9228165080230093e-1, 8.7324304892685189e-1, 8.5550060788506266e-1, 8.3878360531064566e-1,
+    8.2290721139526057e-1, 8.0773829469611980e-1, 7.9317701178385791e-1, 7.7914608594170187e-1,
+    7.6558417390923472e-1, 7.5244155918570255e-1, 7.3967724368333698e-1, 7.2725691835450479e-1,
+    7.1515150742047595e-1, 7.0333609902581640e-1, 6.9178914344603482e-1, 6.8049184100641336e-1,
+    6.6942766735770520e-1, 6.5858200005865275e-1, 6.4794182111854992e-1, 6.3749547734314405e-1,
+    6.2723248525781372e-1, 6.1714337082656168e-1, 6.0721953663260408e-1, 5.9745315095181151e-1,
+    5.8783705444181977e-1, 5.7836468112670159e-1, 5.6902999107472085e-1, 5.5982741271069403e-1,
+    5.5075179312105449e-1, 5.4179835503172336e-1, 5.3296265938998688e-1, 5.2424057267899216e-1,
+    5.1562823824987147e-1, 5.0712205108130404e-1, 4.9871863547658379e-1, 4.9041482528932112e-1,
+    4.8220764633483820e-1, 4.7409430069824915e-1, 4.6607215269457053e-1, 4.5813871627287150e-1,
+    4.5029164368692653e-1, 4.4252871528024618e-1, 4.3484783025466145e-1, 4.2724699830956198e-1,
+    4.1972433205403781e-1, 4.1227804010702418e-1, 4.0490642081148792e-1, 3.9760785649804214e-1,
+    3.9038080824138910e-1, 3.8322381105988329e-1, 3.7613546951445409e-1, 3.6911445366827483e-1,
+    3.6215949537303294e-1, 3.5526938485154685e-1, 3.4844296754987220e-1, 3.4167914123501342e-1,
+    3.3497685331697091e-1, 3.2833509837615216e-1, 3.2175291587920840e-1, 3.1522938806815731e-1,
+    3.0876363800925172e-1, 3.0235482778947959e-1, 2.9600215684985565e-1, 2.8970486044581029e-1,
+    2.8346220822601231e-1, 2.7727350292189751e-1, 2.7113807914102506e-1, 2.6505530225816171e-1,
+    2.5902456739871049e-1, 2.5304529850976559e-1, 2.4711694751469648e-1, 2.4123899354775108e-1,
+    2.3541094226572742e-1, 2.2963232523430246e-1, 2.2390269938713364e-1, 2.1822164655637036e-1,
+    2.1258877307373586e-1, 2.0700370944187357e-1, 2.0146611007620302e-1, 1.9597565311811019e-1,
+    1.9053204032091350e-1, 1.8513499701071323e-1, 1.7978427212496192e-1, 1.7447963833240213e-1,
+    1.6922089223892456e-1, 1.6400785468492757e-1, 1.5884037114093491e-1, 1.5371831220958640e-1,
+    1.4864157424369681e-1, 1.4361008009193285e-1, 1.3862377998585090e-1, 1.3368265258464751e-1,
+    1.2878670619710383e-1, 1.2393598020398162e-1, 1.1913054670871848e-1, 1.1437051244988816e-1,
+    1.0965602101581766e-1, 1.0498725541035445e-1, 1.0036444102954545e-1, 9.5787849122578067e-2,
+    9.1257800827634628e-2, 8.6774671895542888e-2, 8.2338898242957329e-2, 7.7950982514654640e-2,
+    7.3611501884754821e-2, 6.9321117394180190e-2, 6.5080585213631817e-2, 6.0890770348566325e-2,
+    5.6752663481538533e-2, 5.2667401903503122e-2, 4.8636295860284007e-2, 4.4660862200872391e-2,
+    4.0742868074790571e-2, 3.6884388786968744e-2, 3.3087886146505131e-2, 2.9356317440253809e-2,
+    2.5693291936149599e-2, 2.2103304616111576e-2, 1.8592102737165800e-2, 1.5167298010672032e-2,
+    1.1839478657982306e-2, 8.6244844129304658e-3, 5.5489952208164668e-3, 2.6696290839025019e-3,
 ];
 
 #[cfg(test)]
@@ -355,8 +1319,148 @@ mod tests {
         ($mu:expr, $sigma:expr) => (Gaussian::new($mu, $sigma));
     );
 
+    #[test]
+    fn add() {
+        let d = new!(1.0, 3.0) + new!(-2.0, 4.0);
+        assert_eq!((d.mu(), d.sigma()), (-1.0, 5.0));
+    }
+
+    #[test]
+    fn bivariate_cdf() {
+        use copulas::GaussianCopula;
+        use distribution::gaussian::{bivariate_cdf, owens_t};
+        use std::f64::consts::PI;
+
+        let cases = vec![
+            ((0.0, 0.0, 0.5), 0.33333333333333333333),
+            ((1.0, -0.5, 0.3), 0.28313842024448095212),
+            ((-1.0, 2.0, -0.7), 0.14021985419403971253),
+            ((0.5, 0.5, 0.95), 0.64690719536678961108),
+            ((-2.0, -1.5, 0.99), 0.022749515662487262648),
+            ((1.5, -1.0, -0.95), 0.093243825314026883334),
+            ((-3.0, -3.0, 0.5), 8.1889661832192112167e-5),
+            ((2.0, 1.0, -0.2), 0.82010981220242567729),
+            ((-8.0, -8.0, 0.9), 3.8902724959148900329e-17),
+        ];
+        for &((x, y, rho), p) in &cases {
+            assert::close(bivariate_cdf(x, y, rho), p, 1e-15);
+            assert::close(bivariate_cdf(y, x, rho), p, 1e-15);
+        }
+
+        let d = StandardGaussian;
+        for &rho in &[-0.99, -0.9, -0.5, -0.1, 0.1, 0.5, 0.9, 0.99] {
+            assert::close(bivariate_cdf(0.0, 0.0, rho), 0.25 + rho.asin() / (2.0 * PI), 1e-15);
+            for &x in &[-3.0, -1.0, 0.0, 0.5, 2.0] {
+                let a = ((1.0 - rho) / (1.0 + rho)).sqrt();
+                assert::close(bivariate_cdf(x, x, rho), d.distribution(x) - 2.0 * owens_t(x, a),
+                              1e-15);
+                for &y in &[-2.0, 0.3, 1.5] {
+                    assert::close(bivariate_cdf(x, y, rho) + bivariate_cdf(x, -y, -rho),
+                                  d.distribution(x), 1e-15);
+                }
+            }
+        }
+
+        assert_eq!(bivariate_cdf(0.5, -1.0, 0.0), d.distribution(0.5) * d.distribution(-1.0));
+        assert_eq!(bivariate_cdf(0.5, -1.0, 1.0), d.distribution(-1.0));
+        assert::close(bivariate_cdf(0.5, -0.2, -1.0), d.distribution(0.5) - d.distribution(0.2),
+                      1e-16);
+        assert_eq!(bivariate_cdf(0.5, -1.0, -1.0), 0.0);
+        assert::close(bivariate_cdf(0.5, -1.0, 1.0 - 1e-12), d.distribution(-1.0), 1e-12);
+        assert::close(bivariate_cdf(0.5, -0.2, -1.0 + 1e-12),
+                      d.distribution(0.5) - d.distribution(0.2), 1e-12);
+        assert_eq!(bivariate_cdf(NEG_INFINITY, 1.0, 0.5), 0.0);
+        assert_eq!(bivariate_cdf(INFINITY, 1.0, 0.5), d.distribution(1.0));
+        assert_eq!(bivariate_cdf(INFINITY, INFINITY, -0.5), 1.0);
+        assert_eq!(bivariate_cdf(-40.0, -40.0, 0.5), 0.0);
+        assert_eq!(bivariate_cdf(40.0, 40.0, -0.5), 1.0);
+        assert!(bivariate_cdf(::std::f64::NAN, 1.0, 0.5).is_nan());
+
+        let mut source = source::seeded(42);
+        let n = 100000;
+        for &rho in &[-0.8, 0.3, 0.95] {
+            let copula = GaussianCopula::new(vec![vec![1.0, rho], vec![rho, 1.0]]);
+            let (x, y) = (0.3, -0.4);
+            let (u, v) = (d.distribution(x), d.distribution(y));
+            let count = (0..n).filter(|_| {
+                let w = copula.sample_uniform(&mut source);
+                w[0] <= u && w[1] <= v
+            }).count();
+            assert::close(count as f64 / n as f64, bivariate_cdf(x, y, rho), 0.01);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn bivariate_cdf_correlation() {
+        ::distribution::gaussian::bivariate_cdf(0.0, 0.0, 1.5);
+    }
+
+    #[test]
+    fn central_moment() {
+        let d = new!(1.0, 2.0);
+        assert_eq!(d.central_moment(0), 1.0);
+        assert_eq!(d.central_moment(1), 0.0);
+        assert_eq!(d.central_moment(2), 4.0);
+        assert_eq!(d.central_moment(7), 0.0);
+        assert_eq!(d.central_moment(8), 26880.0);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-14);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-14);
+    }
+
+    #[test]
+    fn cross_entropy() {
+        let (p, q) = (new!(1.0, 2.0), new!(-1.0, 3.0));
+        assert::close(p.cross_entropy(&p), p.entropy(), 1e-15);
+        assert::close(p.cross_entropy(&q), 2.4619952663172268776, 1e-15);
+    }
+
+    #[test]
+    fn cumulative_hazard() {
+        use std::f64::consts::LN_2;
+        let d = new!(1.0, 2.0);
+        let x = vec![-15.0, 1.0, 21.0, 81.0];
+        let p = vec![
+            6.2209605742717860585e-16, LN_2, 53.231285150512470578,
+            804.60844201375378817,
+        ];
+        assert::close(&x.iter().map(|&x| d.cumulative_hazard(x)).collect::<Vec<_>>(), &p, 1e-12);
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Gaussian::default(), Gaussian::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn degenerate() {
+        let d = new!(1.5, 0.0);
+        let x = [NEG_INFINITY, 1.0, 1.5, 2.0, INFINITY];
+        assert_eq!(d.density_vec(&x), vec![0.0, 0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!(d.distribution_vec(&x), vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                   vec![1.0, 1.0, 0.0, 0.0, 0.0]);
+        assert_eq!((d.ln_density(1.5), d.ln_density(2.0)), (INFINITY, NEG_INFINITY));
+        assert_eq!((d.ln_distribution(1.0), d.ln_survival(1.0)), (NEG_INFINITY, 0.0));
+        for &p in &[0.0, 0.25, 1.0] {
+            assert_eq!((d.inverse(p), d.inverse_survival(p)), (1.5, 1.5));
+        }
+        assert_eq!(d.inverse_ln(NEG_INFINITY), 1.5);
+        assert_eq!((d.log_likelihood(&[1.5, 1.5]), d.log_likelihood(&[1.5, 2.0])),
+                   (INFINITY, NEG_INFINITY));
+        assert!(d.samples(&mut source::seeded(42), 10).iter().all(|&x| x == 1.5));
+        assert_eq!((d.mean(), d.variance(), d.entropy()), (1.5, 0.0, NEG_INFINITY));
+        assert_eq!((d.skewness(), d.kurtosis()), (0.0, 0.0));
+        assert_eq!(d.kl_divergence(&d), 0.0);
+        assert_eq!(d.kl_divergence(&new!(1.5, 1.0)), INFINITY);
+        assert_eq!(new!(1.5, 1.0).kl_divergence(&d), INFINITY);
+        assert_eq!(Gaussian::from_deviation(1.5f32, 0.0).inverse(0.0), 1.5);
+    }
+
     #[test]
     fn density() {
+        use std::f64::consts::PI;
+        use std::f64::EPSILON;
         let d = new!(1.0, 2.0);
         let x = vec![
             -4.0, -3.5, -3.0, -2.5, -2.0, -1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5,
@@ -372,6 +1476,37 @@ mod tests {
         ];
 
         assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-14);
+
+        // The cached constants change the rounding of the exponent, whose error
+        // is amplified by the exponential, so the results agree with the direct
+        // formulas to a number of units in the last place growing with it.
+        for &(mu, sigma) in &[(1.0, 2.0), (-3.0, 0.1), (0.0, 1.0), (1e3, 7.5)] {
+            let (d, norm) = (new!(mu, sigma), (2.0 * PI).sqrt() * sigma);
+            for i in -100..101 {
+                let x = mu + sigma * i as f64 / 10.0;
+                let exponent = -((x - mu) / sigma).powi(2) / 2.0;
+                let ulps = 4.0 * EPSILON * (1.0 + exponent.abs());
+                let (density, ln_density) = (exponent.exp() / norm, exponent - norm.ln());
+                assert::close(d.density(x), density, ulps * density);
+                assert::close(d.ln_density(x), ln_density, ulps * (1.0 + norm.ln().abs()));
+            }
+        }
+    }
+
+    #[test]
+    fn density_slice() {
+        let d = new!(1.0, 2.0);
+        let x = (-80..81).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let p = d.density_vec(&x);
+        for (&x, &p) in x.iter().zip(&p) {
+            assert::close(p, d.density(x), 1e-15 * d.density(x));
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Gaussian::new(0.0, 1.0).to_string(), "Gaussian(μ = 0, σ = 1)");
+        assert_eq!(Gaussian::new(-1.5, 0.25).to_string(), "Gaussian(μ = -1.5, σ = 0.25)");
     }
 
     #[test]
@@ -393,12 +1528,164 @@ mod tests {
         assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-14);
     }
 
+    #[test]
+    fn distribution_slice() {
+        let d = new!(1.0, 2.0);
+        let x = (-80..81).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let mut p = vec![0.0; x.len()];
+        d.distribution_slice(&x, &mut p);
+        for (&x, &p) in x.iter().zip(&p) {
+            assert::close(p, d.distribution(x), 1e-13 * d.distribution(x));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the output should have the same length as the input (got 2 and 3)")]
+    fn distribution_slice_mismatch() {
+        new!(1.0, 2.0).distribution_slice(&[0.0, 1.0, 2.0], &mut [0.0, 0.0]);
+    }
+
+    #[test]
+    fn distribution_tail() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-19.0, -39.0, -59.0, -73.0];
+        let p = vec![
+            7.619853024160526066e-24, 2.7536241186062336951e-89, 4.9067139271481870595e-198,
+            5.7255712225245768227e-300,
+        ];
+        for (&x, &p) in x.iter().zip(&p) {
+            assert::close(d.distribution(x) / p, 1.0, 1e-12);
+            assert::close(d.survival(2.0 - x) / p, 1.0, 1e-12);
+            assert::close(StandardGaussian.distribution((x - 1.0) / 2.0) / p, 1.0, 1e-12);
+        }
+    }
+
     #[test]
     fn entropy() {
         use std::f64::consts::PI;
         assert_eq!(new!(0.0, 1.0).entropy(), ((2.0 * PI).ln() + 1.0) / 2.0);
     }
 
+    #[test]
+    fn fisher_information() {
+        assert_eq!(new!(1.0, 2.0).fisher_information(), vec![0.25, 0.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn fit_mle() {
+        use std::f64::{INFINITY, NAN};
+
+        let d = Gaussian::fit_mle(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(d.mu(), 2.5);
+        assert::close(d.variance(), 1.25, 1e-15);
+
+        let n = 100000;
+        let x = new!(1.0, 2.0).samples(&mut source::seeded(42), n);
+        let d = Gaussian::fit_mle(&x).unwrap();
+        assert!((d.mu() - 1.0).abs() < 5.0 * 2.0 / (n as f64).sqrt());
+        assert!((d.variance() - 4.0).abs() < 5.0 * 4.0 * (2.0 / n as f64).sqrt());
+
+        assert_eq!(Gaussian::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Gaussian::fit_mle(&[1.0, 1.0]), Ok(new!(1.0, 0.0)));
+        assert_eq!(Gaussian::fit_mle(&[1.0, INFINITY]),
+                   Err(FitError::DatumNotFinite { value: INFINITY }));
+        assert!(Gaussian::fit_mle(&[1.0, NAN]).is_err());
+        assert_eq!(Gaussian::fit_mle(&[-1e300, 1e300]),
+                   Err(FitError::Parameter(ParameterError::VarianceNotNonnegative {
+                       value: INFINITY,
+                   })));
+    }
+
+    #[test]
+    fn fit_mle_with_ci() {
+        use distribution;
+
+        let d = new!(1.0, 2.0);
+        let mut source = source::seeded(42);
+        let (m, n) = (2000, 50);
+        let mut covered = [0; 2];
+        for _ in 0..m {
+            let x = d.samples(&mut source, n);
+            let fit = Gaussian::fit_mle_with_ci(&x, 0.95).unwrap();
+            for (i, &value) in [1.0, 2.0].iter().enumerate() {
+                let (a, b) = fit.intervals[i];
+                covered[i] += (a < value && value < b) as usize;
+            }
+            assert!(fit.intervals[1].0 > 0.0);
+
+            let information = distribution::observed_information(&fit.distribution, &x);
+            let expected = fit.distribution.fisher_information().iter().map(|&i| n as f64 * i)
+                                                                       .collect::<Vec<_>>();
+            assert::close(&information, &expected, 1e-5 * expected[3]);
+        }
+        for &covered in &covered {
+            let coverage = covered as f64 / m as f64;
+            assert!(0.93 < coverage && coverage < 0.97);
+        }
+
+        assert_eq!(Gaussian::fit_mle_with_ci(&[1.0, 2.0], 1.0).err(),
+                   Some(FitError::Parameter(ParameterError::ProbabilityOutOfRange { value: 1.0 })));
+    }
+
+    #[test]
+    fn fit_moments() {
+        let x = new!(1.0, 2.0).samples(&mut source::seeded(42), 1000);
+        let mean = x.iter().sum::<f64>() / 1000.0;
+        let variance = x.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / 1000.0;
+        let d = Gaussian::fit_moments(&x).unwrap();
+        assert::close(d.mean(), mean, 1e-12);
+        assert::close(d.variance(), variance, 1e-12);
+
+        assert_eq!(Gaussian::fit_moments(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Gaussian::fit_moments(&[1.0]), Ok(new!(1.0, 0.0)));
+        assert_eq!(Gaussian::fit_moments_from(1.0, -1.0),
+                   Err(FitError::MomentsInfeasible { mean: 1.0, variance: -1.0 }));
+    }
+
+    #[test]
+    fn from_precision() {
+        let d = Gaussian::from_precision(0.0, 4.0);
+        assert_eq!(d.sigma(), 0.5);
+        assert_eq!(d.precision(), 4.0);
+        assert_eq!(Gaussian::from_precision(1.0, 0.25), new!(1.0, 2.0));
+        assert_eq!(Gaussian::try_from_precision(0.0, 0.0),
+                   Err(ParameterError::PrecisionNotPositive { value: 0.0 }));
+        assert_eq!(Gaussian::try_from_precision(INFINITY, 1.0),
+                   Err(ParameterError::MuNotFinite { value: INFINITY }));
+    }
+
+    #[test]
+    fn from_variance() {
+        let d = Gaussian::from_variance(1.0, 4.0);
+        assert_eq!(d, new!(1.0, 2.0));
+        assert_eq!(d.variance(), 4.0);
+        assert_eq!(Gaussian::from_variance(1.0, 0.0), new!(1.0, 0.0));
+        assert_eq!(Gaussian::try_from_variance(0.0, -1.0),
+                   Err(ParameterError::VarianceNotNonnegative { value: -1.0 }));
+    }
+
+    #[test]
+    fn hazard() {
+        let d = new!(0.0, 1.0);
+        let x = vec![0.0, 1.0, 3.0, 10.0];
+        let p = vec![
+            0.79788456080286535588, 1.5251352761609812091, 3.2830986549304365069,
+            10.098093233962511963,
+        ];
+        assert::close(&x.iter().map(|&x| d.hazard(x)).collect::<Vec<_>>(), &p, 1e-12);
+        assert!((d.density(40.0) / d.survival(40.0)).is_nan());
+        assert::close(d.hazard(40.0) / 40.0, 1.0, 1e-3);
+        assert::close(new!(1.0, 2.0).hazard(21.0), p[3] / 2.0, 1e-12);
+    }
+
+    #[test]
+    fn hpd_interval() {
+        let d = new!(1.0, 2.0);
+        let (lower, upper) = d.hpd_interval(0.95);
+        assert::close(lower, 1.0 - 2.0 * 1.959963984540054, 1e-8);
+        assert::close(upper, 1.0 + 2.0 * 1.959963984540054, 1e-8);
+    }
+
     #[test]
     fn inverse() {
         let d = new!(-1.0, 0.25);
@@ -419,16 +1706,156 @@ mod tests {
         assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x, 1e-14);
     }
 
+    #[test]
+    fn inverse_ln() {
+        let d = new!(0.0, 1.0);
+        let ln_p = vec![-1e-20, -1e-5, -3.0, -30.0, -745.0, -1000.0, -1e5, -1e20];
+        let x = vec![
+            9.2623400897984075796, 4.2648919103089225399, -1.6469217205277147766,
+            -7.3576668150087499278, -38.481948964330200141, -44.61574773196940302,
+            -447.19789367852505149, -14142135623.730950486,
+        ];
+        for (&ln_p, &x) in ln_p.iter().zip(&x) {
+            assert::close(d.inverse_ln(ln_p) / x, 1.0, 1e-15);
+        }
+        for i in 1..100 {
+            let p = i as f64 / 100.0;
+            assert::close(d.inverse_ln(p.ln()), d.inverse(p), 1e-14);
+        }
+        assert_eq!(d.inverse_ln(0.0), INFINITY);
+        assert_eq!(d.inverse_ln(NEG_INFINITY), NEG_INFINITY);
+        assert_eq!(new!(1.0, 2.0).inverse_ln(-1000.0), 1.0 + 2.0 * d.inverse_ln(-1000.0));
+    }
+
+    #[test]
+    fn inverse_survival() {
+        let d = new!(1.0, 2.0);
+        let p = vec![0.0, 0.05, 0.5, 0.95, 1.0];
+        assert::close(&p.iter().map(|&p| d.inverse_survival(p)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| d.inverse(1.0 - p)).collect::<Vec<_>>(), 1e-14);
+        assert::close(new!(0.0, 1.0).inverse_survival(1e-300), 37.047096299361199, 1e-12);
+        assert_eq!(new!(0.0, 1.0).inverse(1.0 - 1e-300), INFINITY);
+    }
+
+    #[test]
+    fn inverse_tail() {
+        use std::f64::EPSILON;
+
+        let d = new!(0.0, 1.0);
+        let mut p = (1..14).map(|i| 10f64.powi(-i)).collect::<Vec<_>>();
+        p.extend((1..17).map(|i| 1.0 - 10f64.powi(-i)));
+        for &p in &p {
+            let x = d.inverse(p);
+            assert::close(d.distribution(x) / p, 1.0, 1e-14);
+        }
+
+        // A change of x by one unit in the last place changes the distribution
+        // function by about x^2 units in the last place in relative terms, and
+        // so does the rounding of the argument of erfc. The round trip cannot
+        // attain 1e-14 beyond |x| of about 7, and the quantiles are compared
+        // with the exact values instead.
+        for i in 14..301 {
+            let p = 10f64.powi(-i);
+            let x = d.inverse(p);
+            assert::close(d.distribution(x) / p, 1.0, 4.0 * EPSILON * x * x);
+        }
+        let p = [1e-20, 1e-50, 1e-100, 1e-150, 1e-200, 1e-250, 1e-300, 5e-324];
+        let x = [
+            -9.262340089798407579572, -14.93333753478848898066, -21.27345356096532429418,
+            -26.12296119059398350925, -30.20559417957964306312, -33.79958617269483746959,
+            -37.04709629936119923655, -38.46740561714434625078,
+        ];
+        for (&p, &x) in p.iter().zip(x.iter()) {
+            assert::close(d.inverse(p) / x, 1.0, 1e-15);
+        }
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let (p, q) = (new!(1.0, 2.0), new!(-1.0, 3.0));
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&q), 0.34990955255260882642, 1e-15);
+        assert::close(q.kl_divergence(&p), 0.71953489189183561802, 1e-15);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.0, 2.0).kurtosis(), 0.0);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-4.0, 0.0, 1.0, 2.5, 4.0];
+        assert::close(&x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| d.density(x).ln()).collect::<Vec<_>>(), 1e-14);
+        assert_eq!(d.density(1000.0), 0.0);
+        assert::close(d.ln_density(1000.0), -124751.73708571376462, 1e-10);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        use std::f64::consts::LN_2;
+        let d = new!(0.0, 1.0);
+        let x = vec![-40.0, -38.0, -36.0, -30.0, -10.0, -5.0, 0.0, 3.0, 8.0];
+        let p = vec![
+            -804.60844201375378817, -726.5572160188201301, -652.50322759379839685,
+            -454.32124395634319711, -53.231285150512470578, -15.064998393988725736,
+            -LN_2, -0.0013508099647481937988, -6.2209605742717860585e-16,
+        ];
+        assert::close(&x.iter().map(|&x| d.ln_distribution(x)).collect::<Vec<_>>(), &p, 1e-12);
+        assert_eq!(d.distribution(-40.0).ln(), NEG_INFINITY);
+        assert_eq!(d.ln_distribution(NEG_INFINITY), NEG_INFINITY);
+    }
+
+    #[test]
+    fn ln_survival() {
+        use std::f64::consts::LN_2;
+        let d = new!(1.0, 2.0);
+        let x = vec![81.0, 77.0, 73.0, 61.0, 21.0, 11.0, 1.0, -5.0, -15.0];
+        let p = vec![
+            -804.60844201375378817, -726.5572160188201301, -652.50322759379839685,
+            -454.32124395634319711, -53.231285150512470578, -15.064998393988725736,
+            -LN_2, -0.0013508099647481937988, -6.2209605742717860585e-16,
+        ];
+        assert::close(&x.iter().map(|&x| d.ln_survival(x)).collect::<Vec<_>>(), &p, 1e-12);
+        assert_eq!((1.0 - d.distribution(81.0)).ln(), NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_likelihood() {
+        use math;
+
+        let d = new!(1.0, 2.0);
+        let x = vec![-4.0, 0.0, 1.0, 2.5, 4.0];
+        assert::close(d.log_likelihood(&x), x.iter().map(|&x| d.ln_density(x)).sum::<f64>(),
+                      1e-14);
+        assert_eq!(d.log_likelihood(&[]), 0.0);
+
+        let x = Independent(&new!(3.0, 1.5), &mut source::seeded(42)).take(1_000_000)
+                                                                    .collect::<Vec<_>>();
+        let e = math::sum(x.iter().map(|&x| d.ln_density(x)));
+        assert::close(d.log_likelihood(&x) / e, 1.0, 1e-10);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.0, 1.0).mean(), 0.0);
     }
 
+    #[test]
+    fn mean_residual_life() {
+        let d = new!(0.0, 1.0);
+        let x = vec![-1.0, 0.0, 1.0, 3.0, 10.0];
+        let p = vec![
+            1.2875999709391783612, 0.79788456080286535588, 0.52513527616098120909,
+            0.28309865493043650693, 0.098093233962511962844,
+        ];
+        assert::close(&x.iter().map(|&x| d.mean_residual_life(x)).collect::<Vec<_>>(), &p,
+                      1e-10);
+        assert::close(new!(1.0, 2.0).mean_residual_life(21.0), 2.0 * p[4], 1e-10);
+    }
+
     #[test]
     fn median() {
         assert_eq!(new!(0.0, 2.0).median(), 0.0);
@@ -439,11 +1866,248 @@ mod tests {
         assert_eq!(new!(2.0, 5.0).modes(), vec![2.0]);
     }
 
+    #[test]
+    fn moment() {
+        let d = new!(1.0, 2.0);
+        assert_eq!(d.moment(1), 1.0);
+        assert_eq!(d.moment(2), 5.0);
+        assert_eq!(d.moment(3), 13.0);
+        assert_eq!(d.moment(5), 281.0);
+        assert::close(d.moment(8) / 57233.0, 1.0, 1e-14);
+    }
+
+    #[test]
+    #[should_panic(expected = "sigma should be nonnegative and finite (got -2)")]
+    fn new() {
+        Gaussian::new(1.0, -2.0);
+    }
+
+    #[test]
+    fn owens_t() {
+        use distribution::gaussian::owens_t;
+        use std::f64::consts::PI;
+
+        let cases = vec![
+            ((0.0625, 0.25), 0.038911930234701366897),
+            ((6.5, 0.4375), 2.0005773048508315410e-11),
+            ((7.0, 0.96875), 6.3990627193898685308e-13),
+            ((4.78125, 0.0625), 1.0632974804687463806e-7),
+            ((2.0, 0.5), 0.0086250779855215071311),
+            ((1.0, 0.9999975), 0.066741808978228592292),
+            ((0.5, 3.0), 0.15108404307601841107),
+            ((3.0, 10.0), 6.7494901581504726333e-4),
+        ];
+        for &((h, a), t) in &cases {
+            assert::close(owens_t(h, a), t, 1e-16);
+            assert::close(owens_t(-h, a), t, 1e-16);
+            assert::close(owens_t(h, -a), -t, 1e-16);
+            assert::close(owens_t(h, a) / t, 1.0, 1e-12);
+        }
+
+        let d = StandardGaussian;
+        assert_eq!(owens_t(0.0, 0.5), 0.5f64.atan() / (2.0 * PI));
+        assert_eq!(owens_t(1.5, 0.0), 0.0);
+        assert_eq!(owens_t(INFINITY, 0.5), 0.0);
+        assert::close(owens_t(1.0, 1.0), d.distribution(1.0) * d.survival(1.0) / 2.0, 1e-17);
+        assert::close(owens_t(1.0, INFINITY), d.survival(1.0) / 2.0, 1e-17);
+        assert::close(owens_t(1.0, 1e300), d.survival(1.0) / 2.0, 1e-17);
+        assert::close(owens_t(4.0, 0.7), owens_t(4.0 + 1e-12, 0.7), 1e-15);
+        assert::close(owens_t(1.2, 1.0 - 1e-12), owens_t(1.2, 1.0 + 1e-12), 1e-12);
+        assert_eq!(owens_t(40.0, 0.5), 0.0);
+        assert!(owens_t(::std::f64::NAN, 0.5).is_nan());
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(new!(1.0, 2.0).precision(), 0.25);
+        assert::close(new!(0.0, 0.3).precision(), 1.0 / new!(0.0, 0.3).variance(), 1e-14);
+    }
+
+    #[test]
+    fn probability() {
+        let d = new!(0.0, 1.0);
+        assert::close(d.probability(-1.0, 1.0), 0.68268949213708589717, 1e-15);
+        assert_eq!(d.probability(1.0, -1.0), 0.0);
+        assert_eq!(d.distribution(11.0) - d.distribution(10.0), 0.0);
+        assert::close(d.probability(10.0, 11.0) / 7.6196619582030761984e-24, 1.0, 1e-13);
+        assert::close(d.probability(-11.0, -10.0) / 7.6196619582030761984e-24, 1.0, 1e-13);
+    }
+
+    #[test]
+    fn sample() {
+        let n = 10000000;
+        let x = StandardGaussian.samples(&mut source::seeded(42), n);
+
+        let sums = x.iter().fold([0.0; 4], |sums, &x| {
+            let y = x * x;
+            [sums[0] + x, sums[1] + y, sums[2] + x * y, sums[3] + y * y]
+        });
+        let moment = |k: usize| sums[k - 1] / n as f64;
+        assert!(moment(1).abs() < 5.0 / (n as f64).sqrt());
+        assert!((moment(2) - 1.0).abs() < 5.0 * (2.0 / n as f64).sqrt());
+        assert!(moment(3).abs() < 5.0 * (6.0 / n as f64).sqrt());
+        assert!((moment(4) - 3.0).abs() < 5.0 * (96.0 / n as f64).sqrt());
+
+        // The Kolmogorov–Smirnov statistic is evaluated at the edges of bins
+        // that are much narrower than its critical value.
+        let m = 1 << 20;
+        let mut counts = vec![0; m];
+        for &x in &x {
+            counts[((StandardGaussian.distribution(x) * m as f64) as usize).min(m - 1)] += 1;
+        }
+        let mut total = 0;
+        let statistic = counts.iter().enumerate().fold(0.0f64, |statistic, (i, &count)| {
+            total += count;
+            statistic.max((total as f64 / n as f64 - (i + 1) as f64 / m as f64).abs())
+        });
+        assert!(statistic < 1.63 / (n as f64).sqrt());
+    }
+
+    #[test]
+    fn sample_granularity() {
+        struct Counter(u64);
+
+        impl source::Source for Counter {
+            fn read_u64(&mut self) -> u64 {
+                self.0 += 1 << 12;
+                self.0
+            }
+        }
+
+        // The layer and the sign stay the same, and the positions within the
+        // layer are consecutive, which the samples should resolve.
+        let mut source = Counter((1 << 63) | 0x80 | 64);
+        let x = (0..1000).map(|_| super::sample(&mut source)).collect::<Vec<_>>();
+        for pair in x.windows(2) {
+            let step = pair[1] - pair[0];
+            assert!(step > 0.0 && step < 1e-15);
+        }
+    }
+
+    #[test]
+    fn sample_into() {
+        use source::Source;
+
+        let d = new!(1.0, 2.0);
+        let mut one = source::Xorshift128Plus::new([42, 69]);
+        let mut other = source::Xorshift128Plus::new([42, 69]);
+        let mut x = vec![0.0; 1000];
+        d.sample_into(&mut one, &mut x);
+        assert_eq!(x, (0..1000).map(|_| d.sample(&mut other)).collect::<Vec<_>>());
+        assert_eq!(one.read::<u64>(), other.read::<u64>());
+    }
+
+    #[test]
+    fn sample_ziggurat() {
+        use source::Source;
+        use super::{K, R, W, Y};
+
+        fn sample<S: Source>(source: &mut S) -> f64 {
+            loop {
+                let u = source.read::<u64>();
+                let (i, j) = ((u & 0x7f) as usize, u >> 12);
+                let x = j as f64 * W[i];
+                let x = if j < K[i] {
+                    x
+                } else if i == 127 {
+                    loop {
+                        let x = R - (-source.read::<f64>()).ln_1p() / R;
+                        let y = (-R * (x - 0.5 * R)).exp() * source.read::<f64>();
+                        if y < (-0.5 * x * x).exp() {
+                            break x;
+                        }
+                    }
+                } else if Y[i + 1] + (Y[i] - Y[i + 1]) * source.read::<f64>()
+                          < (-0.5 * x * x).exp() {
+                    x
+                } else {
+                    continue;
+                };
+                return if u & 0x80 != 0 { x } else { -x };
+            }
+        }
+
+        let mut one = source::seeded(42);
+        let mut other = source::seeded(42);
+        for _ in 0..1000000 {
+            assert_eq!(super::sample(&mut one), sample(&mut other));
+        }
+        assert_eq!(one.read::<u64>(), other.read::<u64>());
+    }
+
+    #[test]
+    fn single_precision() {
+        use std::mem;
+
+        assert_eq!(mem::size_of::<Gaussian<f32>>(), 8);
+
+        let (d, e) = (Gaussian::from_deviation(1.0f32, 2.0), new!(1.0, 2.0));
+        assert_eq!((d.mu(), d.sigma()), (1.0f32, 2.0f32));
+        assert_eq!(d.to_string(), "Gaussian(μ = 1, σ = 2)");
+        for i in -40..41 {
+            let x = i as f64 / 4.0;
+            assert::close(d.density(x), e.density(x), 1e-6);
+            assert::close(d.distribution(x), e.distribution(x), 1e-6);
+            assert::close(d.survival(x), e.survival(x), 1e-6);
+        }
+        for i in 1..100 {
+            let p = i as f64 / 100.0;
+            assert::close(d.inverse(p) as f64, e.inverse(p), 1e-6 * e.sigma());
+            assert::close(d.inverse_survival(p) as f64, e.inverse_survival(p), 1e-6 * e.sigma());
+        }
+        assert_eq!(d.inverse(0.0), ::std::f32::NEG_INFINITY);
+        assert_eq!(d.inverse(1.0), ::std::f32::INFINITY);
+
+        let x = d.samples(&mut source::seeded(42), 1000);
+        let y = e.samples(&mut source::seeded(42), 1000);
+        for (&x, &y) in x.iter().zip(&y) {
+            assert::close(x as f64, y, 1e-6 * y.abs().max(1.0));
+        }
+
+        assert_eq!(Gaussian::try_from_deviation(0.0f32, -1.0).err(),
+                   Some(ParameterError::SigmaNotNonnegative { value: -1.0 }));
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(0.0, 2.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn standard() {
+        const STANDARD: Gaussian = Gaussian::standard();
+        assert_eq!(STANDARD, Gaussian::default());
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-5.0, 1.0, 7.0, 17.0, 41.0];
+        let p = vec![
+            0.99865010196836990547, 0.5, 1.3498980316300945267e-03, 6.2209605742717841235e-16,
+            2.7536241186062336951e-89,
+        ];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(), &p, 1e-15);
+        assert::close(d.survival(17.0) / p[3], 1.0, 1e-13);
+        assert::close(d.survival(41.0) / p[4], 1.0, 1e-13);
+        assert_eq!(1.0 - d.distribution(41.0), 0.0);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Gaussian::try_new(1.0, 2.0).is_ok());
+        assert_eq!(Gaussian::try_new(INFINITY, 1.0).err(),
+                   Some(ParameterError::MuNotFinite { value: INFINITY }));
+        assert!(Gaussian::try_new(1.0, 0.0).is_ok());
+        assert_eq!(Gaussian::try_new(1.0, -2.0).err(),
+                   Some(ParameterError::SigmaNotNonnegative { value: -2.0 }));
+        assert_eq!(Gaussian::try_new(1.0, INFINITY).err(),
+                   Some(ParameterError::SigmaNotNonnegative { value: INFINITY }));
+        assert!(Gaussian::try_new(NAN, 1.0).is_err());
+        assert!(Gaussian::try_new(1.0, NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.0, 2.0).variance(), 4.0);
@@ -453,4 +2117,108 @@ mod tests {
     fn deviation() {
         assert_eq!(new!(0.0, 2.0).deviation(), 2.0);
     }
+
+    mod standard_gaussian {
+        use assert;
+        use prelude::*;
+
+        #[test]
+        fn affine() {
+            assert_eq!(StandardGaussian * 2.0 + 1.0, Gaussian::new(1.0, 2.0));
+            assert_eq!(-StandardGaussian, Gaussian::new(0.0, 1.0));
+        }
+
+        #[test]
+        fn density() {
+            let d = Gaussian::new(0.0, 1.0);
+            for i in -800..801 {
+                let x = i as f64 / 20.0;
+                assert_eq!(StandardGaussian.density(x), d.density(x));
+                assert_eq!(StandardGaussian.ln_density(x), d.ln_density(x));
+            }
+        }
+
+        #[test]
+        fn display() {
+            assert_eq!(StandardGaussian.to_string(), "StandardGaussian");
+        }
+
+        #[test]
+        fn distribution() {
+            let d = Gaussian::new(0.0, 1.0);
+            for i in -800..801 {
+                let x = i as f64 / 20.0;
+                assert_eq!(StandardGaussian.distribution(x), d.distribution(x));
+                assert_eq!(StandardGaussian.survival(x), d.survival(x));
+                assert_eq!(StandardGaussian.ln_distribution(x), d.ln_distribution(x));
+                assert_eq!(StandardGaussian.ln_survival(x), d.ln_survival(x));
+            }
+        }
+
+        #[test]
+        fn entropy() {
+            assert_eq!(StandardGaussian.entropy(), Gaussian::new(0.0, 1.0).entropy());
+        }
+
+        #[test]
+        fn from() {
+            assert_eq!(Gaussian::from(StandardGaussian), Gaussian::new(0.0, 1.0));
+        }
+
+        #[test]
+        fn inverse() {
+            let d = Gaussian::new(0.0, 1.0);
+            for i in 0..1001 {
+                let p = i as f64 / 1000.0;
+                assert_eq!(StandardGaussian.inverse(p), d.inverse(p));
+                assert_eq!(StandardGaussian.inverse_survival(p), d.inverse_survival(p));
+                assert_eq!(StandardGaussian.inverse_ln(p.ln()), d.inverse_ln(p.ln()));
+            }
+        }
+
+        #[test]
+        fn moment() {
+            let d = Gaussian::new(0.0, 1.0);
+            for k in 0..9 {
+                assert_eq!(StandardGaussian.moment(k), d.moment(k));
+                assert_eq!(StandardGaussian.central_moment(k), d.central_moment(k));
+            }
+        }
+
+        #[test]
+        fn moments() {
+            assert_eq!((StandardGaussian.mean(), StandardGaussian.variance()), (0.0, 1.0));
+            assert_eq!((StandardGaussian.skewness(), StandardGaussian.kurtosis()), (0.0, 0.0));
+            assert_eq!(StandardGaussian.median(), 0.0);
+            assert_eq!(StandardGaussian.modes(), vec![0.0]);
+        }
+
+        #[test]
+        fn sample() {
+            let mut one = source::Xorshift128Plus::new([42, 69]);
+            let mut other = source::Xorshift128Plus::new([42, 69]);
+            let d = Gaussian::new(0.0, 1.0);
+            for _ in 0..1000 {
+                assert_eq!(StandardGaussian.sample(&mut one), d.sample(&mut other));
+            }
+            let x = Independent(&StandardGaussian, &mut one).take(100000).collect::<Vec<_>>();
+            assert::close(x.iter().sum::<f64>() / 100000.0, 0.0, 0.02);
+        }
+    }
+
+    #[test]
+    fn ziggurat() {
+        use distribution::ziggurat;
+        use special::Error;
+        use std::f64::consts::{PI, SQRT_2};
+        use super::{R, ZIGGURAT};
+
+        let density = |x: f64| (-0.5 * x * x).exp();
+        let inverse = |y: f64| (-2.0 * y.ln()).sqrt();
+        let tail = |x: f64| (PI / 2.0).sqrt() * Error::erfc(x / SQRT_2);
+
+        assert_eq!(ziggurat::radius(128, density, inverse, tail, 3.0, 4.0), R);
+        let (k, w, y) = ziggurat::generate(128, R, density, inverse, tail);
+        ziggurat::assert_tables(&ZIGGURAT, &k, &w, &y);
+    }
 }
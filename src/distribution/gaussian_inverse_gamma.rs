@@ -0,0 +1,197 @@
+use std::fmt;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use distribution::{self, StudentT};
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Float;
+
+/// A Gaussian–inverse-gamma distribution.
+///
+/// The distribution is the joint distribution of a pair `(μ, σ²)` where `σ²`
+/// follows the inverse-gamma distribution with shape `alpha` and scale
+/// `beta`, and `μ` given `σ²` follows the Gaussian distribution with mean
+/// `mu` and variance `σ² / lambda`. It is the conjugate prior for the mean
+/// and variance of Gaussian data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaussianInverseGamma {
+    mu: f64,
+    lambda: f64,
+    alpha: f64,
+    beta: f64,
+}
+
+impl GaussianInverseGamma {
+    /// Create a Gaussian–inverse-gamma distribution with location `mu`,
+    /// precision multiplier `lambda`, shape `alpha`, and scale `beta`.
+    ///
+    /// It should hold that `lambda > 0`, `alpha > 0`, and `beta > 0`; the
+    /// function panics otherwise.
+    #[inline]
+    pub fn new(mu: f64, lambda: f64, alpha: f64, beta: f64) -> Self {
+        unwrap!(GaussianInverseGamma::try_new(mu, lambda, alpha, beta))
+    }
+
+    /// Create a Gaussian–inverse-gamma distribution with location `mu`,
+    /// precision multiplier `lambda`, shape `alpha`, and scale `beta` or
+    /// return an error if the parameters are invalid.
+    pub fn try_new(mu: f64, lambda: f64, alpha: f64, beta: f64)
+                   -> Result<Self, ParameterError> {
+        ensure!(mu.is_finite(), ParameterError::MuNotFinite { value: mu });
+        ensure!(lambda > 0.0 && lambda.is_finite(),
+                ParameterError::PrecisionNotPositive { value: lambda });
+        ensure!(alpha > 0.0 && alpha.is_finite(),
+                ParameterError::AlphaNotPositive { value: alpha });
+        ensure!(beta > 0.0 && beta.is_finite(), ParameterError::BetaNotPositive { value: beta });
+        Ok(GaussianInverseGamma { mu: mu, lambda: lambda, alpha: alpha, beta: beta })
+    }
+
+    /// Return the location parameter.
+    #[inline(always)]
+    pub fn mu(&self) -> f64 { self.mu }
+
+    /// Return the precision multiplier.
+    #[inline(always)]
+    pub fn lambda(&self) -> f64 { self.lambda }
+
+    /// Return the shape parameter.
+    #[inline(always)]
+    pub fn alpha(&self) -> f64 { self.alpha }
+
+    /// Return the scale parameter.
+    #[inline(always)]
+    pub fn beta(&self) -> f64 { self.beta }
+
+    /// Compute the marginal distribution of `μ`.
+    ///
+    /// The marginal is a Student’s t distribution with `2 alpha` degrees of
+    /// freedom, location `mu`, and scale `√(beta / (alpha lambda))`.
+    #[inline]
+    pub fn mean_marginal(&self) -> StudentT {
+        StudentT::new(2.0 * self.alpha, self.mu, (self.beta / (self.alpha * self.lambda)).sqrt())
+    }
+}
+
+impl fmt::Display for GaussianInverseGamma {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "GaussianInverseGamma(μ = {}, λ = {}, α = {}, β = {})",
+               self.mu, self.lambda, self.alpha, self.beta)
+    }
+}
+
+impl distribution::Multivariate for GaussianInverseGamma {
+    type Value = (f64, f64);
+
+    #[inline]
+    fn dimension(&self) -> usize {
+        2
+    }
+}
+
+impl distribution::MultivariateContinuous for GaussianInverseGamma {
+    fn ln_density(&self, &(mean, variance): &(f64, f64)) -> f64 {
+        use special::Gamma;
+        use std::f64::consts::PI;
+        if !(variance > 0.0) {
+            return ::std::f64::NEG_INFINITY;
+        }
+        let (alpha, beta, lambda) = (self.alpha, self.beta, self.lambda);
+        let deviation = mean - self.mu;
+        0.5 * (lambda / (2.0 * PI)).ln() + alpha * beta.ln() - Gamma::ln_gamma(alpha).0 -
+            (alpha + 1.5) * variance.ln() -
+            (2.0 * beta + lambda * deviation * deviation) / (2.0 * variance)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::MultivariateMean for GaussianInverseGamma {
+    /// Compute the vector of expected values.
+    ///
+    /// The expected value of `σ²` is infinite for `alpha ≤ 1`.
+    #[inline]
+    fn mean_vector(&self) -> Vec<f64> {
+        let variance = if self.alpha > 1.0 {
+            self.beta / (self.alpha - 1.0)
+        } else {
+            ::std::f64::INFINITY
+        };
+        vec![self.mu, variance]
+    }
+}
+
+impl distribution::MultivariateSample for GaussianInverseGamma {
+    /// Draw a sample.
+    ///
+    /// The variance is drawn first, and the mean is drawn given the variance.
+    fn sample<S>(&self, source: &mut S) -> (f64, f64) where S: Source {
+        use distribution::{gamma, gaussian};
+        let variance = self.beta / gamma::sample(self.alpha, source);
+        let mean = self.mu + (variance / self.lambda).sqrt() * gaussian::sample(source);
+        (mean, variance)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::MultivariateVariance for GaussianInverseGamma {
+    /// Compute the covariance matrix.
+    ///
+    /// The components are uncorrelated. The variance of `μ` is infinite for
+    /// `alpha ≤ 1`, and that of `σ²` is infinite for `alpha ≤ 2`.
+    fn covariance(&self) -> Vec<f64> {
+        use std::f64::INFINITY;
+        let (alpha, beta) = (self.alpha, self.beta);
+        let mean = if alpha > 1.0 { beta / ((alpha - 1.0) * self.lambda) } else { INFINITY };
+        let variance = if alpha > 2.0 {
+            beta * beta / ((alpha - 1.0) * (alpha - 1.0) * (alpha - 2.0))
+        } else {
+            INFINITY
+        };
+        vec![mean, 0.0, 0.0, variance]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use tests::ks_one_sample;
+
+    #[test]
+    fn display() {
+        assert_eq!(GaussianInverseGamma::new(1.0, 2.0, 3.0, 4.0).to_string(),
+                   "GaussianInverseGamma(μ = 1, λ = 2, α = 3, β = 4)");
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = GaussianInverseGamma::new(1.0, 2.0, 3.0, 4.0);
+        assert::close(d.ln_density(&(0.5, 1.5)), -1.7645553599450465922, 1e-14);
+        assert_eq!(d.ln_density(&(0.5, 0.0)), ::std::f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn moments() {
+        let d = GaussianInverseGamma::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(d.mean_vector(), vec![1.0, 2.0]);
+        assert_eq!(d.covariance(), vec![1.0, 0.0, 0.0, 4.0]);
+        assert_eq!(d.mean_marginal().variance(), 1.0);
+    }
+
+    #[test]
+    fn sample() {
+        let d = GaussianInverseGamma::new(1.0, 2.0, 3.0, 4.0);
+        let mut source = source::seeded(42);
+        let x = (0..100000).map(|_| d.sample(&mut source).0).collect::<Vec<_>>();
+        assert!(ks_one_sample(&x, &d.mean_marginal()).p_value > 0.01);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(GaussianInverseGamma::try_new(1.0, 0.0, 3.0, 4.0).err(),
+                   Some(ParameterError::PrecisionNotPositive { value: 0.0 }));
+        assert_eq!(GaussianInverseGamma::try_new(1.0, 2.0, 3.0, -4.0).err(),
+                   Some(ParameterError::BetaNotPositive { value: -4.0 }));
+    }
+}
@@ -0,0 +1,431 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{self, ModeList};
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A histogram distribution.
+///
+/// The density is piecewise constant: each bin receives the probability mass
+/// proportional to its count, which is spread uniformly over the bin. The
+/// moments are those of this piecewise-uniform distribution, that is, they
+/// account for the spread within the bins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    edges: Vec<f64>,
+    counts: Vec<u64>,
+    p: Vec<f64>,
+    cumsum: Vec<f64>,
+}
+
+impl Histogram {
+    /// Create a histogram distribution with bin edges `edges` and bin counts
+    /// `counts`.
+    ///
+    /// The `i`th bin spans the interval `[edges[i], edges[i + 1]]`.
+    ///
+    /// It should hold that `edges` are finite and strictly increasing,
+    /// `edges.len() == counts.len() + 1`, and `sum(counts) > 0`; the function
+    /// panics otherwise.
+    #[inline]
+    pub fn new(edges: &[f64], counts: &[u64]) -> Self {
+        unwrap!(Histogram::try_new(edges, counts))
+    }
+
+    /// Create a histogram distribution with bin edges `edges` and bin counts
+    /// `counts` or return an error if the parameters are invalid.
+    pub fn try_new(edges: &[f64], counts: &[u64]) -> Result<Self, ParameterError> {
+        ensure!(edges.len() == counts.len() + 1, ParameterError::EdgesMismatch {
+            edges: edges.len(), counts: counts.len(),
+        });
+        ensure!(edges.iter().all(|x| x.is_finite()) && edges.windows(2).all(|w| w[0] < w[1]),
+                ParameterError::EdgesNotOrdered);
+        let total = counts.iter().fold(0, |sum, &count| sum + count);
+        ensure!(total > 0, ParameterError::CountsZero);
+        let p = counts.iter().map(|&count| count as f64 / total as f64).collect::<Vec<_>>();
+        let mut cumsum = Vec::with_capacity(edges.len());
+        let mut sum = 0;
+        cumsum.push(0.0);
+        for &count in counts {
+            sum += count;
+            cumsum.push(sum as f64 / total as f64);
+        }
+        Ok(Histogram { edges: edges.to_vec(), counts: counts.to_vec(), p: p, cumsum: cumsum })
+    }
+
+    /// Create a histogram distribution from observations `data`.
+    ///
+    /// The bins are of equal width and span the range of the data. If `bins`
+    /// is `None`, the bin width is chosen according to the Freedman–Diaconis
+    /// rule; when the interquartile range vanishes, the number of bins is
+    /// chosen according to Sturges’ rule instead. If all observations are
+    /// equal, a single bin of unit width centered at the value is used.
+    ///
+    /// It should hold that `data` is not empty and contains only finite values
+    /// and that `bins` is positive if given; the function panics otherwise.
+    #[inline]
+    pub fn from_samples(data: &[f64], bins: Option<usize>) -> Self {
+        unwrap!(Histogram::try_from_samples(data, bins))
+    }
+
+    /// Create a histogram distribution from observations `data` or return an
+    /// error if the parameters are invalid.
+    ///
+    /// The bins are chosen as in `from_samples`.
+    pub fn try_from_samples(data: &[f64], bins: Option<usize>)
+                            -> Result<Self, ParameterError> {
+        use distribution::{Empirical, Inverse};
+
+        let empirical = Empirical::try_new(data)?;
+        ensure!(bins != Some(0), ParameterError::BinsZero);
+
+        let (min, max) = (empirical.inverse(0.0), empirical.inverse(1.0));
+        if min == max {
+            return Histogram::try_new(&[min - 0.5, min + 0.5], &[data.len() as u64]);
+        }
+        let bins = bins.unwrap_or_else(|| {
+            let n = data.len() as f64;
+            let iqr = empirical.inverse(0.75) - empirical.inverse(0.25);
+            if iqr > 0.0 {
+                ((max - min) / (2.0 * iqr / n.cbrt())).ceil() as usize
+            } else {
+                n.log2().ceil() as usize + 1
+            }
+        });
+        let width = (max - min) / bins as f64;
+        let mut edges = (0..bins).map(|i| min + i as f64 * width).collect::<Vec<_>>();
+        edges.push(max);
+        let mut counts = vec![0; bins];
+        for &x in data {
+            let i = ((x - min) / width) as usize;
+            counts[if i < bins { i } else { bins - 1 }] += 1;
+        }
+        Histogram::try_new(&edges, &counts)
+    }
+
+    /// Return the bin edges.
+    #[inline(always)]
+    pub fn edges(&self) -> &[f64] { &self.edges }
+
+    /// Return the bin counts.
+    #[inline(always)]
+    pub fn counts(&self) -> &[u64] { &self.counts }
+
+    // Find the bin containing `x`, which should lie within the support.
+    fn bin(&self, x: f64) -> usize {
+        let (mut lower, mut upper) = (0, self.counts.len());
+        while upper - lower > 1 {
+            let middle = (lower + upper) / 2;
+            if self.edges[middle] <= x {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        lower
+    }
+
+    // Compute the expectation of `(X - center)^k`.
+    fn about(&self, k: u32, center: f64) -> f64 {
+        use distribution::translate;
+        (0..self.counts.len()).filter(|&i| self.p[i] > 0.0).fold(0.0, |sum, i| {
+            let half = self.width(i) / 2.0;
+            let moment = |j: u32| {
+                if j % 2 == 1 { 0.0 } else { half.powi(j as i32) / (j + 1) as f64 }
+            };
+            sum + self.p[i] * translate(moment, k, self.middle(i) - center)
+        })
+    }
+
+    #[inline(always)]
+    fn width(&self, i: usize) -> f64 {
+        self.edges[i + 1] - self.edges[i]
+    }
+
+    #[inline(always)]
+    fn middle(&self, i: usize) -> f64 {
+        (self.edges[i] + self.edges[i + 1]) / 2.0
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Histogram(bins = {}, a = {}, b = {})", self.counts.len(), self.edges[0],
+               self.edges[self.counts.len()])
+    }
+}
+
+impl distribution::Continuous for Histogram {
+    fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        if x < self.edges[0] || x > self.edges[self.counts.len()] {
+            return 0.0;
+        }
+        let i = self.bin(x);
+        self.p[i] / self.width(i)
+    }
+
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 {
+        use distribution::Mean;
+        self.about(k, self.mean())
+    }
+
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        self.about(k, 0.0)
+    }
+}
+
+impl distribution::Distribution for Histogram {
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
+        if x <= self.edges[0] {
+            return 0.0;
+        }
+        if x >= self.edges[self.counts.len()] {
+            return 1.0;
+        }
+        let i = self.bin(x);
+        self.cumsum[i] + self.p[i] * (x - self.edges[i]) / self.width(i)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.edges[0], self.edges[self.counts.len()])
+    }
+}
+
+impl distribution::Entropy for Histogram {
+    fn entropy(&self) -> f64 {
+        (0..self.counts.len()).filter(|&i| self.p[i] > 0.0).fold(0.0, |sum, i| {
+            sum - self.p[i] * (self.p[i] / self.width(i)).ln()
+        })
+    }
+}
+
+impl distribution::Inverse for Histogram {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The bin is located by binary search, and the function is inverted
+    /// exactly within the bin.
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        let bins = self.counts.len();
+        let (mut lower, mut upper) = (0, bins - 1);
+        while lower < upper {
+            let middle = (lower + upper) / 2;
+            if self.cumsum[middle + 1] < p {
+                lower = middle + 1;
+            } else {
+                upper = middle;
+            }
+        }
+        let mut i = lower;
+        while self.p[i] == 0.0 {
+            i += 1;
+        }
+        let x = self.edges[i] + (p - self.cumsum[i]).max(0.0) / self.p[i] * self.width(i);
+        if x < self.edges[i + 1] { x } else { self.edges[i + 1] }
+    }
+}
+
+impl distribution::Mean for Histogram {
+    fn mean(&self) -> f64 {
+        (0..self.counts.len()).fold(0.0, |sum, i| sum + self.p[i] * self.middle(i))
+    }
+}
+
+impl distribution::Modes for Histogram {
+    /// Compute the modes.
+    ///
+    /// The modes are the midpoints of the bins with the largest density.
+    fn modes(&self) -> ModeList<f64> {
+        use distribution::Continuous;
+        let mut modes = ModeList::new();
+        let mut max = 0.0;
+        for i in 0..self.counts.len() {
+            let density = self.density(self.middle(i));
+            if density == max {
+                modes.push(self.middle(i));
+            }
+            if density > max {
+                max = density;
+                modes = ModeList::one(self.middle(i));
+            }
+        }
+        modes
+    }
+}
+
+impl distribution::Sample for Histogram {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        use distribution::Inverse;
+        self.inverse(source.read::<f64>())
+    }
+}
+
+impl distribution::Variance for Histogram {
+    fn variance(&self) -> f64 {
+        use distribution::Mean;
+        let mean = self.mean();
+        (0..self.counts.len()).fold(0.0, |sum, i| {
+            let width = self.width(i);
+            sum + self.p[i] * ((self.middle(i) - mean).powi(2) + width * width / 12.0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($edges:expr, $counts:expr) => (Histogram::new(&$edges, &$counts));
+    );
+
+    #[test]
+    fn central_moment() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        assert_eq!(d.central_moment(0), 1.0);
+        assert::close(d.central_moment(1), 0.0, 1e-15);
+        assert::close(d.central_moment(2), d.variance(), 1e-14);
+        assert::close(d.central_moment(3), -2.53125, 1e-14);
+    }
+
+    #[test]
+    fn density() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0, 3.0, 3.5, 4.0, 5.0];
+        let p = vec![0.0, 0.25, 0.25, 0.0, 0.0, 0.75, 0.75, 0.75, 0.0];
+        assert_eq!(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Histogram::new(&[0.0, 1.0, 3.0], &[1, 2]).to_string(),
+                   "Histogram(bins = 2, a = 0, b = 3)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0, 3.0, 3.5, 4.0, 5.0];
+        let p = vec![0.0, 0.0, 0.125, 0.25, 0.25, 0.25, 0.625, 1.0, 1.0];
+        assert_eq!(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p);
+
+        let d = new!([1.0, 3.0], [5]);
+        assert_eq!(d.distribution(2.5), 0.75);
+    }
+
+    #[test]
+    fn entropy() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        assert::close(d.entropy(), -0.25 * 0.25f64.ln() - 0.75 * 0.75f64.ln(), 1e-15);
+        assert_eq!(new!([1.0, 3.0], [5]).entropy(), Uniform::new(1.0, 3.0).entropy());
+    }
+
+    #[test]
+    fn from_samples() {
+        let d = Histogram::from_samples(&[0.0, 1.0, 1.5, 3.0, 4.0], Some(4));
+        assert_eq!(d.edges(), &[0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(d.counts(), &[1, 2, 0, 2]);
+
+        let d = Histogram::from_samples(&[2.0, 2.0, 2.0], None);
+        assert_eq!((d.edges(), d.counts()), (&[1.5, 2.5][..], &[3][..]));
+
+        let d = Histogram::from_samples(&[0.0, 0.0, 0.0, 0.0, 1.0, 2.0], None);
+        assert_eq!(d.counts(), &[4, 1, 1]);
+
+        let d = Histogram::from_samples(&[0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0], None);
+        assert_eq!(d.counts(), &[5, 0, 1, 1]);
+
+        let data = Independent(&Gaussian::new(1.0, 2.0), &mut source::seeded(42)).take(10000)
+                                                                            .collect::<Vec<_>>();
+        let d = Histogram::from_samples(&data, None);
+        let e = Gaussian::new(1.0, 2.0);
+        for &x in &[-3.0, -1.0, 0.0, 1.0, 2.0, 3.0, 5.0] {
+            assert!((d.distribution(x) - e.distribution(x)).abs() < 0.02);
+        }
+        let x = Independent(&d, &mut source::seeded(42)).take(10000).collect::<Vec<_>>();
+        let d = Empirical::new(&x);
+        for &x in &[-3.0, -1.0, 0.0, 1.0, 2.0, 3.0, 5.0] {
+            assert!((d.distribution(x) - e.distribution(x)).abs() < 0.03);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        let p = vec![0.0, 0.125, 0.25, 0.625, 1.0];
+        let x = vec![0.0, 0.5, 1.0, 3.5, 4.0];
+        assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
+
+        let d = new!([0.0, 1.0, 2.0], [0, 3]);
+        assert_eq!((d.inverse(0.0), d.inverse(0.5)), (1.0, 1.5));
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]).mean(), 2.75);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]).modes(), vec![3.5]);
+        assert_eq!(new!([0.0, 1.0, 3.0, 4.0], [2, 4, 2]).modes(), vec![0.5, 2.0, 3.5]);
+    }
+
+    #[test]
+    fn moment() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        assert_eq!(d.moment(0), 1.0);
+        assert_eq!(d.moment(1), d.mean());
+        assert::close(d.moment(2), 28.0 / 3.0, 1e-14);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        for x in Independent(&d, &mut source::seeded(42)).take(100) {
+            assert!(0.0 <= x && x <= 1.0 || 3.0 <= x && x <= 4.0);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Histogram::try_new(&[0.0, 1.0, 3.0], &[1, 2]).is_ok());
+        assert_eq!(Histogram::try_new(&[0.0, 1.0], &[1, 2]).err(),
+                   Some(ParameterError::EdgesMismatch { edges: 2, counts: 2 }));
+        assert_eq!(Histogram::try_new(&[0.0], &[]).err(), Some(ParameterError::CountsZero));
+        assert_eq!(Histogram::try_new(&[0.0, 3.0, 1.0], &[1, 2]).err(),
+                   Some(ParameterError::EdgesNotOrdered));
+        assert_eq!(Histogram::try_new(&[0.0, 1.0, INFINITY], &[1, 2]).err(),
+                   Some(ParameterError::EdgesNotOrdered));
+        assert_eq!(Histogram::try_new(&[0.0, NAN, 3.0], &[1, 2]).err(),
+                   Some(ParameterError::EdgesNotOrdered));
+        assert_eq!(Histogram::try_new(&[0.0, 1.0, 3.0], &[0, 0]).err(),
+                   Some(ParameterError::CountsZero));
+        assert_eq!(Histogram::try_from_samples(&[], None).err(),
+                   Some(ParameterError::SamplesEmpty));
+        assert_eq!(Histogram::try_from_samples(&[1.0, 2.0], Some(0)).err(),
+                   Some(ParameterError::BinsZero));
+        assert!(Histogram::try_from_samples(&[1.0, NAN], None).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        let d = new!([0.0, 1.0, 3.0, 4.0], [2, 0, 6]);
+        assert::close(d.variance(), 0.25 * 2.25 * 2.25 + 0.75 * 0.75 * 0.75 + 1.0 / 12.0, 1e-15);
+        assert_eq!(new!([1.0, 3.0], [5]).variance(), Uniform::new(1.0, 3.0).variance());
+    }
+}
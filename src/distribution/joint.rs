@@ -0,0 +1,245 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+
+use distribution::{self, Empirical};
+use source::Source;
+
+/// A joint distribution of independent components.
+///
+/// The components are given either as a tuple of up to eight distributions,
+/// in which case the outcomes are tuples, or as a vector of distributions of
+/// the same type, in which case the outcomes are vectors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Joint<T> {
+    components: T,
+}
+
+impl<T> Joint<T> {
+    /// Create a joint distribution with independent components `components`.
+    #[inline]
+    pub fn new(components: T) -> Self {
+        Joint { components: components }
+    }
+
+    /// Return the components.
+    #[inline(always)]
+    pub fn components(&self) -> &T { &self.components }
+
+    /// Draw `n` samples, apply `f` to each of them, and return the empirical
+    /// distribution of the results.
+    ///
+    /// It should hold that `n > 0`.
+    pub fn map<F, S>(&self, f: F, n: usize, source: &mut S) -> Empirical
+        where Self: distribution::MultivariateSample,
+              F: Fn(<Self as distribution::Multivariate>::Value) -> f64,
+              S: Source
+    {
+        use distribution::MultivariateSample;
+        should!(n > 0);
+        Empirical::new(&(0..n).map(|_| f(self.sample(source))).collect::<Vec<_>>())
+    }
+}
+
+macro_rules! implement(
+    ($($kind:ident: $index:tt),+) => (
+        impl<$($kind),+> fmt::Display for Joint<($($kind,)+)> where $($kind: fmt::Display),+ {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                let components = vec![$(self.components.$index.to_string()),+];
+                write!(formatter, "Joint({})", components.join(", "))
+            }
+        }
+
+        impl<$($kind),+> distribution::Multivariate for Joint<($($kind,)+)>
+            where $($kind: distribution::Distribution),+
+        {
+            type Value = ($($kind::Value,)+);
+
+            #[inline]
+            fn dimension(&self) -> usize {
+                [$($index),+].len()
+            }
+        }
+
+        impl<$($kind),+> distribution::MultivariateContinuous for Joint<($($kind,)+)>
+            where $($kind: distribution::Continuous<Value=f64>),+
+        {
+            #[inline]
+            fn ln_density(&self, x: &Self::Value) -> f64 {
+                0.0 $(+ self.components.$index.ln_density(x.$index))+
+            }
+        }
+
+        impl<$($kind),+> distribution::MultivariateMean for Joint<($($kind,)+)>
+            where $($kind: distribution::Mean),+
+        {
+            #[inline]
+            fn mean_vector(&self) -> Vec<f64> {
+                vec![$(self.components.$index.mean()),+]
+            }
+        }
+
+        impl<$($kind),+> distribution::MultivariateSample for Joint<($($kind,)+)>
+            where $($kind: distribution::Sample),+
+        {
+            #[inline]
+            fn sample<S>(&self, source: &mut S) -> Self::Value where S: Source {
+                ($(self.components.$index.sample(source),)+)
+            }
+        }
+
+        impl<$($kind),+> distribution::MultivariateVariance for Joint<($($kind,)+)>
+            where $($kind: distribution::Variance),+
+        {
+            #[inline]
+            fn covariance(&self) -> Vec<f64> {
+                diagonal(&[$(self.components.$index.variance()),+])
+            }
+        }
+    );
+);
+
+implement!(A: 0);
+implement!(A: 0, B: 1);
+implement!(A: 0, B: 1, C: 2);
+implement!(A: 0, B: 1, C: 2, D: 3);
+implement!(A: 0, B: 1, C: 2, D: 3, E: 4);
+implement!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+implement!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+implement!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+impl<T> fmt::Display for Joint<Vec<T>> where T: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let components = self.components.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        write!(formatter, "Joint({})", components.join(", "))
+    }
+}
+
+impl<T> distribution::Multivariate for Joint<Vec<T>> where T: distribution::Distribution {
+    type Value = Vec<T::Value>;
+
+    #[inline]
+    fn dimension(&self) -> usize {
+        self.components.len()
+    }
+}
+
+impl<T> distribution::MultivariateContinuous for Joint<Vec<T>>
+    where T: distribution::Continuous<Value=f64>
+{
+    fn ln_density(&self, x: &Vec<f64>) -> f64 {
+        should!(x.len() == self.components.len());
+        self.components.iter().zip(x).fold(0.0, |sum, (d, &x)| sum + d.ln_density(x))
+    }
+}
+
+impl<T> distribution::MultivariateMean for Joint<Vec<T>> where T: distribution::Mean {
+    #[inline]
+    fn mean_vector(&self) -> Vec<f64> {
+        self.components.iter().map(|d| d.mean()).collect()
+    }
+}
+
+impl<T> distribution::MultivariateSample for Joint<Vec<T>> where T: distribution::Sample {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> Vec<T::Value> where S: Source {
+        self.components.iter().map(|d| d.sample(source)).collect()
+    }
+}
+
+impl<T> distribution::MultivariateVariance for Joint<Vec<T>> where T: distribution::Variance {
+    #[inline]
+    fn covariance(&self) -> Vec<f64> {
+        diagonal(&self.components.iter().map(|d| d.variance()).collect::<Vec<_>>())
+    }
+}
+
+fn diagonal(values: &[f64]) -> Vec<f64> {
+    let n = values.len();
+    let mut matrix = vec![0.0; n * n];
+    for (i, &value) in values.iter().enumerate() {
+        matrix[i * n + i] = value;
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        () => (Joint::new((Uniform::new(0.0, 1.0), Gaussian::new(1.0, 2.0),
+                           Exponential::new(2.0))));
+    );
+
+    #[test]
+    fn covariance() {
+        assert_eq!(new!().covariance(),
+                   vec![1.0 / 12.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.25]);
+        let d = Joint::new(vec![Gaussian::new(0.0, 1.0), Gaussian::new(0.0, 3.0)]);
+        assert_eq!(d.covariance(), vec![1.0, 0.0, 0.0, 9.0]);
+    }
+
+    #[test]
+    fn dimension() {
+        assert_eq!(new!().dimension(), 3);
+        assert_eq!(Joint::new((Uniform::new(0.0, 1.0),)).dimension(), 1);
+        assert_eq!(Joint::new(vec![Uniform::new(0.0, 1.0); 5]).dimension(), 5);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(new!().to_string(),
+                   "Joint(Uniform(a = 0, b = 1), Gaussian(μ = 1, σ = 2), Exponential(λ = 2))");
+        assert_eq!(Joint::new(vec![Poisson::new(1.0), Poisson::new(2.5)]).to_string(),
+                   "Joint(Poisson(λ = 1), Poisson(λ = 2.5))");
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!();
+        let x = (0.5, 2.0, 1.0);
+        let p = Uniform::new(0.0, 1.0).density(0.5) * Gaussian::new(1.0, 2.0).density(2.0) *
+                Exponential::new(2.0).density(1.0);
+        assert::close(d.ln_density(&x), p.ln(), 1e-14);
+        assert_eq!(d.ln_density(&(2.0, 2.0, 1.0)), -::std::f64::INFINITY);
+
+        let d = Joint::new(vec![Gaussian::new(0.0, 1.0), Gaussian::new(0.0, 3.0)]);
+        let p = Gaussian::new(0.0, 1.0).density(1.0) * Gaussian::new(0.0, 3.0).density(-1.0);
+        assert::close(d.ln_density(&vec![1.0, -1.0]), p.ln(), 1e-14);
+    }
+
+    #[test]
+    fn map() {
+        let d = new!();
+        let e = d.map(|(x, y, z)| x + y + z, 10000, &mut source::seeded(42));
+        assert!((e.mean() - 2.0).abs() < 0.05);
+        assert!((e.variance() - (1.0 / 12.0 + 4.0 + 0.25)).abs() < 0.15);
+    }
+
+    #[test]
+    fn mean_vector() {
+        assert_eq!(new!().mean_vector(), vec![0.5, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!();
+        let mut source = source::seeded(42);
+        let samples = (0..10000).map(|_| d.sample(&mut source)).collect::<Vec<_>>();
+        let x = Empirical::new(&samples.iter().map(|x| x.0).collect::<Vec<_>>());
+        let y = Empirical::new(&samples.iter().map(|x| x.1).collect::<Vec<_>>());
+        let z = Empirical::new(&samples.iter().map(|x| x.2).collect::<Vec<_>>());
+        assert!((x.mean() - 0.5).abs() < 0.01 && (x.variance() - 1.0 / 12.0).abs() < 0.01);
+        assert!((y.mean() - 1.0).abs() < 0.05 && (y.variance() - 4.0).abs() < 0.15);
+        assert!((z.mean() - 0.5).abs() < 0.02 && (z.variance() - 0.25).abs() < 0.03);
+
+        let d = Joint::new(vec![Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0)]);
+        let x = d.sample(&mut source);
+        assert!(x.len() == 2 && 0.0 <= x[0] && x[0] <= 1.0 && 2.0 <= x[1] && x[1] <= 3.0);
+    }
+}
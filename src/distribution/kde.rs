@@ -0,0 +1,390 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A kernel density estimate.
+///
+/// The distribution is an equally weighted mixture of Gaussian kernels
+/// centered at the observations and sharing the same bandwidth, that is,
+/// standard deviation. Kernels farther than 40 bandwidths from the point of
+/// evaluation are skipped, since their contributions underflow, which makes
+/// the evaluation cost proportional to the number of observations in the
+/// window rather than the total number.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kde {
+    samples: Vec<f64>,
+    bandwidth: f64,
+}
+
+impl Kde {
+    /// Create a kernel density estimate from observations `samples`.
+    ///
+    /// The bandwidth is chosen according to Silverman’s rule of thumb,
+    /// `0.9 * min(s, IQR / 1.34) * n^(-1/5)`, where `s` is the sample
+    /// standard deviation and `IQR` is the interquartile range. When the
+    /// interquartile range vanishes, `s` is used alone, and, when the data
+    /// show no spread at all, the bandwidth is one.
+    ///
+    /// It should hold that `samples` is not empty and contains only finite
+    /// values; the function panics otherwise.
+    #[inline]
+    pub fn new(samples: &[f64]) -> Self {
+        unwrap!(Kde::try_new(samples))
+    }
+
+    /// Create a kernel density estimate from observations `samples` or return
+    /// an error if the observations are invalid.
+    pub fn try_new(samples: &[f64]) -> Result<Self, ParameterError> {
+        use distribution::{Empirical, Inverse};
+
+        ensure!(!samples.is_empty(), ParameterError::SamplesEmpty);
+        for &x in samples {
+            ensure!(x.is_finite(), ParameterError::SampleNotFinite { value: x });
+        }
+
+        let n = samples.len() as f64;
+        let empirical = Empirical::new(samples);
+        let iqr = (empirical.inverse(0.75) - empirical.inverse(0.25)) / 1.34;
+        let deviation = if samples.len() > 1 {
+            let mean = samples.iter().fold(0.0, |sum, &x| sum + x) / n;
+            (samples.iter().fold(0.0, |sum, &x| sum + (x - mean).powi(2)) / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let spread = if iqr > 0.0 { deviation.min(iqr) } else { deviation };
+        let bandwidth = if spread > 0.0 { 0.9 * spread * n.powf(-0.2) } else { 1.0 };
+        Kde::try_with_bandwidth(samples, bandwidth)
+    }
+
+    /// Create a kernel density estimate from observations `samples` with
+    /// bandwidth `bandwidth`.
+    ///
+    /// It should hold that `samples` is not empty and contains only finite
+    /// values and that `bandwidth > 0`; the function panics otherwise.
+    #[inline]
+    pub fn with_bandwidth(samples: &[f64], bandwidth: f64) -> Self {
+        unwrap!(Kde::try_with_bandwidth(samples, bandwidth))
+    }
+
+    /// Create a kernel density estimate from observations `samples` with
+    /// bandwidth `bandwidth` or return an error if the parameters are invalid.
+    pub fn try_with_bandwidth(samples: &[f64], bandwidth: f64) -> Result<Self, ParameterError> {
+        ensure!(!samples.is_empty(), ParameterError::SamplesEmpty);
+        for &x in samples {
+            ensure!(x.is_finite(), ParameterError::SampleNotFinite { value: x });
+        }
+        ensure!(bandwidth > 0.0 && bandwidth.is_finite(),
+                ParameterError::BandwidthNotPositive { value: bandwidth });
+        let mut samples = samples.to_vec();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Ok(Kde { samples: samples, bandwidth: bandwidth })
+    }
+
+    /// Return the sorted observations.
+    #[inline(always)]
+    pub fn samples(&self) -> &[f64] { &self.samples }
+
+    /// Return the bandwidth.
+    #[inline(always)]
+    pub fn bandwidth(&self) -> f64 { self.bandwidth }
+
+    // Find the range of observations whose kernels are not negligible at `x`.
+    fn window(&self, x: f64) -> (usize, usize) {
+        let delta = WINDOW * self.bandwidth;
+        (count(&self.samples, x - delta), count(&self.samples, x + delta))
+    }
+
+    fn central(&self, order: i32) -> f64 {
+        use distribution::Mean;
+        let mean = self.mean();
+        self.samples.iter().fold(0.0, |sum, &x| sum + (x - mean).powi(order)) /
+            self.samples.len() as f64
+    }
+}
+
+impl fmt::Display for Kde {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Kde(n = {}, bandwidth = {})", self.samples.len(), self.bandwidth)
+    }
+}
+
+impl distribution::Continuous for Kde {
+    fn density(&self, x: f64) -> f64 {
+        use std::f64::consts::PI;
+        guard!(density(x));
+        let (lower, upper) = self.window(x);
+        let sum = self.samples[lower..upper].iter().fold(0.0, |sum, &y| {
+            sum + (-((x - y) / self.bandwidth).powi(2) / 2.0).exp()
+        });
+        sum / ((2.0 * PI).sqrt() * self.bandwidth * self.samples.len() as f64)
+    }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The moment is computed exactly by combining the central moments of the
+    /// observations with those of the kernel.
+    fn central_moment(&self, k: u32) -> f64 {
+        use distribution::Gaussian;
+        let kernel = Gaussian::new(0.0, self.bandwidth);
+        let (mut sum, mut binomial) = (0.0, 1.0);
+        for j in 0..(k + 1) {
+            sum += binomial * self.central((k - j) as i32) * kernel.central_moment(j);
+            binomial = binomial * (k - j) as f64 / (j + 1) as f64;
+        }
+        sum
+    }
+
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        use distribution::{Mean, translate};
+        translate(|j| self.central_moment(j), k, self.mean())
+    }
+}
+
+impl distribution::Distribution for Kde {
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        use special::Error;
+        use std::f64::consts::SQRT_2;
+        guard!(distribution(x));
+        let (lower, upper) = self.window(x);
+        let sum = self.samples[lower..upper].iter().fold(lower as f64, |sum, &y| {
+            sum + Error::erfc((y - x) / (self.bandwidth * SQRT_2)) / 2.0
+        });
+        sum / self.samples.len() as f64
+    }
+}
+
+impl distribution::Inverse for Kde {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The solution is bracketed using the extreme observations and then
+    /// refined by Newton’s method, falling back to bisection whenever a step
+    /// leaves the bracket.
+    fn inverse(&self, p: f64) -> f64 {
+        use distribution::{Continuous, Distribution};
+
+        guard!(inverse(p));
+        if p == 0.0 {
+            return -::std::f64::INFINITY;
+        }
+        if p == 1.0 {
+            return ::std::f64::INFINITY;
+        }
+
+        let n = self.samples.len();
+        let delta = WINDOW * self.bandwidth;
+        let (mut a, mut b) = (self.samples[0] - delta, self.samples[n - 1] + delta);
+        let mut x = self.samples[((n - 1) as f64 * p) as usize];
+        for _ in 0..100 {
+            let f = self.distribution(x) - p;
+            if f < 0.0 { a = x; } else { b = x; }
+            let density = self.density(x);
+            let mut y = x - f / density;
+            if !(density > 0.0) || !(a < y && y < b) {
+                y = (a + b) / 2.0;
+            }
+            if (y - x).abs() <= 1e-15 * x.abs().max(self.bandwidth) || y == a || y == b {
+                return y;
+            }
+            x = y;
+        }
+        x
+    }
+}
+
+impl distribution::Kurtosis for Kde {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        use distribution::Variance;
+        let h2 = self.bandwidth * self.bandwidth;
+        let fourth = self.central(4) + 6.0 * self.central(2) * h2 + 3.0 * h2 * h2;
+        fourth / self.variance().powi(2) - 3.0
+    }
+}
+
+impl distribution::Mean for Kde {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.samples.iter().fold(0.0, |sum, &x| sum + x) / self.samples.len() as f64
+    }
+}
+
+impl distribution::Sample for Kde {
+    /// Draw a sample.
+    ///
+    /// An observation is drawn uniformly, and Gaussian noise scaled by the
+    /// bandwidth is added to it.
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        use distribution::gaussian;
+        let n = self.samples.len();
+        let i = (source.read::<f64>() * n as f64) as usize;
+        self.samples[if i < n { i } else { n - 1 }] + self.bandwidth * gaussian::sample(source)
+    }
+}
+
+impl distribution::Skewness for Kde {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        use distribution::Variance;
+        self.central(3) / self.variance().powf(1.5)
+    }
+}
+
+impl distribution::Variance for Kde {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.central(2) + self.bandwidth * self.bandwidth
+    }
+}
+
+const WINDOW: f64 = 40.0;
+
+// Count the sorted values less than `x`.
+fn count(values: &[f64], x: f64) -> usize {
+    let (mut lower, mut upper) = (0, values.len());
+    while lower < upper {
+        let middle = (lower + upper) / 2;
+        if values[middle] < x {
+            lower = middle + 1;
+        } else {
+            upper = middle;
+        }
+    }
+    lower
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($samples:expr) => (Kde::new(&$samples));
+        ($samples:expr, $bandwidth:expr) => (Kde::with_bandwidth(&$samples, $bandwidth));
+    );
+
+    static SAMPLES: [f64; 5] = [2.1, -1.2, 0.3, 3.4, 0.5];
+
+    #[test]
+    fn bandwidth() {
+        assert::close(new!(SAMPLES).bandwidth(), 0.8762261605655721, 1e-15);
+        assert::close(new!([0.0, 0.0, 0.0, 1.0]).bandwidth(), 0.12725232368091027, 1e-15);
+        assert_eq!(new!([1.0]).bandwidth(), 1.0);
+    }
+
+    #[test]
+    fn central_moment() {
+        let d = new!(SAMPLES, 0.8);
+        assert::close(d.central_moment(1), 0.0, 1e-15);
+        assert::close(d.central_moment(2), d.variance(), 1e-14);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-14);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-14);
+        assert::close(d.central_moment(4), d.moment(4) - 4.0 * d.mean() * d.moment(3) +
+                      6.0 * d.mean().powi(2) * d.moment(2) - 3.0 * d.mean().powi(4), 1e-12);
+    }
+
+    #[test]
+    fn density() {
+        let d = new!(SAMPLES, 0.8);
+        let x = vec![-5.0, -1.0, 0.0, 0.4, 1.0, 2.5, 4.0, 50.0];
+        let p = vec![
+            1.257411642527238e-06, 1.405529250238728e-01, 2.105763895798521e-01,
+            2.219349803353483e-01, 1.921884108251852e-01, 1.476432179807619e-01,
+            8.123655091844803e-02, 0.000000000000000e+00,
+        ];
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Kde::with_bandwidth(&[3.0, 1.0, 2.0], 0.5).to_string(),
+                   "Kde(n = 3, bandwidth = 0.5)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(SAMPLES, 0.8);
+        let x = vec![-50.0, -5.0, -1.0, 0.0, 0.4, 1.0, 2.5, 4.0, 50.0];
+        let p = vec![
+            0.000000000000000e+00, 2.034207408941921e-07, 1.362474596405436e-01,
+            3.114703395991812e-01, 3.988263183570941e-01, 5.252326750493400e-01,
+            7.625131353715972e-01, 9.529180455742633e-01, 1.000000000000000e+00,
+        ];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-14);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(SAMPLES, 0.8);
+        for &x in &[-5.0, -1.0, 0.0, 0.4, 1.0, 2.5, 4.0] {
+            assert::close(d.inverse(d.distribution(x)), x, 1e-10);
+        }
+        assert_eq!(d.inverse(0.0), -::std::f64::INFINITY);
+        assert_eq!(d.inverse(1.0), ::std::f64::INFINITY);
+
+        let d = new!([1.0], 2.0);
+        assert::close(d.inverse(0.3), Gaussian::new(1.0, 2.0).inverse(0.3), 1e-12);
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert::close(new!([0.0], 1.0).kurtosis(), 0.0, 1e-15);
+        assert::close(new!(SAMPLES, 0.8).kurtosis(), -0.733761241716032, 1e-14);
+    }
+
+    #[test]
+    fn mean() {
+        assert::close(new!(SAMPLES, 0.8).mean(), 1.02, 1e-15);
+    }
+
+    #[test]
+    fn moment() {
+        let d = new!(SAMPLES, 0.8);
+        assert_eq!(d.moment(0), 1.0);
+        assert::close(d.moment(1), d.mean(), 1e-15);
+        assert::close(d.moment(2), d.variance() + d.mean().powi(2), 1e-14);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!(SAMPLES, 0.8);
+        let x = Independent(&d, &mut source::seeded(42)).take(10000).collect::<Vec<_>>();
+        let e = Empirical::new(&x);
+        assert!((e.mean() - d.mean()).abs() < 0.05);
+        assert!((e.variance() - d.variance()).abs() < 0.1);
+    }
+
+    #[test]
+    fn skewness() {
+        assert::close(new!(SAMPLES, 0.8).skewness(), 0.11757771751576442, 1e-14);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Kde::try_new(&[1.0, 2.0]).is_ok());
+        assert_eq!(Kde::try_new(&[]).err(), Some(ParameterError::SamplesEmpty));
+        assert_eq!(Kde::try_new(&[1.0, INFINITY]).err(),
+                   Some(ParameterError::SampleNotFinite { value: INFINITY }));
+        assert!(Kde::try_new(&[NAN]).is_err());
+        for &bandwidth in &[0.0, -1.0, INFINITY] {
+            assert_eq!(Kde::try_with_bandwidth(&[1.0], bandwidth).err(),
+                       Some(ParameterError::BandwidthNotPositive { value: bandwidth }));
+        }
+        assert!(Kde::try_with_bandwidth(&[1.0], NAN).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        assert::close(new!(SAMPLES, 0.8).variance(), 2.5096 + 0.64, 1e-14);
+    }
+}
@@ -0,0 +1,497 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{self, Categorical, Gaussian};
+use source::Source;
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// Options of the expectation–maximization algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmOptions {
+    /// The maximum number of iterations.
+    pub iterations: usize,
+    /// The tolerance on the increase of the log-likelihood per datum below
+    /// which the algorithm stops.
+    pub tolerance: f64,
+    /// The smallest variance of a component relative to the variance of the
+    /// data, which prevents components from collapsing onto single points.
+    pub variance_floor: f64,
+    /// The seed of the source of randomness used in the initialization.
+    pub seed: u64,
+}
+
+/// A report of the expectation–maximization algorithm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmReport {
+    /// The log-likelihood of the fitted mixture.
+    pub log_likelihood: f64,
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// The log-likelihood at each iteration, which is nondecreasing.
+    pub trace: Vec<f64>,
+    /// The flag telling whether the tolerance was reached within the maximum
+    /// number of iterations.
+    pub converged: bool,
+    /// The flag telling whether the variance of some component of the fitted
+    /// mixture was raised to the floor.
+    pub floored: bool,
+}
+
+impl Default for EmOptions {
+    #[inline]
+    fn default() -> Self {
+        EmOptions { iterations: 1000, tolerance: 1e-10, variance_floor: 1e-6, seed: 42 }
+    }
+}
+
+/// A mixture distribution.
+///
+/// The components can be of any type implementing the traits of interest.
+/// Mixtures of heterogeneous components can be constructed by wrapping the
+/// components in an enum that dispatches to the corresponding distributions.
+///
+/// The modes are not computed since they generally do not admit a closed-form
+/// expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mixture<T> {
+    components: Vec<T>,
+    weights: Categorical,
+}
+
+impl<T> Mixture<T> where T: distribution::Distribution {
+    /// Create a mixture distribution with components `components` and weights
+    /// `weights`.
+    ///
+    /// The weights are normalized to sum up to one. It should hold that the
+    /// number of weights is equal to the number of components, which is
+    /// positive, `weights[i] >= 0`, and `sum(weights) > 0`; the function
+    /// panics otherwise.
+    #[inline]
+    pub fn new(components: Vec<T>, weights: Vec<f64>) -> Self {
+        unwrap!(Mixture::try_new(components, weights))
+    }
+
+    /// Create a mixture distribution with components `components` and weights
+    /// `weights` or return an error if the parameters are invalid.
+    pub fn try_new(components: Vec<T>, weights: Vec<f64>) -> Result<Self, ParameterError> {
+        ensure!(!components.is_empty(), ParameterError::ComponentsEmpty);
+        ensure!(components.len() == weights.len(), ParameterError::WeightsMismatch {
+            components: components.len(), weights: weights.len(),
+        });
+        for &w in &weights {
+            ensure!(w >= 0.0 && w.is_finite(), ParameterError::WeightNotNonnegative { value: w });
+        }
+        let sum = weights.iter().fold(0.0, |sum, &w| sum + w);
+        ensure!(sum > 0.0, ParameterError::WeightsZero);
+        let weights = weights.iter().map(|&w| w / sum).collect::<Vec<_>>();
+        Ok(Mixture { components: components, weights: Categorical::new(&weights) })
+    }
+
+    /// Return the components.
+    #[inline(always)]
+    pub fn components(&self) -> &[T] { &self.components }
+
+    /// Return the normalized weights.
+    #[inline(always)]
+    pub fn weights(&self) -> &[f64] { self.weights.p() }
+}
+
+impl Mixture<Gaussian> {
+    /// Fit a mixture of `k` Gaussian distributions to `data` using the
+    /// expectation–maximization algorithm.
+    ///
+    /// The means are initialized by the k-means++ seeding, the variances by
+    /// the variance of the data, and the weights uniformly. The
+    /// responsibilities are computed in the logarithmic domain. The fitted
+    /// mixture is the one whose log-likelihood is the last one in the trace;
+    /// the algorithm stops at the maximum number of iterations without an
+    /// error, which is indicated in the report.
+    ///
+    /// ## References
+    ///
+    /// 1. D. Arthur and S. Vassilvitskii, “k-means++: The advantages of
+    ///    careful seeding,” Proceedings of the Eighteenth Annual ACM-SIAM
+    ///    Symposium on Discrete Algorithms, pp. 1027–1035, 2007.
+    pub fn fit_em(data: &[f64], k: usize, options: EmOptions)
+                  -> Result<(Self, EmReport), FitError> {
+        use distribution::{average, examine};
+        use math;
+        use source;
+        use std::f64::consts::PI;
+
+        examine(data)?;
+        ensure!(k > 0, FitError::Parameter(ParameterError::ComponentsEmpty));
+        let mean = average(data, None, |x| x);
+        let variance = average(data, None, |x| (x - mean) * (x - mean));
+        ensure!(variance > 0.0, FitError::DataNotDistinct);
+        let floor = options.variance_floor * variance;
+
+        let n = data.len();
+        let mut source = source::seeded(options.seed);
+        let mut mu = Vec::with_capacity(k);
+        mu.push(data[pick(&mut source, n)]);
+        let mut distances = data.iter().map(|&x| (x - mu[0]) * (x - mu[0])).collect::<Vec<_>>();
+        while mu.len() < k {
+            let total = distances.iter().fold(0.0, |sum, &d| sum + d);
+            let i = if total > 0.0 {
+                let mut u = source.read::<f64>() * total;
+                distances.iter().position(|&d| { u -= d; u < 0.0 }).unwrap_or(n - 1)
+            } else {
+                pick(&mut source, n)
+            };
+            let center = data[i];
+            mu.push(center);
+            for (d, &x) in distances.iter_mut().zip(data) {
+                *d = d.min((x - center) * (x - center));
+            }
+        }
+        let mut sigma2 = vec![variance; k];
+        let mut weights = vec![1.0 / k as f64; k];
+
+        let mut trace = Vec::new();
+        let mut responsibilities = vec![0.0; n * k];
+        let mut converged = false;
+        let mut terms = vec![0.0; k];
+        for iteration in 0..options.iterations {
+            let mut log_likelihood = 0.0;
+            for (i, &x) in data.iter().enumerate() {
+                for j in 0..k {
+                    let z = x - mu[j];
+                    terms[j] = weights[j].ln() - 0.5 * ((2.0 * PI * sigma2[j]).ln() +
+                                                        z * z / sigma2[j]);
+                }
+                let ln_sum = math::log_sum_exp(&terms);
+                for j in 0..k {
+                    responsibilities[i * k + j] = (terms[j] - ln_sum).exp();
+                }
+                log_likelihood += ln_sum;
+            }
+            let done = match trace.last() {
+                Some(&previous) => log_likelihood - previous <= options.tolerance * n as f64,
+                _ => false,
+            };
+            trace.push(log_likelihood);
+            if done || iteration + 1 == options.iterations {
+                converged = done;
+                break;
+            }
+            for j in 0..k {
+                let total = (0..n).fold(0.0, |sum, i| sum + responsibilities[i * k + j]);
+                weights[j] = total / n as f64;
+                if total > 0.0 {
+                    mu[j] = (0..n).fold(0.0, |sum, i| {
+                        sum + responsibilities[i * k + j] * data[i]
+                    }) / total;
+                    sigma2[j] = ((0..n).fold(0.0, |sum, i| {
+                        sum + responsibilities[i * k + j] * (data[i] - mu[j]) * (data[i] - mu[j])
+                    }) / total).max(floor);
+                }
+            }
+        }
+
+        let mut components = Vec::with_capacity(k);
+        for j in 0..k {
+            components.push(Gaussian::try_new(mu[j], sigma2[j].sqrt())?);
+        }
+        let mixture = Mixture::try_new(components, weights)?;
+        let report = EmReport {
+            log_likelihood: *trace.last().unwrap_or(&::std::f64::NAN),
+            iterations: trace.len(),
+            trace: trace,
+            converged: converged,
+            floored: sigma2.iter().any(|&s| s <= floor),
+        };
+        Ok((mixture, report))
+    }
+}
+
+impl<T> fmt::Display for Mixture<T> where T: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let components = self.components.iter().zip(self.weights.p()).map(|(c, w)| {
+            format!("{} × {}", w, c)
+        }).collect::<Vec<_>>();
+        write!(formatter, "Mixture({})", components.join(", "))
+    }
+}
+
+impl<T> distribution::Continuous for Mixture<T> where T: distribution::Continuous {
+    fn density(&self, x: f64) -> f64 {
+        self.components.iter().zip(self.weights()).fold(0.0, |sum, (c, &w)| {
+            sum + w * c.density(x)
+        })
+    }
+}
+
+impl<T> distribution::Discrete for Mixture<T>
+    where T: distribution::Discrete, T::Value: Copy
+{
+    fn mass(&self, x: T::Value) -> f64 {
+        self.components.iter().zip(self.weights()).fold(0.0, |sum, (c, &w)| {
+            sum + w * c.mass(x)
+        })
+    }
+}
+
+impl<T> distribution::Distribution for Mixture<T> where T: distribution::Distribution {
+    type Value = T::Value;
+
+    fn distribution(&self, x: f64) -> f64 {
+        self.components.iter().zip(self.weights()).fold(0.0, |sum, (c, &w)| {
+            sum + w * c.distribution(x)
+        })
+    }
+
+    /// Return the support.
+    ///
+    /// The support is the smallest interval containing the supports of the
+    /// components with positive weights.
+    fn support(&self) -> (f64, f64) {
+        use std::f64::{INFINITY, NEG_INFINITY};
+        let (mut a, mut b) = (INFINITY, NEG_INFINITY);
+        for (c, &w) in self.components.iter().zip(self.weights()) {
+            if w > 0.0 {
+                let (lower, upper) = c.support();
+                a = a.min(lower);
+                b = b.max(upper);
+            }
+        }
+        (a, b)
+    }
+}
+
+impl<T> distribution::Inverse for Mixture<T> where T: distribution::Inverse<Value=f64> {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The quantile of a mixture lies between the smallest and the largest
+    /// quantiles of the components, and it is found by Brent’s method within
+    /// this interval; see `distribution::invert`.
+    fn inverse(&self, p: f64) -> f64 {
+        use std::f64::{EPSILON, INFINITY, NAN, NEG_INFINITY};
+
+        guard!(inverse(p));
+
+        let (mut a, mut b) = (INFINITY, NEG_INFINITY);
+        for (c, &w) in self.components.iter().zip(self.weights()) {
+            if w > 0.0 {
+                let x = c.inverse(p);
+                a = a.min(x);
+                b = b.max(x);
+            }
+        }
+        if a == b || p == 0.0 || p == 1.0 || !a.is_finite() || !b.is_finite() {
+            return if p < 0.5 { a } else { b };
+        }
+        distribution::invert(self, p, (a, b), EPSILON * (b - a)).unwrap_or(NAN)
+    }
+}
+
+impl<T> distribution::Mean for Mixture<T> where T: distribution::Mean {
+    fn mean(&self) -> f64 {
+        self.components.iter().zip(self.weights()).fold(0.0, |sum, (c, &w)| {
+            sum + w * c.mean()
+        })
+    }
+}
+
+impl<T> distribution::Sample for Mixture<T> where T: distribution::Sample {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> T::Value where S: Source {
+        self.components[self.weights.sample(source)].sample(source)
+    }
+}
+
+impl<T> distribution::Variance for Mixture<T> where T: distribution::Variance {
+    /// Compute the variance.
+    ///
+    /// The variance is computed according to the law of total variance.
+    fn variance(&self) -> f64 {
+        use distribution::Mean;
+        let mean = self.mean();
+        self.components.iter().zip(self.weights()).fold(0.0, |sum, (c, &w)| {
+            sum + w * (c.variance() + (c.mean() - mean).powi(2))
+        })
+    }
+}
+
+fn pick<S>(source: &mut S, n: usize) -> usize where S: Source {
+    ((source.read::<f64>() * n as f64) as usize).min(n - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($(($mu:expr, $sigma:expr)),+; $($w:expr),+) => (
+            Mixture::new(vec![$(Gaussian::new($mu, $sigma)),+], vec![$($w),+])
+        );
+    );
+
+    fn integrate<F>(f: F, a: f64, b: f64) -> f64 where F: Fn(f64) -> f64 {
+        let n = 100000;
+        let h = (b - a) / n as f64;
+        (0..(n + 1)).fold(0.0, |sum, i| {
+            let w = if i == 0 || i == n { 0.5 } else { 1.0 };
+            sum + w * f(a + i as f64 * h)
+        }) * h
+    }
+
+    #[test]
+    fn density() {
+        let d = new!((-5.0, 1.0), (5.0, 2.0); 1.0, 3.0);
+        let (a, b) = (Gaussian::new(-5.0, 1.0), Gaussian::new(5.0, 2.0));
+        let x = vec![-7.0, -5.0, -1.0, 0.0, 1.0, 5.0, 7.0];
+        let p = x.iter().map(|&x| 0.25 * a.density(x) + 0.75 * b.density(x)).collect::<Vec<_>>();
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-15);
+        assert::close(integrate(|x| d.density(x), -30.0, 30.0), 1.0, 1e-10);
+    }
+
+    #[test]
+    fn display() {
+        let d = Mixture::new(vec![Gaussian::new(0.0, 1.0), Gaussian::new(3.0, 2.0)],
+                             vec![1.0, 3.0]);
+        assert_eq!(d.to_string(),
+                   "Mixture(0.25 × Gaussian(μ = 0, σ = 1), 0.75 × Gaussian(μ = 3, σ = 2))");
+        let d = Mixture::new(vec![Affine::new(Exponential::new(1.0), 2.0, 0.0)], vec![1.0]);
+        assert_eq!(d.to_string(), "Mixture(1 × Affine(Exponential(λ = 1), scale = 2, shift = 0))");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!((0.0, 1.0), (0.5, 1.5); 0.5, 0.5);
+        let (a, b) = (Gaussian::new(0.0, 1.0), Gaussian::new(0.5, 1.5));
+        let x = vec![-3.0, -1.0, 0.0, 0.25, 0.5, 1.0, 3.0];
+        let p = x.iter().map(|&x| 0.5 * (a.distribution(x) + b.distribution(x)))
+                        .collect::<Vec<_>>();
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn fit_em() {
+        let d = new!((-5.0, 1.0), (5.0, 2.0); 0.3, 0.7);
+        let x = Independent(&d, &mut source::seeded(42)).take(10000).collect::<Vec<_>>();
+        let (e, report) = Mixture::fit_em(&x, 2, EmOptions::default()).unwrap();
+        assert!(report.converged && !report.floored);
+        assert_eq!(report.iterations, report.trace.len());
+        assert!(report.trace.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert::close(report.log_likelihood / e.log_likelihood(&x), 1.0, 1e-12);
+
+        let mut components = e.components().iter().zip(e.weights()).collect::<Vec<_>>();
+        components.sort_by(|one, other| one.0.mu().partial_cmp(&other.0.mu()).unwrap());
+        let expected = [(-5.0, 1.0, 0.3), (5.0, 2.0, 0.7)];
+        for (&(c, &w), &(mu, sigma, weight)) in components.iter().zip(&expected) {
+            assert!((c.mu() - mu).abs() < 0.1);
+            assert!((c.sigma() - sigma).abs() < 0.1);
+            assert!((w - weight).abs() < 0.02);
+        }
+
+        let (f, _) = Mixture::fit_em(&x, 2, EmOptions::default()).unwrap();
+        assert_eq!(e, f);
+
+        let options = EmOptions { iterations: 3, ..EmOptions::default() };
+        let (e, report) = Mixture::fit_em(&x, 2, options).unwrap();
+        assert!(!report.converged);
+        assert_eq!(report.iterations, 3);
+        assert::close(report.log_likelihood / e.log_likelihood(&x), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn fit_em_degenerate() {
+        let mut x = Gaussian::new(0.0, 1.0).samples(&mut source::seeded(42), 500);
+        x.extend(vec![3.0; 500]);
+        let (e, report) = Mixture::fit_em(&x, 3, EmOptions::default()).unwrap();
+        assert!(report.floored);
+        assert!(report.trace.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!(e.components().iter().all(|c| c.sigma().is_finite() && c.sigma() > 0.0));
+
+        assert_eq!(Mixture::fit_em(&[], 2, EmOptions::default()).err(), Some(FitError::DataEmpty));
+        assert_eq!(Mixture::fit_em(&[1.0, 1.0], 2, EmOptions::default()).err(),
+                   Some(FitError::DataNotDistinct));
+        assert_eq!(Mixture::fit_em(&[1.0, 2.0], 0, EmOptions::default()).err(),
+                   Some(FitError::Parameter(ParameterError::ComponentsEmpty)));
+    }
+
+    #[test]
+    fn hpd_interval() {
+        let d = Mixture::new(vec![Gaussian::new(-3.0, 1.0), Gaussian::new(3.0, 1.0)],
+                             vec![0.5, 0.5]);
+        let (lower, upper) = d.hpd_interval(0.3);
+        assert!(upper < 0.0 || lower > 0.0);
+        assert::close(d.distribution(upper) - d.distribution(lower), 0.3, 1e-12);
+        assert::close(d.density(lower), d.density(upper), 1e-8);
+    }
+
+    #[test]
+    fn inverse() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = new!((-5.0, 1.0), (5.0, 2.0); 1.0, 1.0);
+        assert_eq!(d.inverse(0.0), NEG_INFINITY);
+        assert_eq!(d.inverse(1.0), INFINITY);
+        for &p in &[0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+            assert::close(d.distribution(d.inverse(p)), p, 1e-14);
+        }
+
+        let d = new!((0.0, 1.0), (0.5, 1.5); 0.3, 0.7);
+        for &p in &[0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+            assert::close(d.distribution(d.inverse(p)), p, 1e-14);
+        }
+
+        let d = new!((1.0, 2.0); 1.0);
+        assert::close(d.inverse(0.3), Gaussian::new(1.0, 2.0).inverse(0.3), 1e-14);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!((-5.0, 1.0), (5.0, 2.0); 1.0, 3.0).mean(), 2.5);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!((-5.0, 1.0), (5.0, 2.0); 1.0, 1.0);
+        let x = Independent(&d, &mut source::seeded(42)).take(10000).collect::<Vec<_>>();
+        let mean = x.iter().fold(0.0, |sum, &x| sum + x) / x.len() as f64;
+        assert!(mean.abs() < 0.2);
+        assert!(x.iter().filter(|&&x| x < 0.0).count() > 4500);
+        assert!(x.iter().filter(|&&x| x > 0.0).count() > 4500);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        let components = vec![Gaussian::new(0.0, 1.0), Gaussian::new(3.0, 2.0)];
+        assert!(Mixture::try_new(components.clone(), vec![1.0, 3.0]).is_ok());
+        assert_eq!(Mixture::<Gaussian>::try_new(vec![], vec![]).err(),
+                   Some(ParameterError::ComponentsEmpty));
+        assert_eq!(Mixture::try_new(components.clone(), vec![1.0]).err(),
+                   Some(ParameterError::WeightsMismatch { components: 2, weights: 1 }));
+        for &w in &[-1.0, INFINITY] {
+            assert_eq!(Mixture::try_new(components.clone(), vec![1.0, w]).err(),
+                       Some(ParameterError::WeightNotNonnegative { value: w }));
+        }
+        assert_eq!(Mixture::try_new(components.clone(), vec![0.0, 0.0]).err(),
+                   Some(ParameterError::WeightsZero));
+        assert!(Mixture::try_new(components, vec![1.0, NAN]).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        for d in &[new!((-5.0, 1.0), (5.0, 2.0); 1.0, 3.0),
+                   new!((0.0, 1.0), (0.5, 1.5); 0.5, 0.5)] {
+            let mean = integrate(|x| x * d.density(x), -40.0, 40.0);
+            let variance = integrate(|x| (x - mean).powi(2) * d.density(x), -40.0, 40.0);
+            assert::close(d.mean(), mean, 1e-10);
+            assert::close(d.variance(), variance, 1e-8);
+        }
+        assert_eq!(new!((-5.0, 1.0), (5.0, 2.0); 1.0, 1.0).variance(), 27.5);
+    }
+
+    #[test]
+    fn weights() {
+        assert_eq!(new!((-5.0, 1.0), (5.0, 2.0); 1.0, 3.0).weights(), &[0.25, 0.75]);
+    }
+}
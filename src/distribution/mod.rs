@@ -1,17 +1,237 @@
 //! Probability distributions.
+//!
+//! Distributions are compared by their parameters exactly, without any
+//! tolerance. As with `f64`, a distribution with a NaN parameter, which can be
+//! obtained only by bypassing the validation of the constructors, is not equal
+//! to itself.
+//!
+//! The functions of a point, that is, the density, cumulative distribution, and
+//! survival functions and their logarithms, return NaN when the point is NaN
+//! and their limits when the point is infinite; for instance, the cumulative
+//! distribution function is zero at `-∞` and one at `∞`, and the density
+//! vanishes at both. Discrete distributions evaluate their cumulative
+//! distribution functions at points between outcomes as step functions; see
+//! `Discrete`. The inverse of the cumulative distribution function and that of
+//! the survival function panic when the probability is not in `[0, 1]`,
+//! including when it is NaN, regardless of the build profile.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 use source::Source;
+use statistics::OnlineStats;
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
 
 /// A continuous distribution.
 pub trait Continuous: Distribution {
     /// Compute the probability density function.
     fn density(&self, f64) -> f64;
+
+    /// Compute the probability density function at each point of `xs` and
+    /// write the results to `out`.
+    ///
+    /// The slices should have the same length; the function panics otherwise.
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        conform(xs.len(), out.len());
+        for (y, &x) in out.iter_mut().zip(xs) {
+            *y = self.density(x);
+        }
+    }
+
+    /// Compute the probability density function at each point of `xs`.
+    #[cfg(feature = "alloc")]
+    fn density_vec(&self, xs: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; xs.len()];
+        self.density_slice(xs, &mut out);
+        out
+    }
+
+    /// Compute the central moment of order `k`.
+    ///
+    /// The default implementation integrates the density function over the
+    /// support using the adaptive Gauss–Kronrod rule; see `math::integrate`.
+    /// The part between the quantiles of order `1e-12` and `1 - 1e-12` is
+    /// split into 16 pieces so that no probability mass is overlooked.
+    #[cfg(feature = "alloc")]
+    fn central_moment(&self, k: u32) -> f64 where Self: Inverse<Value=f64> {
+        let mean = self.moment(1);
+        integrate(self, |x| (x - mean).powi(k as i32))
+    }
+
+    /// Evaluate the probability density function on a grid of `n` evenly
+    /// spaced points covering the bulk of the distribution.
+    ///
+    /// The grid spans the support if it is bounded and the quantiles of order
+    /// `0.001` and `0.999` in place of infinite endpoints; see
+    /// `density_grid_between`.
+    #[cfg(feature = "alloc")]
+    fn density_grid(&self, n: usize) -> Vec<(f64, f64)> where Self: Inverse<Value=f64> {
+        let (a, b) = span(self, 1e-3);
+        self.density_grid_between(a, b, n)
+    }
+
+    /// Evaluate the probability density function on a grid of `n` evenly
+    /// spaced points from `a` to `b`.
+    ///
+    /// An endpoint toward which the density grows without bound, such as the
+    /// edge of the support of a U-shaped beta distribution, is moved inside by
+    /// one step; the growth is detected by comparing the density at two points
+    /// approaching the endpoint. It should hold that `a < b`, both finite, and
+    /// `n > 1`.
+    #[cfg(feature = "alloc")]
+    fn density_grid_between(&self, a: f64, b: f64, n: usize) -> Vec<(f64, f64)> {
+        let skip = (diverges(self, a, b) as usize, diverges(self, b, a) as usize);
+        grid(a, b, n, skip).into_iter().map(|x| (x, self.density(x))).collect()
+    }
+
+    /// Compute the hazard function, that is, the ratio of the probability
+    /// density function to the survival function.
+    ///
+    /// The ratio is computed in the logarithmic domain, which keeps it finite
+    /// in the right tail where both functions underflow.
+    #[inline]
+    fn hazard(&self, x: f64) -> f64 {
+        let density = self.ln_density(x);
+        if density == ::std::f64::NEG_INFINITY {
+            return 0.0;
+        }
+        (density - self.ln_survival(x)).exp()
+    }
+
+    /// Compute the highest-posterior-density interval at level `level`, that
+    /// is, the shortest interval containing probability `level`.
+    ///
+    /// The lower tail probability `p` is chosen by minimizing the width
+    /// `inverse(p + level) - inverse(p)` via the golden-section search, and
+    /// the result is refined by solving for the point where the density takes
+    /// the same value at both endpoints. An optimum at the edge of the support,
+    /// as for the exponential distribution, yields an interval with an endpoint
+    /// at the edge.
+    ///
+    /// The distribution is assumed to be unimodal. For a multimodal
+    /// distribution, the highest-density region can be a union of intervals;
+    /// the function then falls back to a single interval of probability
+    /// `level` that is locally the shortest. It should hold that
+    /// `0 < level < 1`.
+    fn hpd_interval(&self, level: f64) -> (f64, f64) where Self: Inverse<Value=f64> {
+        use math;
+        should!(0.0 < level && level < 1.0);
+        let limit = 1.0 - level;
+        let width = |p: f64| self.inverse(p + level) - self.inverse(p);
+        let balance = |p: f64| self.ln_density(self.inverse(p)) -
+                               self.ln_density(self.inverse(p + level));
+        let p = math::golden(width, 0.0, limit, 1e-10 * limit);
+        let (lower, upper) = ((p - 1e-6 * limit).max(0.0), (p + 1e-6 * limit).min(limit));
+        let (low, high) = (balance(lower), balance(upper));
+        let p = if lower == 0.0 && low >= 0.0 {
+            0.0
+        } else if upper == limit && high <= 0.0 {
+            limit
+        } else if low < 0.0 && high > 0.0 {
+            math::bisect(balance, 0.0, lower, upper)
+        } else {
+            p
+        };
+        (self.inverse(p), self.inverse(p + level))
+    }
+
+    /// Compute the natural logarithm of the probability density function.
+    #[inline(always)]
+    fn ln_density(&self, x: f64) -> f64 {
+        self.density(x).ln()
+    }
+
+    /// Compute the log-likelihood of a sample, that is, the sum of the
+    /// natural logarithms of the probability density function at the data.
+    ///
+    /// The default implementation uses compensated summation, which keeps the
+    /// error from growing with the number of data.
+    fn log_likelihood(&self, data: &[f64]) -> f64 {
+        use math;
+        math::sum(data.iter().map(|&x| self.ln_density(x)))
+    }
+
+    /// Compute the mean residual life, that is, the expected value of `X - x`
+    /// given that `X > x`.
+    ///
+    /// The integral of the survival function is computed using the composite
+    /// Simpson rule up to the point where the survival function drops below
+    /// `1e-12` of its value at `x`. The mean residual life is zero when the
+    /// survival function vanishes at `x`.
+    fn mean_residual_life(&self, x: f64) -> f64 where Self: Inverse<Value=f64> {
+        use math;
+        let survival = self.ln_survival(x);
+        if survival == ::std::f64::NEG_INFINITY {
+            return 0.0;
+        }
+        let upper = self.inverse_survival((survival + (1e-12f64).ln()).exp());
+        if upper <= x {
+            return 0.0;
+        }
+        math::simpson(|t| (self.ln_survival(t) - survival).exp(), x, upper, 10000)
+    }
+
+    /// Compute the raw moment of order `k`.
+    ///
+    /// The default implementation integrates in the same way as the one of
+    /// `central_moment`.
+    #[cfg(feature = "alloc")]
+    fn moment(&self, k: u32) -> f64 where Self: Inverse<Value=f64> {
+        integrate(self, |x| x.powi(k as i32))
+    }
 }
 
 /// A discrete distribution.
+///
+/// The cumulative distribution function accepts real arguments and is a
+/// right-continuous step function: at `x`, it gives the probability of the
+/// outcomes not exceeding `x`. If the distribution implements `Inverse`, the
+/// inverse returns the smallest outcome `k` in the support such that
+/// `distribution(k) >= p`, with `distribution` evaluated exactly as above.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// fn surprise<D: Discrete>(d: &D, k: D::Value) -> f64 {
+///     -d.ln_mass(k)
+/// }
+///
+/// let d = Binomial::new(10, 0.5);
+/// assert!((surprise(&d, 10) - 10.0 * 2f64.ln()).abs() < 1e-12);
+/// assert_eq!(d.inverse(d.distribution(4.0)), 4);
+/// ```
 pub trait Discrete: Distribution {
     /// Compute the probability mass function.
     fn mass(&self, Self::Value) -> f64;
+
+    /// Compute the natural logarithm of the probability mass function.
+    #[inline(always)]
+    fn ln_mass(&self, x: Self::Value) -> f64 {
+        self.mass(x).ln()
+    }
+
+    /// Compute the log-likelihood of a sample, that is, the sum of the
+    /// natural logarithms of the probability mass function at the data.
+    ///
+    /// The default implementation uses compensated summation, which keeps the
+    /// error from growing with the number of data.
+    fn log_likelihood(&self, data: &[Self::Value]) -> f64 where Self::Value: Clone {
+        use math;
+        math::sum(data.iter().map(|x| self.ln_mass(x.clone())))
+    }
+
+    /// Evaluate the probability mass function at the consecutive outcomes
+    /// covering all but `1e-9` of the probability mass.
+    ///
+    /// The outcomes run from the quantile of order `5e-10` to that of order
+    /// `1 - 5e-10`.
+    #[cfg(feature = "alloc")]
+    fn mass_grid(&self) -> Vec<(usize, f64)> where Self: Inverse<Value=usize> {
+        const TAIL: f64 = 5e-10;
+        (self.inverse(TAIL)..(self.inverse(1.0 - TAIL) + 1)).map(|k| (k, self.mass(k))).collect()
+    }
 }
 
 /// A distribution.
@@ -21,6 +241,253 @@ pub trait Distribution {
 
     /// Compute the cumulative distribution function.
     fn distribution(&self, f64) -> f64;
+
+    /// Compute the cumulative distribution function at each point of `xs` and
+    /// write the results to `out`.
+    ///
+    /// The slices should have the same length; the function panics otherwise.
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        conform(xs.len(), out.len());
+        for (y, &x) in out.iter_mut().zip(xs) {
+            *y = self.distribution(x);
+        }
+    }
+
+    /// Compute the cumulative distribution function at each point of `xs`.
+    #[cfg(feature = "alloc")]
+    fn distribution_vec(&self, xs: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; xs.len()];
+        self.distribution_slice(xs, &mut out);
+        out
+    }
+
+    /// Evaluate the cumulative distribution function on a grid of `n` evenly
+    /// spaced points covering the bulk of the distribution.
+    ///
+    /// The grid is chosen as in `Continuous::density_grid`.
+    #[cfg(feature = "alloc")]
+    fn distribution_grid(&self, n: usize) -> Vec<(f64, f64)>
+        where Self: Inverse<Value=f64> + Sized
+    {
+        let (a, b) = span(self, 1e-3);
+        self.distribution_grid_between(a, b, n)
+    }
+
+    /// Evaluate the cumulative distribution function on a grid of `n` evenly
+    /// spaced points from `a` to `b`.
+    ///
+    /// It should hold that `a < b`, both finite, and `n > 1`.
+    #[cfg(feature = "alloc")]
+    fn distribution_grid_between(&self, a: f64, b: f64, n: usize) -> Vec<(f64, f64)> {
+        grid(a, b, n, (0, 0)).into_iter().map(|x| (x, self.distribution(x))).collect()
+    }
+
+    /// Compute the cumulative hazard function, that is, the negated natural
+    /// logarithm of the survival function.
+    #[inline(always)]
+    fn cumulative_hazard(&self, x: f64) -> f64 {
+        -self.ln_survival(x)
+    }
+
+    /// Approximate the inverse of the cumulative distribution function at `p`
+    /// by the Cornish–Fisher expansion with the first four moments.
+    ///
+    /// An error is returned if the expansion is not valid at `p`; see
+    /// `approx::cornish_fisher_quantile`.
+    fn inverse_cornish_fisher(&self, p: f64) -> Result<f64, ParameterError>
+        where Self: Kurtosis + Sized
+    {
+        use approx;
+        approx::cornish_fisher_quantile(self.mean(), self.variance().sqrt(), self.skewness(),
+                                        self.kurtosis(), p)
+    }
+
+    /// Compute the natural logarithm of the cumulative distribution function.
+    #[inline(always)]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        self.distribution(x).ln()
+    }
+
+    /// Compute the probability of interval `(a, b]`.
+    ///
+    /// The probability is zero when `b <= a`. The default implementation
+    /// subtracts the values of the survival function when `a` lies above the
+    /// median and those of the cumulative distribution function otherwise,
+    /// which avoids cancellation when both endpoints are in the right tail.
+    #[inline]
+    fn probability(&self, a: f64, b: f64) -> f64 {
+        if b <= a {
+            return 0.0;
+        }
+        let p = self.distribution(a);
+        if p > 0.5 {
+            self.survival(a) - self.survival(b)
+        } else {
+            self.distribution(b) - p
+        }
+    }
+
+    /// Return the support, that is, the smallest closed interval containing
+    /// all outcomes with positive density or mass.
+    ///
+    /// The endpoints can be infinite. The default implementation returns the
+    /// whole real line.
+    #[inline(always)]
+    fn support(&self) -> (f64, f64) {
+        (::std::f64::NEG_INFINITY, ::std::f64::INFINITY)
+    }
+
+    /// Compute the survival function, that is, the complement of the
+    /// cumulative distribution function.
+    #[inline(always)]
+    fn survival(&self, x: f64) -> f64 {
+        1.0 - self.distribution(x)
+    }
+
+    /// Compute the natural logarithm of the survival function.
+    #[inline(always)]
+    fn ln_survival(&self, x: f64) -> f64 {
+        self.survival(x).ln()
+    }
+}
+
+/// A distribution capable of computing the Kullback–Leibler divergence.
+///
+/// The type parameter is the type of the other distribution. Numerical
+/// fallbacks for pairs without a closed form are provided by module
+/// `divergence`.
+pub trait Divergence<T: ?Sized = Self>: Distribution {
+    /// Compute the Kullback–Leibler divergence of `other` from the
+    /// distribution.
+    ///
+    /// The divergence is computed in nats. It is infinite when `other`
+    /// vanishes where the distribution does not.
+    fn kl_divergence(&self, other: &T) -> f64;
+
+    /// Compute the cross-entropy of `other` relative to the distribution.
+    ///
+    /// The cross-entropy is computed in nats as the sum of the entropy of the
+    /// distribution and the divergence of `other` from it.
+    #[inline]
+    fn cross_entropy(&self, other: &T) -> f64 where Self: Entropy {
+        self.entropy() + self.kl_divergence(other)
+    }
+}
+
+/// A distribution fitted to data along with the standard errors of the
+/// estimates of its parameters.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fitted<D> {
+    /// The fitted distribution.
+    pub distribution: D,
+    /// The standard errors of the estimates of the parameters in the order of
+    /// the constructor, computed from the observed information.
+    pub standard_errors: Vec<f64>,
+}
+
+/// A distribution fitted to data along with the standard errors of the
+/// estimates of its parameters and their confidence intervals.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FitWithCi<D> {
+    /// The fitted distribution.
+    pub distribution: D,
+    /// The standard errors of the estimates of the parameters in the order of
+    /// `Parametric::parameters`.
+    pub standard_errors: Vec<f64>,
+    /// The Wald confidence intervals of the parameters in the same order.
+    pub intervals: Vec<(f64, f64)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<D> FitWithCi<D> where D: Parametric {
+    /// Compute the standard errors and the Wald confidence intervals at level
+    /// `level` of the parameters of `distribution` given the information
+    /// matrix `information` of the data, which is stored in the row-major
+    /// order.
+    ///
+    /// The standard errors are the square roots of the diagonal of the
+    /// inverse of the information matrix. The intervals of the parameters
+    /// that are constrained to be positive are computed on the logarithmic
+    /// scale and transformed back, which keeps them positive.
+    pub fn from_information(distribution: D, information: &[f64], level: f64)
+                            -> Result<Self, FitError> {
+        use math;
+
+        ensure!(0.0 < level && level < 1.0,
+                FitError::Parameter(ParameterError::ProbabilityOutOfRange { value: level }));
+        let parameters = distribution.parameters();
+        let n = parameters.len();
+        should!(information.len() == n * n && D::POSITIVE.len() == n);
+        let covariance = match math::invert(information) {
+            Some(covariance) => covariance,
+            _ => return Err(FitError::InformationSingular),
+        };
+        let z = Gaussian::standard().inverse(0.5 + level / 2.0);
+        let mut standard_errors = Vec::with_capacity(n);
+        let mut intervals = Vec::with_capacity(n);
+        for (i, &value) in parameters.iter().enumerate() {
+            let error = covariance[i * n + i].sqrt();
+            ensure!(error.is_finite(), FitError::InformationSingular);
+            intervals.push(if D::POSITIVE[i] {
+                let factor = (z * error / value).exp();
+                (value / factor, value * factor)
+            } else {
+                (value - z * error, value + z * error)
+            });
+            standard_errors.push(error);
+        }
+        Ok(FitWithCi {
+            distribution: distribution,
+            standard_errors: standard_errors,
+            intervals: intervals,
+        })
+    }
+}
+
+/// Compute the observed information matrix of `data`, that is, the negated
+/// Hessian matrix of the log-likelihood with respect to the parameters.
+///
+/// The derivatives are approximated by central differences with steps of
+/// `1e-4` relative to the parameters, and the matrix is stored in the
+/// row-major order. The function is applicable to distributions without the
+/// Fisher information; see `FitWithCi::from_information`.
+#[cfg(feature = "alloc")]
+pub fn observed_information<D>(distribution: &D, data: &[f64]) -> Vec<f64>
+    where D: Continuous + Parametric
+{
+    const STEP: f64 = 1e-4;
+
+    let parameters = distribution.parameters();
+    let n = parameters.len();
+    let steps = parameters.iter().map(|&p| if p == 0.0 { STEP } else { STEP * p.abs() })
+                                 .collect::<Vec<_>>();
+    let log_likelihood = |shifts: &[(usize, f64)]| {
+        let mut parameters = parameters.clone();
+        for &(i, sign) in shifts {
+            parameters[i] += sign * steps[i];
+        }
+        match distribution.with_parameters(&parameters) {
+            Ok(distribution) => distribution.log_likelihood(data),
+            _ => ::std::f64::NAN,
+        }
+    };
+    let center = log_likelihood(&[]);
+    let mut information = vec![0.0; n * n];
+    for i in 0..n {
+        information[i * n + i] = -(log_likelihood(&[(i, 1.0)]) - 2.0 * center +
+                                   log_likelihood(&[(i, -1.0)])) / (steps[i] * steps[i]);
+        for j in 0..i {
+            let value = -(log_likelihood(&[(i, 1.0), (j, 1.0)]) -
+                          log_likelihood(&[(i, 1.0), (j, -1.0)]) -
+                          log_likelihood(&[(i, -1.0), (j, 1.0)]) +
+                          log_likelihood(&[(i, -1.0), (j, -1.0)])) / (4.0 * steps[i] * steps[j]);
+            information[i * n + j] = value;
+            information[j * n + i] = value;
+        }
+    }
+    information
 }
 
 /// A distribution capable of computing the differential entropy.
@@ -29,18 +496,208 @@ pub trait Entropy: Distribution {
     ///
     /// The entropy is computed in nats.
     fn entropy(&self) -> f64;
+
+    /// Compute the differential entropy in bits.
+    #[inline]
+    fn entropy_bits(&self) -> f64 {
+        self.entropy() / ::std::f64::consts::LN_2
+    }
+}
+
+/// A distribution capable of computing the Fisher information.
+#[cfg(feature = "alloc")]
+pub trait FisherInformation: Distribution {
+    /// Compute the Fisher information matrix of a single observation.
+    ///
+    /// The rows and columns correspond to the parameters in the order
+    /// documented by the implementation, and the matrix is stored in the
+    /// row-major order. Parameters that are not estimated, such as the number
+    /// of trials of a binomial distribution, are excluded.
+    fn fisher_information(&self) -> Vec<f64>;
 }
 
 /// A distribution capable of inverting the distribution function.
 pub trait Inverse: Distribution {
     /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The function panics if the argument is not in `[0, 1]`. For discrete
+    /// distributions, the result is the smallest outcome whose
+    /// cumulative probability is at least the argument; see `Discrete`.
     fn inverse(&self, f64) -> Self::Value;
+
+    /// Compute the inverse of the cumulative distribution function at each
+    /// probability of `ps` and write the results to `out`.
+    ///
+    /// The slices should have the same length; the function panics otherwise.
+    fn inverse_slice(&self, ps: &[f64], out: &mut [Self::Value]) {
+        conform(ps.len(), out.len());
+        for (x, &p) in out.iter_mut().zip(ps) {
+            *x = self.inverse(p);
+        }
+    }
+
+    /// Compute the inverse of the cumulative distribution function at each
+    /// probability of `ps`.
+    #[cfg(feature = "alloc")]
+    fn inverse_vec(&self, ps: &[f64]) -> Vec<Self::Value> where Self::Value: Clone + Default {
+        let mut out = vec![Default::default(); ps.len()];
+        self.inverse_slice(ps, &mut out);
+        out
+    }
+
+    /// Compute the inverse of the survival function.
+    #[inline(always)]
+    fn inverse_survival(&self, p: f64) -> Self::Value {
+        self.inverse(1.0 - p)
+    }
+}
+
+/// Compute the inverse of the cumulative distribution function numerically.
+///
+/// The quantile is sought by Brent’s method within `bracket`, which is
+/// clamped to the support and expanded by doubling steps until it contains the
+/// quantile; infinite endpoints are replaced with finite ones, so the support
+/// itself is a valid bracket. The distribution function is assumed to be
+/// nondecreasing; where it is flat at level `p`, any point of the flat part
+/// can be returned. The search stops once the quantile is bracketed by an
+/// interval narrower than `tolerance` plus four units of the relative machine
+/// precision.
+///
+/// The function panics if `p` is not in `[0, 1]`, and it returns the
+/// endpoints of the support for `p = 0` and `p = 1`. It returns `None` if
+/// the bracket cannot be expanded to contain the quantile or if the search
+/// does not converge.
+pub fn invert<D>(distribution: &D, p: f64, bracket: (f64, f64), tolerance: f64) -> Option<f64>
+    where D: Distribution + ?Sized
+{
+    use math;
+
+    guard!(inverse(p));
+    should!(bracket.0 <= bracket.1 && tolerance > 0.0);
+    let (lower, upper) = distribution.support();
+    if p == 0.0 {
+        return Some(lower);
+    }
+    if p == 1.0 {
+        return Some(upper);
+    }
+    let (mut a, mut b) = bracket;
+    if !a.is_finite() {
+        a = if b.is_finite() { b - 1.0 } else { -1.0 };
+    }
+    if !b.is_finite() {
+        b = a + 2.0;
+    }
+    let (mut a, mut b) = (a.max(lower).min(upper), b.max(lower).min(upper));
+    let f = |x| distribution.distribution(x) - p;
+    let (mut fa, mut fb) = (f(a), f(b));
+    let mut step = (b - a).max(1.0);
+    while fa > 0.0 {
+        if a <= lower {
+            return Some(lower);
+        }
+        b = a;
+        fb = fa;
+        a = (a - step).max(lower);
+        if !a.is_finite() {
+            return None;
+        }
+        fa = f(a);
+        step *= 2.0;
+    }
+    while fb < 0.0 {
+        if b >= upper {
+            return Some(upper);
+        }
+        a = b;
+        fa = fb;
+        b = (b + step).min(upper);
+        if !b.is_finite() {
+            return None;
+        }
+        fb = f(b);
+        step *= 2.0;
+    }
+    if fa.is_nan() || fb.is_nan() {
+        return None;
+    }
+    math::brent(f, a, b, tolerance)
 }
 
 /// A distribution capable of computing the excess kurtosis.
+///
+/// The excess kurtosis is the fourth standardized central moment minus three,
+/// which makes it zero for the Gaussian distribution. The implementations
+/// provide `kurtosis`, which follows this convention; `kurtosis_excess` and
+/// `kurtosis_raw` state the convention explicitly. A kurtosis that does not
+/// exist is infinite when the fourth moment diverges and NaN when the variance
+/// is infinite or undefined.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let d = Exponential::new(2.0);
+/// assert_eq!(d.kurtosis_excess(), 6.0);
+/// assert_eq!(d.kurtosis_raw(), 9.0);
+/// ```
 pub trait Kurtosis: Skewness {
     /// Compute the excess kurtosis.
     fn kurtosis(&self) -> f64;
+
+    /// Compute the excess kurtosis, that is, the fourth standardized central
+    /// moment minus three.
+    ///
+    /// The result is the one of `kurtosis`.
+    #[inline(always)]
+    fn kurtosis_excess(&self) -> f64 {
+        self.kurtosis()
+    }
+
+    /// Compute the raw kurtosis, that is, the fourth standardized central
+    /// moment.
+    ///
+    /// The result is the excess kurtosis plus three.
+    #[inline(always)]
+    fn kurtosis_raw(&self) -> f64 {
+        self.kurtosis_excess() + 3.0
+    }
+}
+
+/// A distribution capable of estimating its parameters by maximum likelihood.
+///
+/// The estimates are computed in closed form. The data are of the type of the
+/// outcomes, which makes them integers for discrete distributions.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let d = Exponential::fit_mle(&[0.5, 1.0, 1.5]).unwrap();
+/// assert_eq!(d.lambda(), 1.0);
+/// assert_eq!(Exponential::fit_mle(&[]), Err(FitError::DataEmpty));
+/// ```
+pub trait MaximumLikelihood: Distribution + Sized {
+    /// Fit the distribution to `data`.
+    fn fit_mle(data: &[Self::Value]) -> Result<Self, FitError>;
+
+    /// Fit the distribution to `data` along with the confidence intervals of
+    /// the parameters at level `level`.
+    ///
+    /// The information matrix of the data is the Fisher information of the
+    /// fitted distribution scaled by the number of data, which coincides with
+    /// the observed information for the distributions of the crate. See
+    /// `FitWithCi::from_information` for the intervals and
+    /// `observed_information` for distributions without the Fisher
+    /// information.
+    #[cfg(feature = "alloc")]
+    fn fit_mle_with_ci(data: &[Self::Value], level: f64) -> Result<FitWithCi<Self>, FitError>
+        where Self: FisherInformation + Parametric
+    {
+        let distribution = Self::fit_mle(data)?;
+        let n = data.len() as f64;
+        let information = distribution.fisher_information().iter().map(|&i| n * i)
+                                                                  .collect::<Vec<_>>();
+        FitWithCi::from_information(distribution, &information, level)
+    }
 }
 
 /// A distribution capable of computing the expected value.
@@ -59,21 +716,197 @@ pub trait Median: Distribution {
     fn median(&self) -> f64;
 }
 
+/// A distribution capable of estimating its parameters by the method of
+/// moments.
+///
+/// The parameters are computed in closed form from the mean and the variance,
+/// which is helpful when the moments are accumulated elsewhere, for instance,
+/// over a stream of data.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let d = Gamma::fit_moments_from(2.0, 0.5).unwrap();
+/// assert_eq!((d.k(), d.theta()), (8.0, 0.25));
+/// assert_eq!(Gamma::fit_moments_from(-2.0, 0.5),
+///            Err(FitError::MomentsInfeasible { mean: -2.0, variance: 0.5 }));
+/// ```
+pub trait MethodOfMoments: Distribution + Sized {
+    /// Fit the distribution to `data`.
+    ///
+    /// The variance of the data is computed with denominator `n`, that is,
+    /// without the correction for bias. Only the moments of the data are
+    /// taken into account, and the data are not checked to lie within the
+    /// support.
+    fn fit_moments(data: &[f64]) -> Result<Self, FitError> {
+        examine(data)?;
+        let mean = average(data, None, |x| x);
+        Self::fit_moments_from(mean, average(data, None, |x| (x - mean) * (x - mean)))
+    }
+
+    /// Fit the distribution to mean `mean` and variance `variance`.
+    fn fit_moments_from(mean: f64, variance: f64) -> Result<Self, FitError>;
+
+    /// Fit the distribution to the data accumulated by `stats`.
+    ///
+    /// The variance is taken with denominator `n` as in `fit_moments`.
+    fn fit_moments_online(stats: &OnlineStats) -> Result<Self, FitError> {
+        ensure!(stats.count() > 0, FitError::DataEmpty);
+        Self::fit_moments_from(stats.mean(), stats.population_variance())
+    }
+}
+
+/// A distribution capable of computing the moment-generating function.
+///
+/// The functions work with the cumulant generating function
+/// `K(t) = ln E[exp(t X)]`, which is convex and infinite where the
+/// expectation diverges. The domain is the interval where `K` is finite; its
+/// endpoints may or may not belong to it, and it reduces to zero for
+/// distributions whose moment-generating function does not exist. The
+/// default derivatives are computed by central differences refined by the
+/// Richardson extrapolation with a step proportional to `max(1, |t|)` or to
+/// the distance to the nearest endpoint of the domain, whichever is smaller.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let d = Gamma::new(3.0, 2.0);
+/// assert_eq!(d.ln_mgf_derivative(0.0), d.mean());
+/// assert_eq!(d.ln_mgf_second_derivative(0.0), d.variance());
+/// assert_eq!(d.mgf(0.5), std::f64::INFINITY);
+/// ```
+pub trait Mgf: Distribution {
+    /// Compute the cumulant generating function.
+    fn ln_mgf(&self, t: f64) -> f64;
+
+    /// Compute the first derivative of the cumulant generating function.
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        let h = step(self, t);
+        let difference = |h: f64| (self.ln_mgf(t + h) - self.ln_mgf(t - h)) / (2.0 * h);
+        (4.0 * difference(h / 2.0) - difference(h)) / 3.0
+    }
+
+    /// Compute the second derivative of the cumulant generating function.
+    fn ln_mgf_second_derivative(&self, t: f64) -> f64 {
+        let (h, center) = (step(self, t), self.ln_mgf(t));
+        let difference = |h: f64| {
+            (self.ln_mgf(t + h) - 2.0 * center + self.ln_mgf(t - h)) / (h * h)
+        };
+        (4.0 * difference(h / 2.0) - difference(h)) / 3.0
+    }
+
+    /// Compute the moment-generating function.
+    #[inline]
+    fn mgf(&self, t: f64) -> f64 {
+        self.ln_mgf(t).exp()
+    }
+
+    /// Return the endpoints of the domain of the cumulant generating function.
+    #[inline]
+    fn mgf_domain(&self) -> (f64, f64) {
+        (::std::f64::NEG_INFINITY, ::std::f64::INFINITY)
+    }
+
+    /// Check if the distribution is supported on the integers.
+    ///
+    /// The saddlepoint approximations of `approx` apply the continuity
+    /// correction to such distributions.
+    #[inline]
+    fn lattice(&self) -> bool {
+        false
+    }
+}
+
 /// A distribution capable of computing the modes.
 ///
-/// The trait is applicable when the number of modes is finite.
+/// The trait is applicable when the number of modes is finite; for instance,
+/// it is not implemented for `Uniform`, every point of whose support is a mode.
+#[cfg(feature = "alloc")]
 pub trait Modes: Distribution {
     /// Compute the modes.
-    fn modes(&self) -> Vec<Self::Value>;
+    ///
+    /// The modes are sorted in ascending order, and there are none when the
+    /// density or mass function does not attain its supremum.
+    fn modes(&self) -> ModeList<Self::Value>;
+}
+
+/// A distribution with parameters that can be estimated.
+#[cfg(feature = "alloc")]
+pub trait Parametric: Distribution + Sized {
+    /// The flags telling which parameters are constrained to be positive.
+    const POSITIVE: &'static [bool];
+
+    /// Return the parameters that can be estimated in the order of the Fisher
+    /// information matrix.
+    fn parameters(&self) -> Vec<f64>;
+
+    /// Create a distribution with the parameters that can be estimated
+    /// replaced by `parameters` and the others kept.
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError>;
 }
 
 /// A distribution capable of drawing samples.
 pub trait Sample: Distribution {
     /// Draw a sample.
     fn sample<S>(&self, &mut S) -> Self::Value where S: Source;
+
+    /// Draw as many samples as there are elements in `out` in parallel and
+    /// write them to `out`.
+    ///
+    /// The output is split into chunks of a fixed size, and each chunk is
+    /// filled using its own source, which is forked from a source seeded with
+    /// `seed`. Consequently, the samples depend only on `seed` and not on the
+    /// number of threads.
+    #[cfg(feature = "rayon")]
+    fn par_sample_into(&self, seed: u64, out: &mut [Self::Value])
+        where Self: Sync, Self::Value: Send
+    {
+        use rayon::prelude::*;
+
+        let sources = chunk(seed, out.len());
+        out.par_chunks_mut(CHUNK).zip(sources).for_each(|(out, mut source)| {
+            self.sample_into(&mut source, out);
+        });
+    }
+
+    /// Draw `n` samples in parallel.
+    ///
+    /// The samples are the same as those written by `par_sample_into` given
+    /// the same seed.
+    #[cfg(feature = "rayon")]
+    fn par_samples(&self, seed: u64, n: usize) -> Vec<Self::Value>
+        where Self: Sync, Self::Value: Send
+    {
+        use rayon::prelude::*;
+
+        chunk(seed, n).into_par_iter().enumerate().flat_map_iter(|(i, mut source)| {
+            let m = CHUNK.min(n - i * CHUNK);
+            (0..m).map(move |_| self.sample(&mut source))
+        }).collect()
+    }
+
+    /// Draw as many samples as there are elements in `out` and write them to
+    /// `out`.
+    ///
+    /// The samples and the state of the source afterwards are the same as
+    /// those after drawing the samples one by one using `sample`.
+    fn sample_into<S>(&self, source: &mut S, out: &mut [Self::Value]) where S: Source {
+        for x in out.iter_mut() {
+            *x = self.sample(source);
+        }
+    }
+
+    /// Draw `n` samples.
+    #[cfg(feature = "alloc")]
+    fn samples<S>(&self, source: &mut S, n: usize) -> Vec<Self::Value> where S: Source {
+        (0..n).map(|_| self.sample(source)).collect()
+    }
 }
 
 /// A distribution capable of computing the skewness.
+///
+/// The skewness is the third standardized central moment. A skewness that
+/// does not exist is NaN.
 pub trait Skewness: Variance {
     /// Compute the skewness.
     fn skewness(&self) -> f64;
@@ -93,20 +926,1061 @@ pub trait Variance: Mean {
     }
 }
 
+/// A multivariate distribution.
+pub trait Multivariate {
+    /// The type of outcomes.
+    type Value;
+
+    /// Return the number of dimensions.
+    fn dimension(&self) -> usize;
+}
+
+/// A multivariate continuous distribution.
+pub trait MultivariateContinuous: Multivariate {
+    /// Compute the natural logarithm of the probability density function.
+    fn ln_density(&self, x: &Self::Value) -> f64;
+}
+
+/// A multivariate distribution capable of computing the expected value.
+#[cfg(feature = "alloc")]
+pub trait MultivariateMean: Multivariate {
+    /// Compute the vector of expected values.
+    fn mean_vector(&self) -> Vec<f64>;
+}
+
+/// A multivariate distribution capable of drawing samples.
+pub trait MultivariateSample: Multivariate {
+    /// Draw a sample.
+    fn sample<S>(&self, source: &mut S) -> Self::Value where S: Source;
+}
+
+/// A multivariate distribution capable of computing the covariance.
+#[cfg(feature = "alloc")]
+pub trait MultivariateVariance: MultivariateMean {
+    /// Compute the covariance matrix.
+    ///
+    /// The matrix is stored in the row-major order.
+    fn covariance(&self) -> Vec<f64>;
+}
+
+impl<'l, D> Distribution for &'l D where D: Distribution + ?Sized {
+    type Value = D::Value;
+
+    #[inline(always)]
+    fn distribution(&self, x: f64) -> f64 {
+        (**self).distribution(x)
+    }
+
+    #[inline(always)]
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        (**self).distribution_slice(xs, out)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    fn distribution_vec(&self, xs: &[f64]) -> Vec<f64> {
+        (**self).distribution_vec(xs)
+    }
+
+    #[inline(always)]
+    fn cumulative_hazard(&self, x: f64) -> f64 {
+        (**self).cumulative_hazard(x)
+    }
+
+    #[inline(always)]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        (**self).ln_distribution(x)
+    }
+
+    #[inline(always)]
+    fn probability(&self, a: f64, b: f64) -> f64 {
+        (**self).probability(a, b)
+    }
+
+    #[inline(always)]
+    fn support(&self) -> (f64, f64) {
+        (**self).support()
+    }
+
+    #[inline(always)]
+    fn survival(&self, x: f64) -> f64 {
+        (**self).survival(x)
+    }
+
+    #[inline(always)]
+    fn ln_survival(&self, x: f64) -> f64 {
+        (**self).ln_survival(x)
+    }
+}
+
+impl<'l, D> Inverse for &'l D where D: Inverse + ?Sized {
+    #[inline(always)]
+    fn inverse(&self, p: f64) -> Self::Value {
+        (**self).inverse(p)
+    }
+
+    #[inline(always)]
+    fn inverse_slice(&self, ps: &[f64], out: &mut [Self::Value]) {
+        (**self).inverse_slice(ps, out)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    fn inverse_vec(&self, ps: &[f64]) -> Vec<Self::Value> where Self::Value: Clone + Default {
+        (**self).inverse_vec(ps)
+    }
+
+    #[inline(always)]
+    fn inverse_survival(&self, p: f64) -> Self::Value {
+        (**self).inverse_survival(p)
+    }
+}
+
+impl<'l, D> Sample for &'l D where D: Sample + ?Sized {
+    #[inline(always)]
+    fn sample<S>(&self, source: &mut S) -> Self::Value where S: Source {
+        (**self).sample(source)
+    }
+
+    #[inline(always)]
+    fn sample_into<S>(&self, source: &mut S, out: &mut [Self::Value]) where S: Source {
+        (**self).sample_into(source, out)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    fn samples<S>(&self, source: &mut S, n: usize) -> Vec<Self::Value> where S: Source {
+        (**self).samples(source, n)
+    }
+}
+
+#[macro_use]
+mod affine;
+
 mod bernoulli;
 mod beta;
+mod beta_binomial;
 mod binomial;
+mod cached;
+#[cfg(feature = "alloc")]
 mod categorical;
+mod censored;
+mod compound;
+#[cfg(feature = "alloc")]
+mod compound_poisson;
+#[cfg(feature = "alloc")]
+mod dirichlet;
+#[cfg(feature = "alloc")]
+mod empirical;
 mod exponential;
 mod gamma;
-mod gaussian;
+mod gaussian_inverse_gamma;
+#[cfg(feature = "alloc")]
+mod histogram;
+#[cfg(feature = "alloc")]
+mod joint;
+#[cfg(feature = "alloc")]
+mod kde;
+#[cfg(feature = "alloc")]
+mod mixture;
+#[cfg(feature = "alloc")]
+mod modes;
+mod negative_binomial;
+mod order;
+mod point_mass;
+mod poisson;
+mod student;
+mod sum;
 mod uniform;
+mod ziggurat;
+
+#[cfg(feature = "alloc")]
+pub mod distance;
+pub mod divergence;
+pub mod gaussian;
 
+#[cfg(feature = "serde")]
+mod serialization;
+
+pub use self::affine::Affine;
 pub use self::bernoulli::Bernoulli;
 pub use self::beta::Beta;
+pub use self::beta_binomial::BetaBinomial;
 pub use self::binomial::Binomial;
+pub use self::cached::Cached;
+#[cfg(feature = "alloc")]
 pub use self::categorical::Categorical;
+pub use self::censored::{Censored, Censoring};
+pub use self::compound::Compound;
+#[cfg(feature = "alloc")]
+pub use self::compound_poisson::{CompoundPoisson, discretize};
+#[cfg(feature = "alloc")]
+pub use self::dirichlet::Dirichlet;
+#[cfg(feature = "alloc")]
+pub use self::empirical::{Empirical, Interpolation};
 pub use self::exponential::Exponential;
 pub use self::gamma::Gamma;
-pub use self::gaussian::Gaussian;
+pub use self::gaussian::{Gaussian, StandardGaussian};
+pub use self::gaussian_inverse_gamma::GaussianInverseGamma;
+#[cfg(feature = "alloc")]
+pub use self::histogram::Histogram;
+#[cfg(feature = "alloc")]
+pub use self::joint::Joint;
+#[cfg(feature = "alloc")]
+pub use self::kde::Kde;
+#[cfg(feature = "alloc")]
+pub use self::mixture::{EmOptions, EmReport, Mixture};
+#[cfg(feature = "alloc")]
+pub use self::modes::ModeList;
+pub use self::negative_binomial::NegativeBinomial;
+pub use self::order::OrderStatistic;
+pub use self::point_mass::PointMass;
+pub use self::poisson::Poisson;
+pub use self::student::StudentT;
+pub use self::sum::Sum;
 pub use self::uniform::Uniform;
+
+// Check that an output slice has the same length as the corresponding input
+// slice.
+#[inline]
+fn conform(input: usize, output: usize) {
+    assert!(input == output,
+            "the output should have the same length as the input (got {} and {})", output, input);
+}
+
+fn average<F>(data: &[f64], weights: Option<&[f64]>, f: F) -> f64 where F: Fn(f64) -> f64 {
+    use math;
+    match weights {
+        Some(weights) => {
+            math::sum(data.iter().zip(weights).map(|(&x, &w)| w * f(x))) /
+                math::sum(weights.iter().cloned())
+        },
+        _ => math::sum(data.iter().map(|&x| f(x))) / data.len() as f64,
+    }
+}
+
+fn examine(data: &[f64]) -> Result<(), FitError> {
+    ensure!(!data.is_empty(), FitError::DataEmpty);
+    match data.iter().find(|x| !x.is_finite()) {
+        Some(&value) => Err(FitError::DatumNotFinite { value: value }),
+        _ => Ok(()),
+    }
+}
+
+fn examine_weights(data: &[f64], weights: Option<&[f64]>) -> Result<(), FitError> {
+    examine(data)?;
+    if let Some(weights) = weights {
+        ensure!(data.len() == weights.len(),
+                FitError::WeightsMismatch { data: data.len(), weights: weights.len() });
+        if let Some(&value) = weights.iter().find(|&&w| !(w >= 0.0 && w.is_finite())) {
+            return Err(FitError::WeightNotNonnegative { value: value });
+        }
+        ensure!(weights.iter().any(|&w| w > 0.0), FitError::WeightsZero);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+const CHUNK: usize = 1 << 14;
+
+// Create a source for each chunk of `n` samples drawn in parallel by forking
+// a source seeded with `seed`.
+#[cfg(feature = "rayon")]
+fn chunk(seed: u64, n: usize) -> Vec<::source::Xoshiro256PlusPlus> {
+    use source;
+    let mut source = source::seeded(seed);
+    (0..(n + CHUNK - 1) / CHUNK).map(|_| source.fork()).collect()
+}
+
+// Check if the density grows without bound toward `x` from the side of `y` by
+// comparing it at two points approaching `x` by three orders of magnitude.
+#[cfg(feature = "alloc")]
+fn diverges<D>(d: &D, x: f64, y: f64) -> bool where D: Continuous + ?Sized {
+    const RATIO: f64 = 1.5;
+    let delta = 1e-6 * (y - x);
+    !d.density(x).is_finite() || d.density(x + delta) > RATIO * d.density(x + 1e3 * delta)
+}
+
+// Compute `n` evenly spaced points from `a` to `b` excluding the given numbers
+// of points at the two ends of a finer grid that includes them.
+#[cfg(feature = "alloc")]
+fn grid(a: f64, b: f64, n: usize, (left, right): (usize, usize)) -> Vec<f64> {
+    should!(a < b && a.is_finite() && b.is_finite() && n > 1);
+    let m = n + left + right - 1;
+    let step = (b - a) / m as f64;
+    (left..(left + n)).map(|i| if i == m { b } else { a + i as f64 * step }).collect()
+}
+
+// Check if the support of `p` is contained in that of `q`.
+#[inline]
+fn nested<P, Q>(p: &P, q: &Q) -> bool where P: Distribution + ?Sized, Q: Distribution + ?Sized {
+    let ((a, b), (c, d)) = (p.support(), q.support());
+    c <= a && b <= d
+}
+
+// Approximate the smallest outcome not exceeding `limit` at which the
+// distribution function of a discrete distribution is at least `p` given the
+// mean, standard deviation, and skewness. The Gaussian quantile is corrected
+// for the skewness by the Cornish–Fisher expansion and for the discreteness
+// by half an outcome.
+fn guess(p: f64, mean: f64, deviation: f64, skewness: f64, limit: usize) -> usize {
+    let z = gaussian::inverse(p);
+    let x = (mean + deviation * (z + skewness * (z * z - 1.0) / 6.0) - 0.5).ceil();
+    if !(x > 0.0) {
+        0
+    } else if x >= limit as f64 {
+        limit
+    } else {
+        x as usize
+    }
+}
+
+// Find the smallest outcome not exceeding `limit` at which the distribution
+// function of a discrete distribution is at least `p` starting from an
+// approximation `k`. Steps of doubling length are taken from `k` toward the
+// outcome until it is bracketed, and the bracket is then bisected, so the
+// number of evaluations of the distribution function is logarithmic in the
+// error of the approximation.
+fn search<D>(d: &D, p: f64, k: usize, limit: usize) -> usize where D: Distribution + ?Sized {
+    let below = |k: usize| d.distribution(k as f64) < p;
+    let k = k.min(limit);
+    let (mut a, mut b) = (k, k);
+    let mut step = 1;
+    if below(k) {
+        loop {
+            if b == limit {
+                return limit;
+            }
+            b = if limit - b > step { b + step } else { limit };
+            if !below(b) {
+                break;
+            }
+            a = b;
+            step *= 2;
+        }
+    } else {
+        loop {
+            if a == 0 {
+                return 0;
+            }
+            a = if a > step { a - step } else { 0 };
+            if below(a) {
+                break;
+            }
+            b = a;
+            step *= 2;
+        }
+    }
+    while b - a > 1 {
+        let m = a + (b - a) / 2;
+        if below(m) { a = m } else { b = m }
+    }
+    b
+}
+
+// Compute the expectation of `f(X)` by integrating the density function over
+// the support split at the endpoints of the interval returned by `range` and
+// at evenly spaced points in between.
+#[cfg(feature = "alloc")]
+fn integrate<D, F>(d: &D, f: F) -> f64
+    where D: Continuous + Inverse<Value=f64> + ?Sized, F: Fn(f64) -> f64
+{
+    use math;
+
+    const PIECES: usize = 16;
+    const TOLERANCE: f64 = 1e-12;
+
+    let g = |x| {
+        let density = d.density(x);
+        if density == 0.0 { 0.0 } else { f(x) * density }
+    };
+    let (lower, upper) = d.support();
+    let (a, b) = range(d);
+    let step = (b - a) / PIECES as f64;
+    let mut points = vec![lower];
+    points.extend((0..(PIECES + 1)).map(|i| if i == PIECES { b } else { a + i as f64 * step }));
+    points.push(upper);
+    math::sum(points.windows(2).filter(|pair| pair[0] < pair[1]).map(|pair| {
+        math::integrate(&g, pair[0], pair[1], TOLERANCE).value
+    }))
+}
+
+// Compute the interval containing all but a negligible part of the
+// probability mass. Infinite endpoints of the support are replaced with the
+// quantiles of order `1e-12` and `1 - 1e-12`.
+#[inline]
+fn range<D>(d: &D) -> (f64, f64) where D: Inverse<Value=f64> + ?Sized {
+    span(d, 1e-12)
+}
+
+// Compute the support with infinite endpoints replaced with the quantiles of
+// order `tail` and `1 - tail`.
+fn span<D>(d: &D, tail: f64) -> (f64, f64) where D: Inverse<Value=f64> + ?Sized {
+    let (mut lower, mut upper) = d.support();
+    if !lower.is_finite() {
+        lower = d.inverse(tail);
+    }
+    if !upper.is_finite() {
+        upper = d.inverse(1.0 - tail);
+    }
+    (lower, upper)
+}
+
+// Choose the step of the numerical derivatives of the cumulant generating
+// function at `t`, keeping the points within the domain.
+fn step<D>(distribution: &D, t: f64) -> f64 where D: Mgf + ?Sized {
+    let (a, b) = distribution.mgf_domain();
+    1e-3 * t.abs().max(1.0).min(t - a).min(b - t)
+}
+
+// Compute the moment of order `k` of `Y + shift` given the moments of `Y`
+// computed by `moment`.
+#[cfg(feature = "alloc")]
+fn translate<F>(moment: F, k: u32, shift: f64) -> f64 where F: Fn(u32) -> f64 {
+    let (mut sum, mut binomial) = (0.0, 1.0);
+    for j in 0..(k + 1) {
+        sum += binomial * moment(j) * shift.powi((k - j) as i32);
+        binomial = binomial * (k - j) as f64 / (j + 1) as f64;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use testing::Outcome;
+
+    macro_rules! support(
+        ($($name:ident: $check:ident($distribution:expr),)+) => (
+            mod support {
+                use prelude::*;
+                use super::{continuous, discrete};
+
+                $(
+                    #[test]
+                    fn $name() {
+                        $check(&$distribution);
+                    }
+                )+
+            }
+        );
+    );
+
+    support!(
+        affine: continuous(Affine::new(Exponential::new(2.0), -0.5, 1.0)),
+        bernoulli: discrete(Bernoulli::new(0.3)),
+        beta: continuous(Beta::new(2.0, 3.0, -1.0, 2.0)),
+        binomial: discrete(Binomial::new(10, 0.3)),
+        categorical: discrete(Categorical::new(&[0.2, 0.5, 0.3])),
+        censored: discrete(Censored::new(Exponential::new(2.0), 1.0, Censoring::Left)),
+        compound_poisson: discrete(CompoundPoisson::new(2.0, Exponential::new(1.0))),
+        empirical: discrete(Empirical::new(&[3.0, -1.0, 2.0, 2.0])),
+        exponential: continuous(Exponential::new(2.0)),
+        gamma: continuous(Gamma::new(3.0, 1.5)),
+        gaussian: continuous(Gaussian::new(1.0, 2.0)),
+        histogram: continuous(Histogram::new(&[0.0, 1.0, 3.0, 4.0], &[2, 0, 6])),
+        kde: continuous(Kde::new(&[0.0, 1.0, 3.0])),
+        mixture: continuous(Mixture::new(vec![Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0)],
+                                         vec![0.5, 0.5])),
+        order: continuous(OrderStatistic::new(Uniform::new(-1.0, 1.0), 5, 2)),
+        poisson: discrete(Poisson::new(3.5)),
+        sum: continuous(Sum::new(Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0))),
+        uniform: continuous(Uniform::new(-1.0, 1.0)),
+    );
+
+    macro_rules! limits(
+        ($($name:ident: $check:ident($distribution:expr),)+) => (
+            mod limits {
+                use prelude::*;
+                use super::{continuous_limits, discrete_limits, inverse_limits};
+
+                $(
+                    #[test]
+                    fn $name() {
+                        $check(&$distribution);
+                    }
+                )+
+            }
+        );
+    );
+
+    limits!(
+        affine: continuous_limits(Affine::new(Exponential::new(2.0), -0.5, 1.0)),
+        affine_inverse: inverse_limits(Affine::new(Exponential::new(2.0), -0.5, 1.0)),
+        bernoulli: discrete_limits(Bernoulli::new(0.3)),
+        bernoulli_inverse: inverse_limits(Bernoulli::new(0.3)),
+        beta: continuous_limits(Beta::new(2.0, 3.0, -1.0, 2.0)),
+        beta_inverse: inverse_limits(Beta::new(2.0, 3.0, -1.0, 2.0)),
+        binomial: discrete_limits(Binomial::new(10, 0.3)),
+        binomial_inverse: inverse_limits(Binomial::new(10, 0.3)),
+        categorical: discrete_limits(Categorical::new(&[0.2, 0.5, 0.3])),
+        categorical_inverse: inverse_limits(Categorical::new(&[0.2, 0.5, 0.3])),
+        censored: continuous_limits(Censored::new(Exponential::new(2.0), 1.0, Censoring::Left)),
+        censored_inverse: inverse_limits(Censored::new(Exponential::new(2.0), 1.0,
+                                                       Censoring::Right)),
+        compound: continuous_limits(Compound::new(Gamma::new(2.0, 1.0),
+                                                  |rate| Exponential::new(rate))),
+        compound_inverse: inverse_limits(Compound::new(Gamma::new(2.0, 1.0),
+                                                       |rate| Exponential::new(rate))),
+        compound_discrete: discrete_limits(Compound::new(Gamma::new(2.0, 1.0),
+                                                         |rate| Poisson::new(rate))),
+        compound_poisson: continuous_limits(CompoundPoisson::new(2.0, Exponential::new(1.0))),
+        compound_poisson_inverse: inverse_limits(CompoundPoisson::new(2.0,
+                                                                      Exponential::new(1.0))),
+        empirical: discrete_limits(Empirical::new(&[3.0, -1.0, 2.0, 2.0])),
+        empirical_inverse: inverse_limits(Empirical::new(&[3.0, -1.0, 2.0, 2.0])),
+        exponential: continuous_limits(Exponential::new(2.0)),
+        exponential_inverse: inverse_limits(Exponential::new(2.0)),
+        gamma: continuous_limits(Gamma::new(3.0, 1.5)),
+        gamma_inverse: inverse_limits(Gamma::new(3.0, 1.5)),
+        gaussian: continuous_limits(Gaussian::new(1.0, 2.0)),
+        gaussian_inverse: inverse_limits(Gaussian::new(1.0, 2.0)),
+        histogram: continuous_limits(Histogram::new(&[0.0, 1.0, 3.0, 4.0], &[2, 0, 6])),
+        histogram_inverse: inverse_limits(Histogram::new(&[0.0, 1.0, 3.0, 4.0], &[2, 0, 6])),
+        kde: continuous_limits(Kde::new(&[0.0, 1.0, 3.0])),
+        kde_inverse: inverse_limits(Kde::new(&[0.0, 1.0, 3.0])),
+        mixture: continuous_limits(Mixture::new(vec![Gaussian::new(0.0, 1.0),
+                                                     Gaussian::new(3.0, 1.0)],
+                                                vec![0.5, 0.5])),
+        mixture_inverse: inverse_limits(Mixture::new(vec![Gaussian::new(0.0, 1.0),
+                                                          Gaussian::new(3.0, 1.0)],
+                                                     vec![0.5, 0.5])),
+        order: continuous_limits(OrderStatistic::new(Gaussian::new(0.0, 1.0), 5, 2)),
+        order_inverse: inverse_limits(OrderStatistic::new(Gaussian::new(0.0, 1.0), 5, 2)),
+        poisson: discrete_limits(Poisson::new(3.5)),
+        poisson_inverse: inverse_limits(Poisson::new(3.5)),
+        standard_gaussian: continuous_limits(StandardGaussian),
+        standard_gaussian_inverse: inverse_limits(StandardGaussian),
+        sum: continuous_limits(Sum::new(Uniform::new(0.0, 1.0), Gaussian::new(2.0, 3.0))),
+        sum_inverse: inverse_limits(Sum::new(Uniform::new(0.0, 1.0), Gaussian::new(2.0, 3.0))),
+        uniform: continuous_limits(Uniform::new(-1.0, 1.0)),
+        uniform_inverse: inverse_limits(Uniform::new(-1.0, 1.0)),
+    );
+
+    macro_rules! entropy(
+        ($($name:ident: $check:ident($($argument:expr),+),)+) => (
+            mod entropy {
+                use prelude::*;
+                use super::{continuous_entropy, discrete_entropy};
+
+                $(
+                    #[test]
+                    fn $name() {
+                        $check($($argument),+);
+                    }
+                )+
+            }
+        );
+    );
+
+    entropy!(
+        affine: continuous_entropy(&Affine::new(Gamma::new(3.0, 1.5), -2.0, 1.0)),
+        bernoulli: discrete_entropy(&Bernoulli::new(0.3), 0..2),
+        beta: continuous_entropy(&Beta::new(2.0, 3.0, -1.0, 2.0)),
+        binomial: discrete_entropy(&Binomial::new(16, 0.25), 0..17),
+        categorical: discrete_entropy(&Categorical::new(&[0.2, 0.5, 0.3]), 0..3),
+        exponential: continuous_entropy(&Exponential::new(2.0)),
+        gamma: continuous_entropy(&Gamma::new(3.0, 1.5)),
+        gaussian: continuous_entropy(&Gaussian::new(1.0, 2.0)),
+        histogram: continuous_entropy(&Histogram::new(&[0.0, 1.0, 3.0, 4.0], &[2, 1, 6])),
+        poisson: discrete_entropy(&Poisson::new(3.5), 0..100),
+        standard_gaussian: continuous_entropy(&StandardGaussian),
+        uniform: continuous_entropy(&Uniform::new(-1.0, 3.0)),
+    );
+
+    macro_rules! moments(
+        ($($name:ident: $check:ident($($argument:expr),+),)+) => (
+            mod moments {
+                use std::f64::{INFINITY, NAN};
+
+                use prelude::*;
+                use super::{continuous_moments, discrete_moments, undefined_moments};
+
+                $(
+                    #[test]
+                    fn $name() {
+                        $check($($argument),+);
+                    }
+                )+
+            }
+        );
+    );
+
+    moments!(
+        affine: continuous_moments(&Affine::new(Gamma::new(3.0, 1.5), -2.0, 1.0)),
+        affine_reflected: continuous_moments(&Affine::new(Gamma::new(3.0, 1.5), -2.0, -1.0)),
+        bernoulli: discrete_moments(&Bernoulli::new(0.3), 0..2),
+        beta: continuous_moments(&Beta::new(2.0, 3.0, -1.0, 2.0)),
+        beta_skewed: continuous_moments(&Beta::new(5.0, 1.5, 0.0, 1.0)),
+        binomial: discrete_moments(&Binomial::new(16, 0.25), 0..17),
+        categorical: discrete_moments(&Categorical::new(&[0.2, 0.5, 0.3]), 0..3),
+        empirical: discrete_moments(&Empirical::new(&[3.0, -1.0, 2.0, 2.0]),
+                                    vec![-1.0, 2.0, 3.0].into_iter()),
+        exponential: continuous_moments(&Exponential::new(2.0)),
+        gamma: continuous_moments(&Gamma::new(3.0, 1.5)),
+        gaussian: continuous_moments(&Gaussian::new(1.0, 2.0)),
+        kde: continuous_moments(&Kde::new(&[0.0, 1.0, 3.0])),
+        negative_binomial: discrete_moments(&NegativeBinomial::new(2.5, 0.3), 0..1000),
+        point_mass: undefined_moments(&PointMass::new(1.5), NAN, NAN),
+        poisson: discrete_moments(&Poisson::new(3.5), 0..100),
+        standard_gaussian: continuous_moments(&StandardGaussian),
+        student: continuous_moments(&StudentT::new(10.0, 1.0, 2.0)),
+        student_cauchy: undefined_moments(&StudentT::new(1.0, 0.0, 1.0), NAN, NAN),
+        student_heavy: undefined_moments(&StudentT::new(3.5, 0.0, 1.0), 0.0, INFINITY),
+        student_light: undefined_moments(&StudentT::new(3.0, 0.0, 1.0), NAN, INFINITY),
+        uniform: continuous_moments(&Uniform::new(-1.0, 3.0)),
+    );
+
+    macro_rules! fisher(
+        ($($name:ident: ($distribution:expr, $dimension:expr),)+) => (
+            mod fisher {
+                use prelude::*;
+                use super::definite;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let matrix = $distribution.fisher_information();
+                        assert_eq!(matrix.len(), $dimension * $dimension);
+                        assert!(definite(&matrix, $dimension));
+                    }
+                )+
+            }
+        );
+    );
+
+    fisher!(
+        bernoulli: (Bernoulli::new(0.3), 1),
+        beta: (Beta::new(2.0, 3.0, -1.0, 2.0), 2),
+        beta_small: (Beta::new(0.1, 0.2, 0.0, 1.0), 2),
+        binomial: (Binomial::new(10, 0.3), 1),
+        exponential: (Exponential::new(2.0), 1),
+        gamma: (Gamma::new(3.0, 1.5), 2),
+        gamma_small: (Gamma::new(0.01, 100.0), 2),
+        gaussian: (Gaussian::new(1.0, 2.0), 2),
+        poisson: (Poisson::new(3.5), 1),
+    );
+
+    macro_rules! equality(
+        ($($name:ident: ($one:expr, $other:expr),)+) => (
+            mod equality {
+                use prelude::*;
+                use super::equal;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        equal($one, $other);
+                    }
+                )+
+            }
+        );
+    );
+
+    equality!(
+        affine: (Affine::new(Gaussian::new(0.0, 1.0), 2.0, 1.0),
+                 Affine::new(Gaussian::new(0.0, 1.0), 2.0, -1.0)),
+        bernoulli: (Bernoulli::new(0.3), Bernoulli::new(0.7)),
+        beta: (Beta::new(2.0, 3.0, -1.0, 2.0), Beta::new(2.0, 3.0, -1.0, 3.0)),
+        binomial: (Binomial::new(10, 0.3), Binomial::new(11, 0.3)),
+        categorical: (Categorical::new(&[0.2, 0.5, 0.3]), Categorical::new(&[0.5, 0.2, 0.3])),
+        censored: (Censored::new(Exponential::new(2.0), 1.0, Censoring::Left),
+                   Censored::new(Exponential::new(2.0), 1.0, Censoring::Right)),
+        compound_poisson: (CompoundPoisson::new(2.0, Exponential::new(1.0)),
+                           CompoundPoisson::new(2.0, Exponential::new(2.0))),
+        empirical: (Empirical::new(&[3.0, -1.0, 2.0]),
+                    Empirical::with_interpolation(&[3.0, -1.0, 2.0], Interpolation::Lower)),
+        exponential: (Exponential::new(2.0), Exponential::new(2.5)),
+        gamma: (Gamma::new(3.0, 1.5), Gamma::new(1.5, 3.0)),
+        gaussian: (Gaussian::new(1.0, 2.0), Gaussian::new(2.0, 1.0)),
+        histogram: (Histogram::new(&[0.0, 1.0, 3.0], &[2, 6]),
+                    Histogram::new(&[0.0, 1.0, 3.0], &[1, 3])),
+        joint: (Joint::new((Gaussian::new(0.0, 1.0), Poisson::new(1.0))),
+                Joint::new((Gaussian::new(0.0, 1.0), Poisson::new(2.0)))),
+        kde: (Kde::new(&[0.0, 1.0, 3.0]), Kde::with_bandwidth(&[0.0, 1.0, 3.0], 0.1)),
+        mixture: (Mixture::new(vec![Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0)],
+                               vec![0.5, 0.5]),
+                  Mixture::new(vec![Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0)],
+                               vec![0.25, 0.75])),
+        order: (OrderStatistic::new(Uniform::new(-1.0, 1.0), 5, 2),
+                OrderStatistic::new(Uniform::new(-1.0, 1.0), 5, 3)),
+        poisson: (Poisson::new(3.5), Poisson::new(4.5)),
+        sum: (Sum::new(Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0)),
+              Sum::with_nodes(Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0), 10)),
+        uniform: (Uniform::new(-1.0, 1.0), Uniform::new(-1.0, 2.0)),
+    );
+
+    fn continuous<D>(d: &D) where D: Continuous {
+        let (a, b) = d.support();
+        assert!(a < b);
+        assert::close(d.distribution(a), 0.0, 1e-12);
+        assert::close(d.distribution(b), 1.0, 1e-12);
+        if a.is_finite() {
+            assert_eq!(d.density(a - 1.0), 0.0);
+        }
+        if b.is_finite() {
+            assert_eq!(d.density(b + 1.0), 0.0);
+        }
+    }
+
+    // Check the values of the functions of a point at NaN and at the infinities.
+    fn continuous_limits<D>(d: &D) where D: Continuous {
+        use std::f64::{INFINITY, NAN, NEG_INFINITY};
+        discrete_limits(d);
+        assert!(d.density(NAN).is_nan() && d.ln_density(NAN).is_nan());
+        assert_eq!((d.density(NEG_INFINITY), d.density(INFINITY)), (0.0, 0.0));
+        assert_eq!((d.ln_density(NEG_INFINITY), d.ln_density(INFINITY)),
+                   (NEG_INFINITY, NEG_INFINITY));
+    }
+
+    // Check the entropy against the integral of `-p ln p` over the support.
+    fn continuous_entropy<D>(d: &D) where D: Continuous + Entropy + Inverse<Value=f64> {
+        use math;
+        let (a, b) = d.support();
+        let entropy = math::integrate(|x| {
+            let density = d.density(x);
+            if density > 0.0 { -density * density.ln() } else { 0.0 }
+        }, a, b, 1e-10).value;
+        assert::close(d.entropy(), entropy, 1e-6);
+        assert::close(d.entropy_bits(), entropy / ::std::f64::consts::LN_2, 1e-6);
+    }
+
+    // Check the skewness and the excess kurtosis against the standardized
+    // central moments computed by integrating the density function.
+    fn continuous_moments<D>(d: &D) where D: Continuous + Inverse<Value=f64> + Kurtosis {
+        use super::integrate;
+        let mean = integrate(d, |x| x);
+        let deviation = integrate(d, |x| (x - mean).powi(2)).sqrt();
+        let moment = |k| integrate(d, |x| ((x - mean) / deviation).powi(k));
+        standardized(d, moment(3), moment(4));
+    }
+
+    // Check if a symmetric matrix stored in the row-major order is positive
+    // definite by attempting the Cholesky decomposition.
+    fn definite(matrix: &[f64], n: usize) -> bool {
+        let mut lower = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..(i + 1) {
+                assert_eq!(matrix[i * n + j], matrix[j * n + i]);
+                let sum = (0..j).fold(matrix[i * n + j], |sum, k| {
+                    sum - lower[i * n + k] * lower[j * n + k]
+                });
+                if i == j {
+                    if !(sum > 0.0) {
+                        return false;
+                    }
+                    lower[i * n + i] = sum.sqrt();
+                } else {
+                    lower[i * n + j] = sum / lower[j * n + j];
+                }
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn density_grid() {
+        let check = |grid: &[(f64, f64)], n: usize| {
+            assert_eq!(grid.len(), n);
+            assert!(grid.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            assert!(grid.iter().all(|&(x, y)| x.is_finite() && y.is_finite()));
+        };
+
+        let d = Gaussian::new(1.0, 2.0);
+        let grid = d.density_grid(101);
+        check(&grid, 101);
+        assert::close(grid[0].0, d.inverse(0.001), 1e-14);
+        assert::close(grid[100].0, d.inverse(0.999), 1e-14);
+        assert::close(grid[50].0, 1.0, 1e-14);
+        assert_eq!(grid[50].1, d.density(grid[50].0));
+
+        let d = Beta::new(0.5, 0.5, 0.0, 1.0);
+        let grid = d.density_grid(9);
+        check(&grid, 9);
+        assert::close(grid[0].0, 0.1, 1e-15);
+        assert::close(grid[8].0, 0.9, 1e-15);
+
+        let d = Gamma::new(0.5, 1.0);
+        let grid = d.density_grid(10);
+        check(&grid, 10);
+        let step = d.inverse(0.999) / 10.0;
+        assert::close(grid[0].0, step, 1e-14);
+        assert_eq!(grid[9].0, d.inverse(0.999));
+
+        let d = Exponential::new(2.0);
+        let grid = d.density_grid(11);
+        check(&grid, 11);
+        assert_eq!((grid[0].0, grid[0].1), (0.0, 2.0));
+        assert_eq!(grid[10].0, d.inverse(0.999));
+
+        let grid = Uniform::new(-1.0, 3.0).density_grid_between(-2.0, 2.0, 5);
+        assert_eq!(grid, vec![(-2.0, 0.0), (-1.0, 0.25), (0.0, 0.25), (1.0, 0.25), (2.0, 0.25)]);
+    }
+
+    fn discrete<D>(d: &D) where D: Distribution {
+        let (a, b) = d.support();
+        assert!(a < b);
+        assert_eq!(d.distribution(a - 1.0), 0.0);
+        assert!(d.distribution(a) > 0.0);
+        assert_eq!(d.distribution(b), 1.0);
+    }
+
+    // Check the values of the functions of a point at NaN and at the infinities.
+    fn discrete_limits<D>(d: &D) where D: Distribution {
+        use std::f64::{INFINITY, NAN, NEG_INFINITY};
+        assert!(d.distribution(NAN).is_nan() && d.survival(NAN).is_nan());
+        assert!(d.ln_distribution(NAN).is_nan() && d.ln_survival(NAN).is_nan());
+        assert_eq!((d.distribution(NEG_INFINITY), d.distribution(INFINITY)), (0.0, 1.0));
+        assert_eq!((d.survival(NEG_INFINITY), d.survival(INFINITY)), (1.0, 0.0));
+        assert_eq!((d.ln_distribution(NEG_INFINITY), d.ln_distribution(INFINITY)),
+                   (NEG_INFINITY, 0.0));
+        assert_eq!((d.ln_survival(NEG_INFINITY), d.ln_survival(INFINITY)), (0.0, NEG_INFINITY));
+    }
+
+    #[test]
+    fn distribution_grid() {
+        let d = Uniform::new(-1.0, 3.0);
+        assert_eq!(d.distribution_grid(5),
+                   vec![(-1.0, 0.0), (0.0, 0.25), (1.0, 0.5), (2.0, 0.75), (3.0, 1.0)]);
+
+        let d = Gaussian::new(1.0, 2.0);
+        let grid = d.distribution_grid(3);
+        assert::close(grid[0].1, 0.001, 1e-14);
+        assert::close(grid[1].1, 0.5, 1e-14);
+        assert::close(grid[2].1, 0.999, 1e-14);
+
+        let grid = Poisson::new(3.0).distribution_grid_between(0.0, 2.0, 3);
+        assert::close(grid[2].1, 8.5 * (-3f64).exp(), 1e-14);
+    }
+
+    // Check the entropy against the sum of `-p ln p` over `outcomes`.
+    fn discrete_entropy<D, I>(d: &D, outcomes: I) where D: Discrete + Entropy,
+                                                        I: Iterator<Item=D::Value>
+    {
+        let entropy = outcomes.fold(0.0, |sum, k| {
+            let mass = d.mass(k);
+            if mass > 0.0 { sum - mass * mass.ln() } else { sum }
+        });
+        assert::close(d.entropy(), entropy, 1e-6);
+        assert::close(d.entropy_bits(), entropy / ::std::f64::consts::LN_2, 1e-6);
+    }
+
+    // Check the skewness and the excess kurtosis against the standardized
+    // central moments computed by summing over `outcomes`.
+    fn discrete_moments<D, I>(d: &D, outcomes: I) where D: Discrete + Kurtosis,
+                                                        D::Value: Outcome,
+                                                        I: Iterator<Item=D::Value>
+    {
+        use math;
+        let masses = outcomes.map(|k| (k.to_f64(), d.mass(k))).collect::<Vec<_>>();
+        let mean = math::sum(masses.iter().map(|&(x, mass)| x * mass));
+        let deviation = math::sum(masses.iter().map(|&(x, mass)| {
+            (x - mean).powi(2) * mass
+        })).sqrt();
+        let moment = |k| math::sum(masses.iter().map(|&(x, mass)| {
+            ((x - mean) / deviation).powi(k) * mass
+        }));
+        standardized(d, moment(3), moment(4));
+    }
+
+    fn equal<D>(one: D, other: D) where D: Clone + ::std::fmt::Debug + PartialEq {
+        assert_eq!(one.clone(), one);
+        assert_eq!(one, one.clone());
+        assert!(one != other);
+        assert_eq!(format!("{:?}", one), format!("{:?}", one.clone()));
+    }
+
+    // Check that the inverses panic when the probability is not in `[0, 1]`.
+    fn inverse_limits<D>(d: &D) where D: Inverse {
+        use std::panic::{self, AssertUnwindSafe};
+        for &p in &[::std::f64::NAN, -0.5, 1.5] {
+            assert!(panic::catch_unwind(AssertUnwindSafe(|| d.inverse(p))).is_err());
+            assert!(panic::catch_unwind(AssertUnwindSafe(|| d.inverse_survival(p))).is_err());
+        }
+    }
+
+    #[test]
+    fn invert() {
+        use distribution::invert;
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let ps = [1e-10, 1e-3, 0.1, 0.25, 0.5, 0.75, 0.9, 0.999];
+
+        let d = Gaussian::new(1.0, 2.0);
+        for &p in &ps {
+            assert::close(invert(&d, p, d.support(), 1e-15).unwrap(), d.inverse(p), 1e-12);
+            assert::close(invert(&d, p, (50.0, 60.0), 1e-15).unwrap(), d.inverse(p), 1e-12);
+        }
+        assert_eq!(invert(&d, 0.0, (0.0, 1.0), 1e-15), Some(NEG_INFINITY));
+        assert_eq!(invert(&d, 1.0, (0.0, 1.0), 1e-15), Some(INFINITY));
+
+        let d = Exponential::new(2.0);
+        for &p in &ps {
+            assert::close(invert(&d, p, d.support(), 1e-15).unwrap(), d.inverse(p), 1e-12);
+            assert::close(invert(&d, p, (-5.0, -1.0), 1e-15).unwrap(), d.inverse(p), 1e-12);
+        }
+        assert_eq!(invert(&d, 0.0, d.support(), 1e-15), Some(0.0));
+        assert_eq!(invert(&d, 1.0, d.support(), 1e-15), Some(INFINITY));
+
+        let d = Uniform::new(-1.0, 3.0);
+        assert_eq!(invert(&d, 0.0, (0.0, 0.0), 1e-15), Some(-1.0));
+        assert_eq!(invert(&d, 1.0, (0.0, 0.0), 1e-15), Some(3.0));
+        assert::close(invert(&d, 0.5, (2.5, 2.5), 1e-15).unwrap(), 1.0, 1e-15);
+
+        let d = Mixture::new(vec![Gaussian::new(-20.0, 0.1), Gaussian::new(20.0, 0.1)],
+                             vec![0.3, 0.7]);
+        for &p in &[0.01, 0.2, 0.3 - 1e-9, 0.3 + 1e-9, 0.5, 0.99] {
+            let x = invert(&d, p, (-1.0, 1.0), 1e-15).unwrap();
+            assert::close(d.distribution(x), p, 1e-14);
+        }
+        assert!(invert(&d, 0.3, (-1.0, 1.0), 1e-15).unwrap().abs() < 20.0);
+
+        struct Improper;
+
+        impl Distribution for Improper {
+            type Value = f64;
+
+            fn distribution(&self, _: f64) -> f64 {
+                0.25
+            }
+        }
+
+        assert_eq!(invert(&Improper, 0.5, (0.0, 1.0), 1e-15), None);
+    }
+
+    #[test]
+    fn mass_grid() {
+        let d = Poisson::new(3.0);
+        let grid = d.mass_grid();
+        let (first, last) = (grid[0].0, grid[grid.len() - 1].0);
+        assert_eq!(first, 0);
+        assert_eq!(grid.iter().map(|&(k, _)| k).collect::<Vec<_>>(),
+                   (first..(last + 1)).collect::<Vec<_>>());
+        assert!(d.survival(last as f64) <= 5e-10);
+        assert!(d.survival((last - 1) as f64) > 5e-10);
+        assert::close(grid.iter().map(|&(_, p)| p).sum::<f64>(), 1.0, 1e-9);
+        assert_eq!(grid[3].1, d.mass(3));
+
+        let d = Binomial::new(10, 0.5);
+        assert_eq!(d.mass_grid().len(), 11);
+    }
+
+    #[test]
+    fn mgf() {
+        use std::f64::INFINITY;
+
+        // A distribution with the default derivatives.
+        struct Numerical<'l, D: 'l>(&'l D);
+
+        impl<'l, D> Distribution for Numerical<'l, D> where D: Distribution {
+            type Value = D::Value;
+
+            fn distribution(&self, x: f64) -> f64 {
+                self.0.distribution(x)
+            }
+        }
+
+        impl<'l, D> Mgf for Numerical<'l, D> where D: Mgf {
+            fn ln_mgf(&self, t: f64) -> f64 {
+                self.0.ln_mgf(t)
+            }
+
+            fn mgf_domain(&self) -> (f64, f64) {
+                self.0.mgf_domain()
+            }
+        }
+
+        fn check<D>(d: &D, ts: &[f64]) where D: Mgf + Variance {
+            assert_eq!(d.ln_mgf(0.0), 0.0);
+            assert::close(d.ln_mgf_derivative(0.0), d.mean(), 1e-12);
+            assert::close(d.ln_mgf_second_derivative(0.0), d.variance(), 1e-12);
+            let e = Numerical(d);
+            for &t in ts {
+                assert::close(e.ln_mgf_derivative(t), d.ln_mgf_derivative(t),
+                              1e-8 * d.ln_mgf_derivative(t).abs().max(1.0));
+                assert::close(e.ln_mgf_second_derivative(t), d.ln_mgf_second_derivative(t),
+                              1e-6 * d.ln_mgf_second_derivative(t).abs().max(1.0));
+                assert::close(d.mgf(t), d.ln_mgf(t).exp(), 0.0);
+            }
+        }
+
+        check(&Bernoulli::new(0.3), &[-40.0, -1.0, 0.5, 3.0, 40.0]);
+        check(&Binomial::new(10, 0.3), &[-5.0, -0.1, 0.1, 5.0]);
+        check(&Exponential::new(2.0), &[-10.0, -1.0, 1.0, 1.99]);
+        check(&Gamma::new(3.0, 0.5), &[-10.0, 0.5, 1.9]);
+        check(&Gaussian::new(1.0, 2.0), &[-3.0, 0.5, 3.0]);
+        check(&Poisson::new(3.5), &[-5.0, 0.5, 5.0]);
+
+        let d = Uniform::new(1.0, 3.0);
+        assert::close(d.ln_mgf(1e-3), (((3e-3f64).exp() - 1e-3f64.exp()) / 2e-3).ln(), 1e-13);
+        assert::close(d.ln_mgf_derivative(0.0), d.mean(), 1e-9);
+        assert::close(d.ln_mgf_second_derivative(0.0), d.variance(), 1e-6);
+        assert::close(d.ln_mgf(-800.0), -800.0 - 1600f64.ln(), 1e-12);
+
+        assert_eq!(Exponential::new(2.0).ln_mgf(2.0), INFINITY);
+        assert_eq!(Gamma::new(3.0, 0.5).mgf_domain().1, 2.0);
+        assert_eq!(StudentT::new(3.0, 0.0, 1.0).ln_mgf(1e-9), INFINITY);
+        assert!(Poisson::new(1.0).lattice() && !Gaussian::new(0.0, 1.0).lattice());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_sample_into() {
+        use rayon::ThreadPoolBuilder;
+
+        let d = Gaussian::new(1.0, 2.0);
+        let n = 1_000_003;
+        let fill = |threads| {
+            let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let mut x = vec![0.0; n];
+            pool.install(|| d.par_sample_into(42, &mut x));
+            x
+        };
+        let x = fill(1);
+        assert!(x == fill(8));
+        assert!(x == d.par_samples(42, n));
+
+        let mean = x.iter().sum::<f64>() / n as f64;
+        let variance = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        assert::close(mean, 1.0, 0.01);
+        assert::close(variance, 4.0, 0.03);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_samples() {
+        use rayon::ThreadPoolBuilder;
+
+        let d = Poisson::new(3.5);
+        let n = 100_003;
+        let draw = |threads| {
+            let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| d.par_samples(42, n))
+        };
+        let x = draw(1);
+        assert_eq!(x, draw(8));
+        assert!(x != d.par_samples(69, n));
+
+        let mean = x.iter().sum::<usize>() as f64 / n as f64;
+        let variance = x.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        assert::close(mean, 3.5, 0.03);
+        assert::close(variance, 3.5, 0.1);
+    }
+
+    fn standardized<D>(d: &D, third: f64, fourth: f64) where D: Kurtosis {
+        assert::close(d.skewness(), third, 1e-6);
+        assert::close(d.kurtosis_excess(), fourth - 3.0, 1e-6);
+        assert::close(d.kurtosis_raw(), fourth, 1e-6);
+        assert_eq!(d.kurtosis(), d.kurtosis_excess());
+    }
+
+    // Check that the skewness and the excess kurtosis of a distribution whose
+    // moments do not exist are the documented values, with NaN matching NaN.
+    fn undefined_moments<D>(d: &D, skewness: f64, kurtosis: f64) where D: Kurtosis {
+        let same = |x: f64, y: f64| x == y || x.is_nan() && y.is_nan();
+        assert!(same(d.skewness(), skewness));
+        assert!(same(d.kurtosis_excess(), kurtosis));
+        assert!(same(d.kurtosis_raw(), kurtosis + 3.0));
+    }
+}
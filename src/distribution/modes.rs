@@ -0,0 +1,199 @@
+use std::iter::FromIterator;
+use std::{ops, slice};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The modes of a distribution.
+///
+/// The modes are sorted in ascending order. Up to two modes are stored inline,
+/// which spares an allocation in the common cases, and more are stored on the
+/// heap. The list dereferences to a slice.
+#[derive(Clone, Debug)]
+pub struct ModeList<T>(Storage<T>);
+
+#[derive(Clone, Debug)]
+enum Storage<T> {
+    None,
+    One([T; 1]),
+    Two([T; 2]),
+    Many(Vec<T>),
+}
+
+impl<T> ModeList<T> {
+    /// Create an empty list.
+    #[inline(always)]
+    pub fn new() -> Self {
+        ModeList(Storage::None)
+    }
+
+    /// Create a list with one mode.
+    #[inline(always)]
+    pub fn one(x: T) -> Self {
+        ModeList(Storage::One([x]))
+    }
+
+    /// Create a list with two modes.
+    ///
+    /// It should hold that `x < y`.
+    #[inline(always)]
+    pub fn two(x: T, y: T) -> Self {
+        ModeList(Storage::Two([x, y]))
+    }
+
+    /// Remove all the modes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0 = Storage::None;
+    }
+
+    /// Append a mode.
+    ///
+    /// The mode should not be smaller than the modes already in the list.
+    pub fn push(&mut self, x: T) {
+        self.0 = match ::std::mem::replace(&mut self.0, Storage::None) {
+            Storage::None => Storage::One([x]),
+            Storage::One([y]) => Storage::Two([y, x]),
+            Storage::Two([y, z]) => Storage::Many(vec![y, z, x]),
+            Storage::Many(mut modes) => {
+                modes.push(x);
+                Storage::Many(modes)
+            },
+        };
+    }
+
+    /// Convert the list into a vector.
+    pub fn into_vec(self) -> Vec<T> {
+        match self.0 {
+            Storage::None => vec![],
+            Storage::One([x]) => vec![x],
+            Storage::Two([x, y]) => vec![x, y],
+            Storage::Many(modes) => modes,
+        }
+    }
+}
+
+impl<T> Default for ModeList<T> {
+    #[inline]
+    fn default() -> Self {
+        ModeList::new()
+    }
+}
+
+impl<T> ops::Deref for ModeList<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        match self.0 {
+            Storage::None => &[],
+            Storage::One(ref modes) => modes,
+            Storage::Two(ref modes) => modes,
+            Storage::Many(ref modes) => modes,
+        }
+    }
+}
+
+impl<T> ops::DerefMut for ModeList<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self.0 {
+            Storage::None => &mut [],
+            Storage::One(ref mut modes) => modes,
+            Storage::Two(ref mut modes) => modes,
+            Storage::Many(ref mut modes) => modes,
+        }
+    }
+}
+
+impl<T> From<ModeList<T>> for Vec<T> {
+    #[inline]
+    fn from(modes: ModeList<T>) -> Self {
+        modes.into_vec()
+    }
+}
+
+impl<T> FromIterator<T> for ModeList<T> {
+    fn from_iter<I>(iterator: I) -> Self where I: IntoIterator<Item=T> {
+        let mut modes = ModeList::new();
+        for x in iterator {
+            modes.push(x);
+        }
+        modes
+    }
+}
+
+impl<'l, T> IntoIterator for &'l ModeList<T> {
+    type Item = &'l T;
+    type IntoIter = slice::Iter<'l, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> PartialEq for ModeList<T> where T: PartialEq {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T> PartialEq<Vec<T>> for ModeList<T> where T: PartialEq {
+    #[inline]
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'l, T> PartialEq<&'l [T]> for ModeList<T> where T: PartialEq {
+    #[inline]
+    fn eq(&self, other: &&'l [T]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distribution::ModeList;
+
+    #[test]
+    fn collect() {
+        assert_eq!((0..0).collect::<ModeList<usize>>(), vec![]);
+        assert_eq!((0..1).collect::<ModeList<_>>(), ModeList::one(0));
+        assert_eq!((0..2).collect::<ModeList<_>>(), ModeList::two(0, 1));
+        assert_eq!((0..5).collect::<ModeList<_>>(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn deref_mut() {
+        let mut modes = ModeList::two(1.0, 2.0);
+        for x in modes.iter_mut() {
+            *x = -*x;
+        }
+        modes.reverse();
+        assert_eq!(modes, vec![-2.0, -1.0]);
+    }
+
+    #[test]
+    fn into_vec() {
+        assert_eq!(ModeList::<u8>::new().into_vec(), Vec::<u8>::new());
+        assert_eq!(ModeList::one(1).into_vec(), vec![1]);
+        assert_eq!(Vec::from(ModeList::two(1, 2)), vec![1, 2]);
+        assert_eq!(vec![1, 2, 3].into_iter().collect::<ModeList<_>>().into_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn push() {
+        let mut modes = ModeList::new();
+        assert!(modes.is_empty());
+        modes.push(1);
+        assert_eq!(modes, vec![1]);
+        modes.push(2);
+        assert_eq!(modes, vec![1, 2]);
+        modes.push(3);
+        assert_eq!((modes.len(), modes.first(), modes.last()), (3, Some(&1), Some(&3)));
+        modes.clear();
+        assert_eq!(modes, vec![]);
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt;
+
+use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A negative binomial distribution.
+///
+/// The distribution is that of the number of failures before the `r`th
+/// success in a sequence of independent trials with success probability `p`.
+/// The number of successes need not be an integer, in which case the
+/// distribution is the gamma mixture of Poisson distributions with shape `r`
+/// and scale `(1 - p) / p`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NegativeBinomial {
+    r: f64,
+    p: f64,
+    ln_gamma: f64,
+}
+
+impl NegativeBinomial {
+    /// Create a negative binomial distribution with `r` successes and success
+    /// probability `p`.
+    ///
+    /// It should hold that `r > 0` and `0 < p < 1`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn new(r: f64, p: f64) -> Self {
+        unwrap!(NegativeBinomial::try_new(r, p))
+    }
+
+    /// Create a negative binomial distribution with `r` successes and success
+    /// probability `p` or return an error if the parameters are invalid.
+    pub fn try_new(r: f64, p: f64) -> Result<Self, ParameterError> {
+        use special::Gamma;
+        ensure!(r > 0.0 && r.is_finite(), ParameterError::RNotPositive { value: r });
+        ensure!(0.0 < p && p < 1.0, ParameterError::ProbabilityOutOfRange { value: p });
+        Ok(NegativeBinomial { r: r, p: p, ln_gamma: Gamma::ln_gamma(r).0 })
+    }
+
+    /// Return the number of successes.
+    #[inline(always)]
+    pub fn r(&self) -> f64 { self.r }
+
+    /// Return the success probability.
+    #[inline(always)]
+    pub fn p(&self) -> f64 { self.p }
+}
+
+impl fmt::Display for NegativeBinomial {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "NegativeBinomial(r = {}, p = {})", self.r, self.p)
+    }
+}
+
+impl distribution::Discrete for NegativeBinomial {
+    #[inline]
+    fn mass(&self, x: usize) -> f64 {
+        self.ln_mass(x).exp()
+    }
+
+    fn ln_mass(&self, x: usize) -> f64 {
+        use special::Gamma;
+        let x = x as f64;
+        Gamma::ln_gamma(x + self.r).0 - Gamma::ln_gamma(x + 1.0).0 - self.ln_gamma +
+            self.r * self.p.ln() + x * (-self.p).ln_1p()
+    }
+}
+
+impl distribution::Distribution for NegativeBinomial {
+    type Value = usize;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The implementation is based on the incomplete beta function.
+    fn distribution(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(distribution(x));
+        if x < 0.0 {
+            return 0.0;
+        }
+        special::beta_inc(self.r, x.floor() + 1.0, self.p)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, ::std::f64::INFINITY)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(survival(x));
+        if x < 0.0 {
+            return 1.0;
+        }
+        special::beta_inc(x.floor() + 1.0, self.r, 1.0 - self.p)
+    }
+}
+
+impl distribution::Inverse for NegativeBinomial {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The result is the smallest `k` such that `distribution(k) >= p`, which
+    /// is found by a search starting from the Cornish–Fisher approximation.
+    /// For `p = 1`, `usize::MAX` is returned.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::{guess, search, Mean, Skewness, Variance};
+        use std::usize::MAX;
+
+        guard!(inverse(p));
+
+        if p == 1.0 {
+            return MAX;
+        }
+        let k = guess(p, self.mean(), self.deviation(), self.skewness(), MAX);
+        search(self, p, k, MAX)
+    }
+}
+
+impl distribution::Kurtosis for NegativeBinomial {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        6.0 / self.r + self.p * self.p / (self.r * (1.0 - self.p))
+    }
+}
+
+impl distribution::Mean for NegativeBinomial {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.r * (1.0 - self.p) / self.p
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Modes for NegativeBinomial {
+    fn modes(&self) -> ModeList<usize> {
+        if self.r <= 1.0 {
+            return ModeList::one(0);
+        }
+        let x = (self.r - 1.0) * (1.0 - self.p) / self.p;
+        let k = x.floor();
+        if k == x && k > 0.0 {
+            ModeList::two(k as usize - 1, k as usize)
+        } else {
+            ModeList::one(k as usize)
+        }
+    }
+}
+
+impl distribution::Sample for NegativeBinomial {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> usize where S: Source {
+        use distribution::Inverse;
+        self.inverse(source.read::<f64>())
+    }
+}
+
+impl distribution::Skewness for NegativeBinomial {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        (2.0 - self.p) / (self.r * (1.0 - self.p)).sqrt()
+    }
+}
+
+impl distribution::Variance for NegativeBinomial {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.r * (1.0 - self.p) / (self.p * self.p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($r:expr, $p:expr) => (NegativeBinomial::new($r, $p));
+    );
+
+    #[test]
+    fn compound() {
+        let d = new!(2.5, 0.3);
+        let e = Compound::new(Gamma::new(2.5, 0.7 / 0.3), |lambda| Poisson::new(lambda));
+        for k in 0..10 {
+            assert::close(d.mass(k), e.mass(k), 1e-5);
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(new!(2.5, 0.25).to_string(), "NegativeBinomial(r = 2.5, p = 0.25)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(2.5, 0.3);
+        let x = vec![-1.0, 0.0, 2.5, 7.0, 20.0];
+        let p = vec![
+            0.0, 0.049295030175464950211, 0.2412375539211816001, 0.71350538199565621938,
+            0.99154657765871133909,
+        ];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-14);
+        assert::close(d.survival(30.0), 0.00039754756718490809913, 1e-17);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(2.5, 0.3);
+        for k in 0..20 {
+            let p = d.distribution(k as f64);
+            assert_eq!(d.inverse(p), k);
+            assert_eq!(d.inverse(p + 1e-12), k + 1);
+        }
+        assert_eq!(d.inverse(0.0), 0);
+        assert_eq!(d.inverse(1.0), ::std::usize::MAX);
+
+        for &(r, p) in &[(0.5, 0.2), (1.0, 0.5), (2.5, 0.3), (20.0, 0.9)] {
+            let d = new!(r, p);
+            for i in 1..1000 {
+                let p = i as f64 / 1000.0;
+                assert_eq!(d.inverse(p), (0..).find(|&k| d.distribution(k as f64) >= p).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(2.5, 0.3);
+        let p = vec![
+            0.049295030175464950211, 0.086266302807063662869, 0.10567622093865298702,
+            0.11096003198558563637, 0.106799030786126175, 0.097187118015374819252,
+            0.085038728263452966845,
+        ];
+        assert::close(&(0..7).map(|k| d.mass(k)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn moments() {
+        let d = new!(2.5, 0.25);
+        assert_eq!(d.mean(), 7.5);
+        assert_eq!(d.variance(), 30.0);
+        assert::close(d.skewness(), 1.75 / 1.875f64.sqrt(), 1e-15);
+        assert::close(d.kurtosis(), 2.4 + 0.0625 / 1.875, 1e-15);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(&new!(2.5, 0.3).modes()[..], &[3]);
+        assert_eq!(&new!(3.0, 0.5).modes()[..], &[1, 2]);
+        assert_eq!(&new!(0.5, 0.3).modes()[..], &[0]);
+    }
+
+    #[test]
+    fn sample() {
+        let x = new!(2.5, 0.3).samples(&mut source::seeded(42), 100000);
+        let mean = x.iter().sum::<usize>() as f64 / 100000.0;
+        assert!((mean - 2.5 * 0.7 / 0.3).abs() < 0.05);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(NegativeBinomial::try_new(0.0, 0.5).err(),
+                   Some(ParameterError::RNotPositive { value: 0.0 }));
+        assert_eq!(NegativeBinomial::try_new(1.0, 1.0).err(),
+                   Some(ParameterError::ProbabilityOutOfRange { value: 1.0 }));
+    }
+}
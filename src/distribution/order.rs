@@ -0,0 +1,262 @@
+use std::fmt;
+
+use distribution;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A distribution of an order statistic.
+///
+/// The distribution is that of the `k`th smallest of `n` independent draws
+/// from the inner distribution. If `F` is the distribution function of the
+/// inner distribution, the order statistic is distributed as `F⁻¹(U)` where
+/// `U` follows a beta distribution with shape parameters `k` and `n - k + 1`.
+/// The moments are computed by numerical integration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrderStatistic<T> {
+    inner: T,
+    n: usize,
+    k: usize,
+    ln_beta: f64,
+}
+
+impl<T> OrderStatistic<T> where T: distribution::Distribution {
+    /// Create the distribution of the `k`th smallest of `n` draws from
+    /// distribution `inner`.
+    ///
+    /// It should hold that `k >= 1` and `k <= n`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn new(inner: T, n: usize, k: usize) -> Self {
+        unwrap!(OrderStatistic::try_new(inner, n, k))
+    }
+
+    /// Create the distribution of the `k`th smallest of `n` draws from
+    /// distribution `inner` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_new(inner: T, n: usize, k: usize) -> Result<Self, ParameterError> {
+        use special::Beta;
+        ensure!(1 <= k && k <= n, ParameterError::RankOutOfRange { k: k, n: n });
+        let (alpha, beta) = (k as f64, (n - k + 1) as f64);
+        Ok(OrderStatistic { inner: inner, n: n, k: k, ln_beta: alpha.ln_beta(beta) })
+    }
+
+    /// Create the distribution of the largest of `n` draws from distribution
+    /// `inner`.
+    #[inline]
+    pub fn maximum(inner: T, n: usize) -> Self {
+        OrderStatistic::new(inner, n, n)
+    }
+
+    /// Create the distribution of the smallest of `n` draws from distribution
+    /// `inner`.
+    #[inline]
+    pub fn minimum(inner: T, n: usize) -> Self {
+        OrderStatistic::new(inner, n, 1)
+    }
+
+    /// Return the inner distribution.
+    #[inline(always)]
+    pub fn inner(&self) -> &T { &self.inner }
+
+    /// Return the number of draws.
+    #[inline(always)]
+    pub fn n(&self) -> usize { self.n }
+
+    /// Return the rank.
+    #[inline(always)]
+    pub fn k(&self) -> usize { self.k }
+
+    #[inline(always)]
+    fn alpha(&self) -> f64 { self.k as f64 }
+
+    #[inline(always)]
+    fn beta(&self) -> f64 { (self.n - self.k + 1) as f64 }
+}
+
+impl<T> fmt::Display for OrderStatistic<T> where T: fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "OrderStatistic({}, n = {}, k = {})", self.inner, self.n, self.k)
+    }
+}
+
+impl<T> distribution::Continuous for OrderStatistic<T> where T: distribution::Continuous {
+    fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        let density = self.inner.density(x);
+        if density == 0.0 {
+            return 0.0;
+        }
+        let p = self.inner.distribution(x);
+        p.powi(self.k as i32 - 1) * (1.0 - p).powi((self.n - self.k) as i32) *
+            (-self.ln_beta).exp() * density
+    }
+}
+
+impl<T> distribution::Distribution for OrderStatistic<T> where T: distribution::Distribution {
+    type Value = T::Value;
+
+    fn distribution(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(distribution(x));
+        let p = self.inner.distribution(x);
+        if p <= 0.0 {
+            0.0
+        } else if p >= 1.0 {
+            1.0
+        } else {
+            special::beta_inc(self.alpha(), self.beta(), p)
+        }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        self.inner.support()
+    }
+}
+
+impl<T> distribution::Inverse for OrderStatistic<T> where T: distribution::Inverse {
+    #[inline]
+    fn inverse(&self, p: f64) -> T::Value {
+        use math::special;
+        guard!(inverse(p));
+        self.inner.inverse(special::inv_beta_inc(self.alpha(), self.beta(), p))
+    }
+}
+
+impl<T> distribution::Mean for OrderStatistic<T>
+    where T: distribution::Continuous<Value=f64> + distribution::Inverse
+{
+    fn mean(&self) -> f64 {
+        use distribution::{Continuous, range};
+        use math;
+        let (a, b) = range(self);
+        math::simpson(|x| x * self.density(x), a, b, NODES)
+    }
+}
+
+impl<T> distribution::Sample for OrderStatistic<T> where T: distribution::Inverse {
+    /// Draw a sample.
+    ///
+    /// A sample is drawn from the corresponding beta distribution and then
+    /// mapped through the inverse of the distribution function of the inner
+    /// distribution.
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> T::Value where S: Source {
+        use distribution::gamma;
+        let x = gamma::sample(self.alpha(), source);
+        let y = gamma::sample(self.beta(), source);
+        self.inner.inverse(x / (x + y))
+    }
+}
+
+impl<T> distribution::Variance for OrderStatistic<T>
+    where T: distribution::Continuous<Value=f64> + distribution::Inverse
+{
+    fn variance(&self) -> f64 {
+        use distribution::{Continuous, Mean, range};
+        use math;
+        let mean = self.mean();
+        let (a, b) = range(self);
+        math::simpson(|x| (x - mean).powi(2) * self.density(x), a, b, NODES)
+    }
+}
+
+const NODES: usize = 10000;
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($inner:expr, $n:expr, $k:expr) => (OrderStatistic::new($inner, $n, $k));
+    );
+
+    #[test]
+    fn density() {
+        let d = OrderStatistic::maximum(Uniform::new(0.0, 1.0), 5);
+        let x = vec![-0.5f64, 0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0, 1.5];
+        let p = x.iter().map(|&x| if 0.0 <= x && x <= 1.0 { 5.0 * x.powi(4) } else { 0.0 })
+                        .collect::<Vec<_>>();
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-14);
+
+        let d = OrderStatistic::minimum(Exponential::new(2.0), 3);
+        let e = Exponential::new(6.0);
+        let x = vec![-1.0, 0.0, 0.1, 0.5, 1.0, 2.0];
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| e.density(x)).collect::<Vec<_>>(), 1e-14);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(OrderStatistic::new(Uniform::new(0.0, 1.0), 5, 2).to_string(),
+                   "OrderStatistic(Uniform(a = 0, b = 1), n = 5, k = 2)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(Uniform::new(0.0, 1.0), 7, 3);
+        let e = Beta::new(3.0, 5.0, 0.0, 1.0);
+        let x = vec![-0.5, 0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0, 1.5];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| e.distribution(x)).collect::<Vec<_>>(), 1e-14);
+
+        let d = OrderStatistic::minimum(Exponential::new(2.0), 3);
+        let e = Exponential::new(6.0);
+        let x = vec![-1.0, 0.0, 0.1, 0.5, 1.0, 2.0];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| e.distribution(x)).collect::<Vec<_>>(), 1e-14);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = OrderStatistic::maximum(Uniform::new(0.0, 1.0), 5);
+        let e = Beta::new(5.0, 1.0, 0.0, 1.0);
+        let p = vec![0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0];
+        assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| e.inverse(p)).collect::<Vec<_>>(), 1e-14);
+
+        let d = OrderStatistic::minimum(Exponential::new(2.0), 3);
+        let e = Exponential::new(6.0);
+        let p = vec![0.0, 0.1, 0.25, 0.5, 0.75, 0.9];
+        assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| e.inverse(p)).collect::<Vec<_>>(), 1e-14);
+    }
+
+    #[test]
+    fn mean() {
+        assert::close(OrderStatistic::maximum(Uniform::new(0.0, 1.0), 5).mean(), 5.0 / 6.0,
+                      1e-12);
+        assert::close(OrderStatistic::minimum(Exponential::new(2.0), 3).mean(), 1.0 / 6.0,
+                      1e-10);
+        assert::close(new!(Gaussian::new(0.0, 1.0), 3, 2).mean(), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn sample() {
+        let d = OrderStatistic::maximum(Uniform::new(0.0, 1.0), 5);
+        let x = Independent(&d, &mut source::seeded(42)).take(10000).collect::<Vec<_>>();
+        assert!(x.iter().all(|&x| 0.0 <= x && x <= 1.0));
+        assert!((x.iter().fold(0.0, |sum, &x| sum + x) / 10000.0 - 5.0 / 6.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn try_new() {
+        let inner = Uniform::new(0.0, 1.0);
+        assert!(OrderStatistic::try_new(inner, 5, 5).is_ok());
+        assert_eq!(OrderStatistic::try_new(inner, 5, 0).err(),
+                   Some(ParameterError::RankOutOfRange { k: 0, n: 5 }));
+        assert_eq!(OrderStatistic::try_new(inner, 5, 6).err(),
+                   Some(ParameterError::RankOutOfRange { k: 6, n: 5 }));
+    }
+
+    #[test]
+    fn variance() {
+        assert::close(OrderStatistic::maximum(Uniform::new(0.0, 1.0), 5).variance(),
+                      Beta::new(5.0, 1.0, 0.0, 1.0).variance(), 1e-12);
+        assert::close(OrderStatistic::minimum(Exponential::new(2.0), 3).variance(),
+                      Exponential::new(6.0).variance(), 1e-10);
+    }
+}
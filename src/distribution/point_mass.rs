@@ -0,0 +1,242 @@
+use std::fmt;
+
+use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::Float;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use float::Real;
+
+/// A point mass, that is, the distribution of a constant.
+///
+/// The distribution is treated as continuous with a density that is infinite
+/// at the point and zero elsewhere. The cumulative distribution function is
+/// zero below the point and one at the point and above it, and the inverse
+/// maps every probability to the point. The skewness and kurtosis are
+/// undefined and are NaN.
+///
+/// The degenerate members of the families that admit them, such as the
+/// Gaussian distribution with a zero standard deviation, behave as point
+/// masses except for the skewness and kurtosis, which are the limits within
+/// the family.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointMass {
+    value: f64,
+}
+
+impl PointMass {
+    /// Create a point mass at `value`.
+    ///
+    /// It should hold that `value` is finite; the function panics otherwise.
+    #[inline]
+    pub fn new(value: f64) -> Self {
+        unwrap!(PointMass::try_new(value))
+    }
+
+    /// Create a point mass at `value` or return an error if the parameter is
+    /// invalid.
+    #[inline]
+    pub fn try_new(value: f64) -> Result<Self, ParameterError> {
+        ensure!(value.is_finite(), ParameterError::ValueNotFinite { value: value });
+        Ok(PointMass { value: value })
+    }
+
+    /// Return the point.
+    #[inline(always)]
+    pub fn value(&self) -> f64 { self.value }
+}
+
+impl fmt::Display for PointMass {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "PointMass(value = {})", self.value)
+    }
+}
+
+impl distribution::Continuous for PointMass {
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        if x == self.value { ::std::f64::INFINITY } else { 0.0 }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 {
+        if k == 0 { 1.0 } else { 0.0 }
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        self.density(x).ln()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        self.value.powi(k as i32)
+    }
+}
+
+impl distribution::Distribution for PointMass {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
+        if x < self.value { 0.0 } else { 1.0 }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.value, self.value)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        guard!(survival(x));
+        if x < self.value { 1.0 } else { 0.0 }
+    }
+}
+
+impl distribution::Divergence for PointMass {
+    #[inline]
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        if self.value == other.value { 0.0 } else { ::std::f64::INFINITY }
+    }
+}
+
+impl distribution::Entropy for PointMass {
+    /// Compute the differential entropy, which is negative infinity.
+    #[inline]
+    fn entropy(&self) -> f64 {
+        ::std::f64::NEG_INFINITY
+    }
+}
+
+impl distribution::Inverse for PointMass {
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        self.value
+    }
+
+    #[inline]
+    fn inverse_survival(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        self.value
+    }
+}
+
+impl distribution::Kurtosis for PointMass {
+    #[inline]
+    fn kurtosis(&self) -> f64 { ::std::f64::NAN }
+}
+
+impl distribution::Mean for PointMass {
+    #[inline]
+    fn mean(&self) -> f64 { self.value }
+}
+
+impl distribution::Median for PointMass {
+    #[inline]
+    fn median(&self) -> f64 { self.value }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Modes for PointMass {
+    #[inline]
+    fn modes(&self) -> ModeList<f64> {
+        ModeList::one(self.value)
+    }
+}
+
+impl distribution::Sample for PointMass {
+    #[inline]
+    fn sample<S>(&self, _: &mut S) -> f64 where S: Source {
+        self.value
+    }
+}
+
+impl distribution::Skewness for PointMass {
+    #[inline]
+    fn skewness(&self) -> f64 { ::std::f64::NAN }
+}
+
+impl distribution::Variance for PointMass {
+    #[inline]
+    fn variance(&self) -> f64 { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::{INFINITY, NAN, NEG_INFINITY};
+
+    use prelude::*;
+    use ParameterError;
+
+    #[test]
+    fn density() {
+        let d = PointMass::new(1.5);
+        assert_eq!(d.density_vec(&[-INFINITY, 1.0, 1.5, 2.0, INFINITY]),
+                   vec![0.0, 0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!((d.ln_density(1.5), d.ln_density(2.0)), (INFINITY, NEG_INFINITY));
+        assert!(d.density(NAN).is_nan());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(PointMass::new(1.5).to_string(), "PointMass(value = 1.5)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = PointMass::new(1.5);
+        assert_eq!(d.distribution_vec(&[-INFINITY, 1.0, 1.5, 2.0, INFINITY]),
+                   vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(d.survival(1.0), 1.0);
+        assert_eq!(d.survival(1.5), 0.0);
+        assert_eq!(d.support(), (1.5, 1.5));
+    }
+
+    #[test]
+    fn inverse() {
+        let d = PointMass::new(1.5);
+        for &p in &[0.0, 0.25, 1.0] {
+            assert_eq!((d.inverse(p), d.inverse_survival(p)), (1.5, 1.5));
+        }
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let d = PointMass::new(1.5);
+        assert_eq!(d.kl_divergence(&d), 0.0);
+        assert_eq!(d.kl_divergence(&PointMass::new(2.0)), INFINITY);
+    }
+
+    #[test]
+    fn moments() {
+        let d = PointMass::new(-2.0);
+        assert_eq!((d.mean(), d.median(), d.variance(), d.deviation()), (-2.0, -2.0, 0.0, 0.0));
+        assert!(d.skewness().is_nan() && d.kurtosis().is_nan());
+        assert_eq!((d.moment(0), d.moment(3), d.central_moment(0), d.central_moment(2)),
+                   (1.0, -8.0, 1.0, 0.0));
+        assert_eq!(d.entropy(), NEG_INFINITY);
+        assert_eq!(d.modes(), vec![-2.0]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = PointMass::new(-2.0);
+        assert!(d.samples(&mut source::seeded(42), 10).iter().all(|&x| x == -2.0));
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(PointMass::try_new(INFINITY).err(),
+                   Some(ParameterError::ValueNotFinite { value: INFINITY }));
+        assert!(PointMass::try_new(NAN).is_err());
+    }
+}
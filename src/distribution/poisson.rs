@@ -0,0 +1,492 @@
+use std::{fmt, ops};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
+use source::Source;
+use {FitError, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A Poisson distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Poisson {
+    lambda: f64,
+}
+
+impl Poisson {
+    /// Create a Poisson distribution with rate `lambda`.
+    ///
+    /// It should hold that `lambda > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(lambda: f64) -> Self {
+        unwrap!(Poisson::try_new(lambda))
+    }
+
+    /// Create a Poisson distribution with rate `lambda` or return an error if
+    /// the parameter is invalid.
+    #[inline]
+    pub fn try_new(lambda: f64) -> Result<Self, ParameterError> {
+        ensure!(lambda > 0.0 && lambda.is_finite(),
+                ParameterError::LambdaNotPositive { value: lambda });
+        Ok(Poisson { lambda: lambda })
+    }
+
+    /// Return the rate parameter.
+    #[inline(always)]
+    pub fn lambda(&self) -> f64 { self.lambda }
+}
+
+impl ops::Add for Poisson {
+    type Output = Self;
+
+    /// Compute the distribution of the sum of two independent variables.
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Poisson::new(self.lambda + other.lambda)
+    }
+}
+
+impl fmt::Display for Poisson {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Poisson(λ = {})", self.lambda)
+    }
+}
+
+impl distribution::Discrete for Poisson {
+    #[inline]
+    fn mass(&self, x: usize) -> f64 {
+        self.ln_mass(x).exp()
+    }
+
+    fn ln_mass(&self, x: usize) -> f64 {
+        use special::Gamma;
+        let x = x as f64;
+        x * self.lambda.ln() - self.lambda - Gamma::ln_gamma(x + 1.0).0
+    }
+
+    /// Compute the log-likelihood of a sample.
+    ///
+    /// The log-likelihood depends on the parameter only via the number and sum
+    /// of the data. The remaining term, the sum of `ln(k!)`, is accumulated
+    /// from a table of the factorials up to the largest datum when the table
+    /// is shorter than the data.
+    #[cfg(feature = "alloc")]
+    fn log_likelihood(&self, data: &[usize]) -> f64 {
+        use math;
+        use special::Gamma;
+        let n = data.len();
+        let total = data.iter().fold(0.0, |sum, &k| sum + k as f64);
+        let largest = data.iter().cloned().max().unwrap_or(0);
+        let factorials = if largest < n {
+            let mut counts = vec![0usize; largest + 1];
+            for &k in data {
+                counts[k] += 1;
+            }
+            let mut factorial = 0.0;
+            math::sum(counts.iter().enumerate().map(|(k, &count)| {
+                if k > 1 {
+                    factorial += (k as f64).ln();
+                }
+                count as f64 * factorial
+            }))
+        } else {
+            math::sum(data.iter().map(|&k| Gamma::ln_gamma(k as f64 + 1.0).0))
+        };
+        total * self.lambda.ln() - n as f64 * self.lambda - factorials
+    }
+}
+
+impl distribution::Distribution for Poisson {
+    type Value = usize;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The implementation is based on the incomplete gamma function.
+    fn distribution(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(distribution(x));
+        if x < 0.0 {
+            return 0.0;
+        }
+        special::gamma_q(x.floor() + 1.0, self.lambda)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, ::std::f64::INFINITY)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use math::special;
+        guard!(survival(x));
+        if x < 0.0 {
+            return 1.0;
+        }
+        special::gamma_p(x.floor() + 1.0, self.lambda)
+    }
+}
+
+impl distribution::Divergence for Poisson {
+    #[inline]
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        self.lambda * (self.lambda / other.lambda).ln() + other.lambda - self.lambda
+    }
+}
+
+impl distribution::Entropy for Poisson {
+    fn entropy(&self) -> f64 {
+        use distribution::Discrete;
+        use std::f64::consts::{E, PI};
+
+        if self.lambda > 1e4 {
+            // Use an asymptotic expansion.
+            let lambda = self.lambda;
+            0.5 * (2.0 * PI * E * lambda).ln() - 1.0 / (12.0 * lambda) -
+                1.0 / (24.0 * lambda * lambda) - 19.0 / (360.0 * lambda * lambda * lambda)
+        } else {
+            let mut entropy = 0.0;
+            let mut x = 0;
+            loop {
+                let p = self.mass(x);
+                if p > 0.0 {
+                    entropy -= p * p.ln();
+                }
+                if x as f64 > self.lambda && p < 1e-17 {
+                    return entropy;
+                }
+                x += 1;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::FisherInformation for Poisson {
+    /// Compute the Fisher information matrix with respect to `λ`.
+    #[inline]
+    fn fisher_information(&self) -> Vec<f64> {
+        vec![1.0 / self.lambda]
+    }
+}
+
+impl distribution::Inverse for Poisson {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The result is the smallest `k` such that `distribution(k) >= p`, which
+    /// is found by a search starting from the Cornish–Fisher approximation and
+    /// takes a few evaluations of the distribution function regardless of the
+    /// rate. For `p = 1`, `usize::MAX` is returned.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::{guess, search};
+        use std::usize::MAX;
+
+        guard!(inverse(p));
+
+        if p == 1.0 {
+            return MAX;
+        }
+        let deviation = self.lambda.sqrt();
+        search(self, p, guess(p, self.lambda, deviation, 1.0 / deviation, MAX), MAX)
+    }
+}
+
+impl distribution::Kurtosis for Poisson {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.lambda.recip()
+    }
+}
+
+impl distribution::MaximumLikelihood for Poisson {
+    /// Fit the distribution to `data`.
+    ///
+    /// The rate is estimated by the mean of the data.
+    fn fit_mle(data: &[usize]) -> Result<Self, FitError> {
+        ensure!(!data.is_empty(), FitError::DataEmpty);
+        let sum = data.iter().fold(0.0, |sum, &k| sum + k as f64);
+        Ok(Poisson::try_new(sum / data.len() as f64)?)
+    }
+}
+
+impl distribution::Mean for Poisson {
+    #[inline]
+    fn mean(&self) -> f64 { self.lambda }
+}
+
+impl distribution::Mgf for Poisson {
+    #[inline]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        self.lambda * t.exp_m1()
+    }
+
+    #[inline]
+    fn ln_mgf_derivative(&self, t: f64) -> f64 {
+        self.lambda * t.exp()
+    }
+
+    #[inline]
+    fn ln_mgf_second_derivative(&self, t: f64) -> f64 {
+        self.lambda * t.exp()
+    }
+
+    #[inline(always)]
+    fn lattice(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Modes for Poisson {
+    fn modes(&self) -> ModeList<usize> {
+        let x = self.lambda.floor();
+        if x == self.lambda {
+            ModeList::two(x as usize - 1, x as usize)
+        } else {
+            ModeList::one(x as usize)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Parametric for Poisson {
+    const POSITIVE: &'static [bool] = &[true];
+
+    #[inline]
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    #[inline]
+    fn with_parameters(&self, parameters: &[f64]) -> Result<Self, ParameterError> {
+        Poisson::try_new(parameters[0])
+    }
+}
+
+impl distribution::Sample for Poisson {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> usize where S: Source {
+        use distribution::Inverse;
+        self.inverse(source.read::<f64>())
+    }
+}
+
+impl distribution::Skewness for Poisson {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.lambda.sqrt().recip()
+    }
+}
+
+impl distribution::Variance for Poisson {
+    #[inline]
+    fn variance(&self) -> f64 { self.lambda }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($lambda:expr) => (Poisson::new($lambda));
+    );
+
+    #[test]
+    fn add() {
+        let (a, b) = (new!(1.5), new!(2.0));
+        let d = a + b;
+        assert_eq!(d.lambda(), 3.5);
+        let p = (0..10).map(|x| {
+            (0..(x + 1)).fold(0.0, |sum, i| sum + a.mass(i) * b.mass(x - i))
+        }).collect::<Vec<_>>();
+        assert::close(&(0..10).map(|x| d.mass(x)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn cross_entropy() {
+        assert::close(new!(3.5).cross_entropy(&new!(2.0)), 2.4738277802869516831, 1e-14);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Poisson::new(3.5).to_string(), "Poisson(λ = 3.5)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(3.5);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0, 3.0, 4.0, 6.0, 10.0];
+        let p = vec![
+            0.000000000000000e+00, 3.019738342231850e-02, 3.019738342231850e-02,
+            1.358882254004332e-01, 3.208471988621341e-01, 5.366326679007850e-01,
+            7.254449533096046e-01, 9.347119029710463e-01, 9.989806055623830e-01,
+        ];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-14);
+    }
+
+    #[test]
+    fn entropy() {
+        assert::close(new!(3.5).entropy(), 2.015172522512972, 1e-14);
+        assert::close(new!(1e5).entropy(), 7.175400432352287, 1e-12);
+    }
+
+    #[test]
+    fn fisher_information() {
+        assert_eq!(new!(4.0).fisher_information(), vec![0.25]);
+    }
+
+    #[test]
+    fn fit_mle() {
+        assert_eq!(Poisson::fit_mle(&[1, 4, 0, 3]), Ok(new!(2.0)));
+
+        let n = 100000;
+        let x = new!(3.5).samples(&mut source::seeded(42), n);
+        let d = Poisson::fit_mle(&x).unwrap();
+        assert!((d.lambda() - 3.5).abs() < 5.0 * (3.5 / n as f64).sqrt());
+
+        assert_eq!(Poisson::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Poisson::fit_mle(&[0, 0]),
+                   Err(FitError::Parameter(ParameterError::LambdaNotPositive { value: 0.0 })));
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(3.5);
+        let p = vec![0.0, 0.01, 0.0301, 0.1, 0.5, 0.9, 0.99, 0.999];
+        let x = vec![0, 0, 0, 1, 3, 6, 8, 11];
+        assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
+
+        let d = new!(3.0);
+        for k in 0..10 {
+            let p = d.distribution(k as f64);
+            assert_eq!(d.inverse(p), k);
+            assert_eq!(d.inverse(p + 1e-12), k + 1);
+        }
+
+        for &lambda in &[0.1, 1.0, 7.5, 30.0] {
+            let d = new!(lambda);
+            for i in 1..1000 {
+                let p = i as f64 / 1000.0;
+                assert_eq!(d.inverse(p), (0..).find(|&k| d.distribution(k as f64) >= p).unwrap());
+            }
+        }
+
+        let d = new!(1e6);
+        let k = d.inverse(0.5);
+        assert!(d.distribution(k as f64) >= 0.5 && d.distribution(k as f64 - 1.0) < 0.5);
+        assert_eq!(d.inverse(1.0), ::std::usize::MAX);
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let p = new!(3.5);
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&new!(2.0)), 0.45865525777397940195, 1e-15);
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert_eq!(new!(2.0).kurtosis(), 0.5);
+    }
+
+    #[test]
+    fn ln_mass() {
+        let d = new!(3.5);
+        assert::close(&(0..10).map(|x| d.ln_mass(x)).collect::<Vec<_>>(),
+                      &(0..10).map(|x| d.mass(x).ln()).collect::<Vec<_>>(), 1e-14);
+
+        let d = new!(1000.0);
+        assert_eq!(d.mass(0), 0.0);
+        assert_eq!(d.ln_mass(0), -1000.0);
+        assert::close(d.ln_mass(5000), -4052.3671139660813089, 1e-10);
+    }
+
+    #[test]
+    fn log_likelihood() {
+        use math;
+
+        let d = new!(3.5);
+        let x = vec![0, 2, 7, 1000];
+        assert::close(d.log_likelihood(&x), x.iter().map(|&x| d.ln_mass(x)).sum::<f64>(), 1e-10);
+
+        let x = Independent(&new!(2.0), &mut source::seeded(42)).take(1_000_000)
+                                                               .collect::<Vec<_>>();
+        let e = math::sum(x.iter().map(|&x| d.ln_mass(x)));
+        assert::close(d.log_likelihood(&x) / e, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(3.5);
+        let p = vec![
+            3.019738342231850e-02, 1.056908419781147e-01, 1.849589734617008e-01,
+            2.157854690386510e-01, 1.888122854088196e-01, 1.321685997861737e-01,
+        ];
+        assert::close(&(0..6).map(|x| d.mass(x)).collect::<Vec<_>>(), &p, 1e-15);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(3.5).mean(), 3.5);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(3.5).modes(), vec![3]);
+        assert_eq!(new!(3.0).modes(), vec![2, 3]);
+    }
+
+    #[test]
+    fn sample() {
+        let sum = Independent(&new!(3.5), &mut source::seeded(42)).take(10000)
+                                                                   .fold(0, |a, b| a + b);
+        assert!((sum as f64 / 10000.0 - 3.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn sample_into() {
+        use source::Source;
+
+        let d = new!(3.5);
+        let mut one = source::Xorshift128Plus::new([42, 69]);
+        let mut other = source::Xorshift128Plus::new([42, 69]);
+        let mut x = vec![0; 1000];
+        d.sample_into(&mut one, &mut x);
+        assert_eq!(x, (0..1000).map(|_| d.sample(&mut other)).collect::<Vec<_>>());
+        assert_eq!(one.read::<u64>(), other.read::<u64>());
+    }
+
+    #[test]
+    fn skewness() {
+        assert_eq!(new!(4.0).skewness(), 0.5);
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(3.5);
+        let x = vec![-1.0, 0.0, 2.5, 4.0, 10.0];
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &x.iter().map(|&x| 1.0 - d.distribution(x)).collect::<Vec<_>>(), 1e-14);
+        assert_eq!(1.0 - d.distribution(30.0), 0.0);
+        assert::close(d.survival(30.0) / 3.0280745798206684767e-19, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Poisson::try_new(2.0).is_ok());
+        for &lambda in &[0.0, -1.0, INFINITY] {
+            assert_eq!(Poisson::try_new(lambda).err(),
+                       Some(ParameterError::LambdaNotPositive { value: lambda }));
+        }
+        assert!(Poisson::try_new(NAN).is_err());
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!(3.5).variance(), 3.5);
+    }
+}
@@ -0,0 +1,588 @@
+//! Serialization of distributions.
+//!
+//! A distribution is serialized as a structure of the arguments of its
+//! constructor. Deserialization goes through the corresponding fallible
+//! constructor, and invalid parameters are reported as errors. Compound
+//! distributions are not serializable as they contain closures.
+
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use distribution::{self, Affine, Bernoulli, Beta, BetaBinomial, Binomial, Categorical, Censored};
+use distribution::{Dirichlet, Empirical, Exponential, Gamma, Gaussian, GaussianInverseGamma};
+use distribution::{Histogram, Kde, Mixture, NegativeBinomial, OrderStatistic, PointMass, Poisson};
+use distribution::{StudentT, Sum, Uniform};
+
+mod parameters {
+    use distribution::{Censoring, Interpolation};
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Affine<T> {
+        pub inner: T,
+        pub scale: f64,
+        pub shift: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Bernoulli {
+        pub p: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Beta {
+        pub alpha: f64,
+        pub beta: f64,
+        pub a: f64,
+        pub b: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct BetaBinomial {
+        pub n: usize,
+        pub alpha: f64,
+        pub beta: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Binomial {
+        pub n: usize,
+        pub p: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Categorical<P> {
+        pub p: P,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Censored<T> {
+        pub inner: T,
+        pub limit: f64,
+        pub censoring: Censoring,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Dirichlet<A> {
+        pub alpha: A,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Empirical<S> {
+        pub samples: S,
+        pub interpolation: Interpolation,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Exponential {
+        pub lambda: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Gamma {
+        pub k: f64,
+        pub theta: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Gaussian {
+        pub mu: f64,
+        pub sigma: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct GaussianInverseGamma {
+        pub mu: f64,
+        pub lambda: f64,
+        pub alpha: f64,
+        pub beta: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Histogram<E, C> {
+        pub edges: E,
+        pub counts: C,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Kde<S> {
+        pub samples: S,
+        pub bandwidth: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Mixture<C, W> {
+        pub components: C,
+        pub weights: W,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct NegativeBinomial {
+        pub r: f64,
+        pub p: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct OrderStatistic<T> {
+        pub inner: T,
+        pub n: usize,
+        pub k: usize,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct PointMass {
+        pub value: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Poisson {
+        pub lambda: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct StudentT {
+        pub nu: f64,
+        pub mu: f64,
+        pub sigma: f64,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Sum<A, B> {
+        pub a: A,
+        pub b: B,
+        pub nodes: usize,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Uniform {
+        pub a: f64,
+        pub b: f64,
+    }
+}
+
+macro_rules! implement(
+    ($kind:ident, $($field:ident),+) => (
+        impl Serialize for $kind {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer
+            {
+                parameters::$kind { $($field: self.$field()),+ }.serialize(serializer)
+            }
+        }
+
+        impl<'l> Deserialize<'l> for $kind {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'l>
+            {
+                let parameters::$kind { $($field),+ } = Deserialize::deserialize(deserializer)?;
+                $kind::try_new($($field),+).map_err(D::Error::custom)
+            }
+        }
+    );
+);
+
+implement!(Bernoulli, p);
+implement!(Beta, alpha, beta, a, b);
+implement!(BetaBinomial, n, alpha, beta);
+implement!(Binomial, n, p);
+implement!(Exponential, lambda);
+implement!(Gamma, k, theta);
+implement!(Gaussian, mu, sigma);
+implement!(GaussianInverseGamma, mu, lambda, alpha, beta);
+implement!(NegativeBinomial, r, p);
+implement!(PointMass, value);
+implement!(Poisson, lambda);
+implement!(StudentT, nu, mu, sigma);
+implement!(Uniform, a, b);
+
+impl<T> Serialize for Affine<T> where T: distribution::Distribution<Value=f64> + Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Affine {
+            inner: self.inner(), scale: self.scale(), shift: self.shift(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'l, T> Deserialize<'l> for Affine<T>
+    where T: distribution::Distribution<Value=f64> + Deserialize<'l>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Affine { inner, scale, shift } =
+            parameters::Affine::<T>::deserialize(deserializer)?;
+        Affine::try_new(inner, scale, shift).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Categorical {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Categorical { p: self.p() }.serialize(serializer)
+    }
+}
+
+impl<'l> Deserialize<'l> for Categorical {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Categorical { p } =
+            parameters::Categorical::<Vec<f64>>::deserialize(deserializer)?;
+        Categorical::try_new(&p).map_err(D::Error::custom)
+    }
+}
+
+impl<T> Serialize for Censored<T> where T: distribution::Distribution<Value=f64> + Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Censored {
+            inner: self.inner(), limit: self.limit(), censoring: self.kind(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'l, T> Deserialize<'l> for Censored<T>
+    where T: distribution::Distribution<Value=f64> + Deserialize<'l>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Censored { inner, limit, censoring } =
+            parameters::Censored::<T>::deserialize(deserializer)?;
+        Censored::try_new(inner, limit, censoring).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Dirichlet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Dirichlet { alpha: self.alpha() }.serialize(serializer)
+    }
+}
+
+impl<'l> Deserialize<'l> for Dirichlet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Dirichlet { alpha } =
+            parameters::Dirichlet::<Vec<f64>>::deserialize(deserializer)?;
+        Dirichlet::try_new(&alpha).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Empirical {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Empirical {
+            samples: self.samples(), interpolation: self.interpolation(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'l> Deserialize<'l> for Empirical {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Empirical { samples, interpolation } =
+            parameters::Empirical::<Vec<f64>>::deserialize(deserializer)?;
+        Empirical::try_with_interpolation(&samples, interpolation).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Histogram {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Histogram { edges: self.edges(), counts: self.counts() }.serialize(serializer)
+    }
+}
+
+impl<'l> Deserialize<'l> for Histogram {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Histogram { edges, counts } =
+            parameters::Histogram::<Vec<f64>, Vec<u64>>::deserialize(deserializer)?;
+        Histogram::try_new(&edges, &counts).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Kde {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Kde {
+            samples: self.samples(), bandwidth: self.bandwidth(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'l> Deserialize<'l> for Kde {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Kde { samples, bandwidth } =
+            parameters::Kde::<Vec<f64>>::deserialize(deserializer)?;
+        Kde::try_with_bandwidth(&samples, bandwidth).map_err(D::Error::custom)
+    }
+}
+
+impl<T> Serialize for Mixture<T> where T: distribution::Distribution + Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Mixture {
+            components: self.components(), weights: self.weights(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'l, T> Deserialize<'l> for Mixture<T> where T: distribution::Distribution + Deserialize<'l> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Mixture { components, weights } =
+            parameters::Mixture::<Vec<T>, Vec<f64>>::deserialize(deserializer)?;
+        Mixture::try_new(components, weights).map_err(D::Error::custom)
+    }
+}
+
+impl<T> Serialize for OrderStatistic<T> where T: distribution::Distribution + Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::OrderStatistic {
+            inner: self.inner(), n: self.n(), k: self.k(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'l, T> Deserialize<'l> for OrderStatistic<T>
+    where T: distribution::Distribution + Deserialize<'l>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::OrderStatistic { inner, n, k } =
+            parameters::OrderStatistic::<T>::deserialize(deserializer)?;
+        OrderStatistic::try_new(inner, n, k).map_err(D::Error::custom)
+    }
+}
+
+impl<A, B> Serialize for Sum<A, B>
+    where A: distribution::Inverse<Value=f64> + Serialize,
+          B: distribution::Inverse<Value=f64> + Serialize
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        parameters::Sum { a: self.a(), b: self.b(), nodes: self.nodes() }.serialize(serializer)
+    }
+}
+
+impl<'l, A, B> Deserialize<'l> for Sum<A, B>
+    where A: distribution::Inverse<Value=f64> + Deserialize<'l>,
+          B: distribution::Inverse<Value=f64> + Deserialize<'l>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'l> {
+        let parameters::Sum { a, b, nodes } =
+            parameters::Sum::<A, B>::deserialize(deserializer)?;
+        Sum::try_with_nodes(a, b, nodes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode;
+    use serde::de::DeserializeOwned;
+    use serde::ser::Serialize;
+    use serde_json;
+
+    use prelude::*;
+
+    fn check<T, F, V>(d: T, parameters: F) where T: Serialize + DeserializeOwned,
+                                                 F: Fn(&T) -> V,
+                                                 V: PartialEq + ::std::fmt::Debug
+    {
+        let e: T = serde_json::from_str(&serde_json::to_string(&d).unwrap()).unwrap();
+        assert_eq!(parameters(&e), parameters(&d));
+        let e: T = bincode::deserialize(&bincode::serialize(&d).unwrap()).unwrap();
+        assert_eq!(parameters(&e), parameters(&d));
+    }
+
+    #[test]
+    fn affine() {
+        check(Affine::new(Gaussian::new(1.0, 2.0), -3.0, 4.0), |d| {
+            (d.inner().mu(), d.inner().sigma(), d.scale(), d.shift())
+        });
+    }
+
+    #[test]
+    fn bernoulli() {
+        check(Bernoulli::new(0.25), |d| d.p());
+    }
+
+    #[test]
+    fn beta() {
+        check(Beta::new(2.0, 3.0, -1.0, 2.0), |d| (d.alpha(), d.beta(), d.a(), d.b()));
+    }
+
+    #[test]
+    fn beta_binomial() {
+        check(BetaBinomial::new(10, 2.0, 3.0), |d| (d.n(), d.alpha(), d.beta()));
+    }
+
+    #[test]
+    fn binomial() {
+        check(Binomial::new(16, 0.25), |d| (d.n(), d.p()));
+    }
+
+    #[test]
+    fn categorical() {
+        check(Categorical::new(&[0.25, 0.5, 0.25]), |d| d.p().to_vec());
+    }
+
+    #[test]
+    fn censored() {
+        check(Censored::new(Exponential::new(2.0), 1.0, Censoring::Left), |d| {
+            (d.inner().lambda(), d.limit(), d.kind())
+        });
+    }
+
+    #[test]
+    fn dirichlet() {
+        check(Dirichlet::new(&[1.0, 2.5, 0.5]), |d| d.alpha().to_vec());
+    }
+
+    #[test]
+    fn empirical() {
+        check(Empirical::with_interpolation(&[3.0, 1.0, 2.0], Interpolation::Nearest), |d| {
+            (d.samples().to_vec(), d.interpolation())
+        });
+    }
+
+    #[test]
+    fn exponential() {
+        check(Exponential::new(2.0), |d| d.lambda());
+    }
+
+    #[test]
+    fn gamma() {
+        check(Gamma::new(9.0, 0.5), |d| (d.k(), d.theta()));
+    }
+
+    #[test]
+    fn gaussian() {
+        check(Gaussian::new(1.0, 2.0), |d| (d.mu(), d.sigma()));
+        assert_eq!(serde_json::to_string(&Gaussian::new(0.5, 2.0)).unwrap(),
+                   r#"{"mu":0.5,"sigma":2.0}"#);
+    }
+
+    #[test]
+    fn gaussian_inverse_gamma() {
+        check(GaussianInverseGamma::new(1.0, 2.0, 3.0, 4.0), |d| {
+            (d.mu(), d.lambda(), d.alpha(), d.beta())
+        });
+    }
+
+    #[test]
+    fn histogram() {
+        check(Histogram::new(&[0.0, 1.0, 3.0], &[1, 2]), |d| {
+            (d.edges().to_vec(), d.counts().to_vec())
+        });
+    }
+
+    #[test]
+    fn invalid() {
+        fn error<T>(json: &str) -> String where T: DeserializeOwned {
+            match serde_json::from_str::<T>(json) {
+                Err(error) => error.to_string(),
+                Ok(_) => panic!("deserialized {}", json),
+            }
+        }
+
+        assert_eq!(error::<Gaussian>(r#"{"mu": 0.0, "sigma": -1.0}"#),
+                   "sigma should be nonnegative and finite (got -1)");
+        assert_eq!(error::<Uniform>(r#"{"a": 1.0, "b": 0.0}"#),
+                   "a and b should be finite with a <= b (got a = 1, b = 0)");
+        assert_eq!(error::<Bernoulli>(r#"{"p": 1.5}"#),
+                   "probability should be within its range (got 1.5)");
+        assert_eq!(error::<Categorical>(r#"{"p": []}"#), "probabilities should not be empty");
+        assert_eq!(error::<Affine<Gaussian>>(r#"{
+            "inner": {"mu": 0.0, "sigma": 1.0}, "scale": 0.0, "shift": 1.0
+        }"#), "scale should be nonzero and finite (got 0)");
+        assert_eq!(error::<Mixture<Exponential>>(r#"{
+            "components": [{"lambda": 1.0}], "weights": [1.0, 2.0]
+        }"#), "weights should match components (got 1 components and 2 weights)");
+        assert!(error::<Affine<Gaussian>>(r#"{
+            "inner": {"mu": 0.0, "sigma": -1.0}, "scale": 1.0, "shift": 1.0
+        }"#).starts_with("sigma should be nonnegative and finite (got -1)"));
+        assert!(error::<Gaussian>(r#"{"mu": 0.0}"#).starts_with("missing field `sigma`"));
+        assert!(error::<Gaussian>(r#"{"mu": 0.0, "sigma": 1.0, "nu": 1.0}"#)
+                    .starts_with("unknown field `nu`"));
+
+        let bytes = bincode::serialize(&(1.0, -1.0)).unwrap();
+        assert!(bincode::deserialize::<Gaussian>(&bytes).is_err());
+    }
+
+    #[test]
+    fn joint() {
+        check(Joint::new((Gaussian::new(1.0, 2.0), Poisson::new(3.0))), |d| {
+            let (ref a, ref b) = *d.components();
+            (a.mu(), a.sigma(), b.lambda())
+        });
+        check(Joint::new(vec![Uniform::new(0.0, 1.0), Uniform::new(2.0, 3.0)]), |d| {
+            d.components().iter().map(|d| (d.a(), d.b())).collect::<Vec<_>>()
+        });
+    }
+
+    #[test]
+    fn kde() {
+        check(Kde::with_bandwidth(&[3.0, 1.0, 2.0], 0.5), |d| {
+            (d.samples().to_vec(), d.bandwidth())
+        });
+    }
+
+    #[test]
+    fn mixture() {
+        check(Mixture::new(vec![Gaussian::new(0.0, 1.0), Gaussian::new(3.0, 2.0)],
+                           vec![0.25, 0.75]), |d| {
+            (d.components().iter().map(|d| (d.mu(), d.sigma())).collect::<Vec<_>>(),
+             d.weights().to_vec())
+        });
+    }
+
+    #[test]
+    fn negative_binomial() {
+        check(NegativeBinomial::new(2.5, 0.25), |d| (d.r(), d.p()));
+    }
+
+    #[test]
+    fn order() {
+        check(OrderStatistic::new(Uniform::new(0.0, 1.0), 5, 2), |d| {
+            (d.inner().a(), d.inner().b(), d.n(), d.k())
+        });
+    }
+
+    #[test]
+    fn point_mass() {
+        check(PointMass::new(-1.5), |d| d.value());
+    }
+
+    #[test]
+    fn poisson() {
+        check(Poisson::new(3.0), |d| d.lambda());
+    }
+
+    #[test]
+    fn standard_gaussian() {
+        check(StandardGaussian, |d| d.to_string());
+    }
+
+    #[test]
+    fn student() {
+        check(StudentT::new(3.0, 1.0, 2.0), |d| (d.nu(), d.mu(), d.sigma()));
+    }
+
+    #[test]
+    fn sum() {
+        check(Sum::with_nodes(Uniform::new(0.0, 1.0), Exponential::new(2.0), 100), |d| {
+            (d.a().a(), d.a().b(), d.b().lambda(), d.nodes())
+        });
+    }
+
+    #[test]
+    fn uniform() {
+        check(Uniform::new(-1.0, 1.0), |d| (d.a(), d.b()));
+    }
+}
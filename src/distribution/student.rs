@@ -0,0 +1,309 @@
+use std::fmt;
+
+use distribution;
+#[cfg(feature = "alloc")]
+use distribution::ModeList;
+use source::Source;
+use ParameterError;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A Student’s t distribution with location and scale parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StudentT {
+    nu: f64,
+    mu: f64,
+    sigma: f64,
+    ln_norm: f64,
+}
+
+impl StudentT {
+    /// Create a Student’s t distribution with `nu` degrees of freedom,
+    /// location `mu`, and scale `sigma`.
+    ///
+    /// It should hold that `nu > 0` and `sigma > 0`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn new(nu: f64, mu: f64, sigma: f64) -> Self {
+        unwrap!(StudentT::try_new(nu, mu, sigma))
+    }
+
+    /// Create a Student’s t distribution with `nu` degrees of freedom,
+    /// location `mu`, and scale `sigma` or return an error if the parameters
+    /// are invalid.
+    pub fn try_new(nu: f64, mu: f64, sigma: f64) -> Result<Self, ParameterError> {
+        use special::Beta;
+        ensure!(nu > 0.0 && nu.is_finite(), ParameterError::NuNotPositive { value: nu });
+        ensure!(mu.is_finite(), ParameterError::MuNotFinite { value: mu });
+        ensure!(sigma > 0.0 && sigma.is_finite(),
+                ParameterError::SigmaNotPositive { value: sigma });
+        Ok(StudentT {
+            nu: nu,
+            mu: mu,
+            sigma: sigma,
+            ln_norm: (nu / 2.0).ln_beta(0.5) + 0.5 * nu.ln() + sigma.ln(),
+        })
+    }
+
+    /// Return the number of degrees of freedom.
+    #[inline(always)]
+    pub fn nu(&self) -> f64 { self.nu }
+
+    /// Return the location parameter.
+    #[inline(always)]
+    pub fn mu(&self) -> f64 { self.mu }
+
+    /// Return the scale parameter.
+    #[inline(always)]
+    pub fn sigma(&self) -> f64 { self.sigma }
+}
+
+impl fmt::Display for StudentT {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "StudentT(ν = {}, μ = {}, σ = {})", self.nu, self.mu, self.sigma)
+    }
+}
+
+impl distribution::Continuous for StudentT {
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        self.ln_density(x).exp()
+    }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        guard!(ln_density(x));
+        let z = (x - self.mu) / self.sigma;
+        -(self.nu + 1.0) / 2.0 * (z * z / self.nu).ln_1p() - self.ln_norm
+    }
+}
+
+impl distribution::Distribution for StudentT {
+    type Value = f64;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The implementation is based on the incomplete beta function.
+    fn distribution(&self, x: f64) -> f64 {
+        use math;
+        guard!(distribution(x));
+        math::student((self.mu - x) / self.sigma, self.nu)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use math;
+        guard!(survival(x));
+        math::student((x - self.mu) / self.sigma, self.nu)
+    }
+}
+
+impl distribution::Entropy for StudentT {
+    fn entropy(&self) -> f64 {
+        use special::Gamma;
+        let nu = self.nu;
+        (nu + 1.0) / 2.0 * (((nu + 1.0) / 2.0).digamma() - (nu / 2.0).digamma()) + self.ln_norm
+    }
+}
+
+impl distribution::Inverse for StudentT {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The quantile of the standard distribution is found by inverting the
+    /// incomplete beta function and refining the result by Newton’s method.
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        use math;
+        guard!(inverse(p));
+        self.mu + self.sigma * math::student_inverse(p, self.nu)
+    }
+}
+
+impl distribution::Kurtosis for StudentT {
+    /// Compute the excess kurtosis.
+    ///
+    /// The kurtosis is infinite for `2 < ν ≤ 4` and undefined for `ν ≤ 2`.
+    fn kurtosis(&self) -> f64 {
+        if self.nu > 4.0 {
+            6.0 / (self.nu - 4.0)
+        } else if self.nu > 2.0 {
+            ::std::f64::INFINITY
+        } else {
+            ::std::f64::NAN
+        }
+    }
+}
+
+impl distribution::Mean for StudentT {
+    /// Compute the expected value.
+    ///
+    /// The expected value is undefined for `ν ≤ 1`.
+    #[inline]
+    fn mean(&self) -> f64 {
+        if self.nu > 1.0 { self.mu } else { ::std::f64::NAN }
+    }
+}
+
+impl distribution::Median for StudentT {
+    #[inline]
+    fn median(&self) -> f64 { self.mu }
+}
+
+impl distribution::Mgf for StudentT {
+    /// Compute the cumulant generating function.
+    ///
+    /// The moment-generating function does not exist, and the function is
+    /// infinite everywhere except at zero.
+    #[inline]
+    fn ln_mgf(&self, t: f64) -> f64 {
+        if t == 0.0 { 0.0 } else { ::std::f64::INFINITY }
+    }
+
+    #[inline]
+    fn mgf_domain(&self) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl distribution::Modes for StudentT {
+    #[inline]
+    fn modes(&self) -> ModeList<f64> {
+        ModeList::one(self.mu)
+    }
+}
+
+impl distribution::Sample for StudentT {
+    /// Draw a sample.
+    ///
+    /// A standard Gaussian variable is divided by the square root of an
+    /// independent chi-squared one divided by its degrees of freedom.
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        use distribution::{gamma, gaussian};
+        let z = gaussian::sample(source);
+        let chi = 2.0 * gamma::sample(self.nu / 2.0, source);
+        self.mu + self.sigma * z / (chi / self.nu).sqrt()
+    }
+}
+
+impl distribution::Skewness for StudentT {
+    /// Compute the skewness.
+    ///
+    /// The skewness is undefined for `ν ≤ 3`.
+    #[inline]
+    fn skewness(&self) -> f64 {
+        if self.nu > 3.0 { 0.0 } else { ::std::f64::NAN }
+    }
+}
+
+impl distribution::Variance for StudentT {
+    /// Compute the variance.
+    ///
+    /// The variance is infinite for `1 < ν ≤ 2` and undefined for `ν ≤ 1`.
+    fn variance(&self) -> f64 {
+        if self.nu > 2.0 {
+            self.sigma * self.sigma * self.nu / (self.nu - 2.0)
+        } else if self.nu > 1.0 {
+            ::std::f64::INFINITY
+        } else {
+            ::std::f64::NAN
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use tests::ks_one_sample;
+
+    macro_rules! new(
+        ($nu:expr, $mu:expr, $sigma:expr) => (StudentT::new($nu, $mu, $sigma));
+    );
+
+    #[test]
+    fn density() {
+        let d = new!(3.0, 1.0, 2.0);
+        let x = vec![-3.0, 0.0, 1.0, 2.5, 10.0];
+        let p = vec![
+            0.033754830331946452011, 0.15659045550441430992, 0.18377629847393068317,
+            0.13032335847458796369, 0.0030597510672038407188,
+        ];
+        assert::close(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p, 1e-15);
+        assert_eq!(d.density(::std::f64::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(new!(3.0, 1.0, 2.0).to_string(), "StudentT(ν = 3, μ = 1, σ = 2)");
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(3.0, 1.0, 2.0);
+        let x = vec![-3.0, 0.0, 1.0, 2.5, 10.0];
+        let p = vec![
+            0.069662984279421588424, 0.32572398242407549722, 0.5, 0.7461427102879328938,
+            0.98975479382777329531,
+        ];
+        assert::close(&x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(), &p, 1e-14);
+        assert::close(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+                      &p.iter().map(|&p| 1.0 - p).collect::<Vec<_>>(), 1e-14);
+    }
+
+    #[test]
+    fn entropy() {
+        assert::close(new!(3.0, 1.0, 2.0).entropy(), 2.4666247524232362575, 1e-14);
+    }
+
+    #[test]
+    fn gaussian_limit() {
+        let (d, e) = (new!(1e7, 1.0, 2.0), Gaussian::new(1.0, 2.0));
+        for &x in &[-2.0, 0.5, 3.0] {
+            assert::close(d.density(x), e.density(x), 1e-7);
+            assert::close(d.distribution(x), e.distribution(x), 1e-7);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(3.0, 1.0, 2.0);
+        let p = vec![0.01, 0.3, 0.5, 0.9, 0.999];
+        let x = vec![
+            -8.0814057171362671105, -0.16877945487963733822, 1.0, 4.2754887073924202109,
+            21.4290637048147731,
+        ];
+        assert::close(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x, 1e-12);
+        assert_eq!(d.inverse(0.0), ::std::f64::NEG_INFINITY);
+        assert_eq!(d.inverse(1.0), ::std::f64::INFINITY);
+    }
+
+    #[test]
+    fn moments() {
+        let d = new!(5.0, 1.0, 2.0);
+        assert_eq!(d.mean(), 1.0);
+        assert::close(d.variance(), 4.0 * 5.0 / 3.0, 1e-15);
+        assert_eq!(d.skewness(), 0.0);
+        assert_eq!(d.kurtosis(), 6.0);
+        assert_eq!((d.median(), &d.modes()[..]), (1.0, &[1.0][..]));
+
+        let d = new!(2.0, 1.0, 2.0);
+        assert_eq!(d.variance(), ::std::f64::INFINITY);
+        assert!(d.skewness().is_nan() && d.kurtosis().is_nan());
+        assert!(new!(1.0, 0.0, 1.0).mean().is_nan());
+    }
+
+    #[test]
+    fn sample() {
+        let x = new!(5.0, 1.0, 2.0).samples(&mut source::seeded(42), 100000);
+        assert!(ks_one_sample(&x, &new!(5.0, 1.0, 2.0)).p_value > 0.01);
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(StudentT::try_new(3.0, 0.0, 1.0).is_ok());
+        assert_eq!(StudentT::try_new(0.0, 0.0, 1.0).err(),
+                   Some(ParameterError::NuNotPositive { value: 0.0 }));
+        assert_eq!(StudentT::try_new(3.0, 0.0, -1.0).err(),
+                   Some(ParameterError::SigmaNotPositive { value: -1.0 }));
+    }
+}
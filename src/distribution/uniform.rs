@@ -1,93 +1,286 @@
+use std::fmt;
+
 use distribution;
 use source::Source;
+use {FitError, Float, ParameterError};
+#[cfg(not(feature = "std"))]
+use float::Real;
 
 /// A continuous uniform distribution.
-#[derive(Clone, Copy)]
-pub struct Uniform {
-    a: f64,
-    b: f64,
+///
+/// The support is the closed interval `[a, b]`: the cumulative distribution
+/// function is exactly zero at `a` and one at `b`, the density is `1 / (b -
+/// a)` at the endpoints as well as between them and zero elsewhere, and the
+/// inverse maps zero and one to `a` and `b` exactly. Samples are drawn from
+/// `[a, b)`, that is, `b` is never drawn. Intervals whose width overflows are
+/// supported; the computations are then carried out on the halved endpoints.
+///
+/// The endpoints are allowed to coincide, in which case the distribution is
+/// the point mass at `a`: the density is infinite at `a`, the distribution
+/// function is a step at `a`, and every sample is `a`. The skewness and
+/// kurtosis remain those of the nondegenerate distribution.
+///
+/// The distribution is generic over the precision of its parameters and
+/// values, which is `f64` unless stated otherwise; see `Float`. In other
+/// precisions, the distribution is created using `from_bounds`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform<T = f64> {
+    a: T,
+    b: T,
 }
 
-impl Uniform {
-    /// Create a uniform distribution on interval `[a, b]`.
+impl<T> Uniform<T> where T: Float {
+    /// Create a uniform distribution on interval `[a, b]` in the precision of
+    /// `T`.
     ///
-    /// It should hold that `a < b`.
+    /// The function is the same as `new` except that it is generic over the
+    /// precision. It should hold that `a` and `b` are finite and `a <= b`;
+    /// the function panics otherwise.
     #[inline]
-    pub fn new(a: f64, b: f64) -> Self {
-        should!(a < b);
-        Uniform { a: a, b: b }
+    pub fn from_bounds(a: T, b: T) -> Self {
+        unwrap!(Uniform::try_from_bounds(a, b))
+    }
+
+    /// Create a uniform distribution on interval `[a, b]` in the precision of
+    /// `T` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_from_bounds(a: T, b: T) -> Result<Self, ParameterError> {
+        ensure!(a <= b && a.is_finite() && b.is_finite(),
+                ParameterError::BoundsNotOrdered { a: a.to_f64(), b: b.to_f64() });
+        Ok(Uniform { a: a, b: b })
     }
 
     /// Return the left endpoint of the support.
     #[inline(always)]
-    pub fn a(&self) -> f64 { self.a }
+    pub fn a(&self) -> T { self.a }
 
     /// Return the right endpoint of the support.
     #[inline(always)]
-    pub fn b(&self) -> f64 { self.a }
+    pub fn b(&self) -> T { self.b }
+
+    // Return the endpoints divided by a factor that keeps the width finite
+    // together with the factor, which is one unless the width overflows.
+    #[inline]
+    fn reduced(&self) -> (T, T, T) {
+        if (self.b - self.a).is_finite() {
+            (self.a, self.b, T::from_f64(1.0))
+        } else {
+            let half = T::from_f64(0.5);
+            (half * self.a, half * self.b, T::from_f64(2.0))
+        }
+    }
+}
+
+impl Uniform {
+    /// Create a uniform distribution on interval `[a, b]`.
+    ///
+    /// It should hold that `a` and `b` are finite and `a <= b`; the function
+    /// panics otherwise.
+    #[inline]
+    pub fn new(a: f64, b: f64) -> Self {
+        unwrap!(Uniform::try_new(a, b))
+    }
+
+    /// Create a uniform distribution on interval `[a, b]` or return an error
+    /// if the parameters are invalid.
+    #[inline]
+    pub fn try_new(a: f64, b: f64) -> Result<Self, ParameterError> {
+        Uniform::try_from_bounds(a, b)
+    }
+
+    /// Create the standard uniform distribution, that is, the one on interval
+    /// `[0, 1]`.
+    ///
+    /// ```
+    /// use probability::distribution::Uniform;
+    ///
+    /// const STANDARD: Uniform = Uniform::standard();
+    /// assert_eq!(STANDARD, Uniform::new(0.0, 1.0));
+    /// ```
+    #[inline]
+    pub const fn standard() -> Self {
+        Uniform { a: 0.0, b: 1.0 }
+    }
 }
 
 impl Default for Uniform {
     #[inline]
     fn default() -> Self {
-        Uniform::new(0.0, 1.0)
+        Uniform::standard()
+    }
+}
+
+affine!(Uniform => Uniform,
+        |d: Uniform, shift| Uniform::new(d.a + shift, d.b + shift),
+        |d: Uniform, scale: f64| if scale > 0.0 {
+            Uniform::new(scale * d.a, scale * d.b)
+        } else {
+            Uniform::new(scale * d.b, scale * d.a)
+        });
+
+impl<T> fmt::Display for Uniform<T> where T: Float {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Uniform(a = {}, b = {})", self.a, self.b)
     }
 }
 
-impl distribution::Continuous for Uniform {
+impl<T> distribution::Continuous for Uniform<T> where T: Float {
     #[inline]
     fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        let x = T::from_f64(x);
         if x < self.a || x > self.b {
             0.0
         } else {
-            1.0 / (self.b - self.a)
+            let (a, b, factor) = self.reduced();
+            (T::from_f64(1.0) / factor / (b - a)).to_f64()
         }
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn central_moment(&self, k: u32) -> f64 {
+        central_moment((self.b - self.a).to_f64(), k)
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        guard!(ln_density(x));
+        let x = T::from_f64(x);
+        if x < self.a || x > self.b {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let (a, b, factor) = self.reduced();
+            (-(b - a).ln() - factor.ln()).to_f64()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn moment(&self, k: u32) -> f64 {
+        use distribution::{Mean, translate};
+        let width = (self.b - self.a).to_f64();
+        translate(|j| central_moment(width, j), k, self.mean())
+    }
 }
 
-impl distribution::Distribution for Uniform {
-    type Value = f64;
+impl<T> distribution::Distribution for Uniform<T> where T: Float {
+    type Value = T;
 
     #[inline]
     fn distribution(&self, x: f64) -> f64 {
-        if x <= self.a {
+        let x = T::from_f64(x);
+        if x >= self.b {
+            1.0
+        } else if x <= self.a {
             0.0
-        } else if x >= self.b {
+        } else {
+            let (a, b, factor) = self.reduced();
+            ((x / factor - a) / (b - a)).to_f64()
+        }
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.a.to_f64(), self.b.to_f64())
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        let x = T::from_f64(x);
+        if x >= self.b {
+            0.0
+        } else if x <= self.a {
             1.0
         } else {
-            (x - self.a) / (self.b - self.a)
+            let (a, b, factor) = self.reduced();
+            ((b - x / factor) / (b - a)).to_f64()
+        }
+    }
+}
+
+impl distribution::Divergence for Uniform {
+    #[inline]
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        use distribution::nested;
+        if !nested(self, other) {
+            return ::std::f64::INFINITY;
+        }
+        if self == other {
+            return 0.0;
         }
+        use distribution::Entropy;
+        other.entropy() - self.entropy()
     }
 }
 
 impl distribution::Entropy for Uniform {
     #[inline]
     fn entropy(&self) -> f64 {
-        (self.b - self.a).ln()
+        let (a, b, factor) = self.reduced();
+        (b - a).ln() + factor.ln()
     }
 }
 
-impl distribution::Inverse for Uniform {
+impl<T> distribution::Inverse for Uniform<T> where T: Float {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The result is `a` when `p` is zero and `b` when `p` is one, and it
+    /// stays within `[a, b]` despite rounding.
+    #[inline]
+    fn inverse(&self, p: f64) -> T {
+        guard!(inverse(p));
+        if p == 1.0 {
+            return self.b;
+        }
+        let (a, b, factor) = self.reduced();
+        clamp(factor * (a + (b - a) * T::from_f64(p)), self.a, self.b)
+    }
+
+    /// Compute the inverse of the survival function.
+    ///
+    /// The result is `b` when `p` is zero and `a` when `p` is one, and it
+    /// stays within `[a, b]` despite rounding.
     #[inline]
-    fn inverse(&self, p: f64) -> f64 {
-        should!(0.0 <= p && p <= 1.0);
-        self.a + (self.b - self.a) * p
+    fn inverse_survival(&self, p: f64) -> T {
+        guard!(inverse(p));
+        if p == 1.0 {
+            return self.a;
+        }
+        let (a, b, factor) = self.reduced();
+        clamp(factor * (b - (b - a) * T::from_f64(p)), self.a, self.b)
     }
 }
 
-impl distribution::Kurtosis for Uniform {
+impl<T> distribution::Kurtosis for Uniform<T> where T: Float {
     #[inline]
     fn kurtosis(&self) -> f64 { -1.2 }
 }
 
-impl distribution::Mean for Uniform {
+impl distribution::MaximumLikelihood for Uniform {
+    /// Fit the distribution to `data`.
+    ///
+    /// The endpoints are estimated by the smallest and largest data, which
+    /// makes the estimated interval narrower than the true one. If all the
+    /// data are the same, the estimate is the degenerate distribution at that
+    /// value.
+    fn fit_mle(data: &[f64]) -> Result<Self, FitError> {
+        use distribution::examine;
+        examine(data)?;
+        let a = data.iter().cloned().fold(::std::f64::INFINITY, f64::min);
+        let b = data.iter().cloned().fold(::std::f64::NEG_INFINITY, f64::max);
+        Ok(Uniform::try_from_bounds(a, b)?)
+    }
+}
+
+impl<T> distribution::Mean for Uniform<T> where T: Float {
     #[inline]
     fn mean(&self) -> f64 {
-        (self.a + self.b) / 2.0
+        self.a.to_f64() / 2.0 + self.b.to_f64() / 2.0
     }
 }
 
-impl distribution::Median for Uniform {
+impl<T> distribution::Median for Uniform<T> where T: Float {
     #[inline]
     fn median(&self) -> f64 {
         use distribution::Mean;
@@ -95,33 +288,135 @@ impl distribution::Median for Uniform {
     }
 }
 
-impl distribution::Sample for Uniform {
+impl distribution::MethodOfMoments for Uniform {
+    /// Fit the distribution to mean `mean` and variance `variance`.
+    ///
+    /// The endpoints are `mean ± √(3 variance)`.
+    fn fit_moments_from(mean: f64, variance: f64) -> Result<Self, FitError> {
+        ensure!(mean.is_finite() && variance >= 0.0 && variance.is_finite(),
+                FitError::MomentsInfeasible { mean: mean, variance: variance });
+        let half = (3.0 * variance).sqrt();
+        Ok(Uniform::try_from_bounds(mean - half, mean + half)?)
+    }
+}
+
+impl distribution::Mgf for Uniform {
+    /// Compute the cumulant generating function.
+    ///
+    /// The derivatives are computed numerically.
+    fn ln_mgf(&self, t: f64) -> f64 {
+        let u = t * (self.b - self.a);
+        if u == 0.0 {
+            t * self.a
+        } else if u < 0.0 {
+            t * self.a + (u.exp_m1() / u).ln()
+        } else {
+            t * self.b + (-(-u).exp_m1() / u).ln()
+        }
+    }
+}
+
+impl<T> distribution::Sample for Uniform<T> where T: Float {
+    /// Draw a sample.
+    ///
+    /// The standard sample is drawn in double precision and rounded. The
+    /// sample is in `[a, b)`; the rare draws that land on `b`, either because
+    /// the standard sample is one or because of rounding, are repeated. If the
+    /// distribution is degenerate, the sample is `a`.
     #[inline]
-    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
-        self.a + (self.b - self.a) * source.read::<f64>()
+    fn sample<S>(&self, source: &mut S) -> T where S: Source {
+        if self.a == self.b {
+            return self.a;
+        }
+        let (a, b, factor) = self.reduced();
+        loop {
+            let x = factor * (a + (b - a) * T::from_f64(source.read::<f64>()));
+            if x < self.b {
+                return x;
+            }
+        }
     }
 }
 
-impl distribution::Skewness for Uniform {
+impl<T> distribution::Skewness for Uniform<T> where T: Float {
     #[inline]
     fn skewness(&self) -> f64 { 0.0 }
 }
 
-impl distribution::Variance for Uniform {
+impl<T> distribution::Variance for Uniform<T> where T: Float {
     #[inline]
     fn variance(&self) -> f64 {
-        (self.b - self.a).powi(2) / 12.0
+        (self.b - self.a).to_f64().powi(2) / 12.0
+    }
+}
+
+#[inline]
+fn clamp<T>(x: T, a: T, b: T) -> T where T: Float {
+    if x < a { a } else if x > b { b } else { x }
+}
+
+// Compute the central moment of order `k` of a uniform distribution on an
+// interval of width `width`.
+#[cfg(feature = "alloc")]
+fn central_moment(width: f64, k: u32) -> f64 {
+    if k % 2 == 1 {
+        0.0
+    } else {
+        (width / 2.0).powi(k as i32) / (k + 1) as f64
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use assert;
     use prelude::*;
 
     macro_rules! new(
         ($a:expr, $b:expr) => (Uniform::new($a, $b));
     );
 
+    #[test]
+    fn central_moment() {
+        let d = new!(-1.0, 3.0);
+        assert_eq!(d.central_moment(1), 0.0);
+        assert::close(d.central_moment(2), d.variance(), 1e-15);
+        assert_eq!(d.central_moment(7), 0.0);
+        assert::close(d.central_moment(8), 256.0 / 9.0, 1e-14);
+        assert::close(d.central_moment(3) / d.deviation().powi(3), d.skewness(), 1e-14);
+        assert::close(d.central_moment(4) / d.variance().powi(2) - 3.0, d.kurtosis(), 1e-14);
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Uniform::default(), Uniform::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn degenerate() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = new!(1.5, 1.5);
+        let x = [NEG_INFINITY, 1.0, 1.5, 2.0, INFINITY];
+        assert_eq!(d.density_vec(&x), vec![0.0, 0.0, INFINITY, 0.0, 0.0]);
+        assert_eq!(d.distribution_vec(&x), vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!((d.survival(1.0), d.survival(1.5)), (1.0, 0.0));
+        assert_eq!((d.ln_density(1.5), d.ln_density(2.0)), (INFINITY, NEG_INFINITY));
+        for &p in &[0.0, 0.25, 1.0] {
+            assert_eq!((d.inverse(p), d.inverse_survival(p)), (1.5, 1.5));
+        }
+        assert!(d.samples(&mut source::seeded(42), 10).iter().all(|&x| x == 1.5));
+        assert_eq!((d.mean(), d.variance(), d.entropy()), (1.5, 0.0, NEG_INFINITY));
+        assert_eq!(d.kl_divergence(&d), 0.0);
+        assert_eq!(d.kl_divergence(&new!(1.0, 2.0)), INFINITY);
+        assert_eq!(new!(1.0, 2.0).kl_divergence(&d), INFINITY);
+        assert_eq!(Uniform::from_bounds(1.5f32, 1.5).sample(&mut source::seeded(42)), 1.5);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Uniform::new(-1.0, 1.0).to_string(), "Uniform(a = -1, b = 1)");
+    }
+
     #[test]
     fn distribution() {
         let d = new!(-1.0, 1.0);
@@ -140,12 +435,74 @@ mod tests {
         assert_eq!(&x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(), &p);
     }
 
+    #[test]
+    fn endpoints() {
+        use std::f64::EPSILON;
+
+        let step = |x: f64, up: bool| if (x > 0.0) == up {
+            f64::from_bits(x.to_bits() + 1)
+        } else {
+            f64::from_bits(x.to_bits() - 1)
+        };
+        let pairs = [(0.1, 0.7), (-3.3, 1e-3), (1.0, 1.0 + 4.0 * EPSILON), (1e15, 1e15 + 1.5)];
+        for &(a, b) in &pairs {
+            let d = new!(a, b);
+            let (below, above) = (step(a, false), step(b, true));
+            assert_eq!((d.distribution(below), d.distribution(a)), (0.0, 0.0));
+            assert_eq!((d.distribution(b), d.distribution(above)), (1.0, 1.0));
+            assert_eq!((d.survival(a), d.survival(b)), (1.0, 0.0));
+            assert_eq!((d.inverse(0.0), d.inverse(1.0)), (a, b));
+            assert_eq!((d.inverse_survival(0.0), d.inverse_survival(1.0)), (b, a));
+            assert_eq!((d.density(below), d.density(above)), (0.0, 0.0));
+            assert_eq!((d.density(a), d.density(b)), (1.0 / (b - a), 1.0 / (b - a)));
+            for i in 0..101 {
+                let x = d.inverse(i as f64 / 100.0);
+                assert!(a <= x && x <= b);
+            }
+        }
+    }
+
     #[test]
     fn entropy() {
         use std::f64::consts::E;
         assert_eq!(new!(0.0, E).entropy(), 1.0);
     }
 
+    #[test]
+    fn fit_mle() {
+        use std::f64::NEG_INFINITY;
+
+        let d = Uniform::fit_mle(&[0.5, -1.0, 2.0, 0.0]).unwrap();
+        assert_eq!((d.a(), d.b()), (-1.0, 2.0));
+
+        let n = 100000;
+        let x = new!(-1.0, 2.0).samples(&mut source::seeded(42), n);
+        let d = Uniform::fit_mle(&x).unwrap();
+        assert!(d.a() >= -1.0 && d.a() - -1.0 < 3.0 * 20.0 / n as f64);
+        assert!(d.b() <= 2.0 && 2.0 - d.b() < 3.0 * 20.0 / n as f64);
+
+        assert_eq!(Uniform::fit_mle(&[]), Err(FitError::DataEmpty));
+        assert_eq!(Uniform::fit_mle(&[1.0]), Ok(new!(1.0, 1.0)));
+        assert_eq!(Uniform::fit_mle(&[1.0, 1.0]), Ok(new!(1.0, 1.0)));
+        assert_eq!(Uniform::fit_mle(&[1.0, NEG_INFINITY]),
+                   Err(FitError::DatumNotFinite { value: NEG_INFINITY }));
+    }
+
+    #[test]
+    fn fit_moments() {
+        let x = new!(-1.0, 2.0).samples(&mut source::seeded(42), 1000);
+        let mean = x.iter().sum::<f64>() / 1000.0;
+        let variance = x.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / 1000.0;
+        let d = Uniform::fit_moments(&x).unwrap();
+        assert::close(d.mean(), mean, 1e-12);
+        assert::close(d.variance(), variance, 1e-12);
+
+        assert_eq!(Uniform::fit_moments_from(1.0, 3.0), Ok(new!(-2.0, 4.0)));
+        assert_eq!(Uniform::fit_moments_from(1.0, 0.0), Ok(new!(1.0, 1.0)));
+        assert_eq!(Uniform::fit_moments_from(1.0, -3.0),
+                   Err(FitError::MomentsInfeasible { mean: 1.0, variance: -3.0 }));
+    }
+
     #[test]
     fn inverse() {
         let d = new!(-1.0, 1.0);
@@ -155,11 +512,37 @@ mod tests {
         assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(-1.0, 1.0);
+        let p = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+        let x = vec![1.0, 0.5, 0.0, -0.5, -1.0];
+
+        assert_eq!(&p.iter().map(|&p| d.inverse_survival(p)).collect::<Vec<_>>(), &x);
+    }
+
+    #[test]
+    fn kl_divergence() {
+        let (p, q) = (new!(0.0, 1.0), new!(0.0, 2.0));
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert::close(p.kl_divergence(&q), 2f64.ln(), 1e-15);
+        assert_eq!(q.kl_divergence(&p), ::std::f64::INFINITY);
+        assert::close(new!(0.5, 1.5).kl_divergence(&new!(-1.0, 2.0)), 3f64.ln(), 1e-15);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.0, 2.0).kurtosis(), -1.2);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = Uniform::new(-1.0, 3.0);
+        let x = vec![-1.5, -1.0, 0.0, 3.0, 3.5];
+        assert_eq!(&x.iter().map(|&x| d.ln_density(x)).collect::<Vec<_>>(),
+                   &x.iter().map(|&x| d.density(x).ln()).collect::<Vec<_>>());
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.0, 2.0).mean(), 1.0);
@@ -170,20 +553,129 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).median(), 1.0);
     }
 
+    #[test]
+    fn moment() {
+        let d = new!(-1.0, 3.0);
+        assert_eq!(d.moment(0), 1.0);
+        assert_eq!(d.moment(1), 1.0);
+        assert::close(d.moment(3), 5.0, 1e-14);
+        assert::close(d.moment(8), 546.77777777777777778, 1e-12);
+    }
+
     #[test]
     fn sample() {
-        for x in Independent(&new!(7.0, 42.0), &mut source::default()).take(100) {
+        for x in Independent(&new!(7.0, 42.0), &mut source::seeded(42)).take(100) {
             assert!(7.0 <= x && x <= 42.0);
         }
     }
 
+    #[test]
+    fn sample_endpoint() {
+        let (a, b) = (1.0, 1.0 + 4.0 * ::std::f64::EPSILON);
+        let d = new!(a, b);
+        let mut seen = [false; 4];
+        for x in Independent(&d, &mut source::seeded(42)).take(10_000_000) {
+            assert!(a <= x && x < b);
+            seen[((x - a) / ::std::f64::EPSILON) as usize] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+
+        let (a, b) = (1.0f32, 1.0f32 + 4.0 * ::std::f32::EPSILON);
+        let d = Uniform::from_bounds(a, b);
+        for x in Independent(&d, &mut source::seeded(42)).take(100_000) {
+            assert!(a <= x && x < b);
+        }
+    }
+
+    #[test]
+    fn single_precision() {
+        use std::mem;
+
+        assert_eq!(mem::size_of::<Uniform<f32>>(), 8);
+
+        let (d, e) = (Uniform::from_bounds(-1.0f32, 3.0), new!(-1.0, 3.0));
+        assert_eq!((d.a(), d.b()), (-1.0f32, 3.0f32));
+        assert_eq!(d.to_string(), "Uniform(a = -1, b = 3)");
+        for i in -10..31 {
+            let x = i as f64 / 5.0 - 2.0;
+            assert::close(d.density(x), e.density(x), 1e-6);
+            assert::close(d.ln_density(x), e.ln_density(x), 1e-6);
+            assert::close(d.distribution(x), e.distribution(x), 1e-6);
+            assert::close(d.survival(x), e.survival(x), 1e-6);
+        }
+        for i in 0..101 {
+            let p = i as f64 / 100.0;
+            assert::close(d.inverse(p) as f64, e.inverse(p), 1e-6);
+        }
+
+        let x = d.samples(&mut source::seeded(42), 1000);
+        let y = e.samples(&mut source::seeded(42), 1000);
+        for (&x, &y) in x.iter().zip(&y) {
+            assert::close(x as f64, y, 1e-6);
+        }
+
+        assert_eq!(Uniform::try_from_bounds(1.0f32, 0.0).err(),
+                   Some(ParameterError::BoundsNotOrdered { a: 1.0, b: 0.0 }));
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(0.0, 2.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn standard() {
+        const STANDARD: Uniform = Uniform::standard();
+        assert_eq!(STANDARD, Uniform::default());
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(-1.0, 1.0);
+        let x = vec![-1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5];
+        let p = vec![1.0, 1.0, 0.75, 0.5, 0.25, 0.0, 0.0];
+
+        assert_eq!(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(), &p);
+    }
+
+    #[test]
+    fn try_new() {
+        use std::f64::{INFINITY, NAN};
+        assert!(Uniform::try_new(-1.0, 1.0).is_ok());
+        assert!(Uniform::try_new(1.0, 1.0).is_ok());
+        for &(a, b) in &[(1.0, -1.0), (-INFINITY, 1.0), (0.0, INFINITY)] {
+            assert_eq!(Uniform::try_new(a, b).err(),
+                       Some(ParameterError::BoundsNotOrdered { a: a, b: b }));
+        }
+        assert!(Uniform::try_new(NAN, 1.0).is_err());
+        assert!(Uniform::try_new(0.0, NAN).is_err());
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.0, 12.0).variance(), 12.0);
     }
+
+    #[test]
+    fn wide() {
+        use std::f64::{INFINITY, MAX};
+
+        let d = new!(-MAX, MAX);
+        assert_eq!(d.density(0.0), 0.5 / MAX);
+        assert_eq!(d.ln_density(MAX), -MAX.ln() - 2f64.ln());
+        assert_eq!(d.entropy(), MAX.ln() + 2f64.ln());
+        assert_eq!((d.distribution(0.0), d.distribution(MAX / 2.0)), (0.5, 0.75));
+        assert_eq!((d.survival(0.0), d.survival(MAX / 2.0)), (0.5, 0.25));
+        assert_eq!((d.inverse(0.0), d.inverse(0.25), d.inverse(0.5), d.inverse(1.0)),
+                   (-MAX, -MAX / 2.0, 0.0, MAX));
+        assert_eq!((d.inverse_survival(0.0), d.inverse_survival(0.25)), (MAX, MAX / 2.0));
+        assert_eq!((d.mean(), d.variance()), (0.0, INFINITY));
+        assert_eq!(d.kl_divergence(&d), 0.0);
+        for x in Independent(&d, &mut source::seeded(42)).take(1000) {
+            assert!(x.is_finite());
+        }
+
+        let d = new!(MAX / 2.0, MAX);
+        assert_eq!(d.mean(), 0.75 * MAX);
+    }
 }
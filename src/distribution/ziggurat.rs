@@ -0,0 +1,147 @@
+//! The ziggurat method.
+
+use source::Source;
+
+/// The tables of a ziggurat.
+///
+/// The region under an unnormalized density that decreases away from zero is
+/// covered by `n` layers of equal area, where `n` is a power of two: `n - 1`
+/// rectangles stacked on top of a base, which is a rectangle extended by the
+/// tail of the density. Layer `i` lies between heights `y[i + 1]` and `y[i]`,
+/// except for the base, which lies below `y[n - 1]`; `w[i]` is the width of
+/// the layer divided by `2^52`, and `k[i]` is the width of the layer above it
+/// in the same units, that is, the part of the layer that lies entirely under
+/// the density.
+pub struct Ziggurat {
+    pub k: &'static [u64],
+    pub w: &'static [f64],
+    pub y: &'static [f64],
+    pub symmetric: bool,
+}
+
+/// Draw a sample using the ziggurat method.
+///
+/// Each attempt reads one `u64`, whose lowest bits select the layer, the next
+/// bit, if the distribution is symmetric, selects the sign, and the highest 52
+/// bits select the position within the layer, so that the samples have the
+/// full precision of `f64`. The unnormalized density `density` is evaluated
+/// only for the positions outside the layer above, and `tail` draws a sample
+/// from the tail beyond the base.
+///
+/// ## References
+///
+/// 1. G. Marsaglia and W. W. Tsang, “The ziggurat method for generating
+///    random variables,” Journal of Statistical Software, vol. 5, no. 8,
+///    pp. 1–7, 10 2000.
+///
+/// 2. J. A. Doornik, “An improved ziggurat method to generate normal random
+///    samples,” 2005.
+#[inline(always)]
+pub fn sample<S, D, T>(source: &mut S, ziggurat: &Ziggurat, density: D, mut tail: T) -> f64
+    where S: Source, D: Fn(f64) -> f64, T: FnMut(&mut S) -> f64
+{
+    let n = ziggurat.k.len();
+    should!(n.is_power_of_two() && n <= 1 << 11);
+    should!(ziggurat.w.len() == n && ziggurat.y.len() == n);
+    loop {
+        let u = source.read::<u64>();
+
+        let i = u as usize & (n - 1);
+        let j = u >> 12;
+        let x = if j < ziggurat.k[i] {
+            j as f64 * ziggurat.w[i]
+        } else if i == n - 1 {
+            tail(source)
+        } else {
+            let x = j as f64 * ziggurat.w[i];
+            let (lower, upper) = (ziggurat.y[i + 1], ziggurat.y[i]);
+            if lower + (upper - lower) * source.read::<f64>() >= density(x) {
+                continue;
+            }
+            x
+        };
+
+        return if !ziggurat.symmetric || u & n as u64 != 0 { x } else { -x };
+    }
+}
+
+/// Assert that the tables of a ziggurat match the tables `k`, `w`, and `y`
+/// to within one unit of the integers and one unit in the last place of the
+/// floating-point numbers.
+#[cfg(test)]
+pub fn assert_tables(ziggurat: &Ziggurat, k: &[u64], w: &[f64], y: &[f64]) {
+    fn ulps(one: f64, other: f64) -> u64 {
+        (one.to_bits() as i64 - other.to_bits() as i64).abs() as u64
+    }
+
+    let n = ziggurat.k.len();
+    assert_eq!((k.len(), w.len(), y.len()), (n, n, n));
+    for i in 0..n {
+        assert!(ziggurat.k[i].max(k[i]) - ziggurat.k[i].min(k[i]) <= 1,
+                "k[{}] = {} instead of {}", i, ziggurat.k[i], k[i]);
+        assert!(ulps(ziggurat.w[i], w[i]) <= 1,
+                "w[{}] = {:e} instead of {:e}", i, ziggurat.w[i], w[i]);
+        assert!(ulps(ziggurat.y[i], y[i]) <= 1,
+                "y[{}] = {:e} instead of {:e}", i, ziggurat.y[i], y[i]);
+    }
+}
+
+/// Find the radius of the base of a ziggurat with `n` layers under an
+/// unnormalized density that is one at zero.
+///
+/// The radius is the smallest one in `[a, b]` for which the layers close at
+/// the top. The function `inverse` is the inverse of the density, and `tail`
+/// computes the area under the density beyond a point.
+#[cfg(test)]
+pub fn radius<D, I, T>(n: usize, density: D, inverse: I, tail: T, a: f64, b: f64) -> f64
+    where D: Fn(f64) -> f64, I: Fn(f64) -> f64, T: Fn(f64) -> f64
+{
+    use math;
+    math::bisect(|r| -stack(n, r, &density, &inverse, &tail).2, -density(0.0), a, b)
+}
+
+/// Compute the tables of a ziggurat with `n` layers and the base of width `r`
+/// under an unnormalized density that is one at zero.
+///
+/// The function `inverse` is the inverse of the density, and `tail` computes
+/// the area under the density beyond a point. The radius `r` should be the one
+/// given by `radius`. The tables are returned in the order `k`, `w`, and `y`.
+#[cfg(test)]
+pub fn generate<D, I, T>(n: usize, r: f64, density: D, inverse: I, tail: T)
+                         -> (Vec<u64>, Vec<f64>, Vec<f64>)
+    where D: Fn(f64) -> f64, I: Fn(f64) -> f64, T: Fn(f64) -> f64
+{
+    let (x, mut y, _) = stack(n, r, &density, &inverse, &tail);
+    y[0] = density(0.0);
+    let area = r * y[n - 1] + tail(r);
+    let width = |i: usize| if i < n - 1 { x[i + 1] } else { area / y[n - 1] };
+    let scale = (1u64 << 52) as f64;
+    let k = (0..n).map(|i| (scale * x[i] / width(i)) as u64).collect();
+    let w = (0..n).map(|i| width(i) / scale).collect();
+    (k, w, y)
+}
+
+/// Stack the layers of a ziggurat from the base up and return their edges,
+/// their bottoms, and the height at which the top layer closes, which is
+/// infinite if the layers overshoot the density.
+#[cfg(test)]
+fn stack<D, I, T>(n: usize, r: f64, density: &D, inverse: &I, tail: &T)
+                  -> (Vec<f64>, Vec<f64>, f64)
+    where D: Fn(f64) -> f64, I: Fn(f64) -> f64, T: Fn(f64) -> f64
+{
+    let (top, area) = (density(0.0), r * density(r) + tail(r));
+    let (mut x, mut y) = (vec![0.0; n], vec![0.0; n]);
+    x[n - 1] = r;
+    y[n - 1] = density(r);
+    for i in (1..(n - 1)).rev() {
+        y[i] = y[i + 1] + area / x[i + 1];
+        if y[i] >= top {
+            return (x, y, ::std::f64::INFINITY);
+        }
+        x[i] = inverse(y[i]);
+    }
+    let height = y[1] + area / x[1];
+    (x, y, height)
+}
+
+
@@ -0,0 +1,472 @@
+//! Errors.
+
+use std::{error, fmt};
+
+/// An error in fitting a distribution to data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FitError {
+    /// The covariance matrix is not positive definite even after adding a
+    /// small multiple of the identity.
+    CovarianceNotDefinite,
+    /// There are no data.
+    DataEmpty,
+    /// The data are all equal, but the distribution requires at least two
+    /// distinct values.
+    DataNotDistinct,
+    /// A datum is not finite.
+    DatumNotFinite { value: f64 },
+    /// A datum lies outside the support of the distribution.
+    DatumOutOfSupport { value: f64 },
+    /// There are no events, that is, all observations are censored.
+    EventsZero,
+    /// The information matrix is singular, and the standard errors are
+    /// undefined.
+    InformationSingular,
+    /// The iteration did not converge within the given number of steps; the
+    /// last iterate of the parameters is given in the order of the
+    /// constructor.
+    IterationsExhausted { iterations: usize, estimate: [f64; 2] },
+    /// The moments cannot be attained by any distribution of the family.
+    MomentsInfeasible { mean: f64, variance: f64 },
+    /// The estimated parameters are invalid.
+    Parameter(ParameterError),
+    /// No transitions out of a state are observed, and its transition
+    /// probabilities are undefined.
+    TransitionsZero { state: usize },
+    /// The number of weights differs from the number of data.
+    WeightsMismatch { data: usize, weights: usize },
+    /// A weight is negative or not finite.
+    WeightNotNonnegative { value: f64 },
+    /// The weights sum up to zero.
+    WeightsZero,
+}
+
+/// An error in the parameters of a distribution.
+///
+/// Each variant names the offending parameter and the violated constraint.
+/// NaNs violate every constraint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParameterError {
+    /// The first shape parameter is not positive and finite.
+    AlphaNotPositive { value: f64 },
+    /// The autoregressive coefficients do not define a stationary process,
+    /// that is, some root of the characteristic polynomial lies on or within
+    /// the unit circle.
+    ArNotStationary,
+    /// The bandwidth is not positive and finite.
+    BandwidthNotPositive { value: f64 },
+    /// The second shape parameter is not positive and finite.
+    BetaNotPositive { value: f64 },
+    /// The number of bins is zero.
+    BinsZero,
+    /// The endpoints of an interval are not finite or decreasing.
+    BoundsNotOrdered { a: f64, b: f64 },
+    /// There are no components.
+    ComponentsEmpty,
+    /// There are no concentration parameters.
+    ConcentrationsEmpty,
+    /// The Markov chain is reducible or periodic.
+    ChainNotErgodic,
+    /// A coefficient is not finite.
+    CoefficientNotFinite { value: f64 },
+    /// The correlation matrix is not symmetric and positive definite with a
+    /// unit diagonal.
+    CorrelationNotDefinite,
+    /// The bin counts sum up to zero.
+    CountsZero,
+    /// The number of bin edges does not exceed the number of bin counts by one.
+    EdgesMismatch { edges: usize, counts: usize },
+    /// The bin edges are not finite or not strictly increasing.
+    EdgesNotOrdered,
+    /// The Cornish–Fisher expansion with the given skewness and excess
+    /// kurtosis is not increasing up to the requested quantile.
+    ExpansionNotMonotone { skewness: f64, kurtosis: f64 },
+    /// The shape parameter is not positive and finite.
+    KNotPositive { value: f64 },
+    /// The rate parameter is not positive and finite.
+    LambdaNotPositive { value: f64 },
+    /// The length scale is not positive and finite.
+    LengthScaleNotPositive { value: f64 },
+    /// The censoring limit is not finite.
+    LimitNotFinite { value: f64 },
+    /// The mean is not positive and finite.
+    MeanNotPositive { value: f64 },
+    /// The mean lies outside its admissible range.
+    MeanOutOfRange { value: f64 },
+    /// A row of a matrix differs in length from the number of rows.
+    MatrixNotSquare { rows: usize, columns: usize },
+    /// The location parameter is not finite.
+    MuNotFinite { value: f64 },
+    /// The number of integration nodes is zero.
+    NodesZero,
+    /// The standard deviation of the noise is negative or not finite.
+    NoiseNotNonnegative { value: f64 },
+    /// The number of degrees of freedom is not positive and finite.
+    NuNotPositive { value: f64 },
+    /// The precision is not positive and finite.
+    PrecisionNotPositive { value: f64 },
+    /// There are no probabilities.
+    ProbabilitiesEmpty,
+    /// The probabilities do not sum up to one.
+    ProbabilitiesNotNormalized { sum: f64 },
+    /// A probability lies outside its admissible range.
+    ProbabilityOutOfRange { value: f64 },
+    /// The rate parameter is not positive and finite.
+    RateNotPositive { value: f64 },
+    /// The rank of an order statistic is not between one and the number of
+    /// draws.
+    RankOutOfRange { k: usize, n: usize },
+    /// The number of successes is not positive and finite.
+    RNotPositive { value: f64 },
+    /// The initial value of a process is not positive and finite.
+    S0NotPositive { value: f64 },
+    /// The sample size is not positive and finite.
+    SampleSizeNotPositive { value: f64 },
+    /// There are no observations.
+    SamplesEmpty,
+    /// An observation is not finite.
+    SampleNotFinite { value: f64 },
+    /// The scale is zero or not finite.
+    ScaleNotNonzero { value: f64 },
+    /// The shift is not finite.
+    ShiftNotFinite { value: f64 },
+    /// The standard deviation is negative or not finite.
+    SigmaNotNonnegative { value: f64 },
+    /// The standard deviation is not positive and finite.
+    SigmaNotPositive { value: f64 },
+    /// The lower endpoint of the support is negative.
+    SupportNotNonnegative { value: f64 },
+    /// The scale parameter is negative or not finite.
+    ThetaNotNonnegative { value: f64 },
+    /// The scale parameter is not positive and finite.
+    ThetaNotPositive { value: f64 },
+    /// The value is not finite.
+    ValueNotFinite { value: f64 },
+    /// The variance is negative or not finite.
+    VarianceNotNonnegative { value: f64 },
+    /// The variance is not positive and finite.
+    VarianceNotPositive { value: f64 },
+    /// The number of weights differs from the number of components.
+    WeightsMismatch { components: usize, weights: usize },
+    /// A weight is negative or not finite.
+    WeightNotNonnegative { value: f64 },
+    /// The weights sum up to zero.
+    WeightsZero,
+    /// The width is not positive and finite.
+    WidthNotPositive { value: f64 },
+}
+
+/// An error in sampling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SamplingError {
+    /// No sample was accepted within the given number of attempts.
+    AttemptsExhausted { attempts: usize },
+    /// The scaled proposal density falls below the target density at a
+    /// point, which means that the bound is too small or the proposal does
+    /// not cover the target.
+    BoundExceeded { x: f64, excess: f64 },
+    /// The number of items to choose exceeds the number of items available.
+    SizeExceeded { k: usize, n: usize },
+    /// The slice was not bracketed within the given number of steps, which
+    /// suggests an improper target.
+    StepsExhausted { steps: usize },
+    /// The weights of all the particles vanish, which means that none of them
+    /// can explain the observation.
+    WeightsZero,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        use self::FitError::*;
+        match *self {
+            CovarianceNotDefinite => write!(formatter, "covariance should be positive definite"),
+            DataEmpty => write!(formatter, "data should not be empty"),
+            DataNotDistinct => write!(formatter, "data should contain distinct values"),
+            DatumNotFinite { value } => write!(formatter, "data should be finite (got {})", value),
+            DatumOutOfSupport { value } => {
+                write!(formatter, "data should be within the support (got {})", value)
+            },
+            EventsZero => write!(formatter, "observations should contain events"),
+            InformationSingular => write!(formatter, "information should be nonsingular"),
+            IterationsExhausted { iterations, estimate } => {
+                write!(formatter, "estimate should converge within {} iterations (stopped at \
+                                   {:?})", iterations, estimate)
+            },
+            MomentsInfeasible { mean, variance } => {
+                write!(formatter, "moments should be attainable by the distribution (got mean = \
+                                   {}, variance = {})", mean, variance)
+            },
+            Parameter(ref error) => write!(formatter, "estimate is invalid: {}", error),
+            TransitionsZero { state } => {
+                write!(formatter, "transitions from state {} should be observed", state)
+            },
+            WeightsMismatch { data, weights } => {
+                write!(formatter, "weights should match data (got {} data and {} weights)",
+                       data, weights)
+            },
+            WeightNotNonnegative { value } => {
+                write!(formatter, "weights should be nonnegative and finite (got {})", value)
+            },
+            WeightsZero => write!(formatter, "weights should not sum up to zero"),
+        }
+    }
+}
+
+impl error::Error for FitError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            FitError::Parameter(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParameterError> for FitError {
+    #[inline]
+    fn from(error: ParameterError) -> Self {
+        FitError::Parameter(error)
+    }
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        use self::ParameterError::*;
+        match *self {
+            AlphaNotPositive { value } => {
+                write!(formatter, "alpha should be positive and finite (got {})", value)
+            },
+            ArNotStationary => {
+                write!(formatter, "autoregressive coefficients should be stationary")
+            },
+            BandwidthNotPositive { value } => {
+                write!(formatter, "bandwidth should be positive and finite (got {})", value)
+            },
+            BetaNotPositive { value } => {
+                write!(formatter, "beta should be positive and finite (got {})", value)
+            },
+            BinsZero => write!(formatter, "bins should be positive"),
+            BoundsNotOrdered { a, b } => {
+                write!(formatter, "a and b should be finite with a <= b (got a = {}, b = {})",
+                       a, b)
+            },
+            ChainNotErgodic => write!(formatter, "chain should be irreducible and aperiodic"),
+            CoefficientNotFinite { value } => {
+                write!(formatter, "coefficients should be finite (got {})", value)
+            },
+            ComponentsEmpty => write!(formatter, "components should not be empty"),
+            ConcentrationsEmpty => write!(formatter, "concentrations should not be empty"),
+            CorrelationNotDefinite => {
+                write!(formatter, "correlation should be symmetric and positive definite with a \
+                                   unit diagonal")
+            },
+            CountsZero => write!(formatter, "counts should not sum up to zero"),
+            EdgesMismatch { edges, counts } => {
+                write!(formatter, "edges should outnumber counts by one (got {} edges and {} \
+                                   counts)", edges, counts)
+            },
+            EdgesNotOrdered => write!(formatter, "edges should be finite and strictly increasing"),
+            ExpansionNotMonotone { skewness, kurtosis } => {
+                write!(formatter, "expansion should be increasing (got skewness {} and kurtosis \
+                                   {})", skewness, kurtosis)
+            },
+            KNotPositive { value } => {
+                write!(formatter, "k should be positive and finite (got {})", value)
+            },
+            LambdaNotPositive { value } => {
+                write!(formatter, "lambda should be positive and finite (got {})", value)
+            },
+            LengthScaleNotPositive { value } => {
+                write!(formatter, "length scale should be positive and finite (got {})", value)
+            },
+            LimitNotFinite { value } => {
+                write!(formatter, "limit should be finite (got {})", value)
+            },
+            MeanNotPositive { value } => {
+                write!(formatter, "mean should be positive and finite (got {})", value)
+            },
+            MeanOutOfRange { value } => {
+                write!(formatter, "mean should be within its range (got {})", value)
+            },
+            MatrixNotSquare { rows, columns } => {
+                write!(formatter, "matrix should be square (got {} rows and a row of {} columns)",
+                       rows, columns)
+            },
+            MuNotFinite { value } => write!(formatter, "mu should be finite (got {})", value),
+            NodesZero => write!(formatter, "nodes should be positive"),
+            NoiseNotNonnegative { value } => {
+                write!(formatter, "noise should be nonnegative and finite (got {})", value)
+            },
+            NuNotPositive { value } => {
+                write!(formatter, "nu should be positive and finite (got {})", value)
+            },
+            PrecisionNotPositive { value } => {
+                write!(formatter, "precision should be positive and finite (got {})", value)
+            },
+            ProbabilitiesEmpty => write!(formatter, "probabilities should not be empty"),
+            ProbabilitiesNotNormalized { sum } => {
+                write!(formatter, "probabilities should sum up to one (got {})", sum)
+            },
+            ProbabilityOutOfRange { value } => {
+                write!(formatter, "probability should be within its range (got {})", value)
+            },
+            RateNotPositive { value } => {
+                write!(formatter, "rate should be positive and finite (got {})", value)
+            },
+            RankOutOfRange { k, n } => {
+                write!(formatter, "k should be between 1 and n (got k = {}, n = {})", k, n)
+            },
+            RNotPositive { value } => {
+                write!(formatter, "r should be positive and finite (got {})", value)
+            },
+            S0NotPositive { value } => {
+                write!(formatter, "s0 should be positive and finite (got {})", value)
+            },
+            SampleSizeNotPositive { value } => {
+                write!(formatter, "sample size should be positive and finite (got {})", value)
+            },
+            SamplesEmpty => write!(formatter, "samples should not be empty"),
+            SampleNotFinite { value } => {
+                write!(formatter, "samples should be finite (got {})", value)
+            },
+            ScaleNotNonzero { value } => {
+                write!(formatter, "scale should be nonzero and finite (got {})", value)
+            },
+            ShiftNotFinite { value } => write!(formatter, "shift should be finite (got {})", value),
+            SigmaNotNonnegative { value } => {
+                write!(formatter, "sigma should be nonnegative and finite (got {})", value)
+            },
+            SigmaNotPositive { value } => {
+                write!(formatter, "sigma should be positive and finite (got {})", value)
+            },
+            SupportNotNonnegative { value } => {
+                write!(formatter, "support should be nonnegative (got a lower endpoint of {})",
+                       value)
+            },
+            ThetaNotNonnegative { value } => {
+                write!(formatter, "theta should be nonnegative and finite (got {})", value)
+            },
+            ThetaNotPositive { value } => {
+                write!(formatter, "theta should be positive and finite (got {})", value)
+            },
+            ValueNotFinite { value } => {
+                write!(formatter, "value should be finite (got {})", value)
+            },
+            VarianceNotNonnegative { value } => {
+                write!(formatter, "variance should be nonnegative and finite (got {})", value)
+            },
+            VarianceNotPositive { value } => {
+                write!(formatter, "variance should be positive and finite (got {})", value)
+            },
+            WeightsMismatch { components, weights } => {
+                write!(formatter, "weights should match components (got {} components and {} \
+                                   weights)", components, weights)
+            },
+            WeightNotNonnegative { value } => {
+                write!(formatter, "weights should be nonnegative and finite (got {})", value)
+            },
+            WeightsZero => write!(formatter, "weights should not sum up to zero"),
+            WidthNotPositive { value } => {
+                write!(formatter, "width should be positive and finite (got {})", value)
+            },
+        }
+    }
+}
+
+impl error::Error for ParameterError {}
+
+impl fmt::Display for SamplingError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        use self::SamplingError::*;
+        match *self {
+            AttemptsExhausted { attempts } => {
+                write!(formatter, "a sample should be accepted within {} attempts", attempts)
+            },
+            BoundExceeded { x, excess } => {
+                write!(formatter, "the scaled proposal should cover the target (exceeded at {} \
+                                   by {} in the log domain)", x, excess)
+            },
+            SizeExceeded { k, n } => {
+                write!(formatter, "k should not exceed the number of available items, {} (got \
+                                   {})", n, k)
+            },
+            StepsExhausted { steps } => {
+                write!(formatter, "the slice should be bracketed within {} steps", steps)
+            },
+            WeightsZero => write!(formatter, "some particle should have a positive weight"),
+        }
+    }
+}
+
+impl error::Error for SamplingError {}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::{FitError, ParameterError, SamplingError};
+
+    #[test]
+    fn display() {
+        assert_eq!(ParameterError::SigmaNotPositive { value: -2.0 }.to_string(),
+                   "sigma should be positive and finite (got -2)");
+        assert_eq!(ParameterError::BoundsNotOrdered { a: 1.0, b: 0.0 }.to_string(),
+                   "a and b should be finite with a <= b (got a = 1, b = 0)");
+        assert_eq!(ParameterError::RankOutOfRange { k: 0, n: 3 }.to_string(),
+                   "k should be between 1 and n (got k = 0, n = 3)");
+        assert_eq!(ParameterError::S0NotPositive { value: 0.0 }.to_string(),
+                   "s0 should be positive and finite (got 0)");
+        assert_eq!(ParameterError::MatrixNotSquare { rows: 2, columns: 3 }.to_string(),
+                   "matrix should be square (got 2 rows and a row of 3 columns)");
+        assert_eq!(ParameterError::ChainNotErgodic.to_string(),
+                   "chain should be irreducible and aperiodic");
+        assert_eq!(ParameterError::CorrelationNotDefinite.to_string(),
+                   "correlation should be symmetric and positive definite with a unit diagonal");
+        assert_eq!(ParameterError::ArNotStationary.to_string(),
+                   "autoregressive coefficients should be stationary");
+        assert_eq!(ParameterError::CoefficientNotFinite { value: ::std::f64::INFINITY }
+                       .to_string(),
+                   "coefficients should be finite (got inf)");
+        assert_eq!(ParameterError::LengthScaleNotPositive { value: 0.0 }.to_string(),
+                   "length scale should be positive and finite (got 0)");
+        assert_eq!(ParameterError::NoiseNotNonnegative { value: -1.0 }.to_string(),
+                   "noise should be nonnegative and finite (got -1)");
+        assert_eq!(FitError::DatumOutOfSupport { value: -1.0 }.to_string(),
+                   "data should be within the support (got -1)");
+        assert_eq!(FitError::Parameter(ParameterError::LambdaNotPositive { value: 0.0 })
+                       .to_string(),
+                   "estimate is invalid: lambda should be positive and finite (got 0)");
+        assert_eq!(FitError::IterationsExhausted { iterations: 100, estimate: [1.5, 2.0] }
+                       .to_string(),
+                   "estimate should converge within 100 iterations (stopped at [1.5, 2.0])");
+        assert_eq!(FitError::EventsZero.to_string(), "observations should contain events");
+        assert_eq!(FitError::TransitionsZero { state: 2 }.to_string(),
+                   "transitions from state 2 should be observed");
+        assert_eq!(FitError::InformationSingular.to_string(), "information should be nonsingular");
+        assert_eq!(FitError::CovarianceNotDefinite.to_string(),
+                   "covariance should be positive definite");
+        assert_eq!(FitError::MomentsInfeasible { mean: 0.5, variance: 0.5 }.to_string(),
+                   "moments should be attainable by the distribution (got mean = 0.5, variance = \
+                    0.5)");
+        assert_eq!(SamplingError::AttemptsExhausted { attempts: 10 }.to_string(),
+                   "a sample should be accepted within 10 attempts");
+        assert_eq!(SamplingError::BoundExceeded { x: 0.5, excess: 0.25 }.to_string(),
+                   "the scaled proposal should cover the target (exceeded at 0.5 by 0.25 in the \
+                    log domain)");
+        assert_eq!(SamplingError::SizeExceeded { k: 3, n: 2 }.to_string(),
+                   "k should not exceed the number of available items, 2 (got 3)");
+        assert_eq!(SamplingError::StepsExhausted { steps: 50 }.to_string(),
+                   "the slice should be bracketed within 50 steps");
+        assert_eq!(SamplingError::WeightsZero.to_string(),
+                   "some particle should have a positive weight");
+    }
+
+    #[test]
+    fn error() {
+        let error: Box<dyn Error> = Box::new(ParameterError::LambdaNotPositive { value: 0.0 });
+        assert_eq!(error.to_string(), "lambda should be positive and finite (got 0)");
+
+        let error: Box<dyn Error> = Box::new(FitError::from(ParameterError::BinsZero));
+        assert_eq!(error.source().unwrap().to_string(), "bins should be positive");
+        assert!(FitError::DataEmpty.source().is_none());
+    }
+}
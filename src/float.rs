@@ -0,0 +1,199 @@
+//! Floating-point types.
+
+use std::{fmt, ops};
+
+#[cfg(not(feature = "std"))]
+use libm;
+
+/// A floating-point type.
+///
+/// The trait is implemented for `f32` and `f64` and covers the operations
+/// needed by the distributions that are generic over the precision, such as
+/// `Gaussian` and `Uniform`. The arguments and results of the functions of
+/// the distribution traits remain `f64`; the computations behind them are
+/// carried out in the precision of the type, and only the results are
+/// converted.
+pub trait Float: Copy + Default + fmt::Debug + fmt::Display + PartialOrd + 'static
+                 + ops::Add<Output=Self> + ops::Sub<Output=Self> + ops::Mul<Output=Self>
+                 + ops::Div<Output=Self> + ops::Neg<Output=Self>
+{
+    /// The storage of a quantity that a distribution precomputes once at
+    /// construction.
+    ///
+    /// For `f64`, it is an `f64` holding the quantity. For `f32`, it is `()`:
+    /// the quantity is recomputed on every use, so that a distribution takes
+    /// no more space than its parameters.
+    type Cache: Copy + fmt::Debug + PartialEq;
+
+    /// Store a precomputed quantity.
+    fn cache(value: f64) -> Self::Cache;
+
+    /// Return a stored quantity or, if nothing is stored, compute it.
+    fn cached<F>(cache: Self::Cache, compute: F) -> f64 where F: FnOnce() -> f64;
+
+    /// Convert an `f64` to the type, rounding to the nearest value.
+    fn from_f64(value: f64) -> Self;
+
+    /// Convert the value to an `f64`, which is exact.
+    fn to_f64(self) -> f64;
+
+    /// Compute the complementary error function.
+    fn erfc(self) -> Self;
+
+    /// Compute the exponential function.
+    fn exp(self) -> Self;
+
+    /// Check if the value is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+
+    /// Compute the natural logarithm.
+    fn ln(self) -> Self;
+
+    /// Compute the square root.
+    fn sqrt(self) -> Self;
+}
+
+macro_rules! implement(
+    ($kind:ident) => (
+        #[inline(always)]
+        fn from_f64(value: f64) -> Self { value as $kind }
+
+        #[inline(always)]
+        fn to_f64(self) -> f64 { self as f64 }
+
+        #[inline(always)]
+        fn erfc(self) -> Self { ::special::Error::erfc(self) }
+
+        #[cfg(feature = "std")]
+        #[inline(always)]
+        fn exp(self) -> Self { <$kind>::exp(self) }
+
+        #[cfg(not(feature = "std"))]
+        #[inline(always)]
+        fn exp(self) -> Self { libm::Libm::<$kind>::exp(self) }
+
+        #[inline(always)]
+        fn is_finite(self) -> bool { <$kind>::is_finite(self) }
+
+        #[cfg(feature = "std")]
+        #[inline(always)]
+        fn ln(self) -> Self { <$kind>::ln(self) }
+
+        #[cfg(not(feature = "std"))]
+        #[inline(always)]
+        fn ln(self) -> Self { libm::Libm::<$kind>::log(self) }
+
+        #[cfg(feature = "std")]
+        #[inline(always)]
+        fn sqrt(self) -> Self { <$kind>::sqrt(self) }
+
+        #[cfg(not(feature = "std"))]
+        #[inline(always)]
+        fn sqrt(self) -> Self { libm::Libm::<$kind>::sqrt(self) }
+    );
+);
+
+impl Float for f32 {
+    type Cache = ();
+
+    #[inline(always)]
+    fn cache(_: f64) {}
+
+    #[inline(always)]
+    fn cached<F>(_: (), compute: F) -> f64 where F: FnOnce() -> f64 { compute() }
+
+    implement!(f32);
+}
+
+impl Float for f64 {
+    type Cache = f64;
+
+    #[inline(always)]
+    fn cache(value: f64) -> f64 { value }
+
+    #[inline(always)]
+    fn cached<F>(cache: f64, _: F) -> f64 where F: FnOnce() -> f64 { cache }
+
+    implement!(f64);
+}
+
+/// The elementary functions of `f64` that `core` lacks.
+///
+/// Without the `std` feature, the trait stands in for the inherent methods of
+/// the same names and evaluates them via `libm`. The exponential function,
+/// the natural logarithm, and the square root are provided by `Float`.
+#[cfg(not(feature = "std"))]
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub trait Real {
+    fn asin(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn cbrt(self) -> Self;
+    fn ceil(self) -> Self;
+    fn cos(self) -> Self;
+    fn exp_m1(self) -> Self;
+    fn floor(self) -> Self;
+    fn fract(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn log2(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn round(self) -> Self;
+    fn sin(self) -> Self;
+    fn sinh(self) -> Self;
+}
+
+#[cfg(not(feature = "std"))]
+impl Real for f64 {
+    #[inline(always)]
+    fn asin(self) -> Self { libm::asin(self) }
+
+    #[inline(always)]
+    fn atan(self) -> Self { libm::atan(self) }
+
+    #[inline(always)]
+    fn atan2(self, other: Self) -> Self { libm::atan2(self, other) }
+
+    #[inline(always)]
+    fn cbrt(self) -> Self { libm::cbrt(self) }
+
+    #[inline(always)]
+    fn ceil(self) -> Self { libm::ceil(self) }
+
+    #[inline(always)]
+    fn cos(self) -> Self { libm::cos(self) }
+
+    #[inline(always)]
+    fn exp_m1(self) -> Self { libm::expm1(self) }
+
+    #[inline(always)]
+    fn floor(self) -> Self { libm::floor(self) }
+
+    #[inline(always)]
+    fn fract(self) -> Self { self - libm::trunc(self) }
+
+    #[inline(always)]
+    fn hypot(self, other: Self) -> Self { libm::hypot(self, other) }
+
+    #[inline(always)]
+    fn ln_1p(self) -> Self { libm::log1p(self) }
+
+    #[inline(always)]
+    fn log2(self) -> Self { libm::log2(self) }
+
+    #[inline(always)]
+    fn powf(self, n: Self) -> Self { libm::pow(self, n) }
+
+    #[inline(always)]
+    fn powi(self, n: i32) -> Self { libm::pow(self, n as f64) }
+
+    #[inline(always)]
+    fn round(self) -> Self { libm::round(self) }
+
+    #[inline(always)]
+    fn sin(self) -> Self { libm::sin(self) }
+
+    #[inline(always)]
+    fn sinh(self) -> Self { libm::sinh(self) }
+}
@@ -0,0 +1,224 @@
+//! Interoperability with the `rand` crate.
+//!
+//! With feature `rand` enabled, every distribution of this crate that can be
+//! sampled implements `rand::distributions::Distribution`, and, conversely,
+//! any distribution of the `rand` ecosystem can be wrapped into `FromRand` and
+//! sampled using the sources and samplers of this crate.
+//!
+//! ```
+//! extern crate probability;
+//! extern crate rand;
+//!
+//! use probability::prelude::*;
+//! use rand::{Rng, SeedableRng};
+//!
+//! # fn main() {
+//! let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+//! let x: f64 = rng.sample(&Gaussian::new(0.0, 1.0));
+//! let k: usize = rng.sample(Poisson::new(3.5));
+//!
+//! let mut source = source::Xorshift128Plus::new([42, 69]);
+//! let d = FromRand::new(rand::distributions::Uniform::new(0.0, 1.0));
+//! let samples = Independent(&d, &mut source).take(10).collect::<Vec<f64>>();
+//! # }
+//! ```
+
+use rand::distributions::Distribution as RandDistribution;
+use rand::{Error, Rng, RngCore};
+use std::marker::PhantomData;
+
+use distribution::{self, Sample};
+use source::Source;
+
+/// A distribution of the `rand` ecosystem.
+///
+/// The type of outcomes is fixed by `T` since a distribution of the `rand`
+/// ecosystem can produce outcomes of several types.
+#[derive(Clone, Copy, Debug)]
+pub struct FromRand<D, T>(pub D, PhantomData<T>);
+
+/// A source of randomness backed by a random number generator of the `rand`
+/// ecosystem.
+pub struct Generator<'l, R: ?Sized + 'l>(pub &'l mut R);
+
+/// A random number generator of the `rand` ecosystem backed by a source of
+/// randomness.
+pub struct Randomness<'l, S: ?Sized + 'l>(pub &'l mut S);
+
+impl<D, T> FromRand<D, T> where D: RandDistribution<T> {
+    /// Wrap a distribution.
+    #[inline(always)]
+    pub fn new(distribution: D) -> Self {
+        FromRand(distribution, PhantomData)
+    }
+
+    /// Draw a sample.
+    #[inline(always)]
+    pub fn sample<S>(&self, source: &mut S) -> T where S: Source {
+        self.0.sample(&mut Randomness(source))
+    }
+
+    /// Draw `n` samples.
+    pub fn samples<S>(&self, source: &mut S, n: usize) -> Vec<T> where S: Source {
+        (0..n).map(|_| self.sample(source)).collect()
+    }
+}
+
+impl<'l, R> Source for Generator<'l, R> where R: RngCore + ?Sized {
+    #[inline(always)]
+    fn read_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+impl<'l, S> RngCore for Randomness<'l, S> where S: Source + ?Sized {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        (self.0.read_u64() >> 32) as u32
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.0.read_u64()
+    }
+
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(8) {
+            let value = self.0.read_u64().to_le_bytes();
+            let count = chunk.len();
+            chunk.copy_from_slice(&value[..count]);
+        }
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(bytes);
+        Ok(())
+    }
+}
+
+macro_rules! implement(
+    ($($kind:ident),*) => ($(
+        impl RandDistribution<<distribution::$kind as distribution::Distribution>::Value>
+            for distribution::$kind
+        {
+            #[inline(always)]
+            fn sample<R>(&self, rng: &mut R) -> <Self as distribution::Distribution>::Value
+                where R: Rng + ?Sized
+            {
+                Sample::sample(self, &mut Generator(rng))
+            }
+        }
+    )*);
+);
+
+implement!(Bernoulli, Beta, Binomial, Categorical, Empirical, Exponential, Gamma, Gaussian,
+           Histogram, Kde, Poisson, StandardGaussian, Uniform);
+
+impl<T> RandDistribution<f64> for distribution::Affine<T>
+    where T: distribution::Sample<Value=f64>
+{
+    #[inline(always)]
+    fn sample<R>(&self, rng: &mut R) -> f64 where R: Rng + ?Sized {
+        Sample::sample(self, &mut Generator(rng))
+    }
+}
+
+impl<T> RandDistribution<f64> for distribution::Censored<T>
+    where T: distribution::Sample<Value=f64>
+{
+    #[inline(always)]
+    fn sample<R>(&self, rng: &mut R) -> f64 where R: Rng + ?Sized {
+        Sample::sample(self, &mut Generator(rng))
+    }
+}
+
+impl<P, F, D> RandDistribution<D::Value> for distribution::Compound<P, F>
+    where P: distribution::Continuous<Value=f64> + distribution::Inverse + distribution::Sample,
+          F: Fn(f64) -> D,
+          D: distribution::Sample
+{
+    #[inline(always)]
+    fn sample<R>(&self, rng: &mut R) -> D::Value where R: Rng + ?Sized {
+        Sample::sample(self, &mut Generator(rng))
+    }
+}
+
+impl<T> RandDistribution<T::Value> for distribution::Mixture<T> where T: distribution::Sample {
+    #[inline(always)]
+    fn sample<R>(&self, rng: &mut R) -> T::Value where R: Rng + ?Sized {
+        Sample::sample(self, &mut Generator(rng))
+    }
+}
+
+impl<T> RandDistribution<T::Value> for distribution::OrderStatistic<T>
+    where T: distribution::Inverse
+{
+    #[inline(always)]
+    fn sample<R>(&self, rng: &mut R) -> T::Value where R: Rng + ?Sized {
+        Sample::sample(self, &mut Generator(rng))
+    }
+}
+
+impl<A, B> RandDistribution<f64> for distribution::Sum<A, B>
+    where A: distribution::Sample<Value=f64> + distribution::Continuous + distribution::Inverse,
+          B: distribution::Sample<Value=f64> + distribution::Inverse
+{
+    #[inline(always)]
+    fn sample<R>(&self, rng: &mut R) -> f64 where R: Rng + ?Sized {
+        Sample::sample(self, &mut Generator(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::{Distribution, Standard};
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand::rngs::StdRng;
+
+    use prelude::*;
+    use source::Source;
+    use super::{Generator, Randomness};
+
+    #[test]
+    fn from_rand() {
+        let d = FromRand::new(Standard);
+        let mut one = source::Xorshift128Plus::new([42, 69]);
+        let mut other = source::Xorshift128Plus::new([42, 69]);
+        let x: Vec<u64> = Independent(&d, &mut one).take(10).collect();
+        assert_eq!(x, (0..10).map(|_| other.read::<u64>()).collect::<Vec<_>>());
+        assert_eq!(d.samples(&mut one, 10), d.samples(&mut other, 10));
+    }
+
+    #[test]
+    fn generator() {
+        let mut one = StdRng::seed_from_u64(42);
+        let mut other = StdRng::seed_from_u64(42);
+        let x = Gaussian::new(1.0, 2.0).samples(&mut Generator(&mut one), 10);
+        assert_eq!(x, (0..10).map(|_| other.sample(&Gaussian::new(1.0, 2.0)))
+                             .collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn randomness() {
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        let mut bytes = [0u8; 13];
+        Randomness(&mut source).fill_bytes(&mut bytes);
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        let (first, second) = (source.read::<u64>(), source.read::<u64>());
+        assert_eq!(&bytes[..8], &first.to_le_bytes()[..]);
+        assert_eq!(&bytes[8..], &second.to_le_bytes()[..5]);
+    }
+
+    #[test]
+    fn sample() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let x: f64 = rng.sample(&Gaussian::new(0.0, 1.0));
+        assert!(x.is_finite());
+        let x: Vec<usize> = Poisson::new(3.5).sample_iter(&mut rng).take(10).collect();
+        assert_eq!(x.len(), 10);
+        let d = Mixture::new(vec![Gaussian::new(0.0, 1.0), Gaussian::new(5.0, 1.0)],
+                             vec![0.5, 0.5]);
+        assert!(Distribution::sample(&d, &mut rng).is_finite());
+    }
+}
@@ -5,16 +5,75 @@
 //! ```
 //! use probability::prelude::*;
 //!
-//! let mut source = source::default();
+//! let mut source = source::seeded(42);
 //! let distribution = Uniform::new(0.0, 1.0);
 //! let mut sampler = Independent(&distribution, &mut source);
 //! let samples = sampler.take(10).collect::<Vec<_>>();
 //! ```
+//!
+//! The distributions are also available at the root of the crate for those
+//! who prefer to import them by name:
+//!
+//! ```
+//! use probability::Gaussian;
+//! use probability::distribution::Continuous;
+//!
+//! assert_eq!(Gaussian::new(0.0, 1.0).density(0.0), 0.3989422804014327);
+//! ```
+//!
+//! ## Features
+//!
+//! The `std` feature is enabled by default. Without it, the crate builds
+//! against `core` and takes the elementary and special functions from `libm`,
+//! which should then be enabled explicitly. The `alloc` feature, which `std`
+//! implies, brings back the functions returning vectors, such as `samples` and
+//! `modes`, together with the distributions that own their data. The Monte
+//! Carlo module requires `std`, and so do the `proptest`, `rand`, `rayon`, and
+//! `serde` integrations.
+//!
+//! Without `std`, the `source` module offers the `Source` trait and the
+//! `Xoshiro256PlusPlus` generator but no default, thread-local source.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature should be enabled");
+
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+extern crate libm;
 
 #[cfg(test)]
 extern crate assert;
 
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[cfg(any(test, feature = "proptest"))]
+extern crate proptest;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+#[cfg(feature = "std")]
 extern crate random;
+#[cfg(feature = "std")]
 extern crate special;
 
 macro_rules! should(
@@ -22,7 +81,77 @@ macro_rules! should(
     ($requirement:expr, $code:expr) => (debug_assert!($code, stringify!($requirement)));
 );
 
+macro_rules! ensure(
+    ($requirement:expr, $error:expr) => (if !$requirement { return Err($error); });
+);
+
+macro_rules! guard(
+    (density($x:expr)) => (guard!($x, 0.0, 0.0));
+    (distribution($x:expr)) => (guard!($x, 0.0, 1.0));
+    (inverse($p:expr)) => ({
+        let p = $p;
+        assert!(0.0 <= p && p <= 1.0, "the probability should be in [0, 1] (got {})", p);
+    });
+    (ln_density($x:expr)) => (guard!($x, ::std::f64::NEG_INFINITY, ::std::f64::NEG_INFINITY));
+    (ln_distribution($x:expr)) => (guard!($x, ::std::f64::NEG_INFINITY, 0.0));
+    (ln_survival($x:expr)) => (guard!($x, 0.0, ::std::f64::NEG_INFINITY));
+    (survival($x:expr)) => (guard!($x, 1.0, 0.0));
+    ($x:expr, $below:expr, $above:expr) => ({
+        let x = $x;
+        if !x.is_finite() {
+            return if x.is_nan() { x } else if x < 0.0 { $below } else { $above };
+        }
+    });
+);
+
+macro_rules! unwrap(
+    ($result:expr) => (match $result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    });
+);
+
+pub mod approx;
+#[cfg(feature = "std")]
+pub mod bayes;
+#[cfg(feature = "std")]
+pub mod copulas;
+#[cfg(feature = "std")]
+pub mod diagnostics;
 pub mod distribution;
+mod error;
+mod float;
+#[cfg(feature = "rand")]
+pub mod interop;
+pub mod math;
+#[cfg(feature = "std")]
+pub mod mcmc;
+#[cfg(feature = "std")]
+pub mod monte_carlo;
 pub mod prelude;
+#[cfg(feature = "std")]
+pub mod processes;
+#[cfg(feature = "alloc")]
+pub mod quasi;
 pub mod sampler;
 pub mod source;
+#[cfg(not(feature = "std"))]
+mod special;
+pub mod statistics;
+#[cfg(any(test, feature = "proptest"))]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod tests;
+
+pub use error::{FitError, ParameterError, SamplingError};
+pub use float::Float;
+
+pub use distribution::{Affine, Bernoulli, Beta, BetaBinomial, Binomial, Cached, Censored};
+pub use distribution::{Censoring, Compound, Exponential, Gamma, Gaussian, GaussianInverseGamma};
+pub use distribution::{NegativeBinomial, OrderStatistic, PointMass, Poisson, StandardGaussian};
+pub use distribution::{StudentT, Sum, Uniform};
+
+#[cfg(feature = "alloc")]
+pub use distribution::{Categorical, CompoundPoisson, Dirichlet, Empirical, Histogram};
+#[cfg(feature = "alloc")]
+pub use distribution::{Interpolation, Joint, Kde, Mixture};
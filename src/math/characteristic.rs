@@ -0,0 +1,383 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution;
+use source::Source;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// The grid on which a density is reconstructed from a characteristic
+/// function; see `density_from_cf`.
+///
+/// The density is computed at `points` evenly spaced points of `[lower,
+/// upper)` with spacing `h = (upper - lower) / points`, and the characteristic
+/// function is evaluated at as many evenly spaced frequencies of `[-π / h,
+/// π / h)`. The probability mass outside the interval is folded into it, and
+/// the part of the characteristic function beyond `π / h` is lost, so the
+/// interval should contain all but a negligible part of the mass and the
+/// characteristic function should be negligible beyond `π / h`. The
+/// characteristic function is multiplied by a raised-cosine taper falling from
+/// one to zero over the fraction `damping` of the frequencies closest to
+/// `π / h`, which suppresses the ripples caused by a characteristic function
+/// that has not decayed there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grid {
+    /// The left endpoint.
+    pub lower: f64,
+    /// The right endpoint.
+    pub upper: f64,
+    /// The number of points, which should be a power of two.
+    pub points: usize,
+    /// The fraction of the frequencies that are tapered, which should be in
+    /// `[0, 1)`.
+    pub damping: f64,
+}
+
+impl Grid {
+    /// Choose a grid for characteristic function `cf`.
+    ///
+    /// The mean and the standard deviation are estimated from the behavior of
+    /// the characteristic function near zero, and the interval extends 20
+    /// standard deviations to either side of the mean. The highest frequency
+    /// is doubled until the modulus of the characteristic function stays
+    /// below `1e-13` between it and its double, which determines the spacing.
+    /// The spacing is further limited to a two-hundredth of the standard
+    /// deviation, which keeps the error of the trapezoid rule in the
+    /// distribution function below `1e-6`, and the number of points is the
+    /// smallest power of two achieving the spacing but at most `2^20`. When
+    /// the limit is hit, the characteristic function has not decayed within
+    /// the grid, and the top tenth of the frequencies is tapered; otherwise,
+    /// there is no taper. For heavy-tailed distributions, whose variance is
+    /// infinite, the interval is narrower than required, and it should be set
+    /// by hand.
+    ///
+    /// The characteristic function should belong to a continuous
+    /// distribution; the function panics if it does not decay.
+    pub fn from_cf<F>(cf: &F) -> Self where F: Fn(f64) -> (f64, f64) {
+        const EXTENT: f64 = 20.0;
+        const LIMIT: usize = 1 << 20;
+        const RESOLUTION: f64 = 200.0;
+        const TOLERANCE: f64 = 1e-13;
+
+        let modulus = |t: f64| {
+            let (re, im) = cf(t);
+            re.hypot(im)
+        };
+        let mut delta = 1.0;
+        for _ in 0..200 {
+            let gap = 1.0 - modulus(delta);
+            if gap > 1e-2 {
+                delta /= 2.0;
+            } else if gap < 1e-4 {
+                delta *= 2.0;
+            } else {
+                break;
+            }
+        }
+        let deviation = (-2.0 * modulus(delta).ln()).sqrt() / delta;
+        assert!(deviation > 0.0 && deviation.is_finite(),
+                "the characteristic function should decay (got a scale of {})", deviation);
+        let epsilon = 1e-6 * delta;
+        let (re, im) = cf(epsilon);
+        let mean = im.atan2(re) / epsilon;
+        let (lower, upper) = (mean - EXTENT * deviation, mean + EXTENT * deviation);
+
+        let limit = ::std::f64::consts::PI * LIMIT as f64 / (upper - lower);
+        let mut frequency = delta;
+        while frequency < limit {
+            if (0..16).all(|i| modulus(frequency * (1.0 + i as f64 / 15.0)) < TOLERANCE) {
+                break;
+            }
+            frequency *= 2.0;
+        }
+        let points = ((upper - lower) * frequency / ::std::f64::consts::PI).ceil() as usize;
+        let points = points.max((2.0 * EXTENT * RESOLUTION) as usize);
+        let points = points.next_power_of_two().min(LIMIT);
+        let damping = if frequency < limit { 0.0 } else { 0.1 };
+        Grid { lower: lower, upper: upper, points: points, damping: damping }
+    }
+}
+
+/// A density reconstructed from a characteristic function; see
+/// `density_from_cf`.
+///
+/// The density is interpolated linearly between the points of the grid and
+/// vanishes outside the grid. The distribution function is the integral of
+/// the interpolated density, which is the cumulative trapezoid rule at the
+/// points of the grid, and its inverse solves the quadratic equation arising
+/// between the two points bracketing the probability. Samples are drawn by
+/// inverting the distribution function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconstructedDensity {
+    lower: f64,
+    step: f64,
+    density: Vec<f64>,
+    cumulative: Vec<f64>,
+}
+
+impl ReconstructedDensity {
+    /// Return the points of the grid together with the values of the density
+    /// at them, including the right endpoint.
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.density.iter().enumerate().map(|(i, &y)| (self.node(i), y)).collect()
+    }
+
+    #[inline(always)]
+    fn node(&self, i: usize) -> f64 {
+        self.lower + i as f64 * self.step
+    }
+
+    // Locate `x` within the grid, returning the index of the cell and the
+    // offset within it.
+    #[inline]
+    fn locate(&self, x: f64) -> (usize, f64) {
+        let n = self.density.len() - 1;
+        let i = (((x - self.lower) / self.step).floor().max(0.0) as usize).min(n - 1);
+        (i, x - self.node(i))
+    }
+}
+
+impl distribution::Continuous for ReconstructedDensity {
+    fn density(&self, x: f64) -> f64 {
+        guard!(density(x));
+        let (lower, upper) = distribution::Distribution::support(self);
+        if x < lower || x > upper {
+            return 0.0;
+        }
+        let (i, offset) = self.locate(x);
+        let (a, b) = (self.density[i], self.density[i + 1]);
+        a + (b - a) * offset / self.step
+    }
+}
+
+impl distribution::Distribution for ReconstructedDensity {
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        guard!(distribution(x));
+        let (lower, upper) = self.support();
+        if x <= lower {
+            return 0.0;
+        }
+        if x >= upper {
+            return 1.0;
+        }
+        let (i, offset) = self.locate(x);
+        let (a, b) = (self.density[i], self.density[i + 1]);
+        let p = self.cumulative[i] + offset * (a + (b - a) * offset / (2.0 * self.step));
+        p.min(1.0)
+    }
+
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.lower, self.node(self.density.len() - 1))
+    }
+}
+
+impl distribution::Inverse for ReconstructedDensity {
+    fn inverse(&self, p: f64) -> f64 {
+        guard!(inverse(p));
+        let (lower, upper) = distribution::Distribution::support(self);
+        if p == 0.0 {
+            return lower;
+        }
+        if p == 1.0 {
+            return upper;
+        }
+        let n = self.density.len() - 1;
+        let i = self.cumulative.iter().position(|&c| c >= p).map(|i| i - 1).unwrap_or(n - 1);
+        let (a, b) = (self.density[i], self.density[i + 1]);
+        let (slope, residual) = ((b - a) / self.step, p - self.cumulative[i]);
+        // Solve `a t + slope t² / 2 = residual` in the form that avoids
+        // cancellation.
+        let root = (a * a + 2.0 * slope * residual).max(0.0).sqrt();
+        let offset = if a + root > 0.0 { 2.0 * residual / (a + root) } else { 0.0 };
+        self.node(i) + offset.min(self.step)
+    }
+}
+
+impl distribution::Sample for ReconstructedDensity {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64 where S: Source {
+        distribution::Inverse::inverse(self, source.read::<f64>())
+    }
+}
+
+/// Reconstruct the density of a distribution from its characteristic function
+/// `cf`, which returns the real and imaginary parts, on grid `grid`.
+///
+/// The inversion integral `f(x) = ∫ exp(-itx) φ(t) dt / (2π)` is approximated
+/// by the trapezoid rule at the frequencies of the grid and evaluated at all
+/// points at once by the fast Fourier transform; see `fft`. The error consists
+/// of the mass outside the grid folded into it and of the part of the
+/// characteristic function beyond the highest frequency; both are governed by
+/// the fields of `Grid`, and `Grid::from_cf` chooses them from the decay of
+/// the characteristic function. The small negative values caused by the
+/// truncation are set to zero, and the density is scaled so that its
+/// trapezoid integral is one.
+///
+/// ```
+/// use probability::math::{self, Grid};
+/// use probability::prelude::*;
+///
+/// let cf = |t: f64| ((-t * t / 2.0).exp(), 0.0);
+/// let d = math::density_from_cf(&cf, &Grid::from_cf(&cf));
+/// assert!((d.density(1.0) - Gaussian::new(0.0, 1.0).density(1.0)).abs() < 1e-6);
+/// assert!((d.inverse(0.975) - 1.959963984540054).abs() < 1e-4);
+/// ```
+///
+/// It should hold that `lower < upper`, both finite, that the number of
+/// points is a power of two not smaller than two, and that `0 ≤ damping < 1`;
+/// the function panics otherwise.
+pub fn density_from_cf<F>(cf: F, grid: &Grid) -> ReconstructedDensity
+    where F: Fn(f64) -> (f64, f64)
+{
+    use math;
+    use std::f64::consts::PI;
+
+    let &Grid { lower, upper, points: n, damping } = grid;
+    assert!(lower < upper && lower.is_finite() && upper.is_finite(),
+            "the grid should be a finite interval (got [{}, {}])", lower, upper);
+    assert!(n >= 2 && n.is_power_of_two(),
+            "the number of points should be a power of two (got {})", n);
+    assert!(0.0 <= damping && damping < 1.0,
+            "the damping should be in [0, 1) (got {})", damping);
+
+    let step = (upper - lower) / n as f64;
+    let (frequency, highest) = (2.0 * PI / (n as f64 * step), PI / step);
+    let (mut re, mut im) = (vec![0.0; n], vec![0.0; n]);
+    for k in 0..n {
+        let t = (k as f64 - (n / 2) as f64) * frequency;
+        let u = t.abs() / highest;
+        let taper = if u > 1.0 - damping {
+            (1.0 + (PI * (u - 1.0 + damping) / damping).cos()) / 2.0
+        } else {
+            1.0
+        };
+        let (a, b) = cf(t);
+        let (cos, sin) = ((t * lower).cos(), (t * lower).sin());
+        re[k] = taper * (a * cos + b * sin);
+        im[k] = taper * (b * cos - a * sin);
+    }
+    math::fft(&mut re, &mut im, false);
+
+    let scale = frequency / (2.0 * PI);
+    let mut density = (0..n).map(|j| {
+        let value = scale * if j % 2 == 0 { re[j] } else { -re[j] };
+        value.max(0.0)
+    }).collect::<Vec<_>>();
+    let first = density[0];
+    density.push(first);
+    let mut cumulative = Vec::with_capacity(n + 1);
+    let mut total = 0.0;
+    cumulative.push(0.0);
+    for j in 0..n {
+        total += step * (density[j] + density[j + 1]) / 2.0;
+        cumulative.push(total);
+    }
+    for value in &mut density {
+        *value /= total;
+    }
+    for value in &mut cumulative {
+        *value /= total;
+    }
+    ReconstructedDensity { lower: lower, step: step, density: density, cumulative: cumulative }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use math::{self, Grid};
+    use prelude::*;
+
+    #[test]
+    fn compound_poisson() {
+        let (lambda, mu, sigma) = (40.0, 1.0, 0.5);
+        let cf = |t: f64| {
+            let (modulus, angle) = ((-sigma * sigma * t * t / 2.0).exp(), mu * t);
+            let (re, im) = (modulus * angle.cos() - 1.0, modulus * angle.sin());
+            let scale = (lambda * re).exp();
+            (scale * (lambda * im).cos(), scale * (lambda * im).sin())
+        };
+        let d = math::density_from_cf(&cf, &Grid::from_cf(&cf));
+
+        let mut source = source::seeded(42);
+        let (count, jump) = (Poisson::new(lambda), Gaussian::new(mu, sigma));
+        let n = 20000;
+        let x = (0..n).map(|_| {
+            (0..count.sample(&mut source)).map(|_| jump.sample(&mut source)).sum::<f64>()
+        }).collect::<Vec<_>>();
+        for &y in &[25.0, 35.0, 40.0, 45.0, 55.0] {
+            let p = x.iter().filter(|&&x| x <= y).count() as f64 / n as f64;
+            assert::close(d.distribution(y), p, 0.015);
+        }
+        let mean = d.samples(&mut source, n).iter().sum::<f64>() / n as f64;
+        assert::close(mean, lambda * mu, 0.2);
+    }
+
+    #[test]
+    fn gamma() {
+        let (k, theta) = (5.0, 1.5);
+        let cf = |t: f64| {
+            let (modulus, angle) = ((1.0 + theta * theta * t * t).powf(-k / 2.0),
+                                    k * (theta * t).atan());
+            (modulus * angle.cos(), modulus * angle.sin())
+        };
+        let grid = Grid::from_cf(&cf);
+        assert_eq!(grid.damping, 0.0);
+        let d = math::density_from_cf(&cf, &grid);
+        let e = Gamma::new(k, theta);
+        for (x, y) in d.points().into_iter().filter(|&(x, _)| 0.5 < x && x < 40.0) {
+            assert::close(y, e.density(x), 1e-6);
+            assert::close(d.distribution(x), e.distribution(x), 1e-6);
+        }
+        for &p in &[0.01, 0.25, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse(p), e.inverse(p), 1e-4);
+            assert::close(d.distribution(d.inverse(p)), p, 1e-12);
+        }
+    }
+
+    #[test]
+    fn gaussian() {
+        let (mu, sigma) = (1.0, 2.0);
+        let cf = |t: f64| {
+            let modulus = (-sigma * sigma * t * t / 2.0).exp();
+            (modulus * (mu * t).cos(), modulus * (mu * t).sin())
+        };
+        let grid = Grid::from_cf(&cf);
+        assert::close(grid.lower, mu - 20.0 * sigma, 1e-6);
+        assert::close(grid.upper, mu + 20.0 * sigma, 1e-6);
+        let d = math::density_from_cf(&cf, &grid);
+        let e = Gaussian::new(mu, sigma);
+        for (x, y) in d.points().into_iter().filter(|&(x, _)| (x - mu).abs() < 8.0 * sigma) {
+            assert::close(y, e.density(x), 1e-6);
+            assert::close(d.distribution(x), e.distribution(x), 1e-6);
+        }
+        assert_eq!(d.support(), (grid.lower, grid.upper));
+        assert_eq!((d.distribution(grid.lower - 1.0), d.distribution(grid.upper)), (0.0, 1.0));
+        assert_eq!((d.inverse(0.0), d.inverse(1.0)), (grid.lower, grid.upper));
+        assert_eq!(d.density(grid.upper + 1.0), 0.0);
+    }
+
+    #[test]
+    fn grid() {
+        let cf = |t: f64| ((-t * t / 2.0).exp(), 0.0);
+        let grid = Grid { points: 1 << 12, ..Grid::from_cf(&cf) };
+        let d = math::density_from_cf(&cf, &grid);
+        assert_eq!(d.points().len(), (1 << 12) + 1);
+        assert::close(d.density(0.0), StandardGaussian.density(0.0), 1e-12);
+
+        let cf = |t: f64| (if t == 0.0 { 1.0 } else { t.sin() / t }, 0.0);
+        let grid = Grid { lower: -4.0, upper: 4.0, points: 1 << 12, damping: 0.2 };
+        let d = math::density_from_cf(&cf, &grid);
+        assert::close(d.density(0.0), 0.5, 0.01);
+        assert::close(d.density(2.0), 0.0, 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_points() {
+        let cf = |t: f64| ((-t * t / 2.0).exp(), 0.0);
+        math::density_from_cf(&cf, &Grid { lower: -1.0, upper: 1.0, points: 100, damping: 0.0 });
+    }
+}
@@ -0,0 +1,968 @@
+//! Numerical routines.
+//!
+//! The module collects the numerical building blocks used throughout the
+//! crate, including stable evaluation of expressions in the log domain.
+//!
+//! ```
+//! use probability::math;
+//!
+//! let ln_weights = [-1000.0, -1001.0, -1002.0];
+//! let ln_total = math::log_sum_exp(&ln_weights);
+//! assert!((ln_total + 1000.0 - (1.0 + (-1f64).exp() + (-2f64).exp()).ln()).abs() < 1e-12);
+//! ```
+
+use std::borrow::Borrow;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+#[cfg(feature = "alloc")]
+mod characteristic;
+
+pub mod special;
+
+#[cfg(feature = "alloc")]
+pub use self::characteristic::{Grid, ReconstructedDensity, density_from_cf};
+
+/// Find the smallest `x` such that `f(x) >= p` for a nondecreasing function
+/// `f` by bisection on interval `[a, b]`.
+///
+/// It should hold that `f(a) <= p` and `f(b) >= p`. The search stops once the
+/// interval cannot be halved any further.
+pub fn bisect<F>(f: F, p: f64, mut a: f64, mut b: f64) -> f64 where F: Fn(f64) -> f64 {
+    should!(a <= b);
+    loop {
+        let m = a + (b - a) / 2.0;
+        if m <= a || m >= b {
+            return b;
+        }
+        if f(m) >= p {
+            b = m;
+        } else {
+            a = m;
+        }
+    }
+}
+
+/// Find a root of a function `f` on interval `[a, b]` using Brent’s method,
+/// which combines bisection with the secant method and inverse quadratic
+/// interpolation.
+///
+/// It should hold that `a <= b` and `tolerance > 0`. The function returns
+/// `None` if `f(a)` and `f(b)` are of the same sign or if the search does not
+/// converge within 1000 iterations. The search stops once the root is
+/// bracketed by an interval narrower than `tolerance` plus four units of the
+/// relative machine precision.
+pub fn brent<F>(f: F, mut a: f64, mut b: f64, tolerance: f64) -> Option<f64>
+    where F: Fn(f64) -> f64
+{
+    use std::f64::EPSILON;
+
+    const ITERATIONS: usize = 1000;
+
+    should!(a <= b && tolerance > 0.0);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.is_nan() || fb.is_nan() || (fa > 0.0) == (fb > 0.0) {
+        return None;
+    }
+    let (mut c, mut fc) = (a, fa);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..ITERATIONS {
+        if (fb > 0.0) == (fc > 0.0) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let delta = 2.0 * EPSILON * b.abs() + 0.5 * tolerance;
+        let m = 0.5 * (c - b);
+        if m.abs() <= delta || fb == 0.0 {
+            return Some(b);
+        }
+        if e.abs() >= delta && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let (q, r) = (fa / fc, fb / fc);
+                (s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                 (q - 1.0) * (r - 1.0) * (s - 1.0))
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (delta * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > delta { d } else if m > 0.0 { delta } else { -delta };
+        fb = f(b);
+        if fb.is_nan() {
+            return None;
+        }
+    }
+    None
+}
+
+/// Compute the lower-triangular Cholesky factor of a symmetric matrix stored
+/// in the row-major order.
+///
+/// Only the lower triangle of the matrix is read. The function returns `None`
+/// if the matrix is not positive definite.
+#[cfg(feature = "alloc")]
+pub fn cholesky(matrix: &[f64]) -> Option<Vec<f64>> {
+    let n = (matrix.len() as f64).sqrt() as usize;
+    should!(n * n == matrix.len());
+    let mut lower = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..(i + 1) {
+            let sum = (0..j).fold(matrix[i * n + j], |sum, k| {
+                sum - lower[i * n + k] * lower[j * n + k]
+            });
+            if i == j {
+                if !(sum > 0.0) {
+                    return None;
+                }
+                lower[i * n + i] = sum.sqrt();
+            } else {
+                lower[i * n + j] = sum / lower[j * n + j];
+            }
+        }
+    }
+    Some(lower)
+}
+
+/// Compute the logistic function, `1 / (1 + exp(-x))`.
+///
+/// The exponential is only ever taken of a nonpositive number, so the result
+/// neither overflows nor loses the tiny values attained for very negative `x`.
+pub fn expit(x: f64) -> f64 {
+    if x >= 0.0 {
+        1.0 / (1.0 + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (1.0 + e)
+    }
+}
+
+/// Compute the discrete Fourier transform of a complex sequence in place using
+/// the radix-2 Cooley–Tukey algorithm.
+///
+/// The real and imaginary parts are given separately. The forward transform
+/// uses the kernel `exp(-2πijk / n)`, and the inverse one uses the conjugate
+/// kernel and divides by `n`. It should hold that the parts have the same
+/// length, which is a power of two.
+#[cfg(feature = "alloc")]
+pub fn fft(re: &mut [f64], im: &mut [f64], inverse: bool) {
+    use std::f64::consts::PI;
+
+    let n = re.len();
+    should!(im.len() == n && n.is_power_of_two());
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let twiddles = (0..(n / 2)).map(|k| {
+        let angle = sign * 2.0 * PI * k as f64 / n as f64;
+        (angle.cos(), angle.sin())
+    }).collect::<Vec<_>>();
+    let mut length = 2;
+    while length <= n {
+        let step = n / length;
+        for start in (0..n).step_by(length) {
+            for k in 0..(length / 2) {
+                let (cos, sin) = twiddles[k * step];
+                let (a, b) = (start + k, start + k + length / 2);
+                let (x, y) = (re[b] * cos - im[b] * sin, re[b] * sin + im[b] * cos);
+                re[b] = re[a] - x;
+                im[b] = im[a] - y;
+                re[a] += x;
+                im[a] += y;
+            }
+        }
+        length <<= 1;
+    }
+    if inverse {
+        for i in 0..n {
+            re[i] /= n as f64;
+            im[i] /= n as f64;
+        }
+    }
+}
+
+/// Compute the survival function of the F distribution with `d1` and `d2`
+/// degrees of freedom at `f`.
+///
+/// The function is computed via the regularized incomplete beta function
+/// `I_y(d2 / 2, d1 / 2)` with `y = d2 / (d2 + d1 f)` in the upper tail and via
+/// its complement elsewhere. It should hold that `d1 > 0` and `d2 > 0`.
+pub fn fisher(f: f64, d1: f64, d2: f64) -> f64 {
+    should!(d1 > 0.0 && d2 > 0.0);
+    if f.is_nan() {
+        return f;
+    }
+    if f <= 0.0 {
+        return 1.0;
+    }
+    let (a, b) = (d1 / 2.0, d2 / 2.0);
+    let x = d1 * f / (d2 + d1 * f);
+    if x > a / (a + b) {
+        special::beta_inc(b, a, d2 / (d2 + d1 * f))
+    } else {
+        1.0 - special::beta_inc(a, b, x)
+    }
+}
+
+/// Find a minimum of a unimodal function `f` on interval `[a, b]` using the
+/// golden-section search.
+///
+/// The function is evaluated only at interior points. The search stops once
+/// the bracket is narrower than `tolerance`.
+pub fn golden<F>(f: F, mut a: f64, mut b: f64, tolerance: f64) -> f64 where F: Fn(f64) -> f64 {
+    const RATIO: f64 = 0.6180339887498949;
+    should!(a <= b && tolerance > 0.0);
+    let mut c = b - RATIO * (b - a);
+    let mut d = a + RATIO * (b - a);
+    let (mut fc, mut fd) = (f(c), f(d));
+    while b - a > tolerance {
+        if fc <= fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - RATIO * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + RATIO * (b - a);
+            fd = f(d);
+        }
+    }
+    a + (b - a) / 2.0
+}
+
+/// The result of numerical integration.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integral {
+    /// The estimate of the integral.
+    pub value: f64,
+    /// The estimate of the absolute error.
+    pub error: f64,
+    /// The number of subintervals.
+    pub intervals: usize,
+    /// The flag telling whether the tolerance was reached within the maximum
+    /// number of subintervals.
+    pub converged: bool,
+}
+
+/// Integrate a function over interval `[a, b]` using the adaptive 15-point
+/// Gauss–Kronrod rule.
+///
+/// The subinterval with the largest error is halved until the total error
+/// falls below `tolerance` times the larger of one and the magnitude of the
+/// integral, or until there are 1000 subintervals. Infinite endpoints are
+/// mapped to finite ones by the substitutions `x = a + t / (1 - t)`,
+/// `x = b - (1 - t) / t`, and `x = t / (1 - t²)`. The function is evaluated
+/// only at interior points. It should hold that `a <= b` and `tolerance > 0`.
+///
+/// ```
+/// use probability::math;
+///
+/// let integral = math::integrate(|x| (-x * x).exp(), ::std::f64::NEG_INFINITY,
+///                                ::std::f64::INFINITY, 1e-12);
+/// assert!(integral.converged);
+/// assert!((integral.value - ::std::f64::consts::PI.sqrt()).abs() < 1e-12);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn integrate<F>(f: F, a: f64, b: f64, tolerance: f64) -> Integral where F: Fn(f64) -> f64 {
+    const LIMIT: usize = 1000;
+
+    should!(a <= b && tolerance > 0.0);
+    if a == b {
+        return Integral { value: 0.0, error: 0.0, intervals: 0, converged: true };
+    }
+    let g = |t: f64| match (a.is_finite(), b.is_finite()) {
+        (true, true) => f(t),
+        (true, false) => {
+            let s = 1.0 - t;
+            f(a + t / s) / (s * s)
+        },
+        (false, true) => f(b - (1.0 - t) / t) / (t * t),
+        (false, false) => {
+            let s = 1.0 - t * t;
+            f(t / s) * (1.0 + t * t) / (s * s)
+        },
+    };
+    let (lower, upper) = match (a.is_finite(), b.is_finite()) {
+        (true, true) => (a, b),
+        (false, false) => (-1.0, 1.0),
+        _ => (0.0, 1.0),
+    };
+    let (value, error) = kronrod(&g, lower, upper);
+    let mut intervals = vec![(lower, upper, value, error)];
+    loop {
+        let (mut value, mut error, mut k) = (0.0, 0.0, 0);
+        for (i, &(_, _, v, e)) in intervals.iter().enumerate() {
+            value += v;
+            error += e;
+            if e > intervals[k].3 {
+                k = i;
+            }
+        }
+        let n = intervals.len();
+        let result = |converged| {
+            Integral { value: value, error: error, intervals: n, converged: converged }
+        };
+        if error <= tolerance * value.abs().max(1.0) {
+            return result(true);
+        }
+        if !value.is_finite() || !error.is_finite() || intervals.len() >= LIMIT {
+            return result(false);
+        }
+        let (a, b, _, _) = intervals[k];
+        let m = a + (b - a) / 2.0;
+        if m <= a || m >= b {
+            return result(false);
+        }
+        let ((v, e), (w, d)) = (kronrod(&g, a, m), kronrod(&g, m, b));
+        intervals[k] = (a, m, v, e);
+        intervals.push((m, b, w, d));
+    }
+}
+
+/// Invert a square matrix stored in the row-major order using Gauss–Jordan
+/// elimination with partial pivoting.
+///
+/// The function returns `None` if the matrix is singular.
+#[cfg(feature = "alloc")]
+pub fn invert(matrix: &[f64]) -> Option<Vec<f64>> {
+    let n = (matrix.len() as f64).sqrt() as usize;
+    should!(n * n == matrix.len());
+    let mut a = matrix.to_vec();
+    let mut inverse = (0..(n * n)).map(|k| if k / n == k % n { 1.0 } else { 0.0 })
+                                  .collect::<Vec<_>>();
+    for j in 0..n {
+        let pivot = (j..n).fold(j, |p, i| {
+            if a[i * n + j].abs() > a[p * n + j].abs() { i } else { p }
+        });
+        if a[pivot * n + j] == 0.0 || !a[pivot * n + j].is_finite() {
+            return None;
+        }
+        for k in 0..n {
+            a.swap(j * n + k, pivot * n + k);
+            inverse.swap(j * n + k, pivot * n + k);
+        }
+        let scale = 1.0 / a[j * n + j];
+        for k in 0..n {
+            a[j * n + k] *= scale;
+            inverse[j * n + k] *= scale;
+        }
+        for i in (0..n).filter(|&i| i != j) {
+            let factor = a[i * n + j];
+            for k in 0..n {
+                a[i * n + k] -= factor * a[j * n + k];
+                inverse[i * n + k] -= factor * inverse[j * n + k];
+            }
+        }
+    }
+    Some(inverse)
+}
+
+/// Compute `ln(1 - exp(x))` for `x <= 0`.
+///
+/// Following Mächler, the function switches from `ln(-expm1(x))` to
+/// `ln1p(-exp(x))` at `x = -ln 2`, which keeps full relative accuracy on both
+/// sides. It should hold that `x <= 0`; the function panics otherwise.
+pub fn log1mexp(x: f64) -> f64 {
+    assert!(x <= 0.0, "the argument should be nonpositive (got {})", x);
+    if x > -::std::f64::consts::LN_2 { (-x.exp_m1()).ln() } else { (-x.exp()).ln_1p() }
+}
+
+/// Compute `ln(exp(a) + exp(b))` without overflow or underflow.
+pub fn log_add_exp(a: f64, b: f64) -> f64 {
+    let (max, min) = if a > b { (a, b) } else { (b, a) };
+    if max.is_infinite() {
+        return max;
+    }
+    max + (min - max).exp().ln_1p()
+}
+
+/// Compute `ln(exp(a) - exp(b))` without overflow or underflow.
+///
+/// It should hold that `a >= b`; the function panics otherwise. The result is
+/// negative infinity if `a == b`.
+pub fn log_diff_exp(a: f64, b: f64) -> f64 {
+    assert!(a >= b, "the minuend should not be less than the subtrahend (got {} and {})", a, b);
+    if b == ::std::f64::NEG_INFINITY {
+        return a;
+    }
+    a + log1mexp(b - a)
+}
+
+/// Compute `ln(exp(values[0]) + exp(values[1]) + ...)` without overflow or
+/// underflow.
+///
+/// The terms are shifted by the largest one, which then contributes exactly
+/// one to the sum, so the logarithm is taken by `ln1p` of the others. The
+/// result is negative infinity if `values` is empty or all its elements are
+/// negative infinity, and positive infinity if any is positive infinity.
+pub fn log_sum_exp(values: &[f64]) -> f64 {
+    let mut position = None;
+    for (i, &value) in values.iter().enumerate() {
+        if value.is_nan() {
+            return value;
+        }
+        match position {
+            Some(j) if values[j] >= value => {},
+            _ => position = Some(i),
+        }
+    }
+    let (j, max) = match position {
+        Some(j) if values[j].is_finite() => (j, values[j]),
+        Some(j) => return values[j],
+        _ => return ::std::f64::NEG_INFINITY,
+    };
+    let rest = sum(values.iter().enumerate().filter(|&(i, _)| i != j)
+                                .map(|(_, &value)| (value - max).exp()));
+    max + rest.ln_1p()
+}
+
+/// Compute the log-odds, `ln(p / (1 - p))`.
+///
+/// The function is the inverse of `expit`. It returns negative infinity at
+/// zero and positive infinity at one. It should hold that `0 <= p <= 1`; the
+/// function panics otherwise.
+pub fn logit(p: f64) -> f64 {
+    assert!(0.0 <= p && p <= 1.0, "the probability should be in [0, 1] (got {})", p);
+    p.ln() - (-p).ln_1p()
+}
+
+/// Sum up a function of a slice of numbers using pairwise summation.
+///
+/// The error grows logarithmically with the length of the slice, and the
+/// short runs at the bottom of the recursion are accumulated in several
+/// independent lanes, which the compiler is free to vectorize.
+pub fn pairwise<F>(values: &[f64], f: F) -> f64 where F: Fn(f64) -> f64 + Copy {
+    const BLOCK: usize = 128;
+    const LANES: usize = 8;
+    if values.len() > BLOCK {
+        let (left, right) = values.split_at(values.len() / 2);
+        return pairwise(left, f) + pairwise(right, f);
+    }
+    let mut lanes = [0.0; LANES];
+    let mut chunks = values.chunks_exact(LANES);
+    for chunk in chunks.by_ref() {
+        for i in 0..LANES {
+            lanes[i] += f(chunk[i]);
+        }
+    }
+    let sum = chunks.remainder().iter().fold(0.0, |sum, &value| sum + f(value));
+    lanes.iter().fold(sum, |sum, &lane| sum + lane)
+}
+
+/// Integrate a function over interval `[a, b]` using the composite Simpson
+/// rule with `n` subintervals.
+///
+/// The number of subintervals is rounded up to the nearest even number.
+pub fn simpson<F>(f: F, a: f64, b: f64, n: usize) -> f64 where F: Fn(f64) -> f64 {
+    let n = n + n % 2;
+    let h = (b - a) / n as f64;
+    let mut sum = f(a) + f(b);
+    for i in 1..n {
+        sum += if i % 2 == 0 { 2.0 } else { 4.0 } * f(a + i as f64 * h);
+    }
+    sum * h / 3.0
+}
+
+/// Compute the survival function of Student’s t distribution with `nu`
+/// degrees of freedom at `t`.
+///
+/// The function is computed via the regularized incomplete beta function
+/// `I_x(ν / 2, 1 / 2)` with `x = ν / (ν + t²)` or, near the center, via its
+/// complement in terms of `1 - x`, which keeps it accurate everywhere. It
+/// should hold that `nu > 0`.
+pub fn student(t: f64, nu: f64) -> f64 {
+    should!(nu > 0.0);
+    if t.is_nan() {
+        return t;
+    }
+    let (a, b) = (nu / 2.0, 0.5);
+    let tail = if t * t > nu {
+        0.5 * special::beta_inc(a, b, nu / (nu + t * t))
+    } else {
+        0.5 - 0.5 * special::beta_inc(b, a, t * t / (nu + t * t))
+    };
+    if t > 0.0 { tail } else { 1.0 - tail }
+}
+
+/// Compute the inverse of the distribution function of Student’s t
+/// distribution with `nu` degrees of freedom at `p`.
+///
+/// The quantile is obtained by inverting the regularized incomplete beta
+/// function in whichever of its two forms keeps the argument away from one,
+/// and it is refined by Newton’s method. It should hold that `0 <= p <= 1`
+/// and `nu > 0`.
+pub fn student_inverse(p: f64, nu: f64) -> f64 {
+    use special::Gamma;
+
+    should!(0.0 <= p && p <= 1.0 && nu > 0.0);
+    if p == 0.0 || p == 1.0 {
+        return if p == 0.0 { ::std::f64::NEG_INFINITY } else { ::std::f64::INFINITY };
+    }
+    let target = p.min(1.0 - p);
+    let (a, b) = (nu / 2.0, 0.5);
+    let mut t = if target < 0.25 {
+        let x = special::inv_beta_inc(a, b, 2.0 * target);
+        (nu * (1.0 - x) / x).sqrt()
+    } else {
+        let y = special::inv_beta_inc(b, a, 1.0 - 2.0 * target);
+        (nu * y / (1.0 - y)).sqrt()
+    };
+    let ln_norm = Gamma::ln_gamma((nu + 1.0) / 2.0).0 - Gamma::ln_gamma(a).0 -
+                  0.5 * (nu * ::std::f64::consts::PI).ln();
+    for _ in 0..20 {
+        let density = (ln_norm - (nu + 1.0) / 2.0 * (t * t / nu).ln_1p()).exp();
+        let step = (student(t, nu) - target) / density;
+        t += step;
+        if step.abs() <= 1e-15 * t.abs() {
+            break;
+        }
+    }
+    if p < 0.5 { -t } else { t }
+}
+
+/// Sum up a sequence of numbers using compensated summation.
+///
+/// The Kahan–Babuška variant is used, whose error does not grow with the
+/// length of the sequence. Infinite and NaN terms propagate as in plain
+/// summation. Both numbers and references to numbers are accepted, so a slice
+/// can be passed directly.
+pub fn sum<I>(values: I) -> f64 where I: IntoIterator, I::Item: Borrow<f64> {
+    let (mut sum, mut compensation) = (0.0f64, 0.0);
+    for value in values {
+        let value = *value.borrow();
+        let total = sum + value;
+        if sum.abs() >= value.abs() {
+            compensation += (sum - total) + value;
+        } else {
+            compensation += (value - total) + sum;
+        }
+        sum = total;
+    }
+    if sum.is_finite() { sum + compensation } else { sum }
+}
+
+/// Compute the trigamma function, that is, the derivative of the digamma
+/// function.
+///
+/// The argument is shifted above ten by the recurrence `ψ'(x) = ψ'(x + 1) +
+/// 1 / x^2`, after which the asymptotic expansion is used. It should hold
+/// that `x > 0`.
+pub fn trigamma(mut x: f64) -> f64 {
+    const LIMIT: f64 = 10.0;
+
+    should!(x > 0.0);
+    let mut sum = 0.0;
+    while x < LIMIT {
+        sum += 1.0 / (x * x);
+        x += 1.0;
+    }
+    let r = 1.0 / (x * x);
+    let series = 1.0 / 6.0 - r * (1.0 / 30.0 - r * (1.0 / 42.0 - r * (1.0 / 30.0 - r * (
+        5.0 / 66.0 - r * (691.0 / 2730.0 - r * 7.0 / 6.0)))));
+    sum + (1.0 + (0.5 + series / x) / x) / x
+}
+
+// Integrate a function over interval `[a, b]` using the 15-point Kronrod rule
+// and estimate the error by comparing it with the embedded 7-point Gauss rule
+// as in QUADPACK.
+#[cfg(feature = "alloc")]
+fn kronrod<F>(f: &F, a: f64, b: f64) -> (f64, f64) where F: Fn(f64) -> f64 {
+    use std::f64::{EPSILON, MIN_POSITIVE};
+
+    const NODES: [f64; 8] = [
+        0.991455371120812639206854697526329, 0.949107912342758524526189684047851,
+        0.864864423359769072789712788640926, 0.741531185599394439863864773280788,
+        0.586087235467691130294144845693013, 0.405845151377397166906606412076961,
+        0.207784955007898467600689403773245, 0.0,
+    ];
+    const KRONROD: [f64; 8] = [
+        0.022935322010529224963732008058970, 0.063092092629978553290700663189204,
+        0.104790010322250183839876322541518, 0.140653259715525918745189590510238,
+        0.169004726639267902826583426598550, 0.190350578064785409913256402421014,
+        0.204432940075298892414161999234649, 0.209482141084727828012999174891714,
+    ];
+    const GAUSS: [f64; 4] = [
+        0.129484966168869693270611432679082, 0.279705391489276667901467771423780,
+        0.381830050505118944950369775488975, 0.417959183673469387755102040816327,
+    ];
+
+    let (center, half) = ((a + b) / 2.0, (b - a) / 2.0);
+    let mut values = [(0.0, 0.0); 7];
+    let middle = f(center);
+    let (mut gauss, mut kronrod) = (GAUSS[3] * middle, KRONROD[7] * middle);
+    let mut absolute = kronrod.abs();
+    for (i, value) in values.iter_mut().enumerate() {
+        let (left, right) = (f(center - half * NODES[i]), f(center + half * NODES[i]));
+        *value = (left, right);
+        kronrod += KRONROD[i] * (left + right);
+        absolute += KRONROD[i] * (left.abs() + right.abs());
+        if i % 2 == 1 {
+            gauss += GAUSS[i / 2] * (left + right);
+        }
+    }
+    let mean = kronrod / 2.0;
+    let deviation = values.iter().zip(&KRONROD).fold(KRONROD[7] * (middle - mean).abs(),
+                                                     |sum, (&(left, right), &weight)| {
+        sum + weight * ((left - mean).abs() + (right - mean).abs())
+    });
+    let (absolute, deviation) = (absolute * half.abs(), deviation * half.abs());
+    let mut error = ((kronrod - gauss) * half).abs();
+    if deviation != 0.0 && error != 0.0 {
+        error = deviation * (200.0 * error / deviation).powf(1.5).min(1.0);
+    }
+    if absolute > MIN_POSITIVE / (50.0 * EPSILON) {
+        error = error.max(50.0 * EPSILON * absolute);
+    }
+    (kronrod * half, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    #[test]
+    fn bisect() {
+        assert::close(super::bisect(|x| x * x, 2.0, 0.0, 2.0), 2f64.sqrt(), 1e-15);
+        assert_eq!(super::bisect(|x| if x < 1.0 { 0.0 } else { 1.0 }, 0.5, -3.0, 7.0), 1.0);
+    }
+
+    #[test]
+    fn brent() {
+        use super::brent;
+
+        assert::close(brent(|x| x * x - 2.0, 0.0, 2.0, 1e-15).unwrap(), 2f64.sqrt(), 1e-15);
+        assert::close(brent(|x| x.cos() - x, 0.0, 1.0, 1e-15).unwrap(), 0.7390851332151607,
+                      1e-15);
+        assert::close(brent(|x| (x - 1.0).powi(3), -10.0, 5.0, 1e-12).unwrap(), 1.0, 1e-4);
+        assert_eq!(brent(|x| x - 1.0, 1.0, 3.0, 1e-12), Some(1.0));
+        assert_eq!(brent(|x| x * x + 1.0, -1.0, 1.0, 1e-12), None);
+        assert_eq!(brent(|x| if x < 0.5 { -1.0 } else { ::std::f64::NAN }, 0.0, 1.0, 1e-12),
+                   None);
+    }
+
+    #[test]
+    fn cholesky() {
+        assert_eq!(super::cholesky(&[4.0]), Some(vec![2.0]));
+        let lower = super::cholesky(&[4.0, 2.0, 2.0, 2.0, 5.0, 1.0, 2.0, 1.0, 6.0]).unwrap();
+        assert::close(&lower, &vec![2.0, 0.0, 0.0, 1.0, 2.0, 0.0, 1.0, 0.0, 5f64.sqrt()],
+                      1e-15);
+        assert_eq!(super::cholesky(&[1.0, 2.0, 2.0, 1.0]), None);
+        assert_eq!(super::cholesky(&[0.0]), None);
+        assert_eq!(super::cholesky(&[::std::f64::NAN]), None);
+    }
+
+    #[test]
+    fn expit() {
+        use super::expit;
+
+        assert_eq!(expit(0.0), 0.5);
+        assert_eq!(expit(800.0), 1.0);
+        assert_eq!(expit(-800.0), 0.0);
+        assert!(expit(-745.0) > 0.0);
+        assert::close(expit(-40.0) / 4.2483542552915889773e-18, 1.0, 1e-15);
+        assert::close(expit(36.0), 0.999999999999999768047717, 1e-16);
+        assert_eq!(expit(::std::f64::INFINITY), 1.0);
+        assert_eq!(expit(::std::f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn fft() {
+        use std::f64::consts::PI;
+
+        let n = 16;
+        let x = (0..n).map(|i| ((i * i) as f64).sin() + 0.5 * i as f64).collect::<Vec<_>>();
+        let y = (0..n).map(|i| (i as f64).cos()).collect::<Vec<_>>();
+        let (mut re, mut im) = (x.clone(), y.clone());
+        super::fft(&mut re, &mut im, false);
+        for k in 0..n {
+            let (mut a, mut b) = (0.0, 0.0);
+            for j in 0..n {
+                let angle = -2.0 * PI * (j * k) as f64 / n as f64;
+                a += x[j] * angle.cos() - y[j] * angle.sin();
+                b += x[j] * angle.sin() + y[j] * angle.cos();
+            }
+            assert::close(re[k], a, 1e-12);
+            assert::close(im[k], b, 1e-12);
+        }
+        super::fft(&mut re, &mut im, true);
+        assert::close(&re, &x, 1e-14);
+        assert::close(&im, &y, 1e-14);
+
+        let (mut re, mut im) = (vec![3.0], vec![-1.0]);
+        super::fft(&mut re, &mut im, false);
+        assert_eq!((re[0], im[0]), (3.0, -1.0));
+    }
+
+    #[test]
+    fn fisher() {
+        use super::fisher;
+
+        let cases = [(3.5, 2.0, 12.0, 0.063469615969142973431),
+                     (0.25, 4.0, 30.0, 0.90738072830931983962),
+                     (40.0, 3.0, 8.0, 0.00003659959038092365676),
+                     (1e-6, 5.0, 5.0, 0.99999999999999456753),
+                     (200.0, 1.0, 100.0, 1.3512423796021731251e-25),
+                     (0.9, 50.0, 2.0, 0.66281643662887203763)];
+        for &(f, d1, d2, p) in &cases {
+            assert::close(fisher(f, d1, d2), p, 1e-13 * p);
+        }
+        assert_eq!(fisher(0.0, 3.0, 7.0), 1.0);
+    }
+
+    #[test]
+    fn golden() {
+        let x = super::golden(|x| (x - 0.3).powi(2) + 1.0, -2.0, 5.0, 1e-10);
+        assert::close(x, 0.3, 1e-7);
+        let x = super::golden(|x| x.exp() - 2.0 * x, 0.0, 3.0, 1e-10);
+        assert::close(x, 2f64.ln(), 1e-7);
+    }
+
+    #[test]
+    fn integrate() {
+        use special::Gamma;
+        use std::f64::consts::PI;
+        use std::f64::{INFINITY, NEG_INFINITY};
+        use super::integrate;
+
+        let check = |f: &dyn Fn(f64) -> f64, a, b, value: f64, tolerance| {
+            let integral = integrate(f, a, b, 1e-12);
+            assert!(integral.converged);
+            assert!((integral.value - value).abs() <= integral.error.max(1e-15));
+            assert::close(integral.value, value, tolerance);
+        };
+
+        check(&|x| x.sin(), 0.0, PI, 2.0, 1e-14);
+        check(&|x| x.sqrt(), 0.0, 1.0, 2.0 / 3.0, 1e-12);
+        check(&|x| if x < 0.3 { 1.0 } else { 0.0 }, 0.0, 1.0, 0.3, 1e-12);
+        check(&|x| (-x * x / 2.0).exp(), NEG_INFINITY, INFINITY, (2.0 * PI).sqrt(), 1e-14);
+        check(&|x| (-x * x / 2.0).exp(), 0.0, INFINITY, (PI / 2.0).sqrt(), 1e-14);
+        check(&|x| (-(x - 3.0).powi(2)).exp(), NEG_INFINITY, 3.0, PI.sqrt() / 2.0, 1e-14);
+        for &z in &[0.5, 1.0, 2.5, 5.0, 10.0] {
+            let gamma = Gamma::gamma(z);
+            check(&|t| t.powf(z - 1.0) * (-t).exp(), 0.0, INFINITY, gamma, 1e-11 * gamma);
+        }
+
+        let integral = integrate(|x| x.exp(), 1.0, 1.0, 1e-12);
+        assert_eq!((integral.value, integral.converged), (0.0, true));
+
+        let integral = integrate(|x| 1.0 / x, 0.0, 1.0, 1e-12);
+        assert!(!integral.converged);
+        assert_eq!(integral.intervals, 1000);
+        assert!(integral.value.is_finite() && integral.error.is_finite());
+    }
+
+    #[test]
+    fn invert() {
+        assert_eq!(super::invert(&[2.0]), Some(vec![0.5]));
+        let inverse = super::invert(&[0.0, 2.0, 4.0, 1.0]).unwrap();
+        assert::close(&inverse, &vec![-0.125, 0.25, 0.5, 0.0], 1e-15);
+        let matrix = [4.0, 1.0, 2.0, 1.0, 3.0, 0.5, 2.0, 0.5, 5.0];
+        let inverse = super::invert(&matrix).unwrap();
+        let product = (0..9).map(|k| {
+            (0..3).fold(0.0, |sum, l| sum + matrix[k / 3 * 3 + l] * inverse[l * 3 + k % 3])
+        }).collect::<Vec<_>>();
+        assert::close(&product, &vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0], 1e-15);
+        assert_eq!(super::invert(&[1.0, 2.0, 2.0, 4.0]), None);
+    }
+
+    #[test]
+    fn log1mexp() {
+        use super::log1mexp;
+
+        let ln_2 = ::std::f64::consts::LN_2;
+        let x = vec![-1e-20, -1e-10, -0.5, -ln_2 + 1e-4, -ln_2 - 1e-4, -1.0, -10.0, -40.0];
+        let y = vec![
+            -46.05170185988091368, -23.02585092999045684, -0.93275212956718857189,
+            -0.69324719056094542994, -0.69304719055894545194, -0.45867514538708189102,
+            -0.000045400960370489209504, -4.2483542552915890044e-18,
+        ];
+        for (&x, &y) in x.iter().zip(&y) {
+            assert::close(log1mexp(x) / y, 1.0, 1e-15);
+        }
+        assert_eq!(log1mexp(-745.0), -(-745f64).exp());
+        assert_eq!(log1mexp(-800.0), 0.0);
+        assert_eq!(log1mexp(0.0), ::std::f64::NEG_INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "the argument should be nonpositive")]
+    fn log1mexp_positive() {
+        super::log1mexp(1e-3);
+    }
+
+    #[test]
+    fn log_add_exp() {
+        use super::log_add_exp;
+        use std::f64::consts::LN_2;
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        assert_eq!(log_add_exp(-745.0, -745.0), -745.0 + LN_2);
+        assert_eq!(log_add_exp(1000.0, 1000.0), 1000.0 + LN_2);
+        assert::close(log_add_exp(0.0, -40.0) / 4.2483542552915889863e-18, 1.0, 1e-15);
+        assert_eq!(log_add_exp(-40.0, 0.0), log_add_exp(0.0, -40.0));
+        assert_eq!(log_add_exp(3.0, NEG_INFINITY), 3.0);
+        assert_eq!(log_add_exp(NEG_INFINITY, NEG_INFINITY), NEG_INFINITY);
+        assert_eq!(log_add_exp(INFINITY, 0.0), INFINITY);
+        assert!(log_add_exp(::std::f64::NAN, 0.0).is_nan());
+    }
+
+    #[test]
+    fn log_diff_exp() {
+        use super::log_diff_exp;
+        use std::f64::NEG_INFINITY;
+
+        assert::close(log_diff_exp(-745.0, -746.0), -745.45867514538708189, 1e-13);
+        assert::close(log_diff_exp(0.0, -1e-20) / -46.05170185988091368, 1.0, 1e-15);
+        assert::close(log_diff_exp(1000.0, 999.0), 1000.0 - 0.45867514538708189102, 1e-12);
+        assert_eq!(log_diff_exp(2.0, NEG_INFINITY), 2.0);
+        assert_eq!(log_diff_exp(2.0, 2.0), NEG_INFINITY);
+        assert_eq!(log_diff_exp(NEG_INFINITY, NEG_INFINITY), NEG_INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "the minuend should not be less than the subtrahend")]
+    fn log_diff_exp_negative() {
+        super::log_diff_exp(-1.0, 0.0);
+    }
+
+    #[test]
+    fn log_sum_exp() {
+        use super::log_sum_exp;
+        use std::f64::{INFINITY, NAN, NEG_INFINITY};
+
+        assert_eq!(log_sum_exp(&[]), NEG_INFINITY);
+        assert_eq!(log_sum_exp(&[NEG_INFINITY, NEG_INFINITY]), NEG_INFINITY);
+        assert_eq!(log_sum_exp(&[1.5]), 1.5);
+        assert_eq!(log_sum_exp(&[NEG_INFINITY, 1.5]), 1.5);
+        assert::close(log_sum_exp(&[-745.0, -745.0, -745.0]), -743.90138771133189031, 1e-13);
+        assert::close(log_sum_exp(&[-40.0, 0.0, -40.0]) / 8.4967085105831779546e-18, 1.0,
+                      1e-15);
+        assert_eq!(log_sum_exp(&[-745.0, -700.0, -1000.0]), -700.0);
+        assert_eq!(log_sum_exp(&[1000.0, 0.0]), 1000.0);
+        assert_eq!(log_sum_exp(&[0.0, INFINITY]), INFINITY);
+        assert!(log_sum_exp(&[0.0, NAN, INFINITY]).is_nan());
+        let values = (0..1000).map(|i| -(i as f64) / 100.0).collect::<Vec<_>>();
+        let expected = (1.0 / (1.0 - (-0.01f64).exp()) * (1.0 - (-10f64).exp())).ln();
+        assert::close(log_sum_exp(&values), expected, 1e-13);
+    }
+
+    #[test]
+    fn logit() {
+        use super::{expit, logit};
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        assert_eq!(logit(0.5), 0.0);
+        assert_eq!(logit(0.0), NEG_INFINITY);
+        assert_eq!(logit(1.0), INFINITY);
+        assert::close(logit(1e-300), -690.77552789821370521, 1e-12);
+        assert::close(logit(0.999999), 13.815509557963774104, 1e-9);
+        for i in 0..36 {
+            let x = i as f64 - 30.0;
+            assert::close(logit(expit(x)), x, 1e-12);
+        }
+    }
+
+    #[test]
+    fn pairwise() {
+        assert_eq!(super::pairwise(&[], |x| x), 0.0);
+        assert_eq!(super::pairwise(&[1.0, 2.0, 3.0], |x| x * x), 14.0);
+        assert_eq!(super::pairwise(&vec![0.1; 1_000_000], |x| x), 100000.0);
+    }
+
+    #[test]
+    fn simpson() {
+        assert::close(super::simpson(|x| x * x * x, 0.0, 2.0, 2), 4.0, 1e-15);
+        assert::close(super::simpson(|x| x.sin(), 0.0, ::std::f64::consts::PI, 101), 2.0, 1e-7);
+    }
+
+    #[test]
+    fn student() {
+        use super::{student, student_inverse};
+
+        assert::close(student(2.5705818356363148, 5.0), 0.025, 1e-16);
+        assert::close(student(-0.25712304263817058, 18.0), 0.6, 1e-15);
+        assert::close(student(1e-9, 40.0), 0.5 - 3.9645692469934390e-10, 1e-17);
+        assert_eq!(student(0.0, 3.0), 0.5);
+
+        let cases = [(0.975, 5.0, 2.5705818356363148), (0.6, 18.0, 0.25712304263817058),
+                     (0.999999, 3.5, 61.045121207218698), (0.95, 12.345, 1.7781466086094707),
+                     (1e-8, 1.0, -31830988.618379067)];
+        for &(p, nu, t) in &cases {
+            assert::close(student_inverse(p, nu), t, 1e-14 * t.abs());
+        }
+        assert_eq!(student_inverse(0.5, 7.0), 0.0);
+    }
+
+    #[test]
+    fn sum() {
+        assert_eq!(super::sum(Vec::<f64>::new()), 0.0);
+        assert_eq!(super::sum(&[1e100, 1.0, -1e100]), 1.0);
+        assert_eq!(super::sum(vec![1.0, 1e100, 1.0, -1e100]), 2.0);
+        assert_eq!(super::sum((0..1_000_000).map(|_| 0.1)), 100000.0);
+        assert_eq!(super::sum(vec![1.0, ::std::f64::NEG_INFINITY, 2.0]), ::std::f64::NEG_INFINITY);
+        assert!(super::sum(vec![::std::f64::INFINITY, ::std::f64::NEG_INFINITY]).is_nan());
+    }
+
+    #[test]
+    fn trigamma() {
+        let x = vec![1e-3, 0.5, 1.0, 2.5, 3.0, 10.0, 1e3];
+        let y = vec![
+            1.00000164253319582734467e+06, 4.934802200544679309417245e+00,
+            1.644934066848226436472415e+00, 4.903577561002348649728011e-01,
+            3.949340668482264364724152e-01, 1.051663356816857461222010e-01,
+            1.000500166666633333357143e-03,
+        ];
+        for (&x, &y) in x.iter().zip(&y) {
+            assert::close(super::trigamma(x) / y, 1.0, 1e-14);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use statistics;
+
+/// A sequence of draws of a Markov chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chain {
+    /// The draws, each being a point of the state space.
+    pub draws: Vec<Vec<f64>>,
+    /// The fraction of the proposals that were accepted.
+    pub acceptance_rate: f64,
+    /// The names of the dimensions, which is empty if the dimensions are
+    /// unnamed.
+    pub names: Vec<String>,
+}
+
+impl Chain {
+    /// Return the number of dimensions of the state space.
+    #[inline]
+    pub fn dimension(&self) -> usize {
+        self.draws.first().map_or(0, |draw| draw.len())
+    }
+
+    /// Estimate the effective sample size of each dimension.
+    ///
+    /// The integrated autocorrelation time is estimated using Geyer’s initial
+    /// monotone sequence, that is, by summing the autocorrelations in
+    /// consecutive pairs until a pair sum becomes nonpositive, with the pair
+    /// sums forced to be nonincreasing. The estimate is NaN for a dimension
+    /// whose draws are constant.
+    pub fn effective_sample_sizes(&self) -> Vec<f64> {
+        (0..self.dimension()).map(|i| effective_sample_size(&self.trace(i))).collect()
+    }
+
+    /// Check if there are no draws.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    /// Return the number of draws.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.draws.len()
+    }
+
+    /// Compute the mean of each dimension.
+    pub fn means(&self) -> Vec<f64> {
+        (0..self.dimension()).map(|i| statistics::mean(&self.trace(i))).collect()
+    }
+
+    /// Find the dimension named `name`.
+    #[inline]
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|other| other == name)
+    }
+
+    /// Compute the standard deviation of each dimension.
+    pub fn sds(&self) -> Vec<f64> {
+        (0..self.dimension()).map(|i| statistics::sd(&self.trace(i))).collect()
+    }
+
+    /// Return the draws of dimension `i`.
+    pub fn trace(&self, i: usize) -> Vec<f64> {
+        self.draws.iter().map(|draw| draw[i]).collect()
+    }
+}
+
+fn effective_sample_size(x: &[f64]) -> f64 {
+    let n = x.len();
+    if n < 4 {
+        return n as f64;
+    }
+    let rho = statistics::acf_fft(x, n - 1);
+    if rho[0].is_nan() {
+        return rho[0];
+    }
+    let (mut sum, mut last) = (0.0, ::std::f64::INFINITY);
+    let mut k = 0;
+    while k + 1 < n {
+        let pair = (rho[k] + rho[k + 1]).min(last);
+        if pair <= 0.0 {
+            break;
+        }
+        sum += pair;
+        last = pair;
+        k += 2;
+    }
+    n as f64 / (2.0 * sum - 1.0).max(1.0 / n as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::Chain;
+
+    #[test]
+    fn effective_sample_sizes() {
+        let mut source = source::seeded(42);
+        let n = 20000;
+        let white = Gaussian::new(0.0, 1.0).samples(&mut source, n);
+        let mut ar = vec![0.0; n];
+        for i in 1..n {
+            ar[i] = 0.5 * ar[i - 1] + white[i];
+        }
+        let chain = Chain {
+            draws: white.iter().zip(&ar).map(|(&x, &y)| vec![x, y, 1.0]).collect(),
+            acceptance_rate: 1.0,
+            names: vec![],
+        };
+        let sizes = chain.effective_sample_sizes();
+        assert::close(sizes[0] / n as f64, 1.0, 0.1);
+        assert::close(sizes[1] / n as f64, 1.0 / 3.0, 0.05);
+        assert!(sizes[2].is_nan());
+    }
+
+    #[test]
+    fn statistics() {
+        let chain = Chain {
+            draws: vec![vec![1.0, 2.0], vec![3.0, 2.0], vec![5.0, 2.0]],
+            acceptance_rate: 0.5,
+            names: vec!["a".to_string(), "b".to_string()],
+        };
+        assert_eq!((chain.len(), chain.dimension()), (3, 2));
+        assert_eq!(chain.trace(0), vec![1.0, 3.0, 5.0]);
+        assert_eq!((chain.position("b"), chain.position("c")), (Some(1), None));
+        assert_eq!(chain.means(), vec![3.0, 2.0]);
+        assert_eq!(chain.sds(), vec![2.0, 0.0]);
+    }
+}
@@ -0,0 +1,172 @@
+use mcmc::Chain;
+use source::Source;
+
+/// A Gibbs sampler.
+///
+/// The state consists of named scalar blocks. Each block is registered along
+/// with a function drawing it from its full conditional distribution given
+/// the current state, and a sweep updates the blocks in the order of their
+/// registration, each one seeing the values already updated in the sweep.
+///
+/// ```
+/// use probability::mcmc::Gibbs;
+/// use probability::prelude::*;
+///
+/// let rho = 0.5;
+/// let deviation = (1.0f64 - rho * rho).sqrt();
+/// let mut gibbs = Gibbs::new();
+/// gibbs.add("x", 0.0, move |state, source| {
+///     Gaussian::new(rho * state.get("y"), deviation).sample(source)
+/// });
+/// gibbs.add("y", 0.0, move |state, source| {
+///     Gaussian::new(rho * state.get("x"), deviation).sample(source)
+/// });
+/// let chain = gibbs.run(&mut source::seeded(42), 10000, 100);
+/// assert!(chain.means().iter().all(|&mean| mean.abs() < 0.1));
+/// ```
+pub struct Gibbs<'l, S> {
+    names: Vec<String>,
+    values: Vec<f64>,
+    updates: Vec<Update<'l, S>>,
+}
+
+/// The state of a Gibbs sampler seen by the full conditionals.
+pub struct GibbsState<'l> {
+    names: &'l [String],
+    values: &'l [f64],
+}
+
+type Update<'l, S> = Box<dyn Fn(&GibbsState, &mut S) -> f64 + 'l>;
+
+impl<'l, S> Gibbs<'l, S> where S: Source {
+    /// Create a sampler with no blocks.
+    #[inline]
+    pub fn new() -> Self {
+        Gibbs { names: vec![], values: vec![], updates: vec![] }
+    }
+
+    /// Register a block named `name` with the initial value `initial` and the
+    /// full conditional `update`.
+    ///
+    /// It should hold that the name is not taken; the function panics
+    /// otherwise.
+    pub fn add<F>(&mut self, name: &str, initial: f64, update: F)
+        where F: Fn(&GibbsState, &mut S) -> f64 + 'l
+    {
+        assert!(self.names.iter().all(|other| other != name),
+                "the block should have a unique name (got {})", name);
+        self.names.push(name.to_string());
+        self.values.push(initial);
+        self.updates.push(Box::new(update));
+    }
+
+    /// Draw a chain of `samples` sweeps after discarding `burn_in` sweeps.
+    ///
+    /// The dimensions of the chain are the blocks named in the order of their
+    /// registration. The sampler continues from the last state in subsequent
+    /// calls.
+    pub fn run(&mut self, source: &mut S, samples: usize, burn_in: usize) -> Chain {
+        for _ in 0..burn_in {
+            self.sweep(source);
+        }
+        let mut draws = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            self.sweep(source);
+            draws.push(self.values.clone());
+        }
+        Chain { draws: draws, acceptance_rate: 1.0, names: self.names.clone() }
+    }
+
+    /// Return the current state.
+    #[inline]
+    pub fn state(&self) -> GibbsState<'_> {
+        GibbsState { names: &self.names, values: &self.values }
+    }
+
+    fn sweep(&mut self, source: &mut S) {
+        for i in 0..self.updates.len() {
+            let value = {
+                let state = GibbsState { names: &self.names, values: &self.values };
+                (self.updates[i])(&state, source)
+            };
+            self.values[i] = value;
+        }
+    }
+}
+
+impl<'l, S> Default for Gibbs<'l, S> where S: Source {
+    #[inline]
+    fn default() -> Self {
+        Gibbs::new()
+    }
+}
+
+impl<'l> GibbsState<'l> {
+    /// Return the value of the block named `name`.
+    ///
+    /// It should hold that the block exists; the function panics otherwise.
+    pub fn get(&self, name: &str) -> f64 {
+        match self.names.iter().position(|other| other == name) {
+            Some(i) => self.values[i],
+            None => panic!("the block should exist (got {})", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::{Gibbs, GibbsState};
+    use prelude::*;
+    use statistics;
+
+    #[test]
+    fn gaussian() {
+        let (rho, deviation) = (0.6, 0.8);
+        let x = move |state: &GibbsState, source: &mut source::Xoshiro256PlusPlus| {
+            Gaussian::new(rho * state.get("y"), deviation).sample(source)
+        };
+        let y = move |state: &GibbsState, source: &mut source::Xoshiro256PlusPlus| {
+            Gaussian::new(rho * state.get("x"), deviation).sample(source)
+        };
+        let (mut one, mut two) = (Gibbs::new(), Gibbs::new());
+        one.add("x", 5.0, x);
+        one.add("y", 5.0, y);
+        two.add("y", 5.0, y);
+        two.add("x", 5.0, x);
+        for gibbs in &mut [one, two] {
+            let chain = gibbs.run(&mut source::seeded(42), 50000, 100);
+            let (i, j) = (chain.position("x").unwrap(), chain.position("y").unwrap());
+            assert::close(&chain.means(), &vec![0.0, 0.0], 0.05);
+            assert::close(&chain.sds(), &vec![1.0, 1.0], 0.05);
+            assert::close(statistics::pearson(&chain.trace(i), &chain.trace(j)), rho, 0.02);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the block should exist (got z)")]
+    fn missing() {
+        let mut gibbs = Gibbs::new();
+        gibbs.add("x", 0.0, |state, _| state.get("z"));
+        gibbs.run(&mut source::seeded(42), 1, 0);
+    }
+
+    #[test]
+    fn order() {
+        let mut gibbs = Gibbs::new();
+        gibbs.add("a", 1.0, |state, _| state.get("b") + 1.0);
+        gibbs.add("b", 0.0, |state, _| state.get("a") * 2.0);
+        let chain = gibbs.run(&mut source::seeded(42), 2, 0);
+        assert_eq!(chain.draws, vec![vec![1.0, 2.0], vec![3.0, 6.0]]);
+        assert_eq!(chain.names, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(gibbs.state().get("b"), 6.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the block should have a unique name (got x)")]
+    fn unique() {
+        let mut gibbs = Gibbs::<source::Xoshiro256PlusPlus>::new();
+        gibbs.add("x", 0.0, |_, _| 0.0);
+        gibbs.add("x", 0.0, |_, _| 0.0);
+    }
+}
@@ -0,0 +1,200 @@
+use distribution::{Sample, StandardGaussian};
+use mcmc::Chain;
+use source::Source;
+
+/// A random-walk Metropolis–Hastings sampler.
+///
+/// The proposal perturbs each dimension of the current state by independent
+/// Gaussian noise with a per-dimension standard deviation. A proposal whose
+/// log-density is `-∞`, which marks a point outside the support, or NaN is
+/// rejected.
+pub struct MetropolisHastings<F> {
+    ln_target: F,
+    state: Vec<f64>,
+    ln_density: f64,
+    scale: Vec<f64>,
+}
+
+impl<F> MetropolisHastings<F> where F: Fn(&[f64]) -> f64 {
+    /// Create a sampler of the distribution with the log-density `ln_target`,
+    /// which can be unnormalized, starting at `initial` and proposing with the
+    /// standard deviations `proposal_scale`.
+    ///
+    /// It should hold that `initial` and `proposal_scale` have the same length,
+    /// that the log-density at `initial` is finite, and that the scales are
+    /// positive; the function panics otherwise.
+    pub fn new(ln_target: F, initial: Vec<f64>, proposal_scale: Vec<f64>) -> Self {
+        assert!(initial.len() == proposal_scale.len(),
+                "the initial state and the scales should have the same length (got {} and {})",
+                initial.len(), proposal_scale.len());
+        assert!(proposal_scale.iter().all(|&scale| scale > 0.0 && scale.is_finite()),
+                "the scales should be positive and finite");
+        let ln_density = ln_target(&initial);
+        assert!(ln_density.is_finite(),
+                "the log-density at the initial state should be finite (got {})", ln_density);
+        MetropolisHastings {
+            ln_target: ln_target,
+            state: initial,
+            ln_density: ln_density,
+            scale: proposal_scale,
+        }
+    }
+
+    /// Tune the scales of the proposal by running `iterations` steps whose
+    /// draws are discarded.
+    ///
+    /// The scales are multiplied by a common factor adjusted by stochastic
+    /// approximation so that the acceptance rate approaches `target`; the
+    /// rates of `0.44` for univariate and `0.234` for multivariate targets
+    /// are known to be near optimal. The chain continues from the last state.
+    /// It should hold that `0 < target < 1`.
+    pub fn adapt<S>(&mut self, source: &mut S, iterations: usize, target: f64) where S: Source {
+        should!(0.0 < target && target < 1.0);
+        let mut ln_factor = 0.0;
+        let scale = self.scale.clone();
+        for i in 0..iterations {
+            let (_, probability) = self.step(source);
+            ln_factor += (probability - target) / (i as f64 + 1.0).powf(0.6);
+            let factor = ln_factor.exp();
+            for (current, &initial) in self.scale.iter_mut().zip(&scale) {
+                *current = initial * factor;
+            }
+        }
+    }
+
+    /// Return the scales of the proposal.
+    #[inline(always)]
+    pub fn proposal_scale(&self) -> &[f64] {
+        &self.scale
+    }
+
+    /// Draw a chain of `samples` draws after discarding `burn_in` steps and
+    /// keeping every `thin`th step afterwards.
+    ///
+    /// The acceptance rate of the chain is computed over all the steps after
+    /// the burn-in. The sampler continues from the last state in subsequent
+    /// calls. It should hold that `thin > 0`.
+    pub fn run<S>(&mut self, source: &mut S, samples: usize, burn_in: usize, thin: usize)
+                  -> Chain
+        where S: Source
+    {
+        should!(thin > 0);
+        for _ in 0..burn_in {
+            self.step(source);
+        }
+        let mut draws = Vec::with_capacity(samples);
+        let mut accepted = 0;
+        for _ in 0..samples {
+            for _ in 0..thin {
+                if self.step(source).0 {
+                    accepted += 1;
+                }
+            }
+            draws.push(self.state.clone());
+        }
+        let steps = samples * thin;
+        Chain {
+            draws: draws,
+            acceptance_rate: if steps > 0 { accepted as f64 / steps as f64 } else { 0.0 },
+            names: vec![],
+        }
+    }
+
+    /// Return the current state.
+    #[inline(always)]
+    pub fn state(&self) -> &[f64] {
+        &self.state
+    }
+
+    // Perform one step and return whether the proposal was accepted along with
+    // its acceptance probability.
+    fn step<S>(&mut self, source: &mut S) -> (bool, f64) where S: Source {
+        let proposal = self.state.iter().zip(&self.scale).map(|(&x, &scale)| {
+            x + scale * StandardGaussian.sample(source)
+        }).collect::<Vec<_>>();
+        let ln_density = (self.ln_target)(&proposal);
+        let ln_ratio = ln_density - self.ln_density;
+        if !(ln_ratio > ::std::f64::NEG_INFINITY) {
+            return (false, 0.0);
+        }
+        let accepted = ln_ratio >= 0.0 || source.read::<f64>().ln() < ln_ratio;
+        if accepted {
+            self.state = proposal;
+            self.ln_density = ln_density;
+        }
+        (accepted, ln_ratio.min(0.0).exp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::MetropolisHastings;
+    use prelude::*;
+    use statistics;
+
+    #[test]
+    fn adapt() {
+        let ln_target = |x: &[f64]| -0.5 * x[0] * x[0];
+        let mut sampler = MetropolisHastings::new(ln_target, vec![0.0], vec![0.01]);
+        let mut source = source::seeded(42);
+        sampler.adapt(&mut source, 5000, 0.44);
+        let chain = sampler.run(&mut source, 20000, 0, 1);
+        assert::close(chain.acceptance_rate, 0.44, 0.03);
+        assert::close(sampler.proposal_scale()[0], 2.4, 0.6);
+    }
+
+    #[test]
+    fn banana() {
+        let ln_target = |x: &[f64]| {
+            let y = x[1] - 0.1 * x[0] * x[0] + 10.0;
+            -x[0] * x[0] / 200.0 - y * y / 2.0
+        };
+        let mut sampler = MetropolisHastings::new(ln_target, vec![0.0, -10.0], vec![1.0, 1.0]);
+        let mut source = source::seeded(42);
+        sampler.adapt(&mut source, 2000, 0.234);
+        let chain = sampler.run(&mut source, 5000, 1000, 2);
+        assert!(chain.draws.iter().all(|draw| draw.iter().all(|x| x.is_finite())));
+        assert!(chain.means().iter().chain(&chain.sds()).all(|x| x.is_finite()));
+        assert!(chain.acceptance_rate > 0.1);
+    }
+
+    #[test]
+    fn gaussian() {
+        let (mu, sigma, rho) = ([1.0, -2.0], [1.0, 2.0], 0.8);
+        let ln_target = move |x: &[f64]| {
+            let (a, b) = ((x[0] - mu[0]) / sigma[0], (x[1] - mu[1]) / sigma[1]);
+            -(a * a - 2.0 * rho * a * b + b * b) / (2.0 * (1.0 - rho * rho))
+        };
+        let mut sampler = MetropolisHastings::new(ln_target, vec![0.0, 0.0], vec![1.0, 1.0]);
+        let mut source = source::seeded(42);
+        sampler.adapt(&mut source, 5000, 0.234);
+        let chain = sampler.run(&mut source, 50000, 1000, 2);
+        assert::close(chain.acceptance_rate, 0.234, 0.03);
+        assert::close(&chain.means(), &mu.to_vec(), 0.1);
+        assert::close(&chain.sds(), &sigma.to_vec(), 0.1);
+        let covariance = statistics::covariance(&chain.trace(0), &chain.trace(1));
+        assert::close(covariance, rho * sigma[0] * sigma[1], 0.1);
+        assert!(chain.effective_sample_sizes().iter().all(|&size| size > 1000.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the log-density at the initial state should be finite")]
+    fn initial() {
+        let ln_target = |x: &[f64]| if x[0] > 0.0 { 0.0 } else { ::std::f64::NEG_INFINITY };
+        MetropolisHastings::new(ln_target, vec![-1.0], vec![1.0]);
+    }
+
+    #[test]
+    fn support() {
+        let ln_target = |x: &[f64]| if x[0] > 0.0 { -x[0] } else { ::std::f64::NEG_INFINITY };
+        let mut sampler = MetropolisHastings::new(ln_target, vec![1.0], vec![2.0]);
+        let chain = sampler.run(&mut source::seeded(42), 20000, 100, 1);
+        assert!(chain.trace(0).iter().all(|&x| x > 0.0));
+        assert::close(chain.means()[0], 1.0, 0.1);
+        let ln_target = |x: &[f64]| if x[0] > 0.0 { -x[0] } else { ::std::f64::NAN };
+        let mut sampler = MetropolisHastings::new(ln_target, vec![1.0], vec![2.0]);
+        let chain = sampler.run(&mut source::seeded(42), 1000, 0, 1);
+        assert!(chain.trace(0).iter().all(|&x| x > 0.0));
+    }
+}
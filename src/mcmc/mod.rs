@@ -0,0 +1,25 @@
+//! Markov chain Monte Carlo methods.
+//!
+//! ```
+//! use probability::mcmc::MetropolisHastings;
+//! use probability::prelude::*;
+//!
+//! let ln_target = |x: &[f64]| -0.5 * (x[0] * x[0] + x[1] * x[1]);
+//! let mut sampler = MetropolisHastings::new(ln_target, vec![0.0, 0.0], vec![1.0, 1.0]);
+//! let mut source = source::seeded(42);
+//! sampler.adapt(&mut source, 1000, 0.234);
+//! let chain = sampler.run(&mut source, 10000, 1000, 1);
+//! assert!(chain.means().iter().all(|&mean| mean.abs() < 0.1));
+//! ```
+
+mod chain;
+mod gibbs;
+mod metropolis;
+mod particle;
+mod slice;
+
+pub use self::chain::Chain;
+pub use self::gibbs::{Gibbs, GibbsState};
+pub use self::metropolis::MetropolisHastings;
+pub use self::particle::ParticleFilter;
+pub use self::slice::{SliceSampler, slice_sample};
@@ -0,0 +1,265 @@
+use math;
+use source::Source;
+use SamplingError;
+
+/// A bootstrap particle filter, that is, sequential importance resampling.
+///
+/// The filter tracks a scalar hidden state whose initial distribution is
+/// sampled by `init`, which evolves according to `transition`, and which
+/// generates observations with the log-likelihood `ln_likelihood`. Each step
+/// propagates the particles through the transition, reweights them by the
+/// likelihood of the observation in the log domain, and resamples them using
+/// systematic resampling when the effective sample size falls below a
+/// fraction of the number of particles, one half by default.
+///
+/// ```
+/// use probability::mcmc::ParticleFilter;
+/// use probability::prelude::*;
+///
+/// let mut filter = ParticleFilter::new(
+///     1000,
+///     |source: &mut source::Xoshiro256PlusPlus| Gaussian::new(0.0, 1.0).sample(source),
+///     |x, source: &mut source::Xoshiro256PlusPlus| Gaussian::new(x, 0.1).sample(source),
+///     |x, y: &f64| Gaussian::new(x, 0.5).ln_density(*y),
+/// );
+/// let mut source = source::seeded(42);
+/// for y in &[0.1, 0.3, 0.2, 0.4] {
+///     filter.step(y, &mut source).unwrap();
+/// }
+/// assert!((filter.means()[3] - 0.25).abs() < 0.2);
+/// ```
+pub struct ParticleFilter<I, T, L> {
+    init: I,
+    transition: T,
+    ln_likelihood: L,
+    threshold: f64,
+    particles: Vec<f64>,
+    ln_weights: Vec<f64>,
+    means: Vec<f64>,
+    variances: Vec<f64>,
+    effective_sample_sizes: Vec<f64>,
+    ln_marginal_likelihood: f64,
+}
+
+impl<I, T, L> ParticleFilter<I, T, L> {
+    /// Create a filter with `n` particles.
+    ///
+    /// The particles are drawn from `init` at the first step. It should hold
+    /// that `n > 0`.
+    pub fn new(n: usize, init: I, transition: T, ln_likelihood: L) -> Self {
+        should!(n > 0);
+        ParticleFilter {
+            init: init,
+            transition: transition,
+            ln_likelihood: ln_likelihood,
+            threshold: 0.5,
+            particles: Vec::with_capacity(n),
+            ln_weights: vec![-(n as f64).ln(); n],
+            means: vec![],
+            variances: vec![],
+            effective_sample_sizes: vec![],
+            ln_marginal_likelihood: 0.0,
+        }
+    }
+
+    /// Return the effective sample size `1 / Σw²` at each step before
+    /// resampling.
+    #[inline(always)]
+    pub fn effective_sample_sizes(&self) -> &[f64] {
+        &self.effective_sample_sizes
+    }
+
+    /// Return the natural logarithm of the likelihood of the observations so
+    /// far, which is the sum of the logarithms of the average weights at each
+    /// step.
+    #[inline(always)]
+    pub fn ln_marginal_likelihood(&self) -> f64 {
+        self.ln_marginal_likelihood
+    }
+
+    /// Return the filtered mean of the state at each step.
+    #[inline(always)]
+    pub fn means(&self) -> &[f64] {
+        &self.means
+    }
+
+    /// Return the particles.
+    #[inline(always)]
+    pub fn particles(&self) -> &[f64] {
+        &self.particles
+    }
+
+    /// Set the fraction of the number of particles below which the effective
+    /// sample size triggers resampling.
+    ///
+    /// Zero disables resampling, and one enforces it at every step. It should
+    /// hold that `0 <= threshold <= 1`.
+    #[inline]
+    pub fn set_threshold(&mut self, threshold: f64) {
+        should!(0.0 <= threshold && threshold <= 1.0);
+        self.threshold = threshold;
+    }
+
+    /// Return the filtered variance of the state at each step.
+    #[inline(always)]
+    pub fn variances(&self) -> &[f64] {
+        &self.variances
+    }
+
+    /// Return the normalized weights of the particles.
+    pub fn weights(&self) -> Vec<f64> {
+        self.ln_weights.iter().map(|&w| w.exp()).collect()
+    }
+
+    /// Incorporate observation `observation`.
+    ///
+    /// An error is returned if the observation has zero likelihood under all
+    /// the particles; the filter should not be used afterwards.
+    pub fn step<S, O>(&mut self, observation: &O, source: &mut S) -> Result<(), SamplingError>
+        where I: Fn(&mut S) -> f64, T: Fn(f64, &mut S) -> f64, L: Fn(f64, &O) -> f64, S: Source
+    {
+        let n = self.ln_weights.len();
+        if self.particles.is_empty() {
+            for _ in 0..n {
+                let x = (self.init)(source);
+                self.particles.push(x);
+            }
+        }
+        for (x, w) in self.particles.iter_mut().zip(&mut self.ln_weights) {
+            *x = (self.transition)(*x, source);
+            *w += (self.ln_likelihood)(*x, observation);
+        }
+        let ln_sum = math::log_sum_exp(&self.ln_weights);
+        if !ln_sum.is_finite() {
+            return Err(SamplingError::WeightsZero);
+        }
+        self.ln_marginal_likelihood += ln_sum;
+        for w in &mut self.ln_weights {
+            *w -= ln_sum;
+        }
+        let (mut mean, mut squares) = (0.0, 0.0);
+        for (&x, &w) in self.particles.iter().zip(&self.ln_weights) {
+            mean += w.exp() * x;
+        }
+        for (&x, &w) in self.particles.iter().zip(&self.ln_weights) {
+            squares += w.exp() * (x - mean) * (x - mean);
+        }
+        let size = 1.0 / self.ln_weights.iter().map(|&w| (2.0 * w).exp()).sum::<f64>();
+        self.means.push(mean);
+        self.variances.push(squares);
+        self.effective_sample_sizes.push(size);
+        if size < self.threshold * n as f64 {
+            self.resample(source);
+        }
+        Ok(())
+    }
+
+    // Resample the particles using systematic resampling and reset the
+    // weights.
+    fn resample<S>(&mut self, source: &mut S) where S: Source {
+        let n = self.particles.len();
+        let step = 1.0 / n as f64;
+        let mut u = step * source.read::<f64>();
+        let mut cumulative = 0.0;
+        let mut resampled = Vec::with_capacity(n);
+        for (&x, &w) in self.particles.iter().zip(&self.ln_weights) {
+            cumulative += w.exp();
+            while u < cumulative && resampled.len() < n {
+                resampled.push(x);
+                u += step;
+            }
+        }
+        while resampled.len() < n {
+            let x = self.particles[n - 1];
+            resampled.push(x);
+        }
+        self.particles = resampled;
+        for w in &mut self.ln_weights {
+            *w = -(n as f64).ln();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::ParticleFilter;
+    use prelude::*;
+    use SamplingError;
+
+    type Source = source::Xoshiro256PlusPlus;
+
+    #[test]
+    fn degenerate() {
+        let mut filter = ParticleFilter::new(
+            100,
+            |source: &mut Source| Uniform::new(0.0, 1.0).sample(source),
+            |x, _: &mut Source| x,
+            |x, y: &f64| Uniform::new(0.0, 1.0).ln_density(x + *y),
+        );
+        let mut source = source::seeded(42);
+        assert_eq!(filter.step(&0.0, &mut source), Ok(()));
+        assert_eq!(filter.step(&2.0, &mut source), Err(SamplingError::WeightsZero));
+    }
+
+    #[test]
+    fn kalman() {
+        let (a, q, r, p0): (f64, f64, f64, f64) = (0.9, 0.5, 1.0, 2.0);
+        let mut source = source::seeded(42);
+        let mut x = Gaussian::new(0.0, p0.sqrt()).sample(&mut source);
+        let mut observations = vec![];
+        for _ in 0..50 {
+            x = a * x + Gaussian::new(0.0, q.sqrt()).sample(&mut source);
+            observations.push(x + Gaussian::new(0.0, r.sqrt()).sample(&mut source));
+        }
+
+        let (mut m, mut p, mut ln_likelihood) = (0.0, p0, 0.0);
+        let (mut means, mut variances) = (vec![], vec![]);
+        for &y in &observations {
+            m *= a;
+            p = a * a * p + q;
+            let s = p + r;
+            ln_likelihood += Gaussian::new(m, s.sqrt()).ln_density(y);
+            let k = p / s;
+            m += k * (y - m);
+            p *= 1.0 - k;
+            means.push(m);
+            variances.push(p);
+        }
+
+        let mut filter = ParticleFilter::new(
+            20000,
+            |source: &mut Source| Gaussian::new(0.0, p0.sqrt()).sample(source),
+            |x, source: &mut Source| Gaussian::new(a * x, q.sqrt()).sample(source),
+            |x, y: &f64| Gaussian::new(x, r.sqrt()).ln_density(*y),
+        );
+        for y in &observations {
+            filter.step(y, &mut source).unwrap();
+        }
+        assert::close(filter.means(), &means[..], 0.05);
+        assert::close(filter.variances(), &variances[..], 0.05);
+        assert::close(filter.ln_marginal_likelihood(), ln_likelihood, 0.3);
+        assert!(filter.effective_sample_sizes().iter().all(|&size| size > 1000.0));
+        assert::close(filter.weights().iter().sum::<f64>(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn resample() {
+        let mut filter = ParticleFilter::new(
+            4,
+            |_: &mut Source| 0.0,
+            |x, _: &mut Source| x + 1.0,
+            |_, _: &f64| 0.0,
+        );
+        filter.set_threshold(1.0);
+        let mut source = source::seeded(42);
+        filter.step(&0.0, &mut source).unwrap();
+        filter.ln_weights = vec![0.5f64.ln(), 0.25f64.ln(), 0.25f64.ln(), ::std::f64::NEG_INFINITY];
+        filter.particles = vec![1.0, 2.0, 3.0, 4.0];
+        filter.resample(&mut source);
+        let mut particles = filter.particles().to_vec();
+        particles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(particles, vec![1.0, 1.0, 2.0, 3.0]);
+        assert_eq!(filter.weights(), vec![0.25; 4]);
+    }
+}
@@ -0,0 +1,210 @@
+use mcmc::Chain;
+use source::Source;
+use SamplingError;
+
+/// A univariate slice sampler.
+///
+/// The sampler draws a chain by applying `slice_sample` repeatedly and holds
+/// a mutable reference to the source of randomness, so it can be used as an
+/// iterator. An item of the iterator is an error if the slice could not be
+/// bracketed; see `slice_sample`.
+///
+/// ```
+/// use probability::mcmc::SliceSampler;
+/// use probability::prelude::*;
+///
+/// let ln_f = |x: f64| -0.5 * x * x;
+/// let mut source = source::seeded(42);
+/// let mut sampler = SliceSampler::new(ln_f, 0.0, 1.0, 50, &mut source);
+/// let chain = sampler.run(10000, 100, 1).unwrap();
+/// assert!(chain.means()[0].abs() < 0.05);
+/// ```
+pub struct SliceSampler<'l, F, S: 'l> {
+    ln_f: F,
+    state: f64,
+    width: f64,
+    steps: usize,
+    source: &'l mut S,
+}
+
+impl<'l, F, S> SliceSampler<'l, F, S> where F: Fn(f64) -> f64, S: Source {
+    /// Create a sampler of the distribution with the log-density `ln_f`,
+    /// which can be unnormalized, starting at `initial`.
+    ///
+    /// See `slice_sample` for the meaning of `width` and `steps`.
+    #[inline]
+    pub fn new(ln_f: F, initial: f64, width: f64, steps: usize, source: &'l mut S) -> Self {
+        SliceSampler { ln_f: ln_f, state: initial, width: width, steps: steps, source: source }
+    }
+
+    /// Draw a chain of `samples` draws after discarding `burn_in` steps and
+    /// keeping every `thin`th step afterwards.
+    ///
+    /// Every step moves the chain, so the acceptance rate is one. It should
+    /// hold that `thin > 0`.
+    pub fn run(&mut self, samples: usize, burn_in: usize, thin: usize)
+               -> Result<Chain, SamplingError>
+    {
+        should!(thin > 0);
+        for _ in 0..burn_in {
+            self.step()?;
+        }
+        let mut draws = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            for _ in 0..thin {
+                self.step()?;
+            }
+            draws.push(vec![self.state]);
+        }
+        Ok(Chain { draws: draws, acceptance_rate: 1.0, names: vec![] })
+    }
+
+    /// Return the current state.
+    #[inline(always)]
+    pub fn state(&self) -> f64 {
+        self.state
+    }
+
+    fn step(&mut self) -> Result<f64, SamplingError> {
+        let (width, steps) = (self.width, self.steps);
+        self.state = slice_sample(&self.ln_f, self.state, width, steps, self.source)?;
+        Ok(self.state)
+    }
+}
+
+impl<'l, F, S> Iterator for SliceSampler<'l, F, S> where F: Fn(f64) -> f64, S: Source {
+    type Item = Result<f64, SamplingError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.step())
+    }
+}
+
+/// Draw the next state of a chain following the distribution with the
+/// log-density `ln_f`, which can be unnormalized, from the current state `x0`
+/// using Neal’s slice sampler.
+///
+/// A level is drawn uniformly under the density at `x0`. An interval of width
+/// `width` placed at random around `x0` is then stepped out by `width` on each
+/// side until both its ends fall outside the slice, that is, the set of
+/// points where the density exceeds the level, and the next state is drawn
+/// uniformly from the interval, which is shrunk toward `x0` after each
+/// rejection. If the slice is not bracketed within `steps` steps on either
+/// side, which happens when the target is improper or `width` is far too
+/// small, an error is returned.
+///
+/// It should hold that `width > 0` and that the log-density at `x0` is finite;
+/// the function panics otherwise.
+pub fn slice_sample<F, S>(ln_f: F, x0: f64, width: f64, steps: usize, source: &mut S)
+                          -> Result<f64, SamplingError>
+    where F: Fn(f64) -> f64, S: Source
+{
+    assert!(width > 0.0 && width.is_finite(),
+            "the width should be positive and finite (got {})", width);
+    let ln_f0 = ln_f(x0);
+    assert!(ln_f0.is_finite(), "the log-density at the current state should be finite (got {})",
+            ln_f0);
+    let level = ln_f0 + uniform(source).ln();
+    let mut left = x0 - width * source.read::<f64>();
+    let mut right = left + width;
+    let mut count = 0;
+    while ln_f(left) > level {
+        if count == steps {
+            return Err(SamplingError::StepsExhausted { steps: steps });
+        }
+        left -= width;
+        count += 1;
+    }
+    count = 0;
+    while ln_f(right) > level {
+        if count == steps {
+            return Err(SamplingError::StepsExhausted { steps: steps });
+        }
+        right += width;
+        count += 1;
+    }
+    loop {
+        let x = left + (right - left) * source.read::<f64>();
+        if ln_f(x) > level {
+            return Ok(x);
+        }
+        if x == x0 {
+            return Ok(x0);
+        }
+        if x < x0 {
+            left = x;
+        } else {
+            right = x;
+        }
+    }
+}
+
+// Draw a uniform sample from `(0, 1]`.
+#[inline(always)]
+fn uniform<S>(source: &mut S) -> f64 where S: Source {
+    1.0 - source.read::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::{SliceSampler, slice_sample};
+    use prelude::*;
+    use tests::ks_one_sample;
+    use SamplingError;
+
+    fn ln_gamma(x: f64) -> f64 {
+        if x > 0.0 { 2.0 * x.ln() - x / 2.0 } else { ::std::f64::NEG_INFINITY }
+    }
+
+    #[test]
+    fn gamma() {
+        let mut source = source::seeded(42);
+        let mut sampler = SliceSampler::new(ln_gamma, 6.0, 5.0, 50, &mut source);
+        let chain = sampler.run(2000, 100, 10).unwrap();
+        assert!(ks_one_sample(&chain.trace(0), &Gamma::new(3.0, 2.0)).p_value > 0.05);
+        assert!(chain.effective_sample_sizes()[0] > 1000.0);
+    }
+
+    #[test]
+    fn improper() {
+        let mut source = source::seeded(42);
+        assert_eq!(slice_sample(|_| 0.0, 0.0, 1.0, 20, &mut source),
+                   Err(SamplingError::StepsExhausted { steps: 20 }));
+        assert_eq!(slice_sample(|x: f64| x, 0.0, 1.0, 20, &mut source),
+                   Err(SamplingError::StepsExhausted { steps: 20 }));
+        let mut sampler = SliceSampler::new(|_| 0.0, 0.0, 1.0, 20, &mut source);
+        assert!(sampler.next().unwrap().is_err());
+        assert!(sampler.run(10, 0, 1).is_err());
+    }
+
+    #[test]
+    fn iterator() {
+        let mut source = source::seeded(42);
+        let sampler = SliceSampler::new(ln_gamma, 6.0, 5.0, 50, &mut source);
+        let x = sampler.take(1000).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(x.len(), 1000);
+        assert!(x.iter().all(|&x| x > 0.0));
+    }
+
+    #[test]
+    fn tail() {
+        let mut source = source::seeded(42);
+        let mut sampler = SliceSampler::new(ln_gamma, 80.0, 5.0, 50, &mut source);
+        let chain = sampler.run(20000, 200, 1).unwrap();
+        assert::close(chain.means()[0], 6.0, 0.25);
+        let mut sampler = SliceSampler::new(ln_gamma, 1e-8, 5.0, 50, &mut source);
+        assert::close(sampler.run(20000, 200, 1).unwrap().means()[0], 6.0, 0.25);
+    }
+
+    #[test]
+    fn width() {
+        for &width in &[0.5, 2.0, 10.0, 50.0] {
+            let mut source = source::seeded(42);
+            let mut sampler = SliceSampler::new(ln_gamma, 6.0, width, 100, &mut source);
+            let chain = sampler.run(2000, 100, 10).unwrap();
+            assert!(ks_one_sample(&chain.trace(0), &Gamma::new(3.0, 2.0)).p_value > 0.01);
+        }
+    }
+}
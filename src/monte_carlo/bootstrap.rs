@@ -0,0 +1,284 @@
+//! Bootstrap resampling.
+//!
+//! The sampling distribution of a statistic is approximated by the
+//! distribution of the statistic over replicates of the data, which are drawn
+//! either from the data with replacement (the nonparametric bootstrap) or
+//! from a fitted distribution (the parametric bootstrap). The replicates are
+//! drawn one at a time into a single buffer.
+//!
+//! ```
+//! use probability::monte_carlo::bootstrap;
+//! use probability::prelude::*;
+//!
+//! let data = [2.1, 3.4, 1.9, 5.6, 4.2, 3.3, 2.8, 4.9];
+//! let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+//! let result = bootstrap::resample(&data, mean, &mut source::seeded(42), 1000);
+//! let (lower, upper) = result.percentile(0.95);
+//! ```
+
+use distribution::{Distribution, Inverse, Sample};
+use distribution::{Empirical, StandardGaussian};
+use source::Source;
+
+/// The result of bootstrap resampling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bootstrap {
+    estimate: f64,
+    replicates: Vec<f64>,
+    acceleration: f64,
+}
+
+impl Bootstrap {
+    /// Compute the bias-corrected and accelerated confidence interval at
+    /// confidence level `level`.
+    ///
+    /// The interval corrects the percentile interval for the median bias of
+    /// the replicates and for the dependence of the standard error on the
+    /// parameter; the latter is estimated using the jackknife and is taken to
+    /// be zero for the parametric bootstrap. It should hold that
+    /// `0 < level < 1`.
+    pub fn bca(&self, level: f64) -> (f64, f64) {
+        should!(0.0 < level && level < 1.0);
+        let gaussian = StandardGaussian;
+        let n = self.replicates.len() as f64;
+        let below = self.replicates.iter().filter(|&&x| x < self.estimate).count() as f64;
+        let equal = self.replicates.iter().filter(|&&x| x == self.estimate).count() as f64;
+        let bias = gaussian.inverse(((below + equal / 2.0) / n).max(0.5 / n).min(1.0 - 0.5 / n));
+        let adjust = |p: f64| {
+            let z = bias + gaussian.inverse(p);
+            gaussian.distribution(bias + z / (1.0 - self.acceleration * z))
+        };
+        let alpha = (1.0 - level) / 2.0;
+        let empirical = Empirical::new(&self.replicates);
+        (empirical.inverse(adjust(alpha)), empirical.inverse(adjust(1.0 - alpha)))
+    }
+
+    /// Compute the bootstrap estimate of the bias of the statistic.
+    #[inline]
+    pub fn bias(&self) -> f64 {
+        self.mean() - self.estimate
+    }
+
+    /// Return the statistic evaluated on the original data.
+    #[inline(always)]
+    pub fn estimate(&self) -> f64 { self.estimate }
+
+    /// Compute the percentile confidence interval at confidence level
+    /// `level`.
+    ///
+    /// It should hold that `0 < level < 1`.
+    pub fn percentile(&self, level: f64) -> (f64, f64) {
+        should!(0.0 < level && level < 1.0);
+        let alpha = (1.0 - level) / 2.0;
+        let empirical = Empirical::new(&self.replicates);
+        (empirical.inverse(alpha), empirical.inverse(1.0 - alpha))
+    }
+
+    /// Return the statistic evaluated on each replicate.
+    #[inline(always)]
+    pub fn replicates(&self) -> &[f64] { &self.replicates }
+
+    /// Compute the bootstrap estimate of the standard error of the statistic.
+    pub fn standard_error(&self) -> f64 {
+        let mean = self.mean();
+        let n = self.replicates.len() as f64;
+        (self.replicates.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0)).sqrt()
+    }
+
+    fn mean(&self) -> f64 {
+        self.replicates.iter().sum::<f64>() / self.replicates.len() as f64
+    }
+}
+
+/// Perform the paired nonparametric bootstrap.
+///
+/// The observations `x[i]` and `y[i]` form pairs, which are resampled
+/// together. It should hold that `x` and `y` have the same length, which is
+/// greater than one, and that `n > 1`.
+pub fn paired<F, S>(x: &[f64], y: &[f64], statistic: F, source: &mut S, n: usize) -> Bootstrap
+    where F: Fn(&[f64], &[f64]) -> f64, S: Source
+{
+    should!(x.len() == y.len() && x.len() > 1 && n > 1);
+    let m = x.len();
+    let (mut one, mut other) = (vec![0.0; m], vec![0.0; m]);
+    let replicates = (0..n).map(|_| {
+        for (one, other) in one.iter_mut().zip(other.iter_mut()) {
+            let i = ((source.read::<f64>() * m as f64) as usize).min(m - 1);
+            *one = x[i];
+            *other = y[i];
+        }
+        statistic(&one, &other)
+    }).collect();
+    let jackknife = (0..m).map(|i| {
+        copy(x, i, &mut one[..m - 1]);
+        copy(y, i, &mut other[..m - 1]);
+        statistic(&one[..m - 1], &other[..m - 1])
+    }).collect::<Vec<_>>();
+    Bootstrap {
+        estimate: statistic(x, y),
+        replicates: replicates,
+        acceleration: accelerate(&jackknife),
+    }
+}
+
+/// Perform the parametric bootstrap.
+///
+/// The replicates of size `size` are drawn from `distribution`, which is
+/// typically fitted to the data, and `estimate` is the statistic evaluated
+/// on the data. It should hold that `size > 0` and `n > 1`.
+pub fn parametric<D, F, S>(distribution: &D, size: usize, estimate: f64, statistic: F,
+                           source: &mut S, n: usize) -> Bootstrap
+    where D: Sample<Value=f64>, F: Fn(&[f64]) -> f64, S: Source
+{
+    should!(size > 0 && n > 1);
+    let mut buffer = vec![0.0; size];
+    let replicates = (0..n).map(|_| {
+        distribution.sample_into(source, &mut buffer);
+        statistic(&buffer)
+    }).collect();
+    Bootstrap { estimate: estimate, replicates: replicates, acceleration: 0.0 }
+}
+
+/// Perform the ordinary nonparametric bootstrap.
+///
+/// The replicates are drawn from `data` with replacement. It should hold
+/// that `data` contains more than one observation, all finite, and that
+/// `n > 1`.
+pub fn resample<F, S>(data: &[f64], statistic: F, source: &mut S, n: usize) -> Bootstrap
+    where F: Fn(&[f64]) -> f64, S: Source
+{
+    should!(data.len() > 1);
+    let m = data.len();
+    let mut result = parametric(&Empirical::new(data), m, statistic(data), &statistic, source, n);
+    let mut buffer = vec![0.0; m - 1];
+    let jackknife = (0..m).map(|i| {
+        copy(data, i, &mut buffer);
+        statistic(&buffer)
+    }).collect::<Vec<_>>();
+    result.acceleration = accelerate(&jackknife);
+    result
+}
+
+// Estimate the acceleration of the bias-corrected and accelerated interval
+// from the jackknife values of the statistic.
+fn accelerate(jackknife: &[f64]) -> f64 {
+    let mean = jackknife.iter().sum::<f64>() / jackknife.len() as f64;
+    let (mut second, mut third) = (0.0, 0.0);
+    for &x in jackknife {
+        let delta = mean - x;
+        second += delta * delta;
+        third += delta * delta * delta;
+    }
+    if second == 0.0 { 0.0 } else { third / (6.0 * second.powf(1.5)) }
+}
+
+// Copy `data` without the `i`th observation to `buffer`.
+fn copy(data: &[f64], i: usize, buffer: &mut [f64]) {
+    buffer[..i].copy_from_slice(&data[..i]);
+    buffer[i..].copy_from_slice(&data[i + 1..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    fn mean(x: &[f64]) -> f64 {
+        x.iter().sum::<f64>() / x.len() as f64
+    }
+
+    fn variance(x: &[f64]) -> f64 {
+        let mean = mean(x);
+        x.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / x.len() as f64
+    }
+
+    #[test]
+    fn bca() {
+        use super::resample;
+
+        let d = Gaussian::new(0.0, 2.0);
+        let mut source = source::seeded(42);
+        let (mut percentile, mut bca) = (0, 0);
+        for _ in 0..200 {
+            let data = d.samples(&mut source, 20);
+            let result = resample(&data, variance, &mut source, 1000);
+            let (lower, upper) = result.percentile(0.9);
+            if lower <= 4.0 && 4.0 <= upper {
+                percentile += 1;
+            }
+            let (lower, upper) = result.bca(0.9);
+            if lower <= 4.0 && 4.0 <= upper {
+                bca += 1;
+            }
+        }
+        assert!(bca >= percentile + 10);
+    }
+
+    #[test]
+    fn bias() {
+        use super::resample;
+
+        let d = Gaussian::new(0.0, 2.0);
+        let mut source = source::seeded(42);
+        let data = d.samples(&mut source, 20);
+        let result = resample(&data, variance, &mut source, 10000);
+        assert::close(result.bias(), -variance(&data) / 20.0, 0.05);
+        assert_eq!(result.estimate(), variance(&data));
+    }
+
+    #[test]
+    fn paired() {
+        use super::paired;
+
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let y = [1.5, 2.5, 3.5, 4.5, 5.5, 6.5];
+        let difference = |x: &[f64], y: &[f64]| mean(y) - mean(x);
+        let result = paired(&x, &y, difference, &mut source::seeded(42), 100);
+        assert!(result.replicates().iter().all(|&x| (x - 0.5).abs() < 1e-12));
+        assert::close(result.standard_error(), 0.0, 1e-12);
+        let (lower, upper) = result.percentile(0.95);
+        assert::close(&[lower, upper], &[0.5, 0.5], 1e-12);
+    }
+
+    #[test]
+    fn parametric() {
+        use super::parametric;
+
+        let d = Gaussian::new(1.0, 2.0);
+        let result = parametric(&d, 100, 1.0, mean, &mut source::seeded(42), 1000);
+        assert_eq!(result.replicates().len(), 1000);
+        assert::close(result.standard_error(), 0.2, 0.02);
+        let (lower, upper) = result.percentile(0.95);
+        assert::close(lower, 1.0 - 1.96 * 0.2, 0.05);
+        assert::close(upper, 1.0 + 1.96 * 0.2, 0.05);
+        assert_eq!(result.bca(0.95), result.bca(0.95));
+    }
+
+    #[test]
+    fn percentile() {
+        use super::resample;
+
+        let d = Gaussian::new(1.0, 2.0);
+        let mut source = source::seeded(42);
+        let mut covered = 0;
+        for _ in 0..200 {
+            let data = d.samples(&mut source, 50);
+            let (lower, upper) = resample(&data, mean, &mut source, 1000).percentile(0.95);
+            if lower <= 1.0 && 1.0 <= upper {
+                covered += 1;
+            }
+        }
+        assert!(180 <= covered && covered <= 198);
+    }
+
+    #[test]
+    fn resample() {
+        use super::resample;
+
+        let data = [1.0, 2.0, 3.0];
+        let result = resample(&data, mean, &mut source::seeded(42), 1000);
+        assert_eq!(result.estimate(), 2.0);
+        assert!(result.replicates().iter().all(|&x| 1.0 <= x && x <= 3.0));
+        assert::close(result.standard_error(), (2.0f64 / 9.0).sqrt(), 0.02);
+    }
+}
@@ -0,0 +1,276 @@
+//! Crude Monte Carlo estimation.
+//!
+//! The expectation of `f(X)` is estimated by the mean of `f` over draws of
+//! `X`, and the probability of an event by the fraction of draws in which it
+//! occurs. The draws are taken from an iterator, so any sampler can be used,
+//! including `Independent` for univariate distributions and a closure drawing
+//! from a multivariate one; the standard errors assume that the draws are
+//! independent. The sequential variants keep drawing until the relative
+//! standard error falls below a tolerance or until a limit on the number of
+//! samples is reached.
+//!
+//! ```
+//! use probability::monte_carlo::crude;
+//! use probability::prelude::*;
+//!
+//! let d = Gaussian::new(0.0, 1.0);
+//! let estimate = crude::expectation(Independent(&d, &mut source::seeded(42)), |x| x * x,
+//!                                   10000);
+//! let (lower, upper) = estimate.interval(0.95);
+//! assert!(lower < 1.0 && 1.0 < upper);
+//! ```
+
+use distribution::{Inverse, StandardGaussian};
+use statistics::OnlineStats;
+
+/// An estimate of an expectation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    /// The sample mean.
+    pub value: f64,
+    /// The standard error of `value` computed from the sample variance.
+    pub standard_error: f64,
+    /// The number of samples.
+    pub samples: usize,
+}
+
+/// An estimate of a probability.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Proportion {
+    /// The number of samples in which the event occurred.
+    pub successes: usize,
+    /// The number of samples.
+    pub samples: usize,
+}
+
+impl Estimate {
+    /// Compute the confidence interval at confidence level `level` using the
+    /// Gaussian approximation.
+    ///
+    /// It should hold that `0 < level < 1`.
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        let z = critical(level);
+        (self.value - z * self.standard_error, self.value + z * self.standard_error)
+    }
+
+    /// Compute the ratio of the standard error to the magnitude of the
+    /// estimate.
+    #[inline]
+    pub fn relative_error(&self) -> f64 {
+        self.standard_error / self.value.abs()
+    }
+
+    fn from_stats(stats: &OnlineStats) -> Self {
+        let n = stats.count();
+        Estimate {
+            value: stats.mean(),
+            standard_error: (stats.variance() / n as f64).sqrt(),
+            samples: n as usize,
+        }
+    }
+}
+
+impl Proportion {
+    /// Compute the Wilson score interval at confidence level `level`.
+    ///
+    /// Unlike the Gaussian approximation, the interval stays within `[0, 1]`
+    /// and does not collapse to a point when the event occurs in none or all
+    /// of the samples. It should hold that `0 < level < 1`.
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        let z = critical(level);
+        let (n, p) = (self.samples as f64, self.value());
+        let shrinkage = 1.0 + z * z / n;
+        let center = (p + z * z / (2.0 * n)) / shrinkage;
+        let half = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / shrinkage;
+        ((center - half).max(0.0), (center + half).min(1.0))
+    }
+
+    /// Compute the ratio of the standard error to the estimate.
+    ///
+    /// The ratio is infinite when the event has not occurred.
+    #[inline]
+    pub fn relative_error(&self) -> f64 {
+        if self.successes == 0 {
+            return ::std::f64::INFINITY;
+        }
+        self.standard_error() / self.value()
+    }
+
+    /// Compute the standard error of the estimate.
+    #[inline]
+    pub fn standard_error(&self) -> f64 {
+        let p = self.value();
+        (p * (1.0 - p) / self.samples as f64).sqrt()
+    }
+
+    /// Compute the estimate, that is, the fraction of the samples in which the
+    /// event occurred.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.successes as f64 / self.samples as f64
+    }
+}
+
+/// Estimate the expectation of `f` using `n` samples.
+///
+/// Fewer samples are used if the iterator is exhausted earlier. It should
+/// hold that `n > 1`.
+pub fn expectation<I, F>(samples: I, f: F, n: usize) -> Estimate
+    where I: IntoIterator, F: Fn(I::Item) -> f64
+{
+    should!(n > 1);
+    let mut stats = OnlineStats::new();
+    for x in samples.into_iter().take(n) {
+        stats.push(f(x));
+    }
+    Estimate::from_stats(&stats)
+}
+
+/// Estimate the expectation of `f` drawing samples until the relative
+/// standard error falls below `tolerance` or until there are `limit` samples.
+///
+/// The stopping rule is checked once there are at least 100 samples, which
+/// guards against stopping on a lucky streak. It should hold that
+/// `tolerance > 0` and `limit > 1`.
+pub fn expectation_until<I, F>(samples: I, f: F, tolerance: f64, limit: usize) -> Estimate
+    where I: IntoIterator, F: Fn(I::Item) -> f64
+{
+    should!(tolerance > 0.0 && limit > 1);
+    let mut stats = OnlineStats::new();
+    for x in samples.into_iter().take(limit) {
+        stats.push(f(x));
+        if stats.count() >= MINIMUM && Estimate::from_stats(&stats).relative_error() <= tolerance {
+            break;
+        }
+    }
+    Estimate::from_stats(&stats)
+}
+
+/// Estimate the probability of the event given by `predicate` using `n`
+/// samples.
+///
+/// Fewer samples are used if the iterator is exhausted earlier. It should
+/// hold that `n > 0`.
+pub fn probability<I, F>(samples: I, predicate: F, n: usize) -> Proportion
+    where I: IntoIterator, F: Fn(I::Item) -> bool
+{
+    should!(n > 0);
+    let mut proportion = Proportion { successes: 0, samples: 0 };
+    for x in samples.into_iter().take(n) {
+        proportion.samples += 1;
+        proportion.successes += predicate(x) as usize;
+    }
+    proportion
+}
+
+/// Estimate the probability of the event given by `predicate` drawing
+/// samples until the relative standard error falls below `tolerance` or until
+/// there are `limit` samples.
+///
+/// The stopping rule is checked as in `expectation_until`; it is never met
+/// before the event has occurred. It should hold that `tolerance > 0` and
+/// `limit > 0`.
+pub fn probability_until<I, F>(samples: I, predicate: F, tolerance: f64, limit: usize)
+                               -> Proportion where I: IntoIterator, F: Fn(I::Item) -> bool
+{
+    should!(tolerance > 0.0 && limit > 0);
+    let mut proportion = Proportion { successes: 0, samples: 0 };
+    for x in samples.into_iter().take(limit) {
+        proportion.samples += 1;
+        proportion.successes += predicate(x) as usize;
+        if proportion.samples as u64 >= MINIMUM && proportion.relative_error() <= tolerance {
+            break;
+        }
+    }
+    proportion
+}
+
+const MINIMUM: u64 = 100;
+
+// Compute the two-sided critical value of the standard Gaussian distribution
+// at confidence level `level`.
+fn critical(level: f64) -> f64 {
+    should!(0.0 < level && level < 1.0);
+    StandardGaussian.inverse((1.0 + level) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::{Proportion, expectation, expectation_until, probability, probability_until};
+
+    #[test]
+    fn expectation_coverage() {
+        let d = Gaussian::new(0.0, 1.0);
+        let covered = (0..200).filter(|&seed| {
+            let e = expectation(Independent(&d, &mut source::seeded(seed)), |x| x * x, 1000);
+            let (lower, upper) = e.interval(0.95);
+            lower < 1.0 && 1.0 < upper
+        }).count();
+        assert!(180 <= covered && covered <= 198);
+    }
+
+    #[test]
+    fn expectation_multivariate() {
+        let d = Joint::new((Gaussian::new(1.0, 1.0), Exponential::new(2.0)));
+        let mut source = source::seeded(42);
+        let draws = (0..).map(|_| d.sample(&mut source));
+        let e = expectation(draws, |(x, y)| x * y, 10000);
+        assert_eq!(e.samples, 10000);
+        assert::close(e.value, 0.5, 4.0 * e.standard_error);
+    }
+
+    #[test]
+    fn expectation_until_limit() {
+        let d = Gaussian::new(1.0, 1.0);
+        let e = expectation_until(Independent(&d, &mut source::seeded(42)), |x| x, 1e-6, 500);
+        assert_eq!(e.samples, 500);
+        assert!(e.relative_error() > 1e-6);
+
+        let e = expectation_until(Independent(&d, &mut source::seeded(42)), |x| x, 0.02,
+                                  1_000_000);
+        assert!(100 <= e.samples && e.samples < 1_000_000);
+        assert!(e.relative_error() <= 0.02);
+        assert::close(e.value, 1.0, 4.0 * e.standard_error);
+
+        let e = expectation_until(vec![1.0, 2.0, 3.0], |x| x, 1e-6, 100);
+        assert_eq!((e.value, e.samples), (2.0, 3));
+    }
+
+    #[test]
+    fn probability_interval() {
+        let d = Uniform::new(0.0, 1.0);
+        let p = probability(Independent(&d, &mut source::seeded(42)), |x| x < 0.3, 10000);
+        assert_eq!(p.samples, 10000);
+        let (lower, upper) = p.interval(0.99);
+        assert!(lower < 0.3 && 0.3 < upper);
+        assert!(lower < p.value() && p.value() < upper);
+
+        let p = Proportion { successes: 0, samples: 100 };
+        let (lower, upper) = p.interval(0.95);
+        assert_eq!((p.value(), p.standard_error(), lower), (0.0, 0.0, 0.0));
+        assert::close(upper, 1.959963984540054f64.powi(2) / (100.0 + 1.959963984540054f64.powi(2)),
+                      1e-15);
+
+        let p = Proportion { successes: 100, samples: 100 };
+        let (lower, upper) = p.interval(0.95);
+        assert!(0.9 < lower && lower < 1.0);
+        assert_eq!(upper, 1.0);
+    }
+
+    #[test]
+    fn probability_until_limit() {
+        let d = Gaussian::new(0.0, 1.0);
+        let p = probability_until(Independent(&d, &mut source::seeded(42)), |x| x > 10.0, 0.1,
+                                  2000);
+        assert_eq!((p.successes, p.samples), (0, 2000));
+        assert_eq!(p.relative_error(), ::std::f64::INFINITY);
+
+        let p = probability_until(Independent(&d, &mut source::seeded(42)), |x| x > 1.0, 0.05,
+                                  1_000_000);
+        assert!(p.samples < 1_000_000 && p.relative_error() <= 0.05);
+        assert::close(p.value(), 0.15865525393145705, 4.0 * p.standard_error());
+    }
+}
@@ -0,0 +1,159 @@
+//! Importance sampling.
+//!
+//! The expectation of `f(X)` with `X` following a target distribution is
+//! estimated by drawing samples from a proposal distribution and weighting
+//! them by the ratio of the target density to the proposal density. The
+//! proposal should cover the target, and it should put more mass where
+//! `f(x)` times the target density is large, which is how rare events can be
+//! estimated with few samples.
+//!
+//! ```
+//! use probability::monte_carlo::importance;
+//! use probability::prelude::*;
+//!
+//! let target = Gaussian::new(0.0, 1.0);
+//! let proposal = Affine::new(Exponential::new(4.0), 1.0, 4.0);
+//! let mut source = source::seeded(42);
+//! let estimate = importance::estimate(|x| if x > 4.0 { 1.0 } else { 0.0 },
+//!                                     &target, &proposal, &mut source, 10000);
+//! assert!((estimate.value - 3.167e-5).abs() < 1e-6);
+//! assert!(!estimate.degenerate(0.1));
+//! ```
+
+use distribution::{Continuous, Sample};
+use source::Source;
+
+/// An importance-sampling estimate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    /// The estimate normalized by the number of samples, which requires the
+    /// target density to be normalized.
+    pub value: f64,
+    /// The standard error of `value`.
+    pub standard_error: f64,
+    /// The estimate normalized by the sum of the weights, which is valid for
+    /// a target density known only up to a constant factor.
+    pub normalized_value: f64,
+    /// The standard error of `normalized_value` given by the delta method.
+    pub normalized_standard_error: f64,
+    /// The effective sample size `(Σw)² / Σw²`.
+    pub effective_sample_size: f64,
+    /// The number of samples.
+    pub samples: usize,
+}
+
+impl Estimate {
+    /// Check if the effective sample size is smaller than fraction
+    /// `threshold` of the number of samples.
+    ///
+    /// A degenerate estimate is dominated by a few samples with large
+    /// weights, which is a sign of a proposal that is a poor match for the
+    /// target; the standard errors are then unreliable too.
+    #[inline]
+    pub fn degenerate(&self, threshold: f64) -> bool {
+        self.efficiency() < threshold
+    }
+
+    /// Compute the ratio of the effective sample size to the number of
+    /// samples.
+    #[inline]
+    pub fn efficiency(&self) -> f64 {
+        self.effective_sample_size / self.samples as f64
+    }
+}
+
+/// Estimate the expectation of `f` under `target` using `n` samples from
+/// `proposal`.
+///
+/// The weights are computed in the log domain and scaled by the largest one
+/// before exponentiation, which prevents overflow and underflow. It should
+/// hold that `n > 1`.
+pub fn estimate<F, T, P, S>(f: F, target: &T, proposal: &P, source: &mut S, n: usize) -> Estimate
+    where F: Fn(f64) -> f64, T: Continuous, P: Continuous + Sample<Value=f64>, S: Source
+{
+    use math;
+
+    should!(n > 1);
+    let x = proposal.samples(source, n);
+    let values = x.iter().map(|&x| f(x)).collect::<Vec<_>>();
+    let weights = x.iter().map(|&x| target.ln_density(x) - proposal.ln_density(x))
+                          .collect::<Vec<_>>();
+    let shift = weights.iter().cloned().fold(-::std::f64::INFINITY, f64::max);
+    let weights = weights.iter().map(|&w| (w - shift).exp()).collect::<Vec<_>>();
+
+    let sum = math::sum(weights.iter().cloned());
+    let squares = math::sum(weights.iter().map(|&w| w * w));
+    let products = weights.iter().zip(&values).map(|(&w, &f)| w * f).collect::<Vec<_>>();
+    let total = math::sum(products.iter().cloned());
+
+    let scale = shift.exp();
+    let mean = total / n as f64;
+    let variance = math::sum(products.iter().map(|&p| (p - mean) * (p - mean))) /
+                   (n - 1) as f64;
+    let normalized = total / sum;
+    let spread = math::sum(weights.iter().zip(&values).map(|(&w, &f)| {
+        (w * (f - normalized)).powi(2)
+    }));
+
+    Estimate {
+        value: scale * mean,
+        standard_error: scale * (variance / n as f64).sqrt(),
+        normalized_value: normalized,
+        normalized_standard_error: spread.sqrt() / sum,
+        effective_sample_size: sum * sum / squares,
+        samples: n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::estimate;
+
+    #[test]
+    fn degenerate() {
+        let target = Gaussian::new(0.0, 1.0);
+        let proposal = Gaussian::new(0.0, 0.2);
+        let e = estimate(|x| x * x, &target, &proposal, &mut source::seeded(42), 10000);
+        assert!(e.degenerate(0.1));
+        assert!(e.efficiency() < 0.1);
+
+        let proposal = Gaussian::new(0.0, 1.5);
+        let e = estimate(|x| x * x, &target, &proposal, &mut source::seeded(42), 10000);
+        assert!(!e.degenerate(0.1));
+        assert::close(e.value, 1.0, 4.0 * e.standard_error);
+        assert::close(e.normalized_value, 1.0, 4.0 * e.normalized_standard_error);
+    }
+
+    #[test]
+    fn efficiency() {
+        let d = Gaussian::new(1.0, 2.0);
+        let e = estimate(|x| x, &d, &d, &mut source::seeded(42), 1000);
+        let x = d.samples(&mut source::seeded(42), 1000);
+        let mean = x.iter().sum::<f64>() / 1000.0;
+        assert_eq!(e.efficiency(), 1.0);
+        assert_eq!(e.effective_sample_size, 1000.0);
+        assert::close(e.value, mean, 1e-12);
+        assert::close(e.normalized_value, mean, 1e-12);
+    }
+
+    #[test]
+    fn value() {
+        let p = 3.1671241833119921254e-5;
+        let target = Gaussian::new(0.0, 1.0);
+        let indicator = |x: f64| if x > 4.0 { 1.0 } else { 0.0 };
+
+        let mut source = source::seeded(42);
+        let proposal = Affine::new(Exponential::new(4.0), 1.0, 4.0);
+        let e = estimate(&indicator, &target, &proposal, &mut source, 10000);
+        assert!((e.value - p).abs() < 0.05 * p);
+        assert!(e.standard_error < 0.01 * p);
+        // The proposal covers only the tail, where the indicator is one.
+        assert_eq!(e.normalized_value, 1.0);
+
+        let x = target.samples(&mut source, 10000);
+        assert_eq!(x.iter().filter(|&&x| x > 4.0).count(), 0);
+    }
+}
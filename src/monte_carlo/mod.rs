@@ -0,0 +1,6 @@
+//! Monte Carlo methods.
+
+pub mod bootstrap;
+pub mod crude;
+pub mod importance;
+pub mod rejection;
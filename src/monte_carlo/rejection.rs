@@ -0,0 +1,245 @@
+//! Rejection sampling.
+//!
+//! A sample from a target density `p` is drawn by drawing a candidate `x`
+//! from a proposal distribution with density `q` and accepting it with
+//! probability `p(x) / (M q(x))`, where `M` is a bound such that
+//! `p(x) <= M q(x)` everywhere. The target density needs to be known only up
+//! to a constant factor, which is then absorbed into `M`. All densities and
+//! bounds are given in the log domain.
+//!
+//! ```
+//! use probability::monte_carlo::rejection;
+//! use probability::prelude::*;
+//!
+//! // The density of Beta(2, 5) up to a constant factor.
+//! let target = |x: f64| x.ln() + 4.0 * (1.0 - x).ln();
+//! let proposal = Uniform::new(0.0, 1.0);
+//! let ln_bound = (0.2f64 * 0.8f64.powi(4)).ln();
+//!
+//! let mut source = source::seeded(42);
+//! let x = rejection::sample(&target, &proposal, ln_bound, &mut source).unwrap();
+//! ```
+
+use distribution::{Continuous, Sample};
+use source::Source;
+use SamplingError;
+
+/// The number of attempts after which sampling is abandoned.
+pub const ATTEMPTS: usize = 1_000_000;
+
+/// The number of candidates drawn in order to estimate the bound in
+/// `adaptive`.
+pub const PILOT: usize = 1000;
+
+/// Statistics of rejection sampling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    /// The number of accepted candidates.
+    pub accepted: usize,
+    /// The number of drawn candidates.
+    pub draws: usize,
+    /// The final bound in the log domain.
+    pub ln_bound: f64,
+}
+
+impl Statistics {
+    /// Compute the fraction of the drawn candidates that have been accepted.
+    #[inline]
+    pub fn acceptance_rate(&self) -> f64 {
+        self.accepted as f64 / self.draws as f64
+    }
+}
+
+/// Draw `n` samples from `target` while estimating the bound.
+///
+/// The bound is first estimated as the largest ratio of the target density to
+/// the proposal density over `PILOT` candidates and is then raised whenever a
+/// candidate exceeds it. The samples drawn before the last raise follow the
+/// target only approximately; the smaller the number of raises, the better.
+pub fn adaptive<F, D, S>(target: F, proposal: &D, source: &mut S, n: usize)
+                         -> Result<(Vec<f64>, Statistics), SamplingError>
+    where F: Fn(f64) -> f64, D: Continuous + Sample<Value=f64>, S: Source
+{
+    let mut ln_bound = -::std::f64::INFINITY;
+    for _ in 0..PILOT {
+        let x = proposal.sample(source);
+        ln_bound = ln_bound.max(target(x) - proposal.ln_density(x));
+    }
+    let mut statistics = Statistics { accepted: 0, draws: PILOT, ln_bound: ln_bound };
+    let mut samples = Vec::with_capacity(n);
+    let mut attempts = 0;
+    while samples.len() < n {
+        if attempts == ATTEMPTS {
+            return Err(SamplingError::AttemptsExhausted { attempts: ATTEMPTS });
+        }
+        attempts += 1;
+        statistics.draws += 1;
+        let x = proposal.sample(source);
+        let ratio = target(x) - proposal.ln_density(x);
+        if ratio > statistics.ln_bound {
+            statistics.ln_bound = ratio;
+        }
+        if uniform(source).ln() <= ratio - statistics.ln_bound {
+            samples.push(x);
+            attempts = 0;
+        }
+    }
+    statistics.accepted = n;
+    Ok((samples, statistics))
+}
+
+/// Draw a sample from `target` using `proposal` with bound `ln_bound`.
+///
+/// An error is returned if a candidate reveals that the bound is too small or
+/// if no candidate is accepted within `ATTEMPTS` attempts.
+pub fn sample<F, D, S>(target: F, proposal: &D, ln_bound: f64, source: &mut S)
+                       -> Result<f64, SamplingError>
+    where F: Fn(f64) -> f64, D: Continuous + Sample<Value=f64>, S: Source
+{
+    squeeze(target, |_| -::std::f64::INFINITY, proposal, ln_bound, source)
+}
+
+/// Draw `n` samples from `target` using `proposal` with bound `ln_bound`.
+pub fn samples<F, D, S>(target: F, proposal: &D, ln_bound: f64, source: &mut S, n: usize)
+                        -> Result<Vec<f64>, SamplingError>
+    where F: Fn(f64) -> f64, D: Continuous + Sample<Value=f64>, S: Source
+{
+    (0..n).map(|_| sample(&target, proposal, ln_bound, source)).collect()
+}
+
+/// Draw a sample from `target` using `proposal` with bound `ln_bound` and
+/// the squeeze function `lower`.
+///
+/// The squeeze function should be a cheap lower bound on the target density
+/// in the log domain. A candidate falling under it is accepted without
+/// evaluating the target density, which pays off when the latter is
+/// expensive.
+pub fn squeeze<F, G, D, S>(target: F, lower: G, proposal: &D, ln_bound: f64, source: &mut S)
+                           -> Result<f64, SamplingError>
+    where F: Fn(f64) -> f64, G: Fn(f64) -> f64, D: Continuous + Sample<Value=f64>, S: Source
+{
+    should!(ln_bound.is_finite());
+    for _ in 0..ATTEMPTS {
+        let x = proposal.sample(source);
+        let ln_envelope = proposal.ln_density(x) + ln_bound;
+        let u = uniform(source).ln();
+        if u <= lower(x) - ln_envelope {
+            return Ok(x);
+        }
+        let excess = target(x) - ln_envelope;
+        if excess > 0.0 {
+            return Err(SamplingError::BoundExceeded { x: x, excess: excess });
+        }
+        if u <= excess {
+            return Ok(x);
+        }
+    }
+    Err(SamplingError::AttemptsExhausted { attempts: ATTEMPTS })
+}
+
+// Draw a uniform sample from `(0, 1]`.
+#[inline(always)]
+fn uniform<S>(source: &mut S) -> f64 where S: Source {
+    ((source.read_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use prelude::*;
+
+    macro_rules! target(
+        () => (|x: f64| 30f64.ln() + x.ln() + 4.0 * (1.0 - x).ln());
+    );
+
+    fn deviation(x: &mut Vec<f64>) -> f64 {
+        let d = Beta::new(2.0, 5.0, 0.0, 1.0);
+        let n = x.len() as f64;
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        x.iter().enumerate().fold(0.0f64, |deviation, (i, &x)| {
+            let p = d.distribution(x);
+            deviation.max((p - i as f64 / n).abs()).max((p - (i + 1) as f64 / n).abs())
+        })
+    }
+
+    #[test]
+    fn adaptive() {
+        use super::adaptive;
+
+        let proposal = Uniform::new(0.0, 1.0);
+        let (mut x, statistics) = adaptive(target!(), &proposal, &mut source::seeded(42), 10000)
+                                      .unwrap();
+        assert!(deviation(&mut x) < 0.015);
+        assert_eq!(statistics.accepted, 10000);
+        assert!(statistics.ln_bound <= 2.4576f64.ln());
+        assert!((statistics.acceptance_rate() - 1.0 / 2.4576).abs() < 0.02);
+
+        let result = adaptive(|_| -::std::f64::INFINITY, &proposal, &mut source::seeded(42), 1);
+        assert_eq!(result.unwrap_err(), SamplingError::AttemptsExhausted { attempts: 1_000_000 });
+    }
+
+    #[test]
+    fn sample() {
+        use super::sample;
+
+        let mut source = source::seeded(42);
+        let proposal = Uniform::new(0.0, 1.0);
+        let x = sample(target!(), &proposal, 2.4576f64.ln(), &mut source).unwrap();
+        assert!(0.0 < x && x < 1.0);
+
+        let mut undercovered = false;
+        for _ in 0..100 {
+            if let Err(SamplingError::BoundExceeded { x, excess }) =
+                   sample(target!(), &proposal, 0.0, &mut source) {
+                assert!(excess > 0.0 && Beta::new(2.0, 5.0, 0.0, 1.0).density(x) > 1.0);
+                undercovered = true;
+            }
+        }
+        assert!(undercovered);
+
+        let proposal = Gaussian::new(0.2, 0.05);
+        let result = super::samples(target!(), &proposal, 3.0, &mut source, 1000);
+        match result {
+            Err(SamplingError::BoundExceeded { .. }) => {},
+            _ => panic!("the undercoverage should be detected"),
+        }
+    }
+
+    #[test]
+    fn samples() {
+        use super::samples;
+
+        let proposal = Uniform::new(0.0, 1.0);
+        let mut source = source::seeded(42);
+        let mut x = samples(target!(), &proposal, 2.5f64.ln(), &mut source, 10000).unwrap();
+        assert_eq!(x.len(), 10000);
+        assert!(deviation(&mut x) < 0.015);
+    }
+
+    #[test]
+    fn squeeze() {
+        use super::squeeze;
+
+        let count = Cell::new(0);
+        let target = |x: f64| {
+            count.set(count.get() + 1);
+            30f64.ln() + x.ln() + 4.0 * (1.0 - x).ln()
+        };
+        let lower = |x: f64| 30f64.ln() + x.ln() - 4.0 * x / (1.0 - x);
+
+        let proposal = Uniform::new(0.0, 1.0);
+        let mut source = source::seeded(42);
+        let mut x = (0..10000).map(|_| {
+            squeeze(&target, &lower, &proposal, 2.4576f64.ln(), &mut source).unwrap()
+        }).collect::<Vec<_>>();
+        assert!(deviation(&mut x) < 0.015);
+
+        let squeezed = count.replace(0);
+        let mut source = source::seeded(42);
+        for _ in 0..10000 {
+            super::sample(&target, &proposal, 2.4576f64.ln(), &mut source).unwrap();
+        }
+        assert!((squeezed as f64) < 0.75 * count.get() as f64);
+    }
+}
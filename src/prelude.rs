@@ -1,29 +1,115 @@
 //! Reexports of commonly used entities.
+//!
+//! The prelude covers the distribution traits, the distributions, the
+//! samplers, and the sources of randomness, which suffices to fit, evaluate,
+//! and sample a distribution:
+//!
+//! ```
+//! use probability::prelude::*;
+//!
+//! let data = [1.2, 0.4, 2.1, 1.6, 0.9, 1.3];
+//! let distribution = Kde::new(&data);
+//! let density = distribution.density(1.0);
+//! let samples = Independent(&distribution, &mut source::seeded(42)).take(10).collect::<Vec<_>>();
+//! # assert!(density > 0.0 && samples.len() == 10);
+//! ```
+//!
+//! Some names, such as `Distribution` and `Uniform`, coincide with the names
+//! in the prelude of the `rand` crate, and importing both preludes with globs
+//! makes them ambiguous; in that case, one of the two should be imported by
+//! name, or the distributions can be imported from the root of the crate.
 
 pub use distribution::Distribution;
 
 pub use distribution::Continuous;
 pub use distribution::Discrete;
 
+pub use distribution::Divergence;
 pub use distribution::Entropy;
+#[cfg(feature = "alloc")]
+pub use distribution::FisherInformation;
 pub use distribution::Inverse;
 pub use distribution::Kurtosis;
+pub use distribution::MaximumLikelihood;
 pub use distribution::Mean;
 pub use distribution::Median;
+pub use distribution::MethodOfMoments;
+pub use distribution::Mgf;
+#[cfg(feature = "alloc")]
 pub use distribution::Modes;
+#[cfg(feature = "alloc")]
+pub use distribution::Parametric;
 pub use distribution::Sample;
 pub use distribution::Skewness;
 pub use distribution::Variance;
 
+pub use distribution::Multivariate;
+pub use distribution::MultivariateContinuous;
+#[cfg(feature = "alloc")]
+pub use distribution::MultivariateMean;
+pub use distribution::MultivariateSample;
+#[cfg(feature = "alloc")]
+pub use distribution::MultivariateVariance;
+
+pub use distribution::Affine;
 pub use distribution::Bernoulli;
 pub use distribution::Beta;
+pub use distribution::BetaBinomial;
 pub use distribution::Binomial;
+pub use distribution::Cached;
+#[cfg(feature = "alloc")]
 pub use distribution::Categorical;
+pub use distribution::Censored;
+pub use distribution::Censoring;
+pub use distribution::Compound;
+#[cfg(feature = "alloc")]
+pub use distribution::CompoundPoisson;
+#[cfg(feature = "alloc")]
+pub use distribution::Dirichlet;
+#[cfg(feature = "alloc")]
+pub use distribution::EmOptions;
+#[cfg(feature = "alloc")]
+pub use distribution::Empirical;
 pub use distribution::Exponential;
 pub use distribution::Gamma;
 pub use distribution::Gaussian;
+pub use distribution::GaussianInverseGamma;
+#[cfg(feature = "alloc")]
+pub use distribution::Histogram;
+#[cfg(feature = "alloc")]
+pub use distribution::Interpolation;
+#[cfg(feature = "alloc")]
+pub use distribution::Joint;
+#[cfg(feature = "alloc")]
+pub use distribution::Kde;
+#[cfg(feature = "alloc")]
+pub use distribution::Mixture;
+pub use distribution::NegativeBinomial;
+pub use distribution::OrderStatistic;
+pub use distribution::PointMass;
+pub use distribution::Poisson;
+pub use distribution::StandardGaussian;
+pub use distribution::StudentT;
+pub use distribution::Sum;
 pub use distribution::Uniform;
 
+pub use sampler::Antithetic;
+#[cfg(feature = "alloc")]
+pub use sampler::Common;
 pub use sampler::Independent;
+#[cfg(feature = "alloc")]
+pub use sampler::LatinHypercube;
+#[cfg(feature = "alloc")]
+pub use sampler::Quasi;
+
+#[cfg(feature = "rand")]
+pub use interop::FromRand;
+
+pub use FitError;
+pub use ParameterError;
+pub use SamplingError;
+
+#[cfg(feature = "alloc")]
+pub use quasi::{self, LowDiscrepancy};
 
 pub use source;
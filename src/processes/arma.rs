@@ -0,0 +1,428 @@
+use distribution::{Gaussian, Sample, Variance};
+use math;
+use source::Source;
+use ParameterError;
+
+/// An autoregressive process.
+///
+/// The process is an `Arma` process without the moving-average part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ar<D = Gaussian>(Arma<D>);
+
+/// An autoregressive moving-average process.
+///
+/// The process follows `X(t) = Σ φ(i) X(t - i) + ε(t) + Σ θ(j) ε(t - j)`,
+/// where `φ` are the autoregressive coefficients, `θ` are the moving-average
+/// coefficients, and `ε` are independent innovations, which are Gaussian
+/// unless another distribution is given. The innovations are centered by
+/// subtracting the mean of their distribution. The autoregressive part is
+/// required to be stationary, which is checked by converting the coefficients
+/// into partial autocorrelations using the Durbin–Levinson recursion run
+/// backward; the process is stationary if and only if all of them lie
+/// strictly between minus one and one.
+///
+/// ```
+/// use probability::prelude::*;
+/// use probability::processes::Arma;
+///
+/// let process = Arma::new(vec![0.5], vec![0.4], 1.0);
+/// let x = process.simulate(&mut source::seeded(42), 1000, 100);
+/// assert_eq!(x.len(), 1000);
+/// assert!((process.acf(1)[1] - 1.2 * 0.9 / 1.56).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arma<D = Gaussian> {
+    ar: Vec<f64>,
+    ma: Vec<f64>,
+    noise: D,
+}
+
+/// A moving-average process.
+///
+/// The process is an `Arma` process without the autoregressive part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ma<D = Gaussian>(Arma<D>);
+
+impl Ar {
+    /// Create a process with the coefficients `coefficients` and Gaussian
+    /// innovations with standard deviation `noise_sd`.
+    ///
+    /// It should hold that the coefficients define a stationary process and
+    /// that `noise_sd > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(coefficients: Vec<f64>, noise_sd: f64) -> Self {
+        unwrap!(Ar::try_new(coefficients, noise_sd))
+    }
+
+    /// Create a process with the coefficients `coefficients` and Gaussian
+    /// innovations with standard deviation `noise_sd` or return an error if
+    /// the parameters are invalid.
+    #[inline]
+    pub fn try_new(coefficients: Vec<f64>, noise_sd: f64) -> Result<Self, ParameterError> {
+        Ok(Ar(Arma::try_new(coefficients, vec![], noise_sd)?))
+    }
+}
+
+impl<D> Ar<D> where D: Sample<Value=f64> + Variance {
+    /// Create a process with the coefficients `coefficients` and innovations
+    /// following `noise`.
+    ///
+    /// It should hold that the coefficients define a stationary process; the
+    /// function panics otherwise.
+    #[inline]
+    pub fn with_noise(coefficients: Vec<f64>, noise: D) -> Self {
+        unwrap!(Ar::try_with_noise(coefficients, noise))
+    }
+
+    /// Create a process with the coefficients `coefficients` and innovations
+    /// following `noise` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_with_noise(coefficients: Vec<f64>, noise: D) -> Result<Self, ParameterError> {
+        Ok(Ar(Arma::try_with_noise(coefficients, vec![], noise)?))
+    }
+
+    /// Compute the autocorrelation function at lags from zero up to `max_lag`
+    /// using the Yule–Walker equations.
+    #[inline]
+    pub fn acf(&self, max_lag: usize) -> Vec<f64> {
+        self.0.acf(max_lag)
+    }
+
+    /// Return the coefficients.
+    #[inline(always)]
+    pub fn coefficients(&self) -> &[f64] {
+        self.0.ar()
+    }
+
+    /// Draw a series of length `n` after discarding `burn_in` values; see
+    /// `Arma::simulate`.
+    #[inline]
+    pub fn simulate<S>(&self, source: &mut S, n: usize, burn_in: usize) -> Vec<f64>
+        where S: Source
+    {
+        self.0.simulate(source, n, burn_in)
+    }
+
+    /// Compute the stationary variance.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.0.variance()
+    }
+}
+
+impl Arma {
+    /// Create a process with the autoregressive coefficients `ar`, the
+    /// moving-average coefficients `ma`, and Gaussian innovations with
+    /// standard deviation `noise_sd`.
+    ///
+    /// It should hold that the autoregressive coefficients define a stationary
+    /// process, that the moving-average coefficients are finite, and that
+    /// `noise_sd > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(ar: Vec<f64>, ma: Vec<f64>, noise_sd: f64) -> Self {
+        unwrap!(Arma::try_new(ar, ma, noise_sd))
+    }
+
+    /// Create a process with the autoregressive coefficients `ar`, the
+    /// moving-average coefficients `ma`, and Gaussian innovations with
+    /// standard deviation `noise_sd` or return an error if the parameters are
+    /// invalid.
+    #[inline]
+    pub fn try_new(ar: Vec<f64>, ma: Vec<f64>, noise_sd: f64) -> Result<Self, ParameterError> {
+        ensure!(noise_sd > 0.0 && noise_sd.is_finite(),
+                ParameterError::SigmaNotPositive { value: noise_sd });
+        Arma::try_with_noise(ar, ma, Gaussian::try_new(0.0, noise_sd)?)
+    }
+}
+
+impl<D> Arma<D> where D: Sample<Value=f64> + Variance {
+    /// Create a process with the autoregressive coefficients `ar`, the
+    /// moving-average coefficients `ma`, and innovations following `noise`.
+    ///
+    /// It should hold that the autoregressive coefficients define a stationary
+    /// process and that the moving-average coefficients are finite; the
+    /// function panics otherwise.
+    #[inline]
+    pub fn with_noise(ar: Vec<f64>, ma: Vec<f64>, noise: D) -> Self {
+        unwrap!(Arma::try_with_noise(ar, ma, noise))
+    }
+
+    /// Create a process with the autoregressive coefficients `ar`, the
+    /// moving-average coefficients `ma`, and innovations following `noise` or
+    /// return an error if the parameters are invalid.
+    pub fn try_with_noise(ar: Vec<f64>, ma: Vec<f64>, noise: D)
+                          -> Result<Self, ParameterError>
+    {
+        for &value in ar.iter().chain(&ma) {
+            ensure!(value.is_finite(), ParameterError::CoefficientNotFinite { value: value });
+        }
+        ensure!(is_stationary(&ar), ParameterError::ArNotStationary);
+        Ok(Arma { ar: ar, ma: ma, noise: noise })
+    }
+
+    /// Compute the autocorrelation function at lags from zero up to `max_lag`.
+    ///
+    /// The autocovariances up to the autoregressive order are found by
+    /// solving the generalized Yule–Walker equations, and the rest follow by
+    /// the recursion `γ(k) = Σ φ(i) γ(k - i)` beyond the moving-average order.
+    pub fn acf(&self, max_lag: usize) -> Vec<f64> {
+        let gamma = self.autocovariances(max_lag);
+        gamma.iter().map(|&value| value / gamma[0]).collect()
+    }
+
+    /// Return the autoregressive coefficients.
+    #[inline(always)]
+    pub fn ar(&self) -> &[f64] {
+        &self.ar
+    }
+
+    /// Return the moving-average coefficients.
+    #[inline(always)]
+    pub fn ma(&self) -> &[f64] {
+        &self.ma
+    }
+
+    /// Return the distribution of the innovations.
+    #[inline(always)]
+    pub fn noise(&self) -> &D {
+        &self.noise
+    }
+
+    /// Draw a series of length `n` after discarding `burn_in` values.
+    ///
+    /// The recursion starts from zero values and zero innovations, so the
+    /// burn-in should be long enough for the process to forget the start,
+    /// which takes longer the closer the roots of the characteristic
+    /// polynomial are to the unit circle.
+    pub fn simulate<S>(&self, source: &mut S, n: usize, burn_in: usize) -> Vec<f64>
+        where S: Source
+    {
+        let (p, q) = (self.ar.len(), self.ma.len());
+        let mean = self.noise.mean();
+        let total = burn_in + n;
+        let mut x = Vec::with_capacity(total);
+        let mut e = Vec::with_capacity(total);
+        for t in 0..total {
+            let innovation = self.noise.sample(source) - mean;
+            let mut value = innovation;
+            for i in 1..(p.min(t) + 1) {
+                value += self.ar[i - 1] * x[t - i];
+            }
+            for j in 1..(q.min(t) + 1) {
+                value += self.ma[j - 1] * e[t - j];
+            }
+            x.push(value);
+            e.push(innovation);
+        }
+        x.split_off(burn_in)
+    }
+
+    /// Compute the stationary variance.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.autocovariances(0)[0]
+    }
+
+    fn autocovariances(&self, max_lag: usize) -> Vec<f64> {
+        let (p, q) = (self.ar.len(), self.ma.len());
+        let theta = |j: usize| if j == 0 { 1.0 } else { self.ma[j - 1] };
+        let mut psi = vec![1.0];
+        for j in 1..(q + 1) {
+            let value = theta(j) + (1..(j.min(p) + 1)).map(|i| self.ar[i - 1] * psi[j - i])
+                                                         .sum::<f64>();
+            psi.push(value);
+        }
+        let variance = self.noise.variance();
+        let right = |k: usize| -> f64 {
+            (k..(q + 1)).map(|j| theta(j) * psi[j - k]).sum::<f64>() * variance
+        };
+        let m = p + 1;
+        let mut system = vec![0.0; m * m];
+        for k in 0..m {
+            system[k * m + k] += 1.0;
+            for i in 1..m {
+                let lag = k.max(i) - k.min(i);
+                system[k * m + lag] -= self.ar[i - 1];
+            }
+        }
+        let inverse = math::invert(&system).unwrap();
+        let mut gamma = (0..m).map(|k| {
+            (0..m).map(|l| inverse[k * m + l] * right(l)).sum::<f64>()
+        }).collect::<Vec<_>>();
+        for k in m..(max_lag + 1) {
+            let value = (1..m).map(|i| self.ar[i - 1] * gamma[k - i]).sum::<f64>() + right(k);
+            gamma.push(value);
+        }
+        gamma.truncate(max_lag + 1);
+        gamma
+    }
+}
+
+impl Ma {
+    /// Create a process with the coefficients `coefficients` and Gaussian
+    /// innovations with standard deviation `noise_sd`.
+    ///
+    /// It should hold that the coefficients are finite and that
+    /// `noise_sd > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(coefficients: Vec<f64>, noise_sd: f64) -> Self {
+        unwrap!(Ma::try_new(coefficients, noise_sd))
+    }
+
+    /// Create a process with the coefficients `coefficients` and Gaussian
+    /// innovations with standard deviation `noise_sd` or return an error if
+    /// the parameters are invalid.
+    #[inline]
+    pub fn try_new(coefficients: Vec<f64>, noise_sd: f64) -> Result<Self, ParameterError> {
+        Ok(Ma(Arma::try_new(vec![], coefficients, noise_sd)?))
+    }
+}
+
+impl<D> Ma<D> where D: Sample<Value=f64> + Variance {
+    /// Create a process with the coefficients `coefficients` and innovations
+    /// following `noise`.
+    ///
+    /// It should hold that the coefficients are finite; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn with_noise(coefficients: Vec<f64>, noise: D) -> Self {
+        unwrap!(Ma::try_with_noise(coefficients, noise))
+    }
+
+    /// Create a process with the coefficients `coefficients` and innovations
+    /// following `noise` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_with_noise(coefficients: Vec<f64>, noise: D) -> Result<Self, ParameterError> {
+        Ok(Ma(Arma::try_with_noise(vec![], coefficients, noise)?))
+    }
+
+    /// Compute the autocorrelation function at lags from zero up to
+    /// `max_lag`, which vanishes beyond the order of the process.
+    #[inline]
+    pub fn acf(&self, max_lag: usize) -> Vec<f64> {
+        self.0.acf(max_lag)
+    }
+
+    /// Return the coefficients.
+    #[inline(always)]
+    pub fn coefficients(&self) -> &[f64] {
+        self.0.ma()
+    }
+
+    /// Draw a series of length `n` after discarding `burn_in` values; see
+    /// `Arma::simulate`.
+    #[inline]
+    pub fn simulate<S>(&self, source: &mut S, n: usize, burn_in: usize) -> Vec<f64>
+        where S: Source
+    {
+        self.0.simulate(source, n, burn_in)
+    }
+
+    /// Compute the variance.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.0.variance()
+    }
+}
+
+// Check the stationarity of autoregressive coefficients by running the
+// Durbin–Levinson recursion backward, which turns them into partial
+// autocorrelations.
+fn is_stationary(ar: &[f64]) -> bool {
+    let mut phi = ar.to_vec();
+    for k in (1..(phi.len() + 1)).rev() {
+        let a = phi[k - 1];
+        if !(a.abs() < 1.0) {
+            return false;
+        }
+        let scale = 1.0 - a * a;
+        phi = (1..k).map(|j| (phi[j - 1] + a * phi[k - j - 1]) / scale).collect();
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use processes::{Ar, Arma, Ma};
+    use statistics;
+
+    #[test]
+    fn ar() {
+        let (phi1, phi2) = (0.5, 0.3);
+        let process = Ar::new(vec![phi1, phi2], 2.0);
+        let rho1 = phi1 / (1.0 - phi2);
+        let rho2 = phi1 * rho1 + phi2;
+        let expected = vec![1.0, rho1, rho2, phi1 * rho2 + phi2 * rho1];
+        let rho = process.acf(3);
+        assert::close(&rho, &expected, 1e-12);
+        let variance = 4.0 / (1.0 - phi1 * rho1 - phi2 * rho2);
+        assert::close(process.variance(), variance, 1e-12);
+
+        let x = process.simulate(&mut source::seeded(42), 100000, 1000);
+        let rho = statistics::acf_fft(&x, 10);
+        let theoretical = process.acf(10);
+        let band = 3.0 * (theoretical.iter().map(|rho| rho * rho).sum::<f64>() * 2.0 /
+                          x.len() as f64).sqrt();
+        for k in 1..11 {
+            assert!((rho[k] - theoretical[k]).abs() < band);
+        }
+        assert::close(statistics::variance(&x), variance, 0.2);
+    }
+
+    #[test]
+    fn arma() {
+        let (phi, theta) = (0.7, -0.4);
+        let process = Arma::new(vec![phi], vec![theta], 1.5);
+        let variance = 2.25 * (1.0 + 2.0 * phi * theta + theta * theta) / (1.0 - phi * phi);
+        assert::close(process.variance(), variance, 1e-12);
+        let rho1 = (1.0 + phi * theta) * (phi + theta) / (1.0 + 2.0 * phi * theta + theta * theta);
+        let rho = process.acf(4);
+        assert::close(rho[1], rho1, 1e-12);
+        assert::close(rho[4], rho1 * phi.powi(3), 1e-12);
+        assert_eq!((process.ar(), process.ma()), (&[phi][..], &[theta][..]));
+    }
+
+    #[test]
+    fn ma() {
+        let theta = 0.6;
+        let process = Ma::new(vec![theta], 1.0);
+        let (rho, expected) = (process.acf(3), vec![1.0, theta / (1.0 + theta * theta), 0.0, 0.0]);
+        assert::close(&rho, &expected, 1e-12);
+        assert::close(process.variance(), 1.0 + theta * theta, 1e-12);
+        let x = process.simulate(&mut source::seeded(42), 100000, 10);
+        let rho = statistics::acf_fft(&x, 3);
+        assert::close(rho[1], theta / (1.0 + theta * theta), 0.01);
+        assert::close(rho[2], 0.0, 0.01);
+    }
+
+    #[test]
+    fn noise() {
+        let process = Ar::with_noise(vec![0.8], Exponential::new(1.0));
+        assert::close(process.variance(), 1.0 / (1.0 - 0.64), 1e-12);
+        let x = process.simulate(&mut source::seeded(42), 100000, 100);
+        assert::close(statistics::mean(&x), 0.0, 0.05);
+        assert::close(statistics::variance(&x), 1.0 / (1.0 - 0.64), 0.1);
+        let process = Ma::with_noise(vec![0.5, 0.25], Uniform::new(-1.0, 1.0));
+        assert::close(process.variance(), (1.0 + 0.25 + 0.0625) / 3.0, 1e-12);
+    }
+
+    #[test]
+    fn stationarity() {
+        for coefficients in &[vec![1.0], vec![-1.2], vec![0.5, 0.6], vec![-0.5, 0.6],
+                              vec![0.0, 0.0, 1.0], vec![::std::f64::NAN]] {
+            assert!(Ar::try_new(coefficients.clone(), 1.0).is_err());
+        }
+        assert_eq!(Ar::try_new(vec![0.5, 0.6], 1.0), Err(ParameterError::ArNotStationary));
+        for coefficients in &[vec![], vec![0.99], vec![0.5, 0.3], vec![1.5, -0.75],
+                              vec![0.2, 0.2, 0.2, 0.2]] {
+            assert!(Ar::try_new(coefficients.clone(), 1.0).is_ok());
+        }
+        assert_eq!(Ma::try_new(vec![::std::f64::INFINITY], 1.0),
+                   Err(ParameterError::CoefficientNotFinite { value: ::std::f64::INFINITY }));
+        assert_eq!(Ma::try_new(vec![2.0], 0.0),
+                   Err(ParameterError::SigmaNotPositive { value: 0.0 }));
+        assert_eq!(Ar::new(vec![0.5], 1.0).coefficients(), &[0.5]);
+    }
+}
@@ -0,0 +1,234 @@
+use distribution::{Sample, StandardGaussian};
+use processes::check;
+use source::Source;
+use ParameterError;
+
+/// A Brownian motion with drift.
+///
+/// The process starts at zero at time zero and has independent Gaussian
+/// increments with mean `mu * dt` and variance `sigma² * dt` over intervals of
+/// length `dt`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BrownianMotion {
+    mu: f64,
+    sigma: f64,
+}
+
+/// A geometric Brownian motion.
+///
+/// The process is `s0 * exp((mu - sigma² / 2) * t + sigma * W(t))`, where `W`
+/// is a standard Brownian motion, so that its expected value is
+/// `s0 * exp(mu * t)`, and its value at each time follows a lognormal
+/// distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeometricBrownianMotion {
+    motion: BrownianMotion,
+    mu: f64,
+    s0: f64,
+}
+
+impl BrownianMotion {
+    /// Create a process with drift `mu` and volatility `sigma`.
+    ///
+    /// It should hold that `mu` is finite and `sigma > 0`; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn new(mu: f64, sigma: f64) -> Self {
+        unwrap!(BrownianMotion::try_new(mu, sigma))
+    }
+
+    /// Create a process with drift `mu` and volatility `sigma` or return an
+    /// error if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, ParameterError> {
+        ensure!(mu.is_finite(), ParameterError::MuNotFinite { value: mu });
+        ensure!(sigma > 0.0 && sigma.is_finite(),
+                ParameterError::SigmaNotPositive { value: sigma });
+        Ok(BrownianMotion { mu: mu, sigma: sigma })
+    }
+
+    /// Return the drift.
+    #[inline(always)]
+    pub fn mu(&self) -> f64 { self.mu }
+
+    /// Draw a bridge, that is, a path conditioned on taking the value `start`
+    /// at the first time of `grid` and the value `end` at the last one.
+    ///
+    /// The values at the times of the grid are drawn sequentially, each one
+    /// from its Gaussian distribution given the previous value and the end
+    /// point, and the endpoints are returned exactly. The bridge does not
+    /// depend on the drift. It should hold that the grid has at least two
+    /// times, which are nonnegative, finite, and strictly increasing; the
+    /// function panics otherwise.
+    pub fn sample_bridge<S>(&self, source: &mut S, grid: &[f64], start: f64, end: f64)
+                            -> Vec<f64>
+        where S: Source
+    {
+        check(grid);
+        assert!(grid.len() > 1, "the grid should have at least two times (got {})", grid.len());
+        let last = grid[grid.len() - 1];
+        let mut path = Vec::with_capacity(grid.len());
+        path.push(start);
+        let mut x = start;
+        for pair in grid[..grid.len() - 1].windows(2) {
+            let (dt, remaining) = (pair[1] - pair[0], last - pair[0]);
+            let mean = x + (end - x) * dt / remaining;
+            let variance = self.sigma * self.sigma * dt * (last - pair[1]) / remaining;
+            x = mean + variance.sqrt() * StandardGaussian.sample(source);
+            path.push(x);
+        }
+        path.push(end);
+        path
+    }
+
+    /// Draw the values of a path at the times of `grid`.
+    ///
+    /// The increments between consecutive times are drawn independently with
+    /// the standard deviation scaled by the square root of the time step. It
+    /// should hold that the times are nonnegative, finite, and strictly
+    /// increasing; the function panics otherwise.
+    pub fn sample_path<S>(&self, source: &mut S, grid: &[f64]) -> Vec<f64> where S: Source {
+        check(grid);
+        let (mut time, mut x) = (0.0, 0.0);
+        grid.iter().map(|&next| {
+            let dt = next - time;
+            x += self.mu * dt + self.sigma * dt.sqrt() * StandardGaussian.sample(source);
+            time = next;
+            x
+        }).collect()
+    }
+
+    /// Return the volatility.
+    #[inline(always)]
+    pub fn sigma(&self) -> f64 { self.sigma }
+}
+
+impl GeometricBrownianMotion {
+    /// Create a process with drift `mu`, volatility `sigma`, and initial value
+    /// `s0`.
+    ///
+    /// It should hold that `mu` is finite, `sigma > 0`, and `s0 > 0`; the
+    /// function panics otherwise.
+    #[inline]
+    pub fn new(mu: f64, sigma: f64, s0: f64) -> Self {
+        unwrap!(GeometricBrownianMotion::try_new(mu, sigma, s0))
+    }
+
+    /// Create a process with drift `mu`, volatility `sigma`, and initial value
+    /// `s0` or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, sigma: f64, s0: f64) -> Result<Self, ParameterError> {
+        ensure!(mu.is_finite(), ParameterError::MuNotFinite { value: mu });
+        ensure!(sigma > 0.0 && sigma.is_finite(),
+                ParameterError::SigmaNotPositive { value: sigma });
+        ensure!(s0 > 0.0 && s0.is_finite(), ParameterError::S0NotPositive { value: s0 });
+        let motion = BrownianMotion::try_new(mu - 0.5 * sigma * sigma, sigma)?;
+        Ok(GeometricBrownianMotion { motion: motion, mu: mu, s0: s0 })
+    }
+
+    /// Return the drift.
+    #[inline(always)]
+    pub fn mu(&self) -> f64 { self.mu }
+
+    /// Return the initial value.
+    #[inline(always)]
+    pub fn s0(&self) -> f64 { self.s0 }
+
+    /// Draw the values of a path at the times of `grid`.
+    ///
+    /// The path is the exponential of a Brownian motion with the drift
+    /// `mu - sigma² / 2`. It should hold that the times are nonnegative,
+    /// finite, and strictly increasing; the function panics otherwise.
+    pub fn sample_path<S>(&self, source: &mut S, grid: &[f64]) -> Vec<f64> where S: Source {
+        self.motion.sample_path(source, grid).into_iter().map(|x| self.s0 * x.exp()).collect()
+    }
+
+    /// Return the volatility.
+    #[inline(always)]
+    pub fn sigma(&self) -> f64 { self.motion.sigma }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use processes::{BrownianMotion, GeometricBrownianMotion};
+    use statistics;
+    use tests::ks_one_sample;
+
+    #[test]
+    fn bridge() {
+        let motion = BrownianMotion::new(3.0, 2.0);
+        let grid = [1.0, 1.5, 2.0, 4.0, 5.0];
+        let mut source = source::seeded(42);
+        let mut middle = vec![];
+        for _ in 0..20000 {
+            let path = motion.sample_bridge(&mut source, &grid, -1.0, 3.0);
+            assert_eq!((path[0], path[4]), (-1.0, 3.0));
+            middle.push(path[2]);
+        }
+        assert::close(statistics::mean(&middle), 0.0, 0.03);
+        assert::close(statistics::variance(&middle), 4.0 * 1.0 * 3.0 / 4.0, 0.05);
+        assert_eq!(motion.sample_bridge(&mut source, &[0.0, 1.0], 2.0, 5.0), vec![2.0, 5.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the times should be strictly increasing (got 1 after 1)")]
+    fn duplicate() {
+        BrownianMotion::new(0.0, 1.0).sample_path(&mut source::seeded(42), &[0.5, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn geometric() {
+        let (mu, sigma, s0, time): (f64, f64, f64, f64) = (0.05, 0.3, 100.0, 2.0);
+        let process = GeometricBrownianMotion::new(mu, sigma, s0);
+        let mut source = source::seeded(42);
+        let terminal = (0..20000).map(|_| {
+            process.sample_path(&mut source, &[0.5, 1.0, time])[2]
+        }).collect::<Vec<_>>();
+        let mean = s0 * (mu * time).exp();
+        let variance = mean * mean * ((sigma * sigma * time).exp() - 1.0);
+        assert::close(statistics::mean(&terminal) / mean, 1.0, 0.01);
+        assert::close(statistics::variance(&terminal) / variance, 1.0, 0.05);
+        let logarithms = terminal.iter().map(|&x| x.ln()).collect::<Vec<_>>();
+        let distribution = Gaussian::new(s0.ln() + (mu - 0.5 * sigma * sigma) * time,
+                                         sigma * time.sqrt());
+        assert!(ks_one_sample(&logarithms, &distribution).p_value > 0.05);
+    }
+
+    #[test]
+    fn increments() {
+        let (mu, sigma) = (0.5, 1.5);
+        let motion = BrownianMotion::new(mu, sigma);
+        let grid = [0.1, 0.5, 0.6, 2.0, 2.05, 3.0];
+        let mut source = source::seeded(42);
+        let paths = (0..20000).map(|_| motion.sample_path(&mut source, &grid))
+                              .collect::<Vec<_>>();
+        let mut time = 0.0;
+        for (i, &next) in grid.iter().enumerate() {
+            let dt = next - time;
+            let increments = paths.iter().map(|path| {
+                path[i] - if i == 0 { 0.0 } else { path[i - 1] }
+            }).collect::<Vec<_>>();
+            assert::close(statistics::mean(&increments) / dt.sqrt(), mu * dt.sqrt(), 0.05);
+            assert::close(statistics::variance(&increments) / dt, sigma * sigma, 0.07);
+            time = next;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the times should be strictly increasing (got 0.5 after 1)")]
+    fn monotone() {
+        BrownianMotion::new(0.0, 1.0).sample_path(&mut source::seeded(42), &[1.0, 0.5]);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(BrownianMotion::try_new(0.0, 0.0),
+                   Err(ParameterError::SigmaNotPositive { value: 0.0 }));
+        assert_eq!(GeometricBrownianMotion::try_new(0.0, 1.0, -1.0),
+                   Err(ParameterError::S0NotPositive { value: -1.0 }));
+        let process = GeometricBrownianMotion::new(0.1, 0.2, 3.0);
+        assert_eq!((process.mu(), process.sigma(), process.s0()), (0.1, 0.2, 3.0));
+    }
+}
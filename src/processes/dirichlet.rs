@@ -0,0 +1,333 @@
+use distribution::{Beta, Sample};
+use source::Source;
+use ParameterError;
+
+/// A Chinese restaurant process.
+///
+/// Customers arrive one at a time. The `i`th customer, counting from zero,
+/// joins an occupied table with probability proportional to the number of
+/// customers seated at it or opens a new table with probability
+/// `alpha / (i + alpha)`. The tables induce an exchangeable random partition,
+/// which is the partition of a sample from a Dirichlet process with
+/// concentration `alpha`.
+///
+/// ```
+/// use probability::prelude::*;
+/// use probability::processes::ChineseRestaurant;
+///
+/// let process = ChineseRestaurant::new(1.5);
+/// let labels = process.assign(&mut source::seeded(42), 100);
+/// assert_eq!(labels[0], 0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChineseRestaurant {
+    alpha: f64,
+}
+
+/// A Dirichlet process mixture.
+///
+/// The process draws observations whose partition follows a Chinese
+/// restaurant process with concentration `alpha` and assigns to each cluster
+/// an independent value drawn from a base distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirichletProcessMixture {
+    restaurant: ChineseRestaurant,
+}
+
+/// A stick-breaking construction of the weights of a Dirichlet process.
+///
+/// The `k`th weight is `V_k * (1 - V_1) * ... * (1 - V_{k-1})`, where the
+/// breaks `V_k` are independent beta variables with parameters `1` and
+/// `alpha`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StickBreaking {
+    alpha: f64,
+    breaks: Beta,
+}
+
+/// An iterator over stick weights.
+///
+/// The iterator is infinite, so it should be limited by the caller, for
+/// instance, with `take` or `take_while`.
+pub struct Sticks<'l, S: 'l> {
+    process: &'l StickBreaking,
+    source: &'l mut S,
+    remaining: f64,
+}
+
+impl ChineseRestaurant {
+    /// Create a process with concentration `alpha`.
+    ///
+    /// It should hold that `alpha > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(alpha: f64) -> Self {
+        unwrap!(ChineseRestaurant::try_new(alpha))
+    }
+
+    /// Create a process with concentration `alpha` or return an error if the
+    /// concentration is invalid.
+    #[inline]
+    pub fn try_new(alpha: f64) -> Result<Self, ParameterError> {
+        ensure!(alpha > 0.0 && alpha.is_finite(),
+                ParameterError::AlphaNotPositive { value: alpha });
+        Ok(ChineseRestaurant { alpha: alpha })
+    }
+
+    /// Return the concentration.
+    #[inline(always)]
+    pub fn alpha(&self) -> f64 { self.alpha }
+
+    /// Seat `n` customers and return their table labels.
+    ///
+    /// The tables are labeled in the order in which they are opened, so the
+    /// first customer is at table zero, and every label is at most the
+    /// number of distinct labels before it.
+    pub fn assign<S>(&self, source: &mut S, n: usize) -> Vec<usize> where S: Source {
+        let mut counts: Vec<usize> = vec![];
+        let mut labels = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut u = source.read::<f64>() * (i as f64 + self.alpha);
+            let mut label = counts.len();
+            for (k, &count) in counts.iter().enumerate() {
+                if u < count as f64 {
+                    label = k;
+                    break;
+                }
+                u -= count as f64;
+            }
+            if label == counts.len() {
+                counts.push(0);
+            }
+            counts[label] += 1;
+            labels.push(label);
+        }
+        labels
+    }
+
+    /// Compute the expected number of tables occupied by `n` customers,
+    /// which is `alpha / alpha + alpha / (alpha + 1) + ... + alpha /
+    /// (alpha + n - 1)` and grows like `alpha * ln(n)`.
+    pub fn expected_tables(&self, n: usize) -> f64 {
+        (0..n).map(|i| self.alpha / (self.alpha + i as f64)).sum()
+    }
+}
+
+impl DirichletProcessMixture {
+    /// Create a process with concentration `alpha`.
+    ///
+    /// It should hold that `alpha > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(alpha: f64) -> Self {
+        unwrap!(DirichletProcessMixture::try_new(alpha))
+    }
+
+    /// Create a process with concentration `alpha` or return an error if the
+    /// concentration is invalid.
+    #[inline]
+    pub fn try_new(alpha: f64) -> Result<Self, ParameterError> {
+        Ok(DirichletProcessMixture { restaurant: ChineseRestaurant::try_new(alpha)? })
+    }
+
+    /// Return the concentration.
+    #[inline(always)]
+    pub fn alpha(&self) -> f64 { self.restaurant.alpha }
+
+    /// Draw `n` observations from the prior.
+    ///
+    /// The observations are partitioned by a Chinese restaurant process, and
+    /// the observations in a cluster share a value drawn from `base`.
+    /// Marginally, each observation follows `base`, and jointly, the
+    /// observations are a sample from a random distribution drawn from a
+    /// Dirichlet process. Mapping the values through a kernel yields clustered
+    /// data, for instance, a Gaussian mixture when the values are means.
+    pub fn sample_prior<S, D>(&self, source: &mut S, n: usize, base: &D) -> Vec<D::Value>
+        where S: Source, D: Sample, D::Value: Clone
+    {
+        let labels = self.restaurant.assign(source, n);
+        let mut values: Vec<D::Value> = vec![];
+        labels.into_iter().map(|label| {
+            if label == values.len() {
+                values.push(base.sample(source));
+            }
+            values[label].clone()
+        }).collect()
+    }
+}
+
+impl StickBreaking {
+    /// Create a construction with concentration `alpha`.
+    ///
+    /// It should hold that `alpha > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(alpha: f64) -> Self {
+        unwrap!(StickBreaking::try_new(alpha))
+    }
+
+    /// Create a construction with concentration `alpha` or return an error if
+    /// the concentration is invalid.
+    #[inline]
+    pub fn try_new(alpha: f64) -> Result<Self, ParameterError> {
+        ensure!(alpha > 0.0 && alpha.is_finite(),
+                ParameterError::AlphaNotPositive { value: alpha });
+        Ok(StickBreaking { alpha: alpha, breaks: Beta::new(1.0, alpha, 0.0, 1.0) })
+    }
+
+    /// Return the concentration.
+    #[inline(always)]
+    pub fn alpha(&self) -> f64 { self.alpha }
+
+    /// Draw weights until the remaining mass falls below `epsilon` and
+    /// renormalize them to sum to one.
+    ///
+    /// It should hold that `0 < epsilon < 1`; the function panics otherwise.
+    pub fn truncate<S>(&self, source: &mut S, epsilon: f64) -> Vec<f64> where S: Source {
+        assert!(0.0 < epsilon && epsilon < 1.0,
+                "the tolerance should be in (0, 1) (got {})", epsilon);
+        let mut sticks = self.weights(source);
+        let mut weights = vec![];
+        while sticks.remaining >= epsilon {
+            match sticks.next() {
+                Some(weight) => weights.push(weight),
+                None => unreachable!(),
+            }
+        }
+        let total = weights.iter().sum::<f64>();
+        for weight in &mut weights {
+            *weight /= total;
+        }
+        weights
+    }
+
+    /// Return an iterator over the weights.
+    #[inline]
+    pub fn weights<'l, S>(&'l self, source: &'l mut S) -> Sticks<'l, S> where S: Source {
+        Sticks { process: self, source: source, remaining: 1.0 }
+    }
+}
+
+impl<'l, S> Iterator for Sticks<'l, S> where S: Source {
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        let fraction = self.process.breaks.sample(self.source);
+        let weight = self.remaining * fraction;
+        self.remaining -= weight;
+        Some(weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use processes::{ChineseRestaurant, DirichletProcessMixture, StickBreaking};
+    use statistics;
+    use tests::{chi_squared_gof, ks_one_sample};
+
+    #[test]
+    fn assign() {
+        let process = ChineseRestaurant::new(2.0);
+        let mut source = source::seeded(42);
+        for _ in 0..100 {
+            let labels = process.assign(&mut source, 50);
+            let mut tables = 0;
+            for &label in &labels {
+                assert!(label <= tables);
+                tables = tables.max(label + 1);
+            }
+        }
+        assert!(process.assign(&mut source, 0).is_empty());
+    }
+
+    #[test]
+    fn exchangeability() {
+        let alpha = 0.8;
+        let process = ChineseRestaurant::new(alpha);
+        let mut partitions: Vec<Vec<usize>> = vec![vec![0]];
+        for _ in 1..4 {
+            partitions = partitions.into_iter().flat_map(|partition| {
+                let tables = partition.iter().max().unwrap() + 1;
+                (0..tables + 1).map(move |label| {
+                    let mut partition = partition.clone();
+                    partition.push(label);
+                    partition
+                })
+            }).collect();
+        }
+        assert_eq!(partitions.len(), 15);
+        let probabilities = partitions.iter().map(|partition| {
+            let tables = partition.iter().max().unwrap() + 1;
+            (0..tables).map(|k| {
+                let size = partition.iter().filter(|&&label| label == k).count();
+                alpha * (1..size).product::<usize>() as f64
+            }).product::<f64>() / (alpha * (alpha + 1.0) * (alpha + 2.0) * (alpha + 3.0))
+        }).collect::<Vec<_>>();
+        assert::close(probabilities.iter().sum::<f64>(), 1.0, 1e-12);
+        let mut source = source::seeded(42);
+        let mut counts = vec![0u64; partitions.len()];
+        for _ in 0..100000 {
+            let labels = process.assign(&mut source, 4);
+            counts[partitions.iter().position(|partition| partition == &labels).unwrap()] += 1;
+        }
+        assert!(chi_squared_gof(&counts, &probabilities).p_value > 0.01);
+    }
+
+    #[test]
+    fn expected_tables() {
+        let mut source = source::seeded(42);
+        for &alpha in &[0.5, 2.0, 10.0] {
+            let process = ChineseRestaurant::new(alpha);
+            let n = 1000;
+            let tables = (0..200).map(|_| {
+                process.assign(&mut source, n).into_iter().max().unwrap() as f64 + 1.0
+            }).collect::<Vec<_>>();
+            let expected = process.expected_tables(n);
+            assert::close(statistics::mean(&tables), expected, 0.05 * expected);
+            let growth = expected - process.expected_tables(n / 10);
+            assert::close(growth, alpha * 10f64.ln(), 0.05 * growth);
+        }
+    }
+
+    #[test]
+    fn sample_prior() {
+        let process = DirichletProcessMixture::new(3.0);
+        let base = Gaussian::new(1.0, 2.0);
+        let mut source = source::seeded(42);
+        let values = process.sample_prior(&mut source, 500, &base);
+        let mut distinct = values.clone();
+        distinct.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        distinct.dedup();
+        assert::close(distinct.len() as f64, process.alpha() * (500.0f64 / 3.0).ln(), 10.0);
+        let last = (0..2000).map(|_| *process.sample_prior(&mut source, 10, &base).last().unwrap())
+                            .collect::<Vec<_>>();
+        assert!(ks_one_sample(&last, &base).p_value > 0.05);
+    }
+
+    #[test]
+    fn truncate() {
+        let process = StickBreaking::new(5.0);
+        let mut source = source::seeded(42);
+        for &epsilon in &[1e-2, 1e-6] {
+            let weights = process.truncate(&mut source, epsilon);
+            assert!(weights.iter().all(|&weight| weight >= 0.0));
+            assert::close(weights.iter().sum::<f64>(), 1.0, 1e-12);
+        }
+        let weights = process.weights(&mut source).take(1000).collect::<Vec<_>>();
+        assert!(weights.iter().sum::<f64>() <= 1.0 + 1e-12);
+        let first = (0..5000).map(|_| process.weights(&mut source).next().unwrap())
+                             .collect::<Vec<_>>();
+        assert::close(statistics::mean(&first), 1.0 / 6.0, 0.01);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(ChineseRestaurant::try_new(0.0),
+                   Err(ParameterError::AlphaNotPositive { value: 0.0 }));
+        assert_eq!(StickBreaking::try_new(-1.0),
+                   Err(ParameterError::AlphaNotPositive { value: -1.0 }));
+        assert_eq!(DirichletProcessMixture::try_new(::std::f64::INFINITY),
+                   Err(ParameterError::AlphaNotPositive { value: ::std::f64::INFINITY }));
+        assert_eq!(StickBreaking::new(2.0).alpha(), 2.0);
+    }
+}
@@ -0,0 +1,445 @@
+use math;
+use source::Source;
+use {FitError, ParameterError};
+
+/// A discrete-time Markov chain on a finite set of states.
+///
+/// Each row of the transition matrix is turned into an alias table, so that a
+/// step takes constant time regardless of the number of states.
+///
+/// ```
+/// use probability::prelude::*;
+/// use probability::processes::MarkovChain;
+///
+/// let chain = MarkovChain::new(vec![vec![0.9, 0.1], vec![0.5, 0.5]]);
+/// let path = chain.simulate(&mut source::seeded(42), 0, 1000);
+/// assert_eq!(path.len(), 1000);
+/// let stationary = chain.stationary_distribution().unwrap();
+/// assert!((stationary[0] - 5.0 / 6.0).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkovChain {
+    matrix: Vec<Vec<f64>>,
+    tables: Vec<Alias>,
+}
+
+// An alias table for sampling from a categorical distribution in constant
+// time using Vose’s method.
+#[derive(Clone, Debug, PartialEq)]
+struct Alias {
+    probabilities: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl MarkovChain {
+    /// Create a chain with the transition matrix `matrix`, whose element in
+    /// row `i` and column `j` is the probability of moving from state `i` to
+    /// state `j`.
+    ///
+    /// It should hold that the matrix is square and not empty and that each
+    /// row is a probability vector; the function panics otherwise.
+    #[inline]
+    pub fn new(matrix: Vec<Vec<f64>>) -> Self {
+        unwrap!(MarkovChain::try_new(matrix))
+    }
+
+    /// Create a chain with the transition matrix `matrix` or return an error
+    /// if the matrix is invalid.
+    pub fn try_new(matrix: Vec<Vec<f64>>) -> Result<Self, ParameterError> {
+        const EPSILON: f64 = 1e-12;
+
+        let n = matrix.len();
+        ensure!(n > 0, ParameterError::ProbabilitiesEmpty);
+        for row in &matrix {
+            ensure!(row.len() == n,
+                    ParameterError::MatrixNotSquare { rows: n, columns: row.len() });
+            for &p in row {
+                ensure!(p >= 0.0 && p <= 1.0, ParameterError::ProbabilityOutOfRange { value: p });
+            }
+            let sum = row.iter().sum::<f64>();
+            ensure!((sum - 1.0).abs() < EPSILON,
+                    ParameterError::ProbabilitiesNotNormalized { sum: sum });
+        }
+        let tables = matrix.iter().map(|row| Alias::new(row)).collect();
+        Ok(MarkovChain { matrix: matrix, tables: tables })
+    }
+
+    /// Estimate the transition matrix from the sequence of states `sequence`
+    /// over `states` states.
+    ///
+    /// The estimate of a transition probability is the number of the observed
+    /// transitions increased by `smoothing` and normalized within its row;
+    /// zero smoothing gives the maximum-likelihood estimate, and one gives
+    /// Laplace’s rule of succession. An error is returned if a state falls
+    /// outside the range or if there is nothing to estimate a row from. It
+    /// should hold that `smoothing >= 0`.
+    pub fn fit(sequence: &[usize], states: usize, smoothing: f64) -> Result<Self, FitError> {
+        should!(smoothing >= 0.0 && smoothing.is_finite());
+        ensure!(!sequence.is_empty(), FitError::DataEmpty);
+        for &state in sequence {
+            ensure!(state < states, FitError::DatumOutOfSupport { value: state as f64 });
+        }
+        let mut counts = vec![vec![0.0; states]; states];
+        for pair in sequence.windows(2) {
+            counts[pair[0]][pair[1]] += 1.0;
+        }
+        let mut matrix = Vec::with_capacity(states);
+        for (state, row) in counts.into_iter().enumerate() {
+            let total = row.iter().sum::<f64>() + smoothing * states as f64;
+            ensure!(total > 0.0, FitError::TransitionsZero { state: state });
+            matrix.push(row.into_iter().map(|count| (count + smoothing) / total).collect());
+        }
+        Ok(MarkovChain::try_new(matrix)?)
+    }
+
+    /// Compute the probabilities of absorption.
+    ///
+    /// A state is absorbing if the chain never leaves it. The element in row
+    /// `i` and column `j` of the result is the probability of being absorbed
+    /// in state `j` when starting from state `i`, which is zero if `j` is not
+    /// absorbing. The probabilities for the transient states are found by
+    /// solving a linear system. `None` is returned if there are no absorbing
+    /// states or if some state cannot reach any of them, in which case
+    /// absorption is not certain.
+    pub fn absorption_probabilities(&self) -> Option<Vec<Vec<f64>>> {
+        let n = self.states();
+        let absorbing = (0..n).map(|i| self.matrix[i][i] == 1.0).collect::<Vec<_>>();
+        let transient = (0..n).filter(|&i| !absorbing[i]).collect::<Vec<_>>();
+        if transient.len() == n {
+            return None;
+        }
+        let mut reaching = absorbing.clone();
+        loop {
+            let mut changed = false;
+            for &i in &transient {
+                if !reaching[i] && (0..n).any(|j| self.matrix[i][j] > 0.0 && reaching[j]) {
+                    reaching[i] = true;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        if reaching.iter().any(|&reaching| !reaching) {
+            return None;
+        }
+        let m = transient.len();
+        let mut system = vec![0.0; m * m];
+        for (k, &i) in transient.iter().enumerate() {
+            for (l, &j) in transient.iter().enumerate() {
+                system[k * m + l] = (if k == l { 1.0 } else { 0.0 }) - self.matrix[i][j];
+            }
+        }
+        let inverse = math::invert(&system)?;
+        let mut result = vec![vec![0.0; n]; n];
+        for i in (0..n).filter(|&i| absorbing[i]) {
+            result[i][i] = 1.0;
+        }
+        for (k, &i) in transient.iter().enumerate() {
+            for j in (0..n).filter(|&j| absorbing[j]) {
+                result[i][j] = transient.iter().enumerate().map(|(l, &t)| {
+                    inverse[k * m + l] * self.matrix[t][j]
+                }).sum();
+            }
+        }
+        Some(result)
+    }
+
+    /// Compute the transition matrix over `n` steps, that is, the `n`th power
+    /// of the transition matrix, using repeated squaring.
+    pub fn n_step_matrix(&self, mut n: usize) -> Vec<Vec<f64>> {
+        let size = self.states();
+        let mut result = (0..size).map(|i| {
+            (0..size).map(|j| if i == j { 1.0 } else { 0.0 }).collect()
+        }).collect::<Vec<Vec<_>>>();
+        let mut power = self.matrix.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = multiply(&result, &power);
+            }
+            n >>= 1;
+            if n > 0 {
+                power = multiply(&power, &power);
+            }
+        }
+        result
+    }
+
+    /// Draw a path of `n` states starting with `initial`.
+    ///
+    /// It should hold that `initial` is a valid state; the function panics
+    /// otherwise.
+    pub fn simulate<S>(&self, source: &mut S, initial: usize, n: usize) -> Vec<usize>
+        where S: Source
+    {
+        assert!(initial < self.states(),
+                "the state should be less than the number of states (got {} with {} states)",
+                initial, self.states());
+        let mut path = Vec::with_capacity(n);
+        let mut state = initial;
+        for i in 0..n {
+            if i > 0 {
+                state = self.step(state, source);
+            }
+            path.push(state);
+        }
+        path
+    }
+
+    /// Compute the stationary distribution.
+    ///
+    /// The distribution is found by power iteration starting from the first
+    /// state until the largest change of a probability falls below `1e-12`.
+    /// An error is returned if the chain is reducible, in which case the
+    /// distribution need not be unique, or if the iteration does not
+    /// converge, which happens when the chain is periodic.
+    pub fn stationary_distribution(&self) -> Result<Vec<f64>, ParameterError> {
+        const ITERATIONS: usize = 100000;
+        const TOLERANCE: f64 = 1e-12;
+
+        ensure!(self.is_irreducible(), ParameterError::ChainNotErgodic);
+        let n = self.states();
+        let mut current = vec![0.0; n];
+        current[0] = 1.0;
+        for _ in 0..ITERATIONS {
+            let mut next = vec![0.0; n];
+            for (i, &p) in current.iter().enumerate() {
+                for (j, &q) in self.matrix[i].iter().enumerate() {
+                    next[j] += p * q;
+                }
+            }
+            let sum = next.iter().sum::<f64>();
+            for p in &mut next {
+                *p /= sum;
+            }
+            let change = current.iter().zip(&next).fold(0.0, |max: f64, (&p, &q)| {
+                max.max((p - q).abs())
+            });
+            current = next;
+            if change < TOLERANCE {
+                return Ok(current);
+            }
+        }
+        Err(ParameterError::ChainNotErgodic)
+    }
+
+    /// Return the number of states.
+    #[inline(always)]
+    pub fn states(&self) -> usize { self.matrix.len() }
+
+    /// Draw the state following `state`.
+    ///
+    /// It should hold that `state` is a valid state; the function panics
+    /// otherwise.
+    #[inline]
+    pub fn step<S>(&self, state: usize, source: &mut S) -> usize where S: Source {
+        assert!(state < self.states(),
+                "the state should be less than the number of states (got {} with {} states)",
+                state, self.states());
+        self.tables[state].sample(source)
+    }
+
+    /// Return the transition matrix.
+    #[inline(always)]
+    pub fn transition_matrix(&self) -> &[Vec<f64>] { &self.matrix }
+
+    fn is_irreducible(&self) -> bool {
+        let n = self.states();
+        let reach = |forward: bool| {
+            let mut visited = vec![false; n];
+            let mut stack = vec![0];
+            visited[0] = true;
+            while let Some(i) = stack.pop() {
+                for (j, visited) in visited.iter_mut().enumerate() {
+                    let p = if forward { self.matrix[i][j] } else { self.matrix[j][i] };
+                    if p > 0.0 && !*visited {
+                        *visited = true;
+                        stack.push(j);
+                    }
+                }
+            }
+            visited.into_iter().all(|visited| visited)
+        };
+        reach(true) && reach(false)
+    }
+}
+
+impl Alias {
+    fn new(p: &[f64]) -> Self {
+        let n = p.len();
+        let mut probabilities = p.iter().map(|&p| p * n as f64).collect::<Vec<_>>();
+        let mut aliases = (0..n).collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..n).partition(|&i| {
+            probabilities[i] < 1.0
+        });
+        while !small.is_empty() && !large.is_empty() {
+            let (i, j) = (small.pop().unwrap(), large.pop().unwrap());
+            aliases[i] = j;
+            probabilities[j] -= 1.0 - probabilities[i];
+            if probabilities[j] < 1.0 {
+                small.push(j);
+            } else {
+                large.push(j);
+            }
+        }
+        for i in small.into_iter().chain(large) {
+            probabilities[i] = 1.0;
+        }
+        Alias { probabilities: probabilities, aliases: aliases }
+    }
+
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> usize where S: Source {
+        let n = self.probabilities.len();
+        let i = ((source.read::<f64>() * n as f64) as usize).min(n - 1);
+        if source.read::<f64>() < self.probabilities[i] { i } else { self.aliases[i] }
+    }
+}
+
+fn multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = a.len();
+    (0..n).map(|i| {
+        (0..n).map(|j| (0..n).map(|k| a[i][k] * b[k][j]).sum()).collect()
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use processes::MarkovChain;
+    use tests::chi_squared_gof;
+
+    fn chain() -> MarkovChain {
+        MarkovChain::new(vec![
+            vec![0.5, 0.3, 0.2],
+            vec![0.2, 0.6, 0.2],
+            vec![0.1, 0.4, 0.5],
+        ])
+    }
+
+    #[test]
+    fn absorption_probabilities() {
+        let (p, n) = (0.4, 10);
+        let mut matrix = vec![vec![0.0; n + 1]; n + 1];
+        matrix[0][0] = 1.0;
+        matrix[n][n] = 1.0;
+        for i in 1..n {
+            matrix[i][i + 1] = p;
+            matrix[i][i - 1] = 1.0 - p;
+        }
+        let chain = MarkovChain::new(matrix);
+        let probabilities = chain.absorption_probabilities().unwrap();
+        let ratio: f64 = (1.0 - p) / p;
+        for (i, row) in probabilities.iter().enumerate() {
+            let win = (1.0 - ratio.powi(i as i32)) / (1.0 - ratio.powi(n as i32));
+            assert::close(row[n], win, 1e-12);
+            assert::close(row[0], 1.0 - win, 1e-12);
+            assert!(row[1..n].iter().all(|&p| p == 0.0));
+        }
+
+        assert_eq!(self::chain().absorption_probabilities(), None);
+        let chain = MarkovChain::new(vec![
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![0.5, 0.0, 0.5, 0.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+            vec![0.0, 0.0, 1.0, 0.0],
+        ]);
+        assert_eq!(chain.absorption_probabilities(), None);
+    }
+
+    #[test]
+    fn fit() {
+        let chain = chain();
+        let path = chain.simulate(&mut source::seeded(42), 0, 100000);
+        let fitted = MarkovChain::fit(&path, 3, 0.0).unwrap();
+        for (row, expected) in fitted.transition_matrix().iter().zip(chain.transition_matrix()) {
+            assert::close(row, expected, 0.01);
+        }
+
+        let fitted = MarkovChain::fit(&[0, 1, 1, 0, 1], 3, 1.0).unwrap();
+        assert_eq!(fitted.transition_matrix(), &[
+            vec![1.0 / 5.0, 3.0 / 5.0, 1.0 / 5.0],
+            vec![2.0 / 5.0, 2.0 / 5.0, 1.0 / 5.0],
+            vec![1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+        ][..]);
+        assert_eq!(MarkovChain::fit(&[0, 1, 1, 0, 1], 3, 0.0),
+                   Err(FitError::TransitionsZero { state: 2 }));
+        assert_eq!(MarkovChain::fit(&[0, 3], 3, 1.0),
+                   Err(FitError::DatumOutOfSupport { value: 3.0 }));
+        assert_eq!(MarkovChain::fit(&[], 3, 1.0), Err(FitError::DataEmpty));
+    }
+
+    #[test]
+    fn n_step_matrix() {
+        let chain = chain();
+        assert_eq!(chain.n_step_matrix(0), vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(chain.n_step_matrix(1), chain.transition_matrix());
+        let matrix = chain.transition_matrix();
+        let three = chain.n_step_matrix(3);
+        for i in 0..3 {
+            for j in 0..3 {
+                let mut expected = 0.0;
+                for k in 0..3 {
+                    for l in 0..3 {
+                        expected += matrix[i][k] * matrix[k][l] * matrix[l][j];
+                    }
+                }
+                assert::close(three[i][j], expected, 1e-12);
+            }
+        }
+        let expected = vec![12.0 / 49.0, 23.0 / 49.0, 2.0 / 7.0];
+        for row in chain.n_step_matrix(100) {
+            assert::close(&row, &expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn stationary_distribution() {
+        let chain = chain();
+        let expected = vec![12.0 / 49.0, 23.0 / 49.0, 2.0 / 7.0];
+        let stationary = chain.stationary_distribution().unwrap();
+        assert::close(&stationary, &expected, 1e-10);
+        let path = chain.simulate(&mut source::seeded(42), 2, 200000);
+        let mut frequencies = vec![0.0; 3];
+        for state in path {
+            frequencies[state] += 1.0 / 200000.0;
+        }
+        assert::close(&frequencies, &expected, 0.01);
+
+        let periodic = MarkovChain::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        assert_eq!(periodic.stationary_distribution(), Err(ParameterError::ChainNotErgodic));
+        let reducible = MarkovChain::new(vec![vec![1.0, 0.0], vec![0.5, 0.5]]);
+        assert_eq!(reducible.stationary_distribution(), Err(ParameterError::ChainNotErgodic));
+    }
+
+    #[test]
+    fn step() {
+        let p = [0.05, 0.0, 0.3, 0.15, 0.25, 0.1, 0.15];
+        let chain = MarkovChain::new(vec![p.to_vec(); 7]);
+        let mut source = source::seeded(42);
+        let mut observed = vec![0; 7];
+        for i in 0..70000 {
+            observed[chain.step(i % 7, &mut source)] += 1;
+        }
+        assert_eq!(observed[1], 0);
+        observed.remove(1);
+        let mut q = p.to_vec();
+        q.remove(1);
+        assert!(chi_squared_gof(&observed, &q).p_value > 0.01);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(MarkovChain::try_new(vec![vec![0.5, 0.5], vec![1.0]]),
+                   Err(ParameterError::MatrixNotSquare { rows: 2, columns: 1 }));
+        assert_eq!(MarkovChain::try_new(vec![vec![0.5, 0.4], vec![1.0, 0.0]]),
+                   Err(ParameterError::ProbabilitiesNotNormalized { sum: 0.9 }));
+        assert_eq!(MarkovChain::try_new(vec![]), Err(ParameterError::ProbabilitiesEmpty));
+    }
+}
@@ -0,0 +1,38 @@
+//! Stochastic processes.
+//!
+//! ```
+//! use probability::prelude::*;
+//! use probability::processes::PoissonProcess;
+//!
+//! let process = PoissonProcess::new(2.0);
+//! let mut source = source::seeded(42);
+//! let arrivals = process.sample_arrivals(&mut source, 10.0);
+//! assert!(arrivals.iter().all(|&time| 0.0 < time && time <= 10.0));
+//! ```
+
+mod arma;
+mod brownian;
+mod dirichlet;
+mod markov;
+mod ornstein;
+mod poisson;
+
+pub use self::arma::{Ar, Arma, Ma};
+pub use self::brownian::{BrownianMotion, GeometricBrownianMotion};
+pub use self::dirichlet::{ChineseRestaurant, DirichletProcessMixture, StickBreaking, Sticks};
+pub use self::markov::MarkovChain;
+pub use self::ornstein::OrnsteinUhlenbeck;
+pub use self::poisson::{Arrivals, InhomogeneousPoissonProcess, PoissonProcess};
+
+// Check that the times of a grid are nonnegative, finite, and strictly
+// increasing.
+fn check(grid: &[f64]) {
+    for &time in grid {
+        assert!(time >= 0.0 && time.is_finite(),
+                "the times should be nonnegative and finite (got {})", time);
+    }
+    for pair in grid.windows(2) {
+        assert!(pair[0] < pair[1],
+                "the times should be strictly increasing (got {} after {})", pair[1], pair[0]);
+    }
+}
@@ -0,0 +1,210 @@
+use distribution::{Continuous, Gaussian, Sample};
+use processes::check;
+use source::Source;
+use ParameterError;
+
+/// An Ornstein–Uhlenbeck process.
+///
+/// The process solves `dX = theta * (mu - X) dt + sigma dW`, where `W` is a
+/// standard Brownian motion, so it reverts to the mean `mu` at the rate
+/// `theta`. Its transitions are Gaussian and are sampled exactly, without
+/// discretization error.
+///
+/// ```
+/// use probability::prelude::*;
+/// use probability::processes::OrnsteinUhlenbeck;
+///
+/// let process = OrnsteinUhlenbeck::new(2.0, 1.0, 0.5);
+/// let grid = (1..101).map(|i| i as f64 / 10.0).collect::<Vec<_>>();
+/// let path = process.sample_path(&mut source::seeded(42), &grid, 5.0);
+/// assert!((path[99] - 1.0).abs() < 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrnsteinUhlenbeck {
+    theta: f64,
+    mu: f64,
+    sigma: f64,
+}
+
+impl OrnsteinUhlenbeck {
+    /// Create a process with rate `theta`, mean `mu`, and volatility `sigma`.
+    ///
+    /// It should hold that `theta > 0`, `mu` is finite, and `sigma > 0`; the
+    /// function panics otherwise.
+    #[inline]
+    pub fn new(theta: f64, mu: f64, sigma: f64) -> Self {
+        unwrap!(OrnsteinUhlenbeck::try_new(theta, mu, sigma))
+    }
+
+    /// Create a process with rate `theta`, mean `mu`, and volatility `sigma`
+    /// or return an error if the parameters are invalid.
+    #[inline]
+    pub fn try_new(theta: f64, mu: f64, sigma: f64) -> Result<Self, ParameterError> {
+        ensure!(theta > 0.0 && theta.is_finite(),
+                ParameterError::ThetaNotPositive { value: theta });
+        ensure!(mu.is_finite(), ParameterError::MuNotFinite { value: mu });
+        ensure!(sigma > 0.0 && sigma.is_finite(),
+                ParameterError::SigmaNotPositive { value: sigma });
+        Ok(OrnsteinUhlenbeck { theta: theta, mu: mu, sigma: sigma })
+    }
+
+    /// Compute the log-likelihood of the values `path` observed at the times
+    /// of `grid` conditioned on the first value.
+    ///
+    /// The log-likelihood is the sum of the log-densities of the transitions
+    /// between consecutive values. It should hold that `path` and `grid` have
+    /// the same length and that the times are nonnegative, finite, and
+    /// strictly increasing; the function panics otherwise.
+    pub fn log_likelihood(&self, path: &[f64], grid: &[f64]) -> f64 {
+        assert!(path.len() == grid.len(),
+                "the path and the grid should have the same length (got {} and {})",
+                path.len(), grid.len());
+        check(grid);
+        path.windows(2).zip(grid.windows(2)).map(|(x, time)| {
+            self.transition(x[0], time[1] - time[0]).ln_density(x[1])
+        }).sum()
+    }
+
+    /// Return the mean.
+    #[inline(always)]
+    pub fn mu(&self) -> f64 { self.mu }
+
+    /// Draw the values of a path starting at `x0` at time zero at the times of
+    /// `grid`.
+    ///
+    /// It should hold that the times are nonnegative, finite, and strictly
+    /// increasing; the function panics otherwise.
+    pub fn sample_path<S>(&self, source: &mut S, grid: &[f64], x0: f64) -> Vec<f64>
+        where S: Source
+    {
+        check(grid);
+        let (mut time, mut x) = (0.0, x0);
+        grid.iter().map(|&next| {
+            if next > time {
+                x = self.transition(x, next - time).sample(source);
+            }
+            time = next;
+            x
+        }).collect()
+    }
+
+    /// Return the volatility.
+    #[inline(always)]
+    pub fn sigma(&self) -> f64 { self.sigma }
+
+    /// Return the stationary distribution, which is Gaussian with mean `mu`
+    /// and variance `sigma² / (2 * theta)`.
+    #[inline]
+    pub fn stationary(&self) -> Gaussian {
+        Gaussian::new(self.mu, self.sigma / (2.0 * self.theta).sqrt())
+    }
+
+    /// Return the rate of mean reversion.
+    #[inline(always)]
+    pub fn theta(&self) -> f64 { self.theta }
+
+    /// Return the distribution of the value after time `dt` given the value
+    /// `x`.
+    ///
+    /// The distribution is Gaussian with mean `mu + (x - mu) * exp(-theta * dt)`
+    /// and variance `sigma² * (1 - exp(-2 * theta * dt)) / (2 * theta)`. It
+    /// should hold that `x` is finite and `dt > 0`; the function panics
+    /// otherwise.
+    pub fn transition(&self, x: f64, dt: f64) -> Gaussian {
+        assert!(dt > 0.0 && dt.is_finite(), "the time step should be positive and finite (got {})",
+                dt);
+        let decay = (-self.theta * dt).exp();
+        let variance = -self.sigma * self.sigma * (-2.0 * self.theta * dt).exp_m1() /
+                       (2.0 * self.theta);
+        Gaussian::new(self.mu + (x - self.mu) * decay, variance.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use math;
+    use prelude::*;
+    use processes::OrnsteinUhlenbeck;
+    use statistics;
+
+    #[test]
+    fn autocorrelation() {
+        let process = OrnsteinUhlenbeck::new(0.5, 2.0, 1.0);
+        let mut source = source::seeded(42);
+        let x0 = process.stationary().sample(&mut source);
+        let grid = (1..100001).map(|i| i as f64 / 10.0).collect::<Vec<_>>();
+        let path = process.sample_path(&mut source, &grid, x0);
+        let rho = statistics::acf_fft(&path, 30);
+        for &lag in &[5, 10, 20, 30] {
+            assert::close(rho[lag], (-0.5 * lag as f64 / 10.0).exp(), 0.03);
+        }
+    }
+
+    #[test]
+    fn log_likelihood() {
+        let process = OrnsteinUhlenbeck::new(1.5, -1.0, 0.5);
+        let (path, grid) = ([0.0, -0.5, -0.8], [0.0, 0.5, 1.5]);
+        let expected = process.transition(0.0, 0.5).ln_density(-0.5) +
+                       process.transition(-0.5, 1.0).ln_density(-0.8);
+        assert_eq!(process.log_likelihood(&path, &grid), expected);
+        assert_eq!(process.log_likelihood(&path[..1], &grid[..1]), 0.0);
+    }
+
+    #[test]
+    fn maximum_likelihood() {
+        let (theta, mu, sigma) = (2.0, 1.0, 0.5);
+        let process = OrnsteinUhlenbeck::new(theta, mu, sigma);
+        let mut source = source::seeded(42);
+        let mut grid = vec![];
+        let mut time = 0.0;
+        for _ in 0..5000 {
+            time += Uniform::new(0.05, 0.15).sample(&mut source);
+            grid.push(time);
+        }
+        let path = process.sample_path(&mut source, &grid, 3.0);
+        let ln_likelihood = |parameters: &[f64]| {
+            OrnsteinUhlenbeck::new(parameters[0], parameters[1], parameters[2])
+                .log_likelihood(&path, &grid)
+        };
+        let bounds = [(0.1, 10.0), (-5.0, 5.0), (0.05, 5.0)];
+        let mut estimate = vec![1.0, 0.0, 1.0];
+        for _ in 0..10 {
+            for i in 0..3 {
+                let other = estimate.clone();
+                estimate[i] = math::golden(|value| {
+                    let mut parameters = other.clone();
+                    parameters[i] = value;
+                    -ln_likelihood(&parameters)
+                }, bounds[i].0, bounds[i].1, 1e-8);
+            }
+        }
+        assert::close(estimate[0], theta, 0.3);
+        assert::close(estimate[1], mu, 0.05);
+        assert::close(estimate[2], sigma, 0.02);
+    }
+
+    #[test]
+    fn stationary() {
+        let process = OrnsteinUhlenbeck::new(3.0, -2.0, 1.5);
+        assert_eq!(process.stationary(), Gaussian::new(-2.0, 1.5 / 6f64.sqrt()));
+        let mut source = source::seeded(42);
+        let grid = (1..200001).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let path = process.sample_path(&mut source, &grid, 10.0);
+        assert::close(statistics::mean(&path[100..]), -2.0, 0.01);
+        assert::close(statistics::variance(&path[100..]), 1.5 * 1.5 / 6.0, 0.01);
+        let transition = process.transition(1.0, 100.0);
+        assert::close(transition.mean(), -2.0, 1e-12);
+        assert::close(transition.variance(), 1.5 * 1.5 / 6.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(OrnsteinUhlenbeck::try_new(0.0, 0.0, 1.0),
+                   Err(ParameterError::ThetaNotPositive { value: 0.0 }));
+        let process = OrnsteinUhlenbeck::new(1.0, 2.0, 3.0);
+        assert_eq!((process.theta(), process.mu(), process.sigma()), (1.0, 2.0, 3.0));
+        let path = process.sample_path(&mut source::seeded(42), &[0.0, 1.0], 5.0);
+        assert_eq!(path[0], 5.0);
+    }
+}
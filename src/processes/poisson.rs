@@ -0,0 +1,268 @@
+use distribution::{Exponential, Poisson, Sample};
+use source::Source;
+use ParameterError;
+
+/// A homogeneous Poisson process.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoissonProcess {
+    rate: f64,
+}
+
+/// An inhomogeneous Poisson process.
+///
+/// The arrivals are generated by Lewis–Shedler thinning: candidates are drawn
+/// from a homogeneous process with the rate `bound`, and a candidate at time
+/// `t` is kept with probability `rate(t) / bound`. The bound is trusted rather
+/// than validated up front, since the rate function can only be evaluated
+/// pointwise; instead, the rate is checked at every candidate, and a panic is
+/// raised if it exceeds the bound there. A loose bound is correct but wastes
+/// candidates.
+#[derive(Clone, Copy, Debug)]
+pub struct InhomogeneousPoissonProcess<F> {
+    rate: F,
+    bound: f64,
+}
+
+/// An iterator over the arrival times of a process.
+///
+/// The iterator is infinite, so it should be limited by the caller, for
+/// instance, with `take` or `take_while`.
+pub struct Arrivals<'l, P: 'l, S: 'l> {
+    process: &'l P,
+    source: &'l mut S,
+    time: f64,
+}
+
+impl PoissonProcess {
+    /// Create a process with rate `rate`.
+    ///
+    /// It should hold that `rate > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(rate: f64) -> Self {
+        unwrap!(PoissonProcess::try_new(rate))
+    }
+
+    /// Create a process with rate `rate` or return an error if the parameter
+    /// is invalid.
+    #[inline]
+    pub fn try_new(rate: f64) -> Result<Self, ParameterError> {
+        ensure!(rate > 0.0 && rate.is_finite(), ParameterError::RateNotPositive { value: rate });
+        Ok(PoissonProcess { rate: rate })
+    }
+
+    /// Return an iterator over the arrival times starting from zero.
+    #[inline]
+    pub fn arrivals<'l, S>(&'l self, source: &'l mut S) -> Arrivals<'l, Self, S> where S: Source {
+        Arrivals { process: self, source: source, time: 0.0 }
+    }
+
+    /// Return the rate.
+    #[inline(always)]
+    pub fn rate(&self) -> f64 { self.rate }
+
+    /// Draw the arrival times in `(0, horizon]`.
+    ///
+    /// The inter-arrival times are independent exponential variables. It
+    /// should hold that `horizon >= 0`.
+    pub fn sample_arrivals<S>(&self, source: &mut S, horizon: f64) -> Vec<f64> where S: Source {
+        should!(horizon >= 0.0);
+        self.arrivals(source).take_while(|&time| time <= horizon).collect()
+    }
+
+    /// Draw the number of arrivals in `(0, horizon]`.
+    ///
+    /// The count is drawn directly from the Poisson distribution with the
+    /// rate `rate * horizon`. It should hold that `horizon >= 0`.
+    pub fn sample_count<S>(&self, source: &mut S, horizon: f64) -> usize where S: Source {
+        should!(horizon >= 0.0);
+        if horizon == 0.0 {
+            return 0;
+        }
+        Poisson::new(self.rate * horizon).sample(source)
+    }
+}
+
+impl<F> InhomogeneousPoissonProcess<F> where F: Fn(f64) -> f64 {
+    /// Create a process with the rate function `rate` bounded from above by
+    /// `bound` on the positive half-line.
+    ///
+    /// It should hold that `bound > 0`; the function panics otherwise.
+    #[inline]
+    pub fn new(rate: F, bound: f64) -> Self {
+        unwrap!(InhomogeneousPoissonProcess::try_new(rate, bound))
+    }
+
+    /// Create a process with the rate function `rate` bounded from above by
+    /// `bound` or return an error if the bound is invalid.
+    #[inline]
+    pub fn try_new(rate: F, bound: f64) -> Result<Self, ParameterError> {
+        ensure!(bound > 0.0 && bound.is_finite(), ParameterError::RateNotPositive { value: bound });
+        Ok(InhomogeneousPoissonProcess { rate: rate, bound: bound })
+    }
+
+    /// Return an iterator over the arrival times starting from zero.
+    ///
+    /// The iterator does not terminate if the rate vanishes from some point
+    /// on.
+    #[inline]
+    pub fn arrivals<'l, S>(&'l self, source: &'l mut S) -> Arrivals<'l, Self, S> where S: Source {
+        Arrivals { process: self, source: source, time: 0.0 }
+    }
+
+    /// Return the upper bound of the rate.
+    #[inline(always)]
+    pub fn bound(&self) -> f64 { self.bound }
+
+    /// Draw the arrival times in `(0, horizon]`.
+    ///
+    /// It should hold that `horizon >= 0`. The function panics if the rate
+    /// exceeds the bound at a candidate time.
+    pub fn sample_arrivals<S>(&self, source: &mut S, horizon: f64) -> Vec<f64> where S: Source {
+        should!(horizon >= 0.0);
+        let mut arrivals = vec![];
+        let mut time = 0.0;
+        while let Some(next) = self.next(source, time, horizon) {
+            arrivals.push(next);
+            time = next;
+        }
+        arrivals
+    }
+
+    /// Draw the number of arrivals in `(0, horizon]`.
+    ///
+    /// It should hold that `horizon >= 0`. The function panics if the rate
+    /// exceeds the bound at a candidate time.
+    #[inline]
+    pub fn sample_count<S>(&self, source: &mut S, horizon: f64) -> usize where S: Source {
+        self.sample_arrivals(source, horizon).len()
+    }
+
+    // Draw the first arrival after `time` or return `None` if the candidates
+    // pass `horizon` first.
+    fn next<S>(&self, source: &mut S, mut time: f64, horizon: f64) -> Option<f64>
+        where S: Source
+    {
+        loop {
+            time += Exponential::new(self.bound).sample(source);
+            if time > horizon {
+                return None;
+            }
+            let rate = (self.rate)(time);
+            assert!(rate <= self.bound,
+                    "the rate should not exceed the bound (got {} at {})", rate, time);
+            if source.read::<f64>() * self.bound < rate {
+                return Some(time);
+            }
+        }
+    }
+}
+
+impl<'l, S> Iterator for Arrivals<'l, PoissonProcess, S> where S: Source {
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.time += Exponential::new(self.process.rate).sample(self.source);
+        Some(self.time)
+    }
+}
+
+impl<'l, F, S> Iterator for Arrivals<'l, InhomogeneousPoissonProcess<F>, S>
+    where F: Fn(f64) -> f64, S: Source
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        let time = self.process.next(self.source, self.time, ::std::f64::INFINITY);
+        if let Some(time) = time {
+            self.time = time;
+        }
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use processes::{InhomogeneousPoissonProcess, PoissonProcess};
+    use tests::{chi_squared_gof, chi_squared_gof_dist, ks_one_sample};
+
+    #[test]
+    #[should_panic(expected = "the rate should not exceed the bound")]
+    fn bound() {
+        let process = InhomogeneousPoissonProcess::new(|time: f64| time, 1.0);
+        process.sample_arrivals(&mut source::seeded(42), 100.0);
+    }
+
+    #[test]
+    fn count() {
+        let process = PoissonProcess::new(3.0);
+        let mut source = source::seeded(42);
+        let (mut direct, mut counted) = (vec![0; 20], vec![0; 20]);
+        for _ in 0..10000 {
+            direct[process.sample_count(&mut source, 2.0).min(19)] += 1;
+            counted[process.sample_arrivals(&mut source, 2.0).len().min(19)] += 1;
+        }
+        let distribution = Poisson::new(6.0);
+        assert!(chi_squared_gof_dist(&direct, &distribution, 0, 5.0).p_value > 0.01);
+        assert!(chi_squared_gof_dist(&counted, &distribution, 0, 5.0).p_value > 0.01);
+        assert_eq!(process.sample_count(&mut source, 0.0), 0);
+        assert!(process.sample_arrivals(&mut source, 0.0).is_empty());
+    }
+
+    #[test]
+    fn inhomogeneous() {
+        let process = InhomogeneousPoissonProcess::new(|time: f64| 2.0 + time.sin(), 3.0);
+        let mut source = source::seeded(42);
+        let mut observed = vec![0; 10];
+        for _ in 0..2000 {
+            for time in process.sample_arrivals(&mut source, 10.0) {
+                observed[(time as usize).min(9)] += 1;
+            }
+        }
+        let integral = |a: f64, b: f64| 2.0 * (b - a) + a.cos() - b.cos();
+        let total = integral(0.0, 10.0);
+        let probabilities = (0..10).map(|i| {
+            integral(i as f64, i as f64 + 1.0) / total
+        }).collect::<Vec<_>>();
+        assert!(chi_squared_gof(&observed, &probabilities).p_value > 0.01);
+        let count = observed.iter().sum::<u64>() as f64;
+        assert::close(count / 2000.0, total, 0.2);
+    }
+
+    #[test]
+    fn inter_arrival() {
+        let process = PoissonProcess::new(4.0);
+        let mut source = source::seeded(42);
+        let arrivals = process.arrivals(&mut source).take(5000).collect::<Vec<_>>();
+        let mut gaps = vec![arrivals[0]];
+        gaps.extend(arrivals.windows(2).map(|pair| pair[1] - pair[0]));
+        assert!(ks_one_sample(&gaps, &Exponential::new(4.0)).p_value > 0.05);
+    }
+
+    #[test]
+    fn iterator() {
+        let process = PoissonProcess::new(2.0);
+        let arrivals = process.sample_arrivals(&mut source::seeded(42), 50.0);
+        let mut source = source::seeded(42);
+        let streamed = process.arrivals(&mut source).take(arrivals.len()).collect::<Vec<_>>();
+        assert_eq!(streamed, arrivals);
+
+        let process = InhomogeneousPoissonProcess::new(|time: f64| 1.0 + time.cos(), 2.0);
+        let arrivals = process.sample_arrivals(&mut source::seeded(42), 50.0);
+        let mut source = source::seeded(42);
+        let streamed = process.arrivals(&mut source).take(arrivals.len()).collect::<Vec<_>>();
+        assert_eq!(streamed, arrivals);
+        assert!(arrivals.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(PoissonProcess::try_new(0.0),
+                   Err(ParameterError::RateNotPositive { value: 0.0 }));
+        assert!(InhomogeneousPoissonProcess::try_new(|_| 1.0, ::std::f64::INFINITY).is_err());
+        assert_eq!(PoissonProcess::new(2.0).rate(), 2.0);
+    }
+}
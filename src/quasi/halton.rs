@@ -0,0 +1,150 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use quasi::LowDiscrepancy;
+use source::{Source, Xoshiro256PlusPlus};
+
+/// The Halton sequence.
+///
+/// The coordinate of a point along the `j`th dimension is the radical inverse
+/// of the index of the point in the `j`th prime base. The origin, which is the
+/// first point of the sequence, is skipped. The sequence can be scrambled by
+/// permuting the digits in each base at random, which breaks the correlations
+/// between the coordinates in high dimensions.
+///
+/// ## References
+///
+/// 1. John H. Halton, “On the Efficiency of Certain Quasi-Random Sequences of
+///    Points in Evaluating Multi-Dimensional Integrals,” Numerische
+///    Mathematik, 1960.
+///
+/// 2. Eric Braaten and George Weller, “An Improved Low-Discrepancy Sequence
+///    for Multidimensional Quasi-Monte Carlo Integration,” Journal of
+///    Computational Physics, 1979.
+#[derive(Clone, Debug)]
+pub struct Halton {
+    bases: Vec<u64>,
+    index: u64,
+    permutations: Vec<Vec<u64>>,
+    source: Option<Xoshiro256PlusPlus>,
+}
+
+impl Halton {
+    /// Create a sequence.
+    #[inline]
+    pub fn new() -> Self {
+        Halton { bases: vec![], index: 0, permutations: vec![], source: None }
+    }
+
+    /// Create a sequence scrambled using `source`.
+    ///
+    /// The permutations of the digits leave zero in place, so that the
+    /// coordinates remain in `(0, 1)`.
+    #[inline]
+    pub fn scrambled<S>(source: &mut S) -> Self where S: Source {
+        let source = Xoshiro256PlusPlus::seeded(source.read_u64());
+        Halton { bases: vec![], index: 0, permutations: vec![], source: Some(source) }
+    }
+
+    fn extend(&mut self, dimension: usize) {
+        while self.bases.len() < dimension {
+            let mut base = self.bases.last().map(|&base| base + 1).unwrap_or(2);
+            while self.bases.iter().any(|&prime| base % prime == 0) {
+                base += 1;
+            }
+            self.bases.push(base);
+            if let Some(ref mut source) = self.source {
+                let mut permutation = (0..base).collect::<Vec<_>>();
+                for i in (2..base as usize).rev() {
+                    let j = 1 + (source.read::<f64>() * i as f64) as usize;
+                    permutation.swap(i, j.min(i));
+                }
+                self.permutations.push(permutation);
+            }
+        }
+    }
+}
+
+impl Default for Halton {
+    #[inline]
+    fn default() -> Self {
+        Halton::new()
+    }
+}
+
+impl LowDiscrepancy for Halton {
+    fn next_point(&mut self, dimension: usize) -> Vec<f64> {
+        self.extend(dimension);
+        self.index += 1;
+        (0..dimension).map(|j| {
+            let base = self.bases[j];
+            let permutation = self.permutations.get(j);
+            let (mut index, mut value, mut factor) = (self.index, 0.0, 1.0 / base as f64);
+            while index > 0 {
+                let digit = index % base;
+                let digit = permutation.map(|permutation| permutation[digit as usize])
+                                       .unwrap_or(digit);
+                value += digit as f64 * factor;
+                index /= base;
+                factor /= base as f64;
+            }
+            value
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use quasi::tests::discrepancy;
+    use super::Halton;
+
+    #[test]
+    fn discrepancy_random() {
+        let n = 1024;
+        let mut source = source::seeded(42);
+        let random = (0..n).map(|_| Uniform::new(0.0, 1.0).samples(&mut source, 5))
+                           .collect::<Vec<_>>();
+        let mut sequence = Halton::new();
+        let points = (0..n).map(|_| sequence.next_point(5)).collect::<Vec<_>>();
+        assert!(discrepancy(&points) < discrepancy(&random) / 2.0);
+        let mut sequence = Halton::scrambled(&mut source);
+        let points = (0..n).map(|_| sequence.next_point(5)).collect::<Vec<_>>();
+        assert!(discrepancy(&points) < discrepancy(&random) / 2.0);
+    }
+
+    #[test]
+    fn next_point() {
+        let mut sequence = Halton::new();
+        let points = (0..4).flat_map(|_| sequence.next_point(3)).collect::<Vec<_>>();
+        assert::close(&points, &[1.0 / 2.0, 1.0 / 3.0, 1.0 / 5.0,
+                                 1.0 / 4.0, 2.0 / 3.0, 2.0 / 5.0,
+                                 3.0 / 4.0, 1.0 / 9.0, 3.0 / 5.0,
+                                 1.0 / 8.0, 4.0 / 9.0, 4.0 / 5.0], 1e-15);
+        assert::close(sequence.next_point(10)[9], 5.0 / 29.0, 1e-15);
+    }
+
+    #[test]
+    fn scrambled() {
+        let mut one = Halton::scrambled(&mut source::seeded(42));
+        let mut other = Halton::scrambled(&mut source::seeded(42));
+        let mut plain = Halton::new();
+        let mut distinct = false;
+        for _ in 0..1000 {
+            let x = one.next_point(20);
+            assert_eq!(x, other.next_point(20));
+            assert!(x.iter().all(|&x| 0.0 < x && x < 1.0));
+            let y = plain.next_point(20);
+            assert_eq!(x[0], y[0]);
+            distinct |= x != y;
+        }
+        assert!(distinct);
+        let mut one = Halton::scrambled(&mut source::seeded(42));
+        let mut other = Halton::scrambled(&mut source::seeded(42));
+        let _ = one.next_point(20);
+        let _ = other.next_point(1);
+        assert_eq!(one.next_point(20), other.next_point(20));
+    }
+}
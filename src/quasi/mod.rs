@@ -0,0 +1,55 @@
+//! Low-discrepancy sequences.
+//!
+//! The points of a low-discrepancy sequence cover the unit hypercube more
+//! evenly than independent uniform samples do, so averages over them converge
+//! faster: for smooth integrands, the error of quasi-Monte Carlo integration
+//! with `n` points decreases nearly as `1 / n` instead of `1 / sqrt(n)`. The
+//! points can be mapped to samples of a distribution using `Quasi`.
+//!
+//! ```
+//! use probability::prelude::*;
+//!
+//! let d = Gaussian::new(0.0, 1.0);
+//! let mut sequence = quasi::Sobol::new();
+//! let n = 4096;
+//! let moment = Quasi(&d, &mut sequence).take(n).map(|x| x * x).sum::<f64>() / n as f64;
+//! assert!((moment - 1.0).abs() < 1e-2);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+mod halton;
+mod sobol;
+
+pub use self::halton::Halton;
+pub use self::sobol::Sobol;
+
+/// A low-discrepancy sequence of points in the unit hypercube.
+pub trait LowDiscrepancy {
+    /// Generate the next point and return its first `dimension` coordinates.
+    ///
+    /// The coordinates lie in `(0, 1)`. The sequence advances by one point
+    /// regardless of `dimension`, and the coordinates of a point do not depend
+    /// on the dimensions requested for the previous points.
+    fn next_point(&mut self, dimension: usize) -> Vec<f64>;
+}
+
+#[cfg(test)]
+mod tests {
+    // Compute the L2 star discrepancy of `points` using Warnock's formula.
+    pub fn discrepancy(points: &[Vec<f64>]) -> f64 {
+        let (n, d) = (points.len() as f64, points[0].len() as i32);
+        let mut first = 0.0;
+        let mut second = 0.0;
+        for x in points {
+            first += x.iter().fold(1.0, |product, &x| product * (1.0 - x * x));
+            for y in points {
+                second += x.iter().zip(y).fold(1.0, |product, (&x, &y)| {
+                    product * (1.0 - x.max(y))
+                });
+            }
+        }
+        (3f64.powi(-d) - 2f64.powi(1 - d) * first / n + second / (n * n)).sqrt()
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use quasi::LowDiscrepancy;
+
+/// The Sobol sequence.
+///
+/// The sequence is generated in the Gray-code order using the direction
+/// numbers of Joe and Kuo, which are available for up to 32 dimensions. The
+/// origin, which is the first point of the sequence, is skipped. At most
+/// `2^32 - 1` points can be generated.
+///
+/// ## References
+///
+/// 1. Stephen Joe and Frances Y. Kuo, “Constructing Sobol Sequences with Better
+///    Two-Dimensional Projections,” SIAM Journal on Scientific Computing, 2008.
+///
+/// 2. Paul Bratley and Bennett L. Fox, “Algorithm 659: Implementing Sobol’s
+///    Quasirandom Sequence Generator,” ACM Transactions on Mathematical
+///    Software, 1988.
+#[derive(Clone, Debug)]
+pub struct Sobol {
+    directions: Vec<[u32; BITS]>,
+    index: u32,
+    state: [u32; DIMENSIONS],
+}
+
+const BITS: usize = 32;
+const DIMENSIONS: usize = 32;
+
+// The degree, the coefficients, and the initial direction numbers for the
+// dimensions starting from the second one.
+const PARAMETERS: [(usize, u32, &[u32]); DIMENSIONS - 1] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+    (7, 7, &[1, 1, 3, 13, 7, 35, 63]),
+    (7, 8, &[1, 3, 5, 9, 1, 25, 53]),
+    (7, 14, &[1, 3, 1, 13, 9, 35, 107]),
+    (7, 19, &[1, 3, 1, 5, 27, 61, 31]),
+    (7, 21, &[1, 1, 5, 11, 19, 41, 61]),
+    (7, 28, &[1, 3, 5, 3, 3, 13, 69]),
+    (7, 31, &[1, 1, 7, 13, 1, 19, 1]),
+    (7, 32, &[1, 3, 7, 5, 13, 19, 59]),
+    (7, 37, &[1, 1, 3, 9, 25, 29, 41]),
+    (7, 41, &[1, 3, 5, 13, 23, 1, 55]),
+    (7, 42, &[1, 3, 7, 3, 13, 59, 17]),
+];
+
+impl Sobol {
+    /// Create a sequence.
+    pub fn new() -> Self {
+        let mut directions = Vec::with_capacity(DIMENSIONS);
+        let mut first = [0; BITS];
+        for (k, value) in first.iter_mut().enumerate() {
+            *value = 1 << (BITS - 1 - k);
+        }
+        directions.push(first);
+        for &(s, a, m) in PARAMETERS.iter() {
+            let mut values = [0; BITS];
+            for k in 0..BITS {
+                values[k] = if k < s {
+                    m[k] << (BITS - 1 - k)
+                } else {
+                    let mut value = values[k - s] ^ (values[k - s] >> s);
+                    for l in 1..s {
+                        if (a >> (s - 1 - l)) & 1 == 1 {
+                            value ^= values[k - l];
+                        }
+                    }
+                    value
+                };
+            }
+            directions.push(values);
+        }
+        Sobol { directions: directions, index: 0, state: [0; DIMENSIONS] }
+    }
+
+    /// Return the largest supported dimension.
+    #[inline(always)]
+    pub fn dimension(&self) -> usize {
+        DIMENSIONS
+    }
+}
+
+impl Default for Sobol {
+    #[inline]
+    fn default() -> Self {
+        Sobol::new()
+    }
+}
+
+impl LowDiscrepancy for Sobol {
+    /// Generate the next point and return its first `dimension` coordinates.
+    ///
+    /// It should hold that `dimension <= 32`.
+    fn next_point(&mut self, dimension: usize) -> Vec<f64> {
+        const SCALE: f64 = 1.0 / (1u64 << BITS) as f64;
+        assert!(dimension <= DIMENSIONS,
+                "the dimension should not exceed {} (got {})", DIMENSIONS, dimension);
+        assert!(self.index < ::std::u32::MAX, "the sequence should not be exhausted");
+        let k = (!self.index).trailing_zeros() as usize;
+        for (state, directions) in self.state.iter_mut().zip(&self.directions) {
+            *state ^= directions[k];
+        }
+        self.index += 1;
+        self.state[..dimension].iter().map(|&x| x as f64 * SCALE).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+
+    use quasi::tests::discrepancy;
+    use super::Sobol;
+
+    #[test]
+    fn dimension() {
+        assert_eq!(Sobol::new().dimension(), 32);
+    }
+
+    #[test]
+    fn discrepancy_random() {
+        let n = 1024;
+        for &dimension in &[2, 5] {
+            let mut sequence = Sobol::new();
+            let points = (0..n).map(|_| sequence.next_point(dimension)).collect::<Vec<_>>();
+            let mut source = source::seeded(42);
+            let random = (0..n).map(|_| Uniform::new(0.0, 1.0).samples(&mut source, dimension))
+                               .collect::<Vec<_>>();
+            assert!(discrepancy(&points) < discrepancy(&random) / 3.0);
+        }
+    }
+
+    #[test]
+    fn integrate() {
+        let d = Gaussian::new(0.0, 1.0);
+        let error = |n| {
+            let mut sequence = Sobol::new();
+            let sum = Quasi(&d, &mut sequence).take(n).map(|x| x * x).sum::<f64>();
+            (sum / n as f64 - 1.0).abs()
+        };
+        let (coarse, fine) = (error(1 << 10), error(1 << 16));
+        assert!(fine < 1e-4);
+        assert!(coarse / fine > 32.0);
+    }
+
+    #[test]
+    fn next_point() {
+        let mut sequence = Sobol::new();
+        let points = (0..7).map(|_| sequence.next_point(2)).collect::<Vec<_>>();
+        assert_eq!(points, vec![vec![0.5, 0.5], vec![0.75, 0.25], vec![0.25, 0.75],
+                                vec![0.375, 0.375], vec![0.875, 0.875], vec![0.625, 0.125],
+                                vec![0.125, 0.625]]);
+
+        let mut sequence = Sobol::new();
+        let points = (0..4).map(|_| sequence.next_point(32)).collect::<Vec<_>>();
+        assert_eq!(&points[3][..8], &[0.375, 0.375, 0.625, 0.875, 0.375, 0.125, 0.375, 0.875]);
+        assert_eq!(&points[3][28..], &[0.125, 0.125, 0.375, 0.125]);
+
+        let mut one = Sobol::new();
+        let mut other = Sobol::new();
+        for i in 0..1000 {
+            let x = one.next_point(5);
+            let y = other.next_point(1 + i % 5);
+            assert_eq!(&x[..y.len()], &y[..]);
+            assert!(x.iter().all(|&x| 0.0 < x && x < 1.0));
+        }
+        assert!(one.next_point(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn next_point_dimension() {
+        let _ = Sobol::new().next_point(33);
+    }
+}
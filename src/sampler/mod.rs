@@ -1,12 +1,240 @@
 //! Samplers of random numbers.
 
-use distribution::Sample;
-use source::Source;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use distribution::{Inverse, Sample};
+#[cfg(feature = "alloc")]
+use distribution::Joint;
+#[cfg(feature = "alloc")]
+use quasi::LowDiscrepancy;
+use source::{self, Source};
+#[cfg(feature = "alloc")]
+use SamplingError;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use float::Float;
+
+/// A means of drawing a sequence of antithetic pairs of samples.
+///
+/// Each pair is obtained by mapping a uniform sample `u` and its reflection
+/// `1 - u` through the inverse of the distribution function. The two samples
+/// of a pair are negatively correlated, so, for a monotone function, the
+/// average over the pairs has a smaller variance than the average over as
+/// many independent samples.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let d = Gaussian::new(0.0, 1.0);
+/// let mut source = source::seeded(42);
+/// let samples = Antithetic(&d, &mut source).take(5).flat_map(|(x, y)| vec![x, y])
+///                                         .collect::<Vec<_>>();
+/// ```
+pub struct Antithetic<D, S>(pub D, pub S);
+
+/// A source of common random numbers.
+///
+/// The source records the values drawn from the underlying source and can be
+/// rewound in order to replay them, which allows several distributions to be
+/// sampled using the same underlying randomness. Comparisons between the
+/// distributions then suffer from no noise due to the randomness itself.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let mut source = Common::new(source::seeded(42));
+/// let x = Independent(&Gaussian::new(0.0, 1.0), &mut source).take(10).collect::<Vec<_>>();
+/// source.rewind();
+/// let y = Independent(&Gaussian::new(0.1, 1.0), &mut source).take(10).collect::<Vec<_>>();
+/// ```
+#[cfg(feature = "alloc")]
+pub struct Common<S> {
+    source: S,
+    values: Vec<u64>,
+    position: usize,
+}
 
 /// A means of drawing a sequence of independent samples.
+///
+/// The distribution can be either owned or borrowed. The source can be either
+/// borrowed or, in the case of the sources provided by module `source`, owned.
+/// A sampler owning both can be returned from a function or stored in a
+/// structure.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// fn gaussian() -> Box<dyn Iterator<Item=f64>> {
+///     let source = source::seeded(42);
+///     Box::new(Independent::new(Gaussian::new(0.0, 1.0), source))
+/// }
+///
+/// let samples = gaussian().take(10).collect::<Vec<_>>();
+/// ```
 pub struct Independent<D, S>(pub D, pub S);
 
-impl<'a, T, D, S> Iterator for Independent<&'a D, &'a mut S> where D: Sample<Value=T>, S: Source {
+/// A Latin hypercube design.
+///
+/// A design of `n` points in `d` dimensions is constructed by splitting the
+/// unit interval into `n` strata of equal width along each dimension, drawing
+/// one point uniformly within each stratum, and permuting the points of each
+/// dimension independently at random. Each stratum of each dimension then
+/// contains exactly one point.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let design = LatinHypercube::new(2, 10);
+/// let distributions = [Gaussian::new(0.0, 1.0), Gaussian::new(1.0, 2.0)];
+/// let samples = design.sample(&distributions, &mut source::seeded(42));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatinHypercube {
+    dimension: usize,
+    size: usize,
+}
+
+/// A means of drawing a sequence of quasi-random samples.
+///
+/// The samples are obtained by mapping the points of a low-discrepancy
+/// sequence through the inverse of the distribution function. For a joint
+/// distribution of a vector of components, each component is given its own
+/// dimension of the sequence.
+///
+/// ```
+/// use probability::prelude::*;
+///
+/// let d = Joint::new(vec![Gaussian::new(0.0, 1.0), Gaussian::new(1.0, 2.0)]);
+/// let mut sequence = quasi::Sobol::new();
+/// let samples = Quasi(&d, &mut sequence).take(10).collect::<Vec<_>>();
+/// ```
+#[cfg(feature = "alloc")]
+pub struct Quasi<D, S>(pub D, pub S);
+
+impl<D, S> Antithetic<D, S> {
+    /// Create a sampler drawing from `distribution` using `source`.
+    #[inline(always)]
+    pub fn new(distribution: D, source: S) -> Self {
+        Antithetic(distribution, source)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S> Common<S> where S: Source {
+    /// Create a source recording the values drawn from `source`.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        Common { source: source, values: vec![], position: 0 }
+    }
+
+    /// Forget the recorded values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.position = 0;
+    }
+
+    /// Rewind the source to the first recorded value.
+    ///
+    /// The recorded values are replayed before any new ones are drawn from
+    /// the underlying source.
+    #[inline(always)]
+    pub fn rewind(&mut self) {
+        self.position = 0;
+    }
+}
+
+impl<D, S> Independent<D, S> {
+    /// Create a sampler drawing from `distribution` using `source`.
+    #[inline(always)]
+    pub fn new(distribution: D, source: S) -> Self {
+        Independent(distribution, source)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl LatinHypercube {
+    /// Create a design of `n` points in `dimension` dimensions.
+    ///
+    /// It should hold that `dimension > 0` and `n > 0`.
+    #[inline]
+    pub fn new(dimension: usize, n: usize) -> Self {
+        should!(dimension > 0 && n > 0);
+        LatinHypercube { dimension: dimension, size: n }
+    }
+
+    /// Draw the points of the design.
+    ///
+    /// The result is an `n`-by-`dimension` matrix stored in the row-major
+    /// order. The coordinates lie in `(0, 1)`.
+    pub fn design<S>(&self, source: &mut S) -> Vec<f64> where S: Source {
+        let (d, n) = (self.dimension, self.size);
+        let mut design = vec![0.0; n * d];
+        for j in 0..d {
+            for (i, k) in permutation(n, source).into_iter().enumerate() {
+                design[i * d + j] = stratum(k, n, open(source));
+            }
+        }
+        design
+    }
+
+    /// Draw the points of the design and map the `j`th coordinate of each
+    /// point through the inverse of the distribution function of the `j`th
+    /// distribution.
+    ///
+    /// The result is an `n`-by-`dimension` matrix stored in the row-major
+    /// order. It should hold that `distributions.len() == dimension`.
+    pub fn sample<D, S>(&self, distributions: &[D], source: &mut S) -> Vec<D::Value>
+        where D: Inverse, S: Source
+    {
+        should!(distributions.len() == self.dimension);
+        let d = self.dimension;
+        self.design(source).iter().enumerate().map(|(i, &p)| {
+            distributions[i % d].inverse(p)
+        }).collect()
+    }
+
+    /// Return the dimension.
+    #[inline(always)]
+    pub fn dimension(&self) -> usize { self.dimension }
+
+    /// Return the number of points.
+    #[inline(always)]
+    pub fn size(&self) -> usize { self.size }
+}
+
+#[cfg(feature = "alloc")]
+impl<D, S> Quasi<D, S> {
+    /// Create a sampler drawing from `distribution` using `sequence`.
+    #[inline(always)]
+    pub fn new(distribution: D, sequence: S) -> Self {
+        Quasi(distribution, sequence)
+    }
+}
+
+impl<'l, T, D, S> Iterator for Antithetic<D, &'l mut S> where D: Inverse<Value=T>, S: Source {
+    type Item = (T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(T, T)> {
+        let u = self.1.read::<f64>();
+        Some((self.0.inverse(u), self.0.inverse(1.0 - u)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S> Source for Common<S> where S: Source {
+    fn read_u64(&mut self) -> u64 {
+        if self.position == self.values.len() {
+            self.values.push(self.source.read_u64());
+        }
+        self.position += 1;
+        self.values[self.position - 1]
+    }
+}
+
+impl<'l, T, D, S> Iterator for Independent<D, &'l mut S> where D: Sample<Value=T>, S: Source {
     type Item = T;
 
     #[inline(always)]
@@ -14,3 +242,458 @@ impl<'a, T, D, S> Iterator for Independent<&'a D, &'a mut S> where D: Sample<Val
         Some(self.0.sample(self.1))
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<'l, T, D, S> Iterator for Quasi<D, &'l mut S> where D: Inverse<Value=T>, S: LowDiscrepancy {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        Some(self.0.inverse(self.1.next_point(1)[0]))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'l, 'm, T, S> Iterator for Quasi<&'l Joint<Vec<T>>, &'m mut S>
+    where T: Inverse, S: LowDiscrepancy
+{
+    type Item = Vec<T::Value>;
+
+    fn next(&mut self) -> Option<Vec<T::Value>> {
+        let components = self.0.components();
+        let point = self.1.next_point(components.len());
+        Some(components.iter().zip(point).map(|(d, p)| d.inverse(p)).collect())
+    }
+}
+
+macro_rules! implement(
+    ($($source:ty),*) => ($(
+        impl<T, D> Iterator for Independent<D, $source> where D: Sample<Value=T> {
+            type Item = T;
+
+            #[inline(always)]
+            fn next(&mut self) -> Option<T> {
+                Some(self.0.sample(&mut self.1))
+            }
+        }
+    )*);
+);
+
+#[cfg(feature = "std")]
+implement!(source::Default, source::Xorshift128Plus);
+implement!(source::Xoshiro256PlusPlus);
+
+/// Choose `k` distinct indices out of `0, 1, …, n - 1` uniformly at random.
+///
+/// The indices are chosen using Floyd's algorithm, which takes time and
+/// memory proportional to `k` regardless of `n`. Every subset is equally
+/// likely, but the order of the indices within the result is not random; it
+/// can be randomized using `permutation`. An error is returned if `k > n`.
+#[cfg(feature = "std")]
+pub fn choose<S>(n: usize, source: &mut S, k: usize) -> Result<Vec<usize>, SamplingError>
+    where S: Source
+{
+    use std::collections::HashSet;
+
+    ensure!(k <= n, SamplingError::SizeExceeded { k: k, n: n });
+    let mut chosen = Vec::with_capacity(k);
+    let mut seen = HashSet::with_capacity(k);
+    for j in (n - k)..n {
+        let i = pick(source, j + 1);
+        let i = if seen.contains(&i) { j } else { i };
+        seen.insert(i);
+        chosen.push(i);
+    }
+    Ok(chosen)
+}
+
+/// Choose `k` distinct indices of `weights` with probabilities proportional
+/// to the weights.
+///
+/// The result is distributed as the outcome of choosing the indices one by
+/// one, each time with probabilities proportional to the weights of the
+/// indices not yet chosen, and the indices are given in the order in which
+/// they would have been chosen. The indices are chosen using the method of
+/// exponential keys by Efraimidis and Spirakis. Indices with zero weights are
+/// never chosen, and an error is returned if `k` exceeds the number of
+/// positive weights. It should hold that the weights are nonnegative and
+/// finite.
+#[cfg(feature = "alloc")]
+pub fn choose_weighted<S>(weights: &[f64], source: &mut S, k: usize)
+                          -> Result<Vec<usize>, SamplingError>
+    where S: Source
+{
+    should!(weights.iter().all(|&w| w >= 0.0 && w.is_finite()));
+    let mut keys = weights.iter().enumerate().filter(|&(_, &w)| w > 0.0).map(|(i, &w)| {
+        (-open(source).ln() / w, i)
+    }).collect::<Vec<_>>();
+    ensure!(k <= keys.len(), SamplingError::SizeExceeded { k: k, n: keys.len() });
+    if k == 0 {
+        return Ok(vec![]);
+    }
+    keys.select_nth_unstable_by(k - 1, |a, b| a.partial_cmp(b).unwrap());
+    keys.truncate(k);
+    keys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(keys.into_iter().map(|(_, i)| i).collect())
+}
+
+/// Draw a permutation of `0, 1, …, n - 1` uniformly at random.
+///
+/// The permutation is drawn using the Fisher–Yates shuffle.
+#[cfg(feature = "alloc")]
+pub fn permutation<S>(n: usize, source: &mut S) -> Vec<usize> where S: Source {
+    let mut permutation = (0..n).collect::<Vec<_>>();
+    for i in (1..n).rev() {
+        permutation.swap(i, pick(source, i + 1));
+    }
+    permutation
+}
+
+/// Draw `n` stratified samples.
+///
+/// The unit interval is split into `n` strata of equal width, one uniform
+/// sample is drawn within each stratum, and the samples are mapped through
+/// the inverse of the distribution function. The samples are in the
+/// ascending order of the strata. The strata never reach the endpoints of the
+/// unit interval, so the samples are finite even if the support is not.
+#[cfg(feature = "alloc")]
+pub fn stratified<D, S>(distribution: &D, source: &mut S, n: usize) -> Vec<D::Value>
+    where D: Inverse, S: Source
+{
+    (0..n).map(|i| distribution.inverse(stratum(i, n, open(source)))).collect()
+}
+
+// Draw a uniform sample from `(0, 1)`.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn open<S>(source: &mut S) -> f64 where S: Source {
+    ((source.read_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+// Draw a uniform sample from `{0, 1, …, n - 1}`.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn pick<S>(source: &mut S, n: usize) -> usize where S: Source {
+    ((source.read_u64() as u128 * n as u128) >> 64) as usize
+}
+
+// Map `u` from `(0, 1)` to the `i`th of `n` strata of the unit interval
+// without reaching the endpoints.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn stratum(i: usize, n: usize, u: f64) -> f64 {
+    ((i as f64 + u) / n as f64).min(1.0 - ::std::f64::EPSILON / 2.0)
+}
+
+#[cfg(feature = "rand")]
+mod rand {
+    use interop::FromRand;
+    use rand::distributions::Distribution;
+    use source::{self, Source};
+
+    use super::Independent;
+
+    impl<'l, 'm, T, D, S> Iterator for Independent<&'l FromRand<D, T>, &'m mut S>
+        where D: Distribution<T>, S: Source
+    {
+        type Item = T;
+
+        #[inline(always)]
+        fn next(&mut self) -> Option<T> {
+            Some(self.0.sample(self.1))
+        }
+    }
+
+    impl<'l, T, D, S> Iterator for Independent<FromRand<D, T>, &'l mut S>
+        where D: Distribution<T>, S: Source
+    {
+        type Item = T;
+
+        #[inline(always)]
+        fn next(&mut self) -> Option<T> {
+            Some(self.0.sample(self.1))
+        }
+    }
+
+    macro_rules! implement(
+        ($($source:ty),*) => ($(
+            impl<T, D> Iterator for Independent<FromRand<D, T>, $source> where D: Distribution<T> {
+                type Item = T;
+
+                #[inline(always)]
+                fn next(&mut self) -> Option<T> {
+                    Some(self.0.sample(&mut self.1))
+                }
+            }
+        )*);
+    );
+
+    implement!(source::Default, source::Xorshift128Plus, source::Xoshiro256PlusPlus);
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn antithetic() {
+        let d = Gaussian::new(0.0, 1.0);
+        let mut source = source::seeded(42);
+        for (x, y) in Antithetic::new(&d, &mut source).take(100) {
+            assert::close(x, -y, 1e-8);
+        }
+
+        let (m, n) = (200, 1000);
+        let (mut plain, mut antithetic) = (vec![], vec![]);
+        for _ in 0..m {
+            let x = Independent(&d, &mut source).take(n).map(f64::exp).sum::<f64>();
+            plain.push(x / n as f64);
+            let x = Antithetic(&d, &mut source).take(n / 2)
+                                               .map(|(x, y)| x.exp() + y.exp())
+                                               .sum::<f64>();
+            antithetic.push(x / n as f64);
+        }
+        let (plain, antithetic) = (Empirical::new(&plain), Empirical::new(&antithetic));
+        let mean = 0.5f64.exp();
+        assert::close(plain.mean(), mean, 0.01);
+        assert::close(antithetic.mean(), mean, 0.01);
+        assert!(antithetic.variance() < 0.8 * plain.variance());
+    }
+
+    #[test]
+    fn borrowed() {
+        let d = Gaussian::new(0.0, 1.0);
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        let x = Independent(&d, &mut source).take(10).collect::<Vec<_>>();
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        assert_eq!(x, d.samples(&mut source, 10));
+    }
+
+    #[test]
+    fn choose() {
+        use super::choose;
+
+        let mut source = source::seeded(42);
+        assert!(choose(10, &mut source, 0).unwrap().is_empty());
+        let mut x = choose(10, &mut source, 10).unwrap();
+        x.sort();
+        assert_eq!(x, (0..10).collect::<Vec<_>>());
+        let x = choose(::std::usize::MAX, &mut source, 5).unwrap();
+        assert!(x.iter().enumerate().all(|(i, a)| x[..i].iter().all(|b| a != b)));
+        assert_eq!(choose(3, &mut source, 4), Err(SamplingError::SizeExceeded { k: 4, n: 3 }));
+
+        let m = 100000;
+        let mut counts = [0; 5];
+        for _ in 0..m {
+            for i in choose(5, &mut source, 2).unwrap() {
+                counts[i] += 1;
+            }
+        }
+        for &count in &counts {
+            assert::close(count as f64 / m as f64, 0.4, 0.01);
+        }
+    }
+
+    #[test]
+    fn choose_weighted() {
+        use super::choose_weighted;
+
+        let weights = [1.0, 2.0, 0.0, 3.0, 4.0];
+        let total = weights.iter().sum::<f64>();
+        let mut expected = [0.0; 5];
+        for a in 0..5 {
+            for b in 0..5 {
+                if a != b {
+                    let p = weights[a] / total * weights[b] / (total - weights[a]);
+                    expected[a] += p;
+                    expected[b] += p;
+                }
+            }
+        }
+
+        let mut source = source::seeded(42);
+        let m = 100000;
+        let mut counts = [0; 5];
+        for _ in 0..m {
+            let x = choose_weighted(&weights, &mut source, 2).unwrap();
+            assert!(x.len() == 2 && x[0] != x[1]);
+            for i in x {
+                counts[i] += 1;
+            }
+        }
+        assert_eq!(counts[2], 0);
+        for (&count, &expected) in counts.iter().zip(&expected) {
+            assert::close(count as f64 / m as f64, expected, 0.01);
+        }
+
+        assert!(choose_weighted(&weights, &mut source, 0).unwrap().is_empty());
+        let mut x = choose_weighted(&weights, &mut source, 4).unwrap();
+        x.sort();
+        assert_eq!(x, vec![0, 1, 3, 4]);
+        assert_eq!(choose_weighted(&weights, &mut source, 5),
+                   Err(SamplingError::SizeExceeded { k: 5, n: 4 }));
+    }
+
+    #[test]
+    fn common() {
+        let (one, other) = (Gaussian::new(0.0, 1.0), Gaussian::new(0.1, 1.0));
+        let (m, n) = (100, 1000);
+        let mut source = source::seeded(42);
+        let (mut independent, mut common) = (vec![], vec![]);
+        for _ in 0..m {
+            let x = Independent(&one, &mut source).take(n).sum::<f64>();
+            let y = Independent(&other, &mut source).take(n).sum::<f64>();
+            independent.push((y - x) / n as f64);
+            let mut source = Common::new(source.fork());
+            let x = Independent(&one, &mut source).take(n).sum::<f64>();
+            source.rewind();
+            let y = Independent(&other, &mut source).take(n).sum::<f64>();
+            common.push((y - x) / n as f64);
+        }
+        let (independent, common) = (Empirical::new(&independent), Empirical::new(&common));
+        assert::close(independent.mean(), 0.1, 0.01);
+        assert::close(common.mean(), 0.1, 1e-12);
+        assert!(independent.variance() > 1e-3);
+        assert!(common.variance() < 1e-20);
+
+        let mut source = Common::new(source::seeded(42));
+        let x = Poisson::new(3.0).samples(&mut source, 10);
+        source.rewind();
+        assert_eq!(Poisson::new(3.0).samples(&mut source, 5), &x[..5]);
+        assert_eq!(Poisson::new(3.0).samples(&mut source, 5), &x[5..]);
+        source.clear();
+        assert!(Poisson::new(3.0).samples(&mut source, 10) != x);
+    }
+
+    #[test]
+    fn latin_hypercube() {
+        let (d, n) = (3, 100);
+        let design = LatinHypercube::new(d, n);
+        let points = design.design(&mut source::seeded(42));
+        assert_eq!(points.len(), n * d);
+        for j in 0..d {
+            let mut strata = (0..n).map(|i| (points[i * d + j] * n as f64) as usize)
+                                   .collect::<Vec<_>>();
+            assert!(strata != (0..n).collect::<Vec<_>>());
+            strata.sort();
+            assert_eq!(strata, (0..n).collect::<Vec<_>>());
+        }
+        assert!(points.iter().all(|&p| 0.0 < p && p < 1.0));
+
+        let distributions = [Gaussian::new(0.0, 1.0), Gaussian::new(1.0, 2.0),
+                             Gaussian::new(-1.0, 0.5)];
+        let x = design.sample(&distributions, &mut source::seeded(42));
+        for (i, (&x, &p)) in x.iter().zip(&points).enumerate() {
+            assert_eq!(x, distributions[i % d].inverse(p));
+        }
+
+        let design = LatinHypercube::new(1, 1);
+        let x = design.sample(&[Exponential::new(1.0)], &mut source::seeded(42));
+        assert!(x.len() == 1 && x[0].is_finite());
+    }
+
+    #[test]
+    fn mixed() {
+        let d = Gaussian::new(0.0, 1.0);
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        let x = Independent::new(d, &mut source).take(10).collect::<Vec<_>>();
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        assert_eq!(x, d.samples(&mut source, 10));
+        let mut source = source::default();
+        assert_eq!(Independent::new(d, &mut source).take(10).count(), 10);
+    }
+
+    #[test]
+    fn owned() {
+        fn gaussian() -> Box<dyn Iterator<Item=f64>> {
+            let source = source::Xorshift128Plus::new([42, 69]);
+            Box::new(Independent::new(Gaussian::new(0.0, 1.0), source))
+        }
+
+        let x = gaussian().take(10).collect::<Vec<_>>();
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        assert_eq!(x, Gaussian::new(0.0, 1.0).samples(&mut source, 10));
+        assert_eq!(Independent::new(Poisson::new(3.0), source::default()).take(10).count(), 10);
+    }
+
+    #[test]
+    fn permutation() {
+        use super::permutation;
+
+        let mut source = source::seeded(42);
+        assert!(permutation(0, &mut source).is_empty());
+        assert_eq!(permutation(1, &mut source), vec![0]);
+
+        let m = 24000;
+        let mut counts = vec![0; 24];
+        for _ in 0..m {
+            let x = permutation(4, &mut source);
+            let mut rank = 0;
+            for i in 0..4 {
+                let smaller = x[i + 1..].iter().filter(|&&y| y < x[i]).count();
+                rank = rank * (4 - i) + smaller;
+            }
+            counts[rank] += 1;
+        }
+        let expected = m as f64 / 24.0;
+        let statistic = counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected)
+                                     .sum::<f64>();
+        // The 0.999 quantile of the chi-squared distribution with 23 degrees of freedom.
+        assert!(statistic < 49.73);
+    }
+
+    #[test]
+    fn quasi() {
+        let d = Exponential::new(2.0);
+        let mut sequence = quasi::Halton::new();
+        let x = Quasi(&d, &mut sequence).take(3).collect::<Vec<_>>();
+        assert_eq!(x, vec![d.inverse(0.5), d.inverse(0.25), d.inverse(0.75)]);
+
+        let d = Joint::new(vec![Uniform::new(0.0, 1.0), Uniform::new(2.0, 4.0)]);
+        let mut sequence = quasi::Halton::new();
+        let x = Quasi::new(&d, &mut sequence).take(2).collect::<Vec<_>>();
+        assert_eq!(x, vec![vec![0.5, 2.0 + 2.0 / 3.0], vec![0.25, 2.0 + 4.0 / 3.0]]);
+    }
+
+    #[test]
+    fn samples() {
+        let d = Poisson::new(3.0);
+        let mut source = source::Xorshift128Plus::new([42, 69]);
+        assert!(d.samples(&mut source, 0).is_empty());
+        assert_eq!(d.samples(&mut source, 1000).len(), 1000);
+    }
+
+    #[test]
+    fn stratified() {
+        use super::stratified;
+
+        let deviation = |x: &mut Vec<f64>| {
+            let d = Gaussian::new(0.0, 1.0);
+            let n = x.len() as f64;
+            x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            x.iter().enumerate().fold(0.0f64, |deviation, (i, &x)| {
+                let p = d.distribution(x);
+                deviation.max((p - i as f64 / n).abs()).max((p - (i + 1) as f64 / n).abs())
+            })
+        };
+
+        let d = Gaussian::new(0.0, 1.0);
+        let mut source = source::seeded(42);
+        for _ in 0..10 {
+            let mut x = stratified(&d, &mut source, 1000);
+            let mut y = d.samples(&mut source, 1000);
+            assert!(deviation(&mut x) <= 1e-3 + 1e-12);
+            assert!(deviation(&mut x) < deviation(&mut y));
+        }
+
+        let x = stratified(&Exponential::new(1.0), &mut source, 1);
+        assert!(x.len() == 1 && x[0].is_finite());
+        let x = stratified(&Uniform::new(0.0, 1.0), &mut source, 1000);
+        assert!(x.iter().enumerate().all(|(i, &x)| {
+            i as f64 / 1000.0 <= x && x < (i + 1) as f64 / 1000.0
+        }));
+        assert!(super::stratum(999, 1000, 1.0 - ::std::f64::EPSILON / 4.0) < 1.0);
+        assert!(stratified(&d, &mut source, 0).is_empty());
+    }
+}
@@ -1,3 +1,16 @@
 //! Sources of randomness.
+//!
+//! Without the `std` feature, the module provides the `Source` trait, which
+//! mirrors the one of the `random` crate, and the generators defined in the
+//! crate, but no default source.
 
+#[cfg(feature = "std")]
 pub use random::*;
+
+#[cfg(not(feature = "std"))]
+mod traits;
+mod xoshiro;
+
+#[cfg(not(feature = "std"))]
+pub use self::traits::{Sequence, Source, Value};
+pub use self::xoshiro::{Xoshiro256PlusPlus, seeded};
@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+/// A source of randomness.
+///
+/// The trait mirrors the one of the `random` crate, which the module reexports
+/// when the `std` feature is enabled.
+pub trait Source {
+    /// Read a random `u64`.
+    ///
+    /// The implied distribution is a discrete uniform distribution over
+    /// `{0, 1, …, u64::MAX}`.
+    fn read_u64(&mut self) -> u64;
+
+    /// Read a random `f64`.
+    ///
+    /// The implied distribution is a continuous uniform distribution over
+    /// `[0, 1]`.
+    #[inline(always)]
+    fn read_f64(&mut self) -> f64 {
+        self.read_u64() as f64 / u64::MAX as f64
+    }
+
+    /// Read a random value.
+    #[inline(always)]
+    fn read<V>(&mut self) -> V where Self: Sized, V: Value {
+        Value::from(self)
+    }
+
+    /// Read a sequence of random values.
+    #[inline(always)]
+    fn iter<'l, V>(&'l mut self) -> Sequence<'l, Self, V> where Self: Sized, V: Value {
+        Sequence { source: self, phantom: PhantomData }
+    }
+}
+
+/// A random value.
+pub trait Value {
+    /// Create a random value from a source.
+    fn from<S>(source: &mut S) -> Self where S: Source;
+}
+
+/// A random sequence.
+pub struct Sequence<'l, S: ?Sized, V> where S: Source + 'l, V: Value + 'l {
+    source: &'l mut S,
+    phantom: PhantomData<&'l V>,
+}
+
+impl<'l, S, V> Iterator for Sequence<'l, S, V> where S: Source, V: Value {
+    type Item = V;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<V> {
+        Some(self.source.read())
+    }
+}
+
+impl Value for f64 {
+    #[inline(always)]
+    fn from<S>(source: &mut S) -> Self where S: Source {
+        source.read_f64()
+    }
+}
+
+impl Value for u64 {
+    #[inline(always)]
+    fn from<S>(source: &mut S) -> Self where S: Source {
+        source.read_u64()
+    }
+}
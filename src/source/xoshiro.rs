@@ -0,0 +1,141 @@
+use source::Source;
+
+/// The Xoshiro256++ algorithm.
+///
+/// The generator has a period of `2^256 - 1` and passes the common batteries
+/// of statistical tests. Given a seed, the sequence it produces is the same
+/// across platforms and versions of the crate.
+///
+/// ## References
+///
+/// 1. David Blackman and Sebastiano Vigna, “Scrambled Linear Pseudorandom
+///    Number Generators,” ACM Transactions on Mathematical Software, 2021.
+///
+/// 2. Guy L. Steele Jr., Doug Lea, and Christine H. Flood, “Fast Splittable
+///    Pseudorandom Number Generators,” OOPSLA, 2014.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Xoshiro256PlusPlus {
+    state: [u64; 4],
+}
+
+impl Xoshiro256PlusPlus {
+    /// Create an instance of the algorithm with the given state.
+    ///
+    /// At least one bit of the state should be nonzero.
+    #[inline(always)]
+    pub fn new(state: [u64; 4]) -> Self {
+        should!(state[0] | state[1] | state[2] | state[3] != 0);
+        Xoshiro256PlusPlus { state: state }
+    }
+
+    /// Create an instance of the algorithm with the state expanded from `seed`.
+    ///
+    /// The state is filled using the SplitMix64 algorithm, so any seed,
+    /// including zero, is acceptable.
+    pub fn seeded(seed: u64) -> Self {
+        let mut seed = seed;
+        let mut state = [0; 4];
+        for value in state.iter_mut() {
+            *value = split(&mut seed);
+        }
+        Xoshiro256PlusPlus::new(state)
+    }
+
+    /// Derive an independent instance of the algorithm.
+    ///
+    /// The new instance is seeded with the next value produced by the current
+    /// one, which therefore advances by one step. Repeated forking from the
+    /// same seed yields the same collection of streams, which makes parallel
+    /// computations reproducible.
+    #[inline]
+    pub fn fork(&mut self) -> Self {
+        Xoshiro256PlusPlus::seeded(self.read_u64())
+    }
+}
+
+impl Source for Xoshiro256PlusPlus {
+    #[inline(always)]
+    fn read_u64(&mut self) -> u64 {
+        let state = &mut self.state;
+        let value = state[0].wrapping_add(state[3]).rotate_left(23).wrapping_add(state[0]);
+        let shift = state[1] << 17;
+        state[2] ^= state[0];
+        state[3] ^= state[1];
+        state[1] ^= state[2];
+        state[0] ^= state[3];
+        state[2] ^= shift;
+        state[3] = state[3].rotate_left(45);
+        value
+    }
+}
+
+/// Create an instance of the Xoshiro256++ algorithm seeded with `seed`.
+///
+/// Unlike `default`, which returns a source shared by the current thread, the
+/// source returned by this function is independent, and the samples drawn
+/// using it are reproducible.
+#[inline(always)]
+pub fn seeded(seed: u64) -> Xoshiro256PlusPlus {
+    Xoshiro256PlusPlus::seeded(seed)
+}
+
+fn split(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut value = *state;
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use source::Source;
+
+    use super::Xoshiro256PlusPlus;
+
+    #[test]
+    fn fork() {
+        let mut source = source::seeded(42);
+        let (mut one, mut other) = (source.fork(), source.fork());
+        assert!(one != other);
+
+        let n = 100000;
+        let d = Gaussian::new(0.0, 1.0);
+        let (x, y) = (d.samples(&mut one, n), d.samples(&mut other, n));
+        let correlation = x.iter().zip(&y).fold(0.0, |sum, (x, y)| sum + x * y) / n as f64;
+        assert!(correlation.abs() < 4.0 / (n as f64).sqrt());
+
+        let mut source = source::seeded(42);
+        assert_eq!(source.fork(), Xoshiro256PlusPlus::seeded(source::seeded(42).read_u64()));
+    }
+
+    #[test]
+    fn new() {
+        let mut source = Xoshiro256PlusPlus::new([1, 2, 3, 4]);
+        assert_eq!([source.read_u64(), source.read_u64(), source.read_u64()],
+                   [41943041, 58720359, 3588806011781223]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_zero_state() {
+        let _ = Xoshiro256PlusPlus::new([0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(Xoshiro256PlusPlus::seeded(42),
+                   Xoshiro256PlusPlus::new([13679457532755275413, 2949826092126892291,
+                                            5139283748462763858, 6349198060258255764]));
+
+        let mut source = source::seeded(42);
+        assert_eq!([source.read_u64(), source.read_u64(), source.read_u64()],
+                   [15021278609987233951, 5881210131331364753, 18149643915985481100]);
+
+        let d = Gaussian::new(0.0, 1.0);
+        assert_eq!(d.samples(&mut source::seeded(69), 1000),
+                   d.samples(&mut source::seeded(69), 1000));
+        assert!(d.samples(&mut source::seeded(69), 10) != d.samples(&mut source::seeded(70), 10));
+    }
+}
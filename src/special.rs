@@ -0,0 +1,84 @@
+//! Special functions without the standard library.
+//!
+//! The module stands in for the `special` crate when the `std` feature is
+//! disabled. It provides the same traits, restricted to the functions the
+//! crate relies upon, and evaluates them via `libm`, which is a port of the
+//! MUSL implementations. The complementary error function is accurate to
+//! within one unit in the last place, and the gamma function and its logarithm
+//! to within a few.
+
+use libm;
+
+use float::Float;
+
+/// Beta functions.
+pub trait Beta {
+    /// Compute the natural logarithm of the beta function.
+    fn ln_beta(self, other: Self) -> Self;
+}
+
+/// Error functions.
+pub trait Error {
+    /// Compute the complementary error function.
+    fn erfc(self) -> Self;
+}
+
+/// Gamma functions.
+pub trait Gamma: Sized {
+    /// Compute the real-valued digamma function.
+    fn digamma(self) -> Self;
+
+    /// Compute the gamma function.
+    fn gamma(self) -> Self;
+
+    /// Compute the natural logarithm of the gamma function together with the
+    /// sign of the gamma function.
+    fn ln_gamma(self) -> (Self, i32);
+}
+
+impl Beta for f64 {
+    fn ln_beta(self, other: f64) -> f64 {
+        should!(self > 0.0 && other > 0.0);
+        Gamma::ln_gamma(self).0 + Gamma::ln_gamma(other).0 - Gamma::ln_gamma(self + other).0
+    }
+}
+
+impl Error for f32 {
+    #[inline]
+    fn erfc(self) -> f32 {
+        libm::erfcf(self)
+    }
+}
+
+impl Error for f64 {
+    #[inline]
+    fn erfc(self) -> f64 {
+        libm::erfc(self)
+    }
+}
+
+impl Gamma for f64 {
+    fn digamma(self) -> f64 {
+        let p = self;
+        if p <= 8.0 {
+            return (p + 1.0).digamma() - p.recip();
+        }
+        let q = p.recip();
+        let q2 = q * q;
+        let series = [
+            1.0 / 12.0, -1.0 / 120.0, 1.0 / 252.0, -1.0 / 240.0,
+            5.0 / 660.0, -691.0 / 32760.0, 1.0 / 12.0, -3617.0 / 8160.0,
+        ].iter().rev().fold(0.0, |sum, &c| q2 * sum + c);
+        p.ln() - 0.5 * q - q2 * series
+    }
+
+    #[inline]
+    fn gamma(self) -> f64 {
+        libm::tgamma(self)
+    }
+
+    #[inline]
+    fn ln_gamma(self) -> (f64, i32) {
+        libm::lgamma_r(self)
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Compute the autocorrelation function of `x` at lags from zero up to
+/// `max_lag`.
+///
+/// The autocovariances are normalized by the length of the series rather than
+/// by the number of terms at each lag, and the autocorrelations are the
+/// autocovariances divided by the variance, which yields a positive
+/// semidefinite sequence. The direct method is used, whose cost is
+/// `O(n max_lag)`; see `acf_fft` for long series. The function returns NaNs
+/// for a constant series. It should hold that `max_lag < n`.
+pub fn acf(x: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = x.len();
+    should!(max_lag < n);
+    let centered = center(x);
+    let covariances = (0..(max_lag + 1)).map(|k| {
+        centered[..(n - k)].iter().zip(&centered[k..]).map(|(&a, &b)| a * b).sum::<f64>()
+    }).collect::<Vec<_>>();
+    normalize(covariances)
+}
+
+/// Compute the autocorrelation function of `x` at lags from zero up to
+/// `max_lag` using the fast Fourier transform.
+///
+/// The result is the same as that of `acf` up to rounding, and the cost is
+/// `O(n log n)` regardless of the number of lags.
+pub fn acf_fft(x: &[f64], max_lag: usize) -> Vec<f64> {
+    use math;
+
+    let n = x.len();
+    should!(max_lag < n);
+    let size = (2 * n).next_power_of_two();
+    let mut re = center(x);
+    re.resize(size, 0.0);
+    let mut im = vec![0.0; size];
+    math::fft(&mut re, &mut im, false);
+    for i in 0..size {
+        re[i] = re[i] * re[i] + im[i] * im[i];
+        im[i] = 0.0;
+    }
+    math::fft(&mut re, &mut im, true);
+    re.truncate(max_lag + 1);
+    normalize(re)
+}
+
+/// Compute the partial autocorrelation function of `x` at lags from one up to
+/// `max_lag`.
+///
+/// The partial autocorrelations are obtained from the autocorrelations given
+/// by `acf` using the Durbin–Levinson recursion. It should hold that
+/// `max_lag < n`.
+pub fn pacf(x: &[f64], max_lag: usize) -> Vec<f64> {
+    let rho = acf(x, max_lag);
+    let mut phi = vec![0.0; max_lag + 1];
+    let mut previous = phi.clone();
+    let mut result = Vec::with_capacity(max_lag);
+    for k in 1..(max_lag + 1) {
+        let mut numerator = rho[k];
+        let mut denominator = 1.0;
+        for j in 1..k {
+            numerator -= previous[j] * rho[k - j];
+            denominator -= previous[j] * rho[j];
+        }
+        phi[k] = numerator / denominator;
+        for j in 1..k {
+            phi[j] = previous[j] - phi[k] * previous[k - j];
+        }
+        result.push(phi[k]);
+        previous.copy_from_slice(&phi);
+    }
+    result
+}
+
+// Subtract the mean, which is done exactly for a constant series so that its
+// variance vanishes.
+fn center(data: &[f64]) -> Vec<f64> {
+    if data.iter().all(|&x| x == data[0]) {
+        return vec![0.0; data.len()];
+    }
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    data.iter().map(|&x| x - mean).collect()
+}
+
+fn normalize(mut covariances: Vec<f64>) -> Vec<f64> {
+    let variance = covariances[0];
+    for value in &mut covariances {
+        *value /= variance;
+    }
+    covariances
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    const X: [f64; 20] = [2.1, 3.4, 1.9, 4.8, 5.2, 3.3, 2.7, 4.1, 6.0, 5.5, 3.8, 2.2, 3.9, 4.4,
+                          5.1, 6.3, 4.0, 3.1, 2.5, 3.6];
+
+    #[test]
+    fn acf() {
+        assert::close(&super::acf(&[1.0, 2.0, 3.0, 4.0, 5.0], 4),
+                      &vec![1.0, 0.4, -0.1, -0.4, -0.4], 1e-15);
+        assert::close(&super::acf(&X, 5),
+                      &vec![1.0, 0.31240600323395971679, -0.25528030267351135811,
+                            -0.47148542363302406631, -0.12815271825303379959,
+                            0.27817940627011413052], 1e-14);
+    }
+
+    #[test]
+    fn acf_autoregressive() {
+        let n = 20000;
+        let noise = Gaussian::new(0.0, 1.0).samples(&mut source::seeded(42), n);
+        let mut x = vec![0.0; n];
+        for i in 1..n {
+            x[i] = 0.7 * x[i - 1] + noise[i];
+        }
+        let rho = super::acf(&x, 5);
+        for k in 0..6 {
+            assert::close(rho[k], 0.7f64.powi(k as i32), 0.03);
+        }
+        let phi = super::pacf(&x, 5);
+        assert::close(phi[0], 0.7, 0.03);
+        for k in 1..5 {
+            assert::close(phi[k], 0.0, 0.03);
+        }
+    }
+
+    #[test]
+    fn acf_constant() {
+        assert!(super::acf(&[0.1; 7], 3).iter().all(|x| x.is_nan()));
+        assert!(super::acf_fft(&[0.1; 7], 3).iter().all(|x| x.is_nan()));
+        assert!(super::pacf(&[0.1; 7], 3).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn acf_fft() {
+        assert::close(&super::acf_fft(&X, 19), &super::acf(&X, 19), 1e-14);
+        let x = Gaussian::new(3.0, 2.0).samples(&mut source::seeded(42), 1000);
+        assert::close(&super::acf_fft(&x, 100), &super::acf(&x, 100), 1e-13);
+    }
+
+    #[test]
+    fn acf_white_noise() {
+        let n = 1000;
+        let x = Gaussian::new(0.0, 1.0).samples(&mut source::seeded(42), n);
+        let band = 1.96 / (n as f64).sqrt();
+        let rho = super::acf(&x, 40);
+        let inside = rho[1..].iter().filter(|rho| rho.abs() < band).count();
+        assert!(inside >= 36, "{}", inside);
+    }
+
+    #[test]
+    fn pacf() {
+        assert::close(&super::pacf(&X, 5),
+                      &vec![0.31240600323395971679, -0.39104259770504609868,
+                            -0.31185292767045697499, 0.074542235676905007335,
+                            0.14700712778223093521], 1e-14);
+    }
+}
@@ -0,0 +1,300 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use float::Float;
+
+/// The result of a test of correlation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CorrelationResult {
+    /// The correlation coefficient.
+    pub coefficient: f64,
+    /// The t statistic `r √((n - 2) / (1 - r²))`.
+    pub statistic: f64,
+    /// The number of degrees of freedom, `n - 2`.
+    pub dof: usize,
+    /// The two-sided p-value.
+    pub p_value: f64,
+}
+
+/// Compute the correlation matrix of `columns` using Pearson’s coefficient.
+///
+/// The matrix is stored in the row-major order. It should hold that the
+/// columns have the same length; the function panics otherwise.
+pub fn correlation_matrix(columns: &[&[f64]]) -> Vec<f64> {
+    let k = columns.len();
+    let mut matrix = vec![1.0; k * k];
+    for i in 0..k {
+        for j in 0..i {
+            let value = pearson(columns[i], columns[j]);
+            matrix[i * k + j] = value;
+            matrix[j * k + i] = value;
+        }
+    }
+    matrix
+}
+
+/// Compute the sample covariance of `x` and `y` with denominator `n - 1`.
+///
+/// It should hold that the slices have the same length, which is at least
+/// two; the function panics if the lengths differ.
+pub fn covariance(x: &[f64], y: &[f64]) -> f64 {
+    conform(x, y);
+    should!(x.len() > 1);
+    let (mean_x, mean_y) = (mean(x), mean(y));
+    let sum = x.iter().zip(y).map(|(&x, &y)| (x - mean_x) * (y - mean_y)).sum::<f64>();
+    sum / (x.len() - 1) as f64
+}
+
+/// Compute Kendall’s rank correlation coefficient τ_b of `x` and `y`.
+///
+/// The coefficient accounts for ties in either slice, and it is computed in
+/// `O(n log n)` time using the algorithm of Knight (1966), which counts the
+/// discordant pairs as the inversions performed by a merge sort. It should
+/// hold that the slices have the same length and contain no NaN; the
+/// function panics if the lengths differ.
+pub fn kendall_tau(x: &[f64], y: &[f64]) -> f64 {
+    conform(x, y);
+    should!(x.len() > 1);
+    let n = x.len();
+    let mut pairs = x.iter().cloned().zip(y.iter().cloned()).collect::<Vec<_>>();
+    pairs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let x_ties = ties(&pairs, |a, b| a.0 == b.0);
+    let joint_ties = ties(&pairs, |a, b| a == b);
+    let mut y = pairs.iter().map(|&(_, y)| y).collect::<Vec<_>>();
+    let swaps = inversions(&mut y);
+    let y_ties = ties(&y, |a, b| a == b);
+    let total = (n * (n - 1) / 2) as f64;
+    let (x_ties, y_ties, joint_ties) = (x_ties as f64, y_ties as f64, joint_ties as f64);
+    let score = total - x_ties - y_ties + joint_ties - 2.0 * swaps as f64;
+    score / ((total - x_ties) * (total - y_ties)).sqrt()
+}
+
+/// Compute Pearson’s correlation coefficient of `x` and `y`.
+///
+/// It should hold that the slices have the same length; the function panics
+/// otherwise.
+pub fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    conform(x, y);
+    let (mean_x, mean_y) = (mean(x), mean(y));
+    let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+    for (&x, &y) in x.iter().zip(y) {
+        let (x, y) = (x - mean_x, y - mean_y);
+        xx += x * x;
+        xy += x * y;
+        yy += y * y;
+    }
+    (xy / (xx * yy).sqrt()).max(-1.0).min(1.0)
+}
+
+/// Test whether `x` and `y` are correlated using Pearson’s coefficient.
+///
+/// Under the hypothesis of no correlation between Gaussian variables, the
+/// statistic follows Student’s t distribution with `n - 2` degrees of
+/// freedom. It should hold that `n > 2`.
+pub fn pearson_test(x: &[f64], y: &[f64]) -> CorrelationResult {
+    test(pearson(x, y), x.len())
+}
+
+/// Compute Spearman’s rank correlation coefficient of `x` and `y`.
+///
+/// The coefficient is Pearson’s coefficient of the ranks, and tied values
+/// receive the average of the ranks they span. It should hold that the
+/// slices have the same length and contain no NaN; the function panics if
+/// the lengths differ.
+pub fn spearman(x: &[f64], y: &[f64]) -> f64 {
+    conform(x, y);
+    pearson(&rank(x), &rank(y))
+}
+
+/// Test whether `x` and `y` are correlated using Spearman’s coefficient.
+///
+/// The p-value is computed using the same t approximation as in
+/// `pearson_test`, which is adequate for moderate `n`. It should hold that
+/// `n > 2`.
+pub fn spearman_test(x: &[f64], y: &[f64]) -> CorrelationResult {
+    test(spearman(x, y), x.len())
+}
+
+fn conform(x: &[f64], y: &[f64]) {
+    assert!(x.len() == y.len(),
+            "the slices should have the same length (got {} and {})", x.len(), y.len());
+}
+
+// Count the inversions in `data` while sorting it using a merge sort.
+fn inversions(data: &mut [f64]) -> u64 {
+    let n = data.len();
+    let mut buffer = data.to_vec();
+    let mut count = 0;
+    let mut width = 1;
+    while width < n {
+        let mut start = 0;
+        while start < n {
+            let middle = (start + width).min(n);
+            let end = (start + 2 * width).min(n);
+            let (mut i, mut j, mut k) = (start, middle, start);
+            while i < middle && j < end {
+                if data[j] < data[i] {
+                    buffer[k] = data[j];
+                    count += (middle - i) as u64;
+                    j += 1;
+                } else {
+                    buffer[k] = data[i];
+                    i += 1;
+                }
+                k += 1;
+            }
+            buffer[k..(k + middle - i)].copy_from_slice(&data[i..middle]);
+            k += middle - i;
+            buffer[k..(k + end - j)].copy_from_slice(&data[j..end]);
+            start = end;
+        }
+        data.copy_from_slice(&buffer);
+        width *= 2;
+    }
+    count
+}
+
+fn mean(data: &[f64]) -> f64 {
+    data.iter().sum::<f64>() / data.len() as f64
+}
+
+// Compute the ranks with ties receiving the average of the ranks they span.
+fn rank(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| data[i].partial_cmp(&data[j]).unwrap());
+    let mut ranks = vec![0.0; n];
+    let mut i = 0;
+    while i < n {
+        let mut j = i + 1;
+        while j < n && data[order[j]] == data[order[i]] {
+            j += 1;
+        }
+        let rank = (i + j + 1) as f64 / 2.0;
+        for &k in &order[i..j] {
+            ranks[k] = rank;
+        }
+        i = j;
+    }
+    ranks
+}
+
+fn test(coefficient: f64, n: usize) -> CorrelationResult {
+    use math;
+
+    should!(n > 2);
+    let dof = n - 2;
+    let statistic = coefficient * (dof as f64 / (1.0 - coefficient * coefficient)).sqrt();
+    CorrelationResult {
+        coefficient: coefficient,
+        statistic: statistic,
+        dof: dof,
+        p_value: 2.0 * math::student(statistic.abs(), dof as f64),
+    }
+}
+
+// Count the pairs within the runs of equal elements of sorted `data` in the
+// sense of `equal`.
+fn ties<T, F>(data: &[T], equal: F) -> u64 where F: Fn(&T, &T) -> bool {
+    let (mut count, mut i) = (0, 0);
+    while i < data.len() {
+        let mut j = i + 1;
+        while j < data.len() && equal(&data[j], &data[i]) {
+            j += 1;
+        }
+        let t = (j - i) as u64;
+        count += t * (t - 1) / 2;
+        i = j;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    const X: [f64; 20] = [2.1, 3.4, 1.9, 5.6, 4.2, 3.3, 2.8, 4.9, 3.4, 6.1, 2.2, 4.2, 5.0, 1.7, 3.9,
+                          4.2, 2.6, 5.3, 3.1, 4.4];
+    const Y: [f64; 20] = [1.2, 2.9, 1.5, 4.1, 3.3, 2.2, 2.9, 3.8, 3.1, 4.0, 1.1, 3.6, 2.9, 1.9, 3.0,
+                          3.7, 2.0, 4.6, 2.9, 3.2];
+
+    #[test]
+    fn correlation_matrix() {
+        use super::correlation_matrix;
+
+        let z = X.iter().zip(&Y).map(|(&x, &y)| 0.5 * x - y).collect::<Vec<_>>();
+        let matrix = correlation_matrix(&[&X, &Y, &z]);
+        let (a, b, c) = (0.88550897013639993, -0.44565292463796276, -0.81056223495143524);
+        assert::close(&matrix, &vec![1.0, a, b, a, 1.0, c, b, c, 1.0], 1e-14);
+    }
+
+    #[test]
+    fn covariance() {
+        use super::covariance;
+
+        assert::close(covariance(&X, &Y), 1.1079736842105262, 1e-14);
+        assert_eq!(covariance(&[1.0, 2.0, 3.0], &[6.0, 4.0, 2.0]), -2.0);
+    }
+
+    #[test]
+    fn kendall_tau() {
+        use super::kendall_tau;
+
+        assert::close(kendall_tau(&[1.0, 2.0, 2.0, 3.0], &[1.0, 3.0, 2.0, 3.0]), 0.8, 1e-15);
+        assert_eq!(kendall_tau(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]), -1.0);
+        assert::close(kendall_tau(&X, &Y), 0.73514587510949797, 1e-14);
+
+        let x = (0..1000).map(|i| ((i * 7919) % 1009) as f64).collect::<Vec<_>>();
+        let y = (0..1000).map(|i| ((i * 104729) % 97) as f64).collect::<Vec<_>>();
+        let (mut concordant, mut discordant, mut x_ties, mut y_ties) = (0, 0, 0, 0);
+        for i in 0..1000 {
+            for j in (i + 1)..1000 {
+                let (dx, dy) = (x[i] - x[j], y[i] - y[j]);
+                if dx == 0.0 && dy == 0.0 {
+                } else if dx == 0.0 {
+                    x_ties += 1;
+                } else if dy == 0.0 {
+                    y_ties += 1;
+                } else if dx * dy > 0.0 {
+                    concordant += 1;
+                } else {
+                    discordant += 1;
+                }
+            }
+        }
+        let x_pairs = concordant + discordant + x_ties;
+        let y_pairs = concordant + discordant + y_ties;
+        let expected = (concordant - discordant) as f64 / (x_pairs as f64 * y_pairs as f64).sqrt();
+        assert::close(kendall_tau(&x, &y), expected, 1e-14);
+    }
+
+    #[test]
+    #[should_panic(expected = "the slices should have the same length (got 3 and 2)")]
+    fn mismatch() {
+        super::pearson(&[1.0, 2.0, 3.0], &[1.0, 2.0]);
+    }
+
+    #[test]
+    fn pearson() {
+        use super::{pearson, pearson_test};
+
+        assert::close(pearson(&[1.0, 2.0, 2.0, 3.0], &[1.0, 3.0, 2.0, 3.0]), 2.0 / 5.5f64.sqrt(),
+                      1e-15);
+        let result = pearson_test(&X, &Y);
+        assert::close(result.coefficient, 0.88550897013639993, 1e-14);
+        assert::close(result.statistic, 8.0859152494992603, 1e-12);
+        assert_eq!(result.dof, 18);
+        assert::close(result.p_value, 2.1041577594980458e-7, 1e-18);
+    }
+
+    #[test]
+    fn spearman() {
+        use super::{spearman, spearman_test};
+
+        assert::close(spearman(&[1.0, 2.0, 2.0, 3.0], &[1.0, 3.0, 2.0, 3.0]), 3.75 / 4.5, 1e-15);
+        let result = spearman_test(&X, &Y);
+        assert::close(result.coefficient, 0.89489762918662501, 1e-14);
+        assert::close(result.statistic, 8.5076668981225612, 1e-12);
+        assert::close(result.p_value, 1.0093189852422638e-7, 1e-18);
+    }
+}
@@ -0,0 +1,301 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use distribution::{Empirical, ModeList, Modes};
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// A method for computing sample quantiles.
+///
+/// Given a probability `p` and `n` sorted observations `x[0], ..., x[n - 1]`,
+/// the quantile is computed by interpolating linearly between the
+/// observations adjacent to a fractional position `h`, which is clamped to
+/// `[0, n - 1]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuantileMethod {
+    /// Take `h = (n - 1) p`, which is type 7 in R and the default there.
+    Linear,
+    /// Take `h = n p - 1 / 2`, which is type 5 in R.
+    Hazen,
+}
+
+/// Compute the sample excess kurtosis adjusted for the bias, that is,
+/// `((n + 1) g₂ + 6) (n - 1) / ((n - 2) (n - 3))`, where `g₂` is computed by
+/// `kurtosis`.
+///
+/// It should hold that `n > 3`.
+pub fn adjusted_kurtosis(data: &[f64]) -> f64 {
+    let n = data.len() as f64;
+    should!(n > 3.0);
+    ((n + 1.0) * kurtosis(data) + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0))
+}
+
+/// Compute the sample skewness adjusted for the bias, that is,
+/// `g₁ √(n (n - 1)) / (n - 2)`, where `g₁` is computed by `skewness`.
+///
+/// It should hold that `n > 2`.
+pub fn adjusted_skewness(data: &[f64]) -> f64 {
+    let n = data.len() as f64;
+    should!(n > 2.0);
+    skewness(data) * (n * (n - 1.0)).sqrt() / (n - 2.0)
+}
+
+/// Compute the highest-posterior-density interval at level `level` from
+/// samples of a distribution, such as the output of a Markov chain Monte
+/// Carlo method.
+///
+/// The interval is the narrowest one spanning `⌈level n⌉` of the sorted
+/// samples; ties are resolved in favor of the leftmost interval. It should
+/// hold that `0 < level <= 1`.
+pub fn hpd_from_samples(samples: &[f64], level: f64) -> (f64, f64) {
+    should!(0.0 < level && level <= 1.0);
+    let data = sort(samples);
+    let n = data.len();
+    let m = ((level * n as f64).ceil() as usize).max(1).min(n);
+    let mut best = 0;
+    for i in 1..(n - m + 1) {
+        if data[i + m - 1] - data[i] < data[best + m - 1] - data[best] {
+            best = i;
+        }
+    }
+    (data[best], data[best + m - 1])
+}
+
+/// Compute the interquartile range using linear interpolation.
+pub fn iqr(data: &[f64]) -> f64 {
+    let data = sort(data);
+    interpolate(&data, 0.75, QuantileMethod::Linear) -
+        interpolate(&data, 0.25, QuantileMethod::Linear)
+}
+
+/// Compute the sample excess kurtosis `g₂ = m₄ / m₂² - 3`, where `mₖ` are
+/// the central moments with denominator `n`.
+pub fn kurtosis(data: &[f64]) -> f64 {
+    let (m2, _, m4) = moments(data);
+    m4 / (m2 * m2) - 3.0
+}
+
+/// Compute the maximum.
+pub fn max(data: &[f64]) -> f64 {
+    check(data);
+    data.iter().fold(::std::f64::NEG_INFINITY, |max, &x| max.max(x))
+}
+
+/// Compute the mean.
+///
+/// The sum is computed using compensated summation.
+pub fn mean(data: &[f64]) -> f64 {
+    use math;
+
+    check(data);
+    math::sum(data.iter().cloned()) / data.len() as f64
+}
+
+/// Compute the median using linear interpolation.
+pub fn median(data: &[f64]) -> f64 {
+    quantile(data, 0.5, QuantileMethod::Linear)
+}
+
+/// Compute the minimum.
+pub fn min(data: &[f64]) -> f64 {
+    check(data);
+    data.iter().fold(::std::f64::INFINITY, |min, &x| min.min(x))
+}
+
+/// Compute the modes of discrete-valued data, that is, the most frequent
+/// values in ascending order.
+///
+/// It should hold that `data` are finite.
+pub fn mode(data: &[f64]) -> ModeList<f64> {
+    check(data);
+    Empirical::new(data).modes()
+}
+
+/// Compute the population variance, that is, the variance with denominator
+/// `n`.
+///
+/// The variance is computed in two passes with a correction for the rounding
+/// error of the mean, which keeps it accurate when the mean is large compared
+/// with the spread.
+pub fn population_variance(data: &[f64]) -> f64 {
+    squares(data) / data.len() as f64
+}
+
+/// Compute the quantile of order `p` using `method`.
+///
+/// It should hold that `0 <= p <= 1`.
+pub fn quantile(data: &[f64], p: f64, method: QuantileMethod) -> f64 {
+    interpolate(&sort(data), p, method)
+}
+
+/// Compute the sample standard deviation, that is, the square root of
+/// `variance`.
+#[inline]
+pub fn sd(data: &[f64]) -> f64 {
+    variance(data).sqrt()
+}
+
+/// Compute the sample skewness `g₁ = m₃ / m₂^(3/2)`, where `mₖ` are the
+/// central moments with denominator `n`.
+pub fn skewness(data: &[f64]) -> f64 {
+    let (m2, m3, _) = moments(data);
+    m3 / m2.powf(1.5)
+}
+
+/// Compute the sample variance, that is, the unbiased estimate of the
+/// variance with denominator `n - 1`.
+///
+/// See `population_variance` for the computation. It should hold that
+/// `n > 1`.
+pub fn variance(data: &[f64]) -> f64 {
+    should!(data.len() > 1);
+    squares(data) / (data.len() - 1) as f64
+}
+
+fn check(data: &[f64]) {
+    assert!(!data.is_empty(), "the data should not be empty");
+}
+
+fn interpolate(data: &[f64], p: f64, method: QuantileMethod) -> f64 {
+    should!(0.0 <= p && p <= 1.0);
+    let n = data.len();
+    let h = match method {
+        QuantileMethod::Linear => (n - 1) as f64 * p,
+        QuantileMethod::Hazen => n as f64 * p - 0.5,
+    };
+    let h = h.max(0.0).min((n - 1) as f64);
+    let i = h.floor() as usize;
+    if i + 1 < n {
+        data[i] + (h - i as f64) * (data[i + 1] - data[i])
+    } else {
+        data[i]
+    }
+}
+
+// Compute the central moments of orders two, three, and four with
+// denominator `n`.
+fn moments(data: &[f64]) -> (f64, f64, f64) {
+    let mean = mean(data);
+    let n = data.len() as f64;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for &x in data {
+        let d = x - mean;
+        let d2 = d * d;
+        m2 += d2;
+        m3 += d2 * d;
+        m4 += d2 * d2;
+    }
+    (m2 / n, m3 / n, m4 / n)
+}
+
+fn sort(data: &[f64]) -> Vec<f64> {
+    check(data);
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    data
+}
+
+// Compute the sum of squared deviations from the mean corrected for the
+// rounding error of the mean.
+fn squares(data: &[f64]) -> f64 {
+    let mean = mean(data);
+    let (mut sum, mut compensation) = (0.0, 0.0);
+    for &x in data {
+        let d = x - mean;
+        sum += d * d;
+        compensation += d;
+    }
+    sum - compensation * compensation / data.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::QuantileMethod::{Hazen, Linear};
+
+    const DATA: [f64; 8] = [2.3, 7.1, 4.4, 9.8, 1.2, 5.5, 3.3, 6.0];
+
+    #[test]
+    #[should_panic(expected = "the data should not be empty")]
+    fn empty() {
+        super::quantile(&[], 0.5, Linear);
+    }
+
+    #[test]
+    fn extrema() {
+        assert_eq!(super::min(&DATA), 1.2);
+        assert_eq!(super::max(&DATA), 9.8);
+    }
+
+    #[test]
+    fn hpd_from_samples() {
+        use prelude::*;
+        use super::hpd_from_samples;
+
+        assert_eq!(hpd_from_samples(&DATA, 0.25), (5.5, 6.0));
+        assert_eq!(hpd_from_samples(&DATA, 1.0), (1.2, 9.8));
+        assert_eq!(hpd_from_samples(&[7.0], 0.9), (7.0, 7.0));
+
+        let d = Gamma::new(2.0, 1.0);
+        let samples = d.samples(&mut source::seeded(42), 100000);
+        let (lower, upper) = hpd_from_samples(&samples, 0.95);
+        let (a, b) = d.hpd_interval(0.95);
+        assert::close(lower, a, 0.02);
+        assert::close(upper, b, 0.1);
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert::close(super::kurtosis(&DATA), -0.69241725589911962, 1e-14);
+        assert::close(super::adjusted_kurtosis(&DATA), -0.054076237388151195, 1e-14);
+    }
+
+    #[test]
+    fn mean() {
+        assert::close(super::mean(&DATA), 4.95, 1e-15);
+        assert_eq!(super::mean(&[1e16, 1.0, -1e16, 1.0]), 0.5);
+    }
+
+    #[test]
+    fn mode() {
+        assert_eq!(&*super::mode(&[3.0, 1.0, 2.0, 3.0, 1.0]), &[1.0, 3.0]);
+        assert_eq!(&*super::mode(&[2.0, 2.0, 5.0]), &[2.0]);
+    }
+
+    #[test]
+    fn quantile() {
+        use super::{iqr, median, quantile};
+
+        let p = [0.0, 0.1, 0.25, 0.5, 0.9, 1.0];
+        let x = p.iter().map(|&p| quantile(&DATA, p, Linear)).collect::<Vec<_>>();
+        assert::close(&x, &vec![1.2, 1.97, 3.05, 4.95, 7.91, 9.8], 1e-14);
+        let x = p.iter().map(|&p| quantile(&DATA, p, Hazen)).collect::<Vec<_>>();
+        assert::close(&x, &vec![1.2, 1.53, 2.8, 4.95, 8.99, 9.8], 1e-14);
+
+        assert::close(median(&DATA), 4.95, 1e-15);
+        assert::close(iqr(&DATA), 3.225, 1e-14);
+        assert_eq!(quantile(&[7.0], 0.3, Hazen), 7.0);
+    }
+
+    #[test]
+    fn skewness() {
+        assert::close(super::skewness(&DATA), 0.35506130342340983, 1e-14);
+        assert::close(super::adjusted_skewness(&DATA), 0.44283924957059504, 1e-14);
+    }
+
+    #[test]
+    fn variance() {
+        use super::{population_variance, sd, variance};
+
+        assert::close(variance(&DATA), 7.6657142857142867, 1e-14);
+        assert::close(population_variance(&DATA), 6.7075000000000009, 1e-14);
+        assert::close(sd(&DATA), 2.7687026358412502, 1e-14);
+
+        let data = [1e15 + 4.0, 1e15 + 7.0, 1e15 + 13.0, 1e15 + 16.0];
+        assert_eq!(variance(&data), 30.0);
+        assert_eq!(population_variance(&data), 22.5);
+        let data = DATA.iter().map(|&x| 1e9 + x).collect::<Vec<_>>();
+        assert::close(variance(&data), 7.6657142857142867, 1e-6);
+    }
+}
@@ -0,0 +1,40 @@
+//! Sample statistics.
+//!
+//! The functions panic when given an empty slice.
+//!
+//! ```
+//! use probability::statistics::{self, QuantileMethod};
+//!
+//! let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+//! assert_eq!(statistics::mean(&data), 5.0);
+//! assert_eq!(statistics::population_variance(&data), 4.0);
+//! assert_eq!(statistics::quantile(&data, 0.5, QuantileMethod::Linear), 4.5);
+//! ```
+
+#[cfg(feature = "alloc")]
+mod autocorrelation;
+#[cfg(feature = "alloc")]
+mod correlation;
+#[cfg(feature = "alloc")]
+mod descriptive;
+mod online;
+#[cfg(feature = "alloc")]
+mod sketch;
+
+#[cfg(feature = "alloc")]
+pub use self::autocorrelation::{acf, acf_fft, pacf};
+#[cfg(feature = "alloc")]
+pub use self::correlation::{CorrelationResult, correlation_matrix, covariance, kendall_tau};
+#[cfg(feature = "alloc")]
+pub use self::correlation::{pearson, pearson_test, spearman, spearman_test};
+#[cfg(feature = "alloc")]
+pub use self::descriptive::{QuantileMethod, adjusted_kurtosis, adjusted_skewness, hpd_from_samples};
+#[cfg(feature = "alloc")]
+pub use self::descriptive::{iqr, kurtosis};
+#[cfg(feature = "alloc")]
+pub use self::descriptive::{max, mean, median, min, mode, population_variance, quantile, sd};
+#[cfg(feature = "alloc")]
+pub use self::descriptive::{skewness, variance};
+pub use self::online::{OnlineStats, WeightedOnlineStats};
+#[cfg(feature = "alloc")]
+pub use self::sketch::OnlineQuantile;
@@ -0,0 +1,419 @@
+#[cfg(not(feature = "std"))]
+use float::{Float, Real};
+
+/// An accumulator of the statistics of a stream of data.
+///
+/// The first four central moments are updated using the formulas of Welford
+/// and Chan et al. generalized to higher orders by Pébay (2008), which avoid
+/// the cancellation that plagues accumulating the powers of the data. Two
+/// accumulators can be merged, which allows reducing a stream in parallel,
+/// and merging gives the same result as pushing all the data into one
+/// accumulator up to rounding. The statistics of an empty accumulator are
+/// NaN.
+///
+/// ```
+/// use probability::statistics::OnlineStats;
+///
+/// let mut left = OnlineStats::from_slice(&[2.0, 4.0, 4.0, 4.0]);
+/// let right = OnlineStats::from_slice(&[5.0, 5.0, 7.0, 9.0]);
+/// left.merge(&right);
+/// assert_eq!(left.mean(), 5.0);
+/// assert_eq!(left.population_variance(), 4.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OnlineStats {
+    moments: Moments,
+    count: u64,
+}
+
+/// An accumulator of the statistics of a stream of weighted data.
+///
+/// The weights are treated as reliability weights, which affects only the
+/// correction of the sample variance for the bias. See `OnlineStats` for the
+/// algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedOnlineStats {
+    moments: Moments,
+    count: u64,
+    squares: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Moments {
+    weight: f64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    max: f64,
+}
+
+impl OnlineStats {
+    /// Create an empty accumulator.
+    #[inline]
+    pub fn new() -> Self {
+        OnlineStats { moments: Moments::new(), count: 0 }
+    }
+
+    /// Create an accumulator of `data`.
+    pub fn from_slice(data: &[f64]) -> Self {
+        let mut stats = OnlineStats::new();
+        for &x in data {
+            stats.push(x);
+        }
+        stats
+    }
+
+    /// Return the number of data.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Compute the sample excess kurtosis with denominator `n`.
+    #[inline]
+    pub fn kurtosis(&self) -> f64 {
+        self.moments.kurtosis()
+    }
+
+    /// Return the maximum.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.moments.defined(self.moments.max)
+    }
+
+    /// Compute the mean.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.moments.defined(self.moments.mean)
+    }
+
+    /// Merge the statistics of another stream.
+    pub fn merge(&mut self, other: &Self) {
+        self.moments.merge(&other.moments);
+        self.count += other.count;
+    }
+
+    /// Return the minimum.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.moments.defined(self.moments.min)
+    }
+
+    /// Compute the population variance, that is, the variance with
+    /// denominator `n`.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        self.moments.m2 / self.moments.weight
+    }
+
+    /// Push a datum.
+    #[inline]
+    pub fn push(&mut self, x: f64) {
+        self.moments.merge(&Moments::one(x, 1.0));
+        self.count += 1;
+    }
+
+    /// Compute the sample standard deviation.
+    #[inline]
+    pub fn sd(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Compute the sample skewness with denominator `n`.
+    #[inline]
+    pub fn skewness(&self) -> f64 {
+        self.moments.skewness()
+    }
+
+    /// Compute the sample variance with denominator `n - 1`.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.moments.defined(self.moments.m2 / (self.moments.weight - 1.0))
+    }
+}
+
+impl Default for OnlineStats {
+    #[inline]
+    fn default() -> Self {
+        OnlineStats::new()
+    }
+}
+
+impl WeightedOnlineStats {
+    /// Create an empty accumulator.
+    #[inline]
+    pub fn new() -> Self {
+        WeightedOnlineStats { moments: Moments::new(), count: 0, squares: 0.0 }
+    }
+
+    /// Create an accumulator of `data` given as pairs of values and weights.
+    pub fn from_slice(data: &[(f64, f64)]) -> Self {
+        let mut stats = WeightedOnlineStats::new();
+        for &(x, w) in data {
+            stats.push(x, w);
+        }
+        stats
+    }
+
+    /// Return the number of data.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Compute the sample excess kurtosis with the total weight as the
+    /// denominator.
+    #[inline]
+    pub fn kurtosis(&self) -> f64 {
+        self.moments.kurtosis()
+    }
+
+    /// Return the maximum.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.moments.defined(self.moments.max)
+    }
+
+    /// Compute the weighted mean.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.moments.defined(self.moments.mean)
+    }
+
+    /// Merge the statistics of another stream.
+    pub fn merge(&mut self, other: &Self) {
+        self.moments.merge(&other.moments);
+        self.count += other.count;
+        self.squares += other.squares;
+    }
+
+    /// Return the minimum.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.moments.defined(self.moments.min)
+    }
+
+    /// Compute the population variance, that is, the variance with the total
+    /// weight as the denominator.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        self.moments.m2 / self.moments.weight
+    }
+
+    /// Push a datum `x` with weight `w`.
+    ///
+    /// It should hold that `w > 0`.
+    #[inline]
+    pub fn push(&mut self, x: f64, w: f64) {
+        should!(w > 0.0);
+        self.moments.merge(&Moments::one(x, w));
+        self.count += 1;
+        self.squares += w * w;
+    }
+
+    /// Compute the sample standard deviation.
+    #[inline]
+    pub fn sd(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Compute the sample skewness with the total weight as the denominator.
+    #[inline]
+    pub fn skewness(&self) -> f64 {
+        self.moments.skewness()
+    }
+
+    /// Return the total weight.
+    #[inline]
+    pub fn total_weight(&self) -> f64 {
+        self.moments.weight
+    }
+
+    /// Compute the sample variance with denominator `W - Σw² / W`, where `W`
+    /// is the total weight, which reduces to `n - 1` for unit weights.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        let weight = self.moments.weight;
+        self.moments.m2 / (weight - self.squares / weight)
+    }
+}
+
+impl Default for WeightedOnlineStats {
+    #[inline]
+    fn default() -> Self {
+        WeightedOnlineStats::new()
+    }
+}
+
+impl Moments {
+    #[inline]
+    fn new() -> Self {
+        Moments {
+            weight: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            min: ::std::f64::INFINITY,
+            max: ::std::f64::NEG_INFINITY,
+        }
+    }
+
+    #[inline]
+    fn one(x: f64, w: f64) -> Self {
+        Moments { weight: w, mean: x, m2: 0.0, m3: 0.0, m4: 0.0, min: x, max: x }
+    }
+
+    #[inline]
+    fn defined(&self, value: f64) -> f64 {
+        if self.weight > 0.0 { value } else { ::std::f64::NAN }
+    }
+
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.weight * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.weight == 0.0 {
+            return;
+        }
+        if self.weight == 0.0 {
+            *self = *other;
+            return;
+        }
+        let (a, b) = (self.weight, other.weight);
+        let weight = a + b;
+        let delta = other.mean - self.mean;
+        let delta_weight = delta / weight;
+        let delta_weight2 = delta_weight * delta_weight;
+        let term = delta * delta_weight * a * b;
+        let m2 = self.m2 + other.m2 + term;
+        let m3 = self.m3 + other.m3 + term * delta_weight * (a - b) +
+                 3.0 * delta_weight * (a * other.m2 - b * self.m2);
+        let m4 = self.m4 + other.m4 + term * delta_weight2 * (a * a - a * b + b * b) +
+                 6.0 * delta_weight2 * (a * a * other.m2 + b * b * self.m2) +
+                 4.0 * delta_weight * (a * other.m3 - b * self.m3);
+        self.mean += delta_weight * b;
+        self.weight = weight;
+        self.m2 = m2;
+        self.m3 = m3;
+        self.m4 = m4;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.weight.sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::{OnlineStats, WeightedOnlineStats};
+
+    const DATA: [f64; 8] = [2.3, 7.1, 4.4, 9.8, 1.2, 5.5, 3.3, 6.0];
+
+    fn stream(seed: u64, n: usize) -> Vec<f64> {
+        let mut source = source::seeded(seed);
+        let d = Gaussian::new(0.0, 1e-3);
+        (0..n).map(|i| 1e9 * (1 + i % 3) as f64 + d.sample(&mut source)).collect()
+    }
+
+    fn statistics(stats: &OnlineStats) -> Vec<f64> {
+        vec![stats.mean(), stats.variance(), stats.skewness(), stats.kurtosis()]
+    }
+
+    #[test]
+    fn empty() {
+        let stats = OnlineStats::new();
+        assert_eq!(stats.count(), 0);
+        assert!(statistics(&stats).iter().all(|x| x.is_nan()));
+        assert!(stats.min().is_nan() && stats.max().is_nan());
+
+        let mut other = OnlineStats::from_slice(&DATA);
+        other.merge(&stats);
+        assert_eq!(other, OnlineStats::from_slice(&DATA));
+        let mut stats = stats;
+        stats.merge(&other);
+        assert_eq!(stats, other);
+    }
+
+    #[test]
+    fn from_slice() {
+        use statistics;
+
+        let stats = OnlineStats::from_slice(&DATA);
+        assert_eq!(stats.count(), 8);
+        assert::close(&statistics(&stats), &vec![statistics::mean(&DATA),
+                                                 statistics::variance(&DATA),
+                                                 statistics::skewness(&DATA),
+                                                 statistics::kurtosis(&DATA)], 1e-14);
+        assert::close(stats.population_variance(), statistics::population_variance(&DATA),
+                      1e-14);
+        assert::close(stats.sd(), statistics::sd(&DATA), 1e-14);
+        assert_eq!((stats.min(), stats.max()), (1.2, 9.8));
+    }
+
+    #[test]
+    fn merge() {
+        use statistics;
+
+        let x = stream(42, 1000);
+        let whole = OnlineStats::from_slice(&x);
+        let expected = statistics(&whole);
+        for &k in &[1, 10, 333, 500, 999] {
+            let mut left = OnlineStats::from_slice(&x[..k]);
+            left.merge(&OnlineStats::from_slice(&x[k..]));
+            assert_eq!(left.count(), 1000);
+            assert_eq!((left.min(), left.max()), (whole.min(), whole.max()));
+            let actual = statistics(&left);
+            for (&actual, &expected) in actual.iter().zip(&expected) {
+                assert::close(actual, expected, 1e-12 * expected.abs());
+            }
+        }
+        assert::close(whole.variance(), statistics::variance(&x), 1e-12 * whole.variance());
+        assert::close(whole.kurtosis(), statistics::kurtosis(&x), 1e-9);
+    }
+
+    #[test]
+    fn variance() {
+        for seed in 0..10 {
+            let mut stats = OnlineStats::new();
+            for x in stream(seed, 200) {
+                stats.push(x);
+                assert!(!(stats.population_variance() < 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        let data = [(2.3, 1.0), (7.1, 3.0), (4.4, 2.0), (9.8, 1.0)];
+        let stats = WeightedOnlineStats::from_slice(&data);
+        let repeated = data.iter().flat_map(|&(x, w)| (0..w as usize).map(move |_| x))
+                                  .collect::<Vec<_>>();
+        let expected = OnlineStats::from_slice(&repeated);
+        assert_eq!((stats.count(), stats.total_weight()), (4, 7.0));
+        assert::close(&[stats.mean(), stats.population_variance(), stats.skewness(),
+                        stats.kurtosis()],
+                      &[expected.mean(), expected.population_variance(), expected.skewness(),
+                        expected.kurtosis()], 1e-14);
+        assert_eq!((stats.min(), stats.max()), (2.3, 9.8));
+
+        let mut left = WeightedOnlineStats::from_slice(&data[..1]);
+        left.merge(&WeightedOnlineStats::from_slice(&data[1..]));
+        assert::close(left.variance(), stats.variance(), 1e-14);
+
+        let unit = DATA.iter().map(|&x| (x, 1.0)).collect::<Vec<_>>();
+        let stats = WeightedOnlineStats::from_slice(&unit);
+        assert::close(stats.variance(), OnlineStats::from_slice(&DATA).variance(), 1e-14);
+        assert::close(stats.sd(), OnlineStats::from_slice(&DATA).sd(), 1e-14);
+    }
+}
@@ -0,0 +1,261 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use float::Real;
+
+/// A streaming estimator of quantiles with bounded memory.
+///
+/// The estimator maintains a Greenwald–Khanna sketch, which stores a subset
+/// of the data along with bounds on their ranks. Given `n` data, the rank of
+/// the estimate of the quantile of order `p` among the data deviates from
+/// `⌈p n⌉` by at most `ε n`, where `ε` is the error specified upon creation.
+/// The sketch is compressed after each batch, and it typically stores a small
+/// multiple of `1 / ε` values; for instance, fewer than a thousand for
+/// `ε = 0.001` and a million data. The data are buffered and sorted in
+/// batches of about `1 / (2ε)` before being added to the sketch.
+///
+/// Two estimators can be merged, which allows summarizing a stream in
+/// parallel; the error of the result is the larger of the two errors.
+///
+/// ```
+/// use probability::prelude::*;
+/// use probability::statistics::OnlineQuantile;
+///
+/// let mut estimator = OnlineQuantile::new(0.001);
+/// for x in Uniform::new(0.0, 1.0).samples(&mut source::seeded(42), 10000) {
+///     estimator.push(x);
+/// }
+/// assert!((estimator.quantile(0.9) - 0.9).abs() < 0.02);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnlineQuantile {
+    epsilon: f64,
+    count: u64,
+    tuples: Vec<Tuple>,
+    buffer: Vec<f64>,
+}
+
+// A stored value along with the difference between its smallest possible
+// rank and that of the preceding value, `g`, and the difference between its
+// largest and smallest possible ranks, `delta`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tuple {
+    value: f64,
+    g: u64,
+    delta: u64,
+}
+
+impl OnlineQuantile {
+    /// Create an estimator with error `epsilon`.
+    ///
+    /// It should hold that `0 < epsilon < 1`.
+    pub fn new(epsilon: f64) -> Self {
+        should!(0.0 < epsilon && epsilon < 1.0);
+        OnlineQuantile { epsilon: epsilon, count: 0, tuples: vec![], buffer: vec![] }
+    }
+
+    /// Return the number of data.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Return the error.
+    #[inline]
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Merge the sketch of another stream.
+    pub fn merge(&mut self, other: &Self) {
+        let mut other = other.clone();
+        other.flush();
+        self.flush();
+        self.epsilon = self.epsilon.max(other.epsilon);
+        self.count += other.count;
+        self.combine(&other.tuples);
+    }
+
+    /// Push a datum.
+    ///
+    /// It should hold that `x` is not NaN.
+    pub fn push(&mut self, x: f64) {
+        should!(!x.is_nan());
+        self.buffer.push(x);
+        self.count += 1;
+        if self.buffer.len() as f64 >= 0.5 / self.epsilon {
+            self.flush();
+        }
+    }
+
+    /// Estimate the quantile of order `p`.
+    ///
+    /// The estimate is NaN if there are no data. It should hold that
+    /// `0 <= p <= 1`.
+    pub fn quantile(&self, p: f64) -> f64 {
+        should!(0.0 <= p && p <= 1.0);
+        if !self.buffer.is_empty() {
+            let mut estimator = self.clone();
+            estimator.flush();
+            return estimator.quantile(p);
+        }
+        if self.tuples.is_empty() {
+            return ::std::f64::NAN;
+        }
+        let n = self.count as f64;
+        let rank = (p * n).ceil().max(1.0);
+        let bound = self.epsilon * n;
+        let mut rmin = 0;
+        for tuple in &self.tuples {
+            rmin += tuple.g;
+            if rank - rmin as f64 <= bound && (rmin + tuple.delta) as f64 - rank <= bound {
+                return tuple.value;
+            }
+        }
+        self.tuples[self.tuples.len() - 1].value
+    }
+
+    fn combine(&mut self, other: &[Tuple]) {
+        let left = ranks(&self.tuples);
+        let right = ranks(other);
+        let (n, m) = (left.len(), right.len());
+        let (total_left, total_right) = (total(&left), total(&right));
+        let mut result = Vec::with_capacity(n + m);
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            let (value, rmin, rmax) = if j == m || (i < n && left[i].0 <= right[j].0) {
+                let (before, after) = neighbors(&right, j, total_right);
+                i += 1;
+                (left[i - 1].0, left[i - 1].1 + before, left[i - 1].2 + after)
+            } else {
+                let (before, after) = neighbors(&left, i, total_left);
+                j += 1;
+                (right[j - 1].0, right[j - 1].1 + before, right[j - 1].2 + after)
+            };
+            result.push((value, rmin, rmax));
+        }
+        let mut previous = 0;
+        self.tuples = result.into_iter().map(|(value, rmin, rmax)| {
+            let tuple = Tuple { value: value, g: rmin - previous, delta: rmax - rmin };
+            previous = rmin;
+            tuple
+        }).collect();
+        self.compress();
+    }
+
+    fn compress(&mut self) {
+        let threshold = (2.0 * self.epsilon * self.count as f64).floor() as u64;
+        let n = self.tuples.len();
+        let mut result: Vec<Tuple> = Vec::with_capacity(n);
+        for (i, tuple) in self.tuples.iter().enumerate().rev() {
+            if i > 0 && i + 1 < n {
+                let next = result.last_mut().unwrap();
+                if tuple.g + next.g + next.delta <= threshold {
+                    next.g += tuple.g;
+                    continue;
+                }
+            }
+            result.push(*tuple);
+        }
+        result.reverse();
+        self.tuples = result;
+    }
+
+    fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut buffer = ::std::mem::take(&mut self.buffer);
+        buffer.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let tuples = buffer.iter().map(|&x| Tuple { value: x, g: 1, delta: 0 }).collect::<Vec<_>>();
+        self.combine(&tuples);
+    }
+}
+
+// Compute the values along with their smallest and largest possible ranks.
+fn ranks(tuples: &[Tuple]) -> Vec<(f64, u64, u64)> {
+    let mut rmin = 0;
+    tuples.iter().map(|tuple| {
+        rmin += tuple.g;
+        (tuple.value, rmin, rmin + tuple.delta)
+    }).collect()
+}
+
+fn total(ranks: &[(f64, u64, u64)]) -> u64 {
+    ranks.last().map(|&(_, rmin, _)| rmin).unwrap_or(0)
+}
+
+// Compute the contributions of the other sketch to the smallest and largest
+// possible ranks of a value inserted right before position `k` in it.
+fn neighbors(ranks: &[(f64, u64, u64)], k: usize, total: u64) -> (u64, u64) {
+    let before = if k > 0 { ranks[k - 1].1 } else { 0 };
+    let after = if k < ranks.len() { ranks[k].2 - 1 } else { total };
+    (before, after)
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+
+    use super::OnlineQuantile;
+
+    fn check(estimator: &OnlineQuantile, sorted: &[f64]) {
+        let n = sorted.len() as f64;
+        let bound = estimator.epsilon() * n;
+        for &p in &[0.0, 0.01, 0.5, 0.9, 0.99, 1.0] {
+            let value = estimator.quantile(p);
+            let lower = sorted.iter().take_while(|&&x| x < value).count() as f64 + 1.0;
+            let upper = sorted.iter().take_while(|&&x| x <= value).count() as f64;
+            let rank = (p * n).ceil().max(1.0);
+            assert!(upper >= rank - bound && lower <= rank + bound);
+        }
+    }
+
+    #[test]
+    fn merge() {
+        let mut source = source::seeded(42);
+        let mut data = Gaussian::new(0.0, 1.0).samples(&mut source, 100000);
+        let mut estimator = OnlineQuantile::new(0.001);
+        for chunk in data.chunks(30000) {
+            let mut other = OnlineQuantile::new(0.001);
+            for &x in chunk {
+                other.push(x);
+            }
+            estimator.merge(&other);
+        }
+        assert_eq!(estimator.count(), 100000);
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        check(&estimator, &data);
+        assert_eq!((estimator.quantile(0.0), estimator.quantile(1.0)),
+                   (data[0], data[data.len() - 1]));
+    }
+
+    #[test]
+    fn quantile() {
+        let mut source = source::seeded(42);
+        let mut data = Gaussian::new(0.0, 1.0).samples(&mut source, 1000000);
+        let mut estimator = OnlineQuantile::new(0.001);
+        for &x in &data {
+            estimator.push(x);
+        }
+        assert_eq!(estimator.count(), 1000000);
+        assert!(estimator.tuples.len() < 1000);
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        check(&estimator, &data);
+
+        let data = (0..100000).map(|i| i as f64).collect::<Vec<_>>();
+        let mut estimator = OnlineQuantile::new(0.01);
+        for &x in &data {
+            estimator.push(x);
+        }
+        assert!(estimator.tuples.len() < 1000);
+        check(&estimator, &data);
+
+        let mut estimator = OnlineQuantile::new(0.1);
+        assert!(estimator.quantile(0.5).is_nan());
+        for &x in &[3.0, 1.0, 2.0, 2.0] {
+            estimator.push(x);
+        }
+        assert_eq!(estimator.quantile(0.5), 2.0);
+    }
+}
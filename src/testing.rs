@@ -0,0 +1,874 @@
+//! Checks of the contracts of distributions.
+//!
+//! The functions assert the properties that every distribution should have,
+//! whether it is defined in the crate or elsewhere, and panic with a message
+//! naming the violated property and the point at which it is violated:
+//!
+//! * the distribution function vanishes at `-∞`, reaches one at `∞`, stays
+//!   within `[0, 1]`, and is nondecreasing;
+//! * the inverse undoes the distribution function on the support;
+//! * the density or mass function is nonnegative, and it integrates or sums
+//!   to the increments of the distribution function and to one overall;
+//! * the mean and variance agree with the moments computed numerically, and
+//!   the square of the standard deviation is the variance; and
+//! * the moments of samples converge to the mean and variance.
+//!
+//! The distributions of the crate implement `Arbitrary` of the `proptest`
+//! crate, generating instances with random valid parameters so that the
+//! checks can be run as property tests over many instances. The parameters
+//! are drawn from ranges that cover the typical shapes while keeping the
+//! distributions numerically well behaved; for instance, shapes are between
+//! 0.5 and 20, and scales are between 0.1 and 10. The degrees of freedom of
+//! Student’s t distribution are at least 3 so that the variance is finite.
+//! The module is available with feature `proptest`.
+//!
+//! ```
+//! # extern crate probability;
+//! # #[cfg(feature = "proptest")]
+//! # extern crate proptest;
+//! # #[cfg(feature = "proptest")]
+//! # fn main() {
+//! use probability::prelude::*;
+//! use probability::testing::{self, Config};
+//! use proptest::arbitrary::any;
+//! use proptest::test_runner::{Config as RunnerConfig, TestRunner};
+//!
+//! let config = Config::default();
+//! let mut runner = TestRunner::new(RunnerConfig::with_cases(5));
+//! runner.run(&any::<Gamma>(), |d| {
+//!     testing::check_distribution(&d, &config);
+//!     Ok(())
+//! }).unwrap();
+//! # }
+//! # #[cfg(not(feature = "proptest"))]
+//! # fn main() {}
+//! ```
+
+use proptest::arbitrary::{Arbitrary, any};
+use proptest::collection;
+use proptest::strategy::{BoxedStrategy, Just, Map, Strategy};
+use std::ops::Range;
+
+use distribution::{Continuous, Discrete, Distribution, Inverse, Sample, Variance};
+use distribution::{Affine, Bernoulli, Beta, BetaBinomial, Binomial, Categorical, Censored};
+use distribution::{Censoring, Dirichlet, Empirical, Exponential, Gamma, Gaussian};
+use distribution::{GaussianInverseGamma, Histogram, Joint, Kde, Mixture, NegativeBinomial};
+use distribution::{OrderStatistic, Poisson, StandardGaussian, StudentT, Sum, Uniform};
+use math;
+use source;
+use statistics::OnlineStats;
+
+/// A configuration of the checks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// The number of points at which the functions are evaluated.
+    pub points: usize,
+    /// The number of samples drawn.
+    pub samples: usize,
+    /// The seed of the source of the samples.
+    pub seed: u64,
+    /// The tolerance on the errors of probabilities and the relative errors
+    /// of moments.
+    pub tolerance: f64,
+}
+
+/// An outcome that can be treated as a real number.
+pub trait Outcome: Copy + PartialOrd {
+    /// Convert the outcome to a real number.
+    fn to_f64(self) -> f64;
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Config { points: 100, samples: 10000, seed: 42, tolerance: 1e-8 }
+    }
+}
+
+/// Check the distribution function, its inverse, and the density function of
+/// a continuous distribution.
+///
+/// The functions are evaluated at quantiles of evenly spaced orders and at
+/// evenly spaced points. The density function is integrated between
+/// consecutive points and compared with the increments of the distribution
+/// function; the inverse is required to recover a point unless the
+/// distribution function is flat around it.
+pub fn check_continuous<D>(d: &D, config: &Config) where D: Continuous + Inverse<Value=f64> {
+    let tolerance = config.tolerance;
+    check_limits(d);
+    let points = points(d, config.points);
+    let mut previous = 0.0;
+    for &x in &points {
+        let p = d.distribution(x);
+        assert!(0.0 <= p && p <= 1.0, "the distribution function should be in [0, 1] \
+                                       (got {} at {})", p, x);
+        assert!(previous <= p, "the distribution function should be nondecreasing \
+                                (got {} after {} at {})", p, previous, x);
+        previous = p;
+        let density = d.density(x);
+        assert!(density >= 0.0, "the density function should be nonnegative (got {} at {})",
+                density, x);
+        if 0.0 < p && p < 1.0 {
+            let y = d.inverse(p);
+            assert!((y - x).abs() <= tolerance * (1.0 + x.abs()) ||
+                    (d.distribution(y) - p).abs() <= tolerance,
+                    "the inverse should undo the distribution function (got {} at {})", y, x);
+        }
+    }
+    for i in 1..config.points {
+        let p = i as f64 / config.points as f64;
+        let q = d.distribution(d.inverse(p));
+        assert!((q - p).abs() <= tolerance, "the distribution function should undo the \
+                                             inverse (got {} at {})", q, p);
+    }
+    let (lower, upper) = d.support();
+    let mut total = 0.0;
+    for pair in bracket(lower, &points, upper).windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if a >= b {
+            continue;
+        }
+        let area = expect(d, &[a, b], |_| 1.0, 0.01 * tolerance);
+        let increment = d.distribution(b) - d.distribution(a);
+        assert!((area - increment).abs() <= tolerance, "the density function should integrate \
+                                                        to the increment of the distribution \
+                                                        function (got {} instead of {} on \
+                                                        [{}, {}])", area, increment, a, b);
+        total += area;
+    }
+    assert!((total - 1.0).abs() <= tolerance, "the density function should integrate to one \
+                                               (got {})", total);
+}
+
+/// Check the distribution function, its inverse, and the mass function of a
+/// discrete distribution.
+///
+/// The outcomes are visited in increasing order by inverting the distribution
+/// function just above its value at the previous outcome, until the remaining
+/// probability falls below the tolerance. The distribution function should
+/// be constant between outcomes, and its jumps should be the masses.
+pub fn check_discrete<D>(d: &D, config: &Config)
+    where D: Discrete + Inverse, D::Value: Outcome
+{
+    let tolerance = config.tolerance;
+    check_limits(d);
+    let (mut previous, mut total) = (0.0, 0.0);
+    let outcomes = outcomes(d, tolerance);
+    for (i, &x) in outcomes.iter().enumerate() {
+        let value = x.to_f64();
+        let p = d.distribution(value);
+        assert!(0.0 <= p && p <= 1.0, "the distribution function should be in [0, 1] \
+                                       (got {} at {})", p, value);
+        assert!(previous <= p, "the distribution function should be nondecreasing \
+                                (got {} after {} at {})", p, previous, value);
+        let mass = d.mass(x);
+        assert!(mass >= 0.0, "the mass function should be nonnegative (got {} at {})",
+                mass, value);
+        assert!((mass - (p - previous)).abs() <= tolerance, "the mass function should be the \
+                                                             jump of the distribution \
+                                                             function (got {} instead of {} \
+                                                             at {})", mass, p - previous, value);
+        if previous < p {
+            assert!(d.inverse(p) == x, "the inverse should undo the distribution function \
+                                        (got {} at {})", d.inverse(p).to_f64(), value);
+        }
+        if let Some(&y) = outcomes.get(i + 1) {
+            let middle = value + 0.5 * (y.to_f64() - value);
+            let q = d.distribution(middle);
+            assert!(q == p, "the distribution function should be constant between outcomes \
+                             (got {} instead of {} at {})", q, p, middle);
+        }
+        previous = p;
+        total += mass;
+    }
+    assert!((total - 1.0).abs() <= 2.0 * tolerance, "the mass function should sum to one \
+                                                     (got {})", total);
+}
+
+/// Check the mean and variance of a discrete distribution against the sums
+/// over the outcomes visited as in `check_discrete`.
+///
+/// Infinite and undefined moments are not checked.
+pub fn check_discrete_moments<D>(d: &D, config: &Config)
+    where D: Discrete + Inverse + Variance, D::Value: Outcome
+{
+    let outcomes = outcomes(d, 1e-6 * config.tolerance);
+    let mean = math::sum(outcomes.iter().map(|&x| x.to_f64() * d.mass(x)));
+    let variance = math::sum(outcomes.iter().map(|&x| (x.to_f64() - mean).powi(2) * d.mass(x)));
+    check_moments_against(d, mean, variance, config.tolerance);
+}
+
+/// Run `check_continuous`, `check_moments`, and `check_sampling`.
+pub fn check_distribution<D>(d: &D, config: &Config)
+    where D: Continuous + Inverse<Value=f64> + Sample + Variance
+{
+    check_continuous(d, config);
+    check_moments(d, config);
+    check_sampling(d, config);
+}
+
+/// Check the mean and variance of a continuous distribution against the
+/// moments computed by integrating the density function.
+///
+/// Infinite and undefined moments are not checked.
+pub fn check_moments<D>(d: &D, config: &Config)
+    where D: Continuous + Inverse<Value=f64> + Variance
+{
+    let tolerance = 0.01 * config.tolerance;
+    let (lower, upper) = d.support();
+    let points = bracket(lower, &points(d, config.points), upper);
+    let mean = expect(d, &points, |x| x, tolerance);
+    let variance = expect(d, &points, |x| (x - mean).powi(2), tolerance);
+    check_moments_against(d, mean, variance, config.tolerance);
+}
+
+/// Check that the mean and variance of samples are within six standard errors
+/// of the mean and variance of the distribution.
+///
+/// The standard error of the sample variance is estimated using the sample
+/// kurtosis. Infinite and undefined moments are not checked.
+pub fn check_sampling<D>(d: &D, config: &Config) where D: Sample + Variance, D::Value: Outcome {
+    const LIMIT: f64 = 6.0;
+
+    let mut source = source::seeded(config.seed);
+    let mut stats = OnlineStats::new();
+    for _ in 0..config.samples {
+        stats.push(d.sample(&mut source).to_f64());
+    }
+    let n = config.samples as f64;
+    let (mean, variance) = (d.mean(), d.variance());
+    if !mean.is_finite() || !variance.is_finite() {
+        return;
+    }
+    let error = (variance / n).sqrt();
+    assert!((stats.mean() - mean).abs() <= LIMIT * error, "the sample mean should converge to \
+                                                           the mean (got {} instead of {})",
+            stats.mean(), mean);
+    let kurtosis = if stats.variance() > 0.0 { stats.kurtosis() } else { 0.0 };
+    let error = (stats.variance().powi(2) * (kurtosis + 2.0) / n).sqrt();
+    assert!((stats.variance() - variance).abs() <= LIMIT * error, "the sample variance should \
+                                                                   converge to the variance \
+                                                                   (got {} instead of {})",
+            stats.variance(), variance);
+}
+
+macro_rules! implement {
+    ($($kind:ty,)*) => ($(
+        impl Outcome for $kind {
+            #[inline(always)]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*);
+}
+
+implement!(f64, u8, usize,);
+
+impl<T> Arbitrary for Affine<T> where T: Arbitrary + Distribution<Value=f64> + 'static {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<T>(), any::<bool>(), scale(0.1, 10.0), between(-10.0, 10.0))
+            .prop_map(|(inner, negative, scale, shift)| {
+                Affine::new(inner, if negative { -scale } else { scale }, shift)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Bernoulli {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        between(0.01, 0.99).prop_map(Bernoulli::new).boxed()
+    }
+}
+
+impl Arbitrary for Beta {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (scale(0.5, 20.0), scale(0.5, 20.0), between(-10.0, 10.0), scale(0.1, 10.0))
+            .prop_map(|(alpha, beta, a, width)| Beta::new(alpha, beta, a, a + width))
+            .boxed()
+    }
+}
+
+impl Arbitrary for BetaBinomial {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1usize..51, scale(0.5, 20.0), scale(0.5, 20.0))
+            .prop_map(|(n, alpha, beta)| BetaBinomial::new(n, alpha, beta))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Binomial {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1usize..101, between(0.01, 0.99)).prop_map(|(n, p)| Binomial::new(n, p)).boxed()
+    }
+}
+
+impl Arbitrary for Categorical {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1usize..11).prop_flat_map(weights).prop_map(|p| Categorical::new(&p)).boxed()
+    }
+}
+
+impl<T> Arbitrary for Censored<T> where T: Arbitrary + Inverse<Value=f64> + 'static {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<T>(), between(0.1, 0.9), any::<bool>())
+            .prop_map(|(inner, p, left)| {
+                let limit = inner.inverse(p);
+                let censoring = if left { Censoring::Left } else { Censoring::Right };
+                Censored::new(inner, limit, censoring)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Dirichlet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        collection::vec(scale(0.5, 20.0), 2..6).prop_map(|alpha| Dirichlet::new(&alpha)).boxed()
+    }
+}
+
+impl Arbitrary for Empirical {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        collection::vec(between(-10.0, 10.0), 1..21)
+            .prop_map(|samples| Empirical::new(&samples))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Exponential {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        scale(0.1, 10.0).prop_map(Exponential::new).boxed()
+    }
+}
+
+impl Arbitrary for Gamma {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (scale(0.5, 20.0), scale(0.1, 10.0)).prop_map(|(k, theta)| Gamma::new(k, theta)).boxed()
+    }
+}
+
+impl Arbitrary for Gaussian {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (between(-10.0, 10.0), scale(0.1, 10.0))
+            .prop_map(|(mu, sigma)| Gaussian::new(mu, sigma))
+            .boxed()
+    }
+}
+
+impl Arbitrary for GaussianInverseGamma {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (between(-10.0, 10.0), scale(0.1, 10.0), scale(0.5, 20.0), scale(0.1, 10.0))
+            .prop_map(|(mu, lambda, alpha, beta)| {
+                GaussianInverseGamma::new(mu, lambda, alpha, beta)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Histogram {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1usize..11)
+            .prop_flat_map(|k| {
+                (between(-10.0, 10.0), collection::vec(scale(0.1, 10.0), k),
+                 collection::vec(0u64..11, k), 0..k)
+            })
+            .prop_map(|(start, widths, mut counts, i)| {
+                let mut edges = vec![start];
+                for width in widths {
+                    let edge = edges[edges.len() - 1] + width;
+                    edges.push(edge);
+                }
+                counts[i] += 1;
+                Histogram::new(&edges, &counts)
+            })
+            .boxed()
+    }
+}
+
+impl<T> Arbitrary for Joint<Vec<T>> where T: Arbitrary + 'static {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        collection::vec(any::<T>(), 1..6).prop_map(Joint::new).boxed()
+    }
+}
+
+impl Arbitrary for Kde {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        collection::vec(between(-10.0, 10.0), 1..21).prop_map(|samples| Kde::new(&samples)).boxed()
+    }
+}
+
+impl<T> Arbitrary for Mixture<T> where T: Arbitrary + Distribution + 'static {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1usize..5)
+            .prop_flat_map(|k| (collection::vec(any::<T>(), k), weights(k)))
+            .prop_map(|(components, weights)| Mixture::new(components, weights))
+            .boxed()
+    }
+}
+
+impl Arbitrary for NegativeBinomial {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (scale(0.5, 20.0), between(0.05, 0.95))
+            .prop_map(|(r, p)| NegativeBinomial::new(r, p))
+            .boxed()
+    }
+}
+
+impl<T> Arbitrary for OrderStatistic<T> where T: Arbitrary + Distribution + 'static {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<T>(), (1usize..11).prop_flat_map(|n| (Just(n), 1..(n + 1))))
+            .prop_map(|(inner, (n, k))| OrderStatistic::new(inner, n, k))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Poisson {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        scale(0.1, 100.0).prop_map(Poisson::new).boxed()
+    }
+}
+
+impl Arbitrary for StandardGaussian {
+    type Parameters = ();
+    type Strategy = Just<Self>;
+
+    #[inline(always)]
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        Just(StandardGaussian)
+    }
+}
+
+impl Arbitrary for StudentT {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (scale(3.0, 30.0), between(-10.0, 10.0), scale(0.1, 10.0))
+            .prop_map(|(nu, mu, sigma)| StudentT::new(nu, mu, sigma))
+            .boxed()
+    }
+}
+
+impl<A, B> Arbitrary for Sum<A, B> where A: Arbitrary + Inverse<Value=f64> + 'static,
+                                         B: Arbitrary + Inverse<Value=f64> + 'static
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<A>(), any::<B>()).prop_map(|(a, b)| Sum::new(a, b)).boxed()
+    }
+}
+
+impl Arbitrary for Uniform {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (between(-10.0, 10.0), scale(0.1, 10.0))
+            .prop_map(|(a, width)| Uniform::new(a, a + width))
+            .boxed()
+    }
+}
+
+// Draw uniformly from `[a, b)`.
+#[inline]
+fn between(a: f64, b: f64) -> Range<f64> {
+    a..b
+}
+
+// Insert the endpoints of the support around sorted points.
+fn bracket(lower: f64, points: &[f64], upper: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(points.len() + 2);
+    result.push(lower);
+    result.extend(points.iter().cloned().filter(|&x| lower < x && x < upper));
+    result.push(upper);
+    result
+}
+
+fn check_limits<D>(d: &D) where D: Distribution {
+    use std::f64::INFINITY;
+
+    let (lower, upper) = (d.distribution(-INFINITY), d.distribution(INFINITY));
+    assert!(lower == 0.0, "the distribution function should vanish at -∞ (got {})", lower);
+    assert!(upper == 1.0, "the distribution function should reach one at ∞ (got {})", upper);
+}
+
+fn check_moments_against<D>(d: &D, mean: f64, variance: f64, tolerance: f64)
+    where D: Variance
+{
+    let (expected, deviation) = (d.mean(), d.deviation());
+    if expected.is_finite() {
+        assert!((expected - mean).abs() <= tolerance * (mean.abs() + variance.sqrt()),
+                "the mean should be the first moment (got {} instead of {})", expected, mean);
+    }
+    let expected = d.variance();
+    if expected.is_finite() {
+        assert!((expected - variance).abs() <= tolerance * variance, "the variance should be \
+                                                                      the second central \
+                                                                      moment (got {} instead \
+                                                                      of {})", expected,
+                variance);
+        assert!((deviation * deviation - expected).abs() <= 1e-12 * expected,
+                "the square of the standard deviation should be the variance (got {} instead \
+                 of {})", deviation * deviation, expected);
+    }
+}
+
+// Compute the expectation of `f(X)` by integrating the density function
+// between consecutive points. Infinite values of the density function, which
+// integrable singularities at the endpoints of the support produce when the
+// nodes of the quadrature rule coincide with the endpoints, are skipped.
+fn expect<D, F>(d: &D, points: &[f64], f: F, tolerance: f64) -> f64
+    where D: Continuous, F: Fn(f64) -> f64
+{
+    let g = |x| {
+        let density = d.density(x);
+        if density == 0.0 || density.is_infinite() { 0.0 } else { f(x) * density }
+    };
+    math::sum(points.windows(2).filter(|pair| pair[0] < pair[1]).map(|pair| {
+        math::integrate(&g, pair[0], pair[1], tolerance).value
+    }))
+}
+
+// Visit the outcomes in increasing order until the remaining probability falls
+// below `tail`.
+fn outcomes<D>(d: &D, tail: f64) -> Vec<D::Value> where D: Inverse, D::Value: Outcome {
+    const LIMIT: usize = 1_000_000;
+
+    let mut x = d.inverse(0.0);
+    let mut outcomes = vec![x];
+    for _ in 0..LIMIT {
+        let p = d.distribution(x.to_f64());
+        if p >= 1.0 || d.survival(x.to_f64()) <= tail {
+            return outcomes;
+        }
+        let y = d.inverse(f64::from_bits(p.to_bits() + 1));
+        assert!(x < y, "the inverse should advance past an outcome (got {} after {})",
+                y.to_f64(), x.to_f64());
+        outcomes.push(y);
+        x = y;
+    }
+    panic!("the outcomes should be exhausted within {} steps", LIMIT)
+}
+
+// Compute the quantiles of evenly spaced orders together with evenly spaced
+// points between the endpoints of the support or, if they are infinite, the
+// quantiles of order `1e-3` and `1 - 1e-3`, in increasing order. The points
+// spread over the whole support, so that no interval between them conceals a
+// region where the density function is positive.
+fn points<D>(d: &D, n: usize) -> Vec<f64> where D: Inverse<Value=f64> {
+    let mut points = (1..n).map(|i| d.inverse(i as f64 / n as f64)).collect::<Vec<_>>();
+    let (lower, upper) = d.support();
+    let a = if lower.is_finite() { lower } else { d.inverse(1e-3) };
+    let b = if upper.is_finite() { upper } else { d.inverse(1.0 - 1e-3) };
+    points.extend((0..(n + 1)).map(|i| a + (b - a) * i as f64 / n as f64));
+    points.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    points.dedup();
+    points
+}
+
+// Draw log-uniformly from `[a, b)`.
+#[inline]
+fn scale(a: f64, b: f64) -> Map<Range<f64>, fn(f64) -> f64> {
+    (a.ln()..b.ln()).prop_map(f64::exp as fn(f64) -> f64)
+}
+
+// Draw `k` weights summing up to one.
+fn weights(k: usize) -> BoxedStrategy<Vec<f64>> {
+    fn normalize(mut weights: Vec<f64>) -> Vec<f64> {
+        let k = weights.len();
+        let sum = weights.iter().fold(0.0, |sum, &w| sum + w);
+        for w in &mut weights {
+            *w /= sum;
+        }
+        let last = 1.0 - weights[..(k - 1)].iter().fold(0.0, |sum, &w| sum + w);
+        weights[k - 1] = last;
+        weights
+    }
+    collection::vec(between(0.1, 1.0), k).prop_map(normalize).boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use proptest::arbitrary::any;
+    use proptest::test_runner::{self, TestRng, TestRunner};
+
+    use super::Config;
+    use super::{check_continuous, check_discrete, check_discrete_moments, check_distribution};
+    use super::check_sampling;
+
+    const INSTANCES: u32 = 20;
+
+    macro_rules! run(
+        ($check:ident, $kind:ty) => (run!($check, $kind, Config::default()));
+        ($check:ident, $kind:ty, $config:expr) => ({
+            let config = $config;
+            runner().run(&any::<$kind>(), |d| {
+                $check(&d, &config);
+                Ok(())
+            }).unwrap();
+        });
+    );
+
+    fn runner() -> TestRunner {
+        let config = test_runner::Config {
+            cases: INSTANCES,
+            failure_persistence: None,
+            ..test_runner::Config::default()
+        };
+        let algorithm = config.rng_algorithm;
+        TestRunner::new_with_rng(config, TestRng::deterministic_rng(algorithm))
+    }
+
+    struct Mirrored(Gaussian);
+
+    impl Distribution for Mirrored {
+        type Value = f64;
+
+        fn distribution(&self, x: f64) -> f64 {
+            self.0.distribution(x)
+        }
+    }
+
+    impl Continuous for Mirrored {
+        fn density(&self, x: f64) -> f64 {
+            self.0.density(-x)
+        }
+    }
+
+    impl Inverse for Mirrored {
+        fn inverse(&self, p: f64) -> f64 {
+            self.0.inverse(p)
+        }
+    }
+
+    struct Shifted(Poisson);
+
+    impl Discrete for Shifted {
+        fn mass(&self, k: usize) -> f64 {
+            self.0.mass(k)
+        }
+    }
+
+    impl Distribution for Shifted {
+        type Value = usize;
+
+        fn distribution(&self, x: f64) -> f64 {
+            self.0.distribution(x)
+        }
+    }
+
+    impl Inverse for Shifted {
+        fn inverse(&self, p: f64) -> usize {
+            self.0.inverse(p)
+        }
+    }
+
+    impl Mean for Shifted {
+        fn mean(&self) -> f64 {
+            -self.0.mean()
+        }
+    }
+
+    impl Variance for Shifted {
+        fn variance(&self) -> f64 {
+            self.0.variance()
+        }
+    }
+
+    #[test]
+    fn check_continuous_affine() {
+        // The density function of the gamma distribution has a singularity at
+        // zero when the shape is below one; see the beta distribution below.
+        run!(check_distribution, Affine<Gamma>, Config { tolerance: 1e-6, ..Config::default() });
+    }
+
+    #[test]
+    fn check_continuous_beta() {
+        // The density function has singularities at the endpoints when the
+        // shapes are below one, which the quadrature rule resolves only up to
+        // the spacing of floating-point numbers.
+        run!(check_distribution, Beta, Config { tolerance: 1e-6, ..Config::default() });
+    }
+
+    #[test]
+    #[should_panic(expected = "the density function should integrate")]
+    fn check_continuous_caught() {
+        check_continuous(&Mirrored(Gaussian::new(1.0, 2.0)), &Config::default());
+    }
+
+    #[test]
+    fn check_continuous_exponential() {
+        run!(check_distribution, Exponential);
+    }
+
+    #[test]
+    fn check_continuous_gamma() {
+        run!(check_distribution, Gamma);
+    }
+
+    #[test]
+    fn check_continuous_gaussian() {
+        run!(check_distribution, Gaussian);
+        run!(check_distribution, StandardGaussian);
+    }
+
+    #[test]
+    fn check_continuous_histogram() {
+        // The density function jumps at the edges of the bins, which the
+        // quadrature rule can step over.
+        run!(check_distribution, Histogram, Config { tolerance: 1e-4, ..Config::default() });
+    }
+
+    #[test]
+    fn check_continuous_kde() {
+        run!(check_distribution, Kde);
+    }
+
+    #[test]
+    fn check_continuous_mixture() {
+        run!(check_distribution, Mixture<Gaussian>);
+    }
+
+    #[test]
+    fn check_continuous_order() {
+        run!(check_distribution, OrderStatistic<Exponential>);
+    }
+
+    #[test]
+    fn check_continuous_student() {
+        run!(check_distribution, StudentT);
+    }
+
+    #[test]
+    fn check_continuous_sum() {
+        run!(check_distribution, Sum<Gaussian, Exponential>);
+    }
+
+    #[test]
+    fn check_continuous_uniform() {
+        run!(check_distribution, Uniform);
+    }
+
+    #[test]
+    fn check_discrete_bernoulli() {
+        run!(check_discrete, Bernoulli);
+        run!(check_discrete_moments, Bernoulli);
+        run!(check_sampling, Bernoulli);
+    }
+
+    #[test]
+    fn check_discrete_beta_binomial() {
+        run!(check_discrete, BetaBinomial);
+        run!(check_discrete_moments, BetaBinomial);
+        run!(check_sampling, BetaBinomial);
+    }
+
+    #[test]
+    fn check_discrete_binomial() {
+        run!(check_discrete, Binomial);
+        run!(check_discrete_moments, Binomial);
+        run!(check_sampling, Binomial);
+    }
+
+    #[test]
+    #[should_panic(expected = "the mean should be the first moment")]
+    fn check_discrete_caught() {
+        let d = Shifted(Poisson::new(3.0));
+        check_discrete(&d, &Config::default());
+        check_discrete_moments(&d, &Config::default());
+    }
+
+    #[test]
+    fn check_discrete_categorical() {
+        run!(check_discrete, Categorical);
+        run!(check_discrete_moments, Categorical);
+        run!(check_sampling, Categorical);
+    }
+
+    #[test]
+    fn check_discrete_negative_binomial() {
+        run!(check_discrete, NegativeBinomial);
+        run!(check_discrete_moments, NegativeBinomial);
+        run!(check_sampling, NegativeBinomial);
+    }
+
+    #[test]
+    fn check_discrete_poisson() {
+        run!(check_discrete, Poisson);
+        run!(check_discrete_moments, Poisson);
+        run!(check_sampling, Poisson);
+    }
+
+    #[test]
+    fn check_sampling_censored() {
+        run!(check_sampling, Censored<Gaussian>);
+    }
+
+    #[test]
+    fn check_sampling_empirical() {
+        run!(check_sampling, Empirical);
+    }
+}
@@ -0,0 +1,188 @@
+//! The tests of the equality of several groups.
+//!
+//! ```
+//! use probability::tests;
+//!
+//! let a = [4.2, 4.8, 5.1, 4.6];
+//! let b = [5.9, 6.3, 5.5, 6.1];
+//! let c = [4.9, 5.2, 5.0, 5.6];
+//! let result = tests::anova_one_way(&[&a, &b, &c]);
+//! assert!(result.p_value < 0.01 && result.eta_squared > 0.5);
+//! ```
+
+use distribution::{Distribution, Gamma};
+use math;
+
+/// The result of the one-way analysis of variance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnovaResult {
+    /// The F statistic.
+    pub statistic: f64,
+    /// The numbers of degrees of freedom between and within the groups.
+    pub dof: (usize, usize),
+    /// The p-value.
+    pub p_value: f64,
+    /// The means of the groups.
+    pub means: Vec<f64>,
+    /// The sum of squares between the groups.
+    pub between_sum_of_squares: f64,
+    /// The sum of squares within the groups.
+    pub within_sum_of_squares: f64,
+    /// The residual mean square, that is, the sum of squares within the
+    /// groups divided by its number of degrees of freedom.
+    pub residual_mean_square: f64,
+    /// The proportion of the total sum of squares explained by the groups.
+    pub eta_squared: f64,
+}
+
+/// The result of the Kruskal–Wallis test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KruskalWallisResult {
+    /// The H statistic corrected for ties.
+    pub statistic: f64,
+    /// The number of degrees of freedom.
+    pub dof: usize,
+    /// The p-value.
+    pub p_value: f64,
+}
+
+/// Test whether `groups` have the same mean using the one-way analysis of
+/// variance.
+///
+/// The sums of squares are accumulated about the group means and the grand
+/// mean. It should hold that there are at least two groups and that each is
+/// nonempty; the function panics if the total number of observations does
+/// not exceed the number of groups, in which case there are no degrees of
+/// freedom within the groups.
+pub fn anova_one_way(groups: &[&[f64]]) -> AnovaResult {
+    let k = groups.len();
+    should!(k > 1 && groups.iter().all(|group| !group.is_empty()));
+    let n = groups.iter().map(|group| group.len()).sum::<usize>();
+    assert!(n > k, "the total number of observations should exceed the number of groups \
+                    (got {} and {})", n, k);
+    let grand = math::sum(groups.iter().flat_map(|group| group.iter().cloned())) / n as f64;
+    let means = groups.iter().map(|group| {
+        math::sum(group.iter().cloned()) / group.len() as f64
+    }).collect::<Vec<_>>();
+    let between = math::sum(groups.iter().zip(&means).map(|(group, &mean)| {
+        group.len() as f64 * (mean - grand) * (mean - grand)
+    }));
+    let within = math::sum(groups.iter().zip(&means).flat_map(|(group, &mean)| {
+        group.iter().map(move |&x| (x - mean) * (x - mean))
+    }));
+    let dof = (k - 1, n - k);
+    let residual_mean_square = within / dof.1 as f64;
+    let statistic = between / dof.0 as f64 / residual_mean_square;
+    AnovaResult {
+        statistic: statistic,
+        dof: dof,
+        p_value: math::fisher(statistic, dof.0 as f64, dof.1 as f64),
+        means: means,
+        between_sum_of_squares: between,
+        within_sum_of_squares: within,
+        residual_mean_square: residual_mean_square,
+        eta_squared: between / (between + within),
+    }
+}
+
+/// Test whether `groups` are drawn from the same distribution using the
+/// Kruskal–Wallis test.
+///
+/// The observations are ranked jointly with ties receiving the average of
+/// the ranks they span, the statistic is corrected for ties, and the p-value
+/// is computed from the chi-squared approximation. It should hold that there
+/// are at least two groups, that each is nonempty, and that the observations
+/// contain no NaN.
+pub fn kruskal_wallis(groups: &[&[f64]]) -> KruskalWallisResult {
+    use super::rank::rank;
+
+    let k = groups.len();
+    should!(k > 1 && groups.iter().all(|group| !group.is_empty()));
+    let pooled = groups.iter().flat_map(|group| group.iter().cloned()).collect::<Vec<_>>();
+    let (ranks, ties) = rank(&pooled);
+    let n = pooled.len() as f64;
+    let (mut sum, mut start) = (0.0, 0);
+    for group in groups {
+        let total = ranks[start..(start + group.len())].iter().sum::<f64>();
+        sum += total * total / group.len() as f64;
+        start += group.len();
+    }
+    let statistic = (12.0 / (n * (n + 1.0)) * sum - 3.0 * (n + 1.0)) /
+                    (1.0 - ties / (n * n * n - n));
+    let dof = k - 1;
+    KruskalWallisResult {
+        statistic: statistic,
+        dof: dof,
+        p_value: Gamma::new(dof as f64 / 2.0, 2.0).survival(statistic),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    const CONTROL: [f64; 10] = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+    const FIRST: [f64; 10] = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+    const SECOND: [f64; 10] = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+
+    #[test]
+    fn anova_one_way() {
+        let result = super::anova_one_way(&[&CONTROL, &FIRST, &SECOND]);
+        assert::close(result.statistic, 4.8460878623801358928, 1e-12);
+        assert_eq!(result.dof, (2, 27));
+        assert::close(result.p_value, 0.015909958325622914214, 1e-12);
+        assert::close(&result.means, &vec![5.032, 4.661, 5.526], 1e-14);
+        assert::close(result.between_sum_of_squares, 3.76634, 1e-12);
+        assert::close(result.within_sum_of_squares, 10.49209, 1e-12);
+        assert::close(result.residual_mean_square, 0.38859592592592592593, 1e-14);
+        assert::close(result.eta_squared, 0.26414829683211966535, 1e-14);
+    }
+
+    #[test]
+    fn anova_one_way_identical() {
+        let result = super::anova_one_way(&[&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]]);
+        assert_eq!(result.statistic, 0.0);
+        assert_eq!(result.p_value, 1.0);
+        assert_eq!(result.eta_squared, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the total number of observations should exceed the number of \
+                               groups (got 3 and 3)")]
+    fn anova_one_way_saturated() {
+        super::anova_one_way(&[&[1.0], &[2.0], &[3.0]]);
+    }
+
+    #[test]
+    fn anova_one_way_singleton() {
+        let result = super::anova_one_way(&[&[2.3, 3.1, 2.8, 3.5], &[4.0], &[1.9, 2.2, 2.6],
+                                            &[3.3, 3.9, 4.4, 3.0, 3.6]]);
+        assert::close(result.statistic, 6.3630696512453011653, 1e-12);
+        assert_eq!(result.dof, (3, 9));
+        assert::close(result.p_value, 0.01324950709351974528, 1e-12);
+        assert::close(result.residual_mean_square, 0.24290740740740740741, 1e-14);
+        assert::close(result.eta_squared, 0.67959225854941751221, 1e-14);
+    }
+
+    #[test]
+    fn kruskal_wallis() {
+        let result = super::kruskal_wallis(&[&CONTROL, &FIRST, &SECOND]);
+        assert::close(result.statistic, 7.9882287494437027147, 1e-12);
+        assert_eq!(result.dof, 2);
+        assert::close(result.p_value, 0.018423755731472086646, 1e-12);
+
+        let result = super::kruskal_wallis(&[&[2.3, 3.1, 2.8, 3.5], &[4.0], &[1.9, 2.2, 2.6],
+                                             &[3.3, 3.9, 4.4, 3.0, 3.6]]);
+        assert::close(result.statistic, 8.4483516483516483516, 1e-12);
+        assert::close(result.p_value, 0.037599837862619024679, 1e-12);
+    }
+
+    #[test]
+    fn kruskal_wallis_ties() {
+        let result = super::kruskal_wallis(&[&[1.0, 1.0, 2.0, 2.0, 2.0, 3.0],
+                                             &[2.0, 3.0, 3.0, 3.0, 4.0],
+                                             &[1.0, 4.0, 4.0, 4.0, 4.0, 5.0, 5.0]]);
+        assert::close(result.statistic, 8.1641001855287569573, 1e-12);
+        assert::close(result.p_value, 0.016872839285466694982, 1e-12);
+    }
+}
@@ -0,0 +1,183 @@
+//! Pearson’s chi-squared goodness-of-fit test.
+//!
+//! ```
+//! use probability::tests;
+//!
+//! let observed = [315, 108, 101, 32];
+//! let result = tests::chi_squared_gof(&observed, &[9.0 / 16.0, 3.0 / 16.0, 3.0 / 16.0,
+//!                                                  1.0 / 16.0]);
+//! assert_eq!(result.dof, 3);
+//! assert!(result.p_value > 0.9);
+//! ```
+
+use distribution::{Discrete, Distribution, Gamma};
+
+/// The result of a chi-squared goodness-of-fit test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChiSquaredResult {
+    /// The statistic.
+    pub statistic: f64,
+    /// The number of degrees of freedom.
+    pub dof: usize,
+    /// The p-value.
+    pub p_value: f64,
+    /// The contributions of the bins to the statistic.
+    pub contributions: Vec<f64>,
+    /// The ranges of the original bins, with the end excluded, that the
+    /// contributions correspond to after pooling.
+    pub bins: Vec<(usize, usize)>,
+}
+
+/// Test whether `observed` counts are drawn from the categorical distribution
+/// with probabilities `probabilities`.
+///
+/// The statistic is `Σ (O - E)² / E`, where `O` and `E` are the observed and
+/// expected counts, and it has `k - 1` degrees of freedom, where `k` is the
+/// number of bins. The bins are not pooled; see `chi_squared_gof_dist`. It
+/// should hold that there are at least two bins and that the probabilities
+/// sum up to one.
+pub fn chi_squared_gof(observed: &[u64], probabilities: &[f64]) -> ChiSquaredResult {
+    should!(observed.len() == probabilities.len() && observed.len() > 1);
+    should!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+    let total = observed.iter().sum::<u64>() as f64;
+    let expected = probabilities.iter().map(|&p| total * p).collect::<Vec<_>>();
+    let bins = (0..observed.len()).map(|i| (i, i + 1)).collect();
+    compute(observed, &expected, bins, 0)
+}
+
+/// Test whether `observed` counts are drawn from `distribution`.
+///
+/// Bin `i` counts the occurrences of outcome `i` except for the last bin,
+/// which counts those of all outcomes greater than or equal to its index.
+/// Going from left to right, adjacent bins are pooled until the expected
+/// count reaches `minimum`, which is conventionally five, and a remainder
+/// falling short of it is pooled with the preceding group. The number of
+/// degrees of freedom is `k - 1 - fitted`, where `k` is the number of bins
+/// after pooling and `fitted` is the number of parameters of the distribution
+/// estimated from the same data.
+///
+/// # Panics
+///
+/// The function panics if the number of degrees of freedom is not positive.
+pub fn chi_squared_gof_dist<D>(observed: &[u64], distribution: &D, fitted: usize, minimum: f64)
+                               -> ChiSquaredResult
+    where D: Discrete<Value=usize>
+{
+    should!(!observed.is_empty());
+    let total = observed.iter().sum::<u64>() as f64;
+    let last = observed.len() - 1;
+    let mut expected = (0..last).map(|i| total * distribution.mass(i)).collect::<Vec<_>>();
+    expected.push(total * if last == 0 {
+        1.0
+    } else {
+        distribution.survival((last - 1) as f64)
+    });
+
+    let mut bins: Vec<(usize, usize)> = vec![];
+    let mut start = 0;
+    let mut sum = 0.0;
+    for (i, &value) in expected.iter().enumerate() {
+        sum += value;
+        if sum >= minimum {
+            bins.push((start, i + 1));
+            start = i + 1;
+            sum = 0.0;
+        }
+    }
+    if start < expected.len() {
+        match bins.last_mut() {
+            Some(bin) => bin.1 = expected.len(),
+            _ => bins.push((start, expected.len())),
+        }
+    }
+    assert!(bins.len() > fitted + 1,
+            "the number of bins after pooling should exceed the number of fitted parameters \
+             by more than one (got {} and {})", bins.len(), fitted);
+
+    let observed = bins.iter().map(|&(a, b)| observed[a..b].iter().sum()).collect::<Vec<_>>();
+    let expected = bins.iter().map(|&(a, b)| expected[a..b].iter().sum()).collect::<Vec<_>>();
+    compute(&observed, &expected, bins, fitted)
+}
+
+fn compute(observed: &[u64], expected: &[f64], bins: Vec<(usize, usize)>, fitted: usize)
+           -> ChiSquaredResult {
+    let contributions = observed.iter().zip(expected).map(|(&o, &e)| {
+        let difference = o as f64 - e;
+        difference * difference / e
+    }).collect::<Vec<_>>();
+    let statistic = contributions.iter().sum::<f64>();
+    let dof = observed.len() - 1 - fitted;
+    ChiSquaredResult {
+        statistic: statistic,
+        dof: dof,
+        p_value: Gamma::new(dof as f64 / 2.0, 2.0).survival(statistic),
+        contributions: contributions,
+        bins: bins,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    const OBSERVED: [u64; 15] = [11, 28, 45, 44, 33, 20, 10, 5, 3, 1, 0, 0, 0, 0, 0];
+
+    #[test]
+    fn chi_squared_gof() {
+        use super::chi_squared_gof;
+
+        let result = chi_squared_gof(&[315, 108, 101, 32], &[9.0 / 16.0, 3.0 / 16.0, 3.0 / 16.0,
+                                                            1.0 / 16.0]);
+        assert::close(result.statistic, 0.47002398081534772, 1e-14);
+        assert_eq!(result.dof, 3);
+        assert::close(result.p_value, 0.92542589510361597, 1e-12);
+        assert::close(&result.contributions, &vec![0.016187050359712230, 0.13489208633093525,
+                                                   0.10131894484412470, 0.21762589928057555],
+                      1e-14);
+        assert_eq!(result.bins, vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn chi_squared_gof_dist() {
+        use super::chi_squared_gof_dist;
+
+        let result = chi_squared_gof_dist(&OBSERVED, &Poisson::new(3.0), 0, 5.0);
+        let mut bins = (0..7).map(|i| (i, i + 1)).collect::<Vec<_>>();
+        bins.push((7, 15));
+        assert_eq!(result.bins, bins);
+        assert_eq!(result.contributions.len(), 8);
+        assert::close(result.statistic, 1.0430211595715379, 1e-12);
+        assert_eq!(result.dof, 7);
+        assert::close(result.p_value, 0.99410437298278281, 1e-12);
+
+        let result = chi_squared_gof_dist(&OBSERVED, &Poisson::new(3.0), 0, 0.0);
+        assert_eq!(result.bins.len(), 15);
+        assert_eq!(result.dof, 14);
+    }
+
+    #[test]
+    fn chi_squared_gof_dist_fitted() {
+        use super::chi_squared_gof_dist;
+
+        let data = OBSERVED.iter().enumerate().flat_map(|(k, &count)| {
+            (0..count).map(move |_| k)
+        }).collect::<Vec<_>>();
+        let d = Poisson::fit_mle(&data).unwrap();
+        assert::close(d.lambda(), 3.05, 1e-14);
+        let result = chi_squared_gof_dist(&OBSERVED, &d, 1, 5.0);
+        assert_eq!(result.bins.len(), 8);
+        assert::close(result.statistic, 0.85283384844851248, 1e-12);
+        assert_eq!(result.dof, 6);
+        assert::close(result.p_value, 0.99058164752305155, 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of bins after pooling should exceed the number of \
+                               fitted parameters by more than one (got 1 and 1)")]
+    fn chi_squared_gof_dist_pooled() {
+        use super::chi_squared_gof_dist;
+
+        chi_squared_gof_dist(&[3, 4, 2], &Poisson::new(1.0), 1, 5.0);
+    }
+}
@@ -0,0 +1,319 @@
+//! The Kolmogorov–Smirnov tests.
+//!
+//! The p-value of a sample of size `n ≤ 100` is computed from the exact
+//! distribution of the statistic using the algorithm of Marsaglia, Tsang, and
+//! Wang (2003), and that of a larger sample is computed from the asymptotic
+//! Kolmogorov distribution of `√n D`. The tests assume continuous
+//! distributions; ties, which occur with probability zero under this
+//! assumption, are handled when computing the statistic, but the p-values are
+//! then only approximate and tend to be conservative.
+//!
+//! ```
+//! use probability::prelude::*;
+//! use probability::tests;
+//!
+//! let data = Gaussian::new(0.0, 1.0).samples(&mut source::seeded(42), 100);
+//! let result = tests::ks_one_sample(&data, &Uniform::new(-1.0, 1.0));
+//! assert!(result.p_value < 1e-3);
+//! ```
+
+use distribution::Continuous;
+
+/// The result of a Kolmogorov–Smirnov test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KsResult {
+    /// The statistic, that is, the largest absolute difference between the
+    /// distribution functions.
+    pub statistic: f64,
+    /// The p-value of the two-sided test.
+    pub p_value: f64,
+    /// The point at which the largest difference is attained.
+    pub location: f64,
+}
+
+/// Test whether `data` are drawn from `distribution`.
+///
+/// The statistic is the largest absolute difference between the empirical
+/// distribution function of the data and the distribution function of the
+/// distribution, which is attained either right at or right before one of the
+/// data. It should hold that `data` are nonempty and contain no NaN.
+pub fn ks_one_sample<D>(data: &[f64], distribution: &D) -> KsResult where D: Continuous {
+    should!(!data.is_empty());
+    let data = sort(data);
+    let n = data.len() as f64;
+    let (mut statistic, mut location) = (0.0, data[0]);
+    for (i, &x) in data.iter().enumerate() {
+        let p = distribution.distribution(x);
+        let difference = ((i + 1) as f64 / n - p).max(p - i as f64 / n);
+        if difference > statistic {
+            statistic = difference;
+            location = x;
+        }
+    }
+    KsResult {
+        statistic: statistic,
+        p_value: survival(statistic, data.len()),
+        location: location,
+    }
+}
+
+/// Test whether `a` and `b` are drawn from the same distribution.
+///
+/// The statistic is the largest absolute difference between the empirical
+/// distribution functions of the two samples, and the p-value is that of the
+/// one-sample test of size `n m / (n + m)` rounded to the nearest integer,
+/// where `n` and `m` are the sizes of the samples. It should hold that `a`
+/// and `b` are nonempty and contain no NaN.
+pub fn ks_two_sample(a: &[f64], b: &[f64]) -> KsResult {
+    should!(!a.is_empty() && !b.is_empty());
+    let (a, b) = (sort(a), sort(b));
+    let (n, m) = (a.len(), b.len());
+    let (mut statistic, mut location) = (0.0, a[0].min(b[0]));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        let x = a[i].min(b[j]);
+        while i < n && a[i] == x {
+            i += 1;
+        }
+        while j < m && b[j] == x {
+            j += 1;
+        }
+        let difference = (i as f64 / n as f64 - j as f64 / m as f64).abs();
+        if difference > statistic {
+            statistic = difference;
+            location = x;
+        }
+    }
+    let size = ((n * m) as f64 / (n + m) as f64).round().max(1.0) as usize;
+    KsResult {
+        statistic: statistic,
+        p_value: survival(statistic, size),
+        location: location,
+    }
+}
+
+// Compute the probability that the statistic of a sample of size `n` exceeds
+// `d`.
+fn survival(d: f64, n: usize) -> f64 {
+    if d <= 0.0 {
+        return 1.0;
+    }
+    if d >= 1.0 {
+        return 0.0;
+    }
+    if n > 100 {
+        return kolmogorov((n as f64).sqrt() * d);
+    }
+    let s = n as f64 * d * d;
+    if s > 7.24 || (s > 3.76 && n > 99) {
+        let n = n as f64;
+        return 2.0 * (-(2.000071 + 0.331 / n.sqrt() + 1.409 / n) * s).exp();
+    }
+    (1.0 - marsaglia(d, n)).max(0.0)
+}
+
+// Compute the survival function of the Kolmogorov distribution.
+fn kolmogorov(x: f64) -> f64 {
+    use std::f64::consts::PI;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    if x < 1.18 {
+        for j in 1..100 {
+            let k = (2 * j - 1) as f64;
+            let term = (-k * k * PI * PI / (8.0 * x * x)).exp();
+            sum += term;
+            if term <= ::std::f64::EPSILON * sum {
+                break;
+            }
+        }
+        1.0 - (2.0 * PI).sqrt() / x * sum
+    } else {
+        let mut sign = 1.0;
+        for j in 1..100 {
+            let j = j as f64;
+            let term = (-2.0 * j * j * x * x).exp();
+            sum += sign * term;
+            if term <= ::std::f64::EPSILON * sum {
+                break;
+            }
+            sign = -sign;
+        }
+        2.0 * sum
+    }
+}
+
+// Compute the probability that the statistic of a sample of size `n` is less
+// than `d` using the algorithm of Marsaglia, Tsang, and Wang (2003).
+fn marsaglia(d: f64, n: usize) -> f64 {
+    let nd = n as f64 * d;
+    let k = nd.floor() as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nd;
+    let mut matrix = vec![0.0; m * m];
+    for i in 0..m {
+        for j in 0..(i + 2).min(m) {
+            matrix[i * m + j] = 1.0;
+        }
+    }
+    for i in 0..m {
+        matrix[i * m] -= h.powi(i as i32 + 1);
+        matrix[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 {
+        matrix[(m - 1) * m] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..(i + 1) {
+            for g in 1..(i - j + 2) {
+                matrix[i * m + j] /= g as f64;
+            }
+        }
+    }
+    let (power, mut exponent) = power(&matrix, m, n);
+    let mut value = power[(k - 1) * m + (k - 1)];
+    for i in 1..(n + 1) {
+        value = value * i as f64 / n as f64;
+        if value < 1e-140 {
+            value *= 1e140;
+            exponent -= 140;
+        }
+    }
+    value * 10f64.powi(exponent)
+}
+
+// Raise a square matrix of order `m` to the power `n`, and return the result
+// scaled by a power of ten along with the exponent of the power.
+fn power(matrix: &[f64], m: usize, n: usize) -> (Vec<f64>, i32) {
+    if n == 1 {
+        return (matrix.to_vec(), 0);
+    }
+    let (half, exponent) = power(matrix, m, n / 2);
+    let square = multiply(&half, &half, m);
+    let (mut result, mut exponent) = if n % 2 == 0 {
+        (square, 2 * exponent)
+    } else {
+        (multiply(matrix, &square, m), 2 * exponent)
+    };
+    if result[(m / 2) * m + m / 2] > 1e140 {
+        for value in result.iter_mut() {
+            *value *= 1e-140;
+        }
+        exponent += 140;
+    }
+    (result, exponent)
+}
+
+fn multiply(a: &[f64], b: &[f64], m: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * m];
+    for i in 0..m {
+        for l in 0..m {
+            let value = a[i * m + l];
+            if value == 0.0 {
+                continue;
+            }
+            for j in 0..m {
+                c[i * m + j] += value * b[l * m + j];
+            }
+        }
+    }
+    c
+}
+
+fn sort(data: &[f64]) -> Vec<f64> {
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn ks_one_sample() {
+        use super::ks_one_sample;
+
+        let d = Gaussian::new(0.0, 1.0);
+        let x = [0.61, -1.23, 0.35, 2.08, -0.47, 0.92, -0.15, 1.44, -0.88, 0.27];
+        let result = ks_one_sample(&x, &d);
+        assert::close(result.statistic, 0.20641987319803952, 1e-12);
+        assert::close(result.p_value, 0.71528509285128466, 1e-12);
+        assert_eq!(result.location, 0.27);
+
+        let x = [-1.23, 0.35, 0.35, 2.08, -0.47, 0.92, -0.15, 1.44, 0.35, 0.27];
+        let result = ks_one_sample(&x, &d);
+        assert::close(result.statistic, 0.30641987319803952, 1e-12);
+        assert::close(result.p_value, 0.24891906460270753, 1e-12);
+        assert_eq!(result.location, 0.27);
+
+        let d = Uniform::new(0.0, 1.0);
+        let x = (1..151).map(|i| (i as f64 / 151.0).powf(1.3)).collect::<Vec<_>>();
+        let result = ks_one_sample(&x, &d);
+        assert::close(result.statistic, 0.099051548393101167, 1e-12);
+        assert::close(result.p_value, 0.10536109094796223, 1e-12);
+        assert::close(result.location, 0.32761511827356550, 1e-15);
+
+        let result = ks_one_sample(&[0.7], &d);
+        assert_eq!(result.statistic, 0.7);
+        assert::close(result.p_value, 0.6, 1e-14);
+    }
+
+    #[test]
+    fn ks_one_sample_rate() {
+        use super::ks_one_sample;
+
+        let d = Gaussian::new(1.0, 2.0);
+        let mut source = source::seeded(42);
+        let m = 2000;
+        let rejected = (0..m).filter(|_| {
+            ks_one_sample(&d.samples(&mut source, 50), &d).p_value < 0.05
+        }).count();
+        let rate = rejected as f64 / m as f64;
+        assert!(0.04 < rate && rate < 0.06);
+
+        let x = d.samples(&mut source, 1000);
+        let result = ks_one_sample(&x, &Uniform::new(-1.0, 3.0));
+        assert!(result.p_value < 1e-20);
+        let result = ks_one_sample(&x, &Gaussian::new(1.0, 2.0));
+        assert!(result.p_value > 0.05);
+    }
+
+    #[test]
+    fn ks_two_sample() {
+        use super::ks_two_sample;
+
+        let a = [0.8, 1.9, 2.4, 3.1, 3.3, 4.0, 4.7, 5.5];
+        let b = [2.2, 2.4, 3.6, 4.1, 4.4, 5.0, 5.2, 5.9, 6.3, 7.1];
+        let result = ks_two_sample(&a, &b);
+        assert::close(result.statistic, 0.45, 1e-15);
+        assert::close(result.p_value, 0.2929, 1e-12);
+        assert_eq!(result.location, 4.0);
+        assert_eq!(ks_two_sample(&b, &a), result);
+
+        let result = ks_two_sample(&a, &a);
+        assert_eq!(result.statistic, 0.0);
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn ks_two_sample_rate() {
+        use super::ks_two_sample;
+
+        let d = Exponential::new(2.0);
+        let mut source = source::seeded(42);
+        let m = 2000;
+        let rejected = (0..m).filter(|_| {
+            ks_two_sample(&d.samples(&mut source, 40), &d.samples(&mut source, 60)).p_value < 0.05
+        }).count();
+        let rate = rejected as f64 / m as f64;
+        assert!(rate < 0.06);
+
+        let a = Gaussian::new(0.0, 1.0).samples(&mut source, 500);
+        let b = Uniform::new(-1.0, 1.0).samples(&mut source, 500);
+        assert!(ks_two_sample(&a, &b).p_value < 1e-6);
+    }
+}
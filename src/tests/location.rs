@@ -0,0 +1,268 @@
+//! Tests of the mean.
+//!
+//! ```
+//! use probability::tests::{self, Alternative};
+//!
+//! let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 5.7, 5.5];
+//! let result = tests::t_test_one_sample(&data, 5.0, Alternative::TwoSided);
+//! let (lower, upper) = result.interval(0.95);
+//! assert!(result.p_value < 0.01 && lower > 5.0 && upper < 6.0);
+//! ```
+
+use distribution::{Distribution, Gaussian, Inverse};
+use statistics;
+use tests::Alternative;
+
+/// The result of a t-test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TTestResult {
+    /// The t statistic.
+    pub statistic: f64,
+    /// The number of degrees of freedom, which is fractional for Welch’s test.
+    pub dof: f64,
+    /// The p-value.
+    pub p_value: f64,
+    /// The estimate of the mean or of the difference of the means.
+    pub estimate: f64,
+    /// The standard error of the estimate.
+    pub standard_error: f64,
+    /// The alternative hypothesis.
+    pub alternative: Alternative,
+}
+
+/// The result of a z-test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZTestResult {
+    /// The z statistic.
+    pub statistic: f64,
+    /// The p-value.
+    pub p_value: f64,
+    /// The estimate of the mean.
+    pub estimate: f64,
+    /// The standard error of the estimate.
+    pub standard_error: f64,
+    /// The alternative hypothesis.
+    pub alternative: Alternative,
+}
+
+impl TTestResult {
+    /// Compute the confidence interval of the estimate at level `level`.
+    ///
+    /// The interval is two-sided or one-sided in accordance with the
+    /// alternative hypothesis. It should hold that `0 < level < 1`.
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        use math;
+
+        interval(self.estimate, self.standard_error, self.alternative, level,
+                 |p| math::student_inverse(p, self.dof))
+    }
+}
+
+impl ZTestResult {
+    /// Compute the confidence interval of the estimate at level `level`.
+    ///
+    /// The interval is two-sided or one-sided in accordance with the
+    /// alternative hypothesis. It should hold that `0 < level < 1`.
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        interval(self.estimate, self.standard_error, self.alternative, level,
+                 |p| Gaussian::standard().inverse(p))
+    }
+}
+
+/// Test whether the mean of `data` equals `mu0` using Student’s t-test.
+///
+/// It should hold that `data` contain at least two values.
+pub fn t_test_one_sample(data: &[f64], mu0: f64, alternative: Alternative) -> TTestResult {
+    let n = data.len() as f64;
+    should!(n > 1.0);
+    let standard_error = (statistics::variance(data) / n).sqrt();
+    student(statistics::mean(data), mu0, standard_error, n - 1.0, alternative)
+}
+
+/// Test whether the paired data `a` and `b` have the same mean using
+/// Student’s t-test of their differences.
+///
+/// It should hold that the slices have the same length, which is at least
+/// two; the function panics if the lengths differ.
+pub fn t_test_paired(a: &[f64], b: &[f64], alternative: Alternative) -> TTestResult {
+    assert!(a.len() == b.len(),
+            "the slices should have the same length (got {} and {})", a.len(), b.len());
+    let differences = a.iter().zip(b).map(|(&a, &b)| a - b).collect::<Vec<_>>();
+    t_test_one_sample(&differences, 0.0, alternative)
+}
+
+/// Test whether `a` and `b` have the same mean using Student’s t-test.
+///
+/// If `equal_variance` is true, the variances are assumed to be equal, and
+/// the pooled variance is used; otherwise, Welch’s test is performed with
+/// the degrees of freedom given by the Welch–Satterthwaite equation. The
+/// estimate is the difference between the mean of `a` and that of `b`. It
+/// should hold that each slice contains at least two values.
+pub fn t_test_two_sample(a: &[f64], b: &[f64], alternative: Alternative, equal_variance: bool)
+                         -> TTestResult {
+    let (n, m) = (a.len() as f64, b.len() as f64);
+    should!(n > 1.0 && m > 1.0);
+    let (va, vb) = (statistics::variance(a), statistics::variance(b));
+    let (standard_error, dof) = if equal_variance {
+        let dof = n + m - 2.0;
+        let variance = ((n - 1.0) * va + (m - 1.0) * vb) / dof;
+        ((variance * (1.0 / n + 1.0 / m)).sqrt(), dof)
+    } else {
+        let (sa, sb) = (va / n, vb / m);
+        ((sa + sb).sqrt(), (sa + sb) * (sa + sb) / (sa * sa / (n - 1.0) + sb * sb / (m - 1.0)))
+    };
+    let estimate = statistics::mean(a) - statistics::mean(b);
+    student(estimate, 0.0, standard_error, dof, alternative)
+}
+
+/// Test whether the mean of `data` equals `mu0` given the standard deviation
+/// `sigma` using the z-test.
+///
+/// It should hold that `data` are nonempty and `sigma > 0`.
+pub fn z_test(data: &[f64], mu0: f64, sigma: f64, alternative: Alternative) -> ZTestResult {
+    should!(sigma > 0.0);
+    let estimate = statistics::mean(data);
+    let standard_error = sigma / (data.len() as f64).sqrt();
+    let statistic = (estimate - mu0) / standard_error;
+    let gaussian = Gaussian::standard();
+    ZTestResult {
+        statistic: statistic,
+        p_value: p_value(statistic, alternative, |z| gaussian.survival(z)),
+        estimate: estimate,
+        standard_error: standard_error,
+        alternative: alternative,
+    }
+}
+
+fn interval<F>(estimate: f64, error: f64, alternative: Alternative, level: f64, inverse: F)
+               -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    use std::f64::INFINITY;
+
+    should!(0.0 < level && level < 1.0);
+    match alternative {
+        Alternative::TwoSided => {
+            let quantile = inverse(0.5 + level / 2.0);
+            (estimate - quantile * error, estimate + quantile * error)
+        },
+        Alternative::Less => (-INFINITY, estimate + inverse(level) * error),
+        Alternative::Greater => (estimate - inverse(level) * error, INFINITY),
+    }
+}
+
+fn p_value<F>(statistic: f64, alternative: Alternative, survival: F) -> f64
+    where F: Fn(f64) -> f64
+{
+    match alternative {
+        Alternative::TwoSided => (2.0 * survival(statistic.abs())).min(1.0),
+        Alternative::Less => survival(-statistic),
+        Alternative::Greater => survival(statistic),
+    }
+}
+
+fn student(estimate: f64, mu0: f64, standard_error: f64, dof: f64, alternative: Alternative)
+           -> TTestResult {
+    use math;
+
+    let statistic = (estimate - mu0) / standard_error;
+    TTestResult {
+        statistic: statistic,
+        dof: dof,
+        p_value: p_value(statistic, alternative, |t| math::student(t, dof)),
+        estimate: estimate,
+        standard_error: standard_error,
+        alternative: alternative,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use std::f64::INFINITY;
+    use tests::Alternative;
+
+    const A: [f64; 8] = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 5.7, 5.5];
+    const B: [f64; 6] = [4.2, 4.8, 5.0, 4.4, 4.9, 4.1];
+    const C: [f64; 8] = [5.0, 4.7, 5.9, 5.6, 6.3, 5.0, 5.4, 5.6];
+
+    #[test]
+    fn t_test_one_sample() {
+        let result = super::t_test_one_sample(&A, 5.0, Alternative::TwoSided);
+        assert::close(result.statistic, 3.7453528517603949942, 1e-12);
+        assert_eq!(result.dof, 7.0);
+        assert::close(result.p_value, 0.0072119532198394233444, 1e-12);
+        assert::close(result.estimate, 5.4875, 1e-14);
+        let (lower, upper) = result.interval(0.95);
+        assert::close(lower, 5.1797174309665739611, 1e-12);
+        assert::close(upper, 5.7952825690334260389, 1e-12);
+
+        let result = super::t_test_one_sample(&A, 5.6, Alternative::Less);
+        assert::close(result.statistic, -0.8643121965600911525, 1e-12);
+        assert::close(result.p_value, 0.20802429084368421837, 1e-12);
+        let (lower, upper) = result.interval(0.9);
+        assert_eq!(lower, -INFINITY);
+        assert::close(upper, 5.6716683392808808015, 1e-12);
+    }
+
+    #[test]
+    fn t_test_paired() {
+        let result = super::t_test_paired(&A, &C, Alternative::TwoSided);
+        assert::close(result.statistic, 0.56407607481776620892, 1e-12);
+        assert_eq!(result.dof, 7.0);
+        assert::close(result.p_value, 0.59030902248521546452, 1e-12);
+        assert::close(result.estimate, 0.05, 1e-14);
+        let (lower, upper) = result.interval(0.95);
+        assert::close(lower, -0.15960153755472743617, 1e-12);
+        assert::close(upper, 0.25960153755472743617, 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "the slices should have the same length (got 8 and 6)")]
+    fn t_test_paired_length() {
+        super::t_test_paired(&A, &B, Alternative::TwoSided);
+    }
+
+    #[test]
+    fn t_test_two_sample_pooled() {
+        let result = super::t_test_two_sample(&A, &B, Alternative::TwoSided, true);
+        assert::close(result.statistic, 4.5541203356482758931, 1e-12);
+        assert_eq!(result.dof, 12.0);
+        assert::close(result.p_value, 0.00066143361301103203616, 1e-12);
+        assert::close(result.standard_error, 0.20219784842427826242, 1e-12);
+        let (lower, upper) = result.interval(0.95);
+        assert::close(lower, 0.48028206705540617894, 1e-12);
+        assert::close(upper, 1.3613845996112604877, 1e-12);
+    }
+
+    #[test]
+    fn t_test_two_sample_welch() {
+        let result = super::t_test_two_sample(&A, &B, Alternative::TwoSided, false);
+        assert::close(result.statistic, 4.5263919810676081968, 1e-12);
+        assert::close(result.dof, 10.67117126486685002, 1e-12);
+        assert::close(result.p_value, 0.00093142621600076532691, 1e-12);
+        assert::close(result.estimate, 0.92083333333333333333, 1e-14);
+        let (lower, upper) = result.interval(0.95);
+        assert::close(lower, 0.4713834585441835547, 1e-12);
+        assert::close(upper, 1.370283208122483112, 1e-12);
+
+        let result = super::t_test_two_sample(&A, &B, Alternative::Greater, false);
+        assert::close(result.p_value, 0.00046571310800038266345, 1e-12);
+        let (lower, upper) = result.interval(0.9);
+        assert::close(lower, 0.64291543601306264311, 1e-12);
+        assert_eq!(upper, INFINITY);
+    }
+
+    #[test]
+    fn z_test() {
+        let result = super::z_test(&A, 5.0, 0.4, Alternative::TwoSided);
+        assert::close(result.statistic, 3.4471455582844191815, 1e-12);
+        assert::close(result.p_value, 0.00056654325458016490645, 1e-12);
+        let (lower, upper) = result.interval(0.95);
+        assert::close(lower, 5.2103192351300644109, 1e-12);
+        assert::close(upper, 5.7646807648699355891, 1e-12);
+
+        let result = super::z_test(&A, 5.0, 0.4, Alternative::Greater);
+        assert::close(result.p_value, 0.00028327162729008245323, 1e-12);
+    }
+}
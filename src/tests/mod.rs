@@ -0,0 +1,31 @@
+//! Hypothesis tests.
+
+/// An alternative hypothesis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alternative {
+    /// The parameter differs from its hypothesized value.
+    TwoSided,
+    /// The parameter is less than its hypothesized value.
+    Less,
+    /// The parameter is greater than its hypothesized value.
+    Greater,
+}
+
+mod anova;
+mod chi_squared;
+mod kolmogorov;
+mod location;
+mod portmanteau;
+mod quadratic;
+mod rank;
+
+pub use self::anova::{AnovaResult, KruskalWallisResult, anova_one_way, kruskal_wallis};
+pub use self::chi_squared::{ChiSquaredResult, chi_squared_gof, chi_squared_gof_dist};
+pub use self::kolmogorov::{KsResult, ks_one_sample, ks_two_sample};
+pub use self::location::{TTestResult, ZTestResult, t_test_one_sample, t_test_paired};
+pub use self::location::{t_test_two_sample, z_test};
+pub use self::portmanteau::{LjungBoxResult, ljung_box};
+pub use self::quadratic::{AdResult, CvmResult};
+pub use self::quadratic::{anderson_darling, anderson_darling_gaussian, cramer_von_mises};
+pub use self::rank::{RankOptions, RankTestResult, Reference, ZeroMethod, mann_whitney_u};
+pub use self::rank::wilcoxon_signed_rank;
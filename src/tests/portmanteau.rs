@@ -0,0 +1,73 @@
+//! The tests of serial correlation.
+//!
+//! ```
+//! use probability::prelude::*;
+//! use probability::tests;
+//!
+//! let data = Gaussian::new(0.0, 1.0).samples(&mut source::seeded(42), 500);
+//! assert!(tests::ljung_box(&data, 10).p_value > 0.01);
+//! ```
+
+use distribution::{Distribution, Gamma};
+use statistics;
+
+/// The result of the Ljung–Box test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LjungBoxResult {
+    /// The Q statistic.
+    pub statistic: f64,
+    /// The number of degrees of freedom, which equals the number of lags.
+    pub dof: usize,
+    /// The p-value.
+    pub p_value: f64,
+}
+
+/// Test whether `x` is serially uncorrelated at lags from one up to `lags`
+/// using the Ljung–Box test.
+///
+/// The statistic is `n (n + 2) Σ ρ_k² / (n - k)`, where `ρ_k` is the
+/// autocorrelation given by `statistics::acf`, and the p-value is computed from
+/// the chi-squared distribution with `lags` degrees of freedom. It should hold
+/// that `0 < lags < n`.
+pub fn ljung_box(x: &[f64], lags: usize) -> LjungBoxResult {
+    should!(lags > 0);
+    let n = x.len() as f64;
+    let rho = statistics::acf(x, lags);
+    let sum = (1..(lags + 1)).map(|k| rho[k] * rho[k] / (n - k as f64)).sum::<f64>();
+    let statistic = n * (n + 2.0) * sum;
+    LjungBoxResult {
+        statistic: statistic,
+        dof: lags,
+        p_value: Gamma::new(lags as f64 / 2.0, 2.0).survival(statistic),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn ljung_box() {
+        let x = [2.1, 3.4, 1.9, 4.8, 5.2, 3.3, 2.7, 4.1, 6.0, 5.5, 3.8, 2.2, 3.9, 4.4, 5.1, 6.3,
+                 4.0, 3.1, 2.5, 3.6];
+        let result = super::ljung_box(&x, 3);
+        assert::close(result.statistic, 9.6067576004818067131, 1e-12);
+        assert_eq!(result.dof, 3);
+        assert::close(result.p_value, 0.022222344664290604675, 1e-12);
+        let result = super::ljung_box(&x, 6);
+        assert::close(result.statistic, 14.240883193987990316, 1e-12);
+        assert::close(result.p_value, 0.027058107324165516748, 1e-12);
+    }
+
+    #[test]
+    fn ljung_box_autoregressive() {
+        let mut source = source::seeded(42);
+        let noise = Gaussian::new(0.0, 1.0).samples(&mut source, 500);
+        let mut x = vec![0.0; 500];
+        for i in 1..500 {
+            x[i] = 0.5 * x[i - 1] + noise[i];
+        }
+        assert!(super::ljung_box(&x, 10).p_value < 1e-6);
+    }
+}
@@ -0,0 +1,279 @@
+//! The Anderson–Darling and Cramér–von Mises tests.
+//!
+//! Both statistics are quadratic measures of the discrepancy between the
+//! empirical distribution function of the data and the hypothesized
+//! distribution function. The Anderson–Darling statistic weighs the
+//! discrepancy by the inverse of the variance of the empirical distribution
+//! function, which makes it considerably more sensitive in the tails than the
+//! Kolmogorov–Smirnov statistic.
+//!
+//! ```
+//! use probability::prelude::*;
+//! use probability::tests;
+//!
+//! let data = Gaussian::new(2.0, 3.0).samples(&mut source::seeded(42), 100);
+//! let result = tests::anderson_darling_gaussian(&data);
+//! assert!(result.p_value > 0.05);
+//! ```
+
+use distribution::{Continuous, Gaussian};
+
+/// The result of an Anderson–Darling test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdResult {
+    /// The statistic `A²`.
+    pub statistic: f64,
+    /// The p-value.
+    pub p_value: f64,
+    /// The number of data.
+    pub n: usize,
+}
+
+/// The result of a Cramér–von Mises test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CvmResult {
+    /// The statistic `W²`.
+    pub statistic: f64,
+    /// The p-value.
+    pub p_value: f64,
+    /// The number of data.
+    pub n: usize,
+}
+
+/// Test whether `data` are drawn from `distribution` using the
+/// Anderson–Darling statistic.
+///
+/// The distribution should be fully specified in advance rather than
+/// estimated from the data; see `anderson_darling_gaussian` for the latter.
+/// The p-value is computed using the approximation of Marsaglia and Marsaglia
+/// (2004) to the distribution of the statistic for the given sample size. A
+/// datum at which the distribution function or its complement vanishes makes
+/// the statistic infinite in theory; the corresponding logarithm is limited to
+/// that of the smallest positive number instead, which yields a very large
+/// statistic and a negligible p-value. It should hold that `data` are nonempty
+/// and contain no NaN.
+pub fn anderson_darling<D>(data: &[f64], distribution: &D) -> AdResult where D: Continuous {
+    let statistic = anderson(&prepare(data, distribution));
+    let n = data.len();
+    AdResult {
+        statistic: statistic,
+        p_value: (1.0 - marsaglia(n as f64, statistic)).clamp(0.0, 1.0),
+        n: n,
+    }
+}
+
+/// Test whether `data` are drawn from a Gaussian distribution with unknown
+/// parameters using the Anderson–Darling statistic.
+///
+/// The parameters are estimated by the sample mean and the sample standard
+/// deviation, and the p-value is computed from the statistic multiplied by
+/// `1 + 0.75 / n + 2.25 / n²` using the approximation of D’Agostino and
+/// Stephens (1986), which is intended for `n ≥ 8`. The reported statistic is
+/// not multiplied. It should hold that `data` contain at least two distinct
+/// values and no NaN.
+pub fn anderson_darling_gaussian(data: &[f64]) -> AdResult {
+    should!(data.len() > 1);
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let variance = data.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    let statistic = anderson(&prepare(data, &Gaussian::new(mean, variance.sqrt())));
+    AdResult {
+        statistic: statistic,
+        p_value: stephens(statistic * (1.0 + 0.75 / n + 2.25 / (n * n))),
+        n: data.len(),
+    }
+}
+
+/// Test whether `data` are drawn from `distribution` using the Cramér–von
+/// Mises statistic.
+///
+/// The distribution should be fully specified in advance. The p-value is
+/// computed from the asymptotic distribution of the statistic modified as
+/// `(W² - 0.4 / n + 0.6 / n²) (1 + 1 / n)` following Stephens (1970), which
+/// makes the asymptotic distribution applicable for `n ≥ 5`. It should hold
+/// that `data` are nonempty and contain no NaN.
+pub fn cramer_von_mises<D>(data: &[f64], distribution: &D) -> CvmResult where D: Continuous {
+    let values = prepare(data, distribution);
+    let n = values.len() as f64;
+    let statistic = 1.0 / (12.0 * n) + values.iter().enumerate().map(|(i, &(p, _))| {
+        let difference = p - (2 * i + 1) as f64 / (2.0 * n);
+        difference * difference
+    }).sum::<f64>();
+    let modified = (statistic - 0.4 / n + 0.6 / (n * n)) * (1.0 + 1.0 / n);
+    CvmResult {
+        statistic: statistic,
+        p_value: (1.0 - cramer(modified)).clamp(0.0, 1.0),
+        n: data.len(),
+    }
+}
+
+// Evaluate the distribution function and the survival function at the sorted
+// data.
+fn prepare<D>(data: &[f64], distribution: &D) -> Vec<(f64, f64)> where D: Continuous {
+    should!(!data.is_empty());
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    data.iter().map(|&x| (distribution.distribution(x), distribution.survival(x))).collect()
+}
+
+// Compute the Anderson–Darling statistic given the values of the distribution
+// function and the survival function at the sorted data.
+fn anderson(values: &[(f64, f64)]) -> f64 {
+    let n = values.len();
+    let sum = values.iter().enumerate().map(|(i, &(p, _))| {
+        let q = values[n - 1 - i].1;
+        (2 * i + 1) as f64 * (p.max(f64::MIN_POSITIVE).ln() + q.max(f64::MIN_POSITIVE).ln())
+    }).sum::<f64>();
+    -(n as f64) - sum / n as f64
+}
+
+// Compute the distribution function of the Anderson–Darling statistic for a
+// sample of size `n` using the approximation of Marsaglia and Marsaglia
+// (2004).
+fn marsaglia(n: f64, z: f64) -> f64 {
+    if z <= 0.0 {
+        return 0.0;
+    }
+    let x = if z < 2.0 {
+        (-1.2337141 / z).exp() / z.sqrt() *
+            (2.00012 + (0.247105 - (0.0649821 - (0.0347962 - (0.011672 - 0.00168691 * z) * z) *
+                                    z) * z) * z)
+    } else {
+        (-(1.0776 - (2.30695 - (0.43424 - (0.082433 - (0.008056 - 0.0003146 * z) * z) * z) *
+                     z) * z).exp()).exp()
+    };
+    if x > 0.8 {
+        return x + (-130.2137 + (745.2337 - (1705.091 - (1950.646 - (1116.360 - 255.7844 * x) *
+                                                         x) * x) * x) * x) / n;
+    }
+    let c = 0.01265 + 0.1757 / n;
+    if x < c {
+        let v = x / c;
+        let v = v.sqrt() * (1.0 - v) * (49.0 * v - 102.0);
+        return x + v * (0.0037 / (n * n) + 0.00078 / n + 0.00006) / n;
+    }
+    let v = (x - c) / (0.8 - c);
+    let v = -0.00022633 + (6.54034 - (14.6538 - (14.458 - (8.259 - 1.91864 * v) * v) * v) *
+                           v) * v;
+    x + v * (0.04213 + 0.01365 / n) / n
+}
+
+// Compute the p-value of the modified Anderson–Darling statistic for the
+// Gaussian distribution with estimated parameters using the approximation of
+// D’Agostino and Stephens (1986).
+fn stephens(a: f64) -> f64 {
+    let p = if a >= 0.6 {
+        (1.2937 - 5.709 * a + 0.0186 * a * a).exp()
+    } else if a >= 0.34 {
+        (0.9177 - 4.279 * a - 1.38 * a * a).exp()
+    } else if a >= 0.2 {
+        1.0 - (-8.318 + 42.796 * a - 59.938 * a * a).exp()
+    } else {
+        1.0 - (-13.436 + 101.14 * a - 223.73 * a * a).exp()
+    };
+    p.clamp(0.0, 1.0)
+}
+
+// Compute the asymptotic distribution function of the Cramér–von Mises
+// statistic using the series in terms of the modified Bessel function of the
+// second kind given by Csörgő and Faraway (1996).
+fn cramer(x: f64) -> f64 {
+    use std::f64::consts::PI;
+
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut ratio = PI.sqrt();
+    let mut sum = 0.0;
+    for k in 0..100 {
+        let y = (4 * k + 1) as f64;
+        let q = y * y / (16.0 * x);
+        let term = ratio / (PI.powf(1.5) * x.sqrt()) * y.sqrt() * bessel(q);
+        sum += term;
+        if term.abs() < 1e-16 {
+            break;
+        }
+        ratio *= (k as f64 + 0.5) / (k as f64 + 1.0);
+    }
+    sum
+}
+
+// Compute `exp(-q) K(q)` where `K` is the modified Bessel function of the
+// second kind of order one fourth using its integral representation.
+fn bessel(q: f64) -> f64 {
+    use math;
+
+    let limit = (1.0 + 50.0 / q).acosh();
+    math::simpson(|t| (-q * (1.0 + t.cosh())).exp() * (t / 4.0).cosh(), 0.0, limit, 400)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn anderson_darling() {
+        use super::{anderson_darling, marsaglia};
+
+        let d = Gaussian::new(0.0, 1.0);
+        let x = [0.61, -1.23, 0.35, 2.08, -0.47, 0.92, -0.15, 1.44, -0.88, 0.27];
+        let result = anderson_darling(&x, &d);
+        assert::close(result.statistic, 0.48224770132328230, 1e-12);
+        assert::close(result.p_value, 0.76091524523112807, 1e-12);
+        assert_eq!(result.n, 10);
+
+        let x = [0.0, 0.2, 0.4, 0.6, 0.8];
+        let result = anderson_darling(&x, &Uniform::new(0.0, 1.0));
+        assert!(result.statistic.is_finite() && result.statistic > 100.0);
+        assert!(result.p_value < 1e-3);
+
+        let p = [1.933, 2.492, 3.857].iter().map(|&z| 1.0 - marsaglia(1e6, z))
+                                             .collect::<Vec<_>>();
+        assert::close(&p, &vec![0.10, 0.05, 0.01], 3e-4);
+    }
+
+    #[test]
+    fn anderson_darling_gaussian() {
+        use super::{anderson_darling_gaussian, stephens};
+
+        let x = [0.31, 2.47, -0.52, 1.08, 0.77, 3.91, -0.06, 1.55, 0.94, 1.22, 2.03, 0.48];
+        let result = anderson_darling_gaussian(&x);
+        assert::close(result.statistic, 0.26822828461256559, 1e-12);
+        assert::close(result.p_value, 0.61501191147258854, 1e-12);
+        assert_eq!(result.n, 12);
+
+        let p = [0.631, 0.754, 0.884, 1.047, 1.159].iter().map(|&a| stephens(a))
+                                                          .collect::<Vec<_>>();
+        assert::close(&p, &vec![0.10, 0.05, 0.025, 0.01, 0.005], 2e-3);
+    }
+
+    #[test]
+    fn anderson_darling_power() {
+        use tests::{anderson_darling, ks_one_sample};
+
+        let d = Gaussian::new(0.0, 1.0);
+        let c = Gamma::new(1.5, 2.0);
+        let mut source = source::seeded(13);
+        let x = (0..100).map(|_| d.sample(&mut source) / (c.sample(&mut source) / 3.0).sqrt())
+                        .collect::<Vec<_>>();
+        assert!(anderson_darling(&x, &d).p_value < 1e-3);
+        assert!(ks_one_sample(&x, &d).p_value > 0.2);
+    }
+
+    #[test]
+    fn cramer_von_mises() {
+        use super::{cramer, cramer_von_mises};
+
+        let d = Gaussian::new(0.0, 1.0);
+        let x = [0.61, -1.23, 0.35, 2.08, -0.47, 0.92, -0.15, 1.44, -0.88, 0.27];
+        let result = cramer_von_mises(&x, &d);
+        assert::close(result.statistic, 0.076305525584284257, 1e-12);
+        assert::close(result.p_value, 0.89695935155659695, 1e-10);
+        assert_eq!(result.n, 10);
+
+        let p = [0.34730, 0.46136, 0.74346].iter().map(|&x| 1.0 - cramer(x))
+                                               .collect::<Vec<_>>();
+        assert::close(&p, &vec![0.10, 0.05, 0.01], 1e-5);
+    }
+}
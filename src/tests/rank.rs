@@ -0,0 +1,410 @@
+//! The rank tests.
+//!
+//! The tests follow the conventions of R’s `wilcox.test`. The p-value is
+//! computed from the exact null distribution of the statistic when the
+//! samples are at most as large as the limit given in the options and there
+//! are neither ties nor zero differences; otherwise, it is computed from the
+//! normal approximation with the variance corrected for ties and, optionally,
+//! with a continuity correction.
+//!
+//! ```
+//! use probability::tests::{self, Alternative, RankOptions, Reference};
+//!
+//! let a = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+//! let b = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+//! let result = tests::wilcoxon_signed_rank(&a, Reference::Paired(&b), Alternative::Greater,
+//!                                          RankOptions::default());
+//! assert!(result.exact && result.p_value < 0.05);
+//! ```
+
+use distribution::{Distribution, Gaussian};
+use statistics;
+use tests::Alternative;
+
+/// Options of the rank tests.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RankOptions {
+    /// The largest size of a sample for which the exact distribution is used.
+    pub exact_limit: usize,
+    /// The flag telling whether the normal approximation is corrected for
+    /// continuity.
+    pub correction: bool,
+    /// The treatment of zero differences in the signed-rank test.
+    pub zero_method: ZeroMethod,
+}
+
+/// The result of a rank test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RankTestResult {
+    /// The statistic, that is, the Mann–Whitney `U` of the first sample or
+    /// the sum of the ranks of the positive differences.
+    pub statistic: f64,
+    /// The p-value.
+    pub p_value: f64,
+    /// The flag telling whether the p-value is computed from the exact
+    /// distribution.
+    pub exact: bool,
+    /// The rank-biserial correlation, which ranges from `-1` to `1`.
+    pub rank_biserial: f64,
+    /// The Hodges–Lehmann estimate, that is, the median of the pairwise
+    /// differences between the samples or the pseudomedian of the data.
+    pub estimate: f64,
+    /// The alternative hypothesis.
+    pub alternative: Alternative,
+}
+
+/// The reference against which the signed-rank test is performed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reference<'l> {
+    /// A location, which is subtracted from the data.
+    Location(f64),
+    /// Paired data, which are subtracted from the data pairwise.
+    Paired(&'l [f64]),
+}
+
+/// The treatment of zero differences in the signed-rank test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZeroMethod {
+    /// Discard zero differences before ranking as proposed by Wilcoxon.
+    Wilcox,
+    /// Rank zero differences together with the rest and then drop their
+    /// ranks as proposed by Pratt.
+    Pratt,
+}
+
+impl Default for RankOptions {
+    #[inline]
+    fn default() -> Self {
+        RankOptions { exact_limit: 12, correction: true, zero_method: ZeroMethod::Wilcox }
+    }
+}
+
+/// Test whether `a` and `b` are drawn from the same distribution using the
+/// Mann–Whitney U test, also known as the Wilcoxon rank-sum test.
+///
+/// The alternatives refer to the location of `a` relative to that of `b`.
+/// It should hold that the samples are nonempty and contain no NaN.
+pub fn mann_whitney_u(a: &[f64], b: &[f64], alternative: Alternative, options: RankOptions)
+                      -> RankTestResult {
+    let (n, m) = (a.len(), b.len());
+    should!(n > 0 && m > 0);
+    let pooled = a.iter().chain(b).cloned().collect::<Vec<_>>();
+    let (ranks, ties) = rank(&pooled);
+    let statistic = ranks[..n].iter().sum::<f64>() - (n * (n + 1)) as f64 / 2.0;
+    let product = (n * m) as f64;
+    let exact = ties == 0.0 && n <= options.exact_limit && m <= options.exact_limit;
+    let p_value = if exact {
+        tail(&rank_sum_counts(n, m), statistic as usize, alternative)
+    } else {
+        let total = (n + m) as f64;
+        let variance = product / 12.0 * (total + 1.0 - ties / (total * (total - 1.0)));
+        normal(statistic - product / 2.0, variance.sqrt(), alternative, options.correction)
+    };
+    let differences = a.iter().flat_map(|&a| b.iter().map(move |&b| a - b)).collect::<Vec<_>>();
+    RankTestResult {
+        statistic: statistic,
+        p_value: p_value,
+        exact: exact,
+        rank_biserial: 2.0 * statistic / product - 1.0,
+        estimate: statistics::median(&differences),
+        alternative: alternative,
+    }
+}
+
+/// Test whether `data` are symmetric about a reference using the Wilcoxon
+/// signed-rank test.
+///
+/// The differences between the data and the reference are ranked by their
+/// absolute values, and the zero ones are treated in accordance with the
+/// options. The alternatives refer to the location of the differences
+/// relative to zero, and the estimate is the pseudomedian of the data in the
+/// case of a location and that of the differences in the case of paired
+/// data. It should hold that the data contain no NaN; the function panics if
+/// the paired data have a different length.
+pub fn wilcoxon_signed_rank(data: &[f64], reference: Reference, alternative: Alternative,
+                            options: RankOptions) -> RankTestResult {
+    let (mut differences, shift) = match reference {
+        Reference::Location(mu) => (data.iter().map(|&x| x - mu).collect::<Vec<_>>(), mu),
+        Reference::Paired(other) => {
+            assert!(data.len() == other.len(),
+                    "the slices should have the same length (got {} and {})",
+                    data.len(), other.len());
+            (data.iter().zip(other).map(|(&x, &y)| x - y).collect(), 0.0)
+        },
+    };
+    if options.zero_method == ZeroMethod::Wilcox {
+        differences.retain(|&d| d != 0.0);
+    }
+    let (n, zeros) = (differences.len(), differences.iter().filter(|&&d| d == 0.0).count());
+    let (ranks, _) = rank(&differences.iter().map(|d| d.abs()).collect::<Vec<_>>());
+    let (mut positive, mut negative) = (0.0, 0.0);
+    for (&d, &r) in differences.iter().zip(&ranks) {
+        if d > 0.0 {
+            positive += r;
+        } else if d < 0.0 {
+            negative += r;
+        }
+    }
+    let nonzero = differences.iter().zip(&ranks).filter(|&(&d, _)| d != 0.0)
+                                                .map(|(_, &r)| r).collect::<Vec<_>>();
+    let (_, ties) = rank(&nonzero);
+    let exact = ties == 0.0 && zeros == 0 && n <= options.exact_limit;
+    let p_value = if exact {
+        tail(&signed_rank_counts(n), positive as usize, alternative)
+    } else {
+        let (n, zeros) = (n as f64, zeros as f64);
+        let mean = (n * (n + 1.0) - zeros * (zeros + 1.0)) / 4.0;
+        let variance = (n * (n + 1.0) * (2.0 * n + 1.0) -
+                        zeros * (zeros + 1.0) * (2.0 * zeros + 1.0)) / 24.0 - ties / 48.0;
+        normal(positive - mean, variance.sqrt(), alternative, options.correction)
+    };
+    let mut averages = Vec::with_capacity(n * (n + 1) / 2);
+    for i in 0..n {
+        for j in i..n {
+            averages.push((differences[i] + differences[j]) / 2.0);
+        }
+    }
+    RankTestResult {
+        statistic: positive,
+        p_value: p_value,
+        exact: exact,
+        rank_biserial: (positive - negative) / (positive + negative),
+        estimate: statistics::median(&averages) + shift,
+        alternative: alternative,
+    }
+}
+
+fn normal(difference: f64, sigma: f64, alternative: Alternative, correction: bool) -> f64 {
+    let correction = if !correction || difference == 0.0 {
+        0.0
+    } else {
+        match alternative {
+            Alternative::TwoSided => 0.5 * difference.signum(),
+            Alternative::Less => -0.5,
+            Alternative::Greater => 0.5,
+        }
+    };
+    let z = (difference - correction) / sigma;
+    let gaussian = Gaussian::standard();
+    match alternative {
+        Alternative::TwoSided => 2.0 * gaussian.distribution(z).min(gaussian.survival(z)),
+        Alternative::Less => gaussian.distribution(z),
+        Alternative::Greater => gaussian.survival(z),
+    }
+}
+
+// Compute the ranks with ties receiving the average of the ranks they span
+// and the sum of `t³ - t` over the groups of `t` ties.
+pub fn rank(data: &[f64]) -> (Vec<f64>, f64) {
+    let n = data.len();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| data[i].partial_cmp(&data[j]).unwrap());
+    let (mut ranks, mut ties) = (vec![0.0; n], 0.0);
+    let mut i = 0;
+    while i < n {
+        let mut j = i + 1;
+        while j < n && data[order[j]] == data[order[i]] {
+            j += 1;
+        }
+        let rank = (i + j + 1) as f64 / 2.0;
+        for &k in &order[i..j] {
+            ranks[k] = rank;
+        }
+        let t = (j - i) as f64;
+        ties += t * t * t - t;
+        i = j;
+    }
+    (ranks, ties)
+}
+
+// Count the ways the Mann–Whitney U of samples of sizes `n` and `m` takes each
+// of its values by placing the elements of the pooled sample one by one.
+fn rank_sum_counts(n: usize, m: usize) -> Vec<f64> {
+    let mut counts = vec![vec![0.0; n * m + 1]; n + 1];
+    counts[0][0] = 1.0;
+    for k in 1..(n + m + 1) {
+        for j in (1..(n.min(k) + 1)).rev() {
+            let skipped = k - j;
+            if skipped > m {
+                continue;
+            }
+            for u in (0..(n * m + 1 - skipped)).rev() {
+                counts[j][u + skipped] += counts[j - 1][u];
+            }
+        }
+    }
+    counts.pop().unwrap()
+}
+
+// Count the ways the sum of the ranks of the positive differences among `n`
+// takes each of its values.
+fn signed_rank_counts(n: usize) -> Vec<f64> {
+    let total = n * (n + 1) / 2;
+    let mut counts = vec![0.0; total + 1];
+    counts[0] = 1.0;
+    for k in 1..(n + 1) {
+        for v in (k..(total + 1)).rev() {
+            counts[v] += counts[v - k];
+        }
+    }
+    counts
+}
+
+// Compute the p-value of `statistic` given the counts of a distribution that
+// is symmetric about its center.
+fn tail(counts: &[f64], statistic: usize, alternative: Alternative) -> f64 {
+    let total = counts.iter().sum::<f64>();
+    let lower = || counts[..(statistic + 1)].iter().sum::<f64>() / total;
+    let upper = || counts[statistic..].iter().sum::<f64>() / total;
+    match alternative {
+        Alternative::TwoSided => {
+            let p = if 2 * statistic > counts.len() - 1 { upper() } else { lower() };
+            (2.0 * p).min(1.0)
+        },
+        Alternative::Less => lower(),
+        Alternative::Greater => upper(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use tests::Alternative;
+
+    use super::{RankOptions, Reference, ZeroMethod};
+
+    const X: [f64; 9] = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+    #[allow(clippy::approx_constant)]
+    const Y: [f64; 9] = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+
+    #[test]
+    fn mann_whitney_u_approximate() {
+        let a = [1.98, -1.21, 1.61, -0.79, 1.14, -0.28, 0.6, 0.28, 0.04, 0.84, -0.51, 1.36, -0.99,
+                 1.79, -1.36];
+        let b = [-0.83, 1.51, 1.69, -0.55, -2.0, -0.58, 1.67, 1.53, -0.8, -1.98, -0.33, 1.8, 1.35,
+                 -1.04];
+        let options = RankOptions::default();
+        let result = super::mann_whitney_u(&a, &b, Alternative::TwoSided, options);
+        assert_eq!(result.statistic, 114.0);
+        assert!(!result.exact);
+        assert::close(result.p_value, 0.71065956854039217972, 1e-12);
+        assert::close(result.rank_biserial, 0.08571428571428563, 1e-14);
+        assert::close(result.estimate, 0.1, 1e-14);
+
+        let options = RankOptions { exact_limit: 15, ..options };
+        let result = super::mann_whitney_u(&a, &b, Alternative::TwoSided, options);
+        assert!(result.exact);
+        assert::close(result.p_value, 0.71482955632606813208, 1e-12);
+    }
+
+    #[test]
+    fn mann_whitney_u_exact() {
+        let a = [0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46];
+        let b = [1.15, 0.88, 0.90, 0.74, 1.21];
+        let options = RankOptions::default();
+        let result = super::mann_whitney_u(&a, &b, Alternative::Greater, options);
+        assert_eq!(result.statistic, 35.0);
+        assert!(result.exact);
+        assert::close(result.p_value, 0.12720612720612720613, 1e-14);
+        assert::close(result.rank_biserial, 0.4, 1e-14);
+        assert::close(result.estimate, 0.305, 1e-14);
+        let result = super::mann_whitney_u(&a, &b, Alternative::TwoSided, options);
+        assert::close(result.p_value, 0.25441225441225441225, 1e-14);
+        let result = super::mann_whitney_u(&a, &b, Alternative::Less, options);
+        assert::close(result.p_value, 0.8967698967698967699, 1e-14);
+    }
+
+    #[test]
+    fn mann_whitney_u_ties() {
+        let a = [1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0];
+        let b = [2.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0, 6.0];
+        let options = RankOptions::default();
+        let result = super::mann_whitney_u(&a, &b, Alternative::TwoSided, options);
+        assert_eq!(result.statistic, 15.0);
+        assert!(!result.exact);
+        assert::close(result.p_value, 0.043361248850699117642, 1e-12);
+        assert::close(result.estimate, -1.0, 1e-14);
+        let result = super::mann_whitney_u(&a, &b, Alternative::Less, options);
+        assert::close(result.p_value, 0.021680624425349558821, 1e-12);
+        let options = RankOptions { correction: false, ..options };
+        let result = super::mann_whitney_u(&a, &b, Alternative::TwoSided, options);
+        assert::close(result.p_value, 0.038500412111786544972, 1e-12);
+
+        let result = super::mann_whitney_u(&X, &Y, Alternative::Greater, RankOptions::default());
+        assert_eq!(result.statistic, 58.0);
+        assert::close(result.p_value, 0.06645972909265941196, 1e-12);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_approximate() {
+        let data = [0.68, -0.69, -1.52, -1.41, -0.41, 0.99, 2.1, 2.37, 1.67, 0.35, -0.95, -1.59,
+                    -1.24, -0.09, 1.3, 2.25, 2.29, 1.4, 0.01, -1.18];
+        let options = RankOptions::default();
+        let result = super::wilcoxon_signed_rank(&data, Reference::Location(0.0),
+                                                 Alternative::TwoSided, options);
+        assert_eq!(result.statistic, 130.0);
+        assert!(!result.exact);
+        assert::close(result.p_value, 0.36037518754493343779, 1e-12);
+        assert::close(result.rank_biserial, 0.23809523809523808, 1e-14);
+        assert::close(result.estimate, 0.3475, 1e-14);
+
+        let options = RankOptions { exact_limit: 20, ..options };
+        let result = super::wilcoxon_signed_rank(&data, Reference::Location(0.0),
+                                                 Alternative::Less, options);
+        assert!(result.exact);
+        assert::close(result.p_value, 0.82559490203857421875, 1e-14);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_exact() {
+        let options = RankOptions::default();
+        let result = super::wilcoxon_signed_rank(&X, Reference::Paired(&Y), Alternative::Greater,
+                                                 options);
+        assert_eq!(result.statistic, 40.0);
+        assert!(result.exact);
+        assert::close(result.p_value, 0.01953125, 1e-15);
+        assert::close(result.rank_biserial, 0.7777777777777778, 1e-14);
+        assert::close(result.estimate, 0.46, 1e-14);
+        let result = super::wilcoxon_signed_rank(&X, Reference::Paired(&Y),
+                                                 Alternative::TwoSided, options);
+        assert::close(result.p_value, 0.0390625, 1e-15);
+
+        let data = [2.1, 3.3, 1.4, -0.2, 4.5, 2.8, -1.1, 3.9];
+        let result = super::wilcoxon_signed_rank(&data, Reference::Location(1.0),
+                                                 Alternative::TwoSided, options);
+        assert_eq!(result.statistic, 28.0);
+        assert::close(result.p_value, 0.1953125, 1e-15);
+        assert::close(result.estimate, 2.125, 1e-14);
+    }
+
+    #[test]
+    #[should_panic(expected = "the slices should have the same length (got 9 and 2)")]
+    fn wilcoxon_signed_rank_length() {
+        super::wilcoxon_signed_rank(&X, Reference::Paired(&[1.0, 2.0]), Alternative::TwoSided,
+                                    RankOptions::default());
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_zeros() {
+        let data = [1.5, 2.0, 2.0, 3.0, 3.0, 0.0, 0.0, -1.0, -2.0, 4.0, 4.0, 4.0, -3.0, 2.0];
+        let options = RankOptions::default();
+        let result = super::wilcoxon_signed_rank(&data, Reference::Location(0.0),
+                                                 Alternative::TwoSided, options);
+        assert_eq!(result.statistic, 64.5);
+        assert!(!result.exact);
+        assert::close(result.p_value, 0.04828162410402396439, 1e-12);
+        assert::close(result.rank_biserial, 0.6538461538461539, 1e-14);
+        assert::close(result.estimate, 2.0, 1e-14);
+
+        let options = RankOptions { zero_method: ZeroMethod::Pratt, ..options };
+        let result = super::wilcoxon_signed_rank(&data, Reference::Location(0.0),
+                                                 Alternative::TwoSided, options);
+        assert_eq!(result.statistic, 82.5);
+        assert::close(result.p_value, 0.050038750757714402485, 1e-12);
+        assert::close(result.rank_biserial, 0.6176470588235294, 1e-14);
+        assert::close(result.estimate, 1.5, 1e-14);
+        let result = super::wilcoxon_signed_rank(&data, Reference::Location(0.0),
+                                                 Alternative::Greater, options);
+        assert::close(result.p_value, 0.025019375378857201242, 1e-12);
+    }
+}